@@ -6,10 +6,20 @@
 //! - keep enough context to avoid clipping trailing words (transcript quality)
 //!
 //! To do that, capture tracks speech/silence timing and retains a bounded
-//! rolling buffer with configurable lookback before trimming silence.
+//! rolling buffer with configurable lookback before trimming silence. The
+//! same lookback is kept as pre-roll ahead of the speech onset, measured in
+//! samples from the first above-threshold sample rather than from a frame edge.
 
-use super::vad::{FrameLabel, VadConfig, VadEngine, VadSmoother};
+use super::endpoint::AdaptiveSilenceTail;
+use super::meter::rms_db;
+use super::mute::{DigitalSilenceTracker, MUTE_DETECT_MS};
+use super::preprocess::preprocessor_for_config;
+use super::vad::{db_to_amplitude, FrameLabel, VadConfig, VadEngine, VadSmoother};
 use std::collections::VecDeque;
+use std::path::PathBuf;
+
+/// Status shown when a manual capture is dropped by the utterance thresholds.
+pub const UTTERANCE_REJECTED_STATUS: &str = "Ignored: not enough speech";
 
 /// Metrics collected during audio capture for observability and debugging.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -20,6 +30,8 @@ pub struct CaptureMetrics {
     pub transcribe_ms: u64,
     /// Total speech duration in milliseconds.
     pub speech_ms: u64,
+    /// Span from the first speech frame to the end of the last one, in milliseconds.
+    pub voiced_span_ms: u64,
     /// Trailing silence duration in milliseconds.
     pub silence_tail_ms: u64,
     /// Total frames processed during capture.
@@ -28,6 +40,44 @@ pub struct CaptureMetrics {
     pub frames_dropped: usize,
     /// Reason capture ended early (if any).
     pub early_stop_reason: StopReason,
+    /// Longest run of digitally silent input (exact zeros/flat line) in milliseconds.
+    pub digital_silence_ms: u64,
+    /// Audio kept ahead of the detected speech onset, in milliseconds.
+    pub preroll_ms: u64,
+    /// Capture silenced by `--echo-mode mute` while audio was playing, in milliseconds.
+    pub echo_muted_ms: u64,
+    /// Capture was dropped by `--speaker-filter` as someone else's voice.
+    pub speaker_rejected: bool,
+    /// Capture was too short or too sparse to count as an utterance (a cough, a squeak).
+    pub utterance_rejected: bool,
+    /// Where `--dump-audio` wrote this capture, if it did.
+    pub audio_path: Option<PathBuf>,
+}
+
+impl CaptureMetrics {
+    /// Whether the capture looks like a hardware-muted mic rather than a quiet room.
+    pub fn mic_appears_muted(&self) -> bool {
+        self.speech_ms == 0 && self.digital_silence_ms >= MUTE_DETECT_MS
+    }
+
+    /// Share of the voiced span labelled speech (0.0-1.0); `None` before any speech.
+    pub fn voiced_ratio(&self) -> Option<f32> {
+        (self.voiced_span_ms > 0)
+            .then(|| (self.speech_ms as f32 / self.voiced_span_ms as f32).min(1.0))
+    }
+
+    /// Whether the capture has enough speech, densely enough, to be a real utterance.
+    ///
+    /// A threshold of zero disables that check.
+    pub fn is_utterance(&self, min_speech_ms: u64, min_voiced_ratio: f32) -> bool {
+        if self.speech_ms < min_speech_ms {
+            return false;
+        }
+        min_voiced_ratio <= 0.0
+            || self
+                .voiced_ratio()
+                .is_some_and(|ratio| ratio >= min_voiced_ratio)
+    }
 }
 
 impl Default for CaptureMetrics {
@@ -36,10 +86,17 @@ impl Default for CaptureMetrics {
             capture_ms: 0,
             transcribe_ms: 0,
             speech_ms: 0,
+            voiced_span_ms: 0,
             silence_tail_ms: 0,
             frames_processed: 0,
             frames_dropped: 0,
             early_stop_reason: StopReason::MaxDuration,
+            digital_silence_ms: 0,
+            preroll_ms: 0,
+            echo_muted_ms: 0,
+            speaker_rejected: false,
+            utterance_rejected: false,
+            audio_path: None,
         }
     }
 }
@@ -84,6 +141,15 @@ pub struct CaptureResult {
     pub metrics: CaptureMetrics,
 }
 
+/// Processed frame handed to a streaming consumer while capture is still running.
+#[derive(Debug, Clone)]
+pub struct StreamFrame {
+    /// Mono PCM at the capture sample rate, after preprocessing.
+    pub samples: Vec<f32>,
+    /// Whether the smoothed VAD label for this frame was speech.
+    pub speech: bool,
+}
+
 /// Frame plus its VAD classification so we can trim silence by label later.
 pub(super) struct FrameRecord {
     pub(super) samples: Vec<f32>,
@@ -99,6 +165,12 @@ pub(super) struct FrameAccumulator {
     pub(super) total_samples: usize,
     pub(super) max_samples: usize,
     pub(super) lookback_samples: usize,
+    /// Sample amplitude that marks the exact speech onset.
+    pub(super) onset_threshold: f32,
+    /// How far before the first speech frame to look for the onset; covers the
+    /// frames the smoother holds back before flipping a label to speech.
+    pub(super) onset_search_samples: usize,
+    pub(super) sample_rate: u32,
 }
 
 #[cfg_attr(test, allow(dead_code))]
@@ -106,11 +178,15 @@ impl FrameAccumulator {
     pub(super) fn from_config(cfg: &VadConfig) -> Self {
         let max_samples = ((cfg.buffer_ms * u64::from(cfg.sample_rate)) / 1000).max(1) as usize;
         let lookback_samples = ((cfg.lookback_ms * u64::from(cfg.sample_rate)) / 1000) as usize;
+        let frame_samples = ((cfg.frame_ms * u64::from(cfg.sample_rate)) / 1000) as usize;
         Self {
             frames: VecDeque::new(),
             total_samples: 0,
             max_samples,
             lookback_samples,
+            onset_threshold: db_to_amplitude(cfg.silence_threshold_db),
+            onset_search_samples: frame_samples.saturating_mul(cfg.smoothing_frames),
+            sample_rate: cfg.sample_rate,
         }
     }
 
@@ -121,6 +197,9 @@ impl FrameAccumulator {
             total_samples: 0,
             max_samples,
             lookback_samples,
+            onset_threshold: db_to_amplitude(VadConfig::default().silence_threshold_db),
+            onset_search_samples: 0,
+            sample_rate: 1000,
         }
     }
 
@@ -142,6 +221,69 @@ impl FrameAccumulator {
         self.total_samples == 0
     }
 
+    /// Index of the first sample of speech, refined from the first speech frame.
+    ///
+    /// VAD labels whole frames (and smoothing delays the flip), so the onset is
+    /// the first sample at or above the silence threshold between
+    /// `onset_search_samples` before that frame and its end. Falls back to the
+    /// frame start when no sample crosses the threshold.
+    pub(super) fn speech_onset_sample(&self) -> Option<usize> {
+        let mut frame_start = 0usize;
+        let mut frame_len = None;
+        for record in &self.frames {
+            if record.label == FrameLabel::Speech {
+                frame_len = Some(record.samples.len());
+                break;
+            }
+            frame_start += record.samples.len();
+        }
+        let frame_end = frame_start + frame_len?;
+        let search_start = frame_start.saturating_sub(self.onset_search_samples);
+        let onset = self
+            .frames
+            .iter()
+            .flat_map(|record| record.samples.iter())
+            .enumerate()
+            .skip(search_start)
+            .take(frame_end - search_start)
+            .find(|(_, sample)| sample.abs() >= self.onset_threshold)
+            .map_or(frame_start, |(index, _)| index);
+        Some(onset)
+    }
+
+    /// Drop leading audio so exactly `lookback_samples` precede the speech onset.
+    ///
+    /// Trimming follows the same rule as trailing silence (only on a silence
+    /// stop); other stops keep everything. Returns the pre-roll left in front
+    /// of the onset in milliseconds, or `None` when no speech was detected.
+    pub(super) fn apply_preroll(&mut self, stop_reason: &StopReason) -> Option<u64> {
+        let onset = self.speech_onset_sample()?;
+        let mut preroll = onset;
+        if matches!(stop_reason, StopReason::VadSilence { .. }) {
+            let excess = onset.saturating_sub(self.lookback_samples);
+            self.drop_leading_samples(excess);
+            preroll -= excess;
+        }
+        Some((preroll as u64 * 1000) / u64::from(self.sample_rate.max(1)))
+    }
+
+    fn drop_leading_samples(&mut self, mut count: usize) {
+        while count > 0 {
+            let Some(record) = self.frames.front_mut() else {
+                break;
+            };
+            if record.samples.len() <= count {
+                count -= record.samples.len();
+                self.total_samples = self.total_samples.saturating_sub(record.samples.len());
+                self.frames.pop_front();
+            } else {
+                record.samples.drain(..count);
+                self.total_samples = self.total_samples.saturating_sub(count);
+                count = 0;
+            }
+        }
+    }
+
     pub(super) fn into_audio(mut self, stop_reason: &StopReason) -> Vec<f32> {
         // Only trim when stopping on silence. For manual stop/timeout we keep
         // full buffered audio so users do not lose content unexpectedly.
@@ -210,8 +352,13 @@ pub(super) struct CaptureState<'a> {
     cfg: &'a VadConfig,
     frame_ms: u64,
     speech_ms: u64,
+    /// `total_ms` when the first speech frame began.
+    speech_onset_ms: Option<u64>,
+    /// `total_ms` when the latest speech frame ended.
+    speech_end_ms: u64,
     silence_streak_ms: u64,
     total_ms: u64,
+    adaptive_tail: Option<AdaptiveSilenceTail>,
 }
 
 #[cfg_attr(test, allow(dead_code))]
@@ -221,8 +368,17 @@ impl<'a> CaptureState<'a> {
             cfg,
             frame_ms,
             speech_ms: 0,
+            speech_onset_ms: None,
+            speech_end_ms: 0,
             silence_streak_ms: 0,
             total_ms: 0,
+            adaptive_tail: cfg.adaptive_silence_tail.then(|| {
+                AdaptiveSilenceTail::new(
+                    cfg.silence_duration_ms,
+                    cfg.max_recording_duration_ms,
+                    frame_ms,
+                )
+            }),
         }
     }
 
@@ -231,18 +387,36 @@ impl<'a> CaptureState<'a> {
         Self::new(cfg, frame_ms)
     }
 
+    #[cfg(test)]
+    pub(super) fn on_frame(&mut self, label: FrameLabel) -> Option<StopReason> {
+        self.on_frame_samples(label, &[])
+    }
+
     /// Processes a frame and returns a stop reason if capture should end.
     ///
     /// Silence can stop capture only after speech has already been detected and
     /// minimum duration is met; this avoids ending immediately in quiet rooms.
-    pub(super) fn on_frame(&mut self, label: FrameLabel) -> Option<StopReason> {
+    /// `samples` feed the adaptive silence tail's energy trend when enabled.
+    pub(super) fn on_frame_samples(
+        &mut self,
+        label: FrameLabel,
+        samples: &[f32],
+    ) -> Option<StopReason> {
         match label {
             FrameLabel::Speech => {
                 self.speech_ms = self.speech_ms.saturating_add(self.frame_ms);
+                self.speech_onset_ms.get_or_insert(self.total_ms);
+                self.speech_end_ms = self.total_ms.saturating_add(self.frame_ms);
                 self.silence_streak_ms = 0;
+                if let Some(tail) = self.adaptive_tail.as_mut() {
+                    tail.on_speech(rms_db(samples));
+                }
             }
             FrameLabel::Silence => {
                 self.silence_streak_ms = self.silence_streak_ms.saturating_add(self.frame_ms);
+                if let Some(tail) = self.adaptive_tail.as_mut() {
+                    tail.on_silence();
+                }
             }
             FrameLabel::Uncertain => {
                 self.silence_streak_ms = 0;
@@ -257,7 +431,7 @@ impl<'a> CaptureState<'a> {
         // Silence stop is intentionally gated by prior speech + minimum duration.
         if self.speech_ms > 0
             && self.total_ms >= self.cfg.min_recording_duration_ms
-            && self.silence_streak_ms >= self.cfg.silence_duration_ms
+            && self.silence_streak_ms >= self.required_silence_ms()
         {
             return Some(StopReason::VadSilence {
                 tail_ms: self.silence_streak_ms,
@@ -266,6 +440,7 @@ impl<'a> CaptureState<'a> {
         None
     }
 
+    #[cfg(any(test, feature = "audio"))]
     pub(super) fn on_timeout(&mut self) -> Option<StopReason> {
         // Timeout path advances elapsed time even without frames so stalled input
         // devices cannot keep capture alive forever.
@@ -292,9 +467,22 @@ impl<'a> CaptureState<'a> {
         self.speech_ms
     }
 
+    /// First speech frame to the end of the last one; 0 before any speech.
+    pub(super) fn voiced_span_ms(&self) -> u64 {
+        self.speech_onset_ms
+            .map_or(0, |onset| self.speech_end_ms.saturating_sub(onset))
+    }
+
     pub(super) fn silence_tail_ms(&self) -> u64 {
         self.silence_streak_ms
     }
+
+    /// Silence needed to stop: the configured tail, or the adaptive tail for this pause.
+    pub(super) fn required_silence_ms(&self) -> u64 {
+        self.adaptive_tail
+            .as_ref()
+            .map_or(self.cfg.silence_duration_ms, AdaptiveSilenceTail::tail_ms)
+    }
 }
 
 /// Run the silence-aware capture state machine against synthetic PCM samples.
@@ -309,6 +497,8 @@ pub fn offline_capture_from_pcm(
     let mut accumulator = FrameAccumulator::from_config(cfg);
     let mut state = CaptureState::new(cfg, cfg.frame_ms);
     let mut smoother = VadSmoother::new(cfg.smoothing_frames);
+    let mut preprocessor = preprocessor_for_config(cfg);
+    let mut silence_tracker = DigitalSilenceTracker::new(cfg.frame_ms);
     let mut metrics = CaptureMetrics::default();
     let mut stop_reason = StopReason::MaxDuration;
 
@@ -318,11 +508,16 @@ pub fn offline_capture_from_pcm(
         }
         let mut frame = chunk.to_vec();
         frame.resize(frame_samples, 0.0);
+        silence_tracker.observe(&frame);
+        if let Some(stage) = preprocessor.as_mut() {
+            stage.process_frame(&mut frame);
+        }
         let decision = vad.process_frame(&frame);
         metrics.frames_processed += 1;
         let label = smoother.smooth(FrameLabel::from(decision));
+        let reason = state.on_frame_samples(label, &frame);
         accumulator.push_frame(frame, label);
-        if let Some(reason) = state.on_frame(label) {
+        if let Some(reason) = reason {
             stop_reason = reason;
             break;
         }
@@ -336,7 +531,7 @@ pub fn offline_capture_from_pcm(
     }
 
     if matches!(stop_reason, StopReason::MaxDuration)
-        && state.silence_tail_ms() >= cfg.silence_duration_ms
+        && state.silence_tail_ms() >= state.required_silence_ms()
     {
         // If we ran out the loop while already in long silence, classify as
         // silence stop so metrics reflect user behavior rather than loop order.
@@ -345,10 +540,13 @@ pub fn offline_capture_from_pcm(
         };
     }
 
+    metrics.preroll_ms = accumulator.apply_preroll(&stop_reason).unwrap_or(0);
     let audio = accumulator.into_audio(&stop_reason);
     metrics.speech_ms = state.speech_ms();
+    metrics.voiced_span_ms = state.voiced_span_ms();
     metrics.silence_tail_ms = state.silence_tail_ms();
     metrics.capture_ms = state.total_ms();
+    metrics.digital_silence_ms = silence_tracker.longest_run_ms();
     metrics.early_stop_reason = stop_reason;
 
     CaptureResult { audio, metrics }
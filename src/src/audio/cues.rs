@@ -0,0 +1,259 @@
+//! Audible capture cues so start, stop, and errors are noticeable without watching the HUD.
+//!
+//! With `--audio-cues`, short sine tones play on the output device picked by
+//! `--output-device` (the default output otherwise): a high beep when a
+//! capture starts, a lower beep when it stops, and two low tones on errors.
+//! Tones are synthesized at the device's own rate and played on a short-lived
+//! thread so the caller never waits on audio.
+
+use super::echo::PlaybackGuard;
+use crate::log_debug;
+#[cfg(feature = "audio")]
+use anyhow::Context;
+use anyhow::{anyhow, Result};
+#[cfg(feature = "audio")]
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+#[cfg(feature = "audio")]
+use cpal::{SampleFormat, StreamConfig};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+#[cfg(feature = "audio")]
+use std::time::Duration;
+
+/// Peak amplitude at 100% volume; leaves headroom so cues never clip.
+const MAX_AMPLITUDE: f32 = 0.5;
+/// Fade applied to both ends of every tone so it starts and stops without a click.
+const FADE_MS: u64 = 5;
+/// Extra time the stream stays open so the device drains the last buffer.
+#[cfg(feature = "audio")]
+const DRAIN_MS: u64 = 60;
+
+/// Moment in the capture lifecycle worth a sound.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioCue {
+    Start,
+    Stop,
+    Error,
+}
+
+impl AudioCue {
+    /// `(frequency_hz, duration_ms)` steps; a frequency of 0 is a pause.
+    fn steps(self) -> &'static [(f32, u64)] {
+        match self {
+            Self::Start => &[(880.0, 90)],
+            Self::Stop => &[(587.0, 90)],
+            Self::Error => &[(330.0, 120), (0.0, 60), (247.0, 160)],
+        }
+    }
+}
+
+/// Mono samples for `cue` at `sample_rate`, scaled by `volume` (0.0-1.0).
+pub fn render_cue(cue: AudioCue, sample_rate: u32, volume: f32) -> Vec<f32> {
+    let amplitude = MAX_AMPLITUDE * volume.clamp(0.0, 1.0);
+    let rate = sample_rate as f32;
+    let fade = (sample_rate as u64 * FADE_MS / 1000) as usize;
+    let mut samples = Vec::new();
+    for &(frequency, ms) in cue.steps() {
+        let len = (sample_rate as u64 * ms / 1000) as usize;
+        samples.extend((0..len).map(|index| {
+            if frequency == 0.0 {
+                return 0.0;
+            }
+            let edge = index.min(len - 1 - index);
+            let envelope = if edge < fade {
+                edge as f32 / fade as f32
+            } else {
+                1.0
+            };
+            let phase = std::f32::consts::TAU * frequency * index as f32 / rate;
+            amplitude * envelope * phase.sin()
+        }));
+    }
+    samples
+}
+
+/// Plays cues on one output device.
+#[derive(Debug, Clone)]
+pub struct CuePlayer {
+    device_name: Option<String>,
+    volume: f32,
+    playing: Arc<AtomicUsize>,
+}
+
+impl CuePlayer {
+    /// Check the output device exists now so a typo fails at startup, not on the first cue.
+    pub fn new(output_device: Option<&str>, volume_percent: u32) -> Result<Self> {
+        find_output_device(output_device)?;
+        Ok(Self {
+            device_name: output_device.map(str::to_string),
+            volume: volume_percent.min(100) as f32 / 100.0,
+            playing: Arc::new(AtomicUsize::new(0)),
+        })
+    }
+
+    /// Play `cue` in the background; a cue requested while two are still playing is dropped.
+    pub fn play(&self, cue: AudioCue) {
+        if self.volume == 0.0 || self.playing.fetch_add(1, Ordering::SeqCst) >= 2 {
+            self.playing.fetch_sub(1, Ordering::SeqCst);
+            return;
+        }
+        let player = self.clone();
+        thread::spawn(move || {
+            let _playback = PlaybackGuard::start();
+            if let Err(err) = player.play_blocking(cue) {
+                log_debug(&format!("audio cue {cue:?} failed: {err:#}"));
+            }
+            player.playing.fetch_sub(1, Ordering::SeqCst);
+        });
+    }
+
+    #[cfg(feature = "audio")]
+    fn play_blocking(&self, cue: AudioCue) -> Result<()> {
+        let device = find_output_device(self.device_name.as_deref())?;
+        let default_config = device
+            .default_output_config()
+            .context("no usable output config")?;
+        let format = default_config.sample_format();
+        let config: StreamConfig = default_config.into();
+        let channels = usize::from(config.channels.max(1));
+        let samples = render_cue(cue, config.sample_rate.0, self.volume);
+        let duration = Duration::from_secs_f64(samples.len() as f64 / config.sample_rate.0 as f64);
+
+        let err_fn = |err| log_debug(&format!("audio_cue_stream_error: {err}"));
+        let stream = match format {
+            SampleFormat::F32 => {
+                let mut source = FrameSource::new(samples, channels);
+                device.build_output_stream(
+                    &config,
+                    move |data: &mut [f32], _| source.fill(data, |sample| sample),
+                    err_fn,
+                    None,
+                )?
+            }
+            SampleFormat::I16 => {
+                let mut source = FrameSource::new(samples, channels);
+                device.build_output_stream(
+                    &config,
+                    move |data: &mut [i16], _| {
+                        source.fill(data, |sample| (sample * i16::MAX as f32) as i16)
+                    },
+                    err_fn,
+                    None,
+                )?
+            }
+            SampleFormat::U16 => {
+                let mut source = FrameSource::new(samples, channels);
+                device.build_output_stream(
+                    &config,
+                    move |data: &mut [u16], _| {
+                        source.fill(data, |sample| (sample * 32_767.0 + 32_768.0) as u16)
+                    },
+                    err_fn,
+                    None,
+                )?
+            }
+            other => return Err(anyhow!("unsupported output sample format: {other:?}")),
+        };
+        stream.play()?;
+        thread::sleep(duration + Duration::from_millis(DRAIN_MS));
+        Ok(())
+    }
+
+    #[cfg(not(feature = "audio"))]
+    fn play_blocking(&self, _cue: AudioCue) -> Result<()> {
+        find_output_device(self.device_name.as_deref())
+    }
+}
+
+/// Mono cue samples copied to every channel of the output buffer, then silence.
+#[cfg_attr(not(feature = "audio"), allow(dead_code))]
+struct FrameSource {
+    samples: Vec<f32>,
+    position: usize,
+    channels: usize,
+}
+
+#[cfg_attr(not(feature = "audio"), allow(dead_code))]
+impl FrameSource {
+    fn new(samples: Vec<f32>, channels: usize) -> Self {
+        Self {
+            samples,
+            position: 0,
+            channels,
+        }
+    }
+
+    fn fill<T: Copy>(&mut self, data: &mut [T], convert: impl Fn(f32) -> T) {
+        for frame in data.chunks_mut(self.channels) {
+            let sample = self.samples.get(self.position).copied().unwrap_or(0.0);
+            self.position += 1;
+            frame.fill(convert(sample));
+        }
+    }
+}
+
+#[cfg(feature = "audio")]
+fn find_output_device(name: Option<&str>) -> Result<cpal::Device> {
+    let host = cpal::default_host();
+    match name {
+        Some(name) => host
+            .output_devices()
+            .context("no output devices available")?
+            .find(|device| device.name().is_ok_and(|found| found == name))
+            .ok_or_else(|| anyhow!("output device '{name}' not found")),
+        None => host
+            .default_output_device()
+            .ok_or_else(|| anyhow!("no default output device available")),
+    }
+}
+
+#[cfg(not(feature = "audio"))]
+fn find_output_device(_name: Option<&str>) -> Result<()> {
+    Err(anyhow!(
+        "audio output is not built in (enable the 'audio' feature)"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn peak(samples: &[f32]) -> f32 {
+        samples
+            .iter()
+            .fold(0.0, |max, sample| max.max(sample.abs()))
+    }
+
+    #[test]
+    fn cues_have_their_own_length_and_fade_in_from_silence() {
+        let start = render_cue(AudioCue::Start, 48_000, 1.0);
+        assert_eq!(start.len(), 48_000 * 90 / 1000);
+        assert_eq!(start[0], 0.0);
+        assert!(start.last().unwrap().abs() < 0.01);
+        let error = render_cue(AudioCue::Error, 16_000, 1.0);
+        assert_eq!(error.len(), 16_000 * 340 / 1000);
+        assert!(
+            peak(&error[16_000 * 120 / 1000..16_000 * 180 / 1000]) == 0.0,
+            "error tones are separated by a pause"
+        );
+    }
+
+    #[test]
+    fn volume_scales_peak_amplitude() {
+        let full = peak(&render_cue(AudioCue::Stop, 48_000, 1.0));
+        let half = peak(&render_cue(AudioCue::Stop, 48_000, 0.5));
+        assert!((full - MAX_AMPLITUDE).abs() < 0.01, "{full}");
+        assert!((half - MAX_AMPLITUDE / 2.0).abs() < 0.01, "{half}");
+        assert_eq!(peak(&render_cue(AudioCue::Stop, 48_000, 0.0)), 0.0);
+        assert_eq!(peak(&render_cue(AudioCue::Stop, 48_000, 3.0)), full);
+    }
+
+    #[test]
+    fn frame_source_fills_every_channel_then_silence() {
+        let mut source = FrameSource::new(vec![0.25, -0.5], 2);
+        let mut data = [1.0_f32; 6];
+        source.fill(&mut data, |sample| sample);
+        assert_eq!(data, [0.25, 0.25, -0.5, -0.5, 0.0, 0.0]);
+    }
+}
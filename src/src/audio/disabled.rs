@@ -0,0 +1,83 @@
+//! Recorder stand-in for builds without the `audio` feature.
+//!
+//! Lean overlay builds drop cpal and its native audio libraries. Opening a
+//! recorder fails with a device error, so callers take the same paths they use
+//! when no microphone is present.
+
+use super::capture::{CaptureResult, StreamFrame};
+use super::meter::LiveMeter;
+use super::vad::{VadConfig, VadEngine};
+use crate::voice_error::VoiceErrorKind;
+use anyhow::{anyhow, Context, Result};
+use crossbeam_channel::Sender;
+use std::convert::Infallible;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::Duration;
+
+const UNAVAILABLE: &str = "microphone capture is not built in (enable the 'audio' feature)";
+
+/// Audio input device wrapper; cannot be constructed without the `audio` feature.
+pub struct Recorder {
+    never: Infallible,
+}
+
+impl Recorder {
+    pub fn list_devices() -> Result<Vec<String>> {
+        Err(anyhow!(UNAVAILABLE)).context(VoiceErrorKind::NoDevice)
+    }
+
+    pub fn default_device_name() -> Option<String> {
+        None
+    }
+
+    pub fn new(_preferred_device: Option<&str>) -> Result<Self> {
+        Err(anyhow!(UNAVAILABLE)).context(VoiceErrorKind::NoDevice)
+    }
+
+    pub fn device_name(&self) -> String {
+        match self.never {}
+    }
+
+    pub fn keep_open(&mut self, _lookback_ms: u64) -> Result<()> {
+        match self.never {}
+    }
+
+    pub fn stream_failed(&self) -> bool {
+        match self.never {}
+    }
+
+    pub fn record_for(&self, _duration: Duration) -> Result<Vec<f32>> {
+        match self.never {}
+    }
+
+    pub fn record(&self, _seconds: u64) -> Result<Vec<f32>> {
+        match self.never {}
+    }
+
+    pub fn record_with_vad(
+        &self,
+        _cfg: &VadConfig,
+        _vad: &mut dyn VadEngine,
+        _stop_flag: Option<Arc<AtomicBool>>,
+        _meter: Option<LiveMeter>,
+    ) -> Result<CaptureResult> {
+        match self.never {}
+    }
+
+    pub fn record_with_vad_streaming(
+        &self,
+        _cfg: &VadConfig,
+        _vad: &mut dyn VadEngine,
+        _stop_flag: Option<Arc<AtomicBool>>,
+        _meter: Option<LiveMeter>,
+        _tap: Sender<StreamFrame>,
+    ) -> Result<CaptureResult> {
+        match self.never {}
+    }
+
+    #[cfg(test)]
+    pub(super) fn new_for_tests() -> Option<Self> {
+        None
+    }
+}
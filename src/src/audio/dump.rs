@@ -0,0 +1,343 @@
+//! Capture dumps for `--dump-audio`, kept under a size budget.
+//!
+//! Each capture that reaches STT is written as 16-bit mono WAV or FLAC into the
+//! dump directory. Leading and trailing silence (frames below the VAD threshold)
+//! is trimmed to a short pad unless `--dump-audio-keep-silence` is set, and
+//! `--dump-audio-normalize` scales the clip to a fixed peak, so dumps are ready
+//! to use as an evaluation corpus. After every write the directory is pruned
+//! oldest-first until the dumps fit the `--dump-audio-max-mb` budget; the newest
+//! capture is always kept, and files the overlay did not write are never touched.
+//! [`decode_wav`] reads WAV files back for `voiceterm transcribe`.
+
+use super::flac::encode_flac;
+use super::meter::rms_db;
+use crate::config::AudioDumpFormat;
+use anyhow::{bail, Context, Result};
+use clap::ValueEnum;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const DUMP_PREFIX: &str = "capture-";
+/// Frame length used to find where speech starts and ends.
+const TRIM_FRAME_MS: usize = 10;
+/// Audio kept around the speech so word onsets and tails are not clipped.
+const TRIM_PAD_MS: usize = 150;
+/// Peak level of normalized dumps (about -1 dBFS).
+const NORMALIZE_PEAK: f32 = 0.89;
+/// Quieter clips are left alone rather than amplifying the noise floor.
+const NORMALIZE_MIN_PEAK: f32 = 1e-3;
+
+/// Where and how captures are dumped.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioDump {
+    /// Directory receiving the dumps; created on first write.
+    pub dir: PathBuf,
+    /// Container/codec for new dumps.
+    pub format: AudioDumpFormat,
+    /// Total size the dumps may occupy before the oldest are deleted.
+    pub max_bytes: u64,
+    /// Trim leading/trailing frames quieter than this level (dBFS); `None` keeps them.
+    pub trim_below_db: Option<f32>,
+    /// Scale each dump so its loudest sample sits at a fixed peak.
+    pub normalize: bool,
+}
+
+impl AudioDump {
+    /// Write one capture and prune older dumps; returns the new file's path.
+    pub fn write(&self, samples: &[f32], sample_rate: u32) -> Result<PathBuf> {
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("failed to create {}", self.dir.display()))?;
+        let samples = match self.trim_below_db {
+            Some(threshold_db) => trim_silence(samples, sample_rate, threshold_db),
+            None => samples,
+        };
+        let pcm = if self.normalize {
+            to_pcm16(&peak_normalize(samples))
+        } else {
+            to_pcm16(samples)
+        };
+        let bytes = match self.format {
+            AudioDumpFormat::Wav => encode_wav(&pcm, sample_rate),
+            AudioDumpFormat::Flac => encode_flac(&pcm, sample_rate),
+        };
+        let stamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis())
+            .unwrap_or_default();
+        let path = self
+            .dir
+            .join(format!("{DUMP_PREFIX}{stamp}.{}", self.format.extension()));
+        fs::write(&path, bytes).with_context(|| format!("failed to write {}", path.display()))?;
+        prune_dumps(&self.dir, self.max_bytes)?;
+        Ok(path)
+    }
+}
+
+/// Encode samples as a 16-bit mono WAV file image (also used for cloud STT uploads).
+pub fn wav_bytes(samples: &[f32], sample_rate: u32) -> Vec<u8> {
+    encode_wav(&to_pcm16(samples), sample_rate)
+}
+
+/// Decode a WAV file image to mono samples and its sample rate.
+///
+/// Reads 8/16/24/32-bit integer PCM and 32-bit float data, plain or
+/// `WAVE_FORMAT_EXTENSIBLE`; multi-channel audio is averaged to mono.
+pub fn decode_wav(bytes: &[u8]) -> Result<(Vec<f32>, u32)> {
+    if bytes.len() < 12 || &bytes[..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        bail!("not a RIFF/WAVE file");
+    }
+    let mut format = None;
+    let mut data = None;
+    let mut rest = &bytes[12..];
+    while rest.len() >= 8 {
+        let len = u32::from_le_bytes([rest[4], rest[5], rest[6], rest[7]]) as usize;
+        let body = &rest[8..];
+        let chunk = &body[..len.min(body.len())];
+        match &rest[..4] {
+            b"fmt " => format = Some(chunk),
+            b"data" => data = Some(chunk),
+            _ => {}
+        }
+        // Chunks are padded to an even length.
+        rest = &body[len.saturating_add(len % 2).min(body.len())..];
+    }
+    let format = format
+        .filter(|format| format.len() >= 16)
+        .context("WAV file has no fmt chunk")?;
+    let data = data.context("WAV file has no data chunk")?;
+    let mut tag = u16::from_le_bytes([format[0], format[1]]);
+    let channels = usize::from(u16::from_le_bytes([format[2], format[3]]));
+    let sample_rate = u32::from_le_bytes([format[4], format[5], format[6], format[7]]);
+    let bits = u16::from_le_bytes([format[14], format[15]]);
+    if tag == 0xfffe && format.len() >= 26 {
+        // WAVE_FORMAT_EXTENSIBLE keeps the real format in the sub-format GUID.
+        tag = u16::from_le_bytes([format[24], format[25]]);
+    }
+    if channels == 0 || sample_rate == 0 {
+        bail!("WAV header has {channels} channel(s) at {sample_rate} Hz");
+    }
+    let convert: fn(&[u8]) -> f32 = match (tag, bits) {
+        (1, 8) => |b| (f32::from(b[0]) - 128.0) / 128.0,
+        (1, 16) => |b| f32::from(i16::from_le_bytes([b[0], b[1]])) / 32_768.0,
+        (1, 24) => |b| (i32::from_le_bytes([0, b[0], b[1], b[2]]) >> 8) as f32 / 8_388_608.0,
+        (1, 32) => |b| i32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f32 / 2_147_483_648.0,
+        (3, 32) => |b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]),
+        _ => bail!("unsupported WAV encoding (format {tag}, {bits}-bit)"),
+    };
+    let sample_bytes = usize::from(bits / 8);
+    let samples = data
+        .chunks_exact(sample_bytes * channels)
+        .map(|frame| frame.chunks_exact(sample_bytes).map(convert).sum::<f32>() / channels as f32)
+        .collect();
+    Ok((samples, sample_rate))
+}
+
+/// Cut leading and trailing frames below `threshold_db`, keeping a short pad;
+/// a clip with no frame above the threshold is returned whole.
+fn trim_silence(samples: &[f32], sample_rate: u32, threshold_db: f32) -> &[f32] {
+    let frame = (sample_rate as usize * TRIM_FRAME_MS / 1000).max(1);
+    let loud = |chunk: &[f32]| rms_db(chunk) >= threshold_db;
+    let Some(first) = samples.chunks(frame).position(loud) else {
+        return samples;
+    };
+    let last = samples.chunks(frame).rposition(loud).unwrap_or(first);
+    let pad = sample_rate as usize * TRIM_PAD_MS / 1000;
+    let start = (first * frame).saturating_sub(pad);
+    let end = ((last + 1) * frame + pad).min(samples.len());
+    &samples[start..end]
+}
+
+fn peak_normalize(samples: &[f32]) -> Vec<f32> {
+    let peak = samples
+        .iter()
+        .fold(0.0_f32, |peak, sample| peak.max(sample.abs()));
+    if peak < NORMALIZE_MIN_PEAK {
+        return samples.to_vec();
+    }
+    let gain = NORMALIZE_PEAK / peak;
+    samples.iter().map(|sample| sample * gain).collect()
+}
+
+fn to_pcm16(samples: &[f32]) -> Vec<i16> {
+    samples
+        .iter()
+        .map(|&sample| (sample.clamp(-1.0, 1.0) * f32::from(i16::MAX)).round() as i16)
+        .collect()
+}
+
+fn encode_wav(samples: &[i16], sample_rate: u32) -> Vec<u8> {
+    let data_len = (samples.len() * 2) as u32;
+    let mut out = Vec::with_capacity(44 + data_len as usize);
+    out.extend_from_slice(b"RIFF");
+    out.extend_from_slice(&(36 + data_len).to_le_bytes());
+    out.extend_from_slice(b"WAVEfmt ");
+    out.extend_from_slice(&16u32.to_le_bytes());
+    out.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    out.extend_from_slice(&1u16.to_le_bytes()); // mono
+    out.extend_from_slice(&sample_rate.to_le_bytes());
+    out.extend_from_slice(&(sample_rate * 2).to_le_bytes());
+    out.extend_from_slice(&2u16.to_le_bytes());
+    out.extend_from_slice(&16u16.to_le_bytes());
+    out.extend_from_slice(b"data");
+    out.extend_from_slice(&data_len.to_le_bytes());
+    for sample in samples {
+        out.extend_from_slice(&sample.to_le_bytes());
+    }
+    out
+}
+
+/// Delete the oldest dumps until the rest fit in `max_bytes`, keeping the newest.
+fn prune_dumps(dir: &Path, max_bytes: u64) -> Result<()> {
+    let mut dumps = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("failed to list {}", dir.display()))? {
+        let entry = entry?;
+        let path = entry.path();
+        let Some(stamp) = dump_stamp(&path) else {
+            continue;
+        };
+        dumps.push((stamp, entry.metadata()?.len(), path));
+    }
+    dumps.sort();
+    let mut total: u64 = dumps.iter().map(|(_, len, _)| len).sum();
+    let keep_newest = dumps.len().saturating_sub(1);
+    for (_, len, path) in dumps.into_iter().take(keep_newest) {
+        if total <= max_bytes {
+            break;
+        }
+        fs::remove_file(&path).with_context(|| format!("failed to prune {}", path.display()))?;
+        total -= len;
+    }
+    Ok(())
+}
+
+/// Millisecond timestamp of a dump file name, or `None` for anything else.
+fn dump_stamp(path: &Path) -> Option<u128> {
+    let extension = path.extension()?.to_str()?;
+    if !AudioDumpFormat::value_variants()
+        .iter()
+        .any(|format| format.extension() == extension)
+    {
+        return None;
+    }
+    path.file_stem()?
+        .to_str()?
+        .strip_prefix(DUMP_PREFIX)?
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("voiceterm-dump-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn wav_header_describes_16_bit_mono() {
+        let wav = encode_wav(&[0, 1, -1], 16_000);
+        assert_eq!(wav.len(), 44 + 6);
+        assert_eq!(&wav[..4], b"RIFF");
+        assert_eq!(u32::from_le_bytes(wav[24..28].try_into().unwrap()), 16_000);
+        assert_eq!(u32::from_le_bytes(wav[40..44].try_into().unwrap()), 6);
+        assert_eq!(&wav[44..], [0, 0, 1, 0, 0xff, 0xff]);
+    }
+
+    #[test]
+    fn decode_wav_reads_back_written_files() {
+        let (samples, rate) = decode_wav(&wav_bytes(&[0.0, 0.5, -0.5], 22_050)).unwrap();
+        assert_eq!(rate, 22_050);
+        assert_eq!(samples.len(), 3);
+        assert!((samples[1] - 0.5).abs() < 1e-3 && (samples[2] + 0.5).abs() < 1e-3);
+
+        // Stereo 32-bit float, averaged to mono.
+        let mut wav = encode_wav(&[], 8_000);
+        wav[20..22].copy_from_slice(&3u16.to_le_bytes());
+        wav[22..24].copy_from_slice(&2u16.to_le_bytes());
+        wav[34..36].copy_from_slice(&32u16.to_le_bytes());
+        wav[40..44].copy_from_slice(&8u32.to_le_bytes());
+        wav.extend_from_slice(&0.25f32.to_le_bytes());
+        wav.extend_from_slice(&0.75f32.to_le_bytes());
+        assert_eq!(decode_wav(&wav).unwrap(), (vec![0.5], 8_000));
+
+        assert!(decode_wav(b"ID3\x03 not a wav file").is_err());
+    }
+
+    #[test]
+    fn samples_are_clamped_to_pcm16() {
+        assert_eq!(to_pcm16(&[0.0, 1.0, -1.0, 2.0]), [0, 32767, -32767, 32767]);
+    }
+
+    #[test]
+    fn trim_keeps_speech_with_a_short_pad() {
+        let rate = 16_000;
+        let mut samples = vec![0.0005; 16_000];
+        samples.extend(vec![0.3; 8_000]);
+        samples.extend(vec![0.0; 16_000]);
+        let trimmed = trim_silence(&samples, rate, -40.0);
+        let pad = rate as usize * TRIM_PAD_MS / 1000;
+        assert_eq!(trimmed.len(), 8_000 + 2 * pad);
+        assert_eq!(trimmed[pad], 0.3);
+        assert_eq!(trim_silence(&samples[..16_000], rate, -40.0).len(), 16_000);
+        assert_eq!(
+            trim_silence(&samples[16_000..24_000], rate, -40.0).len(),
+            8_000
+        );
+    }
+
+    #[test]
+    fn normalize_scales_to_the_target_peak() {
+        let normalized = peak_normalize(&[0.1, -0.2, 0.05]);
+        assert!((normalized[1] + NORMALIZE_PEAK).abs() < 1e-6);
+        assert!((normalized[0] - NORMALIZE_PEAK / 2.0).abs() < 1e-6);
+        assert_eq!(peak_normalize(&[0.0, 0.0001]), [0.0, 0.0001]);
+    }
+
+    #[test]
+    fn prune_removes_oldest_dumps_and_keeps_other_files() {
+        let dir = temp_dir("prune");
+        fs::create_dir_all(&dir).unwrap();
+        for (name, len) in [
+            ("capture-100.wav", 40),
+            ("capture-200.flac", 40),
+            ("capture-300.wav", 40),
+            ("notes.wav", 500),
+        ] {
+            fs::write(dir.join(name), vec![0u8; len]).unwrap();
+        }
+        prune_dumps(&dir, 90).unwrap();
+        let mut left: Vec<_> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        left.sort();
+        assert_eq!(left, ["capture-200.flac", "capture-300.wav", "notes.wav"]);
+
+        prune_dumps(&dir, 0).unwrap();
+        assert!(dir.join("capture-300.wav").exists(), "newest dump is kept");
+        assert!(!dir.join("capture-200.flac").exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn write_creates_the_directory_and_uses_the_format_extension() {
+        let dir = temp_dir("write");
+        let dump = AudioDump {
+            dir: dir.clone(),
+            format: AudioDumpFormat::Flac,
+            max_bytes: u64::MAX,
+            trim_below_db: None,
+            normalize: false,
+        };
+        let path = dump.write(&[0.0; 1600], 16_000).unwrap();
+        assert_eq!(path.extension().unwrap(), "flac");
+        assert!(dump_stamp(&path).is_some());
+        assert_eq!(&fs::read(&path).unwrap()[..4], b"fLaC");
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,187 @@
+//! Echo handling so captures do not pick up voiceterm's own speech or the speakers.
+//!
+//! `--echo-mode mute` silences capture frames while voiceterm itself is
+//! playing audio (cues, spoken replies) plus a short hangover for room
+//! reverb, and, when `--echo-reference-device` names a loopback input, while
+//! that reference is audible. `--echo-mode cancel` instead runs an NLMS
+//! adaptive filter that learns the speaker-to-mic path from the reference and
+//! subtracts the predicted echo, so the user can still talk over playback.
+
+use super::meter::rms_db;
+use crate::config::EchoMode;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Capture stays muted this long after playback stops (output latency and room reverb).
+pub(crate) const PLAYBACK_HANGOVER: Duration = Duration::from_millis(250);
+/// A reference frame louder than this means the speakers are playing (dBFS).
+const REFERENCE_AUDIBLE_DB: f32 = -50.0;
+/// Echo path length the canceller models (ms): output latency plus a small room.
+const ECHO_TAIL_MS: u64 = 128;
+/// NLMS step size; smaller converges slower but drifts less while the user talks.
+const NLMS_STEP: f32 = 0.3;
+/// Keeps the NLMS update finite while the reference is silent.
+const NLMS_EPSILON: f32 = 1.0e-6;
+/// Geigel double-talk threshold: a mic peak above this share of the recent
+/// reference peak means the user is talking, so the filter stops adapting.
+const DOUBLE_TALK_RATIO: f32 = 0.5;
+
+static ACTIVE_PLAYBACK: AtomicUsize = AtomicUsize::new(0);
+static LAST_PLAYBACK_END: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Marks voiceterm's own audio output as playing until dropped.
+#[must_use = "playback counts as finished as soon as the guard is dropped"]
+pub struct PlaybackGuard(());
+
+impl PlaybackGuard {
+    pub fn start() -> Self {
+        ACTIVE_PLAYBACK.fetch_add(1, Ordering::SeqCst);
+        Self(())
+    }
+}
+
+impl Drop for PlaybackGuard {
+    fn drop(&mut self) {
+        if let Ok(mut last) = LAST_PLAYBACK_END.lock() {
+            *last = Some(Instant::now());
+        }
+        ACTIVE_PLAYBACK.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Whether voiceterm is playing audio, or stopped less than `hangover` ago.
+pub fn playback_active(hangover: Duration) -> bool {
+    ACTIVE_PLAYBACK.load(Ordering::SeqCst) > 0
+        || LAST_PLAYBACK_END
+            .lock()
+            .ok()
+            .and_then(|last| *last)
+            .is_some_and(|end| end.elapsed() < hangover)
+}
+
+/// Normalized-LMS echo canceller with a Geigel double-talk detector.
+///
+/// The reference must lead the mic (a loopback taps the output before the
+/// speaker), so the echo falls inside the filter's window of past reference.
+#[derive(Debug, Clone)]
+pub struct EchoCanceller {
+    weights: Vec<f32>,
+    /// The last `taps - 1` reference samples, oldest first, then the current frame.
+    history: Vec<f32>,
+}
+
+impl EchoCanceller {
+    pub fn new(sample_rate: u32) -> Self {
+        let taps = ((u64::from(sample_rate) * ECHO_TAIL_MS) / 1000).max(1) as usize;
+        Self {
+            weights: vec![0.0; taps],
+            history: vec![0.0; taps - 1],
+        }
+    }
+
+    /// Subtract the predicted echo of `reference` from `mic` in place.
+    ///
+    /// `reference` is the loopback audio for the same stretch of time; a short
+    /// reference is padded with silence.
+    pub fn process(&mut self, mic: &mut [f32], reference: &[f32]) {
+        let taps = self.weights.len();
+        self.history.extend(
+            reference
+                .iter()
+                .copied()
+                .chain(std::iter::repeat(0.0))
+                .take(mic.len()),
+        );
+        let reference_peak = self
+            .history
+            .iter()
+            .fold(0.0f32, |peak, s| peak.max(s.abs()));
+        let mic_peak = mic.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+        let adapt = reference_peak > 0.0 && mic_peak <= DOUBLE_TALK_RATIO * reference_peak;
+
+        let mut power: f32 = self.history[..taps].iter().map(|s| s * s).sum();
+        for (index, sample) in mic.iter_mut().enumerate() {
+            let window = &self.history[index..index + taps];
+            if index > 0 {
+                let (dropped, added) = (self.history[index - 1], window[taps - 1]);
+                power = (power - dropped * dropped + added * added).max(0.0);
+            }
+            let echo: f32 = self.weights.iter().zip(window).map(|(w, x)| w * x).sum();
+            let error = *sample - echo;
+            if adapt {
+                let step = NLMS_STEP * error / (power + NLMS_EPSILON);
+                for (weight, x) in self.weights.iter_mut().zip(window) {
+                    *weight += step * x;
+                }
+            }
+            *sample = error;
+        }
+        self.history.drain(..mic.len());
+    }
+
+    pub fn reset(&mut self) {
+        self.weights.fill(0.0);
+        self.history.fill(0.0);
+    }
+}
+
+/// Per-capture echo handling selected by `--echo-mode`.
+pub(crate) struct EchoStage {
+    mode: EchoMode,
+    canceller: EchoCanceller,
+    frame_ms: u64,
+    hangover_frames: u64,
+    /// Frames left before capture unmutes after the reference went quiet.
+    hangover_left: u64,
+    muted_ms: u64,
+}
+
+impl EchoStage {
+    pub(crate) fn new(mode: EchoMode, sample_rate: u32, frame_ms: u64) -> Self {
+        let frame_ms = frame_ms.max(1);
+        Self {
+            mode,
+            canceller: EchoCanceller::new(sample_rate),
+            frame_ms,
+            hangover_frames: (PLAYBACK_HANGOVER.as_millis() as u64).div_ceil(frame_ms),
+            hangover_left: 0,
+            muted_ms: 0,
+        }
+    }
+
+    /// Handle one capture frame in place.
+    ///
+    /// `reference` is the matching loopback audio when a reference device is
+    /// open; `own_playback` is whether voiceterm is playing audio itself.
+    /// Cancelling without a reference falls back to muting.
+    pub(crate) fn process_frame(
+        &mut self,
+        mic: &mut [f32],
+        reference: Option<&[f32]>,
+        own_playback: bool,
+    ) {
+        match (self.mode, reference) {
+            (EchoMode::Off, _) => return,
+            (EchoMode::Cancel, Some(reference)) => {
+                self.canceller.process(mic, reference);
+                return;
+            }
+            _ => {}
+        }
+        if reference.is_some_and(|reference| rms_db(reference) > REFERENCE_AUDIBLE_DB) {
+            self.hangover_left = self.hangover_frames;
+        } else if !own_playback && self.hangover_left > 0 {
+            self.hangover_left -= 1;
+        } else if !own_playback {
+            return;
+        }
+        mic.fill(0.0);
+        self.muted_ms += self.frame_ms;
+    }
+
+    /// Capture time silenced so far because something was playing (ms).
+    pub(crate) fn muted_ms(&self) -> u64 {
+        self.muted_ms
+    }
+}
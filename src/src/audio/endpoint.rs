@@ -0,0 +1,171 @@
+//! Adaptive end-of-speech detection for `--voice-silence-tail-adaptive`.
+//!
+//! A fixed silence tail either clips a speaker who pauses mid-sentence or waits
+//! too long after a finished one. The adaptive tail looks at how energy moved
+//! over the last stretch of speech before the pause: rising energy usually
+//! means a breath mid-phrase, so the tail is lengthened; a clear decay
+//! usually means a sentence-final drop, so the tail is shortened.
+
+use std::collections::VecDeque;
+
+/// Speech history used to judge the energy trend before a pause.
+const TREND_WINDOW_MS: u64 = 300;
+/// Energy change (second half minus first half of the window) that counts as rising.
+const RISING_DB: f32 = 2.0;
+/// Energy change that counts as a sentence-final decay.
+const FALLING_DB: f32 = -6.0;
+/// Tail multiplier applied when speech was still rising into the pause.
+const MID_PHRASE_SCALE: f32 = 1.6;
+/// Tail multiplier applied after a sentence-final decay.
+const SENTENCE_END_SCALE: f32 = 0.6;
+/// Shortest tail the adaptive mode will use, matching the fixed-tail lower bound.
+const MIN_ADAPTIVE_TAIL_MS: u64 = 200;
+
+/// How speech ended going into the current pause.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum PauseKind {
+    /// Energy was rising; the speaker is likely mid-phrase.
+    MidPhrase,
+    /// Energy decayed clearly; the speaker likely finished a sentence.
+    SentenceEnd,
+    /// No clear trend (or too little speech to judge).
+    Neutral,
+}
+
+/// Tracks recent speech energy and picks the silence tail for the next pause.
+pub(super) struct AdaptiveSilenceTail {
+    base_tail_ms: u64,
+    max_tail_ms: u64,
+    window_frames: usize,
+    speech_levels: VecDeque<f32>,
+    pause: Option<PauseKind>,
+}
+
+impl AdaptiveSilenceTail {
+    pub(super) fn new(base_tail_ms: u64, max_tail_ms: u64, frame_ms: u64) -> Self {
+        let window_frames = (TREND_WINDOW_MS / frame_ms.max(1)).max(2) as usize;
+        Self {
+            base_tail_ms,
+            max_tail_ms: max_tail_ms.max(base_tail_ms),
+            window_frames,
+            speech_levels: VecDeque::with_capacity(window_frames),
+            pause: None,
+        }
+    }
+
+    /// Record a speech frame's level; a new speech run ends the current pause.
+    pub(super) fn on_speech(&mut self, level_db: f32) {
+        self.pause = None;
+        if self.speech_levels.len() == self.window_frames {
+            self.speech_levels.pop_front();
+        }
+        self.speech_levels.push_back(level_db);
+    }
+
+    /// Record a silence frame; the first one of a pause classifies it.
+    pub(super) fn on_silence(&mut self) {
+        if self.pause.is_none() {
+            self.pause = Some(self.classify());
+        }
+    }
+
+    /// Silence required before stopping, given the current pause.
+    pub(super) fn tail_ms(&self) -> u64 {
+        let scale = match self.pause.unwrap_or(PauseKind::Neutral) {
+            PauseKind::MidPhrase => MID_PHRASE_SCALE,
+            PauseKind::SentenceEnd => SENTENCE_END_SCALE,
+            PauseKind::Neutral => 1.0,
+        };
+        let tail = (self.base_tail_ms as f32 * scale).round() as u64;
+        tail.clamp(
+            MIN_ADAPTIVE_TAIL_MS.min(self.base_tail_ms),
+            self.max_tail_ms,
+        )
+    }
+
+    fn classify(&self) -> PauseKind {
+        // Short utterances carry too little history to tell a trend from noise.
+        let len = self.speech_levels.len();
+        if len < self.window_frames {
+            return PauseKind::Neutral;
+        }
+        let half = len / 2;
+        let early = self.speech_levels.iter().take(half).sum::<f32>() / half as f32;
+        let late = self.speech_levels.iter().skip(half).sum::<f32>() / (len - half) as f32;
+        let change = late - early;
+        if change >= RISING_DB {
+            PauseKind::MidPhrase
+        } else if change <= FALLING_DB {
+            PauseKind::SentenceEnd
+        } else {
+            PauseKind::Neutral
+        }
+    }
+
+    #[cfg(test)]
+    pub(super) fn pause(&self) -> Option<PauseKind> {
+        self.pause
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tail_after(levels: &[f32]) -> AdaptiveSilenceTail {
+        let mut tail = AdaptiveSilenceTail::new(1000, 3000, 20);
+        for &level in levels {
+            tail.on_speech(level);
+        }
+        tail.on_silence();
+        tail
+    }
+
+    #[test]
+    fn rising_energy_lengthens_the_tail() {
+        let levels: Vec<f32> = (0..15).map(|i| -40.0 + i as f32).collect();
+        let tail = tail_after(&levels);
+        assert_eq!(tail.pause(), Some(PauseKind::MidPhrase));
+        assert_eq!(tail.tail_ms(), 1600);
+    }
+
+    #[test]
+    fn decaying_energy_shortens_the_tail() {
+        let levels: Vec<f32> = (0..15).map(|i| -20.0 - 2.0 * i as f32).collect();
+        let tail = tail_after(&levels);
+        assert_eq!(tail.pause(), Some(PauseKind::SentenceEnd));
+        assert_eq!(tail.tail_ms(), 600);
+    }
+
+    #[test]
+    fn short_or_flat_speech_keeps_the_base_tail() {
+        assert_eq!(tail_after(&[-30.0, -20.0]).tail_ms(), 1000);
+        assert_eq!(tail_after(&[-30.0; 15]).tail_ms(), 1000);
+    }
+
+    #[test]
+    fn pause_is_judged_once_and_cleared_by_speech() {
+        let mut tail = tail_after(&(0..15).map(|i| -40.0 + i as f32).collect::<Vec<_>>());
+        tail.on_silence();
+        assert_eq!(tail.pause(), Some(PauseKind::MidPhrase));
+        tail.on_speech(-30.0);
+        assert_eq!(tail.pause(), None);
+    }
+
+    #[test]
+    fn tail_is_clamped_to_bounds() {
+        let mut tail = AdaptiveSilenceTail::new(250, 300, 20);
+        for i in 0..15 {
+            tail.on_speech(-40.0 + i as f32);
+        }
+        tail.on_silence();
+        assert_eq!(tail.tail_ms(), 300);
+
+        let mut tail = AdaptiveSilenceTail::new(250, 3000, 20);
+        for i in 0..15 {
+            tail.on_speech(-20.0 - 2.0 * i as f32);
+        }
+        tail.on_silence();
+        assert_eq!(tail.tail_ms(), 200);
+    }
+}
@@ -0,0 +1,322 @@
+//! Minimal FLAC encoder for `--dump-audio-format flac`.
+//!
+//! Captures are 16 kHz mono speech, so a small fixed-predictor encoder gets
+//! most of FLAC's size win without a native codec dependency: each block picks
+//! the fixed predictor order (0-4) with the cheapest Rice-coded residual. The
+//! STREAMINFO MD5 is left zero, which the format defines as "not computed".
+
+/// Samples per frame; FLAC's usual block size for 16-bit audio.
+const BLOCK_SIZE: usize = 4096;
+/// Largest Rice parameter written without the escape code.
+const MAX_RICE_PARAM: u32 = 14;
+const MAX_FIXED_ORDER: usize = 4;
+
+/// Encode 16-bit mono PCM as a complete FLAC stream.
+pub(super) fn encode_flac(samples: &[i16], sample_rate: u32) -> Vec<u8> {
+    let mut out = Vec::with_capacity(samples.len() + 64);
+    out.extend_from_slice(b"fLaC");
+    write_stream_info(&mut out, samples.len() as u64, sample_rate);
+    for (index, block) in samples.chunks(BLOCK_SIZE).enumerate() {
+        write_frame(&mut out, index as u32, block);
+    }
+    out
+}
+
+fn write_stream_info(out: &mut Vec<u8>, total_samples: u64, sample_rate: u32) {
+    let mut bits = BitWriter::default();
+    bits.put(1, 1); // last metadata block
+    bits.put(0, 7); // STREAMINFO
+    bits.put(34, 24);
+    bits.put(BLOCK_SIZE as u64, 16);
+    bits.put(BLOCK_SIZE as u64, 16);
+    bits.put(0, 24); // min frame size unknown
+    bits.put(0, 24); // max frame size unknown
+    bits.put(u64::from(sample_rate), 20);
+    bits.put(0, 3); // channels - 1
+    bits.put(15, 5); // bits per sample - 1
+    bits.put(total_samples, 36);
+    for _ in 0..16 {
+        bits.put(0, 8); // MD5 not computed
+    }
+    out.extend_from_slice(&bits.finish());
+}
+
+fn write_frame(out: &mut Vec<u8>, frame_number: u32, block: &[i16]) {
+    let mut bits = BitWriter::default();
+    bits.put(0b11_1111_1111_1110, 14); // sync code
+    bits.put(0, 1);
+    bits.put(0, 1); // fixed block size
+    bits.put(0b0111, 4); // block size - 1 follows as 16 bits
+    bits.put(0b0000, 4); // sample rate from STREAMINFO
+    bits.put(0b0000, 4); // mono
+    bits.put(0b100, 3); // 16 bits per sample
+    bits.put(0, 1);
+    for byte in utf8_number(frame_number) {
+        bits.put(u64::from(byte), 8);
+    }
+    bits.put(block.len() as u64 - 1, 16);
+    let header_crc = crc8(&bits.bytes);
+    bits.put(u64::from(header_crc), 8);
+
+    write_subframe(&mut bits, block);
+    let mut frame = bits.finish();
+    let frame_crc = crc16(&frame);
+    frame.extend_from_slice(&frame_crc.to_be_bytes());
+    out.extend_from_slice(&frame);
+}
+
+fn write_subframe(bits: &mut BitWriter, block: &[i16]) {
+    let samples: Vec<i32> = block.iter().map(|&sample| i32::from(sample)).collect();
+    let (order, residual, rice_param) = (0..=MAX_FIXED_ORDER.min(samples.len() - 1))
+        .map(|order| {
+            let residual = fixed_residual(&samples, order);
+            let (rice_param, cost) = best_rice_param(&residual);
+            (order, residual, rice_param, cost + order as u64 * 16)
+        })
+        .min_by_key(|candidate| candidate.3)
+        .map(|(order, residual, rice_param, _)| (order, residual, rice_param))
+        .expect("at least order 0 is always possible");
+
+    bits.put(0, 1);
+    bits.put(0b001000 | order as u64, 6); // SUBFRAME_FIXED
+    bits.put(0, 1); // no wasted bits
+    for &warmup in &samples[..order] {
+        bits.put_signed(warmup, 16);
+    }
+    bits.put(0b00, 2); // Rice coding, 4-bit parameters
+    bits.put(0, 4); // one partition
+    bits.put(u64::from(rice_param), 4);
+    for &value in &residual {
+        let folded = fold(value);
+        let quotient = folded >> rice_param;
+        for _ in 0..quotient {
+            bits.put(0, 1);
+        }
+        bits.put(1, 1);
+        bits.put(u64::from(folded) & ((1 << rice_param) - 1), rice_param);
+    }
+}
+
+/// Prediction error of the fixed polynomial predictor of `order`.
+fn fixed_residual(samples: &[i32], order: usize) -> Vec<i32> {
+    (order..samples.len())
+        .map(|i| {
+            let s = |back: usize| samples[i - back];
+            let predicted = match order {
+                0 => 0,
+                1 => s(1),
+                2 => 2 * s(1) - s(2),
+                3 => 3 * s(1) - 3 * s(2) + s(3),
+                _ => 4 * s(1) - 6 * s(2) + 4 * s(3) - s(4),
+            };
+            samples[i] - predicted
+        })
+        .collect()
+}
+
+/// Rice parameter with the fewest residual bits, and that bit count.
+fn best_rice_param(residual: &[i32]) -> (u32, u64) {
+    (0..=MAX_RICE_PARAM)
+        .map(|param| {
+            let cost = residual
+                .iter()
+                .map(|&value| u64::from(fold(value) >> param) + 1 + u64::from(param))
+                .sum::<u64>();
+            (param, cost)
+        })
+        .min_by_key(|&(_, cost)| cost)
+        .unwrap_or((0, 0))
+}
+
+/// Zigzag-map a signed residual so small magnitudes get small codes.
+fn fold(value: i32) -> u32 {
+    ((value << 1) ^ (value >> 31)) as u32
+}
+
+/// Frame number in FLAC's UTF-8-style variable-length coding.
+fn utf8_number(value: u32) -> Vec<u8> {
+    if value < 0x80 {
+        return vec![value as u8];
+    }
+    let mut continuation = Vec::new();
+    let mut rest = value;
+    // Each continuation byte holds 6 bits; the lead byte holds what remains.
+    loop {
+        continuation.push(0x80 | (rest & 0x3f) as u8);
+        rest >>= 6;
+        let len = continuation.len() + 1;
+        let lead_bits = 7 - len as u32;
+        if rest < (1 << lead_bits) {
+            let marker = !(0xffu8 >> len);
+            let mut bytes = vec![marker | rest as u8];
+            bytes.extend(continuation.iter().rev());
+            return bytes;
+        }
+    }
+}
+
+fn crc8(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0u8, |mut crc, &byte| {
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 {
+                (crc << 1) ^ 0x07
+            } else {
+                crc << 1
+            };
+        }
+        crc
+    })
+}
+
+fn crc16(bytes: &[u8]) -> u16 {
+    bytes.iter().fold(0u16, |mut crc, &byte| {
+        crc ^= u16::from(byte) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x8005
+            } else {
+                crc << 1
+            };
+        }
+        crc
+    })
+}
+
+/// MSB-first bit packer.
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    current: u8,
+    filled: u32,
+}
+
+impl BitWriter {
+    fn put(&mut self, value: u64, width: u32) {
+        for shift in (0..width).rev() {
+            self.current = (self.current << 1) | ((value >> shift) & 1) as u8;
+            self.filled += 1;
+            if self.filled == 8 {
+                self.bytes.push(self.current);
+                self.current = 0;
+                self.filled = 0;
+            }
+        }
+    }
+
+    fn put_signed(&mut self, value: i32, width: u32) {
+        self.put(u64::from(value as u32) & ((1 << width) - 1), width);
+    }
+
+    /// Zero-pad to a byte boundary and return the bytes.
+    fn finish(mut self) -> Vec<u8> {
+        if self.filled > 0 {
+            self.put(0, 8 - self.filled);
+        }
+        self.bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Decode the residual bits of a single-frame stream written by `encode_flac`.
+    struct BitReader<'a> {
+        bytes: &'a [u8],
+        pos: usize,
+    }
+
+    impl BitReader<'_> {
+        fn get(&mut self, width: u32) -> u64 {
+            (0..width).fold(0, |acc, _| {
+                let bit = (self.bytes[self.pos / 8] >> (7 - self.pos % 8)) & 1;
+                self.pos += 1;
+                (acc << 1) | u64::from(bit)
+            })
+        }
+
+        fn get_signed(&mut self, width: u32) -> i32 {
+            let raw = self.get(width) as i32;
+            (raw << (32 - width)) >> (32 - width)
+        }
+    }
+
+    fn decode_single_frame(stream: &[u8]) -> Vec<i16> {
+        let frame = &stream[4 + 4 + 34..];
+        let mut reader = BitReader {
+            bytes: frame,
+            pos: 0,
+        };
+        assert_eq!(reader.get(14), 0b11_1111_1111_1110);
+        reader.get(2 + 4 + 4 + 4 + 3 + 1);
+        assert_eq!(reader.get(8), 0, "frame number");
+        let len = reader.get(16) as usize + 1;
+        let header_crc = reader.get(8) as u8;
+        assert_eq!(crc8(&frame[..reader.pos / 8 - 1]), header_crc);
+
+        assert_eq!(reader.get(1), 0);
+        let kind = reader.get(6) as usize;
+        assert_eq!(kind & 0b111000, 0b001000);
+        let order = kind & 0b111;
+        assert_eq!(reader.get(1), 0);
+        let mut samples: Vec<i32> = (0..order).map(|_| reader.get_signed(16)).collect();
+        assert_eq!(reader.get(2), 0);
+        assert_eq!(reader.get(4), 0);
+        let param = reader.get(4) as u32;
+        while samples.len() < len {
+            let mut quotient = 0;
+            while reader.get(1) == 0 {
+                quotient += 1;
+            }
+            let folded = (quotient << param) | reader.get(param) as u32;
+            let residual = ((folded >> 1) as i32) ^ -((folded & 1) as i32);
+            let i = samples.len();
+            let s = |back: usize| samples[i - back];
+            let predicted = match order {
+                0 => 0,
+                1 => s(1),
+                2 => 2 * s(1) - s(2),
+                3 => 3 * s(1) - 3 * s(2) + s(3),
+                _ => 4 * s(1) - 6 * s(2) + 4 * s(3) - s(4),
+            };
+            samples.push(predicted + residual);
+        }
+        let end = reader.pos.div_ceil(8);
+        let crc = u16::from_be_bytes([frame[end], frame[end + 1]]);
+        assert_eq!(crc16(&frame[..end]), crc);
+        assert_eq!(frame.len(), end + 2);
+        samples.into_iter().map(|s| s as i16).collect()
+    }
+
+    #[test]
+    fn single_frame_round_trips_and_compresses_tones() {
+        let samples: Vec<i16> = (0..3000)
+            .map(|i| ((i as f32 * 0.05).sin() * 8000.0) as i16)
+            .collect();
+        let stream = encode_flac(&samples, 16_000);
+        assert_eq!(&stream[..4], b"fLaC");
+        assert_eq!(decode_single_frame(&stream), samples);
+        assert!(stream.len() < samples.len(), "{} bytes", stream.len());
+    }
+
+    #[test]
+    fn tiny_and_extreme_blocks_round_trip() {
+        for samples in [vec![7], vec![i16::MIN, i16::MAX, i16::MIN, i16::MAX, 0, -1]] {
+            assert_eq!(decode_single_frame(&encode_flac(&samples, 16_000)), samples);
+        }
+    }
+
+    #[test]
+    fn frame_numbers_use_utf8_style_coding() {
+        assert_eq!(utf8_number(0x41), [0x41]);
+        assert_eq!(utf8_number(0xe9), [0xc3, 0xa9]);
+        assert_eq!(utf8_number(0x20ac), [0xe2, 0x82, 0xac]);
+    }
+
+    #[test]
+    fn crcs_match_reference_values() {
+        assert_eq!(crc8(b"123456789"), 0xf4);
+        assert_eq!(crc16(b"123456789"), 0xfee8);
+    }
+}
@@ -0,0 +1,85 @@
+//! Input-device hot-plug monitoring so a recorder can follow a mic that comes and goes.
+//!
+//! cpal has no portable device-change notification, so [`DeviceWatcher`] lists
+//! the input devices on a background thread at a fixed interval and keeps the
+//! latest snapshot for the UI thread to compare against the open recorder.
+
+use super::Recorder;
+use crate::log_debug;
+use anyhow::Result;
+use crossbeam_channel::{bounded, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// Input devices present at one point in time.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InputDevices {
+    pub names: Vec<String>,
+    /// The system default input, when there is one.
+    pub default: Option<String>,
+}
+
+impl InputDevices {
+    /// List the input devices plugged in right now.
+    pub fn scan() -> Result<Self> {
+        Ok(Self {
+            names: Recorder::list_devices()?,
+            default: Recorder::default_device_name(),
+        })
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.names.iter().any(|device| device == name)
+    }
+}
+
+/// Background thread that rescans the input devices every `interval`.
+///
+/// Dropping the watcher stops the thread.
+pub struct DeviceWatcher {
+    latest: Arc<Mutex<Option<InputDevices>>>,
+    stop: Option<Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl DeviceWatcher {
+    pub fn spawn(interval: Duration) -> Self {
+        let latest = Arc::new(Mutex::new(None));
+        let (stop_tx, stop_rx) = bounded::<()>(0);
+        let snapshot = latest.clone();
+        let thread = thread::spawn(move || loop {
+            match InputDevices::scan() {
+                Ok(devices) => {
+                    if let Ok(mut latest) = snapshot.lock() {
+                        *latest = Some(devices);
+                    }
+                }
+                Err(err) => log_debug(&format!("input device scan failed: {err:#}")),
+            }
+            // Closing the channel on drop wakes this up early.
+            if stop_rx.recv_timeout(interval) != Err(RecvTimeoutError::Timeout) {
+                break;
+            }
+        });
+        Self {
+            latest,
+            stop: Some(stop_tx),
+            thread: Some(thread),
+        }
+    }
+
+    /// Most recent scan, or `None` before the first one finishes.
+    pub fn latest(&self) -> Option<InputDevices> {
+        self.latest.lock().ok().and_then(|latest| latest.clone())
+    }
+}
+
+impl Drop for DeviceWatcher {
+    fn drop(&mut self) {
+        self.stop.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
@@ -2,7 +2,8 @@
 //!
 //! Provides microphone recording with automatic silence detection. Audio is
 //! captured via CPAL, resampled to 16kHz mono (Whisper's expected format),
-//! and returned when the user stops speaking.
+//! and returned when the user stops speaking. Builds without the `audio`
+//! feature swap the CPAL recorder for a stand-in that reports no device.
 
 /// Target sample rate for Whisper STT.
 pub const TARGET_RATE: u32 = 16_000;
@@ -11,15 +12,46 @@ pub const TARGET_RATE: u32 = 16_000;
 pub const TARGET_CHANNELS: u32 = 1;
 
 mod capture;
+mod cues;
+#[cfg(not(feature = "audio"))]
+mod disabled;
+#[cfg_attr(not(feature = "audio"), allow(dead_code))]
 mod dispatch;
+mod dump;
+#[cfg_attr(not(feature = "audio"), allow(dead_code))]
+mod echo;
+mod endpoint;
+mod flac;
+mod hotplug;
 mod meter;
+mod mute;
+mod preprocess;
+#[cfg_attr(not(feature = "audio"), allow(dead_code))]
+mod preroll;
+#[cfg(feature = "audio")]
 mod recorder;
+#[cfg_attr(not(feature = "audio"), allow(dead_code))]
 mod resample;
+mod speaker;
 #[cfg(test)]
 mod tests;
 mod vad;
 
-pub use capture::{offline_capture_from_pcm, CaptureMetrics, CaptureResult, StopReason};
+pub use capture::{
+    offline_capture_from_pcm, CaptureMetrics, CaptureResult, StopReason, StreamFrame,
+    UTTERANCE_REJECTED_STATUS,
+};
+pub use cues::{render_cue, AudioCue, CuePlayer};
+#[cfg(not(feature = "audio"))]
+pub use disabled::Recorder;
+pub use dump::{decode_wav, wav_bytes, AudioDump};
+pub use echo::{playback_active, EchoCanceller, PlaybackGuard};
+pub use hotplug::{DeviceWatcher, InputDevices};
 pub use meter::LiveMeter;
+pub use mute::MIC_MUTED_STATUS;
+pub use preprocess::{preprocessor_for_config, AudioPreprocessor, NoiseSuppressor};
+#[cfg(feature = "audio")]
 pub use recorder::Recorder;
+pub use resample::{resample, resample_to_target_rate};
+pub use speaker::{default_voice_print_path, VoicePrint, SPEAKER_REJECTED_STATUS};
 pub use vad::{SimpleThresholdVad, VadConfig, VadDecision, VadEngine};
@@ -0,0 +1,66 @@
+//! Digital-silence tracking so hardware mute switches are reported instead of "no speech".
+//!
+//! Muted headsets usually deliver frames of exact zeros (or a flat DC value),
+//! which even a quiet room never produces. Runs of such frames are tracked on
+//! the raw device audio, before any preprocessing can alter it.
+
+/// Digital silence lasting at least this long marks the mic as muted (ms).
+pub(crate) const MUTE_DETECT_MS: u64 = 1500;
+/// Status text shown instead of "No speech detected" when the mic looks muted.
+pub const MIC_MUTED_STATUS: &str = "Mic appears muted (check headset mute switch)";
+/// Peak amplitude at or below one 16-bit LSB counts as digital silence.
+const DIGITAL_SILENCE_PEAK: f32 = 1.0 / 32_768.0;
+/// Variance floor that catches flat, DC-offset frames from some muted devices.
+const DIGITAL_SILENCE_VARIANCE: f32 = 1.0e-12;
+
+/// Whether a frame carries no real signal (exact zeros or a flat line).
+pub(crate) fn is_digital_silence(samples: &[f32]) -> bool {
+    if samples.is_empty() {
+        return false;
+    }
+    let peak = samples
+        .iter()
+        .fold(0.0f32, |peak, sample| peak.max(sample.abs()));
+    if peak <= DIGITAL_SILENCE_PEAK {
+        return true;
+    }
+    let len = samples.len() as f32;
+    let mean = samples.iter().sum::<f32>() / len;
+    let variance = samples
+        .iter()
+        .map(|sample| (sample - mean) * (sample - mean))
+        .sum::<f32>()
+        / len;
+    variance <= DIGITAL_SILENCE_VARIANCE
+}
+
+/// Tracks the longest run of digitally silent frames during one capture.
+#[derive(Debug, Clone)]
+pub(crate) struct DigitalSilenceTracker {
+    frame_ms: u64,
+    current_run_ms: u64,
+    longest_run_ms: u64,
+}
+
+impl DigitalSilenceTracker {
+    pub(crate) fn new(frame_ms: u64) -> Self {
+        Self {
+            frame_ms,
+            current_run_ms: 0,
+            longest_run_ms: 0,
+        }
+    }
+
+    pub(crate) fn observe(&mut self, samples: &[f32]) {
+        if is_digital_silence(samples) {
+            self.current_run_ms += self.frame_ms;
+            self.longest_run_ms = self.longest_run_ms.max(self.current_run_ms);
+        } else {
+            self.current_run_ms = 0;
+        }
+    }
+
+    pub(crate) fn longest_run_ms(&self) -> u64 {
+        self.longest_run_ms
+    }
+}
@@ -0,0 +1,136 @@
+//! Frame preprocessing stages that clean audio before it reaches the VAD.
+//!
+//! Preprocessors run on every 16 kHz mono frame after format conversion and
+//! before VAD classification, so both the speech/silence decision and the
+//! audio handed to STT benefit from the cleanup.
+
+use super::meter::rms_db;
+use super::vad::VadConfig;
+
+/// High-pass corner used to strip fan rumble and DC offset (Hz).
+const HIGH_PASS_CUTOFF_HZ: f32 = 100.0;
+/// How far above the tracked noise floor a frame must be to pass untouched (dB).
+const GATE_OPEN_MARGIN_DB: f32 = 12.0;
+/// Maximum attenuation applied to frames sitting at the noise floor (dB).
+const MAX_ATTENUATION_DB: f32 = -18.0;
+/// Per-frame rise allowed for the noise-floor estimate (dB).
+const NOISE_FLOOR_RISE_DB: f32 = 0.2;
+/// Lowest noise-floor estimate we track, so digital silence does not pin it (dB).
+const MIN_NOISE_FLOOR_DB: f32 = -90.0;
+/// Gain smoothing coefficient between frames to avoid zipper noise.
+const GAIN_SMOOTHING: f32 = 0.5;
+
+/// A stage that rewrites a capture frame in place before VAD runs.
+pub trait AudioPreprocessor {
+    /// Process one frame of 16 kHz mono samples in place.
+    fn process_frame(&mut self, samples: &mut [f32]);
+    /// Reset internal state between recordings.
+    fn reset(&mut self);
+    /// Stage name used for diagnostics.
+    fn name(&self) -> &'static str {
+        "unknown_preprocessor"
+    }
+}
+
+/// Lightweight stationary-noise suppressor for fans, hum, and room tone.
+///
+/// Combines a one-pole high-pass filter (removes rumble/DC) with a
+/// noise-floor tracking downward expander: frames near the estimated floor are
+/// attenuated, while frames well above it pass through unchanged.
+#[derive(Debug, Clone)]
+pub struct NoiseSuppressor {
+    sample_rate: u32,
+    hp_alpha: f32,
+    hp_prev_input: f32,
+    hp_prev_output: f32,
+    noise_floor_db: Option<f32>,
+    gain: f32,
+}
+
+impl NoiseSuppressor {
+    /// Create a suppressor tuned for the provided sample rate.
+    pub fn new(sample_rate: u32) -> Self {
+        let rate = sample_rate.max(1) as f32;
+        let rc = 1.0 / (2.0 * std::f32::consts::PI * HIGH_PASS_CUTOFF_HZ);
+        let dt = 1.0 / rate;
+        Self {
+            sample_rate,
+            hp_alpha: rc / (rc + dt),
+            hp_prev_input: 0.0,
+            hp_prev_output: 0.0,
+            noise_floor_db: None,
+            gain: 1.0,
+        }
+    }
+
+    /// Current noise-floor estimate in dB, once at least one frame was seen.
+    pub fn noise_floor_db(&self) -> Option<f32> {
+        self.noise_floor_db
+    }
+
+    fn high_pass(&mut self, samples: &mut [f32]) {
+        for sample in samples.iter_mut() {
+            let input = *sample;
+            let output = self.hp_alpha * (self.hp_prev_output + input - self.hp_prev_input);
+            self.hp_prev_input = input;
+            self.hp_prev_output = output;
+            *sample = output;
+        }
+    }
+
+    fn update_noise_floor(&mut self, frame_db: f32) {
+        // Track the minimum quickly and creep upward slowly so sustained speech
+        // cannot drag the floor up, while a louder fan is still learned.
+        let floor = frame_db.max(MIN_NOISE_FLOOR_DB);
+        self.noise_floor_db = Some(match self.noise_floor_db {
+            Some(current) if floor >= current => current + NOISE_FLOOR_RISE_DB,
+            _ => floor,
+        });
+    }
+
+    fn target_gain(&self, frame_db: f32) -> f32 {
+        let Some(noise_floor_db) = self.noise_floor_db else {
+            return 1.0;
+        };
+        let above_floor = frame_db - noise_floor_db;
+        if above_floor >= GATE_OPEN_MARGIN_DB {
+            return 1.0;
+        }
+        let ratio = (above_floor / GATE_OPEN_MARGIN_DB).clamp(0.0, 1.0);
+        let attenuation_db = MAX_ATTENUATION_DB * (1.0 - ratio);
+        10f32.powf(attenuation_db / 20.0)
+    }
+}
+
+impl AudioPreprocessor for NoiseSuppressor {
+    fn process_frame(&mut self, samples: &mut [f32]) {
+        if samples.is_empty() {
+            return;
+        }
+        self.high_pass(samples);
+        let frame_db = rms_db(samples);
+        let target = self.target_gain(frame_db);
+        self.update_noise_floor(frame_db);
+        self.gain = self.gain * GAIN_SMOOTHING + target * (1.0 - GAIN_SMOOTHING);
+        for sample in samples.iter_mut() {
+            *sample *= self.gain;
+        }
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(self.sample_rate);
+    }
+
+    fn name(&self) -> &'static str {
+        "noise_suppressor"
+    }
+}
+
+/// Build the preprocessing stage requested by the capture config, if any.
+pub fn preprocessor_for_config(cfg: &VadConfig) -> Option<Box<dyn AudioPreprocessor + Send>> {
+    if cfg.denoise {
+        Some(Box::new(NoiseSuppressor::new(cfg.sample_rate)))
+    } else {
+        None
+    }
+}
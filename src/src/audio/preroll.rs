@@ -0,0 +1,64 @@
+//! Routing for an input stream kept open between captures.
+//!
+//! With `--keep-mic-open` the recorder's stream keeps running after a capture
+//! ends. Between captures its samples land in a ring holding the last
+//! `lookback_ms`; the next capture takes that ring as pre-roll, so the first
+//! syllable after the hotkey is real audio instead of a cold stream start, and
+//! the stream then feeds that capture's frame dispatcher until it detaches.
+
+use super::dispatch::{append_downmixed_samples, FrameDispatcher};
+use std::collections::VecDeque;
+
+pub(super) struct LiveInput {
+    ring: VecDeque<f32>,
+    capacity: usize,
+    scratch: Vec<f32>,
+    dispatcher: Option<FrameDispatcher>,
+}
+
+#[cfg_attr(test, allow(dead_code))]
+impl LiveInput {
+    /// `capacity` is the ring size in mono samples at the device rate.
+    pub(super) fn new(capacity: usize) -> Self {
+        Self {
+            ring: VecDeque::with_capacity(capacity),
+            capacity,
+            scratch: Vec::new(),
+            dispatcher: None,
+        }
+    }
+
+    /// Route one callback's samples to the active capture, or into the ring.
+    pub(super) fn push<T, F>(&mut self, data: &[T], channels: usize, convert: F)
+    where
+        T: Copy,
+        F: FnMut(T) -> f32,
+    {
+        if let Some(dispatcher) = self.dispatcher.as_mut() {
+            dispatcher.push(data, channels, convert);
+            return;
+        }
+        self.scratch.clear();
+        append_downmixed_samples(&mut self.scratch, data, channels, convert);
+        self.ring.extend(self.scratch.iter().copied());
+        let excess = self.ring.len().saturating_sub(self.capacity);
+        self.ring.drain(..excess);
+    }
+
+    /// Start a capture: return the buffered pre-roll and send later samples to `dispatcher`.
+    pub(super) fn attach(&mut self, dispatcher: FrameDispatcher) -> Vec<f32> {
+        self.dispatcher = Some(dispatcher);
+        self.ring.drain(..).collect()
+    }
+
+    /// End a capture; samples go back into the ring, which starts empty.
+    pub(super) fn detach(&mut self) {
+        self.dispatcher = None;
+        self.ring.clear();
+    }
+
+    #[cfg(test)]
+    pub(super) fn buffered(&self) -> usize {
+        self.ring.len()
+    }
+}
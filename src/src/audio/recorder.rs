@@ -3,46 +3,103 @@
 //! Handles device enumeration, format conversion, and sample rate normalization.
 //! All audio is converted to 16kHz mono f32 PCM for Whisper compatibility.
 
-use super::capture::{CaptureMetrics, CaptureResult};
+use super::capture::{CaptureMetrics, CaptureResult, StreamFrame};
 #[cfg(not(test))]
 use super::capture::{CaptureState, FrameAccumulator, StopReason};
 use super::dispatch::append_downmixed_samples;
 #[cfg(not(test))]
 use super::dispatch::FrameDispatcher;
 #[cfg(not(test))]
+use super::echo::{playback_active, EchoStage, PLAYBACK_HANGOVER};
+#[cfg(not(test))]
 use super::meter::rms_db;
 use super::meter::LiveMeter;
 #[cfg(not(test))]
+use super::mute::DigitalSilenceTracker;
+#[cfg(not(test))]
+use super::preprocess::preprocessor_for_config;
+use super::preroll::LiveInput;
+#[cfg(not(test))]
 use super::resample::convert_frame_to_target;
 use super::resample::resample_to_target_rate;
 #[cfg(not(test))]
 use super::vad::{FrameLabel, VadSmoother};
 use super::vad::{VadConfig, VadEngine};
+#[cfg(not(test))]
+use crate::config::EchoMode;
 use crate::log_debug;
+use crate::voice_error::VoiceErrorKind;
 use anyhow::{anyhow, Context, Result};
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use cpal::{SampleFormat, StreamConfig};
+use crossbeam_channel::{bounded, Sender};
 #[cfg(not(test))]
-use crossbeam_channel::{bounded, RecvTimeoutError};
-use std::sync::atomic::AtomicBool;
-#[cfg(not(test))]
-use std::sync::atomic::{AtomicUsize, Ordering};
+use crossbeam_channel::{Receiver, RecvTimeoutError};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
 use std::time::Duration;
 
+/// Reference frames allowed to queue before the oldest are dropped, so the
+/// echo reference never trails the mic by more than the canceller can model.
+#[cfg(not(test))]
+const MAX_REFERENCE_BACKLOG_FRAMES: usize = 1;
+
 /// Audio input device wrapper.
 ///
 /// Abstracts CPAL device handling and provides methods for recording audio
 /// with automatic format conversion and resampling.
 pub struct Recorder {
     device: cpal::Device,
+    /// Stream left running between captures by [`Recorder::keep_open`].
+    live: Option<LiveStream>,
+}
+
+/// Input stream opened by [`Recorder::keep_open`].
+///
+/// cpal streams are not `Send` on every platform, so the stream is built on and
+/// owned by its own thread; dropping this stops that thread and the stream.
+#[cfg_attr(test, allow(dead_code))]
+struct LiveStream {
+    input: Arc<Mutex<LiveInput>>,
+    device_sample_rate: u32,
+    /// Callbacks that found the input locked and dropped their samples.
+    lock_misses: Arc<AtomicUsize>,
+    /// Set by the stream error callback (for example when the device is unplugged).
+    failed: Arc<AtomicBool>,
+    stop: Option<Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+#[cfg_attr(test, allow(dead_code))]
+impl LiveStream {
+    fn is_running(&self) -> bool {
+        !self.failed.load(Ordering::Relaxed)
+            && self
+                .thread
+                .as_ref()
+                .is_some_and(|thread| !thread.is_finished())
+    }
+}
+
+impl Drop for LiveStream {
+    fn drop(&mut self) {
+        // Closing the channel wakes the stream thread, which drops the stream.
+        self.stop.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
 }
 
 impl Recorder {
     /// List microphone names so the CLI can expose a human-friendly selector.
     pub fn list_devices() -> Result<Vec<String>> {
         let host = cpal::default_host();
-        let devices = host.input_devices().context("no input devices available")?;
+        let devices = host
+            .input_devices()
+            .context("no input devices available")
+            .context(VoiceErrorKind::NoDevice)?;
         let mut names = Vec::new();
         for device in devices {
             if let Ok(name) = device.name() {
@@ -52,22 +109,109 @@ impl Recorder {
         Ok(names)
     }
 
+    /// Name of the system default input device, if one is available.
+    pub fn default_device_name() -> Option<String> {
+        cpal::default_host()
+            .default_input_device()
+            .and_then(|device| device.name().ok())
+    }
+
     /// Create a recorder, optionally forcing a specific device so users can pick
     /// the right microphone when a laptop exposes multiple inputs.
     pub fn new(preferred_device: Option<&str>) -> Result<Self> {
         let host = cpal::default_host();
         let device = match preferred_device {
             Some(name) => {
-                let mut devices = host.input_devices().context("no input devices available")?;
+                let mut devices = host
+                    .input_devices()
+                    .context("no input devices available")
+                    .context(VoiceErrorKind::NoDevice)?;
                 devices
                     .find(|d| d.name().map(|n| n == name).unwrap_or(false))
-                    .ok_or_else(|| anyhow!("input device '{name}' not found"))?
+                    .ok_or_else(|| anyhow!("input device '{name}' not found"))
+                    .context(VoiceErrorKind::NoDevice)?
             }
             None => host
                 .default_input_device()
-                .context("no default input device available")?,
+                .context("no default input device available")
+                .context(VoiceErrorKind::NoDevice)?,
         };
-        Ok(Self { device })
+        Ok(Self { device, live: None })
+    }
+
+    /// Keep the input stream running between captures.
+    ///
+    /// The last `lookback_ms` of audio is buffered while idle and becomes the
+    /// pre-roll of the next capture, so speech that starts right as the capture
+    /// is triggered is not clipped. Captures then read from this stream instead
+    /// of opening their own. The OS shows the microphone as in use the whole time.
+    pub fn keep_open(&mut self, lookback_ms: u64) -> Result<()> {
+        if self.live.as_ref().is_some_and(LiveStream::is_running) {
+            return Ok(());
+        }
+        let default_config = self.device.default_input_config().map_err(device_error)?;
+        let format = default_config.sample_format();
+        let device_config: StreamConfig = default_config.into();
+        let device_sample_rate = device_config.sample_rate.0;
+        let channels = usize::from(device_config.channels.max(1));
+        let capacity = ((u64::from(device_sample_rate) * lookback_ms) / 1000) as usize;
+        let input = Arc::new(Mutex::new(LiveInput::new(capacity)));
+        let lock_misses = Arc::new(AtomicUsize::new(0));
+        let failed = Arc::new(AtomicBool::new(false));
+        let (ready_tx, ready_rx) = bounded::<Result<()>>(1);
+        let (stop_tx, stop_rx) = bounded::<()>(0);
+
+        let device = self.device.clone();
+        let stream_input = input.clone();
+        let stream_misses = lock_misses.clone();
+        let stream_failed = failed.clone();
+        let thread = thread::spawn(move || {
+            let stream = build_live_stream(
+                &device,
+                &device_config,
+                format,
+                channels,
+                stream_input,
+                stream_misses,
+                stream_failed,
+            )
+            .and_then(|stream| stream.play().map_err(device_error).map(|()| stream));
+            match stream {
+                Ok(stream) => {
+                    let _ = ready_tx.send(Ok(()));
+                    // Blocks until the `LiveStream` is dropped.
+                    let _ = stop_rx.recv();
+                    drop(stream);
+                }
+                Err(err) => {
+                    let _ = ready_tx.send(Err(err));
+                }
+            }
+        });
+        let started = ready_rx
+            .recv()
+            .unwrap_or_else(|_| Err(anyhow!("live input thread exited before starting")));
+        let live = LiveStream {
+            input,
+            device_sample_rate,
+            lock_misses,
+            failed,
+            stop: Some(stop_tx),
+            thread: Some(thread),
+        };
+        started?;
+        log_debug(&format!(
+            "input stream kept open ({lookback_ms} ms pre-roll at {device_sample_rate} Hz)"
+        ));
+        self.live = Some(live);
+        Ok(())
+    }
+
+    /// Whether the [`Recorder::keep_open`] stream reported an error, e.g. because the device was unplugged.
+    pub fn stream_failed(&self) -> bool {
+        self.live
+            .as_ref()
+            .is_some_and(|live| live.failed.load(Ordering::Relaxed))
     }
 
     /// Get the name of the active recording device.
@@ -81,7 +225,7 @@ impl Recorder {
     /// 16 kHz mono data that Whisper can consume directly.
     pub fn record_for(&self, duration: Duration) -> Result<Vec<f32>> {
         // Get the device's default config so we know the native format and channel count.
-        let default_config = self.device.default_input_config()?;
+        let default_config = self.device.default_input_config().map_err(device_error)?;
         let format = default_config.sample_format();
         let device_config: StreamConfig = default_config.clone().into();
         let device_sample_rate = device_config.sample_rate.0;
@@ -108,44 +252,53 @@ impl Recorder {
         // Convert every supported sample type to f32 up front so the rest of the
         // pipeline can stay format-agnostic.
         let stream = match format {
-            SampleFormat::F32 => self.device.build_input_stream(
-                &device_config,
-                move |data: &[f32], _| {
-                    if let Ok(mut buf) = buffer_clone.lock() {
-                        append_downmixed_samples(&mut buf, data, channels, |sample| sample);
-                    }
-                },
-                err_fn,
-                None,
-            )?,
-            SampleFormat::I16 => self.device.build_input_stream(
-                &device_config,
-                move |data: &[i16], _| {
-                    if let Ok(mut buf) = buffer_clone.lock() {
-                        append_downmixed_samples(&mut buf, data, channels, |sample| {
-                            sample as f32 / 32_768.0_f32
-                        });
-                    }
-                },
-                err_fn,
-                None,
-            )?,
-            SampleFormat::U16 => self.device.build_input_stream(
-                &device_config,
-                move |data: &[u16], _| {
-                    if let Ok(mut buf) = buffer_clone.lock() {
-                        append_downmixed_samples(&mut buf, data, channels, |sample| {
-                            (sample as f32 - 32_768.0_f32) / 32_768.0_f32
-                        });
-                    }
-                },
-                err_fn,
-                None,
-            )?,
+            SampleFormat::F32 => self
+                .device
+                .build_input_stream(
+                    &device_config,
+                    move |data: &[f32], _| {
+                        if let Ok(mut buf) = buffer_clone.lock() {
+                            append_downmixed_samples(&mut buf, data, channels, |sample| sample);
+                        }
+                    },
+                    err_fn,
+                    None,
+                )
+                .map_err(device_error)?,
+            SampleFormat::I16 => self
+                .device
+                .build_input_stream(
+                    &device_config,
+                    move |data: &[i16], _| {
+                        if let Ok(mut buf) = buffer_clone.lock() {
+                            append_downmixed_samples(&mut buf, data, channels, |sample| {
+                                sample as f32 / 32_768.0_f32
+                            });
+                        }
+                    },
+                    err_fn,
+                    None,
+                )
+                .map_err(device_error)?,
+            SampleFormat::U16 => self
+                .device
+                .build_input_stream(
+                    &device_config,
+                    move |data: &[u16], _| {
+                        if let Ok(mut buf) = buffer_clone.lock() {
+                            append_downmixed_samples(&mut buf, data, channels, |sample| {
+                                (sample as f32 - 32_768.0_f32) / 32_768.0_f32
+                            });
+                        }
+                    },
+                    err_fn,
+                    None,
+                )
+                .map_err(device_error)?,
             other => return Err(anyhow!("unsupported sample format: {other:?}")),
         };
 
-        stream.play()?;
+        stream.play().map_err(device_error)?;
         std::thread::sleep(duration);
         if let Err(err) = stream.pause() {
             log_debug(&format!("failed to pause audio stream: {err}"));
@@ -160,7 +313,8 @@ impl Recorder {
             return Err(anyhow!(
                 "no samples captured from '{device_name}'; check microphone permissions and availability. {}",
                 mic_permission_hint()
-            ));
+            ))
+            .context(VoiceErrorKind::MicPermission);
         }
 
         // Transcription assumes 16 kHz mono, so resample if the hardware rate differs.
@@ -182,7 +336,21 @@ impl Recorder {
         stop_flag: Option<Arc<AtomicBool>>,
         meter: Option<LiveMeter>,
     ) -> Result<CaptureResult> {
-        record_with_vad_impl(self, cfg, vad, stop_flag, meter)
+        record_with_vad_impl(self, cfg, vad, stop_flag, meter, None)
+    }
+
+    #[cfg(not(test))]
+    /// Like [`Recorder::record_with_vad`], but also sends each processed frame to
+    /// `tap` as it arrives so transcription can start before capture ends.
+    pub fn record_with_vad_streaming(
+        &self,
+        cfg: &VadConfig,
+        vad: &mut dyn VadEngine,
+        stop_flag: Option<Arc<AtomicBool>>,
+        meter: Option<LiveMeter>,
+        tap: Sender<StreamFrame>,
+    ) -> Result<CaptureResult> {
+        record_with_vad_impl(self, cfg, vad, stop_flag, meter, Some(tap))
     }
 
     #[cfg(test)]
@@ -200,10 +368,24 @@ impl Recorder {
         })
     }
 
+    #[cfg(test)]
+    /// Test stub for record_with_vad_streaming (sends no frames).
+    pub fn record_with_vad_streaming(
+        &self,
+        cfg: &VadConfig,
+        vad: &mut dyn VadEngine,
+        stop_flag: Option<Arc<AtomicBool>>,
+        meter: Option<LiveMeter>,
+        _tap: Sender<StreamFrame>,
+    ) -> Result<CaptureResult> {
+        self.record_with_vad(cfg, vad, stop_flag, meter)
+    }
+
     #[cfg(test)]
     pub(super) fn new_for_tests() -> Option<Self> {
         let host = cpal::default_host();
-        host.default_input_device().map(|device| Self { device })
+        host.default_input_device()
+            .map(|device| Self { device, live: None })
     }
 }
 
@@ -226,6 +408,255 @@ fn mic_permission_hint() -> &'static str {
     }
 }
 
+/// Build the [`Recorder::keep_open`] stream; every callback goes through `input`.
+fn build_live_stream(
+    device: &cpal::Device,
+    device_config: &StreamConfig,
+    format: SampleFormat,
+    channels: usize,
+    input: Arc<Mutex<LiveInput>>,
+    lock_misses: Arc<AtomicUsize>,
+    failed: Arc<AtomicBool>,
+) -> Result<cpal::Stream> {
+    let err_fn = move |err| {
+        log_debug(&format!("live audio_stream_error: {err}"));
+        failed.store(true, Ordering::Relaxed);
+    };
+    let stream = match format {
+        SampleFormat::F32 => device.build_input_stream(
+            device_config,
+            move |data: &[f32], _| {
+                if let Ok(mut live) = input.try_lock() {
+                    live.push(data, channels, |sample| sample);
+                } else {
+                    lock_misses.fetch_add(1, Ordering::Relaxed);
+                }
+            },
+            err_fn,
+            None,
+        ),
+        SampleFormat::I16 => device.build_input_stream(
+            device_config,
+            move |data: &[i16], _| {
+                if let Ok(mut live) = input.try_lock() {
+                    live.push(data, channels, |sample| sample as f32 / 32_768.0);
+                } else {
+                    lock_misses.fetch_add(1, Ordering::Relaxed);
+                }
+            },
+            err_fn,
+            None,
+        ),
+        SampleFormat::U16 => device.build_input_stream(
+            device_config,
+            move |data: &[u16], _| {
+                if let Ok(mut live) = input.try_lock() {
+                    live.push(data, channels, |sample| {
+                        (sample as f32 - 32_768.0) / 32_768.0
+                    });
+                } else {
+                    lock_misses.fetch_add(1, Ordering::Relaxed);
+                }
+            },
+            err_fn,
+            None,
+        ),
+        other => return Err(anyhow!("unsupported sample format: {other:?}")),
+    };
+    stream.map_err(device_error)
+}
+
+/// Wrap a CPAL/backend error with the matching voice error category.
+fn device_error(err: impl std::fmt::Display) -> anyhow::Error {
+    let message = err.to_string();
+    let kind = match VoiceErrorKind::from_message(&message) {
+        VoiceErrorKind::MicBusy { .. } => VoiceErrorKind::MicBusy {
+            owner: mic_busy_owner(),
+        },
+        VoiceErrorKind::Unknown => VoiceErrorKind::DeviceUnavailable,
+        other => other,
+    };
+    anyhow!(message).context(kind)
+}
+
+/// Best-effort lookup of the process holding a capture device open (Linux only).
+///
+/// Sound servers always hold the device, so they are skipped; when only a sound
+/// server is found we cannot name the real client and return `None`.
+#[cfg(target_os = "linux")]
+fn mic_busy_owner() -> Option<String> {
+    const SOUND_SERVERS: &[&str] = &["pulseaudio", "pipewire", "wireplumber", "jackd"];
+    let own_pid = std::process::id().to_string();
+    let entries = std::fs::read_dir("/proc").ok()?;
+    for entry in entries.flatten() {
+        let pid = entry.file_name().to_string_lossy().to_string();
+        if pid == own_pid || !pid.chars().all(|c| c.is_ascii_digit()) {
+            continue;
+        }
+        let Ok(fds) = std::fs::read_dir(entry.path().join("fd")) else {
+            continue;
+        };
+        let holds_capture = fds.flatten().any(|fd| {
+            std::fs::read_link(fd.path())
+                .map(|target| is_capture_device_path(&target.to_string_lossy()))
+                .unwrap_or(false)
+        });
+        if !holds_capture {
+            continue;
+        }
+        let Ok(comm) = std::fs::read_to_string(entry.path().join("comm")) else {
+            continue;
+        };
+        let name = comm.trim();
+        if !name.is_empty() && !SOUND_SERVERS.contains(&name) {
+            return Some(name.to_string());
+        }
+    }
+    None
+}
+
+#[cfg(not(target_os = "linux"))]
+fn mic_busy_owner() -> Option<String> {
+    None
+}
+
+/// ALSA capture PCM nodes look like `/dev/snd/pcmC0D0c` (trailing `c` = capture).
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn is_capture_device_path(path: &str) -> bool {
+    path.strip_prefix("/dev/snd/pcmC")
+        .is_some_and(|rest| rest.ends_with('c'))
+}
+
+/// Open an input stream for one capture, feeding `dispatcher` from the callback.
+#[cfg(not(test))]
+fn build_capture_stream(
+    device: &cpal::Device,
+    device_config: &StreamConfig,
+    format: SampleFormat,
+    channels: usize,
+    dispatcher: FrameDispatcher,
+    dropped: Arc<AtomicUsize>,
+) -> Result<cpal::Stream> {
+    let dispatcher = Arc::new(Mutex::new(dispatcher));
+    let err_fn = |err| log_debug(&format!("audio_stream_error: {err}"));
+    let stream = match format {
+        SampleFormat::F32 => {
+            let dispatcher = dispatcher.clone();
+            let dropped = dropped.clone();
+            device
+                .build_input_stream(
+                    device_config,
+                    move |data: &[f32], _| {
+                        if let Ok(mut pump) = dispatcher.try_lock() {
+                            pump.push(data, channels, |sample| sample);
+                        } else {
+                            dropped.fetch_add(1, Ordering::Relaxed);
+                        }
+                    },
+                    err_fn,
+                    None,
+                )
+                .map_err(device_error)?
+        }
+        SampleFormat::I16 => {
+            let dispatcher = dispatcher.clone();
+            let dropped = dropped.clone();
+            device
+                .build_input_stream(
+                    device_config,
+                    move |data: &[i16], _| {
+                        if let Ok(mut pump) = dispatcher.try_lock() {
+                            pump.push(data, channels, |sample| sample as f32 / 32_768.0);
+                        } else {
+                            dropped.fetch_add(1, Ordering::Relaxed);
+                        }
+                    },
+                    err_fn,
+                    None,
+                )
+                .map_err(device_error)?
+        }
+        SampleFormat::U16 => {
+            let dispatcher = dispatcher.clone();
+            let dropped = dropped.clone();
+            device
+                .build_input_stream(
+                    device_config,
+                    move |data: &[u16], _| {
+                        if let Ok(mut pump) = dispatcher.try_lock() {
+                            pump.push(data, channels, |sample| {
+                                (sample as f32 - 32_768.0) / 32_768.0
+                            });
+                        } else {
+                            dropped.fetch_add(1, Ordering::Relaxed);
+                        }
+                    },
+                    err_fn,
+                    None,
+                )
+                .map_err(device_error)?
+        }
+        other => return Err(anyhow!("unsupported sample format: {other:?}")),
+    };
+    stream.play().map_err(device_error)?;
+    Ok(stream)
+}
+
+/// Loopback input opened next to the mic so `--echo-mode` hears what the speakers play.
+#[cfg(not(test))]
+struct EchoReference {
+    /// Kept alive for the length of the capture.
+    _stream: cpal::Stream,
+    frames: Receiver<Vec<f32>>,
+    device_sample_rate: u32,
+}
+
+#[cfg(not(test))]
+impl EchoReference {
+    fn open(name: &str, frame_ms: u64, capacity: usize) -> Result<Self> {
+        let device = Recorder::new(Some(name))?.device;
+        let default_config = device.default_input_config().map_err(device_error)?;
+        let format = default_config.sample_format();
+        let device_config: StreamConfig = default_config.into();
+        let device_sample_rate = device_config.sample_rate.0;
+        let channels = usize::from(device_config.channels.max(1));
+        let frame_samples = ((u64::from(device_sample_rate) * frame_ms) / 1000).max(1) as usize;
+        let (sender, frames) = bounded::<Vec<f32>>(capacity.max(1));
+        let dropped = Arc::new(AtomicUsize::new(0));
+        let stream = build_capture_stream(
+            &device,
+            &device_config,
+            format,
+            channels,
+            FrameDispatcher::new(frame_samples, sender, dropped.clone()),
+            dropped,
+        )?;
+        Ok(Self {
+            _stream: stream,
+            frames,
+            device_sample_rate,
+        })
+    }
+
+    /// Reference audio for the mic frame just received, at the capture rate;
+    /// silence when the loopback has nothing queued.
+    fn next_frame(&self, cfg: &VadConfig, target_frame_samples: usize) -> Vec<f32> {
+        while self.frames.len() > MAX_REFERENCE_BACKLOG_FRAMES {
+            let _ = self.frames.try_recv();
+        }
+        match self.frames.try_recv() {
+            Ok(frame) => convert_frame_to_target(
+                frame,
+                self.device_sample_rate,
+                cfg.sample_rate,
+                cfg.resample_quality,
+                target_frame_samples,
+            ),
+            Err(_) => vec![0.0; target_frame_samples],
+        }
+    }
+}
+
 /// Records audio with voice activity detection.
 ///
 /// Captures audio in frames, runs VAD on each frame, and stops when:
@@ -241,87 +672,100 @@ fn record_with_vad_impl(
     vad: &mut dyn VadEngine,
     stop_flag: Option<Arc<AtomicBool>>,
     meter: Option<LiveMeter>,
+    tap: Option<Sender<StreamFrame>>,
 ) -> Result<CaptureResult> {
-    let default_config = recorder.device.default_input_config()?;
+    let live = recorder.live.as_ref().filter(|live| live.is_running());
+    let default_config = recorder
+        .device
+        .default_input_config()
+        .map_err(device_error)?;
     let format = default_config.sample_format();
     let device_config: StreamConfig = default_config.clone().into();
-    let device_sample_rate = device_config.sample_rate.0;
+    let device_sample_rate =
+        live.map_or(device_config.sample_rate.0, |live| live.device_sample_rate);
     let channels = usize::from(device_config.channels.max(1));
     let frame_ms = cfg.frame_ms.clamp(5, 120);
     let device_frame_samples = ((device_sample_rate as u64 * frame_ms) / 1000).max(1) as usize;
     let target_frame_samples = ((cfg.sample_rate as u64 * frame_ms) / 1000).max(1) as usize;
     let (sender, receiver) = bounded::<Vec<f32>>(cfg.channel_capacity.max(1));
     let dropped = Arc::new(AtomicUsize::new(0));
-    let dispatcher = Arc::new(Mutex::new(FrameDispatcher::new(
-        device_frame_samples,
-        sender,
-        dropped.clone(),
-    )));
 
-    let err_fn = |err| log_debug(&format!("audio_stream_error: {err}"));
-    let stream = match format {
-        SampleFormat::F32 => {
-            let dispatcher = dispatcher.clone();
-            let dropped = dropped.clone();
-            recorder.device.build_input_stream(
-                &device_config,
-                move |data: &[f32], _| {
-                    if let Ok(mut pump) = dispatcher.try_lock() {
-                        pump.push(data, channels, |sample| sample);
-                    } else {
-                        dropped.fetch_add(1, Ordering::Relaxed);
-                    }
-                },
-                err_fn,
-                None,
-            )?
-        }
-        SampleFormat::I16 => {
-            let dispatcher = dispatcher.clone();
-            let dropped = dropped.clone();
-            recorder.device.build_input_stream(
-                &device_config,
-                move |data: &[i16], _| {
-                    if let Ok(mut pump) = dispatcher.try_lock() {
-                        pump.push(data, channels, |sample| sample as f32 / 32_768.0);
-                    } else {
-                        dropped.fetch_add(1, Ordering::Relaxed);
-                    }
-                },
-                err_fn,
-                None,
-            )?
-        }
-        SampleFormat::U16 => {
-            let dispatcher = dispatcher.clone();
-            let dropped = dropped.clone();
-            recorder.device.build_input_stream(
-                &device_config,
-                move |data: &[u16], _| {
-                    if let Ok(mut pump) = dispatcher.try_lock() {
-                        pump.push(data, channels, |sample| {
-                            (sample as f32 - 32_768.0) / 32_768.0
-                        });
-                    } else {
-                        dropped.fetch_add(1, Ordering::Relaxed);
-                    }
-                },
-                err_fn,
-                None,
-            )?
+    // Open the echo reference before the mic so it leads the mic audio.
+    let reference = match cfg.echo_reference_device.as_deref() {
+        Some(name) if cfg.echo_mode != EchoMode::Off => {
+            EchoReference::open(name, frame_ms, cfg.channel_capacity)
+                .map_err(|err| {
+                    log_debug(&format!(
+                        "echo reference '{name}' unavailable, muting on playback only: {err:#}"
+                    ))
+                })
+                .ok()
         }
-        other => return Err(anyhow!("unsupported sample format: {other:?}")),
+        _ => None,
     };
 
-    stream.play()?;
+    // A kept-open stream switches over to this capture's dispatcher and hands
+    // back what it buffered while idle; otherwise open a stream for this capture.
+    let mut preroll = Vec::new();
+    let mut live_misses = 0;
+    let stream = if let Some(live) = live {
+        live_misses = live.lock_misses.load(Ordering::Relaxed);
+        let dispatcher = FrameDispatcher::new(device_frame_samples, sender, dropped.clone());
+        preroll = live
+            .input
+            .lock()
+            .map_err(|_| anyhow!("live input lock poisoned"))?
+            .attach(dispatcher);
+        None
+    } else {
+        Some(build_capture_stream(
+            &recorder.device,
+            &device_config,
+            format,
+            channels,
+            FrameDispatcher::new(device_frame_samples, sender, dropped.clone()),
+            dropped.clone(),
+        )?)
+    };
 
     let mut accumulator = FrameAccumulator::from_config(cfg);
     let mut state = CaptureState::new(cfg, frame_ms);
     let mut smoother = VadSmoother::new(cfg.smoothing_frames);
+    let mut preprocessor = preprocessor_for_config(cfg);
+    let mut silence_tracker = DigitalSilenceTracker::new(frame_ms);
+    let mut echo = EchoStage::new(cfg.echo_mode, cfg.sample_rate, frame_ms);
     let mut metrics = CaptureMetrics::default();
     let mut stop_reason = StopReason::MaxDuration;
     let wait_time = Duration::from_millis(frame_ms);
 
+    // Pre-roll is kept as lookback only: it warms the preprocessor and VAD, but
+    // capture timing and speech detection start at the trigger. A partial
+    // oldest frame is dropped so resampling never pads a gap into the audio.
+    let whole_frames = preroll.len() - preroll.len() % device_frame_samples;
+    for chunk in preroll[preroll.len() - whole_frames..].chunks_exact(device_frame_samples) {
+        let mut target_frame = convert_frame_to_target(
+            chunk.to_vec(),
+            device_sample_rate,
+            cfg.sample_rate,
+            cfg.resample_quality,
+            target_frame_samples,
+        );
+        if target_frame.is_empty() {
+            continue;
+        }
+        if let Some(stage) = preprocessor.as_mut() {
+            stage.process_frame(&mut target_frame);
+        }
+        vad.process_frame(&target_frame);
+        if let Some(ref tap) = tap {
+            let _ = tap.send(StreamFrame {
+                samples: target_frame.clone(),
+                speech: false,
+            });
+        }
+        accumulator.push_frame(target_frame, FrameLabel::Silence);
+    }
+
     while state.total_ms() < cfg.max_recording_duration_ms {
         // Check for manual stop signal
         if let Some(ref flag) = stop_flag {
@@ -332,15 +776,28 @@ fn record_with_vad_impl(
         }
         match receiver.recv_timeout(wait_time) {
             Ok(frame) => {
-                let target_frame = convert_frame_to_target(
+                let mut target_frame = convert_frame_to_target(
                     frame,
                     device_sample_rate,
                     cfg.sample_rate,
+                    cfg.resample_quality,
                     target_frame_samples,
                 );
                 if target_frame.is_empty() {
                     continue;
                 }
+                silence_tracker.observe(&target_frame);
+                let reference_frame = reference
+                    .as_ref()
+                    .map(|reference| reference.next_frame(cfg, target_frame_samples));
+                echo.process_frame(
+                    &mut target_frame,
+                    reference_frame.as_deref(),
+                    playback_active(PLAYBACK_HANGOVER),
+                );
+                if let Some(stage) = preprocessor.as_mut() {
+                    stage.process_frame(&mut target_frame);
+                }
 
                 if let Some(ref meter) = meter {
                     meter.set_db(rms_db(&target_frame));
@@ -350,8 +807,16 @@ fn record_with_vad_impl(
                 metrics.frames_processed += 1;
 
                 let label = smoother.smooth(FrameLabel::from(decision));
+                if let Some(ref tap) = tap {
+                    // The streaming worker may have given up; capture carries on regardless.
+                    let _ = tap.send(StreamFrame {
+                        samples: target_frame.clone(),
+                        speech: label == FrameLabel::Speech,
+                    });
+                }
+                let reason = state.on_frame_samples(label, &target_frame);
                 accumulator.push_frame(target_frame, label);
-                if let Some(reason) = state.on_frame(label) {
+                if let Some(reason) = reason {
                     stop_reason = reason;
                     break;
                 }
@@ -369,19 +834,37 @@ fn record_with_vad_impl(
         }
     }
 
-    if let Err(err) = stream.pause() {
-        log_debug(&format!("failed to pause audio stream: {err}"));
+    match stream {
+        Some(stream) => {
+            if let Err(err) = stream.pause() {
+                log_debug(&format!("failed to pause audio stream: {err}"));
+            }
+            drop(stream);
+        }
+        None => {
+            if let Some(live) = live {
+                if let Ok(mut input) = live.input.lock() {
+                    input.detach();
+                }
+                live_misses = live
+                    .lock_misses
+                    .load(Ordering::Relaxed)
+                    .saturating_sub(live_misses);
+            }
+        }
     }
-    drop(stream);
     if let Some(ref meter) = meter {
         meter.set_db(-60.0);
     }
 
     metrics.speech_ms = state.speech_ms();
+    metrics.voiced_span_ms = state.voiced_span_ms();
     metrics.silence_tail_ms = state.silence_tail_ms();
-    metrics.frames_dropped = dropped.load(Ordering::Relaxed);
+    metrics.frames_dropped = dropped.load(Ordering::Relaxed) + live_misses;
     metrics.early_stop_reason = stop_reason;
     metrics.capture_ms = state.total_ms();
+    metrics.digital_silence_ms = silence_tracker.longest_run_ms();
+    metrics.echo_muted_ms = echo.muted_ms();
 
     if accumulator.is_empty() {
         if matches!(metrics.early_stop_reason, StopReason::ManualStop) {
@@ -392,9 +875,13 @@ fn record_with_vad_impl(
         }
         return Err(anyhow!(
             "no samples captured; check microphone permissions and availability"
-        ));
+        ))
+        .context(VoiceErrorKind::MicPermission);
     }
 
+    metrics.preroll_ms = accumulator
+        .apply_preroll(&metrics.early_stop_reason)
+        .unwrap_or(0);
     let audio = accumulator.into_audio(&metrics.early_stop_reason);
 
     Ok(CaptureResult { audio, metrics })
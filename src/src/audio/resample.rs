@@ -1,11 +1,13 @@
 //! Sample-rate conversion that normalizes device audio for the STT pipeline.
 //!
-//! Devices report many input rates, but Whisper expects a stable target rate.
-//! This module favors resilient, low-latency conversion over perfect fidelity:
-//! we try the high-quality `rubato` path when enabled, then fall back to a
-//! predictable linear/FIR path so capture never fails because of resampling.
+//! Devices report many input rates, but the pipeline runs at `--voice-sample-rate`
+//! and Whisper expects 16 kHz. This module favors resilient, low-latency
+//! conversion over perfect fidelity: `--voice-resample-quality high` tries the
+//! `rubato` path when it is compiled in, then falls back to a predictable
+//! linear/FIR path so capture never fails because of resampling.
 
 use super::TARGET_RATE;
+use crate::config::ResampleQuality;
 #[cfg(feature = "high-quality-audio")]
 use crate::log_debug;
 #[cfg(feature = "high-quality-audio")]
@@ -36,26 +38,31 @@ pub(super) static RESAMPLE_WARN_COUNT: AtomicUsize = AtomicUsize::new(0);
 #[cfg(all(test, feature = "high-quality-audio"))]
 pub(super) static FORCE_RUBATO_ERROR: AtomicBool = AtomicBool::new(false);
 
-/// Convert mono PCM to the pipeline target sample rate.
+/// Convert mono PCM to Whisper's 16 kHz rate.
+pub fn resample_to_target_rate(input: &[f32], device_rate: u32) -> Vec<f32> {
+    resample(input, device_rate, TARGET_RATE, ResampleQuality::High)
+}
+
+/// Convert mono PCM from `from_rate` to `to_rate`.
 ///
 /// We treat resampling errors as recoverable because dropping a transcript is
 /// worse UX than a lower-fidelity fallback conversion.
-pub(super) fn resample_to_target_rate(input: &[f32], device_rate: u32) -> Vec<f32> {
+pub fn resample(input: &[f32], from_rate: u32, to_rate: u32, quality: ResampleQuality) -> Vec<f32> {
     // Fast-path identity cases to avoid unnecessary work in the hot path.
-    if device_rate == 0 {
+    if from_rate == 0 || to_rate == 0 {
         return input.to_vec();
     }
     if input.is_empty() {
         return input.to_vec();
     }
-    if device_rate == TARGET_RATE {
+    if from_rate == to_rate {
         return input.to_vec();
     }
 
     #[cfg(feature = "high-quality-audio")]
-    {
-        match resample_with_rubato(input, device_rate) {
-            Ok(output) => output,
+    if quality == ResampleQuality::High {
+        match resample_with_rubato(input, from_rate, to_rate) {
+            Ok(output) => return output,
             Err(err) => {
                 #[cfg(all(test, feature = "high-quality-audio"))]
                 RESAMPLE_FALLBACK_COUNT.fetch_add(1, Ordering::Relaxed);
@@ -68,19 +75,27 @@ pub(super) fn resample_to_target_rate(input: &[f32], device_rate: u32) -> Vec<f3
                         "high-quality resampler failed ({err}); falling back to basic path"
                     ));
                 }
-                basic_resample(input, device_rate)
             }
         }
     }
 
     #[cfg(not(feature = "high-quality-audio"))]
-    {
-        basic_resample(input, device_rate)
-    }
+    let _ = quality;
+    basic_resample(input, from_rate, to_rate)
+}
+
+/// Resample ratio bounds for `to_rate`; the `*_RESAMPLE_RATIO` constants are the 16 kHz case.
+fn ratio_bounds(to_rate: u32) -> (f64, f64) {
+    let scale = f64::from(to_rate) / f64::from(TARGET_RATE);
+    (MIN_RESAMPLE_RATIO * scale, MAX_RESAMPLE_RATIO * scale)
 }
 
 #[cfg(feature = "high-quality-audio")]
-pub(super) fn resample_with_rubato(input: &[f32], device_rate: u32) -> Result<Vec<f32>> {
+pub(super) fn resample_with_rubato(
+    input: &[f32],
+    device_rate: u32,
+    target_rate: u32,
+) -> Result<Vec<f32>> {
     // Keep these early outs aligned with the basic path so behavior is stable
     // regardless of feature flags.
     if device_rate == 0 {
@@ -89,7 +104,7 @@ pub(super) fn resample_with_rubato(input: &[f32], device_rate: u32) -> Result<Ve
     if input.is_empty() {
         return Ok(input.to_vec());
     }
-    if device_rate == TARGET_RATE {
+    if device_rate == target_rate {
         return Ok(input.to_vec());
     }
 
@@ -98,8 +113,9 @@ pub(super) fn resample_with_rubato(input: &[f32], device_rate: u32) -> Result<Ve
             "unsupported device sample rate {device_rate}Hz for resampling"
         ));
     }
-    let ratio = TARGET_RATE as f64 / device_rate as f64;
-    if !(MIN_RESAMPLE_RATIO..=MAX_RESAMPLE_RATIO).contains(&ratio) {
+    let ratio = f64::from(target_rate) / f64::from(device_rate);
+    let (min_ratio, max_ratio) = ratio_bounds(target_rate);
+    if !(min_ratio..=max_ratio).contains(&ratio) {
         return Err(anyhow!("invalid resample ratio {ratio}"));
     }
 
@@ -124,7 +140,7 @@ pub(super) fn resample_with_rubato(input: &[f32], device_rate: u32) -> Result<Ve
         .map_err(|e| anyhow!("failed to construct sinc resampler: {e:?}"))?;
 
     // Reserve based on worst-case ratio to avoid allocator churn while recording.
-    let max_len = ((input.len() as f64) * MAX_RESAMPLE_RATIO).ceil() as usize;
+    let max_len = ((input.len() as f64) * max_ratio).ceil() as usize;
     let mut expect = ((input.len() as f64) * ratio).round() as usize;
     expect = expect.clamp(1, max_len).saturating_add(8);
     let mut out = Vec::with_capacity(expect);
@@ -164,7 +180,7 @@ pub(super) fn resample_with_rubato(input: &[f32], device_rate: u32) -> Result<Ve
     Ok(out)
 }
 
-pub(super) fn basic_resample(input: &[f32], device_rate: u32) -> Vec<f32> {
+pub(super) fn basic_resample(input: &[f32], device_rate: u32, target_rate: u32) -> Vec<f32> {
     // Mirror the same safety guards as the rubato path so callers get
     // predictable behavior no matter which backend is compiled in.
     if device_rate == 0 {
@@ -178,12 +194,13 @@ pub(super) fn basic_resample(input: &[f32], device_rate: u32) -> Vec<f32> {
     }
 
     // Ratio > 1 up-samples; ratio < 1 down-samples.
-    let mut ratio = TARGET_RATE as f32 / device_rate as f32;
-    ratio = ratio.clamp(MIN_RESAMPLE_RATIO as f32, MAX_RESAMPLE_RATIO as f32);
-    let filtered = if device_rate > TARGET_RATE {
+    let (min_ratio, max_ratio) = ratio_bounds(target_rate);
+    let mut ratio = target_rate as f32 / device_rate as f32;
+    ratio = ratio.clamp(min_ratio as f32, max_ratio as f32);
+    let filtered = if device_rate > target_rate {
         // When decimating we run a small FIR low-pass to avoid aliasing.
-        let taps = downsampling_tap_count(device_rate);
-        low_pass_fir(input, device_rate, taps)
+        let taps = downsampling_tap_count(device_rate, target_rate);
+        low_pass_fir(input, device_rate, target_rate, taps)
     } else {
         input.to_vec()
     };
@@ -216,8 +233,8 @@ pub(super) fn resample_linear(input: &[f32], ratio: f32) -> Vec<f32> {
 
 /// Pick a tap count based on the downsampling ratio so the FIR remains short for
 /// near-equal sample rates and longer when we're collapsing 48 kHz into 16 kHz.
-pub(super) fn downsampling_tap_count(device_rate: u32) -> usize {
-    let decimation_ratio = device_rate as f32 / TARGET_RATE as f32;
+pub(super) fn downsampling_tap_count(device_rate: u32, target_rate: u32) -> usize {
+    let decimation_ratio = device_rate as f32 / target_rate as f32;
     let mut taps = (decimation_ratio * 4.0).ceil().max(11.0) as usize;
     if taps.is_multiple_of(2) {
         taps += 1;
@@ -228,7 +245,12 @@ pub(super) fn downsampling_tap_count(device_rate: u32) -> usize {
 /// Basic FIR low-pass that tames frequencies above the target Nyquist before we
 /// drop samples. Prevents high-frequency speech from aliasing when users have
 /// 44.1/48 kHz microphones.
-pub(super) fn low_pass_fir(input: &[f32], device_rate: u32, taps: usize) -> Vec<f32> {
+pub(super) fn low_pass_fir(
+    input: &[f32],
+    device_rate: u32,
+    target_rate: u32,
+    taps: usize,
+) -> Vec<f32> {
     if input.is_empty() {
         return input.to_vec();
     }
@@ -236,7 +258,7 @@ pub(super) fn low_pass_fir(input: &[f32], device_rate: u32, taps: usize) -> Vec<
         return input.to_vec();
     }
 
-    let normalized_cutoff = (TARGET_RATE as f32 * 0.5 / device_rate as f32).min(0.499);
+    let normalized_cutoff = (target_rate as f32 * 0.5 / device_rate as f32).min(0.499);
     let coeffs = design_low_pass(normalized_cutoff, taps);
     let half = taps / 2;
     let mut output = Vec::with_capacity(input.len());
@@ -262,12 +284,13 @@ pub(super) fn convert_frame_to_target(
     frame: Vec<f32>,
     device_rate: u32,
     target_rate: u32,
+    quality: ResampleQuality,
     desired_len: usize,
 ) -> Vec<f32> {
     if device_rate == target_rate {
         return adjust_frame_length(frame, desired_len);
     }
-    let resampled = resample_to_target_rate(&frame, device_rate);
+    let resampled = resample(&frame, device_rate, target_rate, quality);
     adjust_frame_length(resampled, desired_len)
 }
 
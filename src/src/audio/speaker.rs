@@ -0,0 +1,328 @@
+//! Voice prints for `--speaker-filter`, so other people talking nearby do not become transcripts.
+//!
+//! A voice print is the mean and spread of the mel-cepstral coefficients over
+//! the voiced frames of a recording. `--enroll-voice` saves one for the user;
+//! with `--speaker-filter`, each capture gets a print of its own and is dropped
+//! before STT when its cosine distance from the enrolled print exceeds
+//! `--speaker-threshold`. This is a lightweight spectral match, not a trained
+//! speaker model, so the threshold may need tuning per mic and room.
+
+use super::TARGET_RATE;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::f32::consts::PI;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const VOICE_PRINT_VERSION: u32 = 1;
+/// 25 ms analysis frames with a 10 ms hop at 16 kHz.
+const FRAME_LEN: usize = 400;
+const FRAME_HOP: usize = 160;
+const FFT_LEN: usize = 512;
+const MEL_BANDS: usize = 26;
+/// Cepstral coefficients kept, skipping c0 (overall loudness).
+const CEPSTRA: usize = 12;
+/// Frames quieter than the loudest by more than this are treated as pauses.
+const VOICED_RANGE_DB: f32 = 30.0;
+/// Frames below this level never count as voiced (dBFS).
+const VOICED_FLOOR_DB: f32 = -60.0;
+/// Voiced frames needed for a usable print (half a second).
+const MIN_VOICED_FRAMES: usize = 50;
+const PRE_EMPHASIS: f32 = 0.97;
+/// Status text shown instead of "No speech detected" when the speaker filter drops a capture.
+pub const SPEAKER_REJECTED_STATUS: &str = "Ignored: voice did not match your voice print";
+
+/// Spectral summary of one speaker, compared by cosine distance.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VoicePrint {
+    pub version: u32,
+    pub features: Vec<f32>,
+}
+
+impl VoicePrint {
+    /// Print for 16 kHz mono `samples`, or `None` with too little speech to judge.
+    pub fn from_samples(samples: &[f32]) -> Option<Self> {
+        let frames = voiced_frames(samples);
+        if frames.len() < MIN_VOICED_FRAMES {
+            return None;
+        }
+        let filters = mel_filterbank();
+        let window: Vec<f32> = (0..FRAME_LEN)
+            .map(|index| 0.54 - 0.46 * (2.0 * PI * index as f32 / (FRAME_LEN - 1) as f32).cos())
+            .collect();
+        let cepstra: Vec<[f32; CEPSTRA]> = frames
+            .iter()
+            .map(|frame| frame_cepstra(frame, &window, &filters))
+            .collect();
+        let count = cepstra.len() as f32;
+        let mut features = vec![0.0; CEPSTRA * 2];
+        for coeffs in &cepstra {
+            for (mean, value) in features[..CEPSTRA].iter_mut().zip(coeffs) {
+                *mean += value / count;
+            }
+        }
+        for coeffs in &cepstra {
+            for index in 0..CEPSTRA {
+                let delta = coeffs[index] - features[index];
+                features[CEPSTRA + index] += delta * delta / count;
+            }
+        }
+        for variance in &mut features[CEPSTRA..] {
+            *variance = variance.sqrt();
+        }
+        Some(Self {
+            version: VOICE_PRINT_VERSION,
+            features,
+        })
+    }
+
+    /// Cosine distance in `0.0..=2.0`; 0 means the same spectral shape.
+    pub fn distance(&self, other: &Self) -> f32 {
+        if self.features.len() != other.features.len() {
+            return 2.0;
+        }
+        let dot: f32 = self
+            .features
+            .iter()
+            .zip(&other.features)
+            .map(|(a, b)| a * b)
+            .sum();
+        let norm = |features: &[f32]| {
+            features
+                .iter()
+                .map(|value| value * value)
+                .sum::<f32>()
+                .sqrt()
+        };
+        let denom = norm(&self.features) * norm(&other.features);
+        if denom <= f32::EPSILON {
+            return 2.0;
+        }
+        (1.0 - dot / denom).clamp(0.0, 2.0)
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let raw = fs::read_to_string(path)
+            .with_context(|| format!("failed to read voice print {}", path.display()))?;
+        let print: Self = serde_json::from_str(&raw)
+            .with_context(|| format!("invalid voice print {}", path.display()))?;
+        if print.version != VOICE_PRINT_VERSION || print.features.len() != CEPSTRA * 2 {
+            bail!(
+                "voice print {} is from another version; run --enroll-voice again",
+                path.display()
+            );
+        }
+        Ok(print)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("failed to create {}", dir.display()))?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("failed to write voice print {}", path.display()))
+    }
+}
+
+/// `$XDG_CONFIG_HOME/voiceterm/voice-print.json`, else under `~/.config`.
+pub fn default_voice_print_path() -> Option<PathBuf> {
+    let base = env::var_os("XDG_CONFIG_HOME")
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            env::var_os("HOME")
+                .filter(|value| !value.is_empty())
+                .map(|home| PathBuf::from(home).join(".config"))
+        })?;
+    Some(base.join("voiceterm").join("voice-print.json"))
+}
+
+/// Analysis frames loud enough to be speech rather than pauses or room noise.
+fn voiced_frames(samples: &[f32]) -> Vec<&[f32]> {
+    if samples.len() < FRAME_LEN {
+        return Vec::new();
+    }
+    let frames: Vec<(&[f32], f32)> = (0..=samples.len() - FRAME_LEN)
+        .step_by(FRAME_HOP)
+        .map(|start| {
+            let frame = &samples[start..start + FRAME_LEN];
+            let energy = frame.iter().map(|sample| sample * sample).sum::<f32>() / FRAME_LEN as f32;
+            (frame, 10.0 * energy.max(1e-12).log10())
+        })
+        .collect();
+    let loudest = frames
+        .iter()
+        .map(|(_, db)| *db)
+        .fold(f32::NEG_INFINITY, f32::max);
+    let cutoff = (loudest - VOICED_RANGE_DB).max(VOICED_FLOOR_DB);
+    frames
+        .into_iter()
+        .filter(|(_, db)| *db >= cutoff)
+        .map(|(frame, _)| frame)
+        .collect()
+}
+
+fn frame_cepstra(frame: &[f32], window: &[f32], filters: &[Vec<f32>]) -> [f32; CEPSTRA] {
+    let mut re = vec![0.0; FFT_LEN];
+    let mut im = vec![0.0; FFT_LEN];
+    let mut previous = 0.0;
+    for (index, (&sample, weight)) in frame.iter().zip(window).enumerate() {
+        re[index] = (sample - PRE_EMPHASIS * previous) * weight;
+        previous = sample;
+    }
+    fft(&mut re, &mut im);
+    let power: Vec<f32> = (0..=FFT_LEN / 2)
+        .map(|bin| re[bin] * re[bin] + im[bin] * im[bin])
+        .collect();
+    let log_mel: Vec<f32> = filters
+        .iter()
+        .map(|filter| {
+            let energy: f32 = filter.iter().zip(&power).map(|(w, p)| w * p).sum();
+            energy.max(1e-10).ln()
+        })
+        .collect();
+    let mut cepstra = [0.0; CEPSTRA];
+    for (offset, coeff) in cepstra.iter_mut().enumerate() {
+        let order = (offset + 1) as f32;
+        *coeff = log_mel
+            .iter()
+            .enumerate()
+            .map(|(band, value)| {
+                value * (PI * order * (band as f32 + 0.5) / MEL_BANDS as f32).cos()
+            })
+            .sum();
+    }
+    cepstra
+}
+
+/// Triangular filters spaced evenly on the mel scale up to Nyquist.
+fn mel_filterbank() -> Vec<Vec<f32>> {
+    let to_mel = |hz: f32| 2595.0 * (1.0 + hz / 700.0).log10();
+    let to_hz = |mel: f32| 700.0 * (10f32.powf(mel / 2595.0) - 1.0);
+    let max_mel = to_mel(TARGET_RATE as f32 / 2.0);
+    let bins: Vec<f32> = (0..MEL_BANDS + 2)
+        .map(|index| {
+            let hz = to_hz(max_mel * index as f32 / (MEL_BANDS + 1) as f32);
+            hz * FFT_LEN as f32 / TARGET_RATE as f32
+        })
+        .collect();
+    (0..MEL_BANDS)
+        .map(|band| {
+            let (left, center, right) = (bins[band], bins[band + 1], bins[band + 2]);
+            (0..=FFT_LEN / 2)
+                .map(|bin| {
+                    let bin = bin as f32;
+                    if bin <= left || bin >= right {
+                        0.0
+                    } else if bin <= center {
+                        (bin - left) / (center - left)
+                    } else {
+                        (right - bin) / (right - center)
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// In-place iterative radix-2 FFT; the length must be a power of two.
+fn fft(re: &mut [f32], im: &mut [f32]) {
+    let len = re.len();
+    let mut target = 0;
+    for index in 1..len {
+        let mut bit = len >> 1;
+        while target & bit != 0 {
+            target ^= bit;
+            bit >>= 1;
+        }
+        target |= bit;
+        if index < target {
+            re.swap(index, target);
+            im.swap(index, target);
+        }
+    }
+    let mut size = 2;
+    while size <= len {
+        let step = -2.0 * PI / size as f32;
+        for start in (0..len).step_by(size) {
+            for offset in 0..size / 2 {
+                let (sin, cos) = (step * offset as f32).sin_cos();
+                let (a, b) = (start + offset, start + offset + size / 2);
+                let tre = re[b] * cos - im[b] * sin;
+                let tim = re[b] * sin + im[b] * cos;
+                re[b] = re[a] - tre;
+                im[b] = im[a] - tim;
+                re[a] += tre;
+                im[a] += tim;
+            }
+        }
+        size <<= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// One second of a buzzy voice-like tone: a fundamental plus decaying harmonics.
+    fn voice(fundamental: f32, harmonic_decay: f32) -> Vec<f32> {
+        (0..TARGET_RATE as usize)
+            .map(|index| {
+                let t = index as f32 / TARGET_RATE as f32;
+                (1..=12)
+                    .map(|harmonic| {
+                        let harmonic = harmonic as f32;
+                        harmonic_decay.powf(harmonic - 1.0)
+                            * (2.0 * PI * fundamental * harmonic * t).sin()
+                    })
+                    .sum::<f32>()
+                    * 0.05
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fft_finds_a_pure_tone_bin() {
+        let mut re: Vec<f32> = (0..64)
+            .map(|index| (2.0 * PI * 5.0 * index as f32 / 64.0).cos())
+            .collect();
+        let mut im = vec![0.0; 64];
+        fft(&mut re, &mut im);
+        let peak = (0..32)
+            .max_by(|a, b| re[*a].hypot(im[*a]).total_cmp(&re[*b].hypot(im[*b])))
+            .unwrap();
+        assert_eq!(peak, 5);
+    }
+
+    #[test]
+    fn same_voice_is_closer_than_a_different_one() {
+        let enrolled = VoicePrint::from_samples(&voice(120.0, 0.8)).unwrap();
+        let louder: Vec<f32> = voice(120.0, 0.8).iter().map(|s| s * 2.0).collect();
+        let same = VoicePrint::from_samples(&louder).unwrap();
+        let other = VoicePrint::from_samples(&voice(260.0, 0.4)).unwrap();
+        assert!(enrolled.distance(&same) < 0.01);
+        assert!(enrolled.distance(&other) > enrolled.distance(&same) + 0.05);
+    }
+
+    #[test]
+    fn too_little_speech_gives_no_print() {
+        assert!(VoicePrint::from_samples(&[]).is_none());
+        assert!(VoicePrint::from_samples(&vec![0.0; TARGET_RATE as usize]).is_none());
+        let short = &voice(120.0, 0.8)[..FRAME_HOP * 20];
+        assert!(VoicePrint::from_samples(short).is_none());
+    }
+
+    #[test]
+    fn prints_round_trip_through_disk() {
+        let dir = std::env::temp_dir().join(format!("voiceterm-print-{}", std::process::id()));
+        let path = dir.join("nested").join("voice-print.json");
+        let print = VoicePrint::from_samples(&voice(150.0, 0.7)).unwrap();
+        print.save(&path).unwrap();
+        assert_eq!(VoicePrint::load(&path).unwrap(), print);
+        fs::write(&path, r#"{"version":1,"features":[1.0]}"#).unwrap();
+        assert!(VoicePrint::load(&path).is_err());
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -2,15 +2,22 @@
 
 use super::capture::{CaptureState, FrameAccumulator};
 use super::dispatch::{append_downmixed_samples, FrameDispatcher};
+use super::echo::{EchoCanceller, EchoStage};
+use super::meter::rms_db;
+use super::mute::{is_digital_silence, MUTE_DETECT_MS};
+use super::preroll::LiveInput;
 use super::resample::{
     adjust_frame_length, basic_resample, convert_frame_to_target, design_low_pass,
-    downsampling_tap_count, low_pass_fir, resample_linear, resample_to_target_rate,
+    downsampling_tap_count, low_pass_fir, resample, resample_linear, resample_to_target_rate,
     MAX_DEVICE_RATE, MAX_RESAMPLE_RATIO, MIN_DEVICE_RATE, MIN_RESAMPLE_RATIO,
 };
 use super::vad::{FrameLabel, VadSmoother};
 use super::{
-    Recorder, SimpleThresholdVad, StopReason, VadConfig, VadDecision, VadEngine, TARGET_RATE,
+    offline_capture_from_pcm, preprocessor_for_config, AudioPreprocessor, CaptureMetrics,
+    NoiseSuppressor, Recorder, SimpleThresholdVad, StopReason, VadConfig, VadDecision, VadEngine,
+    TARGET_RATE,
 };
+use crate::config::{EchoMode, ResampleQuality};
 use crossbeam_channel::bounded;
 use std::f32::consts::PI;
 use std::sync::atomic::{AtomicUsize, Ordering};
@@ -131,7 +138,8 @@ fn rubato_accepts_valid_rate_without_forced_error() {
     let _guard = lock_resample_test();
     FORCE_RUBATO_ERROR.store(false, Ordering::Relaxed);
     let input: Vec<f32> = (0..256).map(|i| (i as f32 * 0.03).sin()).collect();
-    let output = resample_with_rubato(&input, 48_000).expect("expected rubato success");
+    let output =
+        resample_with_rubato(&input, 48_000, TARGET_RATE).expect("expected rubato success");
     let ratio = TARGET_RATE as f64 / 48_000f64;
     let expected = ((input.len() as f64) * ratio).round() as usize + 8;
     assert_eq!(output.len(), expected);
@@ -144,14 +152,14 @@ fn rubato_rejects_out_of_bounds_rates() {
     let input = vec![0.1f32; 64];
 
     FORCE_RUBATO_ERROR.store(true, Ordering::Relaxed);
-    let err = resample_with_rubato(&input, MIN_DEVICE_RATE - 1)
+    let err = resample_with_rubato(&input, MIN_DEVICE_RATE - 1, TARGET_RATE)
         .expect_err("expected error for low device rate");
     assert!(err.to_string().contains("unsupported device sample rate"));
     assert!(FORCE_RUBATO_ERROR.load(Ordering::Relaxed));
     FORCE_RUBATO_ERROR.store(false, Ordering::Relaxed);
 
     FORCE_RUBATO_ERROR.store(true, Ordering::Relaxed);
-    let err = resample_with_rubato(&input, MAX_DEVICE_RATE + 1)
+    let err = resample_with_rubato(&input, MAX_DEVICE_RATE + 1, TARGET_RATE)
         .expect_err("expected error for high device rate");
     assert!(err.to_string().contains("unsupported device sample rate"));
     assert!(FORCE_RUBATO_ERROR.load(Ordering::Relaxed));
@@ -165,14 +173,14 @@ fn rubato_accepts_boundary_rates() {
     let input = vec![0.1f32; 64];
 
     FORCE_RUBATO_ERROR.store(true, Ordering::Relaxed);
-    let err =
-        resample_with_rubato(&input, MIN_DEVICE_RATE).expect_err("expected forced rubato error");
+    let err = resample_with_rubato(&input, MIN_DEVICE_RATE, TARGET_RATE)
+        .expect_err("expected forced rubato error");
     assert!(err.to_string().contains("forced rubato error"));
     assert!(!FORCE_RUBATO_ERROR.load(Ordering::Relaxed));
 
     FORCE_RUBATO_ERROR.store(true, Ordering::Relaxed);
-    let err =
-        resample_with_rubato(&input, MAX_DEVICE_RATE).expect_err("expected forced rubato error");
+    let err = resample_with_rubato(&input, MAX_DEVICE_RATE, TARGET_RATE)
+        .expect_err("expected forced rubato error");
     assert!(err.to_string().contains("forced rubato error"));
     assert!(!FORCE_RUBATO_ERROR.load(Ordering::Relaxed));
 }
@@ -413,12 +421,69 @@ fn frame_accumulator_trims_zero_length_silence() {
     assert_eq!(acc.frames.back().unwrap().samples.len(), 1);
 }
 
+#[test]
+fn frame_accumulator_preroll_is_sample_accurate() {
+    let mut acc = FrameAccumulator::for_testing(usize::MAX, 3);
+    acc.push_frame(vec![0.0; 4], FrameLabel::Silence);
+    acc.push_frame(vec![0.0; 4], FrameLabel::Silence);
+    acc.push_frame(vec![0.0, 0.0, 1.0, 1.0], FrameLabel::Speech);
+
+    assert_eq!(acc.speech_onset_sample(), Some(10));
+    assert_eq!(
+        acc.apply_preroll(&StopReason::VadSilence { tail_ms: 0 }),
+        Some(3)
+    );
+    let audio = acc.into_audio(&StopReason::VadSilence { tail_ms: 0 });
+    assert_eq!(audio, vec![0.0, 0.0, 0.0, 1.0, 1.0]);
+}
+
+#[test]
+fn frame_accumulator_onset_search_covers_smoothing_delay() {
+    let mut acc = FrameAccumulator::for_testing(usize::MAX, 1);
+    acc.onset_search_samples = 4;
+    acc.push_frame(vec![0.0, 0.0, 0.5, 0.5], FrameLabel::Silence);
+    acc.push_frame(vec![1.0; 4], FrameLabel::Speech);
+
+    assert_eq!(acc.speech_onset_sample(), Some(2));
+    assert_eq!(
+        acc.apply_preroll(&StopReason::VadSilence { tail_ms: 0 }),
+        Some(1)
+    );
+    assert_eq!(acc.total_samples, 7);
+    assert_eq!(acc.frames.front().unwrap().samples, vec![0.0, 0.5, 0.5]);
+}
+
+#[test]
+fn frame_accumulator_preroll_keeps_leading_audio_on_manual_stop() {
+    let mut acc = FrameAccumulator::for_testing(usize::MAX, 2);
+    acc.push_frame(vec![0.0; 6], FrameLabel::Silence);
+    acc.push_frame(vec![1.0; 2], FrameLabel::Speech);
+
+    assert_eq!(acc.apply_preroll(&StopReason::ManualStop), Some(6));
+    assert_eq!(acc.into_audio(&StopReason::ManualStop).len(), 8);
+}
+
+#[test]
+fn frame_accumulator_preroll_needs_speech() {
+    let mut acc = FrameAccumulator::for_testing(usize::MAX, 2);
+    acc.push_frame(vec![0.0; 6], FrameLabel::Silence);
+
+    assert_eq!(acc.speech_onset_sample(), None);
+    assert_eq!(
+        acc.apply_preroll(&StopReason::VadSilence { tail_ms: 60 }),
+        None
+    );
+    assert_eq!(acc.total_samples, 6);
+}
+
 #[test]
 fn vad_config_from_pipeline_config_maps_fields() {
     let cfg = crate::config::VoicePipelineConfig {
         sample_rate: 12_345,
+        resample_quality: crate::config::ResampleQuality::Fast,
         max_capture_ms: 9_999,
         silence_tail_ms: 321,
+        silence_tail_adaptive: true,
         min_speech_ms_before_stt_start: 111,
         lookback_ms: 222,
         buffer_ms: 333,
@@ -429,9 +494,13 @@ fn vad_config_from_pipeline_config_maps_fields() {
         vad_smoothing_frames: 3,
         python_fallback_allowed: true,
         vad_engine: crate::config::VadEngineKind::Simple,
+        denoise: true,
+        echo_mode: crate::config::EchoMode::Cancel,
+        echo_reference_device: Some("Monitor of Speakers".to_string()),
     };
     let vad = VadConfig::from(&cfg);
     assert_eq!(vad.sample_rate, cfg.sample_rate);
+    assert_eq!(vad.resample_quality, cfg.resample_quality);
     assert_eq!(vad.frame_ms, cfg.vad_frame_ms);
     assert_eq!(vad.silence_threshold_db, cfg.vad_threshold_db);
     assert_eq!(vad.silence_duration_ms, cfg.silence_tail_ms);
@@ -444,6 +513,10 @@ fn vad_config_from_pipeline_config_maps_fields() {
     assert_eq!(vad.buffer_ms, cfg.buffer_ms);
     assert_eq!(vad.channel_capacity, cfg.channel_capacity);
     assert_eq!(vad.smoothing_frames, cfg.vad_smoothing_frames);
+    assert!(vad.denoise);
+    assert!(vad.adaptive_silence_tail);
+    assert_eq!(vad.echo_mode, cfg.echo_mode);
+    assert_eq!(vad.echo_reference_device, cfg.echo_reference_device);
 }
 
 #[test]
@@ -618,6 +691,48 @@ fn capture_state_metrics_track_speech_and_silence() {
     assert_eq!(state.silence_tail_ms(), 20);
 }
 
+#[test]
+fn capture_state_voiced_span_runs_from_onset_to_last_speech() {
+    let cfg = VadConfig {
+        max_recording_duration_ms: 10_000,
+        min_recording_duration_ms: 0,
+        silence_duration_ms: 1000,
+        ..Default::default()
+    };
+    let mut state = CaptureState::for_testing(&cfg, 20);
+    assert_eq!(state.voiced_span_ms(), 0);
+    for label in [
+        FrameLabel::Silence,
+        FrameLabel::Speech,
+        FrameLabel::Silence,
+        FrameLabel::Silence,
+        FrameLabel::Speech,
+        FrameLabel::Silence,
+    ] {
+        assert!(state.on_frame(label).is_none());
+    }
+    assert_eq!(state.speech_ms(), 40);
+    assert_eq!(state.voiced_span_ms(), 80);
+}
+
+#[test]
+fn capture_metrics_gate_short_and_sparse_captures() {
+    let metrics = |speech_ms, voiced_span_ms| CaptureMetrics {
+        speech_ms,
+        voiced_span_ms,
+        ..Default::default()
+    };
+    let cough = metrics(120, 120);
+    assert!(cough.is_utterance(0, 0.0));
+    assert!(!cough.is_utterance(300, 0.0));
+    let squeaks = metrics(400, 2000);
+    assert_eq!(squeaks.voiced_ratio(), Some(0.2));
+    assert!(squeaks.is_utterance(300, 0.0));
+    assert!(!squeaks.is_utterance(300, 0.5));
+    assert!(metrics(1500, 2000).is_utterance(300, 0.5));
+    assert_eq!(metrics(0, 0).voiced_ratio(), None);
+}
+
 #[test]
 fn capture_state_requires_min_speech_before_silence_stop() {
     let cfg = VadConfig {
@@ -633,6 +748,54 @@ fn capture_state_requires_min_speech_before_silence_stop() {
     assert!(matches!(reason, Some(StopReason::VadSilence { .. })));
 }
 
+#[test]
+fn capture_state_adaptive_tail_waits_longer_when_speech_rises_into_a_pause() {
+    let cfg = VadConfig {
+        min_recording_duration_ms: 0,
+        silence_duration_ms: 100,
+        adaptive_silence_tail: true,
+        ..Default::default()
+    };
+    let rising: Vec<Vec<f32>> = (0..15).map(|i| vec![0.01 * (i + 1) as f32; 320]).collect();
+    let mut state = CaptureState::for_testing(&cfg, 20);
+    for frame in &rising {
+        assert!(state.on_frame_samples(FrameLabel::Speech, frame).is_none());
+    }
+    for _ in 0..7 {
+        assert!(state.on_frame(FrameLabel::Silence).is_none());
+    }
+    assert_eq!(state.required_silence_ms(), 160);
+    let reason = state.on_frame(FrameLabel::Silence);
+    assert!(matches!(
+        reason,
+        Some(StopReason::VadSilence { tail_ms: 160 })
+    ));
+}
+
+#[test]
+fn capture_state_adaptive_tail_stops_sooner_after_a_sentence_final_drop() {
+    let cfg = VadConfig {
+        min_recording_duration_ms: 0,
+        silence_duration_ms: 500,
+        adaptive_silence_tail: true,
+        ..Default::default()
+    };
+    let mut state = CaptureState::for_testing(&cfg, 20);
+    for i in 0..15 {
+        let frame = vec![0.3 / (i + 1) as f32; 320];
+        assert!(state.on_frame_samples(FrameLabel::Speech, &frame).is_none());
+    }
+    for _ in 0..14 {
+        assert!(state.on_frame(FrameLabel::Silence).is_none());
+    }
+    assert_eq!(state.required_silence_ms(), 300);
+    let reason = state.on_frame(FrameLabel::Silence);
+    assert!(matches!(
+        reason,
+        Some(StopReason::VadSilence { tail_ms: 300 })
+    ));
+}
+
 #[test]
 fn capture_state_manual_stop_sets_reason() {
     let cfg = VadConfig::default();
@@ -730,6 +893,33 @@ fn offline_capture_pads_partial_frame() {
     assert!(result.audio[15..].iter().all(|sample| *sample == 0.0));
 }
 
+#[test]
+fn offline_capture_aligns_preroll_to_speech_onset() {
+    let cfg = VadConfig {
+        sample_rate: 1000,
+        frame_ms: 10,
+        max_recording_duration_ms: 1000,
+        silence_duration_ms: 20,
+        min_recording_duration_ms: 0,
+        lookback_ms: 5,
+        smoothing_frames: 1,
+        ..VadConfig::default()
+    };
+    let mut samples = vec![0.0f32; 30];
+    samples.extend(vec![0.5f32; 20]);
+    samples.extend(vec![0.0f32; 30]);
+    let mut vad = SimpleThresholdVad::new(-20.0);
+    let result = offline_capture_from_pcm(&samples, &cfg, &mut vad);
+    assert!(matches!(
+        result.metrics.early_stop_reason,
+        StopReason::VadSilence { .. }
+    ));
+    assert_eq!(result.metrics.preroll_ms, 5);
+    assert_eq!(result.audio.len(), 30);
+    assert!(result.audio[..5].iter().all(|sample| *sample == 0.0));
+    assert!(result.audio[5..25].iter().all(|sample| *sample == 0.5));
+}
+
 #[test]
 fn append_downmixed_samples_handles_partial_frame() {
     let mut buf = Vec::new();
@@ -756,28 +946,28 @@ fn resample_linear_interpolates_expected_values() {
 #[test]
 fn basic_resample_returns_identity_for_target_rate() {
     let input = vec![0.2f32, -0.2, 0.4];
-    let output = basic_resample(&input, TARGET_RATE);
+    let output = basic_resample(&input, TARGET_RATE, TARGET_RATE);
     assert_eq!(output, input);
 }
 
 #[test]
 fn basic_resample_rejects_out_of_bounds_rates() {
     let input = vec![0.2f32; 32];
-    let low = basic_resample(&input, MIN_DEVICE_RATE - 1);
+    let low = basic_resample(&input, MIN_DEVICE_RATE - 1, TARGET_RATE);
     assert_eq!(low, input);
-    let high = basic_resample(&input, MAX_DEVICE_RATE + 1);
+    let high = basic_resample(&input, MAX_DEVICE_RATE + 1, TARGET_RATE);
     assert_eq!(high, input);
 }
 
 #[test]
 fn basic_resample_accepts_boundary_rates() {
     let input = vec![0.2f32; 100];
-    let low = basic_resample(&input, MIN_DEVICE_RATE);
+    let low = basic_resample(&input, MIN_DEVICE_RATE, TARGET_RATE);
     let expected_low =
         (input.len() as f32 * (TARGET_RATE as f32 / MIN_DEVICE_RATE as f32)).round() as usize;
     assert_eq!(low.len(), expected_low);
 
-    let high = basic_resample(&input, MAX_DEVICE_RATE);
+    let high = basic_resample(&input, MAX_DEVICE_RATE, TARGET_RATE);
     let expected_high =
         (input.len() as f32 * (TARGET_RATE as f32 / MAX_DEVICE_RATE as f32)).round() as usize;
     assert_eq!(high.len(), expected_high);
@@ -788,7 +978,7 @@ fn basic_resample_upsample_matches_linear() {
     let input = vec![0.0f32, 1.0, 0.0, -1.0, 0.5, -0.5, 0.25, -0.25];
     let ratio = TARGET_RATE as f32 / 8_000f32;
     let expected = resample_linear(&input, ratio);
-    let output = basic_resample(&input, 8_000);
+    let output = basic_resample(&input, 8_000, TARGET_RATE);
     assert_eq!(output, expected);
 }
 
@@ -799,7 +989,7 @@ fn basic_resample_downsample_filters_high_freq() {
         .collect();
     let ratio = TARGET_RATE as f32 / 48_000f32;
     let naive = resample_linear(&input, ratio);
-    let output = basic_resample(&input, 48_000);
+    let output = basic_resample(&input, 48_000, TARGET_RATE);
     assert_eq!(output.len(), naive.len());
     let max_diff = output
         .iter()
@@ -811,8 +1001,8 @@ fn basic_resample_downsample_filters_high_freq() {
 
 #[test]
 fn downsampling_tap_count_is_odd_and_scaled() {
-    assert_eq!(downsampling_tap_count(16_000), 11);
-    assert_eq!(downsampling_tap_count(48_000), 13);
+    assert_eq!(downsampling_tap_count(16_000, TARGET_RATE), 11);
+    assert_eq!(downsampling_tap_count(48_000, TARGET_RATE), 13);
 }
 
 #[test]
@@ -826,7 +1016,7 @@ fn design_low_pass_coeffs_are_normalized() {
 #[test]
 fn low_pass_fir_preserves_dc_component() {
     let input = vec![1.0f32; 64];
-    let output = low_pass_fir(&input, 48_000, 11);
+    let output = low_pass_fir(&input, 48_000, TARGET_RATE, 11);
     let avg: f32 = output.iter().sum::<f32>() / output.len() as f32;
     assert!(avg > 0.8 && avg < 1.2);
 }
@@ -866,6 +1056,30 @@ fn frame_dispatcher_accumulates_partial_frames() {
     assert_eq!(frame, vec![1.0, 2.0, 3.0]);
 }
 
+#[test]
+fn live_input_buffers_preroll_then_feeds_the_capture() {
+    let mut live = LiveInput::new(3);
+    // Stereo input is downmixed, and only the newest `capacity` samples are kept.
+    live.push(&[1.0f32, 1.0, 2.0, 2.0, 3.0, 3.0, 4.0, 4.0], 2, |sample| {
+        sample
+    });
+    assert_eq!(live.buffered(), 3);
+
+    let (tx, rx) = bounded::<Vec<f32>>(4);
+    let dropped = Arc::new(AtomicUsize::new(0));
+    let preroll = live.attach(FrameDispatcher::new(2, tx, dropped));
+    assert_eq!(preroll, vec![2.0, 3.0, 4.0]);
+
+    live.push(&[5.0f32, 6.0], 1, |sample| sample);
+    assert_eq!(rx.try_recv().expect("capture frame"), vec![5.0, 6.0]);
+    assert_eq!(live.buffered(), 0, "attached input bypasses the ring");
+
+    live.detach();
+    live.push(&[7.0f32], 1, |sample| sample);
+    assert_eq!(live.buffered(), 1);
+    assert!(rx.try_recv().is_err(), "detached capture gets nothing");
+}
+
 #[test]
 fn adjust_frame_length_truncates_and_pads() {
     let data = vec![0.1f32, 0.2, 0.3];
@@ -880,10 +1094,39 @@ fn adjust_frame_length_truncates_and_pads() {
 #[test]
 fn convert_frame_to_target_skips_resample_when_rates_match() {
     let frame = vec![0.1f32, 0.2, 0.3, 0.4];
-    let output = convert_frame_to_target(frame.clone(), 8_000, 8_000, frame.len());
+    let output = convert_frame_to_target(
+        frame.clone(),
+        8_000,
+        8_000,
+        ResampleQuality::High,
+        frame.len(),
+    );
     assert_eq!(output, frame);
 }
 
+#[test]
+fn fast_resample_converts_to_rates_other_than_whisper() {
+    let input = vec![0.25f32; 960];
+    let down = resample(&input, 48_000, 8_000, ResampleQuality::Fast);
+    assert_eq!(down.len(), 160);
+    assert!(down[4..156]
+        .iter()
+        .all(|sample| (sample - 0.25).abs() < 1e-3));
+    assert_eq!(
+        resample(&input, 44_100, 48_000, ResampleQuality::Fast).len(),
+        1_045
+    );
+    // A 48 kHz device frame becomes a whole 8 kHz frame rather than a truncated 16 kHz one.
+    let frame: Vec<f32> = (0..960).map(|i| i as f32 / 960.0).collect();
+    let converted = convert_frame_to_target(frame, 48_000, 8_000, ResampleQuality::Fast, 160);
+    assert_eq!(converted.len(), 160);
+    assert!(
+        converted[150] > 0.9,
+        "frame end missing: {}",
+        converted[150]
+    );
+}
+
 #[test]
 fn resample_linear_downsamples_midpoints() {
     let input = vec![0.0f32, 2.0, 4.0, 6.0];
@@ -912,7 +1155,7 @@ fn resample_to_target_rate_keeps_non_empty() {
 #[test]
 fn basic_resample_downsamples_constant_signal() {
     let input = vec![1.0f32; 48];
-    let output = basic_resample(&input, 48_000);
+    let output = basic_resample(&input, 48_000, TARGET_RATE);
     assert_eq!(output.len(), 16);
     let min = output.iter().copied().fold(f32::INFINITY, f32::min);
     let max = output.iter().copied().fold(f32::NEG_INFINITY, f32::max);
@@ -922,7 +1165,7 @@ fn basic_resample_downsamples_constant_signal() {
 #[test]
 fn basic_resample_upsamples_constant_signal() {
     let input = vec![1.0f32; 16];
-    let output = basic_resample(&input, 8_000);
+    let output = basic_resample(&input, 8_000, TARGET_RATE);
     assert_eq!(output.len(), 32);
     let min = output.iter().copied().fold(f32::INFINITY, f32::min);
     let max = output.iter().copied().fold(f32::NEG_INFINITY, f32::max);
@@ -931,7 +1174,7 @@ fn basic_resample_upsamples_constant_signal() {
 
 #[test]
 fn downsampling_tap_count_scales_for_large_rate() {
-    assert_eq!(downsampling_tap_count(96_000), 25);
+    assert_eq!(downsampling_tap_count(96_000, TARGET_RATE), 25);
 }
 
 #[test]
@@ -957,7 +1200,7 @@ fn low_pass_fir_matches_reference_impulse() {
     let cutoff = (TARGET_RATE as f32 * 0.5 / device_rate as f32).min(0.499);
     let coeffs = reference_low_pass(cutoff, taps);
     let input = vec![1.0f32, 0.0, 0.0, 0.0, 0.0];
-    let output = low_pass_fir(&input, device_rate, taps);
+    let output = low_pass_fir(&input, device_rate, TARGET_RATE, taps);
 
     let half = taps / 2;
     let mut expected = Vec::with_capacity(input.len());
@@ -981,6 +1224,209 @@ fn low_pass_fir_matches_reference_impulse() {
 #[test]
 fn low_pass_fir_returns_input_for_short_taps() {
     let input = vec![0.2f32, -0.1];
-    let output = low_pass_fir(&input, 48_000, 1);
+    let output = low_pass_fir(&input, 48_000, TARGET_RATE, 1);
     assert_eq!(output, input);
 }
+
+#[test]
+fn preprocessor_for_config_respects_denoise_flag() {
+    let cfg = VadConfig::default();
+    assert!(preprocessor_for_config(&cfg).is_none());
+    let cfg = VadConfig {
+        denoise: true,
+        ..VadConfig::default()
+    };
+    let stage = preprocessor_for_config(&cfg).expect("denoise should build a stage");
+    assert_eq!(stage.name(), "noise_suppressor");
+}
+
+#[test]
+fn noise_suppressor_attenuates_stationary_noise() {
+    let mut suppressor = NoiseSuppressor::new(SAMPLE_RATE);
+    let mut last_rms = 0.0f32;
+    for frame_idx in 0..50 {
+        let mut frame: Vec<f32> = (0..320)
+            .map(|n| {
+                let t = (frame_idx * 320 + n) as f32 / SAMPLE_RATE as f32;
+                (2.0 * PI * 1_000.0 * t).sin() * 0.01
+            })
+            .collect();
+        suppressor.process_frame(&mut frame);
+        last_rms = (frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32).sqrt();
+    }
+    let input_rms = 0.01 / 2f32.sqrt();
+    assert!(last_rms < input_rms * 0.5, "noise should be attenuated");
+    assert!(suppressor.noise_floor_db().is_some());
+}
+
+#[test]
+fn noise_suppressor_passes_speech_above_noise_floor() {
+    let mut suppressor = NoiseSuppressor::new(SAMPLE_RATE);
+    for _ in 0..20 {
+        let mut quiet = vec![0.001f32; 320];
+        for (n, sample) in quiet.iter_mut().enumerate() {
+            *sample *= if n % 2 == 0 { 1.0 } else { -1.0 };
+        }
+        suppressor.process_frame(&mut quiet);
+    }
+    let mut last_rms = 0.0f32;
+    for frame_idx in 0..5 {
+        let mut loud: Vec<f32> = (0..320)
+            .map(|n| {
+                let t = (frame_idx * 320 + n) as f32 / SAMPLE_RATE as f32;
+                (2.0 * PI * 1_000.0 * t).sin() * 0.3
+            })
+            .collect();
+        suppressor.process_frame(&mut loud);
+        last_rms = (loud.iter().map(|s| s * s).sum::<f32>() / loud.len() as f32).sqrt();
+    }
+    let input_rms = 0.3 / 2f32.sqrt();
+    assert!(last_rms > input_rms * 0.8, "speech should pass through");
+}
+
+#[test]
+fn noise_suppressor_reset_clears_noise_floor() {
+    let mut suppressor = NoiseSuppressor::new(SAMPLE_RATE);
+    let mut frame = vec![0.05f32; 320];
+    suppressor.process_frame(&mut frame);
+    assert!(suppressor.noise_floor_db().is_some());
+    suppressor.reset();
+    assert!(suppressor.noise_floor_db().is_none());
+}
+
+#[test]
+fn offline_capture_applies_denoise_stage() {
+    let samples = vec![0.2f32; SAMPLE_RATE as usize / 10];
+    let cfg = VadConfig {
+        denoise: true,
+        min_recording_duration_ms: 0,
+        ..VadConfig::default()
+    };
+    let mut vad = SimpleThresholdVad::new(-120.0);
+    let result = offline_capture_from_pcm(&samples, &cfg, &mut vad);
+    assert!(!result.audio.is_empty());
+    // The high-pass stage removes the DC offset, so output must not match input.
+    assert!(result
+        .audio
+        .iter()
+        .any(|sample| (sample - 0.2).abs() > 1e-3));
+}
+
+#[test]
+fn digital_silence_detects_zeros_and_flat_frames() {
+    assert!(is_digital_silence(&[0.0; 160]));
+    assert!(is_digital_silence(&[0.25; 160]), "flat DC offset");
+    assert!(!is_digital_silence(&[]));
+    // Quiet room tone is still analog noise, not a muted device.
+    let room_tone: Vec<f32> = (0..160).map(|n| (n as f32 * 0.7).sin() * 0.001).collect();
+    assert!(!is_digital_silence(&room_tone));
+}
+
+#[test]
+fn echo_stage_mutes_while_playing_and_through_the_reference_hangover() {
+    let voice = vec![0.2; 320];
+    let loud_reference = vec![0.1; 320];
+    let mut off = EchoStage::new(EchoMode::Off, SAMPLE_RATE, 20);
+    let mut frame = voice.clone();
+    off.process_frame(&mut frame, Some(&loud_reference), true);
+    assert_eq!(frame, voice);
+
+    let mut stage = EchoStage::new(EchoMode::Mute, SAMPLE_RATE, 20);
+    let mut frame = voice.clone();
+    stage.process_frame(&mut frame, None, false);
+    assert_eq!(frame, voice);
+    stage.process_frame(&mut frame, None, true);
+    assert!(frame.iter().all(|sample| *sample == 0.0));
+
+    stage.process_frame(&mut voice.clone(), Some(&loud_reference), false);
+    // 250 ms of hangover at 20 ms frames keeps the next 13 quiet frames muted.
+    let mut muted_frames = 0;
+    loop {
+        let mut frame = voice.clone();
+        stage.process_frame(&mut frame, Some(&[0.0; 320]), false);
+        if frame == voice {
+            break;
+        }
+        muted_frames += 1;
+    }
+    assert_eq!(muted_frames, 13);
+    assert_eq!(stage.muted_ms(), (2 + 13) * 20);
+
+    // Cancelling without a reference falls back to muting.
+    let mut cancel = EchoStage::new(EchoMode::Cancel, SAMPLE_RATE, 20);
+    let mut frame = voice.clone();
+    cancel.process_frame(&mut frame, None, true);
+    assert!(frame.iter().all(|sample| *sample == 0.0));
+}
+
+#[test]
+fn echo_canceller_learns_the_echo_path_and_keeps_near_end_speech() {
+    const RATE: u32 = 8_000;
+    const DELAY: usize = 40;
+    let mut seed = 0x2545_f491u32;
+    let reference: Vec<f32> = (0..RATE as usize * 4)
+        .map(|_| {
+            seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            (seed >> 8) as f32 / (1u32 << 24) as f32 - 0.5
+        })
+        .collect();
+    let echo: Vec<f32> = (0..reference.len())
+        .map(|index| {
+            index
+                .checked_sub(DELAY)
+                .map_or(0.0, |past| 0.3 * reference[past])
+        })
+        .collect();
+
+    let mut canceller = EchoCanceller::new(RATE);
+    let mut residual = Vec::with_capacity(echo.len());
+    for (mic, reference) in echo.chunks(160).zip(reference.chunks(160)) {
+        let mut frame = mic.to_vec();
+        canceller.process(&mut frame, reference);
+        residual.extend(frame);
+    }
+    let tail = RATE as usize / 2;
+    let echo_db = rms_db(&echo[echo.len() - tail..]);
+    let residual_db = rms_db(&residual[residual.len() - tail..]);
+    assert!(
+        residual_db < echo_db - 20.0,
+        "echo {echo_db:.1} dB, residual {residual_db:.1} dB"
+    );
+
+    // With the speakers silent, the user's voice passes through untouched.
+    let voice: Vec<f32> = (0..160).map(|n| 0.2 * (n as f32 * 0.3).sin()).collect();
+    let mut quiet = EchoCanceller::new(RATE);
+    let mut frame = voice.clone();
+    quiet.process(&mut frame, &[]);
+    assert_eq!(frame, voice);
+}
+
+#[test]
+fn offline_capture_flags_muted_mic() {
+    let cfg = VadConfig {
+        max_recording_duration_ms: MUTE_DETECT_MS + 500,
+        silence_duration_ms: MUTE_DETECT_MS + 1000,
+        ..VadConfig::default()
+    };
+    let samples =
+        vec![0.0f32; (SAMPLE_RATE as u64 * cfg.max_recording_duration_ms / 1000) as usize];
+    let mut vad = SimpleThresholdVad::new(-55.0);
+    let result = offline_capture_from_pcm(&samples, &cfg, &mut vad);
+    assert!(result.metrics.digital_silence_ms >= MUTE_DETECT_MS);
+    assert!(result.metrics.mic_appears_muted());
+}
+
+#[test]
+fn offline_capture_quiet_room_is_not_muted() {
+    let cfg = VadConfig {
+        max_recording_duration_ms: MUTE_DETECT_MS + 500,
+        silence_duration_ms: MUTE_DETECT_MS + 1000,
+        ..VadConfig::default()
+    };
+    let total = (SAMPLE_RATE as u64 * cfg.max_recording_duration_ms / 1000) as usize;
+    let samples: Vec<f32> = (0..total).map(|n| (n as f32 * 0.7).sin() * 0.001).collect();
+    let mut vad = SimpleThresholdVad::new(-55.0);
+    let result = offline_capture_from_pcm(&samples, &cfg, &mut vad);
+    assert_eq!(result.metrics.digital_silence_ms, 0);
+    assert!(!result.metrics.mic_appears_muted());
+}
@@ -4,7 +4,7 @@
 //! Used to automatically stop recording after a period of silence.
 
 use super::TARGET_RATE;
-use crate::config::VoicePipelineConfig;
+use crate::config::{EchoMode, ResampleQuality, VoicePipelineConfig};
 use std::cmp::Ordering as CmpOrdering;
 use std::collections::VecDeque;
 
@@ -13,6 +13,8 @@ use std::collections::VecDeque;
 pub struct VadConfig {
     /// Input sample rate in Hz.
     pub sample_rate: u32,
+    /// Converter used when the device rate differs from `sample_rate`.
+    pub resample_quality: ResampleQuality,
     /// Frame size in milliseconds.
     pub frame_ms: u64,
     /// Threshold (dB) below which audio is considered silence.
@@ -31,12 +33,21 @@ pub struct VadConfig {
     pub channel_capacity: usize,
     /// Number of frames to smooth over.
     pub smoothing_frames: usize,
+    /// Run the noise-suppression preprocessor before VAD classification.
+    pub denoise: bool,
+    /// Stretch or shorten the silence tail from the energy trend before each pause.
+    pub adaptive_silence_tail: bool,
+    /// Keep playback picked up by the mic out of the capture.
+    pub echo_mode: EchoMode,
+    /// Loopback input opened next to the mic as the echo reference.
+    pub echo_reference_device: Option<String>,
 }
 
 impl Default for VadConfig {
     fn default() -> Self {
         Self {
             sample_rate: TARGET_RATE,
+            resample_quality: ResampleQuality::High,
             frame_ms: 20,
             silence_threshold_db: -55.0,
             silence_duration_ms: 500,
@@ -46,6 +57,10 @@ impl Default for VadConfig {
             buffer_ms: 10_000,
             channel_capacity: 64,
             smoothing_frames: 3,
+            denoise: false,
+            adaptive_silence_tail: false,
+            echo_mode: EchoMode::Off,
+            echo_reference_device: None,
         }
     }
 }
@@ -54,6 +69,7 @@ impl From<&VoicePipelineConfig> for VadConfig {
     fn from(cfg: &VoicePipelineConfig) -> Self {
         Self {
             sample_rate: cfg.sample_rate,
+            resample_quality: cfg.resample_quality,
             frame_ms: cfg.vad_frame_ms,
             silence_threshold_db: cfg.vad_threshold_db,
             silence_duration_ms: cfg.silence_tail_ms,
@@ -63,6 +79,10 @@ impl From<&VoicePipelineConfig> for VadConfig {
             buffer_ms: cfg.buffer_ms,
             channel_capacity: cfg.channel_capacity,
             smoothing_frames: cfg.vad_smoothing_frames,
+            denoise: cfg.denoise,
+            adaptive_silence_tail: cfg.silence_tail_adaptive,
+            echo_mode: cfg.echo_mode,
+            echo_reference_device: cfg.echo_reference_device.clone(),
         }
     }
 }
@@ -161,6 +181,11 @@ impl VadSmoother {
     }
 }
 
+/// Linear amplitude for a dBFS level (the inverse of the RMS dB used by `SimpleThresholdVad`).
+pub(super) fn db_to_amplitude(db: f32) -> f32 {
+    10f32.powf(db / 20.0)
+}
+
 /// Lightweight fallback VAD that operates on RMS energy. Used when Earshot is
 /// disabled or unavailable.
 #[derive(Debug, Clone)]
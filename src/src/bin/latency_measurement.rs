@@ -127,7 +127,8 @@ fn collect_real_measurements(args: &Args, config: &AppConfig) -> Result<Vec<Late
     let recorder = Arc::new(Mutex::new(recorder));
 
     let transcriber = if let Some(model_path) = &config.whisper_model_path {
-        let t = stt::Transcriber::new(model_path).context("failed to load Whisper model")?;
+        let t = stt::Transcriber::with_device(model_path, config.stt_device)
+            .context("failed to load Whisper model")?;
         Some(Arc::new(Mutex::new(t)))
     } else {
         eprintln!("Warning: No Whisper model configured, using Python fallback");
@@ -171,7 +172,8 @@ fn collect_synthetic_measurements(
     let mut measurements = Vec::with_capacity(args.count);
 
     let transcriber = if let Some(model_path) = &config.whisper_model_path {
-        let t = stt::Transcriber::new(model_path).context("failed to load Whisper model")?;
+        let t = stt::Transcriber::with_device(model_path, config.stt_device)
+            .context("failed to load Whisper model")?;
         Some(Arc::new(Mutex::new(t)))
     } else {
         eprintln!("Warning: No Whisper model configured, using Python fallback");
@@ -1,30 +1,63 @@
-//! Synthetic benchmark binary that tracks voice-capture and VAD latency regressions.
+//! Benchmark binary that tracks voice-capture and VAD latency regressions.
+//!
+//! Runs the silence-aware capture loop over synthetic clips or real WAV
+//! recordings, repeats each run to measure processing time, and prints the
+//! results as `voice_metrics|` lines, CSV, or JSON. `--compare-vad` runs every
+//! clip through each compiled VAD engine so their decisions line up side by side.
+//! `--with-stt` also transcribes each capture with one or more Whisper models and
+//! reports STT time, tokens/s, and the end-of-speech-to-text latency per model.
 
 use std::f32::consts::PI;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
 
 #[cfg(not(feature = "vad_earshot"))]
 use anyhow::bail;
-use anyhow::Result;
-use clap::Parser;
-use voiceterm::audio::{self, VadEngine};
+use anyhow::{Context, Result};
+use clap::{Parser, ValueEnum};
+use serde::Serialize;
+use voiceterm::audio::{self, CaptureMetrics, VadEngine, TARGET_RATE};
 use voiceterm::config::{
-    default_vad_engine, VadEngineKind, VoicePipelineConfig, DEFAULT_VOICE_BUFFER_MS,
-    DEFAULT_VOICE_CHANNEL_CAPACITY, DEFAULT_VOICE_LOOKBACK_MS, DEFAULT_VOICE_MAX_CAPTURE_MS,
-    DEFAULT_VOICE_MIN_SPEECH_MS, DEFAULT_VOICE_SAMPLE_RATE, DEFAULT_VOICE_SILENCE_TAIL_MS,
-    DEFAULT_VOICE_STT_TIMEOUT_MS, DEFAULT_VOICE_VAD_FRAME_MS, DEFAULT_VOICE_VAD_SMOOTHING_FRAMES,
-    DEFAULT_VOICE_VAD_THRESHOLD_DB,
+    default_vad_engine, AppConfig, EchoMode, ResampleQuality, VadEngineKind, VoicePipelineConfig,
+    DEFAULT_VOICE_BUFFER_MS, DEFAULT_VOICE_CHANNEL_CAPACITY, DEFAULT_VOICE_LOOKBACK_MS,
+    DEFAULT_VOICE_MAX_CAPTURE_MS, DEFAULT_VOICE_MIN_SPEECH_MS, DEFAULT_VOICE_SAMPLE_RATE,
+    DEFAULT_VOICE_SILENCE_TAIL_MS, DEFAULT_VOICE_STT_TIMEOUT_MS, DEFAULT_VOICE_VAD_FRAME_MS,
+    DEFAULT_VOICE_VAD_SMOOTHING_FRAMES, DEFAULT_VOICE_VAD_THRESHOLD_DB,
 };
+use voiceterm::stats::percentile;
+use voiceterm::stt::Transcriber;
 #[cfg(feature = "vad_earshot")]
 use voiceterm::vad_earshot;
 
-/// Synthetic benchmark harness for voice capture latency.
+/// Benchmark harness for voice capture latency.
 #[derive(Debug, Parser)]
-#[command(about = "Benchmark the silence-aware capture loop with synthetic clips")]
+#[command(about = "Benchmark the silence-aware capture loop with synthetic clips or WAV files")]
 struct Args {
-    /// Human-friendly label recorded in the output metrics
+    /// Human-friendly label recorded in the output metrics (synthetic clip only)
     #[arg(long, default_value = "clip")]
     label: String,
 
+    /// WAV recording to benchmark instead of a synthetic clip; repeat for several (labelled by file name)
+    #[arg(long = "input", value_name = "WAV")]
+    inputs: Vec<PathBuf>,
+
+    /// Times each clip is run; processing time is reported as p50/p95/max over the runs
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    iterations: u32,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+
+    /// Run each clip through every compiled VAD engine; text output becomes a comparison table
+    #[arg(long = "compare-vad", default_value_t = false)]
+    compare_vad: bool,
+
+    /// Whisper ggml model to also transcribe each capture with; repeat to compare model sizes
+    #[arg(long = "with-stt", value_name = "MODEL")]
+    stt_models: Vec<PathBuf>,
+
     /// Duration of the synthetic speech segment (milliseconds)
     #[arg(long, default_value_t = 1_000)]
     speech_ms: u64,
@@ -42,6 +75,9 @@ struct Args {
     #[arg(long = "voice-silence-tail-ms", default_value_t = DEFAULT_VOICE_SILENCE_TAIL_MS)]
     voice_silence_tail_ms: u64,
 
+    #[arg(long = "voice-silence-tail-adaptive", default_value_t = false)]
+    voice_silence_tail_adaptive: bool,
+
     #[arg(
         long = "voice-min-speech-ms-before-stt",
         default_value_t = DEFAULT_VOICE_MIN_SPEECH_MS
@@ -86,34 +122,398 @@ struct Args {
     voice_vad_engine: VadEngineKind,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    /// One `voice_metrics|key=value|...` line per run (a table with `--compare-vad`)
+    Text,
+    /// Header row plus one row per run
+    Csv,
+    /// Array with one object per run
+    Json,
+}
+
+/// Metrics for one clip and engine; capture metrics are identical across iterations.
+#[derive(Debug, Clone, Serialize)]
+struct BenchResult {
+    label: String,
+    vad_engine: &'static str,
+    vad_threshold_db: f32,
+    iterations: u32,
+    audio_ms: u64,
+    capture_ms: u64,
+    speech_ms: u64,
+    /// Where the detected speech began and ended in the clip; `None` when none was heard.
+    speech_start_ms: Option<u64>,
+    speech_end_ms: Option<u64>,
+    silence_tail_ms: u64,
+    frames_processed: usize,
+    frames_dropped: usize,
+    early_stop: &'static str,
+    process_us_p50: u64,
+    process_us_p95: u64,
+    process_us_max: u64,
+    /// Transcription of the capture with one `--with-stt` model.
+    #[serde(flatten)]
+    stt: Option<SttResult>,
+}
+
+/// Whisper timings for one capture and model.
+#[derive(Debug, Clone, Serialize)]
+struct SttResult {
+    stt_model: String,
+    stt_device: String,
+    stt_load_ms: u64,
+    stt_ms_p50: u64,
+    stt_ms_p95: u64,
+    /// Text tokens decoded per second of STT time (p50 run).
+    tokens_per_s: f64,
+    /// End of speech to transcript: the silence tail the capture waits out plus p50 STT time.
+    mic_to_text_ms: u64,
+}
+
+/// A `--with-stt` model, loaded once and reused for every capture.
+struct SttModel {
+    label: String,
+    transcriber: Transcriber,
+    load_ms: u64,
+}
+
+const CSV_HEADER: &str = "label,vad_engine,vad_threshold_db,iterations,audio_ms,capture_ms,speech_ms,speech_start_ms,speech_end_ms,silence_tail_ms,frames_processed,frames_dropped,early_stop,process_us_p50,process_us_p95,process_us_max,stt_model,stt_device,stt_load_ms,stt_ms_p50,stt_ms_p95,tokens_per_s,mic_to_text_ms";
+
 fn main() -> Result<()> {
     let args = Args::parse();
     ensure_vad_engine_supported(&args)?;
-    let clip = synthesize_clip(args.speech_ms, args.silence_ms, args.voice_sample_rate);
-    let pipeline_cfg = build_pipeline_config(&args);
-    let vad_cfg: audio::VadConfig = (&pipeline_cfg).into();
-    let mut vad_engine = build_vad_engine(&pipeline_cfg);
-    let result = audio::offline_capture_from_pcm(&clip, &vad_cfg, vad_engine.as_mut());
-
-    println!(
-        "voice_metrics|label={}|capture_ms={}|speech_ms={}|silence_tail_ms={}|frames_processed={}|frames_dropped={}|early_stop={}",
-        args.label,
-        result.metrics.capture_ms,
-        result.metrics.speech_ms,
-        result.metrics.silence_tail_ms,
-        result.metrics.frames_processed,
-        result.metrics.frames_dropped,
-        result.metrics.early_stop_reason.label()
+    let engines = if args.compare_vad {
+        VadEngineKind::available().to_vec()
+    } else {
+        vec![args.voice_vad_engine]
+    };
+    let clips = load_clips(&args)?;
+    // Whisper settings (language, beam size, device) use voiceterm's defaults and environment.
+    let stt_config = AppConfig::parse_from(["voice_benchmark"]);
+    let stt_models = load_stt_models(&args.stt_models, &stt_config)?;
+    let mut results = Vec::new();
+    for (label, clip) in &clips {
+        for &engine in &engines {
+            let pipeline_cfg = VoicePipelineConfig {
+                vad_engine: engine,
+                ..build_pipeline_config(&args)
+            };
+            let result = run_clip(label.clone(), clip, &pipeline_cfg, args.iterations);
+            if stt_models.is_empty() || result.speech_ms == 0 {
+                results.push(result);
+                continue;
+            }
+            let speech = captured_speech(clip, &pipeline_cfg);
+            for model in &stt_models {
+                let stt = run_stt(model, &speech, &stt_config, args.iterations, &result)
+                    .with_context(|| {
+                        format!("{} failed to transcribe {}", model.label, result.label)
+                    })?;
+                results.push(BenchResult {
+                    stt: Some(stt),
+                    ..result.clone()
+                });
+            }
+        }
+    }
+    if args.compare_vad && args.format == OutputFormat::Text {
+        print!("{}", render_comparison(&results));
+    } else {
+        print!("{}", render(&results, args.format)?);
+    }
+    Ok(())
+}
+
+/// `(label, samples)` at the pipeline rate: each `--input`, or the synthetic clip.
+fn load_clips(args: &Args) -> Result<Vec<(String, Vec<f32>)>> {
+    if args.inputs.is_empty() {
+        let clip = synthesize_clip(args.speech_ms, args.silence_ms, args.voice_sample_rate);
+        return Ok(vec![(args.label.clone(), clip)]);
+    }
+    args.inputs
+        .iter()
+        .map(|path| Ok((clip_label(path), load_wav(path, args.voice_sample_rate)?)))
+        .collect()
+}
+
+fn clip_label(path: &Path) -> String {
+    path.file_stem()
+        .unwrap_or(path.as_os_str())
+        .to_string_lossy()
+        .into_owned()
+}
+
+fn load_wav(path: &Path, sample_rate: u32) -> Result<Vec<f32>> {
+    let bytes = fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let (samples, file_rate) = audio::decode_wav(&bytes)
+        .with_context(|| format!("failed to decode {}", path.display()))?;
+    Ok(audio::resample(
+        &samples,
+        file_rate,
+        sample_rate,
+        ResampleQuality::High,
+    ))
+}
+
+/// Load each `--with-stt` model and run it once so one-time setup stays out of the timings.
+fn load_stt_models(paths: &[PathBuf], config: &AppConfig) -> Result<Vec<SttModel>> {
+    paths
+        .iter()
+        .map(|path| {
+            let model_path = path
+                .to_str()
+                .with_context(|| format!("model path {} is not UTF-8", path.display()))?;
+            let started = Instant::now();
+            let transcriber = Transcriber::with_device(model_path, config.stt_device)
+                .with_context(|| format!("failed to load Whisper model {}", path.display()))?;
+            let load_ms = elapsed_ms(started);
+            transcriber.transcribe(&vec![0.0; TARGET_RATE as usize], config)?;
+            Ok(SttModel {
+                label: clip_label(path),
+                transcriber,
+                load_ms,
+            })
+        })
+        .collect()
+}
+
+/// The audio a live capture would hand to Whisper: the captured span at 16 kHz.
+fn captured_speech(clip: &[f32], pipeline_cfg: &VoicePipelineConfig) -> Vec<f32> {
+    let vad_cfg: audio::VadConfig = pipeline_cfg.into();
+    let mut vad_engine = build_vad_engine(pipeline_cfg);
+    let capture = audio::offline_capture_from_pcm(clip, &vad_cfg, vad_engine.as_mut());
+    audio::resample(
+        &capture.audio,
+        pipeline_cfg.sample_rate,
+        TARGET_RATE,
+        ResampleQuality::High,
+    )
+}
+
+fn run_stt(
+    model: &SttModel,
+    speech: &[f32],
+    config: &AppConfig,
+    iterations: u32,
+    capture: &BenchResult,
+) -> Result<SttResult> {
+    let mut stt_ms = Vec::with_capacity(iterations as usize);
+    let mut tokens = 0;
+    for _ in 0..iterations.max(1) {
+        let started = Instant::now();
+        tokens = model
+            .transcriber
+            .transcribe_detailed(speech, config)?
+            .tokens;
+        stt_ms.push(elapsed_ms(started));
+    }
+    stt_ms.sort_unstable();
+    let stt_ms_p50 = percentile(&stt_ms, 50);
+    Ok(SttResult {
+        stt_model: model.label.clone(),
+        stt_device: model.transcriber.device_label().to_string(),
+        stt_load_ms: model.load_ms,
+        stt_ms_p50,
+        stt_ms_p95: percentile(&stt_ms, 95),
+        tokens_per_s: tokens_per_second(tokens, stt_ms_p50),
+        mic_to_text_ms: capture.silence_tail_ms + stt_ms_p50,
+    })
+}
+
+/// Tokens per second rounded to 0.1; a sub-millisecond run counts as 1 ms.
+fn tokens_per_second(tokens: usize, stt_ms: u64) -> f64 {
+    (tokens as f64 * 10_000.0 / stt_ms.max(1) as f64).round() / 10.0
+}
+
+fn elapsed_ms(started: Instant) -> u64 {
+    started.elapsed().as_millis().min(u128::from(u64::MAX)) as u64
+}
+
+fn run_clip(
+    label: String,
+    clip: &[f32],
+    pipeline_cfg: &VoicePipelineConfig,
+    iterations: u32,
+) -> BenchResult {
+    let vad_cfg: audio::VadConfig = pipeline_cfg.into();
+    let mut metrics = CaptureMetrics::default();
+    let mut process_us = Vec::with_capacity(iterations as usize);
+    for _ in 0..iterations.max(1) {
+        // A fresh engine per run so no VAD state carries over between iterations.
+        let mut vad_engine = build_vad_engine(pipeline_cfg);
+        let start = Instant::now();
+        metrics = audio::offline_capture_from_pcm(clip, &vad_cfg, vad_engine.as_mut()).metrics;
+        process_us.push(start.elapsed().as_micros().min(u128::from(u64::MAX)) as u64);
+    }
+    process_us.sort_unstable();
+    // Same positions `voiceterm transcribe` reports: speech ends where the silence tail began.
+    let speech_end_ms =
+        (metrics.speech_ms > 0).then(|| metrics.capture_ms.saturating_sub(metrics.silence_tail_ms));
+    BenchResult {
+        label,
+        vad_engine: pipeline_cfg.vad_engine.label(),
+        vad_threshold_db: pipeline_cfg.vad_threshold_db,
+        iterations,
+        audio_ms: clip.len() as u64 * 1000 / u64::from(pipeline_cfg.sample_rate.max(1)),
+        capture_ms: metrics.capture_ms,
+        speech_ms: metrics.speech_ms,
+        speech_start_ms: speech_end_ms.map(|end| end.saturating_sub(metrics.voiced_span_ms)),
+        speech_end_ms,
+        silence_tail_ms: metrics.silence_tail_ms,
+        frames_processed: metrics.frames_processed,
+        frames_dropped: metrics.frames_dropped,
+        early_stop: metrics.early_stop_reason.label(),
+        process_us_p50: percentile(&process_us, 50),
+        process_us_p95: percentile(&process_us, 95),
+        process_us_max: process_us.last().copied().unwrap_or(0),
+        stt: None,
+    }
+}
+
+fn render(results: &[BenchResult], format: OutputFormat) -> Result<String> {
+    let mut out = String::new();
+    match format {
+        OutputFormat::Text => {
+            for result in results {
+                out.push_str(&format!(
+                    "voice_metrics|label={}|capture_ms={}|speech_ms={}|silence_tail_ms={}|frames_processed={}|frames_dropped={}|early_stop={}|process_us_p50={}|process_us_p95={}",
+                    result.label,
+                    result.capture_ms,
+                    result.speech_ms,
+                    result.silence_tail_ms,
+                    result.frames_processed,
+                    result.frames_dropped,
+                    result.early_stop,
+                    result.process_us_p50,
+                    result.process_us_p95
+                ));
+                if let Some(stt) = &result.stt {
+                    out.push_str(&format!(
+                        "|stt_model={}|stt_device={}|stt_load_ms={}|stt_ms_p50={}|stt_ms_p95={}|tokens_per_s={}|mic_to_text_ms={}",
+                        stt.stt_model,
+                        stt.stt_device,
+                        stt.stt_load_ms,
+                        stt.stt_ms_p50,
+                        stt.stt_ms_p95,
+                        stt.tokens_per_s,
+                        stt.mic_to_text_ms
+                    ));
+                }
+                out.push('\n');
+            }
+        }
+        OutputFormat::Csv => {
+            out.push_str(CSV_HEADER);
+            out.push('\n');
+            for result in results {
+                out.push_str(&format!(
+                    "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}\n",
+                    csv_field(&result.label),
+                    result.vad_engine,
+                    result.vad_threshold_db,
+                    result.iterations,
+                    result.audio_ms,
+                    result.capture_ms,
+                    result.speech_ms,
+                    optional_ms(result.speech_start_ms),
+                    optional_ms(result.speech_end_ms),
+                    result.silence_tail_ms,
+                    result.frames_processed,
+                    result.frames_dropped,
+                    result.early_stop,
+                    result.process_us_p50,
+                    result.process_us_p95,
+                    result.process_us_max,
+                    result
+                        .stt
+                        .as_ref()
+                        .map_or_else(|| ",,,,,,".to_string(), stt_csv_fields)
+                ));
+            }
+        }
+        OutputFormat::Json => {
+            out.push_str(&serde_json::to_string_pretty(results)?);
+            out.push('\n');
+        }
+    }
+    Ok(out)
+}
+
+/// Markdown table with one row per clip and engine, grouped by clip; `--with-stt`
+/// adds one row per model with its STT columns.
+fn render_comparison(results: &[BenchResult]) -> String {
+    let with_stt = results.iter().any(|result| result.stt.is_some());
+    let mut out = String::from(
+        "| clip | engine | speech | start_ms | end_ms | speech_ms | capture_ms | frames | early_stop | process_us_p50 |",
     );
+    if with_stt {
+        out.push_str(" model | stt_ms_p50 | tokens_per_s | mic_to_text_ms |");
+    }
+    out.push_str("\n|");
+    out.push_str(&" --- |".repeat(if with_stt { 14 } else { 10 }));
+    out.push('\n');
+    for result in results {
+        out.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} | {} | {} | {} | {} |",
+            result.label,
+            result.vad_engine,
+            if result.speech_ms > 0 { "yes" } else { "no" },
+            optional_ms(result.speech_start_ms),
+            optional_ms(result.speech_end_ms),
+            result.speech_ms,
+            result.capture_ms,
+            result.frames_processed,
+            result.early_stop,
+            result.process_us_p50
+        ));
+        if with_stt {
+            out.push_str(&match &result.stt {
+                Some(stt) => format!(
+                    " {} | {} | {} | {} |",
+                    stt.stt_model, stt.stt_ms_p50, stt.tokens_per_s, stt.mic_to_text_ms
+                ),
+                None => " | | | |".to_string(),
+            });
+        }
+        out.push('\n');
+    }
+    out
+}
 
-    Ok(())
+fn stt_csv_fields(stt: &SttResult) -> String {
+    format!(
+        "{},{},{},{},{},{},{}",
+        csv_field(&stt.stt_model),
+        stt.stt_device,
+        stt.stt_load_ms,
+        stt.stt_ms_p50,
+        stt.stt_ms_p95,
+        stt.tokens_per_s,
+        stt.mic_to_text_ms
+    )
+}
+
+fn optional_ms(ms: Option<u64>) -> String {
+    ms.map(|ms| ms.to_string()).unwrap_or_default()
+}
+
+/// Quote a CSV field when it contains a delimiter, quote, or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
 }
 
 fn build_pipeline_config(args: &Args) -> VoicePipelineConfig {
     VoicePipelineConfig {
         sample_rate: args.voice_sample_rate,
+        resample_quality: ResampleQuality::High,
         max_capture_ms: args.voice_max_capture_ms,
         silence_tail_ms: args.voice_silence_tail_ms,
+        silence_tail_adaptive: args.voice_silence_tail_adaptive,
         min_speech_ms_before_stt_start: args.voice_min_speech_ms_before_stt_start,
         lookback_ms: args.voice_lookback_ms,
         buffer_ms: args.voice_buffer_ms,
@@ -124,6 +524,9 @@ fn build_pipeline_config(args: &Args) -> VoicePipelineConfig {
         vad_smoothing_frames: args.voice_vad_smoothing_frames,
         python_fallback_allowed: true,
         vad_engine: args.voice_vad_engine,
+        denoise: false,
+        echo_mode: EchoMode::Off,
+        echo_reference_device: None,
     }
 }
 
@@ -176,6 +579,175 @@ mod tests {
     use super::*;
     use clap::Parser;
 
+    #[test]
+    fn wav_inputs_are_labelled_by_file_and_resampled_to_the_pipeline_rate() {
+        let dir = std::env::temp_dir().join(format!("voice-benchmark-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("standup, take 2.wav");
+        fs::write(&path, audio::wav_bytes(&[0.1; 8_000], 8_000)).unwrap();
+
+        let args = Args::try_parse_from([
+            "voice_benchmark",
+            "--input",
+            path.to_str().unwrap(),
+            "--iterations",
+            "3",
+            "--format",
+            "csv",
+            "--voice-vad-engine",
+            "simple",
+        ])
+        .unwrap();
+        let clips = load_clips(&args).unwrap();
+        fs::remove_dir_all(&dir).ok();
+        assert_eq!(clips.len(), 1);
+        assert_eq!(clips[0].0, "standup, take 2");
+        assert!(
+            clips[0].1.len().abs_diff(16_000) <= 16,
+            "{}",
+            clips[0].1.len()
+        );
+
+        let result = run_clip(
+            clips[0].0.clone(),
+            &clips[0].1,
+            &build_pipeline_config(&args),
+            args.iterations,
+        );
+        assert_eq!(result.iterations, 3);
+        assert!(result.audio_ms.abs_diff(1_000) <= 1, "{}", result.audio_ms);
+        assert!(result.process_us_p50 <= result.process_us_p95);
+        assert!(result.process_us_p95 <= result.process_us_max);
+        let csv = render(&[result], OutputFormat::Csv).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some(CSV_HEADER));
+        assert!(lines
+            .next()
+            .unwrap()
+            .starts_with("\"standup, take 2\",simple,"));
+    }
+
+    #[test]
+    fn compare_vad_runs_every_compiled_engine_with_boundaries() {
+        let args = Args::try_parse_from([
+            "voice_benchmark",
+            "--compare-vad",
+            "--speech-ms",
+            "600",
+            "--silence-ms",
+            "900",
+            "--voice-silence-tail-ms",
+            "500",
+        ])
+        .unwrap();
+        assert!(args.compare_vad);
+        let (label, clip) = load_clips(&args).unwrap().remove(0);
+        let results: Vec<BenchResult> = VadEngineKind::available()
+            .iter()
+            .map(|&engine| {
+                let cfg = VoicePipelineConfig {
+                    vad_engine: engine,
+                    ..build_pipeline_config(&args)
+                };
+                run_clip(label.clone(), &clip, &cfg, 1)
+            })
+            .collect();
+        let simple = results
+            .iter()
+            .find(|result| result.vad_engine == "simple")
+            .unwrap();
+        assert!(simple.speech_start_ms.is_some_and(|start| start <= 100));
+        let end = simple.speech_end_ms.unwrap();
+        assert!(end.abs_diff(600) <= 100, "speech end {end}");
+
+        let table = render_comparison(&results);
+        assert_eq!(table.lines().count(), 2 + VadEngineKind::available().len());
+        assert!(table.contains("| clip | simple | yes |"), "{table}");
+    }
+
+    #[test]
+    fn percentile_and_json_output() {
+        let sorted: Vec<u64> = (1..=20).collect();
+        assert_eq!(percentile(&sorted, 50), 10);
+        assert_eq!(percentile(&sorted, 95), 19);
+        assert_eq!(percentile(&[7], 95), 7);
+        assert!(Args::try_parse_from(["voice_benchmark", "--iterations", "0"]).is_err());
+
+        let args =
+            Args::try_parse_from(["voice_benchmark", "--voice-vad-engine", "simple"]).unwrap();
+        let (label, clip) = load_clips(&args).unwrap().remove(0);
+        let result = run_clip(label, &clip, &build_pipeline_config(&args), 1);
+        let json: serde_json::Value =
+            serde_json::from_str(&render(&[result], OutputFormat::Json).unwrap()).unwrap();
+        assert_eq!(json[0]["label"], "clip");
+        assert_eq!(json[0]["vad_engine"], "simple");
+        assert!(json[0]["process_us_p95"].is_u64());
+    }
+
+    #[test]
+    fn stt_columns_follow_the_capture_metrics() {
+        let args = Args::try_parse_from([
+            "voice_benchmark",
+            "--with-stt",
+            "models/ggml-base.en.bin",
+            "--with-stt",
+            "models/ggml-small.en.bin",
+            "--voice-vad-engine",
+            "simple",
+        ])
+        .unwrap();
+        let labels: Vec<String> = args
+            .stt_models
+            .iter()
+            .map(|path| clip_label(path))
+            .collect();
+        assert_eq!(labels, ["ggml-base.en", "ggml-small.en"]);
+        assert_eq!(tokens_per_second(12, 400), 30.0);
+        assert_eq!(tokens_per_second(3, 0), 3000.0);
+
+        let (label, clip) = load_clips(&args).unwrap().remove(0);
+        let capture = run_clip(label, &clip, &build_pipeline_config(&args), 1);
+        let with_stt = BenchResult {
+            stt: Some(SttResult {
+                stt_model: labels[0].clone(),
+                stt_device: "cpu".to_string(),
+                stt_load_ms: 180,
+                stt_ms_p50: 400,
+                stt_ms_p95: 450,
+                tokens_per_s: 30.0,
+                mic_to_text_ms: capture.silence_tail_ms + 400,
+            }),
+            ..capture.clone()
+        };
+        let results = [capture, with_stt];
+
+        let text = render(&results, OutputFormat::Text).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert!(!lines[0].contains("stt_model="));
+        assert!(lines[1].ends_with(&format!(
+            "|stt_model=ggml-base.en|stt_device=cpu|stt_load_ms=180|stt_ms_p50=400|stt_ms_p95=450|tokens_per_s=30|mic_to_text_ms={}",
+            results[0].silence_tail_ms + 400
+        )));
+
+        let csv = render(&results, OutputFormat::Csv).unwrap();
+        let columns = CSV_HEADER.split(',').count();
+        for line in csv.lines() {
+            assert_eq!(line.split(',').count(), columns, "{line}");
+        }
+
+        let json: serde_json::Value =
+            serde_json::from_str(&render(&results, OutputFormat::Json).unwrap()).unwrap();
+        assert!(json[0].get("stt_model").is_none());
+        assert_eq!(json[1]["stt_model"], "ggml-base.en");
+        assert_eq!(json[1]["stt_ms_p50"], 400);
+
+        let table = render_comparison(&results);
+        assert!(table.contains("| ggml-base.en | 400 | 30 |"), "{table}");
+        for line in table.lines() {
+            assert_eq!(line.matches('|').count(), 15, "{line}");
+        }
+    }
+
     #[cfg(not(feature = "vad_earshot"))]
     #[test]
     fn earshot_flag_errors_without_feature() {
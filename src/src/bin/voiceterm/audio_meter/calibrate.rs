@@ -0,0 +1,217 @@
+//! `--calibrate` wizard that turns a room-noise sample and a read-aloud phrase
+//! into a VAD threshold and silence tail, saved as the input device's profile
+//! in the config file.
+//!
+//! Levels are measured per VAD-sized frame rather than over the whole sample,
+//! so a keyboard click in the room raises the threshold and the pauses between
+//! words in the phrase size the silence tail.
+
+use anyhow::{anyhow, bail, Result};
+use std::io::{self, Write};
+use std::time::Duration;
+use voiceterm::audio::{Recorder, TARGET_RATE};
+
+use super::format_mic_meter_display;
+use super::measure::{peak_db, rms_db};
+use super::recommend::{recommend_threshold, validate_sample_ms};
+use super::AudioLevel;
+use crate::config::{default_config_path, save_device_profile, OverlayConfig};
+use crate::theme::Theme;
+
+/// Phrase read aloud during calibration; its commas leave natural pauses.
+const CALIBRATION_PHRASE: &str =
+    "Run the tests, then open the first failure, and explain the error.";
+/// Long enough to read the phrase at a relaxed pace.
+const PHRASE_SAMPLE: Duration = Duration::from_secs(6);
+/// Frame length used to measure levels and pauses (the default VAD frame).
+const FRAME_MS: u64 = 20;
+/// Headroom over the longest pause heard, for slower speech in real sessions.
+const TAIL_MARGIN_MS: u64 = 250;
+const MIN_TAIL_MS: u64 = 300;
+const MAX_TAIL_MS: u64 = 1500;
+
+/// Settings derived from one calibration run.
+#[derive(Debug, Clone, PartialEq)]
+struct Calibration {
+    threshold_db: f32,
+    silence_tail_ms: u64,
+    ambient_db: f32,
+    speech_db: f32,
+    longest_pause_ms: u64,
+    warning: Option<&'static str>,
+}
+
+/// Derive a threshold and silence tail from room noise and a spoken phrase.
+fn calibrate(ambient: &[f32], phrase: &[f32], sample_rate: u32) -> Result<Calibration> {
+    let ambient_levels = frame_levels(ambient, sample_rate);
+    let phrase_levels = frame_levels(phrase, sample_rate);
+    if ambient_levels.is_empty() || phrase_levels.is_empty() {
+        bail!("the microphone returned no audio");
+    }
+    // Occasional room noise (typing, a fan) should stay under the threshold,
+    // and the louder part of the phrase is where the voice is.
+    let ambient_db = percentile(&ambient_levels, 0.9);
+    let speech_db = percentile(&phrase_levels, 0.75);
+    let (threshold_db, warning) = recommend_threshold(ambient_db, speech_db);
+    let threshold_db = (threshold_db * 2.0).round() / 2.0;
+
+    let voiced: Vec<bool> = phrase_levels
+        .iter()
+        .map(|level| *level >= threshold_db)
+        .collect();
+    let (Some(first), Some(last)) = (
+        voiced.iter().position(|voiced| *voiced),
+        voiced.iter().rposition(|voiced| *voiced),
+    ) else {
+        bail!("heard no speech above the room noise; move closer to the mic and try again");
+    };
+    let mut longest_gap = 0;
+    let mut gap = 0;
+    for voiced in &voiced[first..=last] {
+        gap = if *voiced { 0 } else { gap + 1 };
+        longest_gap = longest_gap.max(gap);
+    }
+    let longest_pause_ms = longest_gap * FRAME_MS;
+    // Round to 50 ms so saved values read like hand-picked ones.
+    let silence_tail_ms =
+        ((longest_pause_ms + TAIL_MARGIN_MS + 25) / 50 * 50).clamp(MIN_TAIL_MS, MAX_TAIL_MS);
+    Ok(Calibration {
+        threshold_db,
+        silence_tail_ms,
+        ambient_db,
+        speech_db,
+        longest_pause_ms,
+        warning,
+    })
+}
+
+fn frame_levels(samples: &[f32], sample_rate: u32) -> Vec<f32> {
+    let frame = (u64::from(sample_rate) * FRAME_MS / 1000).max(1) as usize;
+    samples.chunks(frame).map(rms_db).collect()
+}
+
+fn percentile(levels: &[f32], fraction: f32) -> f32 {
+    let mut sorted = levels.to_vec();
+    sorted.sort_by(f32::total_cmp);
+    sorted[((sorted.len() - 1) as f32 * fraction).round() as usize]
+}
+
+/// Run `--calibrate`: record, recommend, and save the result under `devices.<device name>`.
+pub(crate) fn run_calibration(config: &OverlayConfig, theme: Theme) -> Result<()> {
+    validate_sample_ms("ambient", config.app.mic_meter_ambient_ms)?;
+    let path = config
+        .config_path
+        .clone()
+        .or_else(default_config_path)
+        .ok_or_else(|| anyhow!("no config path: set --config or HOME"))?;
+
+    let recorder = Recorder::new(config.app.input_device.as_deref())?;
+    let device = recorder.device_name();
+    println!("Calibrating input device: {device}");
+    println!(
+        "Step 1/2: recording room noise for {:.1}s... stay quiet.",
+        config.app.mic_meter_ambient_ms as f32 / 1000.0
+    );
+    io::stdout().flush().ok();
+    let ambient = recorder.record_for(Duration::from_millis(config.app.mic_meter_ambient_ms))?;
+    println!(
+        "Step 2/2: read this aloud at your normal pace ({}s):\n\n  \"{CALIBRATION_PHRASE}\"\n",
+        PHRASE_SAMPLE.as_secs()
+    );
+    io::stdout().flush().ok();
+    let phrase = recorder.record_for(PHRASE_SAMPLE)?;
+
+    let calibration = calibrate(&ambient, &phrase, TARGET_RATE)?;
+    let level = |rms, samples: &[f32]| AudioLevel {
+        rms_db: rms,
+        peak_db: peak_db(samples),
+    };
+    println!(
+        "{}",
+        format_mic_meter_display(
+            level(calibration.ambient_db, &ambient),
+            Some(level(calibration.speech_db, &phrase)),
+            calibration.threshold_db,
+            theme
+        )
+    );
+    println!(
+        "\nLongest pause between words: {} ms",
+        calibration.longest_pause_ms
+    );
+    if let Some(message) = calibration.warning {
+        println!("Note: {message}");
+    }
+
+    save_device_profile(
+        &path,
+        &device,
+        &[
+            (
+                "voice-vad-threshold-db",
+                f64::from(calibration.threshold_db).into(),
+            ),
+            ("voice-silence-tail-ms", calibration.silence_tail_ms.into()),
+        ],
+    )?;
+    println!(
+        "\nSaved to {} for {device}:\n  voice-vad-threshold-db: {:.1}\n  voice-silence-tail-ms: {}",
+        path.display(),
+        calibration.threshold_db,
+        calibration.silence_tail_ms
+    );
+    println!("These apply whenever voiceterm records from this device; flags and environment variables still override them.");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn noise(ms: u64, amplitude: f32) -> Vec<f32> {
+        (0..TARGET_RATE as u64 * ms / 1000)
+            .map(|index| {
+                if index % 2 == 0 {
+                    amplitude
+                } else {
+                    -amplitude
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn calibrate_sets_threshold_between_room_and_voice_and_tail_from_pauses() {
+        let ambient = noise(2_000, 0.003);
+        let mut phrase = noise(300, 0.003);
+        phrase.extend(noise(1_200, 0.2));
+        phrase.extend(noise(400, 0.003));
+        phrase.extend(noise(1_500, 0.2));
+        phrase.extend(noise(180, 0.003));
+        phrase.extend(noise(800, 0.2));
+        phrase.extend(noise(1_000, 0.003));
+
+        let calibration = calibrate(&ambient, &phrase, TARGET_RATE).unwrap();
+        assert!(
+            calibration.threshold_db > calibration.ambient_db
+                && calibration.threshold_db < calibration.speech_db,
+            "{calibration:?}"
+        );
+        assert_eq!(calibration.longest_pause_ms, 400);
+        assert_eq!(calibration.silence_tail_ms, 650);
+        assert_eq!(calibration.warning, None);
+    }
+
+    #[test]
+    fn calibrate_rejects_a_silent_phrase_and_clamps_the_tail() {
+        let ambient = noise(1_000, 0.01);
+        assert!(calibrate(&ambient, &noise(2_000, 0.0), TARGET_RATE).is_err());
+
+        let mut phrase = noise(1_000, 0.3);
+        phrase.extend(noise(2_000, 0.01));
+        phrase.extend(noise(1_000, 0.3));
+        let calibration = calibrate(&ambient, &phrase, TARGET_RATE).unwrap();
+        assert_eq!(calibration.longest_pause_ms, 2_000);
+        assert_eq!(calibration.silence_tail_ms, MAX_TAIL_MS);
+    }
+}
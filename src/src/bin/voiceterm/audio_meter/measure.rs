@@ -7,7 +7,7 @@ use voiceterm::audio::Recorder;
 use super::{AudioLevel, RECOMMENDED_FLOOR_DB};
 
 #[inline]
-fn rms_db(samples: &[f32]) -> f32 {
+pub(super) fn rms_db(samples: &[f32]) -> f32 {
     if samples.is_empty() {
         return RECOMMENDED_FLOOR_DB;
     }
@@ -17,7 +17,7 @@ fn rms_db(samples: &[f32]) -> f32 {
 }
 
 #[inline]
-fn peak_db(samples: &[f32]) -> f32 {
+pub(super) fn peak_db(samples: &[f32]) -> f32 {
     if samples.is_empty() {
         return RECOMMENDED_FLOOR_DB;
     }
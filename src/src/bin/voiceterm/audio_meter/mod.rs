@@ -1,7 +1,9 @@
 //! Audio-meter module so mic level feedback stays consistent across HUD views.
 //!
-//! Provides visual representation of audio levels for mic meter and real-time display.
+//! Provides visual representation of audio levels for mic meter and real-time display,
+//! and the `--calibrate` wizard that saves measured VAD settings.
 
+mod calibrate;
 mod format;
 mod measure;
 mod recommend;
@@ -14,6 +16,8 @@ use voiceterm::config::AppConfig;
 
 use crate::theme::Theme;
 
+pub(crate) use calibrate::run_calibration;
+
 #[allow(unused_imports)]
 pub use format::{
     format_level_compact, format_level_meter, format_mic_meter_display, format_waveform,
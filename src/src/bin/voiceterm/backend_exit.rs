@@ -0,0 +1,160 @@
+//! Backend exit summary and relaunch so a crashed CLI does not end the overlay.
+//!
+//! When the backend closes its terminal the overlay stays up, the status line
+//! says how the process ended, and `r` (or Enter) relaunches the same command
+//! while `q` quits. Auto-voice, the learned prompt, and queued transcripts carry
+//! over to the relaunched backend.
+
+use anyhow::Result;
+use std::os::unix::process::ExitStatusExt;
+use std::process::ExitStatus;
+use std::time::Duration;
+use voiceterm::pty_session::PtyOverlaySession;
+
+/// How long to wait for the exit status after the backend closes its terminal.
+pub(crate) const EXIT_STATUS_WAIT: Duration = Duration::from_millis(500);
+
+/// Command line the backend was started with, kept for relaunching it.
+pub(crate) struct BackendLauncher {
+    command: String,
+    args: Vec<String>,
+    working_dir: String,
+    term_value: String,
+}
+
+impl BackendLauncher {
+    pub(crate) fn new(command: &str, args: &[String], working_dir: &str, term_value: &str) -> Self {
+        Self {
+            command: command.to_string(),
+            args: args.to_vec(),
+            working_dir: working_dir.to_string(),
+            term_value: term_value.to_string(),
+        }
+    }
+
+    pub(crate) fn spawn(&self) -> Result<PtyOverlaySession> {
+        PtyOverlaySession::new(
+            &self.command,
+            &self.working_dir,
+            &self.args,
+            &self.term_value,
+        )
+    }
+}
+
+/// A backend that closed its terminal, waiting to be relaunched.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct BackendExit {
+    /// `None` when the process had not exited yet or was reaped elsewhere.
+    pub(crate) status: Option<ExitStatus>,
+    pub(crate) uptime: Duration,
+}
+
+impl BackendExit {
+    /// Status line text naming how `label` ended and the relaunch keys.
+    pub(crate) fn summary(&self, label: &str) -> String {
+        let how = match self.status {
+            Some(status) => match (status.code(), status.signal()) {
+                (Some(0), _) => "exited".to_string(),
+                (Some(code), _) => format!("exited with code {code}"),
+                (None, Some(signal)) => match signal_name(signal) {
+                    Some(name) => format!("was killed by {name}"),
+                    None => format!("was killed by signal {signal}"),
+                },
+                (None, None) => "exited".to_string(),
+            },
+            None => "exited".to_string(),
+        };
+        format!(
+            "{label} {how} after {} · r relaunch · q quit",
+            format_uptime(self.uptime)
+        )
+    }
+}
+
+/// What a key does while the backend is down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ExitChoice {
+    Relaunch,
+    Quit,
+}
+
+/// `r`/Enter relaunch; `q`, Ctrl+C, and Ctrl+D quit; other keys do nothing.
+pub(crate) fn exit_choice(bytes: &[u8]) -> Option<ExitChoice> {
+    match bytes {
+        b"r" | b"R" | b"\r" | b"\n" => Some(ExitChoice::Relaunch),
+        b"q" | b"Q" | b"\x03" | b"\x04" => Some(ExitChoice::Quit),
+        _ => None,
+    }
+}
+
+fn signal_name(signal: i32) -> Option<&'static str> {
+    Some(match signal {
+        libc::SIGHUP => "SIGHUP",
+        libc::SIGINT => "SIGINT",
+        libc::SIGQUIT => "SIGQUIT",
+        libc::SIGABRT => "SIGABRT",
+        libc::SIGKILL => "SIGKILL",
+        libc::SIGSEGV => "SIGSEGV",
+        libc::SIGPIPE => "SIGPIPE",
+        libc::SIGTERM => "SIGTERM",
+        _ => return None,
+    })
+}
+
+fn format_uptime(uptime: Duration) -> String {
+    let secs = uptime.as_secs();
+    if secs < 60 {
+        format!("{secs}s")
+    } else if secs < 3600 {
+        format!("{}m {}s", secs / 60, secs % 60)
+    } else {
+        format!("{}h {}m", secs / 3600, (secs % 3600) / 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exit(raw_status: i32, secs: u64) -> BackendExit {
+        BackendExit {
+            status: Some(ExitStatus::from_raw(raw_status)),
+            uptime: Duration::from_secs(secs),
+        }
+    }
+
+    #[test]
+    fn summary_names_exit_code_or_signal() {
+        assert_eq!(
+            exit(0, 42).summary("codex"),
+            "codex exited after 42s · r relaunch · q quit"
+        );
+        assert_eq!(
+            exit(1 << 8, 724).summary("codex"),
+            "codex exited with code 1 after 12m 4s · r relaunch · q quit"
+        );
+        assert_eq!(
+            exit(libc::SIGKILL, 3 * 3600 + 120).summary("claude"),
+            "claude was killed by SIGKILL after 3h 2m · r relaunch · q quit"
+        );
+        let unknown = BackendExit {
+            status: None,
+            uptime: Duration::from_secs(5),
+        };
+        assert_eq!(
+            unknown.summary("aider"),
+            "aider exited after 5s · r relaunch · q quit"
+        );
+    }
+
+    #[test]
+    fn exit_choice_maps_relaunch_and_quit_keys() {
+        assert_eq!(exit_choice(b"r"), Some(ExitChoice::Relaunch));
+        assert_eq!(exit_choice(b"\r"), Some(ExitChoice::Relaunch));
+        assert_eq!(exit_choice(b"q"), Some(ExitChoice::Quit));
+        assert_eq!(exit_choice(b"\x03"), Some(ExitChoice::Quit));
+        assert_eq!(exit_choice(b"x"), None);
+        assert_eq!(exit_choice(b"rr"), None);
+    }
+}
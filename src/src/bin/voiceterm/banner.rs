@@ -138,6 +138,8 @@ pub struct BannerConfig {
     pub theme: String,
     /// Pipeline in use (Rust or Python)
     pub pipeline: String,
+    /// Device local Whisper runs on (e.g., "cpu", "gpu/metal")
+    pub stt_device: String,
     /// Microphone sensitivity in dB
     pub sensitivity_db: f32,
     /// Backend CLI name (e.g., "claude", "gemini", "aider")
@@ -150,6 +152,7 @@ impl Default for BannerConfig {
             auto_voice: false,
             theme: "coral".to_string(),
             pipeline: "Rust".to_string(),
+            stt_device: "cpu".to_string(),
             sensitivity_db: -35.0,
             backend: "codex".to_string(),
         }
@@ -172,12 +175,13 @@ pub fn format_startup_banner(config: &BannerConfig, theme: Theme) -> String {
     );
 
     format!(
-        "{}VoiceTerm{} v{} │ {} │ {} │ theme: {} │ auto-voice: {} │ {:.0}dB\n{}\n",
+        "{}VoiceTerm{} v{} │ {} │ {} ({}) │ theme: {} │ auto-voice: {} │ {:.0}dB\n{}\n",
         colors.info,
         colors.reset,
         VERSION,
         config.backend,
         config.pipeline,
+        config.stt_device,
         config.theme,
         auto_voice_status,
         config.sensitivity_db,
@@ -312,11 +316,12 @@ mod tests {
             auto_voice: true,
             theme: "catppuccin".to_string(),
             pipeline: "Rust".to_string(),
+            stt_device: "gpu/cuda".to_string(),
             sensitivity_db: -40.0,
             backend: "gemini".to_string(),
         };
         let banner = format_startup_banner(&config, Theme::Coral);
-        assert!(banner.contains("Rust"));
+        assert!(banner.contains("Rust (gpu/cuda)"));
         assert!(banner.contains("-40dB"));
         assert!(banner.contains("on")); // auto-voice on
         assert!(banner.contains("gemini")); // backend shown
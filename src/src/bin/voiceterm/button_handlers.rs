@@ -4,6 +4,7 @@ use std::time::{Duration, Instant};
 
 use crossbeam_channel::Sender;
 use voiceterm::pty_session::PtyOverlaySession;
+use voiceterm::voice_error::VoiceErrorKind;
 use voiceterm::VoiceCaptureTrigger;
 
 use crate::buttons::{ButtonAction, ButtonRegistry};
@@ -14,7 +15,9 @@ use crate::overlays::{
 };
 use crate::settings::SettingsMenuState;
 use crate::settings_handlers::SettingsActionContext;
-use crate::status_line::{get_button_positions, status_banner_height, StatusLineState};
+use crate::status_line::{
+    get_button_positions, get_status_row_positions, status_banner_height, StatusLineState,
+};
 use crate::terminal::{resolved_cols, update_pty_winsize};
 use crate::theme::Theme;
 use crate::theme_ops::theme_index_from_theme;
@@ -109,7 +112,7 @@ impl<'a> ButtonActionContext<'a> {
                         self.status_clear_deadline,
                         self.current_status,
                         self.status_state,
-                        "Voice capture failed (see log)",
+                        &VoiceErrorKind::classify(&err).status_message(),
                         Some(Duration::from_secs(2)),
                     );
                     log_debug(&format!("voice capture failed: {err:#}"));
@@ -237,7 +240,13 @@ pub(crate) fn update_button_registry(
     }
     let banner_height = status_banner_height(terminal_cols as usize, status_state.hud_style);
     registry.set_hud_offset(banner_height as u16);
-    let positions = get_button_positions(status_state, theme, terminal_cols as usize);
+    let positions = get_button_positions(status_state, theme, terminal_cols as usize)
+        .into_iter()
+        .chain(get_status_row_positions(
+            status_state,
+            theme,
+            terminal_cols as usize,
+        ));
     for pos in positions {
         registry.register(pos.start_x, pos.end_x, pos.row, pos.action);
     }
@@ -1,6 +1,7 @@
 //! CLI startup utilities so boot-time checks stay consistent across entry paths.
 
 use anyhow::Result;
+use std::env;
 use voiceterm::audio;
 
 pub(crate) fn resolve_sound_flag(global: bool, specific: bool) -> bool {
@@ -11,6 +12,18 @@ pub(crate) fn should_print_stats(stats_output: &str) -> bool {
     !stats_output.is_empty()
 }
 
+/// Project directory the backend runs in: `VOICETERM_CWD`, else the current directory.
+pub(crate) fn resolve_working_dir() -> String {
+    env::var("VOICETERM_CWD")
+        .ok()
+        .or_else(|| {
+            env::current_dir()
+                .ok()
+                .map(|dir| dir.to_string_lossy().to_string())
+        })
+        .unwrap_or_else(|| ".".to_string())
+}
+
 pub(crate) fn list_input_devices() -> Result<()> {
     // Support VOICETERM_TEST_DEVICES for testing
     let devices = if let Ok(raw) = std::env::var("VOICETERM_TEST_DEVICES") {
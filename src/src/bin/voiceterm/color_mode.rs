@@ -53,6 +53,21 @@ impl ColorMode {
         Self::Ansi16
     }
 
+    /// The poorer of `self` and `limit`.
+    pub fn capped(self, limit: Self) -> Self {
+        let rank = |mode: Self| match mode {
+            Self::None => 0,
+            Self::Ansi16 => 1,
+            Self::Color256 => 2,
+            Self::TrueColor => 3,
+        };
+        if rank(limit) < rank(self) {
+            limit
+        } else {
+            self
+        }
+    }
+
     /// Check if colors are supported at all.
     pub fn supports_color(&self) -> bool {
         !matches!(self, Self::None)
@@ -210,6 +225,19 @@ mod tests {
         assert_eq!(format!("{}", ColorMode::None), "none");
     }
 
+    #[test]
+    fn capped_never_raises_the_mode() {
+        assert_eq!(
+            ColorMode::TrueColor.capped(ColorMode::Ansi16),
+            ColorMode::Ansi16
+        );
+        assert_eq!(ColorMode::None.capped(ColorMode::Ansi16), ColorMode::None);
+        assert_eq!(
+            ColorMode::Color256.capped(ColorMode::TrueColor),
+            ColorMode::Color256
+        );
+    }
+
     #[test]
     fn rgb_to_256_grayscale() {
         assert_eq!(rgb_to_256(0, 0, 0), 16);
@@ -90,7 +90,8 @@ impl OverlayConfig {
 mod tests {
     use super::*;
     use crate::config::cli::{
-        HudBorderStyle, HudRightPanel, HudStyle, LatencyDisplayMode, VoiceSendMode,
+        BracketedPasteMode, HudBorderStyle, HudRightPanel, HudStyle, LatencyDisplayMode,
+        MultilineMode, TranscriptQueuePolicy, VoiceSendMode,
     };
     use clap::Parser;
     use voiceterm::config::AppConfig;
@@ -100,10 +101,20 @@ mod tests {
             app: AppConfig::parse_from(["test"]),
             prompt_regex: None,
             prompt_log: None,
+            prompt_detector_cmd: None,
             auto_voice: false,
             auto_voice_idle_ms: 1200,
+            auto_voice_quiet_hours: None,
             transcript_idle_ms: 250,
+            batch_window_ms: 0,
+            min_confidence: 0.0,
+            redictate_attempts: 1,
+            mark_low_confidence: 0.0,
             voice_send_mode: VoiceSendMode::Auto,
+            dictation_buffer: false,
+            voice_navigation: false,
+            code_mode: false,
+            multiline_mode: MultilineMode::Paste,
             theme_name: None,
             no_color: false,
             hud_right_panel: HudRightPanel::Ribbon,
@@ -112,11 +123,46 @@ mod tests {
             hud_style: HudStyle::Full,
             latency_display: LatencyDisplayMode::Short,
             minimal_hud: false,
+            no_scroll_region: false,
+            no_output_preview: false,
+            render_mode: crate::config::RenderMode::Auto,
+            allow_same_tty: false,
+            pipe: false,
+            once: false,
+            pipe_json: false,
             backend: backend.to_string(),
             codex: false,
             claude: false,
             gemini: false,
             login: false,
+            config_path: None,
+            project_config: false,
+            preset: None,
+            turbo: false,
+            dump_preset: None,
+            migrate_config: false,
+            calibrate: false,
+            transcript_history: None,
+            transcript_history_encrypt_cmd: None,
+            redact_transcripts: false,
+            translate_to: None,
+            translate_cmd: None,
+            command: None,
+            session_record: None,
+            session_hook: None,
+            startup_profile: false,
+            target: Vec::new(),
+            feedback_dir: None,
+            type_delay_ms: None,
+            echo_check_ms: None,
+            transcript_queue_size: 5,
+            transcript_queue_policy: TranscriptQueuePolicy::DropOldest,
+            bracketed_paste: BracketedPasteMode::Multiline,
+            web_ui: false,
+            web_ui_port: None,
+            ws_listen: None,
+            control: false,
+            control_socket: None,
         }
     }
 
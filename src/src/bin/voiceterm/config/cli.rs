@@ -1,14 +1,73 @@
 //! CLI flag schema so overlay startup behavior is explicit and discoverable.
 
-use clap::{Parser, ValueEnum};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::net::SocketAddr;
 use std::path::PathBuf;
+use std::time::Duration;
 use voiceterm::config::AppConfig;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
+use crate::pipe::parse_timeout;
+use crate::voice_control::QuietHours;
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default, serde::Deserialize, serde::Serialize,
+)]
+#[serde(rename_all = "lowercase")]
 pub(crate) enum VoiceSendMode {
     #[default]
     Auto,
     Insert,
+    Clipboard,
+}
+
+/// How transcripts containing newlines are delivered to the backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
+pub(crate) enum MultilineMode {
+    /// Bracketed paste when the backend enables it, else soft newlines, else join.
+    #[default]
+    Paste,
+    /// Backend soft-newline sequence when known, else bracketed paste, else join.
+    SoftNewline,
+    /// Join lines with spaces.
+    Join,
+}
+
+/// What happens to a transcript that arrives with the pending queue full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
+pub(crate) enum TranscriptQueuePolicy {
+    /// Drop the oldest queued transcript to make room.
+    #[default]
+    DropOldest,
+    /// Drop the transcript that just arrived.
+    DropNewest,
+    /// Keep every transcript and hold off new captures until the queue drains.
+    Block,
+    /// Append each transcript to the newest queued one with the same send mode.
+    MergeImmediately,
+}
+
+/// Which transcripts are wrapped in bracketed paste when the backend has enabled it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
+pub(crate) enum BracketedPasteMode {
+    /// Only transcripts with line breaks (as chosen by `--multiline`).
+    #[default]
+    Multiline,
+    /// Every transcript, so single-line text cannot trigger backend shortcuts.
+    All,
+    /// Never; multi-line text falls back to soft newlines or joining.
+    Off,
+}
+
+/// How the overlay draws its HUD and statuses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
+pub(crate) enum RenderMode {
+    /// Plain mode when terminfo shows no cursor addressing or save/restore cursor.
+    #[default]
+    Auto,
+    /// Always draw the cursor-addressed HUD.
+    Full,
+    /// Print status changes as plain lines and cap themes at 16 colors.
+    Plain,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
@@ -113,6 +172,14 @@ pub(crate) struct OverlayConfig {
     #[arg(long = "prompt-log")]
     pub(crate) prompt_log: Option<PathBuf>,
 
+    /// External prompt detector: receives cleaned output lines on stdin, prints a line per prompt
+    #[arg(
+        long = "prompt-detector-cmd",
+        env = "VOICETERM_PROMPT_DETECTOR_CMD",
+        value_name = "COMMAND"
+    )]
+    pub(crate) prompt_detector_cmd: Option<String>,
+
     /// Start in auto-voice mode
     #[arg(long = "auto-voice", default_value_t = false)]
     pub(crate) auto_voice: bool,
@@ -121,15 +188,91 @@ pub(crate) struct OverlayConfig {
     #[arg(long = "auto-voice-idle-ms", default_value_t = 1200)]
     pub(crate) auto_voice_idle_ms: u64,
 
+    /// Daily local-time window when auto-voice stays off, e.g. 22:00-08:00
+    #[arg(
+        long = "auto-voice-quiet-hours",
+        env = "VOICETERM_AUTO_VOICE_QUIET_HOURS",
+        value_name = "HH:MM-HH:MM"
+    )]
+    pub(crate) auto_voice_quiet_hours: Option<QuietHours>,
+
     /// Idle time before transcripts auto-send when a prompt has not been detected (ms)
     #[arg(long = "transcript-idle-ms", default_value_t = 250)]
     pub(crate) transcript_idle_ms: u64,
 
-    /// Voice transcript handling (auto = send newline, insert = leave for editing)
+    /// Hold auto-send transcripts this long and submit them as one turn (ms, 0 = off)
+    #[arg(long = "batch-window-ms", value_name = "MS", default_value_t = 0)]
+    pub(crate) batch_window_ms: u64,
+
+    /// Transcripts held while the CLI is busy (1-50)
+    #[arg(
+        long = "transcript-queue-size",
+        env = "VOICETERM_TRANSCRIPT_QUEUE_SIZE",
+        value_name = "N",
+        default_value_t = 5
+    )]
+    pub(crate) transcript_queue_size: usize,
+
+    /// What happens when a transcript arrives with the queue full (drop-oldest, drop-newest, block, merge-immediately)
+    #[arg(
+        long = "transcript-queue-policy",
+        env = "VOICETERM_TRANSCRIPT_QUEUE_POLICY",
+        value_enum,
+        default_value_t = TranscriptQueuePolicy::DropOldest
+    )]
+    pub(crate) transcript_queue_policy: TranscriptQueuePolicy,
+
+    /// Discard transcripts below this Whisper confidence (0.0-1.0) and re-arm capture (0 disables)
+    #[arg(
+        long = "min-confidence",
+        env = "VOICETERM_MIN_CONFIDENCE",
+        default_value_t = 0.0,
+        allow_negative_numbers = true
+    )]
+    pub(crate) min_confidence: f32,
+
+    /// Consecutive automatic re-captures after a failed or low-confidence transcription (0-3)
+    #[arg(long = "redictate-attempts", default_value_t = 1)]
+    pub(crate) redictate_attempts: u8,
+
+    /// Wrap words below this Whisper confidence (0.0-1.0) in `??` when inserting transcripts (0 disables)
+    #[arg(
+        long = "mark-low-confidence",
+        env = "VOICETERM_MARK_LOW_CONFIDENCE",
+        default_value_t = 0.0
+    )]
+    pub(crate) mark_low_confidence: f32,
+
+    /// Voice transcript handling (auto = send newline, insert = leave for editing, clipboard = copy instead of typing)
     #[arg(long = "voice-send-mode", value_enum, default_value_t = VoiceSendMode::Auto)]
     pub(crate) voice_send_mode: VoiceSendMode,
 
-    /// Color theme for status line (chatgpt, claude, codex, coral, catppuccin, dracula, gruvbox, nord, tokyonight, ansi, none)
+    /// In insert mode, collect transcripts in an editable draft that Enter types
+    #[arg(long = "dictation-buffer", default_value_t = false)]
+    pub(crate) dictation_buffer: bool,
+
+    /// Let spoken commands ("settings", "next", "select", "confirm") drive overlays and confirmations
+    #[arg(long = "voice-navigation", default_value_t = false)]
+    pub(crate) voice_navigation: bool,
+
+    /// Start with code mode on: spoken "new line", "indent", "open brace" become formatted code
+    #[arg(long = "code-mode", default_value_t = false)]
+    pub(crate) code_mode: bool,
+
+    /// Multi-line transcript delivery (paste, soft-newline, join)
+    #[arg(long = "multiline", value_enum, default_value_t = MultilineMode::Paste)]
+    pub(crate) multiline_mode: MultilineMode,
+
+    /// Bracketed-paste wrapping when the backend enables it (multiline, all, off)
+    #[arg(
+        long = "bracketed-paste",
+        env = "VOICETERM_BRACKETED_PASTE",
+        value_enum,
+        default_value_t = BracketedPasteMode::Multiline
+    )]
+    pub(crate) bracketed_paste: BracketedPasteMode,
+
+    /// Color theme for status line (chatgpt, claude, codex, coral, catppuccin, dracula, gruvbox, nord, solarized, tokyonight, ansi, none, or a custom theme from the config file)
     /// Defaults to the backend-specific theme if not provided.
     #[arg(long = "theme")]
     pub(crate) theme_name: Option<String>,
@@ -170,6 +313,39 @@ pub(crate) struct OverlayConfig {
     #[arg(long = "minimal-hud", default_value_t = false)]
     pub(crate) minimal_hud: bool,
 
+    /// Overdraw the HUD over the bottom rows instead of reserving them with a terminal scroll region
+    #[arg(long = "no-scroll-region", default_value_t = false)]
+    pub(crate) no_scroll_region: bool,
+
+    /// Keep the idle status as `Ready` instead of showing the backend's latest output line during long answers
+    #[arg(long = "no-output-preview", default_value_t = false)]
+    pub(crate) no_output_preview: bool,
+
+    /// HUD rendering (auto = probe terminfo, full = always draw the HUD, plain = text-only statuses)
+    #[arg(
+        long = "render-mode",
+        env = "VOICETERM_RENDER_MODE",
+        value_enum,
+        default_value_t = RenderMode::Auto
+    )]
+    pub(crate) render_mode: RenderMode,
+
+    /// Start even if another VoiceTerm overlay is already running on this terminal
+    #[arg(long = "allow-same-tty", default_value_t = false)]
+    pub(crate) allow_same_tty: bool,
+
+    /// Print each transcript to stdout instead of running a backend (no PTY); Ctrl+C stops
+    #[arg(long = "pipe", default_value_t = false)]
+    pub(crate) pipe: bool,
+
+    /// Capture one utterance, print its transcript to stdout, and exit (implies --pipe)
+    #[arg(long = "once", default_value_t = false)]
+    pub(crate) once: bool,
+
+    /// With --pipe or --once, print JSON lines (text, source, confidence, speech_ms) instead of plain text
+    #[arg(long = "pipe-json", default_value_t = false)]
+    pub(crate) pipe_json: bool,
+
     /// Backend CLI to run (codex, claude, gemini, or custom command)
     ///
     /// Use a preset name or provide a custom command string.
@@ -195,4 +371,323 @@ pub(crate) struct OverlayConfig {
     /// Run backend login before starting the overlay
     #[arg(long = "login", default_value_t = false)]
     pub(crate) login: bool,
+
+    /// Config file path (defaults to ~/.config/voiceterm/config.yaml)
+    #[arg(long = "config", env = "VOICETERM_CONFIG")]
+    pub(crate) config_path: Option<PathBuf>,
+
+    /// Layer the repository's .voiceterm/config.yaml (found from the git root) over the config file
+    #[arg(
+        long = "project-config",
+        env = "VOICETERM_PROJECT_CONFIG",
+        default_value_t = false
+    )]
+    pub(crate) project_config: bool,
+
+    /// Start from a bundled preset (laptop-quiet, office-noisy, remote-ssh, low-latency-gpu, turbo)
+    #[arg(long = "preset", env = "VOICETERM_PRESET", value_name = "NAME")]
+    pub(crate) preset: Option<String>,
+
+    /// Lowest-latency setup: streaming STT, short adaptive silence tail, tiny model (the turbo preset)
+    #[arg(long = "turbo", default_value_t = false)]
+    pub(crate) turbo: bool,
+
+    /// Print a bundled preset as a config file, then exit
+    #[arg(long = "dump-preset", value_name = "NAME")]
+    pub(crate) dump_preset: Option<String>,
+
+    /// Show how the config file would be migrated to the current schema (a diff), then exit without writing it
+    #[arg(long = "migrate-config", default_value_t = false)]
+    pub(crate) migrate_config: bool,
+
+    /// Measure room noise and a spoken phrase, save a VAD threshold and silence tail to the config file, then exit
+    #[arg(long = "calibrate", default_value_t = false)]
+    pub(crate) calibrate: bool,
+
+    /// Append each transcript to this history file (JSON lines)
+    #[arg(long = "transcript-history", env = "VOICETERM_TRANSCRIPT_HISTORY")]
+    pub(crate) transcript_history: Option<PathBuf>,
+
+    /// Encrypt history entries: command reading a transcript on stdin and printing ASCII-armored ciphertext
+    #[arg(
+        long = "transcript-history-encrypt-cmd",
+        env = "VOICETERM_TRANSCRIPT_HISTORY_ENCRYPT_CMD",
+        value_name = "COMMAND"
+    )]
+    pub(crate) transcript_history_encrypt_cmd: Option<String>,
+
+    /// Keep transcript text out of the debug log, prompt log, history file, and status messages (length and hash only)
+    #[arg(
+        long = "redact-transcripts",
+        env = "VOICETERM_REDACT_TRANSCRIPTS",
+        default_value_t = false,
+        conflicts_with = "session_record"
+    )]
+    pub(crate) redact_transcripts: bool,
+
+    /// Translate transcripts into this language (e.g. `en`) with --translate-cmd before sending
+    #[arg(
+        long = "translate-to",
+        env = "VOICETERM_TRANSLATE_TO",
+        value_name = "LANG",
+        requires = "translate_cmd"
+    )]
+    pub(crate) translate_to: Option<String>,
+
+    /// Translation command: reads a transcript on stdin and prints the translation; `{to}` is replaced by --translate-to
+    #[arg(
+        long = "translate-cmd",
+        env = "VOICETERM_TRANSLATE_CMD",
+        value_name = "COMMAND"
+    )]
+    pub(crate) translate_cmd: Option<String>,
+
+    /// Record PTY output, input, and voice events to this file for `replay-session`
+    #[arg(long = "session-record")]
+    pub(crate) session_record: Option<PathBuf>,
+
+    /// Session hook: receives each voice session event as a JSON line on stdin
+    #[arg(
+        long = "session-hook",
+        env = "VOICETERM_SESSION_HOOK",
+        value_name = "COMMAND"
+    )]
+    pub(crate) session_hook: Option<String>,
+
+    /// Accept text from `voiceterm send` on the control socket
+    #[arg(long = "control", default_value_t = false)]
+    pub(crate) control: bool,
+
+    /// Control socket path; implies --control (default: $XDG_RUNTIME_DIR/voiceterm-control.sock)
+    #[arg(
+        long = "control-socket",
+        env = "VOICETERM_CONTROL_SOCKET",
+        value_name = "PATH"
+    )]
+    pub(crate) control_socket: Option<PathBuf>,
+
+    /// Serve a status page with capture buttons on 127.0.0.1
+    #[arg(long = "web-ui", default_value_t = false)]
+    pub(crate) web_ui: bool,
+
+    /// Web UI port; implies --web-ui (default: 8765)
+    #[arg(
+        long = "web-ui-port",
+        env = "VOICETERM_WEB_UI_PORT",
+        value_name = "PORT"
+    )]
+    pub(crate) web_ui_port: Option<u16>,
+
+    /// Serve a WebSocket bridge of capture events and start/stop commands (loopback only, e.g. 127.0.0.1:7700)
+    #[arg(long = "ws-listen", env = "VOICETERM_WS_LISTEN", value_name = "ADDR")]
+    pub(crate) ws_listen: Option<SocketAddr>,
+
+    /// Print per-phase startup timings on exit and log them as a startup_profile event
+    #[arg(long = "startup-profile", default_value_t = false)]
+    pub(crate) startup_profile: bool,
+
+    /// Route transcripts to a tmux pane (tmux:<pane>) as well as the wrapped backend; repeatable, cycle with the cycle_target key
+    #[arg(
+        long = "target",
+        env = "VOICETERM_TARGET",
+        value_name = "TARGET",
+        value_delimiter = ','
+    )]
+    pub(crate) target: Vec<String>,
+
+    /// Where flagged transcripts and their corrections are kept (default: $XDG_DATA_HOME/voiceterm/feedback)
+    #[arg(
+        long = "feedback-dir",
+        env = "VOICETERM_FEEDBACK_DIR",
+        value_name = "DIR"
+    )]
+    pub(crate) feedback_dir: Option<PathBuf>,
+
+    /// Type transcripts into the backend one character at a time, MS apart (overrides the config file's backend profile; max 100)
+    #[arg(
+        long = "type-delay-ms",
+        env = "VOICETERM_TYPE_DELAY_MS",
+        value_name = "MS"
+    )]
+    pub(crate) type_delay_ms: Option<u64>,
+
+    /// Check that transcripts typed into the backend appear in its output within MS; retry once at the next prompt, then warn (0 = off)
+    #[arg(
+        long = "echo-check-ms",
+        env = "VOICETERM_ECHO_CHECK_MS",
+        value_name = "MS"
+    )]
+    pub(crate) echo_check_ms: Option<u64>,
+
+    #[command(subcommand)]
+    pub(crate) command: Option<OverlayCommand>,
+}
+
+/// Alternate overlay run modes.
+#[derive(Debug, Clone, Subcommand)]
+pub(crate) enum OverlayCommand {
+    /// Re-inject transcripts from a history file into a fresh backend session
+    ReplayPrompts(ReplayPromptsArgs),
+    /// Replay a --session-record file through prompt detection and transcript delivery
+    ReplaySession(ReplaySessionArgs),
+    /// Run capture/transcription headless, controlled over a Unix socket (no PTY)
+    Daemon(DaemonArgs),
+    /// Serve voice tools (capture_voice, get_last_transcript, speak) to agents over MCP on stdio
+    Mcp(McpArgs),
+    /// Run an audio file through the VAD and STT pipeline and print what dictation would type
+    Transcribe(TranscribeArgs),
+    /// Record one utterance, print its transcript, and exit 0 (transcript), 1 (error), or 3 (no speech)
+    Capture(CaptureArgs),
+    /// Deliver text through a running overlay started with --control
+    Send(SendArgs),
+    /// Stream prompt ready/busy and voice session events from a running overlay started with --control
+    Events(EventsArgs),
+    /// Write flagged transcripts with audio as an evaluation set (manifest.jsonl + audio/)
+    ExportFeedback(ExportFeedbackArgs),
+    /// Move user state (config, macros, learned corrections, voice print) between machines
+    Profile(ProfileArgs),
+}
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct ReplayPromptsArgs {
+    /// Transcript history file (JSON lines from --transcript-history, or one prompt per line)
+    pub(crate) file: PathBuf,
+
+    /// Prompts to replay by 1-based index (e.g. "1,3-5"); defaults to all
+    #[arg(long = "select")]
+    pub(crate) select: Option<String>,
+
+    /// Minimum delay before each replayed prompt (ms)
+    #[arg(long = "pace-ms", default_value_t = 1500)]
+    pub(crate) pace_ms: u64,
+
+    /// Command that decrypts one --transcript-history-encrypt-cmd entry from stdin to stdout
+    #[arg(long = "decrypt-cmd", value_name = "COMMAND")]
+    pub(crate) decrypt_cmd: Option<String>,
+}
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct ReplaySessionArgs {
+    /// Session record file written with --session-record
+    pub(crate) file: PathBuf,
+}
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct DaemonArgs {
+    /// Control socket path (default: $XDG_RUNTIME_DIR/voiceterm.sock, else $TMPDIR/voiceterm-<uid>.sock)
+    #[arg(long = "socket", env = "VOICETERM_DAEMON_SOCKET", value_name = "PATH")]
+    pub(crate) socket: Option<PathBuf>,
+
+    /// Type each transcript into this terminal (e.g. "$(tty)") via TIOCSTI
+    #[arg(long = "attach", env = "VOICETERM_ATTACH", value_name = "TTY")]
+    pub(crate) attach: Option<PathBuf>,
+
+    /// Type each transcript into the focused application (osascript, wtype, or xdotool)
+    #[arg(
+        long = "type-focused",
+        env = "VOICETERM_TYPE_FOCUSED",
+        default_value_t = false,
+        conflicts_with = "attach"
+    )]
+    pub(crate) type_focused: bool,
+}
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct McpArgs {
+    /// Longest capture_voice recording before it stops and transcribes (seconds)
+    #[arg(
+        long = "capture-timeout-secs",
+        env = "VOICETERM_MCP_CAPTURE_TIMEOUT_SECS",
+        default_value_t = 60,
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub(crate) capture_timeout_secs: u64,
+}
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct TranscribeArgs {
+    /// Audio file (WAV is read directly; MP3 and other formats are decoded with ffmpeg)
+    pub(crate) file: PathBuf,
+
+    /// Print JSON with per-segment timestamps instead of text lines
+    #[arg(long = "json", default_value_t = false)]
+    pub(crate) json: bool,
+}
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct CaptureArgs {
+    /// Longest recording before it stops and transcribes (e.g. 10s, 1500ms, 2m; bare numbers are seconds)
+    #[arg(
+        long = "timeout",
+        env = "VOICETERM_CAPTURE_TIMEOUT",
+        value_name = "DURATION",
+        default_value = "30s",
+        value_parser = parse_timeout
+    )]
+    pub(crate) timeout: Duration,
+
+    /// Print a JSON object (text, source, confidence, speech_ms) instead of plain text
+    #[arg(long = "json", default_value_t = false)]
+    pub(crate) json: bool,
+}
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct SendArgs {
+    /// Text to deliver (words are joined with spaces); reads stdin when omitted or "-"
+    pub(crate) text: Vec<String>,
+
+    /// Send mode for this text (defaults to the running overlay's current mode)
+    #[arg(long = "mode", value_enum)]
+    pub(crate) mode: Option<VoiceSendMode>,
+
+    /// Control socket of the running overlay (default: $XDG_RUNTIME_DIR/voiceterm-control.sock)
+    #[arg(long = "socket", env = "VOICETERM_CONTROL_SOCKET", value_name = "PATH")]
+    pub(crate) socket: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct EventsArgs {
+    /// Print the current state and exit instead of streaming
+    #[arg(long = "once", default_value_t = false)]
+    pub(crate) once: bool,
+
+    /// Control socket of the running overlay (default: $XDG_RUNTIME_DIR/voiceterm-control.sock)
+    #[arg(long = "socket", env = "VOICETERM_CONTROL_SOCKET", value_name = "PATH")]
+    pub(crate) socket: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct ExportFeedbackArgs {
+    /// Output directory for manifest.jsonl and the audio files
+    pub(crate) out: PathBuf,
+}
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct ProfileArgs {
+    #[command(subcommand)]
+    pub(crate) action: ProfileAction,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub(crate) enum ProfileAction {
+    /// Write all user state to one archive file (credential settings and audio are left out)
+    Export(ProfileExportArgs),
+    /// Restore user state from an archive written by `profile export`
+    Import(ProfileImportArgs),
+}
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct ProfileExportArgs {
+    /// Archive file to write
+    pub(crate) out: PathBuf,
+}
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct ProfileImportArgs {
+    /// Archive file written by `profile export`
+    pub(crate) file: PathBuf,
+
+    /// Replace existing files, keeping each old one as <name>.bak
+    #[arg(long = "force", default_value_t = false)]
+    pub(crate) force: bool,
 }
@@ -0,0 +1,695 @@
+//! User config-file loading so persistent preferences live outside CLI flags.
+//!
+//! The file is YAML (matching `.voiceterm/macros.yaml`) and is looked up at
+//! `--config <PATH>`, then `$XDG_CONFIG_HOME/voiceterm/config.yaml`, then
+//! `~/.config/voiceterm/config.yaml`. A missing default file is not an error.
+//! Files written for an older schema are migrated on load (see `migrate.rs`),
+//! and every write goes through a temp file and rename.
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use super::migrate::{migrate, migrate_root, to_yaml, CONFIG_VERSION};
+
+const CONFIG_DIR_NAME: &str = "voiceterm";
+const CONFIG_FILE_NAME: &str = "config.yaml";
+/// Sections a project config file may not set.
+const PERSONAL_SECTIONS: &[&str] = &["settings", "devices"];
+
+/// One or more key specs bound to a single overlay action.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum KeySpecList {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeySpecList {
+    pub(crate) fn specs(&self) -> Vec<&str> {
+        match self {
+            KeySpecList::One(spec) => vec![spec.as_str()],
+            KeySpecList::Many(specs) => specs.iter().map(String::as_str).collect(),
+        }
+    }
+}
+
+/// Case normalization applied after other post-processing rules.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum TranscriptCase {
+    #[default]
+    None,
+    Lower,
+    Sentence,
+}
+
+/// One regex replacement, e.g. `parens` -> `()`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub(crate) struct ReplacementRule {
+    pub(crate) pattern: String,
+    #[serde(default)]
+    pub(crate) replace: String,
+    /// Dictation casing is unpredictable, so patterns ignore case unless told otherwise.
+    #[serde(default = "default_ignore_case")]
+    pub(crate) ignore_case: bool,
+}
+
+fn default_ignore_case() -> bool {
+    true
+}
+
+/// `postprocess` section: transcript cleanup applied before delivery.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub(crate) struct PostprocessConfig {
+    pub(crate) strip_fillers: bool,
+    /// Custom filler words; replaces the built-in list when set.
+    pub(crate) fillers: Option<Vec<String>>,
+    pub(crate) replacements: Vec<ReplacementRule>,
+    pub(crate) case: TranscriptCase,
+}
+
+/// `urgent` section: keywords that flag a transcript before it reaches the backend.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub(crate) struct UrgentConfig {
+    /// Words or phrases matched case-insensitively on word boundaries, e.g. `cancel the deploy`.
+    pub(crate) keywords: Vec<String>,
+    /// Hold matching transcripts until Enter confirms them (Esc discards).
+    pub(crate) confirm: bool,
+}
+
+/// `secrets` section: credential-like text that must be confirmed before it reaches the backend.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub(crate) struct SecretsConfig {
+    /// Check built-in API key, token, and private key formats.
+    pub(crate) api_keys: bool,
+    /// Also hold transcripts containing an email address.
+    pub(crate) emails: bool,
+    /// Extra regexes, e.g. an internal hostname or account ID format.
+    pub(crate) patterns: Vec<String>,
+}
+
+impl Default for SecretsConfig {
+    fn default() -> Self {
+        Self {
+            api_keys: true,
+            emails: false,
+            patterns: Vec::new(),
+        }
+    }
+}
+
+/// `prompt` section: regexes that mark the backend's input prompt.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub(crate) struct PromptConfig {
+    /// Prompt patterns tried in order after `--prompt-regex`; the first match wins.
+    pub(crate) patterns: Vec<String>,
+    /// Lines matching any of these are never prompts, e.g. a confirmation dialog ending in `>`.
+    pub(crate) never_patterns: Vec<String>,
+}
+
+/// `project` section: context added to transcripts for the repository VoiceTerm runs in.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub(crate) struct ProjectConfig {
+    /// Text put in front of each dictated transcript; `{repo}` is the repository name.
+    pub(crate) prefix: Option<String>,
+}
+
+/// Per-backend entry under `backends`, keyed by backend label (e.g. `codex`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub(crate) struct BackendProfile {
+    /// Type transcripts one character at a time, this many ms apart, for TUIs that debounce input.
+    pub(crate) type_delay_ms: Option<u64>,
+}
+
+/// Per-device entry under `devices`, keyed by input device name as `--list-input-devices` prints it.
+///
+/// Applied whenever the recorder opens that device; `--calibrate` writes it.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub(crate) struct DeviceProfile {
+    pub(crate) voice_vad_threshold_db: Option<f32>,
+    pub(crate) voice_silence_tail_ms: Option<u64>,
+}
+
+/// Custom palette under `themes`, keyed by the name `--theme` selects it with.
+///
+/// Colors are `#rrggbb` hex; anything left out comes from `base`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub(crate) struct ThemePalette {
+    /// Built-in theme the palette starts from (default `coral`).
+    pub(crate) base: Option<String>,
+    pub(crate) recording: Option<String>,
+    pub(crate) processing: Option<String>,
+    pub(crate) success: Option<String>,
+    pub(crate) warning: Option<String>,
+    pub(crate) error: Option<String>,
+    pub(crate) info: Option<String>,
+    pub(crate) dim: Option<String>,
+    pub(crate) border: Option<String>,
+    /// Background behind the HUD and overlays; unset keeps the terminal's own.
+    pub(crate) status_bg: Option<String>,
+}
+
+/// Parsed contents of the user config file.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub(crate) struct UserConfig {
+    /// Overlay action name -> key spec(s), e.g. `voice_trigger: ctrl+r`.
+    pub(crate) keybindings: BTreeMap<String, KeySpecList>,
+    /// Transcript post-processing rules.
+    pub(crate) postprocess: PostprocessConfig,
+    /// Keywords that render delivery as a warning and optionally require confirmation.
+    pub(crate) urgent: UrgentConfig,
+    /// Credential patterns that hold a transcript until Enter confirms it.
+    pub(crate) secrets: SecretsConfig,
+    /// Extra prompt patterns and never-prompt patterns for prompt detection.
+    pub(crate) prompt: PromptConfig,
+    /// Context for the current repository, such as a transcript prefix.
+    pub(crate) project: ProjectConfig,
+    /// Backend label -> profile applied only when that backend is wrapped.
+    pub(crate) backends: BTreeMap<String, BackendProfile>,
+    /// Input device name -> VAD levels used while recording from that device.
+    pub(crate) devices: BTreeMap<String, DeviceProfile>,
+    /// Theme name -> custom palette, selectable like a built-in theme.
+    pub(crate) themes: BTreeMap<String, ThemePalette>,
+    /// Flag defaults keyed by long flag name, e.g. `voice-vad-threshold-db: -45`.
+    pub(crate) settings: BTreeMap<String, serde_yaml::Value>,
+    /// Path the config was loaded from (not part of the YAML schema).
+    #[serde(skip)]
+    pub(crate) source_path: Option<PathBuf>,
+    /// Project config file layered on top by `--project-config`, if one was found.
+    #[serde(skip)]
+    pub(crate) project_path: Option<PathBuf>,
+}
+
+impl UserConfig {
+    /// Load the config from an explicit path or the default location.
+    pub(crate) fn load(explicit_path: Option<&Path>) -> Result<Self> {
+        match explicit_path {
+            Some(path) => Self::load_from_path(path),
+            None => match default_config_path() {
+                Some(path) if path.exists() => Self::load_from_path(&path),
+                _ => Ok(Self::default()),
+            },
+        }
+    }
+
+    pub(crate) fn load_from_path(path: &Path) -> Result<Self> {
+        let mut contents = fs::read_to_string(path)
+            .with_context(|| format!("failed to read config file {}", path.display()))?;
+        let migration = migrate(&contents)
+            .with_context(|| format!("invalid config file {}", path.display()))?;
+        if let Some(migration) = migration {
+            let backup = backup_path(path, migration.from);
+            let saved = fs::copy(path, &backup)
+                .with_context(|| format!("failed to back up to {}", backup.display()))
+                .and_then(|_| write_atomic(path, &migration.contents));
+            match saved {
+                Ok(()) => eprintln!(
+                    "voiceterm: migrated {} to config schema v{CONFIG_VERSION} (previous file: {})",
+                    path.display(),
+                    backup.display()
+                ),
+                // A read-only config still loads; the migration just is not persisted.
+                Err(err) => eprintln!(
+                    "voiceterm: warning: could not migrate {} to config schema v{CONFIG_VERSION}: {err:#}",
+                    path.display()
+                ),
+            }
+            contents = migration.contents;
+        }
+        let mut config = Self::parse(&contents)
+            .with_context(|| format!("invalid config file {}", path.display()))?;
+        config.source_path = Some(path.to_path_buf());
+        Ok(config)
+    }
+
+    pub(crate) fn parse(raw: &str) -> Result<Self> {
+        if raw.trim().is_empty() {
+            return Ok(Self::default());
+        }
+        Ok(serde_yaml::from_str(raw)?)
+    }
+
+    /// Layer a project config file over this one; returns the sections it may not set.
+    ///
+    /// Map sections (`keybindings`, `backends`, `themes`) are merged entry by
+    /// entry; any other section the project file contains replaces this one's.
+    /// `settings` and `devices` stay personal, since flags can name commands to
+    /// run and device levels belong to one machine.
+    pub(crate) fn merge_project(&mut self, raw: &str) -> Result<Vec<&'static str>> {
+        if raw.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+        let value: serde_yaml::Value = serde_yaml::from_str(raw)?;
+        let serde_yaml::Value::Mapping(mut root) = value else {
+            bail!("project config file is not a YAML mapping");
+        };
+        migrate_root(&mut root)?;
+        let has = |section: &str| root.contains_key(section);
+        let project: Self = serde_yaml::from_value(serde_yaml::Value::Mapping(root.clone()))?;
+        self.keybindings.extend(project.keybindings);
+        self.backends.extend(project.backends);
+        self.themes.extend(project.themes);
+        if has("postprocess") {
+            self.postprocess = project.postprocess;
+        }
+        if has("urgent") {
+            self.urgent = project.urgent;
+        }
+        if has("secrets") {
+            self.secrets = project.secrets;
+        }
+        if has("prompt") {
+            self.prompt = project.prompt;
+        }
+        if has("project") {
+            self.project = project.project;
+        }
+        Ok(PERSONAL_SECTIONS
+            .iter()
+            .copied()
+            .filter(|section| has(section))
+            .collect())
+    }
+}
+
+/// Write `settings` entries into the config file at `path`, creating it if needed.
+///
+/// Other sections and settings are kept, and the file is brought up to the
+/// current schema. The file is re-serialized, so YAML comments and key order
+/// outside `settings` are not preserved.
+pub(crate) fn save_settings(path: &Path, settings: &[(&str, serde_yaml::Value)]) -> Result<()> {
+    save_entries(path, &["settings"], settings)
+}
+
+/// Write entries into the `devices.<device>` profile, the same way [`save_settings`] does.
+pub(crate) fn save_device_profile(
+    path: &Path,
+    device: &str,
+    entries: &[(&str, serde_yaml::Value)],
+) -> Result<()> {
+    save_entries(path, &["devices", device], entries)
+}
+
+/// Merge `entries` into the mapping at `section` (a key path from the root).
+fn save_entries(
+    path: &Path,
+    section: &[&str],
+    entries: &[(&str, serde_yaml::Value)],
+) -> Result<()> {
+    use serde_yaml::{Mapping, Value};
+
+    let existing = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(err) => {
+            return Err(err)
+                .with_context(|| format!("failed to read config file {}", path.display()))
+        }
+    };
+    let mut root = if existing.trim().is_empty() {
+        Mapping::new()
+    } else {
+        match serde_yaml::from_str(&existing)
+            .with_context(|| format!("invalid config file {}", path.display()))?
+        {
+            Value::Mapping(root) => root,
+            _ => bail!("config file {} is not a YAML mapping", path.display()),
+        }
+    };
+    migrate_root(&mut root).with_context(|| format!("invalid config file {}", path.display()))?;
+    let mut mapping = &mut root;
+    for key in section {
+        let value = mapping
+            .entry(Value::from(*key))
+            .or_insert_with(|| Value::Mapping(Mapping::new()));
+        if !value.is_mapping() {
+            *value = Value::Mapping(Mapping::new());
+        }
+        let Value::Mapping(inner) = value else {
+            unreachable!("config section is a mapping");
+        };
+        mapping = inner;
+    }
+    for (name, value) in entries {
+        mapping.insert(Value::from(*name), value.clone());
+    }
+    write_atomic(path, &to_yaml(root)?)
+}
+
+/// Replace the file at `path` with `contents` via a temp file and rename.
+///
+/// A crash or full disk mid-write leaves the old file intact. A symlinked
+/// config (e.g. from a dotfiles repo) is written through to its target, and an
+/// existing file's permissions are kept.
+pub(crate) fn write_atomic(path: &Path, contents: &str) -> Result<()> {
+    let target = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    if let Some(dir) = target.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    }
+    let name = target
+        .file_name()
+        .with_context(|| format!("{} is not a file path", target.display()))?;
+    let temp = target.with_file_name(format!(
+        ".{}.{}.tmp",
+        name.to_string_lossy(),
+        std::process::id()
+    ));
+    let written = (|| -> std::io::Result<()> {
+        let mut file = fs::File::create(&temp)?;
+        file.write_all(contents.as_bytes())?;
+        if let Ok(metadata) = fs::metadata(&target) {
+            file.set_permissions(metadata.permissions())?;
+        }
+        file.sync_all()?;
+        fs::rename(&temp, &target)
+    })();
+    if written.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    written.with_context(|| format!("failed to write {}", target.display()))
+}
+
+/// Where a pre-migration copy of `path` is kept, e.g. `config.yaml.v0.bak`.
+fn backup_path(path: &Path, version: u64) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".v{version}.bak"));
+    path.with_file_name(name)
+}
+
+/// Default config location, honoring `XDG_CONFIG_HOME` before `~/.config`.
+pub(crate) fn default_config_path() -> Option<PathBuf> {
+    let base = env::var_os("XDG_CONFIG_HOME")
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            env::var_os("HOME")
+                .filter(|value| !value.is_empty())
+                .map(|home| PathBuf::from(home).join(".config"))
+        })?;
+    Some(base.join(CONFIG_DIR_NAME).join(CONFIG_FILE_NAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn parse_accepts_single_and_list_key_specs() {
+        let config = UserConfig::parse(
+            r#"
+keybindings:
+  voice_trigger: ctrl+r
+  decrease_sensitivity: [ctrl+backslash, "ctrl+_"]
+"#,
+        )
+        .expect("config should parse");
+        assert_eq!(
+            config.keybindings.get("voice_trigger"),
+            Some(&KeySpecList::One("ctrl+r".to_string()))
+        );
+        assert_eq!(
+            config
+                .keybindings
+                .get("decrease_sensitivity")
+                .map(KeySpecList::specs),
+            Some(vec!["ctrl+backslash", "ctrl+_"])
+        );
+    }
+
+    #[test]
+    fn parse_reads_postprocess_section() {
+        let config = UserConfig::parse(
+            r#"
+postprocess:
+  strip_fillers: true
+  case: sentence
+  replacements:
+    - pattern: '\bparens\b'
+      replace: "()"
+    - pattern: 'TODO'
+      replace: 'todo'
+      ignore_case: false
+"#,
+        )
+        .expect("config should parse");
+        let postprocess = &config.postprocess;
+        assert!(postprocess.strip_fillers);
+        assert_eq!(postprocess.fillers, None);
+        assert_eq!(postprocess.case, TranscriptCase::Sentence);
+        assert_eq!(postprocess.replacements.len(), 2);
+        assert_eq!(postprocess.replacements[0].pattern, r"\bparens\b");
+        assert!(postprocess.replacements[0].ignore_case);
+        assert!(!postprocess.replacements[1].ignore_case);
+    }
+
+    #[test]
+    fn parse_reads_urgent_section() {
+        let config = UserConfig::parse(
+            r#"
+urgent:
+  keywords: [stop, "cancel the deploy"]
+  confirm: true
+"#,
+        )
+        .expect("config should parse");
+        assert_eq!(config.urgent.keywords, vec!["stop", "cancel the deploy"]);
+        assert!(config.urgent.confirm);
+        assert_eq!(
+            UserConfig::parse("urgent: {}").unwrap().urgent,
+            UrgentConfig::default()
+        );
+    }
+
+    #[test]
+    fn merge_project_layers_shared_sections_and_skips_personal_ones() {
+        let mut config = UserConfig::parse(
+            r#"
+keybindings:
+  voice_trigger: ctrl+r
+  flag_transcript: ctrl+g
+urgent:
+  keywords: [stop]
+settings:
+  theme: coral
+"#,
+        )
+        .unwrap();
+        let ignored = config
+            .merge_project(
+                r#"
+keybindings:
+  flag_transcript: alt+f
+prompt:
+  patterns: ['^› ']
+settings:
+  session-hook: ./exfiltrate.sh
+devices:
+  USB Mic:
+    voice-vad-threshold-db: -40
+"#,
+            )
+            .expect("project config merges");
+        assert_eq!(ignored, vec!["settings", "devices"]);
+        assert_eq!(config.keybindings.len(), 2);
+        assert_eq!(
+            config.keybindings.get("flag_transcript"),
+            Some(&KeySpecList::One("alt+f".to_string()))
+        );
+        assert_eq!(config.prompt.patterns, vec!["^› "]);
+        assert_eq!(config.urgent.keywords, vec!["stop"]);
+        assert!(!config.settings.contains_key("session-hook"));
+        assert!(config.devices.is_empty());
+    }
+
+    #[test]
+    fn parse_reads_secrets_section() {
+        let config = UserConfig::parse(
+            r#"
+secrets:
+  emails: true
+  patterns: ['\bACME-\d{6}\b']
+"#,
+        )
+        .expect("config should parse");
+        assert!(config.secrets.api_keys);
+        assert!(config.secrets.emails);
+        assert_eq!(config.secrets.patterns, vec![r"\bACME-\d{6}\b"]);
+        assert!(UserConfig::default().secrets.api_keys);
+    }
+
+    #[test]
+    fn parse_reads_prompt_section() {
+        let config = UserConfig::parse(
+            r#"
+prompt:
+  patterns: ['^› ', '^codex> $']
+  never_patterns: ['\(y/n\) >$']
+"#,
+        )
+        .expect("config should parse");
+        assert_eq!(config.prompt.patterns, vec!["^› ", "^codex> $"]);
+        assert_eq!(config.prompt.never_patterns, vec![r"\(y/n\) >$"]);
+        assert_eq!(UserConfig::default().prompt, PromptConfig::default());
+    }
+
+    #[test]
+    fn parse_reads_settings_section() {
+        let config = UserConfig::parse(
+            r#"
+settings:
+  voice-vad-threshold-db: -45
+  denoise: true
+  hud-style: minimal
+"#,
+        )
+        .expect("config should parse");
+        assert_eq!(config.settings.len(), 3);
+        assert_eq!(
+            config.settings.get("hud-style"),
+            Some(&serde_yaml::Value::from("minimal"))
+        );
+    }
+
+    #[test]
+    fn parse_empty_file_yields_defaults() {
+        assert_eq!(UserConfig::parse("  \n").unwrap(), UserConfig::default());
+    }
+
+    #[test]
+    fn load_reports_missing_explicit_path() {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        let path = env::temp_dir().join(format!("voiceterm-missing-config-{unique}.yaml"));
+        assert!(UserConfig::load(Some(&path)).is_err());
+    }
+
+    #[test]
+    fn load_from_path_records_source() {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        let path = env::temp_dir().join(format!("voiceterm-config-{unique}.yaml"));
+        fs::write(&path, "keybindings:\n  exit: ctrl+q\n").expect("write config");
+        let config = UserConfig::load(Some(&path)).expect("config should load");
+        assert_eq!(config.source_path.as_deref(), Some(path.as_path()));
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(backup_path(&path, 0));
+    }
+
+    #[test]
+    fn load_migrates_old_files_in_place_and_keeps_a_backup() {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        let dir = env::temp_dir().join(format!("voiceterm-migrate-{unique}"));
+        let path = dir.join("config.yaml");
+        let original = "# hotkeys\nkeybindings:\n  exit: ctrl+q\n";
+        write_atomic(&path, original).expect("seed config");
+        let config = UserConfig::load(Some(&path)).expect("config should load");
+        assert_eq!(
+            config.keybindings.get("exit"),
+            Some(&KeySpecList::One("ctrl+q".to_string()))
+        );
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            format!("version: {CONFIG_VERSION}\n{original}")
+        );
+        assert_eq!(fs::read_to_string(backup_path(&path, 0)).unwrap(), original);
+        let leftovers = fs::read_dir(&dir).unwrap().count();
+        assert_eq!(leftovers, 2, "temp file should be renamed away");
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn save_settings_merges_into_existing_file() {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        let path = env::temp_dir()
+            .join(format!("voiceterm-save-{unique}"))
+            .join("config.yaml");
+        save_settings(&path, &[("theme", "coral".into())]).expect("create config");
+        fs::write(
+            &path,
+            "keybindings:\n  exit: ctrl+q\nsettings:\n  denoise: true\n  theme: coral\n",
+        )
+        .expect("seed config");
+        save_settings(
+            &path,
+            &[
+                ("theme", "dracula".into()),
+                ("voice-silence-tail-ms", 800.into()),
+            ],
+        )
+        .expect("save settings");
+        assert!(fs::read_to_string(&path)
+            .unwrap()
+            .starts_with(&format!("version: {CONFIG_VERSION}\n")));
+        let config = UserConfig::load(Some(&path)).expect("config should load");
+        assert_eq!(
+            config.keybindings.get("exit"),
+            Some(&KeySpecList::One("ctrl+q".to_string()))
+        );
+        assert_eq!(config.settings.get("denoise"), Some(&true.into()));
+        assert_eq!(config.settings.get("theme"), Some(&"dracula".into()));
+        assert_eq!(
+            config.settings.get("voice-silence-tail-ms"),
+            Some(&800.into())
+        );
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn save_device_profile_writes_under_devices() {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        let path = env::temp_dir()
+            .join(format!("voiceterm-device-{unique}"))
+            .join("config.yaml");
+        save_settings(&path, &[("voice-vad-threshold-db", (-40.0).into())]).expect("settings");
+        save_device_profile(
+            &path,
+            "USB Audio: Headset Mic",
+            &[
+                ("voice-vad-threshold-db", (-47.5).into()),
+                ("voice-silence-tail-ms", 650.into()),
+            ],
+        )
+        .expect("save device profile");
+        let config = UserConfig::load(Some(&path)).expect("config should load");
+        assert_eq!(
+            config.devices.get("USB Audio: Headset Mic"),
+            Some(&DeviceProfile {
+                voice_vad_threshold_db: Some(-47.5),
+                voice_silence_tail_ms: Some(650),
+            })
+        );
+        assert_eq!(
+            config.settings.get("voice-vad-threshold-db"),
+            Some(&(-40.0).into())
+        );
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+}
@@ -0,0 +1,214 @@
+//! Config-file schema versions so older files keep loading after upgrades.
+//!
+//! The top-level `version` key records the schema a file was written for; a
+//! file without one predates versioning and counts as schema 0. On load, files
+//! older than [`CONFIG_VERSION`] go through each step in [`MIGRATIONS`] and are
+//! written back in place, with the previous file kept alongside as
+//! `<name>.v<old>.bak`. `--migrate-config` prints the same change as a diff and
+//! writes nothing.
+
+use anyhow::{bail, Context, Result};
+use serde_yaml::{Mapping, Value};
+use std::fs;
+use std::path::Path;
+
+use super::file::default_config_path;
+
+/// Schema written by this build.
+pub(crate) const CONFIG_VERSION: u64 = 1;
+
+const VERSION_KEY: &str = "version";
+
+/// `MIGRATIONS[n]` upgrades a schema-`n` mapping to schema `n + 1`.
+const MIGRATIONS: [fn(&mut Mapping); CONFIG_VERSION as usize] = [
+    // Schema 1 only adds the `version` key.
+    |_| {},
+];
+
+/// A config file rewritten for the current schema.
+pub(crate) struct Migration {
+    /// Schema the file was written for.
+    pub(crate) from: u64,
+    pub(crate) contents: String,
+}
+
+/// Upgrade `root` in place to the current schema, returning the schema it started at.
+pub(super) fn migrate_root(root: &mut Mapping) -> Result<u64> {
+    let from = match root.get(VERSION_KEY) {
+        None => 0,
+        Some(value) => value
+            .as_u64()
+            .with_context(|| format!("config '{VERSION_KEY}' must be a whole number"))?,
+    };
+    if from > CONFIG_VERSION {
+        bail!(
+            "config schema v{from} is newer than this voiceterm supports (v{CONFIG_VERSION}); upgrade voiceterm"
+        );
+    }
+    for step in &MIGRATIONS[from as usize..] {
+        step(root);
+    }
+    root.insert(Value::from(VERSION_KEY), Value::from(CONFIG_VERSION));
+    Ok(from)
+}
+
+/// Rewrite `raw` for the current schema, or `None` when it is already current.
+///
+/// When a migration changes nothing but the version, only the `version` line is
+/// added or replaced, so comments and layout survive; otherwise the file is
+/// re-serialized.
+pub(super) fn migrate(raw: &str) -> Result<Option<Migration>> {
+    if raw.trim().is_empty() {
+        return Ok(None);
+    }
+    let Value::Mapping(original) = serde_yaml::from_str::<Value>(raw)? else {
+        bail!("config file is not a YAML mapping");
+    };
+    let mut root = original.clone();
+    let from = migrate_root(&mut root)?;
+    if from == CONFIG_VERSION {
+        return Ok(None);
+    }
+    let unversioned = |mapping: &Mapping| {
+        let mut mapping = mapping.clone();
+        mapping.remove(VERSION_KEY);
+        mapping
+    };
+    let contents = if unversioned(&root) == unversioned(&original) {
+        stamp_version(raw)
+    } else {
+        to_yaml(root)?
+    };
+    Ok(Some(Migration { from, contents }))
+}
+
+/// Serialize a config mapping with `version` as its first key.
+pub(super) fn to_yaml(mut root: Mapping) -> Result<String> {
+    let mut ordered = Mapping::new();
+    ordered.insert(
+        Value::from(VERSION_KEY),
+        root.remove(VERSION_KEY)
+            .unwrap_or_else(|| Value::from(CONFIG_VERSION)),
+    );
+    ordered.extend(root);
+    Ok(serde_yaml::to_string(&ordered)?)
+}
+
+/// Put `version: CONFIG_VERSION` at the top of `raw`, dropping any older top-level `version` line.
+fn stamp_version(raw: &str) -> String {
+    let mut lines = raw
+        .lines()
+        .filter(|line| !line.starts_with("version:"))
+        .peekable();
+    let mut out = String::new();
+    if let Some(marker) = lines.next_if(|line| line.trim_end() == "---") {
+        out.push_str(marker);
+        out.push('\n');
+    }
+    out.push_str(&format!("{VERSION_KEY}: {CONFIG_VERSION}\n"));
+    for line in lines {
+        out.push_str(line);
+        out.push('\n');
+    }
+    out
+}
+
+/// `--migrate-config`: print how the config file would change, without writing it.
+pub(crate) fn run_migrate_config(explicit_path: Option<&Path>) -> Result<()> {
+    let Some(path) = explicit_path
+        .map(Path::to_path_buf)
+        .or_else(default_config_path)
+    else {
+        bail!("no config path: set --config or HOME");
+    };
+    let raw = match fs::read_to_string(&path) {
+        Ok(raw) => raw,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            println!("No config file at {}", path.display());
+            return Ok(());
+        }
+        Err(err) => {
+            return Err(err)
+                .with_context(|| format!("failed to read config file {}", path.display()))
+        }
+    };
+    match migrate(&raw).with_context(|| format!("invalid config file {}", path.display()))? {
+        None => println!(
+            "{} is already at config schema v{CONFIG_VERSION}",
+            path.display()
+        ),
+        Some(migration) => {
+            println!("--- {} (schema v{})", path.display(), migration.from);
+            println!("+++ {} (schema v{CONFIG_VERSION})", path.display());
+            print!("{}", line_diff(&raw, &migration.contents));
+        }
+    }
+    Ok(())
+}
+
+/// Whole-file line diff: unchanged lines start with a space, removals `-`, additions `+`.
+fn line_diff(old: &str, new: &str) -> String {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    // common[i][j]: longest common subsequence of old[i..] and new[j..].
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    let mut out = String::new();
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            out.push_str(&format!(" {}\n", old[i]));
+            i += 1;
+            j += 1;
+        } else if j < new.len() && (i == old.len() || common[i][j + 1] >= common[i + 1][j]) {
+            out.push_str(&format!("+{}\n", new[j]));
+            j += 1;
+        } else {
+            out.push_str(&format!("-{}\n", old[i]));
+            i += 1;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn migrate_stamps_unversioned_files_and_keeps_comments() {
+        let raw = "# my overlay settings\nsettings:\n  theme: coral # warm\n";
+        let migration = migrate(raw).unwrap().expect("v0 file should migrate");
+        assert_eq!(migration.from, 0);
+        assert_eq!(
+            migration.contents,
+            "version: 1\n# my overlay settings\nsettings:\n  theme: coral # warm\n"
+        );
+        assert!(migrate(&migration.contents).unwrap().is_none());
+        assert!(migrate("").unwrap().is_none());
+        assert_eq!(
+            stamp_version("---\nsettings: {}\n"),
+            "---\nversion: 1\nsettings: {}\n"
+        );
+    }
+
+    #[test]
+    fn migrate_rejects_newer_or_malformed_versions() {
+        let newer = migrate("version: 99\n").err().unwrap();
+        assert!(newer.to_string().contains("upgrade voiceterm"), "{newer}");
+        assert!(migrate("version: one\n").is_err());
+    }
+
+    #[test]
+    fn line_diff_marks_added_and_removed_lines() {
+        assert_eq!(line_diff("a\nb\nc\n", "v\na\nc\n"), "+v\n a\n-b\n c\n");
+    }
+}
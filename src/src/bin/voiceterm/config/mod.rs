@@ -2,13 +2,30 @@
 
 mod backend;
 mod cli;
+mod file;
+mod migrate;
+mod presets;
+mod project;
 mod theme;
 mod util;
 
 #[allow(unused_imports)]
 pub(crate) use backend::ResolvedBackend;
 pub(crate) use cli::{
-    HudBorderStyle, HudRightPanel, HudStyle, LatencyDisplayMode, OverlayConfig, VoiceSendMode,
+    BracketedPasteMode, CaptureArgs, DaemonArgs, EventsArgs, ExportFeedbackArgs, HudBorderStyle,
+    HudRightPanel, HudStyle, LatencyDisplayMode, McpArgs, MultilineMode, OverlayCommand,
+    OverlayConfig, ProfileAction, ProfileArgs, RenderMode, ReplayPromptsArgs, ReplaySessionArgs,
+    SendArgs, TranscribeArgs, TranscriptQueuePolicy, VoiceSendMode,
 };
+#[cfg(test)]
+pub(crate) use file::ReplacementRule;
+pub(crate) use file::{
+    default_config_path, save_device_profile, save_settings, write_atomic, BackendProfile,
+    DeviceProfile, KeySpecList, PostprocessConfig, ProjectConfig, PromptConfig, SecretsConfig,
+    ThemePalette, TranscriptCase, UrgentConfig, UserConfig,
+};
+pub(crate) use migrate::run_migrate_config;
+pub(crate) use presets::{find_preset, parse_overlay_config};
+pub(crate) use project::project_root;
 #[allow(unused_imports)]
 pub(crate) use theme::default_theme_for_backend;
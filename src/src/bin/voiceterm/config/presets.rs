@@ -0,0 +1,310 @@
+//! Bundled presets so common setups load by name instead of a dozen tuning flags.
+//!
+//! A preset is an example config file compiled into the binary. Its `settings`
+//! (long flag name -> value) apply first, the user's config-file `settings`
+//! override them, and environment variables and explicit flags override both.
+//! Those same flags and variables also override per-device `devices` levels.
+
+use anyhow::{anyhow, bail, Context, Result};
+use clap::{CommandFactory, Parser};
+use serde_yaml::Value;
+use std::collections::BTreeMap;
+use std::env;
+use std::ffi::OsString;
+use std::path::Path;
+
+use super::cli::OverlayConfig;
+use super::file::{DeviceProfile, UserConfig};
+use super::project::load_project_config;
+use crate::cli_utils::resolve_working_dir;
+
+/// Flags that pick the settings sources themselves, so settings cannot set them.
+const RESERVED_SETTINGS: &[&str] = &["config", "preset", "turbo", "dump-preset"];
+
+/// Example config file compiled into the binary.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Preset {
+    pub(crate) name: &'static str,
+    /// Full config file text, comments included, as printed by `--dump-preset`.
+    pub(crate) yaml: &'static str,
+}
+
+pub(crate) const PRESETS: &[Preset] = &[
+    Preset {
+        name: "laptop-quiet",
+        yaml: include_str!("presets/laptop-quiet.yaml"),
+    },
+    Preset {
+        name: "office-noisy",
+        yaml: include_str!("presets/office-noisy.yaml"),
+    },
+    Preset {
+        name: "remote-ssh",
+        yaml: include_str!("presets/remote-ssh.yaml"),
+    },
+    Preset {
+        name: "low-latency-gpu",
+        yaml: include_str!("presets/low-latency-gpu.yaml"),
+    },
+    Preset {
+        name: "turbo",
+        yaml: include_str!("presets/turbo.yaml"),
+    },
+];
+
+impl Preset {
+    pub(crate) fn config(&self) -> Result<UserConfig> {
+        UserConfig::parse(self.yaml)
+            .with_context(|| format!("bundled preset {} is invalid", self.name))
+    }
+}
+
+pub(crate) fn find_preset(name: &str) -> Result<&'static Preset> {
+    let name = name.trim();
+    PRESETS
+        .iter()
+        .find(|preset| preset.name.eq_ignore_ascii_case(name))
+        .ok_or_else(|| {
+            let available: Vec<&str> = PRESETS.iter().map(|preset| preset.name).collect();
+            anyhow!(
+                "unknown preset '{name}' (available: {})",
+                available.join(", ")
+            )
+        })
+}
+
+/// Parse the command line, then re-parse with preset and config-file settings underneath it.
+pub(crate) fn parse_overlay_config(argv: Vec<OsString>) -> Result<(OverlayConfig, UserConfig)> {
+    let config = OverlayConfig::parse_from(&argv);
+    if config.dump_preset.is_some() || config.migrate_config {
+        return Ok((config, UserConfig::default()));
+    }
+    let mut user_config = UserConfig::load(config.config_path.as_deref())?;
+    drop_overridden_device_levels(&argv, &mut user_config.devices);
+    let mut settings = BTreeMap::new();
+    // --turbo sits beneath --preset so a named preset can adjust it.
+    let presets = config
+        .turbo
+        .then_some("turbo")
+        .into_iter()
+        .chain(config.preset.as_deref());
+    for name in presets {
+        settings.extend(find_preset(name)?.config()?.settings);
+    }
+    settings.extend(user_config.settings.clone());
+    let config = if settings.is_empty() {
+        config
+    } else {
+        let argv = argv_with_settings(&argv, &settings)?;
+        OverlayConfig::try_parse_from(argv)
+            .context("invalid value in preset or config file settings")?
+    };
+    // Project files cannot set `settings`, so they layer in after the re-parse.
+    if config.project_config {
+        load_project_config(&mut user_config, Path::new(&resolve_working_dir()))?;
+    }
+    Ok((config, user_config))
+}
+
+/// Insert settings as flags ahead of the user's arguments, skipping any set explicitly.
+fn argv_with_settings(
+    argv: &[OsString],
+    settings: &BTreeMap<String, Value>,
+) -> Result<Vec<OsString>> {
+    let command = OverlayConfig::command();
+    let mut extra = Vec::new();
+    for (name, value) in settings {
+        if RESERVED_SETTINGS.contains(&name.as_str()) {
+            bail!("setting '{name}' cannot be set from a config file");
+        }
+        let arg = command
+            .get_arguments()
+            .find(|arg| arg.get_long() == Some(name.as_str()))
+            .ok_or_else(|| {
+                anyhow!(
+                    "unknown setting '{name}' (use a long flag name, e.g. voice-silence-tail-ms)"
+                )
+            })?;
+        if set_explicitly(argv, arg) {
+            continue;
+        }
+        let flag = format!("--{name}");
+        match value {
+            Value::Bool(enabled) if !arg.get_action().takes_values() => {
+                if *enabled {
+                    extra.push(OsString::from(flag));
+                }
+            }
+            Value::Sequence(items) => {
+                for item in items {
+                    extra.push(format!("{flag}={}", setting_scalar(name, item)?).into());
+                }
+            }
+            other => extra.push(format!("{flag}={}", setting_scalar(name, other)?).into()),
+        }
+    }
+    let mut merged = Vec::with_capacity(argv.len() + extra.len());
+    merged.extend(argv.first().cloned());
+    merged.extend(extra);
+    merged.extend(argv.iter().skip(1).cloned());
+    Ok(merged)
+}
+
+/// Clear `devices` levels whose flag was given on the command line or in the environment.
+fn drop_overridden_device_levels(argv: &[OsString], devices: &mut BTreeMap<String, DeviceProfile>) {
+    let command = OverlayConfig::command();
+    let explicit = |name: &str| {
+        command
+            .get_arguments()
+            .find(|arg| arg.get_long() == Some(name))
+            .is_some_and(|arg| set_explicitly(argv, arg))
+    };
+    let (threshold, tail) = (
+        explicit("voice-vad-threshold-db"),
+        explicit("voice-silence-tail-ms"),
+    );
+    for profile in devices.values_mut() {
+        if threshold {
+            profile.voice_vad_threshold_db = None;
+        }
+        if tail {
+            profile.voice_silence_tail_ms = None;
+        }
+    }
+}
+
+/// Whether `arg` was set by the user rather than left to settings or its default.
+fn set_explicitly(argv: &[OsString], arg: &clap::Arg) -> bool {
+    let from_env = arg.get_env().is_some_and(|var| env::var_os(var).is_some());
+    from_env
+        || arg
+            .get_long()
+            .is_some_and(|name| passed_on_command_line(argv, name))
+}
+
+fn passed_on_command_line(argv: &[OsString], name: &str) -> bool {
+    let flag = format!("--{name}");
+    let with_value = format!("{flag}=");
+    argv.iter()
+        .skip(1)
+        .map(|arg| arg.to_string_lossy())
+        .take_while(|arg| arg != "--")
+        .any(|arg| arg == flag || arg.starts_with(&with_value))
+}
+
+fn setting_scalar(name: &str, value: &Value) -> Result<String> {
+    match value {
+        Value::String(text) => Ok(text.clone()),
+        Value::Number(number) => Ok(number.to_string()),
+        Value::Bool(flag) => Ok(flag.to_string()),
+        _ => bail!("setting '{name}' must be a string, number, or boolean"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{HudStyle, LatencyDisplayMode};
+
+    fn os_args(args: &[&str]) -> Vec<OsString> {
+        args.iter().map(OsString::from).collect()
+    }
+
+    fn settings(raw: &str) -> BTreeMap<String, Value> {
+        UserConfig::parse(raw).expect("settings parse").settings
+    }
+
+    #[test]
+    fn every_bundled_preset_parses_into_overlay_flags() {
+        for preset in PRESETS {
+            let config = preset.config().expect("preset parses");
+            assert!(
+                !config.settings.is_empty(),
+                "{} has no settings",
+                preset.name
+            );
+            assert!(
+                preset
+                    .yaml
+                    .contains(&format!("--dump-preset {}", preset.name)),
+                "{} should explain how to save it",
+                preset.name
+            );
+            let argv = argv_with_settings(&os_args(&["voiceterm"]), &config.settings)
+                .expect("settings map to flags");
+            OverlayConfig::try_parse_from(argv).expect("preset flags parse");
+        }
+    }
+
+    #[test]
+    fn find_preset_is_case_insensitive_and_lists_names() {
+        assert_eq!(find_preset(" Office-Noisy ").unwrap().name, "office-noisy");
+        let err = find_preset("studio").unwrap_err().to_string();
+        assert!(
+            err.contains("laptop-quiet") && err.contains("remote-ssh"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn explicit_flags_override_settings() {
+        let argv = argv_with_settings(
+            &os_args(&["voiceterm", "--voice-silence-tail-ms", "900", "--hud-style=full"]),
+            &settings(
+                "settings:\n  voice-silence-tail-ms: 500\n  hud-style: minimal\n  denoise: true\n  latency-display: label\n",
+            ),
+        )
+        .unwrap();
+        let config = OverlayConfig::try_parse_from(argv).unwrap();
+        assert_eq!(config.app.voice_silence_tail_ms, 900);
+        assert_eq!(config.hud_style, HudStyle::Full);
+        assert!(config.app.denoise);
+        assert_eq!(config.latency_display, LatencyDisplayMode::Label);
+    }
+
+    #[test]
+    fn explicit_flags_override_device_levels() {
+        let mut devices = UserConfig::parse(
+            "devices:\n  Headset:\n    voice-vad-threshold-db: -48\n    voice-silence-tail-ms: 650\n",
+        )
+        .unwrap()
+        .devices;
+        drop_overridden_device_levels(
+            &os_args(&["voiceterm", "--voice-silence-tail-ms=900"]),
+            &mut devices,
+        );
+        assert_eq!(
+            devices["Headset"],
+            DeviceProfile {
+                voice_vad_threshold_db: Some(-48.0),
+                voice_silence_tail_ms: None,
+            }
+        );
+    }
+
+    #[test]
+    fn settings_support_negative_numbers_and_repeated_flags() {
+        let argv = argv_with_settings(
+            &os_args(&["voiceterm"]),
+            &settings(
+                "settings:\n  voice-vad-threshold-db: -42.5\n  codex-arg: [--model, o3]\n  denoise: false\n",
+            ),
+        )
+        .unwrap();
+        let config = OverlayConfig::try_parse_from(argv).unwrap();
+        assert_eq!(config.app.voice_vad_threshold_db, -42.5);
+        assert_eq!(config.app.codex_args, vec!["--model", "o3"]);
+        assert!(!config.app.denoise);
+    }
+
+    #[test]
+    fn settings_reject_unknown_and_reserved_names() {
+        let argv = os_args(&["voiceterm"]);
+        let err = argv_with_settings(&argv, &settings("settings:\n  voice-speed: 2\n"))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("unknown setting 'voice-speed'"), "{err}");
+        assert!(argv_with_settings(&argv, &settings("settings:\n  preset: remote-ssh\n")).is_err());
+        assert!(argv_with_settings(&argv, &settings("settings:\n  lang: {code: en}\n")).is_err());
+    }
+}
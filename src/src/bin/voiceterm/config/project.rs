@@ -0,0 +1,99 @@
+//! Per-project config so a team can share prompt patterns and keybindings through the repository.
+//!
+//! With `--project-config`, `.voiceterm/config.yaml` at the repository root
+//! (the nearest parent holding `.git`, else the working directory) is layered
+//! over the user's config file; see [`UserConfig::merge_project`] for which
+//! sections it may set. It is opt-in because a cloned repository's rules can
+//! rewrite what gets typed into the backend.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::file::UserConfig;
+
+/// Project config file, relative to the repository root (next to `macros.yaml`).
+const PROJECT_CONFIG_FILE: &str = ".voiceterm/config.yaml";
+
+/// Nearest directory at or above `dir` that holds `.git` (a directory, or a file in worktrees).
+pub(crate) fn find_git_root(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .find(|candidate| candidate.join(".git").exists())
+        .map(Path::to_path_buf)
+}
+
+/// Repository root for `dir`, or `dir` itself outside a repository.
+pub(crate) fn project_root(dir: &Path) -> PathBuf {
+    find_git_root(dir).unwrap_or_else(|| dir.to_path_buf())
+}
+
+/// Layer the project config for `working_dir` over `user_config`, if the project has one.
+pub(super) fn load_project_config(user_config: &mut UserConfig, working_dir: &Path) -> Result<()> {
+    let path = project_root(working_dir).join(PROJECT_CONFIG_FILE);
+    if !path.is_file() {
+        return Ok(());
+    }
+    let raw = fs::read_to_string(&path)
+        .with_context(|| format!("failed to read project config {}", path.display()))?;
+    let ignored = user_config
+        .merge_project(&raw)
+        .with_context(|| format!("invalid project config {}", path.display()))?;
+    for section in ignored {
+        eprintln!(
+            "voiceterm: warning: ignoring '{section}' in {} (set it in your own config file)",
+            path.display()
+        );
+    }
+    user_config.project_path = Some(path);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn temp_dir() -> PathBuf {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock")
+            .as_nanos();
+        std::env::temp_dir().join(format!(
+            "voiceterm-project-{now}-{}",
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ))
+    }
+
+    #[test]
+    fn project_config_is_found_from_the_git_root() {
+        let root = temp_dir();
+        let nested = root.join("src").join("bin");
+        fs::create_dir_all(&nested).expect("create dirs");
+        fs::create_dir_all(root.join(".git")).expect("create .git");
+        fs::create_dir_all(root.join(".voiceterm")).expect("create .voiceterm");
+        fs::write(
+            root.join(PROJECT_CONFIG_FILE),
+            "prompt:\n  patterns: ['^› ']\nproject:\n  prefix: 'In repo {repo}: '\nsettings:\n  backend: sh\n",
+        )
+        .expect("write project config");
+        assert_eq!(find_git_root(&nested).as_deref(), Some(root.as_path()));
+
+        let mut user_config = UserConfig::parse("settings:\n  theme: coral\n").unwrap();
+        load_project_config(&mut user_config, &nested).expect("project config loads");
+        assert_eq!(user_config.prompt.patterns, vec!["^› "]);
+        assert_eq!(
+            user_config.project.prefix.as_deref(),
+            Some("In repo {repo}: ")
+        );
+        assert_eq!(user_config.settings.len(), 1);
+        assert!(!user_config.settings.contains_key("backend"));
+        assert_eq!(
+            user_config.project_path.as_deref(),
+            Some(root.join(PROJECT_CONFIG_FILE).as_path())
+        );
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -30,7 +30,7 @@ impl OverlayConfig {
         if self.no_color {
             ColorMode::None
         } else {
-            ColorMode::detect()
+            self.render_mode.limit_colors(ColorMode::detect())
         }
     }
 }
@@ -0,0 +1,89 @@
+//! `voiceterm send` and `voiceterm events`: talk to a running overlay over its control socket.
+
+use anyhow::{bail, Context, Result};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::os::unix::net::UnixStream;
+use std::path::Path;
+
+use crate::config::{EventsArgs, SendArgs};
+
+use super::protocol::{encode_line, parse_response, ControlRequest, ControlResponse};
+use super::server::default_control_socket_path;
+
+/// Longer than the server's own reply timeout so its error message gets through.
+const CLIENT_READ_TIMEOUT_SECS: u64 = 10;
+
+/// Run `voiceterm send` and print where the text ended up.
+pub(crate) fn run_send(args: &SendArgs) -> Result<()> {
+    let text = if args.text.is_empty() || args.text == ["-"] {
+        let mut text = String::new();
+        io::stdin()
+            .read_to_string(&mut text)
+            .context("failed to read text from stdin")?;
+        text
+    } else {
+        args.text.join(" ")
+    };
+    if text.trim().is_empty() {
+        bail!("nothing to send");
+    }
+    let path = args
+        .socket
+        .clone()
+        .unwrap_or_else(default_control_socket_path);
+    let request = ControlRequest::Send {
+        text,
+        mode: args.mode,
+    };
+    match send_request(&path, &request)? {
+        ControlResponse::Delivered => println!("delivered"),
+        ControlResponse::Queued { position } => {
+            println!("queued at position {position} (waiting for the backend prompt)")
+        }
+        ControlResponse::Error { message } => bail!("voiceterm rejected the text: {message}"),
+    }
+    Ok(())
+}
+
+/// Run `voiceterm events`: print each event line as it arrives (or just the current state).
+pub(crate) fn run_events(args: &EventsArgs) -> Result<()> {
+    let path = args
+        .socket
+        .clone()
+        .unwrap_or_else(default_control_socket_path);
+    let mut stream = connect(&path)?;
+    stream.write_all(encode_line(&ControlRequest::Subscribe).as_bytes())?;
+    let mut stdout = io::stdout();
+    for line in BufReader::new(stream).lines() {
+        let line = line.context("control socket read failed")?;
+        writeln!(stdout, "{line}")?;
+        stdout.flush()?;
+        if args.once {
+            break;
+        }
+    }
+    Ok(())
+}
+
+fn connect(path: &Path) -> Result<UnixStream> {
+    UnixStream::connect(path).with_context(|| {
+        format!(
+            "no voiceterm control socket at {} (start voiceterm with --control)",
+            path.display()
+        )
+    })
+}
+
+/// Send one request and wait for its response line.
+pub(super) fn send_request(path: &Path, request: &ControlRequest) -> Result<ControlResponse> {
+    let mut stream = connect(path)?;
+    stream.set_read_timeout(Some(std::time::Duration::from_secs(
+        CLIENT_READ_TIMEOUT_SECS,
+    )))?;
+    stream.write_all(encode_line(request).as_bytes())?;
+    let mut line = String::new();
+    BufReader::new(stream)
+        .read_line(&mut line)
+        .context("no response from voiceterm")?;
+    parse_response(&line)
+}
@@ -0,0 +1,17 @@
+//! Control socket so other programs can push text through VoiceTerm's transcript delivery.
+//!
+//! With `--control` (or `--control-socket PATH`) the overlay accepts one JSON request
+//! per connection, e.g. `{"cmd":"send","text":"run the tests"}`, and `voiceterm send`
+//! is the bundled client. Sent text goes through the same queue and prompt-readiness
+//! checks as dictation, so it never types over a running command.
+//! `{"cmd":"subscribe"}` (or `voiceterm events`) instead streams prompt ready/busy
+//! transitions so status bars can mirror the overlay's readiness, plus voice
+//! session events (`{"type":"session","event":"capture_started",...}`).
+
+mod client;
+mod protocol;
+mod server;
+
+pub(crate) use client::{run_events, run_send};
+pub(crate) use protocol::ControlResponse;
+pub(crate) use server::{handle_control_request, ControlServer};
@@ -0,0 +1,145 @@
+//! Control socket protocol: one JSON request line in, one JSON response line out.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::config::VoiceSendMode;
+use crate::session_events::VoiceSessionEvent;
+
+/// Requests accepted on the control socket, tagged by `"cmd"`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "cmd", rename_all = "kebab-case")]
+pub(crate) enum ControlRequest {
+    /// Deliver `text` to the backend as if it had been dictated.
+    Send {
+        text: String,
+        /// Overrides the overlay's current send mode for this text.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        mode: Option<VoiceSendMode>,
+    },
+    /// Keep the connection open and stream `ControlEvent` lines, starting with the current state.
+    Subscribe,
+}
+
+/// Replies written back to the client, tagged by `"type"`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(crate) enum ControlResponse {
+    /// The text was sent to the backend (or clipboard) right away.
+    Delivered,
+    /// The text is waiting for the backend prompt at this 1-based queue position.
+    Queued {
+        position: usize,
+    },
+    Error {
+        message: String,
+    },
+}
+
+/// Whether the backend can take a transcript right now, as the overlay judges it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum PromptState {
+    Ready,
+    Busy,
+}
+
+/// Events streamed to `subscribe` clients, tagged by `"type"`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(crate) enum ControlEvent {
+    /// Prompt readiness changed; `reason` names the signal that decided it.
+    Prompt { state: PromptState, reason: String },
+    /// A voice session lifecycle event, e.g. a capture starting or a transcript delivered.
+    Session(VoiceSessionEvent),
+}
+
+pub(crate) fn parse_request(line: &str) -> Result<ControlRequest> {
+    serde_json::from_str(line.trim()).context("invalid control request")
+}
+
+pub(crate) fn parse_response(line: &str) -> Result<ControlResponse> {
+    serde_json::from_str(line.trim()).context("invalid control response")
+}
+
+/// Serialize a request or response as a single JSON line (newline included).
+pub(crate) fn encode_line(message: &impl Serialize) -> String {
+    let mut line = serde_json::to_string(message).unwrap_or_else(|err| {
+        format!(r#"{{"type":"error","message":"failed to encode message: {err}"}}"#)
+    });
+    line.push('\n');
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn send_requests_round_trip_with_optional_mode() {
+        assert_eq!(
+            parse_request(r#"{"cmd":"send","text":"run the tests"}"#).unwrap(),
+            ControlRequest::Send {
+                text: "run the tests".to_string(),
+                mode: None,
+            }
+        );
+        let request = ControlRequest::Send {
+            text: "ls".to_string(),
+            mode: Some(VoiceSendMode::Insert),
+        };
+        let line = encode_line(&request);
+        assert_eq!(
+            line,
+            "{\"cmd\":\"send\",\"text\":\"ls\",\"mode\":\"insert\"}\n"
+        );
+        assert_eq!(parse_request(&line).unwrap(), request);
+        assert!(parse_request(r#"{"cmd":"send"}"#).is_err());
+        assert!(parse_request(r#"{"cmd":"start"}"#).is_err());
+        assert_eq!(
+            parse_request(r#"{"cmd":"subscribe"}"#).unwrap(),
+            ControlRequest::Subscribe
+        );
+    }
+
+    #[test]
+    fn prompt_events_encode_state_and_reason() {
+        let event = ControlEvent::Prompt {
+            state: PromptState::Ready,
+            reason: "line_complete".to_string(),
+        };
+        assert_eq!(
+            encode_line(&event),
+            "{\"type\":\"prompt\",\"state\":\"ready\",\"reason\":\"line_complete\"}\n"
+        );
+    }
+
+    #[test]
+    fn session_events_nest_under_the_session_type() {
+        let event = ControlEvent::Session(VoiceSessionEvent::CaptureStarted {
+            trigger: "auto".to_string(),
+            source: "native".to_string(),
+        });
+        let line = encode_line(&event);
+        assert_eq!(
+            line,
+            "{\"type\":\"session\",\"event\":\"capture_started\",\"trigger\":\"auto\",\"source\":\"native\"}\n"
+        );
+        assert_eq!(
+            serde_json::from_str::<ControlEvent>(line.trim()).unwrap(),
+            event
+        );
+    }
+
+    #[test]
+    fn responses_encode_as_tagged_json_lines() {
+        assert_eq!(
+            encode_line(&ControlResponse::Queued { position: 2 }),
+            "{\"type\":\"queued\",\"position\":2}\n"
+        );
+        assert_eq!(
+            parse_response("{\"type\":\"delivered\"}\n").unwrap(),
+            ControlResponse::Delivered
+        );
+    }
+}
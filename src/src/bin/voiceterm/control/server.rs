@@ -0,0 +1,390 @@
+//! Control socket listener for the interactive overlay.
+
+use anyhow::Result;
+use crossbeam_channel::{bounded, Receiver, Sender};
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use voiceterm::{log_debug, log_warn};
+
+use crate::config::OverlayConfig;
+use crate::prompt::PromptTracker;
+use crate::session_events::{SessionEventSink, VoiceSessionEvent};
+use crate::transcript::{
+    submit_external_transcript, transcript_ready, PendingTranscript, QueuePolicy, Readiness,
+    TranscriptIo, TranscriptSession,
+};
+use crate::unix_socket::{bind_socket, runtime_socket_path, SocketCleanup};
+
+use super::protocol::{
+    encode_line, parse_request, ControlEvent, ControlRequest, ControlResponse, PromptState,
+};
+
+const COMMAND_CHANNEL_CAPACITY: usize = 16;
+/// Longest request line accepted; sent text is capped well above any dictation.
+const MAX_REQUEST_BYTES: usize = 64 * 1024;
+/// How long a client may take to send its request, and how long it waits for the reply.
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// `$XDG_RUNTIME_DIR/voiceterm-control.sock`, or a per-user socket in the temp dir.
+pub(super) fn default_control_socket_path() -> PathBuf {
+    runtime_socket_path("voiceterm-control")
+}
+
+/// A parsed request waiting for the event loop, plus the channel its answer goes back on.
+pub(crate) struct ControlCommand {
+    pub(crate) request: ControlRequest,
+    reply: Sender<ControlResponse>,
+}
+
+impl ControlCommand {
+    pub(crate) fn respond(self, response: ControlResponse) {
+        let _ = self.reply.send(response);
+    }
+}
+
+/// `subscribe` connections plus the latest event, replayed to each new subscriber.
+#[derive(Default)]
+struct Subscribers {
+    streams: Vec<UnixStream>,
+    last_event: Option<ControlEvent>,
+}
+
+impl Subscribers {
+    fn add(&mut self, mut stream: UnixStream) {
+        if let Some(event) = &self.last_event {
+            if stream.write_all(encode_line(event).as_bytes()).is_err() {
+                return;
+            }
+        }
+        // A subscriber that stops reading is dropped rather than stalling the event loop.
+        if stream.set_nonblocking(true).is_ok() {
+            self.streams.push(stream);
+        }
+    }
+
+    fn broadcast(&mut self, event: &ControlEvent) {
+        let line = encode_line(event);
+        self.streams
+            .retain_mut(|stream| stream.write_all(line.as_bytes()).is_ok());
+    }
+}
+
+/// Forwards voice session events to `subscribe` connections.
+pub(crate) struct ControlSessionEvents {
+    subscribers: Arc<Mutex<Subscribers>>,
+}
+
+impl SessionEventSink for ControlSessionEvents {
+    fn on_event(&mut self, event: &VoiceSessionEvent) {
+        if let Ok(mut subscribers) = self.subscribers.lock() {
+            // Only readiness is replayed to new subscribers; session events are live only.
+            subscribers.broadcast(&ControlEvent::Session(event.clone()));
+        }
+    }
+}
+
+/// Listening control socket; requests are polled from the event loop.
+pub(crate) struct ControlServer {
+    path: PathBuf,
+    commands: Receiver<ControlCommand>,
+    subscribers: Arc<Mutex<Subscribers>>,
+    _cleanup: SocketCleanup,
+}
+
+impl ControlServer {
+    /// Bind the socket when `--control` or `--control-socket` is set.
+    pub(crate) fn from_config(config: &OverlayConfig) -> Result<Option<Self>> {
+        let path = match (&config.control_socket, config.control) {
+            (Some(path), _) => path.clone(),
+            (None, true) => default_control_socket_path(),
+            (None, false) => return Ok(None),
+        };
+        Self::bind(path).map(Some)
+    }
+
+    fn bind(path: PathBuf) -> Result<Self> {
+        let listener = bind_socket(&path)?;
+        let (commands_tx, commands) = bounded(COMMAND_CHANNEL_CAPACITY);
+        let subscribers = Arc::new(Mutex::new(Subscribers::default()));
+        let accept_subscribers = Arc::clone(&subscribers);
+        thread::spawn(move || accept_clients(listener, commands_tx, accept_subscribers));
+        Ok(Self {
+            _cleanup: SocketCleanup(path.clone()),
+            path,
+            commands,
+            subscribers,
+        })
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Next pending request, without blocking.
+    pub(crate) fn try_next(&self) -> Option<ControlCommand> {
+        self.commands.try_recv().ok()
+    }
+
+    /// Subscriber that streams voice session events to `subscribe` clients.
+    pub(crate) fn session_events(&self) -> ControlSessionEvents {
+        ControlSessionEvents {
+            subscribers: Arc::clone(&self.subscribers),
+        }
+    }
+
+    /// Tell subscribers about a prompt-readiness transition; unchanged readiness is not resent.
+    pub(crate) fn publish_readiness(&self, readiness: Readiness) {
+        let state = if readiness.ready {
+            PromptState::Ready
+        } else {
+            PromptState::Busy
+        };
+        let Ok(mut subscribers) = self.subscribers.lock() else {
+            return;
+        };
+        if matches!(
+            subscribers.last_event,
+            Some(ControlEvent::Prompt { state: last, .. }) if last == state
+        ) {
+            return;
+        }
+        log_debug(&format!(
+            "control event|prompt={state:?}|reason={}",
+            readiness.reason
+        ));
+        let event = ControlEvent::Prompt {
+            state,
+            reason: readiness.reason.to_string(),
+        };
+        subscribers.broadcast(&event);
+        subscribers.last_event = Some(event);
+    }
+}
+
+/// Apply one request to the transcript queue and return the client's answer.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn handle_control_request<S: TranscriptSession>(
+    request: &ControlRequest,
+    pending: &mut VecDeque<PendingTranscript>,
+    queue: QueuePolicy,
+    prompt_tracker: &PromptTracker,
+    last_enter_at: &mut Option<Instant>,
+    io: &mut TranscriptIo<'_, S>,
+    now: Instant,
+    transcript_idle_timeout: Duration,
+) -> ControlResponse {
+    let ControlRequest::Send { text, mode } = request else {
+        return ControlResponse::Error {
+            message: "subscribe is answered by the socket, not the event loop".to_string(),
+        };
+    };
+    let text = text.trim();
+    if text.is_empty() {
+        return ControlResponse::Error {
+            message: "text is empty".to_string(),
+        };
+    }
+    let mode = mode.unwrap_or(io.status_state.send_mode);
+    let ready = transcript_ready(prompt_tracker, *last_enter_at, now, transcript_idle_timeout);
+    log_debug(&format!(
+        "control send|chars={}|mode={mode:?}|ready={ready}",
+        text.len()
+    ));
+    match submit_external_transcript(text, mode, ready, pending, queue, last_enter_at, io, now) {
+        Ok(None) => ControlResponse::Delivered,
+        Ok(Some(position)) => ControlResponse::Queued { position },
+        Err(err) => ControlResponse::Error {
+            message: format!("{err:#}"),
+        },
+    }
+}
+
+fn accept_clients(
+    listener: UnixListener,
+    commands: Sender<ControlCommand>,
+    subscribers: Arc<Mutex<Subscribers>>,
+) {
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                let commands = commands.clone();
+                let subscribers = Arc::clone(&subscribers);
+                thread::spawn(move || {
+                    if let Err(err) = serve_client(stream, &commands, &subscribers) {
+                        log_warn(&format!("control client error: {err:#}"));
+                    }
+                });
+            }
+            Err(err) => log_warn(&format!("control accept failed: {err}")),
+        }
+    }
+}
+
+/// Read one request line, hand it to the event loop, and write back its response.
+///
+/// `subscribe` instead parks the connection in the subscriber list.
+fn serve_client(
+    stream: UnixStream,
+    commands: &Sender<ControlCommand>,
+    subscribers: &Mutex<Subscribers>,
+) -> Result<()> {
+    stream.set_read_timeout(Some(CLIENT_TIMEOUT))?;
+    let mut writer = stream.try_clone()?;
+    let mut line = String::new();
+    BufReader::new(stream)
+        .take(MAX_REQUEST_BYTES as u64 + 1)
+        .read_line(&mut line)?;
+    let response = if line.len() > MAX_REQUEST_BYTES {
+        ControlResponse::Error {
+            message: format!("request exceeds {MAX_REQUEST_BYTES} bytes"),
+        }
+    } else {
+        match parse_request(&line) {
+            Ok(ControlRequest::Subscribe) => {
+                if let Ok(mut subscribers) = subscribers.lock() {
+                    subscribers.add(writer);
+                }
+                return Ok(());
+            }
+            Ok(request) => {
+                let (reply, reply_rx) = bounded(1);
+                if commands.send(ControlCommand { request, reply }).is_err() {
+                    return Ok(());
+                }
+                reply_rx
+                    .recv_timeout(CLIENT_TIMEOUT)
+                    .unwrap_or_else(|_| ControlResponse::Error {
+                        message: "voiceterm did not answer in time".to_string(),
+                    })
+            }
+            Err(err) => ControlResponse::Error {
+                message: format!("{err:#}"),
+            },
+        }
+    };
+    writer.write_all(encode_line(&response).as_bytes())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::VoiceSendMode;
+    use crate::control::client::send_request;
+    use std::env;
+
+    fn temp_socket(name: &str) -> PathBuf {
+        env::temp_dir().join(format!(
+            "voiceterm-control-{name}-{}.sock",
+            std::process::id()
+        ))
+    }
+
+    #[test]
+    fn from_config_binds_only_when_requested() {
+        use clap::Parser;
+
+        let config = OverlayConfig::parse_from(["voiceterm"]);
+        if config.control_socket.is_none() {
+            assert!(ControlServer::from_config(&config).unwrap().is_none());
+        }
+        let path = temp_socket("config");
+        let config =
+            OverlayConfig::parse_from(["voiceterm", "--control-socket", path.to_str().unwrap()]);
+        let server = ControlServer::from_config(&config)
+            .unwrap()
+            .expect("control socket bound");
+        assert_eq!(server.path(), path);
+        drop(server);
+        assert!(!path.exists(), "socket removed on drop");
+    }
+
+    #[test]
+    fn requests_reach_the_event_loop_and_answers_reach_the_client() {
+        let path = temp_socket("round-trip");
+        let server = ControlServer::bind(path.clone()).expect("bind control socket");
+        let client_path = path.clone();
+        let client = thread::spawn(move || {
+            let request = ControlRequest::Send {
+                text: "run the tests".to_string(),
+                mode: Some(VoiceSendMode::Auto),
+            };
+            send_request(&client_path, &request)
+        });
+        let deadline = Instant::now() + Duration::from_secs(2);
+        let command = loop {
+            if let Some(command) = server.try_next() {
+                break command;
+            }
+            assert!(Instant::now() < deadline, "request never arrived");
+            thread::sleep(Duration::from_millis(10));
+        };
+        assert_eq!(
+            command.request,
+            ControlRequest::Send {
+                text: "run the tests".to_string(),
+                mode: Some(VoiceSendMode::Auto),
+            }
+        );
+        command.respond(ControlResponse::Queued { position: 1 });
+        assert_eq!(
+            client.join().unwrap().unwrap(),
+            ControlResponse::Queued { position: 1 }
+        );
+    }
+
+    #[test]
+    fn subscribers_get_the_current_state_then_transitions() {
+        let path = temp_socket("subscribe");
+        let server = ControlServer::bind(path.clone()).expect("bind control socket");
+        server.publish_readiness(Readiness {
+            ready: true,
+            reason: "line_complete",
+        });
+        let mut stream = UnixStream::connect(&path).unwrap();
+        stream.write_all(b"{\"cmd\":\"subscribe\"}\n").unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        assert_eq!(
+            line,
+            "{\"type\":\"prompt\",\"state\":\"ready\",\"reason\":\"line_complete\"}\n"
+        );
+
+        server.publish_readiness(Readiness {
+            ready: true,
+            reason: "idle_match",
+        });
+        server.publish_readiness(Readiness {
+            ready: false,
+            reason: "awaiting_prompt",
+        });
+        line.clear();
+        reader.read_line(&mut line).unwrap();
+        assert_eq!(
+            line, "{\"type\":\"prompt\",\"state\":\"busy\",\"reason\":\"awaiting_prompt\"}\n",
+            "unchanged readiness is not resent"
+        );
+        assert!(server.try_next().is_none());
+    }
+
+    #[test]
+    fn malformed_requests_get_an_error_without_reaching_the_event_loop() {
+        let path = temp_socket("malformed");
+        let server = ControlServer::bind(path.clone()).expect("bind control socket");
+        let mut stream = UnixStream::connect(&path).unwrap();
+        stream.write_all(b"{\"cmd\":\"shutdown\"}\n").unwrap();
+        let mut line = String::new();
+        BufReader::new(stream).read_line(&mut line).unwrap();
+        assert!(
+            line.starts_with(r#"{"type":"error","message":"invalid control request"#),
+            "{line}"
+        );
+        assert!(server.try_next().is_none());
+    }
+}
@@ -0,0 +1,143 @@
+//! `daemon --attach <TTY>`: type transcripts into an existing terminal instead of a wrapped backend.
+//!
+//! Text is pushed into the terminal's input queue one byte at a time with
+//! `ioctl(TIOCSTI)`, so whatever runs in that terminal (a Codex started
+//! without the overlay) reads it as typed keys. The kernel only allows this on
+//! the caller's controlling terminal, so the daemon has to be started from the
+//! terminal it attaches to; Linux 6.2+ also needs `dev.tty.legacy_tiocsti=1`.
+
+use anyhow::{bail, Context, Result};
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::os::unix::fs::{FileTypeExt, MetadataExt, OpenOptionsExt};
+use std::os::unix::io::AsRawFd;
+use std::path::{Path, PathBuf};
+
+use crate::transcript::TranscriptSession;
+
+/// Linux sysctl that gates TIOCSTI for unprivileged processes (6.2+).
+const LEGACY_TIOCSTI_SYSCTL: &str = "/proc/sys/dev/tty/legacy_tiocsti";
+
+/// A terminal that receives transcripts as simulated keystrokes.
+pub(crate) struct AttachedTty {
+    path: PathBuf,
+    file: File,
+}
+
+impl AttachedTty {
+    /// Open `path` and check up front that TIOCSTI can reach it.
+    pub(crate) fn open(path: &Path) -> Result<Self> {
+        let metadata = std::fs::metadata(path)
+            .with_context(|| format!("cannot attach to {}", path.display()))?;
+        if !metadata.file_type().is_char_device() {
+            bail!("cannot attach to {}: not a terminal", path.display());
+        }
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .custom_flags(libc::O_NOCTTY)
+            .open(path)
+            .with_context(|| format!("cannot open {}", path.display()))?;
+        if unsafe { libc::isatty(file.as_raw_fd()) } != 1 {
+            bail!("cannot attach to {}: not a terminal", path.display());
+        }
+        let privileged = unsafe { libc::geteuid() } == 0;
+        if !privileged {
+            if std::fs::read_to_string(LEGACY_TIOCSTI_SYSCTL)
+                .is_ok_and(|value| legacy_tiocsti_disabled(&value))
+            {
+                bail!(
+                    "this kernel blocks keystroke injection (TIOCSTI); \
+                     enable it with `sudo sysctl dev.tty.legacy_tiocsti=1`"
+                );
+            }
+            if controlling_tty_rdev() != Some(metadata.rdev()) {
+                bail!(
+                    "{} is not this process's terminal; start the daemon from that \
+                     terminal, e.g. `voiceterm daemon --attach \"$(tty)\" &`",
+                    path.display()
+                );
+            }
+        }
+        Ok(Self {
+            path: path.to_path_buf(),
+            file,
+        })
+    }
+
+    fn inject(&self, bytes: &[u8]) -> Result<()> {
+        let fd = self.file.as_raw_fd();
+        for byte in bytes {
+            // SAFETY: TIOCSTI reads one byte through the pointer, which outlives the call.
+            let rc = unsafe { libc::ioctl(fd, libc::TIOCSTI as _, byte as *const u8) };
+            if rc != 0 {
+                let err = io::Error::last_os_error();
+                return Err(err).with_context(|| {
+                    format!("failed to type into {} (TIOCSTI)", self.path.display())
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TranscriptSession for AttachedTty {
+    fn send_text(&mut self, text: &str) -> Result<()> {
+        self.inject(&injection_bytes(text, false))
+    }
+
+    fn send_text_with_newline(&mut self, text: &str) -> Result<()> {
+        self.inject(&injection_bytes(text, true))
+    }
+}
+
+/// Bytes typed for one transcript; Enter is a carriage return, as a real keypress sends.
+fn injection_bytes(text: &str, submit: bool) -> Vec<u8> {
+    let mut bytes = text.as_bytes().to_vec();
+    if submit {
+        bytes.push(b'\r');
+    }
+    bytes
+}
+
+fn legacy_tiocsti_disabled(sysctl_value: &str) -> bool {
+    sysctl_value.trim() == "0"
+}
+
+/// Device number of this process's controlling terminal, if it has one.
+fn controlling_tty_rdev() -> Option<u64> {
+    let tty = OpenOptions::new()
+        .read(true)
+        .custom_flags(libc::O_NOCTTY)
+        .open("/dev/tty")
+        .ok()?;
+    tty.metadata().ok().map(|metadata| metadata.rdev())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn injection_bytes_submit_with_carriage_return() {
+        assert_eq!(injection_bytes("run tests", false), b"run tests");
+        assert_eq!(injection_bytes("run tests", true), b"run tests\r");
+        assert!(legacy_tiocsti_disabled("0\n"));
+        assert!(!legacy_tiocsti_disabled("1\n"));
+    }
+
+    #[test]
+    fn open_rejects_files_that_are_not_terminals() {
+        let dir = std::env::temp_dir().join(format!("voiceterm-attach-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("not-a-tty");
+        std::fs::write(&file, b"").unwrap();
+        let err = AttachedTty::open(&file).err().expect("regular file");
+        assert!(err.to_string().contains("not a terminal"), "{err}");
+        let err = AttachedTty::open(&dir.join("missing"))
+            .err()
+            .expect("missing");
+        assert!(err.to_string().contains("cannot attach"), "{err}");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,166 @@
+//! `daemon --type-focused`: type transcripts into whichever application has focus.
+//!
+//! Keystrokes go through the platform's input-synthesis tool: `osascript`
+//! (System Events, which needs Accessibility permission) on macOS, `wtype`
+//! (virtual-keyboard protocol) on Wayland, and `xdotool` (XTEST) on X11.
+
+use anyhow::{bail, Context, Result};
+use std::env;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use crate::transcript::TranscriptSession;
+
+/// AppleScript that types its first argument; `on run argv` avoids quoting the text.
+const OSASCRIPT_KEYSTROKE: [&str; 6] = [
+    "-e",
+    "on run argv",
+    "-e",
+    "tell application \"System Events\" to keystroke (item 1 of argv)",
+    "-e",
+    "end run",
+];
+const OSASCRIPT_RETURN: [&str; 2] = ["-e", "tell application \"System Events\" to key code 36"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum KeystrokeTool {
+    Osascript,
+    Wtype,
+    Xdotool,
+}
+
+impl KeystrokeTool {
+    fn program(self) -> &'static str {
+        match self {
+            Self::Osascript => "osascript",
+            Self::Wtype => "wtype",
+            Self::Xdotool => "xdotool",
+        }
+    }
+
+    /// Arguments that type `text` literally.
+    fn type_args(self, text: &str) -> Vec<&str> {
+        let mut args: Vec<&str> = match self {
+            Self::Osascript => OSASCRIPT_KEYSTROKE.to_vec(),
+            Self::Wtype => vec!["--"],
+            Self::Xdotool => vec!["type", "--clearmodifiers", "--"],
+        };
+        args.push(text);
+        args
+    }
+
+    /// Arguments that press Return.
+    fn enter_args(self) -> Vec<&'static str> {
+        match self {
+            Self::Osascript => OSASCRIPT_RETURN.to_vec(),
+            Self::Wtype => vec!["-k", "Return"],
+            Self::Xdotool => vec!["key", "--clearmodifiers", "Return"],
+        }
+    }
+}
+
+/// Tools to try, in order, for the current platform and display.
+fn keystroke_tools(macos: bool, wayland: bool, x11: bool) -> Vec<KeystrokeTool> {
+    let mut tools = Vec::new();
+    if macos {
+        tools.push(KeystrokeTool::Osascript);
+    }
+    if wayland {
+        tools.push(KeystrokeTool::Wtype);
+    }
+    if x11 {
+        tools.push(KeystrokeTool::Xdotool);
+    }
+    tools
+}
+
+fn on_path(program: &str) -> bool {
+    env::var_os("PATH").is_some_and(|paths| {
+        env::split_paths(&paths).any(|dir| Path::new(&dir).join(program).is_file())
+    })
+}
+
+/// The focused application, reached through the first available keystroke tool.
+pub(crate) struct FocusedApp {
+    tool: KeystrokeTool,
+}
+
+impl FocusedApp {
+    /// Pick a keystroke tool for this session, failing at startup when none is installed.
+    pub(crate) fn detect() -> Result<Self> {
+        let has_env = |name: &str| env::var_os(name).is_some_and(|value| !value.is_empty());
+        let tools = keystroke_tools(
+            cfg!(target_os = "macos"),
+            has_env("WAYLAND_DISPLAY"),
+            has_env("DISPLAY"),
+        );
+        if tools.is_empty() {
+            bail!("no graphical session found for --type-focused (DISPLAY and WAYLAND_DISPLAY are unset)");
+        }
+        match tools.into_iter().find(|tool| on_path(tool.program())) {
+            Some(tool) => Ok(Self { tool }),
+            None => bail!("no keystroke tool found for --type-focused (install wtype or xdotool)"),
+        }
+    }
+
+    pub(crate) fn tool_name(&self) -> &'static str {
+        self.tool.program()
+    }
+
+    fn run(&self, args: &[&str]) -> Result<()> {
+        let program = self.tool.program();
+        let output = Command::new(program)
+            .args(args)
+            .stdin(Stdio::null())
+            .output()
+            .with_context(|| format!("failed to run {program}"))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            bail!("{program} exited with {}: {}", output.status, stderr.trim());
+        }
+        Ok(())
+    }
+}
+
+impl TranscriptSession for FocusedApp {
+    fn send_text(&mut self, text: &str) -> Result<()> {
+        if text.is_empty() {
+            return Ok(());
+        }
+        self.run(&self.tool.type_args(text))
+    }
+
+    fn send_text_with_newline(&mut self, text: &str) -> Result<()> {
+        self.send_text(text)?;
+        self.run(&self.tool.enter_args())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keystroke_tools_follow_platform_and_display() {
+        assert_eq!(
+            keystroke_tools(true, false, false),
+            vec![KeystrokeTool::Osascript]
+        );
+        assert_eq!(
+            keystroke_tools(false, true, true),
+            vec![KeystrokeTool::Wtype, KeystrokeTool::Xdotool]
+        );
+        assert!(keystroke_tools(false, false, false).is_empty());
+    }
+
+    #[test]
+    fn type_args_end_options_before_the_text() {
+        assert_eq!(KeystrokeTool::Wtype.type_args("-rf"), ["--", "-rf"]);
+        assert_eq!(
+            KeystrokeTool::Xdotool.type_args("-rf"),
+            ["type", "--clearmodifiers", "--", "-rf"]
+        );
+        let osascript = KeystrokeTool::Osascript.type_args("say \"hi\"");
+        assert_eq!(osascript.last(), Some(&"say \"hi\""));
+    }
+}
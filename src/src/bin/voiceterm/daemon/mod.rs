@@ -0,0 +1,14 @@
+//! Headless daemon mode so editors and tmux bindings can drive capture without a PTY.
+//!
+//! `voiceterm daemon` loads the recorder and Whisper model, then serves
+//! newline-delimited JSON requests (`start`, `stop`, `status`,
+//! `last-transcript`) on a Unix-domain socket owned by the current user.
+//! With `--attach <TTY>` each transcript is also typed into that terminal, and
+//! with `--type-focused` into whichever application has focus.
+
+mod attach;
+mod focused;
+mod protocol;
+mod server;
+
+pub(crate) use server::run_daemon;
@@ -0,0 +1,136 @@
+//! Daemon socket protocol so clients exchange one JSON object per line.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// Requests accepted on the daemon socket, tagged by `"cmd"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(tag = "cmd", rename_all = "kebab-case")]
+pub(crate) enum DaemonRequest {
+    /// Begin a capture; transcription runs when silence is detected or `stop` is sent.
+    Start,
+    /// Stop recording and transcribe what was captured.
+    Stop {
+        /// Reply only after the transcript (or error) is ready.
+        #[serde(default)]
+        wait: bool,
+    },
+    /// Report capture state and counters.
+    Status,
+    /// Return the most recent transcript.
+    LastTranscript,
+}
+
+/// What the voice pipeline is doing right now.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum CaptureState {
+    Idle,
+    Recording,
+    Transcribing,
+}
+
+/// Replies written back to the client, tagged by `"type"`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(crate) enum DaemonResponse {
+    /// A capture was started (or one was already running).
+    Started {
+        already_running: bool,
+    },
+    /// Recording was asked to stop; the transcript arrives later.
+    Stopping,
+    /// `stop` was sent while no capture was running.
+    NotRecording,
+    Status {
+        state: CaptureState,
+        captures: u64,
+        transcripts: u64,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        last_error: Option<String>,
+        uptime_ms: u64,
+    },
+    /// Latest transcript; `text` is null when none has been produced yet or speech was empty.
+    Transcript {
+        text: Option<String>,
+        seq: u64,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        age_ms: Option<u64>,
+    },
+    Error {
+        message: String,
+    },
+}
+
+/// Parse one request line.
+pub(crate) fn parse_request(line: &str) -> Result<DaemonRequest> {
+    serde_json::from_str(line.trim()).context("invalid daemon request")
+}
+
+/// Serialize a response as a single JSON line (newline included).
+pub(crate) fn encode_response(response: &DaemonResponse) -> String {
+    let mut line = serde_json::to_string(response).unwrap_or_else(|err| {
+        format!(r#"{{"type":"error","message":"failed to encode response: {err}"}}"#)
+    });
+    line.push('\n');
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_all_commands() {
+        assert_eq!(
+            parse_request(r#"{"cmd":"start"}"#).unwrap(),
+            DaemonRequest::Start
+        );
+        assert_eq!(
+            parse_request(r#"{"cmd":"stop"}"#).unwrap(),
+            DaemonRequest::Stop { wait: false }
+        );
+        assert_eq!(
+            parse_request(" {\"cmd\":\"stop\",\"wait\":true}\n").unwrap(),
+            DaemonRequest::Stop { wait: true }
+        );
+        assert_eq!(
+            parse_request(r#"{"cmd":"status"}"#).unwrap(),
+            DaemonRequest::Status
+        );
+        assert_eq!(
+            parse_request(r#"{"cmd":"last-transcript"}"#).unwrap(),
+            DaemonRequest::LastTranscript
+        );
+        assert!(parse_request(r#"{"cmd":"reboot"}"#).is_err());
+        assert!(parse_request("start").is_err());
+    }
+
+    #[test]
+    fn responses_encode_as_tagged_json_lines() {
+        assert_eq!(
+            encode_response(&DaemonResponse::Started {
+                already_running: false
+            }),
+            "{\"type\":\"started\",\"already_running\":false}\n"
+        );
+        assert_eq!(
+            encode_response(&DaemonResponse::Status {
+                state: CaptureState::Transcribing,
+                captures: 2,
+                transcripts: 1,
+                last_error: None,
+                uptime_ms: 5,
+            }),
+            "{\"type\":\"status\",\"state\":\"transcribing\",\"captures\":2,\"transcripts\":1,\"uptime_ms\":5}\n"
+        );
+        assert_eq!(
+            encode_response(&DaemonResponse::Transcript {
+                text: None,
+                seq: 0,
+                age_ms: None,
+            }),
+            "{\"type\":\"transcript\",\"text\":null,\"seq\":0}\n"
+        );
+    }
+}
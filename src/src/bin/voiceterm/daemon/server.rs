@@ -0,0 +1,604 @@
+//! Daemon runtime: socket listener, per-client threads, and the capture loop.
+
+use anyhow::{anyhow, bail, Result};
+use crossbeam_channel::{bounded, RecvTimeoutError, Sender};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+use voiceterm::{log_error, log_info, log_warn, VoiceCaptureTrigger, VoiceJobMessage};
+
+use crate::config::{DaemonArgs, OverlayConfig, UserConfig, VoiceSendMode};
+use crate::transcript::{TranscriptPostProcessor, TranscriptSession};
+use crate::unix_socket::{bind_socket, runtime_socket_path, SocketCleanup};
+use crate::voice_control::VoiceManager;
+
+use super::attach::AttachedTty;
+use super::focused::FocusedApp;
+use super::protocol::{
+    encode_response, parse_request, CaptureState, DaemonRequest, DaemonResponse,
+};
+
+/// How often the capture loop polls the voice worker between requests.
+const POLL_INTERVAL_MS: u64 = 50;
+const REQUEST_CHANNEL_CAPACITY: usize = 64;
+/// Longest request line accepted from a client; longer lines close the connection.
+const MAX_REQUEST_BYTES: usize = 4096;
+
+static SHUTDOWN_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+extern "C" fn handle_shutdown_signal(_: libc::c_int) {
+    SHUTDOWN_REQUESTED.store(true, Ordering::SeqCst);
+}
+
+fn install_shutdown_handlers() -> Result<()> {
+    for signal in [libc::SIGINT, libc::SIGTERM] {
+        unsafe {
+            // SAFETY: handle_shutdown_signal only flips an atomic flag, which is
+            // async-signal-safe.
+            let handler = handle_shutdown_signal as *const () as libc::sighandler_t;
+            if libc::signal(signal, handler) == libc::SIG_ERR {
+                return Err(anyhow!("failed to install signal handler for {signal}"));
+            }
+        }
+    }
+    Ok(())
+}
+
+type PendingRequest = (DaemonRequest, Sender<DaemonResponse>);
+
+/// Capture operations the daemon needs, so request handling can be tested without audio.
+trait CaptureControl {
+    /// Start a capture; `Ok(false)` means one was already running.
+    fn start(&mut self) -> Result<bool>;
+    /// Ask a running capture to stop and transcribe; false when idle.
+    fn stop(&mut self) -> bool;
+    fn idle(&self) -> bool;
+}
+
+impl CaptureControl for VoiceManager {
+    fn start(&mut self) -> Result<bool> {
+        self.start_headless_capture(VoiceCaptureTrigger::Manual)
+    }
+
+    fn stop(&mut self) -> bool {
+        self.request_early_stop()
+    }
+
+    fn idle(&self) -> bool {
+        self.is_idle()
+    }
+}
+
+/// Counters and last result shared across clients.
+struct DaemonCore {
+    started_at: Instant,
+    captures: u64,
+    transcripts: u64,
+    stopping: bool,
+    last_transcript: Option<(String, Instant)>,
+    last_error: Option<String>,
+    /// Clients that sent `stop` with `wait` and are blocked on the capture result.
+    waiters: Vec<Sender<DaemonResponse>>,
+}
+
+impl DaemonCore {
+    fn new(now: Instant) -> Self {
+        Self {
+            started_at: now,
+            captures: 0,
+            transcripts: 0,
+            stopping: false,
+            last_transcript: None,
+            last_error: None,
+            waiters: Vec::new(),
+        }
+    }
+
+    fn state(&self, control: &impl CaptureControl) -> CaptureState {
+        if control.idle() {
+            CaptureState::Idle
+        } else if self.stopping {
+            CaptureState::Transcribing
+        } else {
+            CaptureState::Recording
+        }
+    }
+
+    fn handle(
+        &mut self,
+        request: DaemonRequest,
+        control: &mut impl CaptureControl,
+        reply: Sender<DaemonResponse>,
+        now: Instant,
+    ) {
+        let response = match request {
+            DaemonRequest::Start => match control.start() {
+                Ok(true) => {
+                    self.captures += 1;
+                    self.stopping = false;
+                    DaemonResponse::Started {
+                        already_running: false,
+                    }
+                }
+                Ok(false) => DaemonResponse::Started {
+                    already_running: true,
+                },
+                Err(err) => {
+                    let message = format!("{err:#}");
+                    log_error(&format!("daemon capture start failed: {message}"));
+                    self.last_error = Some(message.clone());
+                    DaemonResponse::Error { message }
+                }
+            },
+            DaemonRequest::Stop { wait } => {
+                if control.idle() {
+                    DaemonResponse::NotRecording
+                } else {
+                    if !self.stopping {
+                        control.stop();
+                        self.stopping = true;
+                    }
+                    if wait {
+                        self.waiters.push(reply);
+                        return;
+                    }
+                    DaemonResponse::Stopping
+                }
+            }
+            DaemonRequest::Status => DaemonResponse::Status {
+                state: self.state(control),
+                captures: self.captures,
+                transcripts: self.transcripts,
+                last_error: self.last_error.clone(),
+                uptime_ms: now.duration_since(self.started_at).as_millis() as u64,
+            },
+            DaemonRequest::LastTranscript => DaemonResponse::Transcript {
+                text: self.last_transcript.as_ref().map(|(text, _)| text.clone()),
+                seq: self.transcripts,
+                age_ms: self
+                    .last_transcript
+                    .as_ref()
+                    .map(|(_, at)| now.duration_since(*at).as_millis() as u64),
+            },
+        };
+        let _ = reply.send(response);
+    }
+
+    /// Record a finished capture and answer any clients waiting on `stop`.
+    ///
+    /// Returns the post-processed transcript, if the capture produced one.
+    fn finish_capture(
+        &mut self,
+        message: VoiceJobMessage,
+        postprocessor: &TranscriptPostProcessor,
+        now: Instant,
+    ) -> Option<String> {
+        self.stopping = false;
+        let response = match message {
+            VoiceJobMessage::Transcript { text, .. } => {
+                let text = postprocessor.apply(text.trim());
+                self.transcripts += 1;
+                self.last_error = None;
+                self.last_transcript = Some((text.clone(), now));
+                DaemonResponse::Transcript {
+                    text: Some(text),
+                    seq: self.transcripts,
+                    age_ms: Some(0),
+                }
+            }
+            VoiceJobMessage::Empty { .. } => DaemonResponse::Transcript {
+                text: None,
+                seq: self.transcripts,
+                age_ms: None,
+            },
+            VoiceJobMessage::Error(err) => {
+                log_error(&format!("daemon capture failed: {}", err.message));
+                self.last_error = Some(err.message.clone());
+                DaemonResponse::Error {
+                    message: err.message,
+                }
+            }
+        };
+        for waiter in self.waiters.drain(..) {
+            let _ = waiter.send(response.clone());
+        }
+        match response {
+            DaemonResponse::Transcript { text, .. } => text,
+            _ => None,
+        }
+    }
+
+    /// Type a transcript as keystrokes, pressing Enter in auto-send mode.
+    fn deliver(
+        &mut self,
+        target: &mut dyn TranscriptSession,
+        text: &str,
+        send_mode: VoiceSendMode,
+    ) {
+        let result = match send_mode {
+            VoiceSendMode::Auto => target.send_text_with_newline(text),
+            VoiceSendMode::Insert | VoiceSendMode::Clipboard => target.send_text(text),
+        };
+        if let Err(err) = result {
+            let message = format!("{err:#}");
+            log_error(&format!("daemon keystroke delivery failed: {message}"));
+            self.last_error = Some(message);
+        }
+    }
+}
+
+/// Read request lines from one client and write one response line per request.
+fn serve_client(stream: UnixStream, requests: &Sender<PendingRequest>) -> Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut writer = stream;
+    let mut line = String::new();
+    loop {
+        line.clear();
+        let read = (&mut reader)
+            .take(MAX_REQUEST_BYTES as u64 + 1)
+            .read_line(&mut line)?;
+        if read == 0 {
+            return Ok(());
+        }
+        if line.len() > MAX_REQUEST_BYTES {
+            let response = DaemonResponse::Error {
+                message: format!("request exceeds {MAX_REQUEST_BYTES} bytes"),
+            };
+            writer.write_all(encode_response(&response).as_bytes())?;
+            return Ok(());
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match parse_request(&line) {
+            Ok(request) => {
+                let (reply_tx, reply_rx) = bounded(1);
+                if requests.send((request, reply_tx)).is_err() {
+                    return Ok(());
+                }
+                reply_rx.recv().unwrap_or_else(|_| DaemonResponse::Error {
+                    message: "daemon is shutting down".to_string(),
+                })
+            }
+            Err(err) => DaemonResponse::Error {
+                message: format!("{err:#}"),
+            },
+        };
+        writer.write_all(encode_response(&response).as_bytes())?;
+    }
+}
+
+fn accept_clients(listener: UnixListener, requests: Sender<PendingRequest>) {
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                let requests = requests.clone();
+                thread::spawn(move || {
+                    if let Err(err) = serve_client(stream, &requests) {
+                        log_warn(&format!("daemon client error: {err:#}"));
+                    }
+                });
+            }
+            Err(err) => log_warn(&format!("daemon accept failed: {err}")),
+        }
+    }
+}
+
+/// Where `--attach` or `--type-focused` types transcripts, with a label for the startup line.
+fn keystroke_target(args: &DaemonArgs) -> Result<Option<(String, Box<dyn TranscriptSession>)>> {
+    if let Some(path) = &args.attach {
+        let tty = AttachedTty::open(path)?;
+        return Ok(Some((path.display().to_string(), Box::new(tty))));
+    }
+    if args.type_focused {
+        let app = FocusedApp::detect()?;
+        let label = format!("the focused app (via {})", app.tool_name());
+        return Ok(Some((label, Box::new(app))));
+    }
+    Ok(None)
+}
+
+/// Run `voiceterm daemon` until SIGINT/SIGTERM.
+pub(crate) fn run_daemon(
+    config: &OverlayConfig,
+    args: &DaemonArgs,
+    user_config: &UserConfig,
+) -> Result<()> {
+    let postprocessor = TranscriptPostProcessor::from_config(&user_config.postprocess)?;
+    let mut keystrokes = keystroke_target(args)?;
+    let socket_path = args
+        .socket
+        .clone()
+        .unwrap_or_else(|| runtime_socket_path("voiceterm"));
+    let listener = bind_socket(&socket_path)?;
+    let _cleanup = SocketCleanup(socket_path.clone());
+    install_shutdown_handlers()?;
+
+    let (requests_tx, requests_rx) = bounded::<PendingRequest>(REQUEST_CHANNEL_CAPACITY);
+    thread::spawn(move || accept_clients(listener, requests_tx));
+    eprintln!(
+        "voiceterm daemon listening on {} (Ctrl+C to stop)",
+        socket_path.display()
+    );
+    log_info(&format!("daemon listening on {}", socket_path.display()));
+    if let Some((label, _)) = &keystrokes {
+        eprintln!("voiceterm daemon: typing transcripts into {label}");
+        log_info(&format!("daemon typing transcripts into {label}"));
+    }
+
+    let mut manager = VoiceManager::new(config.app.clone());
+    manager.watch_devices(user_config.devices.clone());
+    // Without --warm-up the daemon loads the model on the first `start`.
+    if config.app.warm_up && manager.start_preload() {
+        eprintln!("voiceterm daemon: warming up model and microphone…");
+    }
+    let mut core = DaemonCore::new(Instant::now());
+    while !SHUTDOWN_REQUESTED.load(Ordering::SeqCst) {
+        match requests_rx.recv_timeout(Duration::from_millis(POLL_INTERVAL_MS)) {
+            Ok((request, reply)) => core.handle(request, &mut manager, reply, Instant::now()),
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => bail!("daemon socket listener stopped"),
+        }
+        if let Some(model_ready) = manager.poll_preload() {
+            if model_ready {
+                eprintln!("voiceterm daemon: model ready");
+            } else {
+                log_warn("daemon warm-up failed; the first capture will retry the model load");
+            }
+        }
+        if let Some(message) = manager.poll_message() {
+            let text = core.finish_capture(message, &postprocessor, Instant::now());
+            if let (Some((_, target)), Some(text)) = (keystrokes.as_mut(), text) {
+                core.deliver(target.as_mut(), &text, config.voice_send_mode);
+            }
+        }
+        manager.release_idle_mic(Instant::now());
+        if let Some(notice) = manager
+            .poll_device_change()
+            .and_then(|change| change.notice)
+        {
+            eprintln!("voiceterm daemon: {notice}");
+            log_info(&format!("daemon input device: {notice}"));
+        }
+    }
+    manager.cancel_capture();
+    log_info("daemon shutting down");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossbeam_channel::Receiver;
+    use std::path::PathBuf;
+    use voiceterm::voice_error::{VoiceError, VoiceErrorKind};
+    use voiceterm::VoiceCaptureSource;
+
+    #[derive(Default)]
+    struct FakeControl {
+        running: bool,
+        stop_calls: usize,
+        fail_start: bool,
+    }
+
+    impl CaptureControl for FakeControl {
+        fn start(&mut self) -> Result<bool> {
+            if self.fail_start {
+                bail!("no input device");
+            }
+            let started = !self.running;
+            self.running = true;
+            Ok(started)
+        }
+
+        fn stop(&mut self) -> bool {
+            self.stop_calls += 1;
+            self.running
+        }
+
+        fn idle(&self) -> bool {
+            !self.running
+        }
+    }
+
+    fn ask(
+        core: &mut DaemonCore,
+        control: &mut FakeControl,
+        request: DaemonRequest,
+    ) -> Receiver<DaemonResponse> {
+        let (tx, rx) = bounded(1);
+        core.handle(request, control, tx, Instant::now());
+        rx
+    }
+
+    fn transcript(text: &str) -> VoiceJobMessage {
+        VoiceJobMessage::Transcript {
+            text: text.to_string(),
+            source: VoiceCaptureSource::Native,
+            confidence: None,
+            words: Vec::new(),
+            metrics: None,
+        }
+    }
+
+    #[test]
+    fn daemon_subcommand_parses_socket_after_overlay_flags() {
+        use crate::config::OverlayCommand;
+        use clap::Parser;
+
+        let config = OverlayConfig::parse_from([
+            "voiceterm",
+            "--no-python-fallback",
+            "daemon",
+            "--socket",
+            "/tmp/vt.sock",
+            "--attach",
+            "/dev/pts/3",
+        ]);
+        assert!(config.app.no_python_fallback);
+        let Some(OverlayCommand::Daemon(args)) = config.command else {
+            panic!("expected daemon subcommand");
+        };
+        assert_eq!(args.socket, Some(PathBuf::from("/tmp/vt.sock")));
+        assert_eq!(args.attach, Some(PathBuf::from("/dev/pts/3")));
+        assert!(!args.type_focused);
+
+        let both = OverlayConfig::try_parse_from([
+            "voiceterm",
+            "daemon",
+            "--attach",
+            "/dev/pts/3",
+            "--type-focused",
+        ]);
+        assert!(both.is_err(), "--attach and --type-focused conflict");
+    }
+
+    #[test]
+    fn start_stop_and_status_track_capture_state() {
+        let mut core = DaemonCore::new(Instant::now());
+        let mut control = FakeControl::default();
+        let started = ask(&mut core, &mut control, DaemonRequest::Start);
+        assert_eq!(
+            started.try_recv().unwrap(),
+            DaemonResponse::Started {
+                already_running: false
+            }
+        );
+        let again = ask(&mut core, &mut control, DaemonRequest::Start);
+        assert_eq!(
+            again.try_recv().unwrap(),
+            DaemonResponse::Started {
+                already_running: true
+            }
+        );
+
+        let stop = ask(&mut core, &mut control, DaemonRequest::Stop { wait: false });
+        assert_eq!(stop.try_recv().unwrap(), DaemonResponse::Stopping);
+        ask(&mut core, &mut control, DaemonRequest::Stop { wait: false });
+        assert_eq!(control.stop_calls, 1, "repeated stop is not forwarded");
+
+        let DaemonResponse::Status {
+            state, captures, ..
+        } = ask(&mut core, &mut control, DaemonRequest::Status)
+            .try_recv()
+            .unwrap()
+        else {
+            panic!("expected status");
+        };
+        assert_eq!(state, CaptureState::Transcribing);
+        assert_eq!(captures, 1);
+
+        control.running = false;
+        core.finish_capture(
+            transcript("hello"),
+            &TranscriptPostProcessor::default(),
+            Instant::now(),
+        );
+        let stop = ask(&mut core, &mut control, DaemonRequest::Stop { wait: false });
+        assert_eq!(stop.try_recv().unwrap(), DaemonResponse::NotRecording);
+    }
+
+    #[test]
+    fn stop_with_wait_replies_when_transcript_arrives() {
+        let mut core = DaemonCore::new(Instant::now());
+        let mut control = FakeControl::default();
+        ask(&mut core, &mut control, DaemonRequest::Start);
+        let waiting = ask(&mut core, &mut control, DaemonRequest::Stop { wait: true });
+        assert!(waiting.try_recv().is_err(), "reply is deferred");
+
+        control.running = false;
+        let delivered = core.finish_capture(
+            transcript("  run the tests  "),
+            &TranscriptPostProcessor::default(),
+            Instant::now(),
+        );
+        assert_eq!(delivered.as_deref(), Some("run the tests"));
+        assert_eq!(
+            waiting.try_recv().unwrap(),
+            DaemonResponse::Transcript {
+                text: Some("run the tests".to_string()),
+                seq: 1,
+                age_ms: Some(0),
+            }
+        );
+        let DaemonResponse::Transcript { text, seq, .. } =
+            ask(&mut core, &mut control, DaemonRequest::LastTranscript)
+                .try_recv()
+                .unwrap()
+        else {
+            panic!("expected transcript");
+        };
+        assert_eq!(text.as_deref(), Some("run the tests"));
+        assert_eq!(seq, 1);
+    }
+
+    #[test]
+    fn failures_are_reported_and_kept_in_status() {
+        let mut core = DaemonCore::new(Instant::now());
+        let mut control = FakeControl {
+            fail_start: true,
+            ..FakeControl::default()
+        };
+        let reply = ask(&mut core, &mut control, DaemonRequest::Start);
+        assert!(matches!(
+            reply.try_recv().unwrap(),
+            DaemonResponse::Error { message } if message.contains("no input device")
+        ));
+
+        control.fail_start = false;
+        ask(&mut core, &mut control, DaemonRequest::Start);
+        let waiting = ask(&mut core, &mut control, DaemonRequest::Stop { wait: true });
+        control.running = false;
+        core.finish_capture(
+            VoiceJobMessage::Error(VoiceError::new(
+                VoiceErrorKind::Transcription,
+                "model crashed",
+            )),
+            &TranscriptPostProcessor::default(),
+            Instant::now(),
+        );
+        assert_eq!(
+            waiting.try_recv().unwrap(),
+            DaemonResponse::Error {
+                message: "model crashed".to_string()
+            }
+        );
+        let DaemonResponse::Status { last_error, .. } =
+            ask(&mut core, &mut control, DaemonRequest::Status)
+                .try_recv()
+                .unwrap()
+        else {
+            panic!("expected status");
+        };
+        assert_eq!(last_error.as_deref(), Some("model crashed"));
+    }
+
+    #[test]
+    fn serve_client_answers_each_line() {
+        let (client, server) = UnixStream::pair().expect("socket pair");
+        let (requests_tx, requests_rx) = bounded::<PendingRequest>(4);
+        let server_thread = thread::spawn(move || serve_client(server, &requests_tx));
+        let responder = thread::spawn(move || {
+            for (request, reply) in requests_rx.iter() {
+                assert_eq!(request, DaemonRequest::Status);
+                let _ = reply.send(DaemonResponse::NotRecording);
+            }
+        });
+
+        let mut writer = client.try_clone().unwrap();
+        writer
+            .write_all(b"{\"cmd\":\"status\"}\n\nnot json\n")
+            .unwrap();
+        writer.shutdown(std::net::Shutdown::Write).unwrap();
+        let mut output = String::new();
+        BufReader::new(client).read_to_string(&mut output).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], r#"{"type":"not_recording"}"#);
+        assert!(lines[1].starts_with(r#"{"type":"error","message":"invalid daemon request"#));
+        server_thread.join().unwrap().unwrap();
+        responder.join().unwrap();
+    }
+}
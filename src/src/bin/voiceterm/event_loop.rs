@@ -3,57 +3,85 @@
 #[cfg(test)]
 use std::cell::Cell;
 use std::io::{self, ErrorKind};
+use std::process::ExitStatus;
 use std::time::{Duration, Instant};
 
 use crossbeam_channel::{never, select, TryRecvError, TrySendError};
 use crossterm::terminal::size as terminal_size;
-use voiceterm::{log_debug, VoiceCaptureSource, VoiceCaptureTrigger};
+use voiceterm::voice_error::VoiceErrorKind;
+use voiceterm::{log_debug, log_warn, VoiceCaptureSource, VoiceCaptureTrigger};
 
 use crate::arrow_keys::{is_arrow_escape_noise, parse_arrow_keys, parse_arrow_keys_only, ArrowKey};
+use crate::backend_exit::{exit_choice, BackendExit, ExitChoice, EXIT_STATUS_WAIT};
 use crate::button_handlers::{
     advance_hud_button_focus, send_enhanced_status_with_buttons, update_button_registry,
     ButtonActionContext,
 };
 use crate::buttons::ButtonAction;
 use crate::config::{HudRightPanel, VoiceSendMode};
+use crate::control::{handle_control_request, ControlResponse};
 use crate::event_state::{EventLoopDeps, EventLoopState, EventLoopTimers};
 use crate::help::{
     help_overlay_height, help_overlay_inner_width_for_terminal, help_overlay_width_for_terminal,
     HELP_OVERLAY_FOOTER,
 };
 use crate::input::InputEvent;
+use crate::latency_overlay::{
+    latency_overlay_height, latency_overlay_inner_width_for_terminal,
+    latency_overlay_width_for_terminal, LATENCY_OVERLAY_FOOTER,
+};
 use crate::overlays::{
-    show_help_overlay, show_settings_overlay, show_theme_picker_overlay, OverlayMode,
+    show_help_overlay, show_latency_overlay, show_queue_overlay, show_search_overlay,
+    show_settings_overlay, show_theme_picker_overlay, OverlayMode,
 };
 use crate::progress;
 use crate::prompt::should_auto_trigger;
+use crate::queue_overlay::{
+    queue_overlay_height, queue_overlay_inner_width_for_terminal, queue_overlay_width_for_terminal,
+    QueueChange, QueuePanel, QUEUE_OVERLAY_FOOTER,
+};
+use crate::search_overlay::{
+    apply_search_input, search_overlay_footer, search_overlay_height,
+    search_overlay_inner_width_for_terminal, search_overlay_width_for_terminal,
+};
+use crate::session_events::{emit_session_event, VoiceSessionEvent};
+use crate::session_record::{record_session_event, SessionEvent};
 use crate::settings::{
     settings_overlay_height, settings_overlay_inner_width_for_terminal,
     settings_overlay_width_for_terminal, SettingsItem, SETTINGS_OVERLAY_FOOTER,
 };
 use crate::settings_handlers::SettingsActionContext;
 use crate::status_line::{RecordingState, METER_HISTORY_MAX};
-use crate::terminal::{apply_pty_winsize, resolved_cols, take_sigwinch, update_pty_winsize};
+use crate::terminal::{
+    apply_pty_winsize, backend_rows, resolved_cols, take_sigwinch, update_pty_winsize,
+};
 use crate::theme::Theme;
 use crate::theme_ops::{
     apply_theme_picker_index, apply_theme_selection, theme_index_from_theme,
     theme_picker_has_longer_match, theme_picker_parse_index,
 };
 use crate::theme_picker::{
-    theme_picker_height, theme_picker_inner_width_for_terminal,
-    theme_picker_total_width_for_terminal, THEME_OPTIONS, THEME_PICKER_FOOTER,
-    THEME_PICKER_OPTION_START_ROW,
+    theme_options, theme_picker_height, theme_picker_inner_width_for_terminal,
+    theme_picker_total_width_for_terminal, THEME_PICKER_FOOTER, THEME_PICKER_OPTION_START_ROW,
+};
+use crate::transcript::{
+    deliver_transcript, loggable, resolve_dictation, resolve_urgent_hold, transcript_readiness,
+    transcript_ready, try_flush_pending, PendingTranscript, TranscriptIo, ECHO_RETRY_LABEL,
 };
-use crate::transcript::{try_flush_pending, TranscriptIo};
 use crate::voice_control::{
-    clear_capture_metrics, drain_voice_messages, reset_capture_visuals, start_voice_capture,
+    clear_capture_metrics, drain_voice_messages, local_minute_of_day, reset_capture_visuals,
+    start_voice_capture, MODEL_WARMUP_STATUS,
 };
+use crate::web_ui::WebUiCommand;
 use crate::writer::{set_status, WriterMessage};
 
 const EVENT_LOOP_IDLE_MS: u64 = 50;
 const THEME_PICKER_NUMERIC_TIMEOUT_MS: u64 = 350;
 const RECORDING_DURATION_UPDATE_MS: u64 = 200;
 const PROCESSING_SPINNER_TICK_MS: u64 = 120;
+const MODEL_WARMUP_TOAST_SECS: u64 = 2;
+const DEVICE_CHANGE_TOAST_SECS: u64 = 4;
+const ECHO_MISS_STATUS_SECS: u64 = 3;
 const METER_DB_FLOOR: f32 = -60.0;
 const PTY_OUTPUT_BATCH_CHUNKS: usize = 8;
 const PTY_INPUT_FLUSH_ATTEMPTS: usize = 16;
@@ -224,6 +252,80 @@ fn flush_pending_pty_input(state: &mut EventLoopState, deps: &mut EventLoopDeps)
     true
 }
 
+/// Hand an SGR mouse report to the backend when it tracks the mouse and the pointer is over its rows.
+fn forward_mouse_report(
+    state: &mut EventLoopState,
+    deps: &mut EventLoopDeps,
+    bytes: Vec<u8>,
+    y: u16,
+) -> bool {
+    let rows = backend_rows(
+        state.terminal_rows,
+        state.terminal_cols,
+        state.overlay_mode,
+        state.status_state.hud_style,
+    );
+    if !state.backend_modes.mouse_tracking() || y == 0 || y > rows {
+        return true;
+    }
+    write_or_queue_pty_input(state, deps, bytes)
+}
+
+/// Open (or refresh) the find overlay with `query` as the search phrase.
+fn open_scrollback_search(state: &mut EventLoopState, deps: &mut EventLoopDeps, query: String) {
+    state.status_state.hud_button_focus = None;
+    state.search_query = query;
+    if state.overlay_mode != OverlayMode::Search {
+        state.overlay_mode = OverlayMode::Search;
+        update_pty_winsize(
+            &mut deps.session,
+            &mut state.terminal_rows,
+            &mut state.terminal_cols,
+            state.overlay_mode,
+            state.status_state.hud_style,
+        );
+    }
+    let cols = resolved_cols(state.terminal_cols);
+    show_search_overlay(
+        &deps.writer_tx,
+        state.theme,
+        cols,
+        &state.search_query,
+        &state.scrollback,
+    );
+}
+
+/// Redraw the queue overlay after a key changed the queue, confirming drops and edits.
+fn apply_queue_change(
+    state: &mut EventLoopState,
+    timers: &mut EventLoopTimers,
+    deps: &mut EventLoopDeps,
+    change: Option<QueueChange>,
+) {
+    let Some(change) = change else {
+        return;
+    };
+    state.status_state.queue_depth = state.pending_transcripts.len();
+    if let Some(status) = change.status(state.pending_transcripts.len()) {
+        set_status(
+            &deps.writer_tx,
+            &mut timers.status_clear_deadline,
+            &mut state.current_status,
+            &mut state.status_state,
+            &status,
+            Some(Duration::from_secs(2)),
+        );
+    }
+    let cols = resolved_cols(state.terminal_cols);
+    show_queue_overlay(
+        &deps.writer_tx,
+        state.theme,
+        cols,
+        &state.pending_transcripts,
+        &mut state.queue_panel,
+    );
+}
+
 fn write_or_queue_pty_input(
     state: &mut EventLoopState,
     deps: &mut EventLoopDeps,
@@ -318,19 +420,57 @@ fn run_periodic_tasks(
                             &deps.backend_label,
                         );
                     }
+                    OverlayMode::Latency => {
+                        show_latency_overlay(
+                            &deps.writer_tx,
+                            state.theme,
+                            cols,
+                            &state.session_stats.voice_metrics,
+                        );
+                    }
+                    OverlayMode::Search => {
+                        show_search_overlay(
+                            &deps.writer_tx,
+                            state.theme,
+                            cols,
+                            &state.search_query,
+                            &state.scrollback,
+                        );
+                    }
+                    OverlayMode::Queue => {
+                        show_queue_overlay(
+                            &deps.writer_tx,
+                            state.theme,
+                            cols,
+                            &state.pending_transcripts,
+                            &mut state.queue_panel,
+                        );
+                    }
                     OverlayMode::None => {}
                 }
             }
         }
     }
 
+    if state.backend_exit.is_some() {
+        // Nothing can reach the backend until it is relaunched; queued work waits.
+        if let Some(control) = deps.control.as_ref() {
+            while let Some(command) = control.try_next() {
+                command.respond(ControlResponse::Error {
+                    message: format!("{} exited; relaunch it first", deps.backend_label),
+                });
+            }
+        }
+        return;
+    }
+
     if state.overlay_mode != OverlayMode::ThemePicker {
         state.theme_picker_digits.clear();
         timers.theme_picker_digit_deadline = None;
     } else if let Some(deadline) = timers.theme_picker_digit_deadline {
         if now >= deadline {
             if let Some(idx) =
-                theme_picker_parse_index(&state.theme_picker_digits, THEME_OPTIONS.len())
+                theme_picker_parse_index(&state.theme_picker_digits, theme_options().len())
             {
                 if apply_theme_picker_index(
                     idx,
@@ -437,18 +577,107 @@ fn run_periodic_tasks(
         }
     }
     state.prompt_tracker.on_idle(now, deps.auto_idle_timeout);
+    if state
+        .output_preview
+        .expire(&state.prompt_tracker, &mut state.status_state, now)
+    {
+        send_enhanced_status_with_buttons(
+            &deps.writer_tx,
+            &deps.button_registry,
+            &state.status_state,
+            state.overlay_mode,
+            state.terminal_cols,
+            state.theme,
+        );
+    }
+    if state.auto_voice_dnd.refresh(now, local_minute_of_day()) {
+        let label = state.auto_voice_dnd.label();
+        log_debug(&format!(
+            "auto-voice do-not-disturb: {}",
+            label.unwrap_or("off")
+        ));
+        state.status_state.auto_voice_dnd = label.map(str::to_string);
+        send_enhanced_status_with_buttons(
+            &deps.writer_tx,
+            &deps.button_registry,
+            &state.status_state,
+            state.overlay_mode,
+            state.terminal_cols,
+            state.theme,
+        );
+    }
+    // Do-not-disturb suspends auto-voice triggers and re-arming, not push-to-talk.
+    let auto_voice_armed = state.auto_voice_enabled && !state.auto_voice_dnd.is_active();
+
+    if let Some(model_ready) = deps.voice_manager.poll_preload() {
+        // A failed switch falls back to the previous model; keep the overlay config in step.
+        state.config.app.whisper_model_path =
+            deps.voice_manager.whisper_model_path().map(str::to_string);
+        // Only replace the warm-up notice; anything newer on the status line wins.
+        if state.current_status.as_deref() == Some(MODEL_WARMUP_STATUS) {
+            let text = if model_ready {
+                "Model ready"
+            } else if deps.voice_manager.model_loaded() {
+                "Model switch failed; keeping the previous model"
+            } else {
+                "Model warm-up failed; retrying on first capture"
+            };
+            set_status(
+                &deps.writer_tx,
+                &mut timers.status_clear_deadline,
+                &mut state.current_status,
+                &mut state.status_state,
+                text,
+                Some(Duration::from_secs(MODEL_WARMUP_TOAST_SECS)),
+            );
+        }
+    }
+
+    deps.voice_manager.release_idle_mic(now);
+    if let Some(change) = deps.voice_manager.poll_device_change() {
+        if change.levels_changed {
+            state.config.app.voice_vad_threshold_db = deps.voice_manager.vad_threshold_db();
+            state.config.app.voice_silence_tail_ms = deps.voice_manager.silence_tail_ms();
+            state.status_state.sensitivity_db = state.config.app.voice_vad_threshold_db;
+        }
+        if let Some(notice) = change.notice {
+            set_status(
+                &deps.writer_tx,
+                &mut timers.status_clear_deadline,
+                &mut state.current_status,
+                &mut state.status_state,
+                &notice,
+                Some(Duration::from_secs(DEVICE_CHANGE_TOAST_SECS)),
+            );
+        }
+    }
 
     drain_voice_messages(
         &mut deps.voice_manager,
         &state.config,
         &deps.voice_macros,
-        &mut deps.session,
+        &deps.postprocessor,
+        &mut deps.feedback,
+        &deps.uncertain_words,
+        &deps.urgent,
+        &deps.secrets,
+        &deps.translator,
+        &deps.prefix,
+        &mut state.urgent_hold,
+        state.dictation.as_mut(),
+        state.batch_window.as_mut(),
+        &mut state.redictation,
+        &deps.voice_navigation,
+        state.overlay_mode,
+        &mut deps.transcript_targets.route(&mut deps.session),
         &deps.writer_tx,
         &mut timers.status_clear_deadline,
         &mut state.current_status,
         &mut state.status_state,
         &mut state.session_stats,
+        &state.multiline,
         &mut state.pending_transcripts,
+        deps.queue_policy,
         &mut state.prompt_tracker,
         &mut timers.last_enter_at,
         now,
@@ -457,31 +686,193 @@ fn run_periodic_tasks(
         &mut timers.preview_clear_deadline,
         &mut timers.last_meter_update,
         &mut timers.last_auto_trigger_at,
-        state.auto_voice_enabled,
+        auto_voice_armed,
         deps.sound_on_complete,
         deps.sound_on_error,
     );
 
-    {
+    let capturing = !deps.voice_manager.is_idle();
+    let (batch_holding, batch_closed) = match state.batch_window.as_mut() {
+        Some(batch) => {
+            let was_open = batch.is_open();
+            let holding = batch.holding(now, capturing);
+            (holding, was_open && !holding)
+        }
+        None => (false, false),
+    };
+    if batch_holding {
+        // While capturing, the recording status owns the row.
+        let countdown = state
+            .batch_window
+            .as_mut()
+            .filter(|_| !capturing)
+            .and_then(|batch| batch.countdown_status(state.pending_transcripts.len(), now));
+        if let Some(countdown) = countdown {
+            set_status(
+                &deps.writer_tx,
+                &mut timers.status_clear_deadline,
+                &mut state.current_status,
+                &mut state.status_state,
+                &countdown,
+                None,
+            );
+        }
+    } else if state.overlay_mode != OverlayMode::Queue {
         let mut io = TranscriptIo {
-            session: &mut deps.session,
+            session: &mut deps.transcript_targets.route(&mut deps.session),
             writer_tx: &deps.writer_tx,
             status_clear_deadline: &mut timers.status_clear_deadline,
             current_status: &mut state.current_status,
             status_state: &mut state.status_state,
+            multiline: &state.multiline,
         };
-        try_flush_pending(
+        if let Some(waited) = try_flush_pending(
             &mut state.pending_transcripts,
             &state.prompt_tracker,
             &mut timers.last_enter_at,
             &mut io,
             now,
             deps.transcript_idle_timeout,
+        ) {
+            state.session_stats.voice_metrics.record_delivery(waited);
+        } else if batch_closed && !state.pending_transcripts.is_empty() {
+            io.set_status(
+                &format!("Transcript queued ({})", state.pending_transcripts.len()),
+                None,
+            );
+        }
+    }
+
+    if let Some(miss) = deps.transcript_targets.poll_echo(now) {
+        let status = if miss.retry {
+            log_debug("transcript not echoed by backend; retrying at the next prompt");
+            // The retry goes first so it is re-sent before anything queued after it.
+            state.pending_transcripts.push_front(PendingTranscript {
+                text: miss.text,
+                label: ECHO_RETRY_LABEL,
+                mode: miss.mode,
+                received_at: now,
+                seq: None,
+            });
+            state.status_state.queue_depth = state.pending_transcripts.len();
+            "Transcript not echoed; retrying at the next prompt"
+        } else {
+            log_warn("transcript not echoed by backend after retry");
+            "Transcript may not have reached the backend (no echo)"
+        };
+        set_status(
+            &deps.writer_tx,
+            &mut timers.status_clear_deadline,
+            &mut state.current_status,
+            &mut state.status_state,
+            status,
+            Some(Duration::from_secs(ECHO_MISS_STATUS_SECS)),
+        );
+    }
+
+    if let Some(control) = deps.control.as_ref() {
+        while let Some(command) = control.try_next() {
+            let mut io = TranscriptIo {
+                session: &mut deps.transcript_targets.route(&mut deps.session),
+                writer_tx: &deps.writer_tx,
+                status_clear_deadline: &mut timers.status_clear_deadline,
+                current_status: &mut state.current_status,
+                status_state: &mut state.status_state,
+                multiline: &state.multiline,
+            };
+            let response = handle_control_request(
+                &command.request,
+                &mut state.pending_transcripts,
+                deps.queue_policy,
+                &state.prompt_tracker,
+                &mut timers.last_enter_at,
+                &mut io,
+                now,
+                deps.transcript_idle_timeout,
+            );
+            command.respond(response);
+        }
+        control.publish_readiness(transcript_readiness(
+            &state.prompt_tracker,
+            timers.last_enter_at,
+            now,
+            deps.transcript_idle_timeout,
+        ));
+    }
+
+    poll_web_ui(state, timers, deps, now);
+
+    if state.overlay_mode == OverlayMode::Latency
+        && state.session_stats.voice_metrics.revision() != state.latency_overlay_revision
+    {
+        let cols = resolved_cols(state.terminal_cols);
+        show_latency_overlay(
+            &deps.writer_tx,
+            state.theme,
+            cols,
+            &state.session_stats.voice_metrics,
         );
+        state.latency_overlay_revision = state.session_stats.voice_metrics.revision();
     }
 
-    if state.auto_voice_enabled
+    if state.overlay_mode == OverlayMode::Queue
+        && state.queue_panel.is_stale(&state.pending_transcripts)
+    {
+        let cols = resolved_cols(state.terminal_cols);
+        show_queue_overlay(
+            &deps.writer_tx,
+            state.theme,
+            cols,
+            &state.pending_transcripts,
+            &mut state.queue_panel,
+        );
+    }
+
+    if let Some(replay) = state.prompt_replay.as_mut() {
+        let backend_ready = state.pending_transcripts.is_empty()
+            && deps.voice_manager.is_idle()
+            && transcript_ready(
+                &state.prompt_tracker,
+                timers.last_enter_at,
+                now,
+                deps.transcript_idle_timeout,
+            );
+        if let Some(prompt) = replay.poll(now, backend_ready) {
+            let label = format!("replay {}/{}", prompt.position, prompt.total);
+            log_debug(&format!("prompt replay sending {label}"));
+            let mut io = TranscriptIo {
+                session: &mut deps.transcript_targets.route(&mut deps.session),
+                writer_tx: &deps.writer_tx,
+                status_clear_deadline: &mut timers.status_clear_deadline,
+                current_status: &mut state.current_status,
+                status_state: &mut state.status_state,
+                multiline: &state.multiline,
+            };
+            // Replayed prompts are always submitted so the next one lands on a fresh prompt.
+            if deliver_transcript(
+                &prompt.text,
+                &label,
+                VoiceSendMode::Auto,
+                &mut io,
+                replay.remaining(),
+                None,
+            ) {
+                timers.last_enter_at = Some(now);
+            }
+        }
+        if replay.is_finished() {
+            log_debug("prompt replay complete");
+            state.prompt_replay = None;
+        }
+    }
+
+    deps.voice_manager.set_capture_blocked(
+        deps.queue_policy
+            .blocks_capture(state.pending_transcripts.len()),
+    );
+    if auto_voice_armed
         && deps.voice_manager.is_idle()
+        && !deps.voice_manager.capture_blocked()
         && should_auto_trigger(
             &state.prompt_tracker,
             now,
@@ -544,6 +935,211 @@ fn run_periodic_tasks(
     }
 }
 
+/// Publish web UI and WebSocket bridge status and apply any commands since the last tick.
+fn poll_web_ui(
+    state: &mut EventLoopState,
+    timers: &mut EventLoopTimers,
+    deps: &mut EventLoopDeps,
+    now: Instant,
+) {
+    let mut commands = Vec::new();
+    if let Some(web_ui) = deps.web_ui.as_mut() {
+        web_ui.publish(&state.status_state, &state.session_stats);
+        commands.extend(std::iter::from_fn(|| web_ui.try_next()));
+    }
+    if let Some(ws_bridge) = deps.ws_bridge.as_mut() {
+        ws_bridge.publish(&state.status_state, &state.session_stats);
+        commands.extend(std::iter::from_fn(|| ws_bridge.try_next()));
+    }
+    for command in commands {
+        log_debug(&format!("web ui command: {command:?}"));
+        match command {
+            WebUiCommand::Capture => {
+                if !deps.voice_manager.is_idle() {
+                    continue;
+                }
+                if let Err(err) = start_voice_capture_with_hook(
+                    &mut deps.voice_manager,
+                    VoiceCaptureTrigger::Manual,
+                    &deps.writer_tx,
+                    &mut timers.status_clear_deadline,
+                    &mut state.current_status,
+                    &mut state.status_state,
+                ) {
+                    set_status(
+                        &deps.writer_tx,
+                        &mut timers.status_clear_deadline,
+                        &mut state.current_status,
+                        &mut state.status_state,
+                        &VoiceErrorKind::classify(&err).status_message(),
+                        Some(Duration::from_secs(2)),
+                    );
+                    log_debug(&format!("voice capture failed: {err:#}"));
+                } else {
+                    timers.recording_started_at = Some(now);
+                    reset_capture_visuals(
+                        &mut state.status_state,
+                        &mut timers.preview_clear_deadline,
+                        &mut timers.last_meter_update,
+                    );
+                }
+            }
+            WebUiCommand::Stop => {
+                // The python fallback cannot stop early; its capture ends on silence.
+                if deps.voice_manager.active_source() == Some(VoiceCaptureSource::Python)
+                    || !deps.voice_manager.request_early_stop()
+                {
+                    continue;
+                }
+                state.status_state.recording_state = RecordingState::Processing;
+                clear_capture_metrics(&mut state.status_state);
+                state.processing_spinner_index = 0;
+                timers.last_processing_tick = Instant::now();
+                set_status(
+                    &deps.writer_tx,
+                    &mut timers.status_clear_deadline,
+                    &mut state.current_status,
+                    &mut state.status_state,
+                    "Processing",
+                    None,
+                );
+            }
+            WebUiCommand::Cancel => {
+                if !deps.voice_manager.cancel_capture() {
+                    continue;
+                }
+                state.status_state.recording_state = RecordingState::Idle;
+                clear_capture_metrics(&mut state.status_state);
+                timers.recording_started_at = None;
+                set_status(
+                    &deps.writer_tx,
+                    &mut timers.status_clear_deadline,
+                    &mut state.current_status,
+                    &mut state.status_state,
+                    "Capture cancelled",
+                    Some(Duration::from_secs(2)),
+                );
+            }
+        }
+    }
+}
+
+/// Keep the overlay up after the backend exits, showing how it ended and the relaunch keys.
+fn handle_backend_exit(
+    state: &mut EventLoopState,
+    timers: &mut EventLoopTimers,
+    deps: &mut EventLoopDeps,
+    status: Option<ExitStatus>,
+) {
+    let exit = BackendExit {
+        status,
+        uptime: timers.backend_started_at.elapsed(),
+    };
+    log_warn(&format!(
+        "backend exited ({status:?}) after {:.1}s",
+        exit.uptime.as_secs_f32()
+    ));
+    emit_session_event(|| VoiceSessionEvent::BackendExited);
+    if deps.voice_manager.cancel_capture() {
+        state.status_state.recording_state = RecordingState::Idle;
+        clear_capture_metrics(&mut state.status_state);
+        timers.recording_started_at = None;
+    }
+    if state.overlay_mode != OverlayMode::None {
+        state.overlay_mode = OverlayMode::None;
+        let _ = deps.writer_tx.send(WriterMessage::ClearOverlay);
+    }
+    // Turn off the modes the backend left on so the terminal reads keys normally again.
+    let mode_reset = state.backend_modes.reset();
+    if !mode_reset.is_empty() {
+        state.multiline.observe_output(&mode_reset);
+        let _ = deps.writer_tx.send(WriterMessage::PtyOutput(mode_reset));
+    }
+    state.pending_pty_input.clear();
+    state.pending_pty_input_offset = 0;
+    state.pending_pty_input_bytes = 0;
+    state.prompt_tracker.on_backend_restart();
+    let summary = exit.summary(&deps.backend_label);
+    state.backend_exit = Some(exit);
+    set_status(
+        &deps.writer_tx,
+        &mut timers.status_clear_deadline,
+        &mut state.current_status,
+        &mut state.status_state,
+        &summary,
+        None,
+    );
+}
+
+/// Start the backend again with the command line it was first launched with.
+fn relaunch_backend(
+    state: &mut EventLoopState,
+    timers: &mut EventLoopTimers,
+    deps: &mut EventLoopDeps,
+) {
+    let session = match deps.launcher.spawn() {
+        Ok(session) => session,
+        Err(err) => {
+            log_warn(&format!("backend relaunch failed: {err:#}"));
+            set_status(
+                &deps.writer_tx,
+                &mut timers.status_clear_deadline,
+                &mut state.current_status,
+                &mut state.status_state,
+                &format!("Relaunch failed: {err} · r retry · q quit"),
+                None,
+            );
+            return;
+        }
+    };
+    log_debug(&format!("backend relaunched, pid: {}", session.child_pid()));
+    deps.session = session;
+    apply_pty_winsize(
+        &mut deps.session,
+        state.terminal_rows,
+        state.terminal_cols,
+        state.overlay_mode,
+        state.status_state.hud_style,
+    );
+    state.backend_exit = None;
+    timers.backend_started_at = Instant::now();
+    timers.last_enter_at = None;
+    emit_session_event(|| VoiceSessionEvent::SessionStarted {
+        backend: deps.backend_label.clone(),
+    });
+    set_status(
+        &deps.writer_tx,
+        &mut timers.status_clear_deadline,
+        &mut state.current_status,
+        &mut state.status_state,
+        &format!("Relaunched {}", deps.backend_label),
+        Some(Duration::from_secs(2)),
+    );
+}
+
+/// Input while the backend is down; returns false to quit.
+fn handle_exited_backend_input(
+    evt: InputEvent,
+    state: &mut EventLoopState,
+    timers: &mut EventLoopTimers,
+    deps: &mut EventLoopDeps,
+) -> bool {
+    let choice = match evt {
+        InputEvent::Bytes(bytes) => exit_choice(&bytes),
+        InputEvent::EnterKey => Some(ExitChoice::Relaunch),
+        InputEvent::Exit => Some(ExitChoice::Quit),
+        _ => None,
+    };
+    match choice {
+        Some(ExitChoice::Relaunch) => {
+            relaunch_backend(state, timers, deps);
+            true
+        }
+        Some(ExitChoice::Quit) => false,
+        None => true,
+    }
+}
+
 fn flush_pending_output_or_continue(state: &mut EventLoopState, deps: &EventLoopDeps) -> bool {
     if state.pending_pty_output.is_none() {
         return true;
@@ -562,7 +1158,11 @@ pub(crate) fn run_event_loop(
     let mut last_periodic_tick = Instant::now();
     while running {
         if !flush_pending_pty_input(state, deps) {
-            running = false;
+            // A write fails once the backend is gone; anything else still ends the session.
+            match deps.session.wait_exit(EXIT_STATUS_WAIT) {
+                Some(status) => handle_backend_exit(state, timers, deps, Some(status)),
+                None => running = false,
+            }
             continue;
         }
         if !flush_pending_output_or_continue(state, deps) {
@@ -573,7 +1173,7 @@ pub(crate) fn run_event_loop(
             run_periodic_tasks(state, timers, deps, now);
             last_periodic_tick = now;
         }
-        let output_guard = if state.pending_pty_output.is_some() {
+        let output_guard = if state.pending_pty_output.is_some() || state.backend_exit.is_some() {
             Some(never::<Vec<u8>>())
         } else {
             None
@@ -589,6 +1189,11 @@ pub(crate) fn run_event_loop(
             recv(input_rx) -> event => {
                 match event {
                     Ok(evt) => {
+                        record_session_event(|| SessionEvent::from_input(&evt));
+                        if state.backend_exit.is_some() {
+                            running = handle_exited_backend_input(evt, state, timers, deps);
+                            continue;
+                        }
                         if state.overlay_mode != OverlayMode::None {
                             match (state.overlay_mode, evt) {
                                 (_, InputEvent::Exit) => running = false,
@@ -712,7 +1317,19 @@ pub(crate) fn run_event_loop(
                                             settings_ctx.toggle_macros_enabled();
                                             should_redraw = true;
                                         }
-                                        SettingsItem::Sensitivity => {}
+                                        SettingsItem::CodeMode => {
+                                            settings_ctx.toggle_code_mode();
+                                            should_redraw = true;
+                                        }
+                                        SettingsItem::Sensitivity | SettingsItem::SilenceTail => {}
+                                        SettingsItem::VadEngine => {
+                                            settings_ctx.cycle_vad_engine(1);
+                                            should_redraw = true;
+                                        }
+                                        SettingsItem::Model => {
+                                            settings_ctx.cycle_whisper_model(1);
+                                            should_redraw = true;
+                                        }
                                         SettingsItem::Theme => {
                                             settings_ctx.cycle_theme(1);
                                             should_redraw = true;
@@ -742,6 +1359,7 @@ pub(crate) fn run_event_loop(
                                             should_redraw = true;
                                         }
                                         SettingsItem::Backend | SettingsItem::Pipeline => {}
+                                        SettingsItem::Save => settings_ctx.save_settings(),
                                         SettingsItem::Close => {
                                             state.overlay_mode = OverlayMode::None;
                                             let _ = deps.writer_tx.send(WriterMessage::ClearOverlay);
@@ -823,10 +1441,26 @@ pub(crate) fn run_event_loop(
                                                         settings_ctx.toggle_macros_enabled();
                                                         should_redraw = true;
                                                     }
+                                                    SettingsItem::CodeMode => {
+                                                        settings_ctx.toggle_code_mode();
+                                                        should_redraw = true;
+                                                    }
                                                     SettingsItem::Sensitivity => {
                                                         settings_ctx.adjust_sensitivity(-5.0);
                                                         should_redraw = true;
                                                     }
+                                                    SettingsItem::SilenceTail => {
+                                                        settings_ctx.adjust_silence_tail(-100);
+                                                        should_redraw = true;
+                                                    }
+                                                    SettingsItem::VadEngine => {
+                                                        settings_ctx.cycle_vad_engine(-1);
+                                                        should_redraw = true;
+                                                    }
+                                                    SettingsItem::Model => {
+                                                        settings_ctx.cycle_whisper_model(-1);
+                                                        should_redraw = true;
+                                                    }
                                                     SettingsItem::Theme => {
                                                         settings_ctx.cycle_theme(-1);
                                                         should_redraw = true;
@@ -857,6 +1491,7 @@ pub(crate) fn run_event_loop(
                                                     }
                                                     SettingsItem::Backend
                                                     | SettingsItem::Pipeline
+                                                    | SettingsItem::Save
                                                     | SettingsItem::Close
                                                     | SettingsItem::Quit => {}
                                                 },
@@ -873,10 +1508,26 @@ pub(crate) fn run_event_loop(
                                                         settings_ctx.toggle_macros_enabled();
                                                         should_redraw = true;
                                                     }
+                                                    SettingsItem::CodeMode => {
+                                                        settings_ctx.toggle_code_mode();
+                                                        should_redraw = true;
+                                                    }
                                                     SettingsItem::Sensitivity => {
                                                         settings_ctx.adjust_sensitivity(5.0);
                                                         should_redraw = true;
                                                     }
+                                                    SettingsItem::SilenceTail => {
+                                                        settings_ctx.adjust_silence_tail(100);
+                                                        should_redraw = true;
+                                                    }
+                                                    SettingsItem::VadEngine => {
+                                                        settings_ctx.cycle_vad_engine(1);
+                                                        should_redraw = true;
+                                                    }
+                                                    SettingsItem::Model => {
+                                                        settings_ctx.cycle_whisper_model(1);
+                                                        should_redraw = true;
+                                                    }
                                                     SettingsItem::Theme => {
                                                         settings_ctx.cycle_theme(1);
                                                         should_redraw = true;
@@ -907,6 +1558,7 @@ pub(crate) fn run_event_loop(
                                                     }
                                                     SettingsItem::Backend
                                                     | SettingsItem::Pipeline
+                                                    | SettingsItem::Save
                                                     | SettingsItem::Close
                                                     | SettingsItem::Quit => {}
                                                 },
@@ -1056,7 +1708,7 @@ pub(crate) fn run_event_loop(
                                         timers.theme_picker_digit_deadline = None;
                                     } else if let Some(keys) = parse_arrow_keys_only(&bytes) {
                                         let mut moved = false;
-                                        let total = THEME_OPTIONS.len();
+                                        let total = theme_options().len();
                                         for key in keys {
                                             let direction = match key {
                                                 ArrowKey::Up | ArrowKey::Left => -1,
@@ -1099,11 +1751,11 @@ pub(crate) fn run_event_loop(
                                             );
                                             if let Some(idx) = theme_picker_parse_index(
                                                 &state.theme_picker_digits,
-                                                THEME_OPTIONS.len(),
+                                                theme_options().len(),
                                             ) {
                                                 if !theme_picker_has_longer_match(
                                                     &state.theme_picker_digits,
-                                                    THEME_OPTIONS.len(),
+                                                    theme_options().len(),
                                                 ) {
                                                     if apply_theme_picker_index(
                                                         idx,
@@ -1127,6 +1779,65 @@ pub(crate) fn run_event_loop(
                                         }
                                     }
                                 }
+                                (_, InputEvent::Find(query)) => {
+                                    open_scrollback_search(state, deps, query);
+                                }
+                                // Typing edits the query; a lone Esc falls through and closes.
+                                (OverlayMode::Search, InputEvent::Bytes(bytes))
+                                    if bytes.as_slice() != [0x1b] =>
+                                {
+                                    if apply_search_input(&mut state.search_query, &bytes) {
+                                        let cols = resolved_cols(state.terminal_cols);
+                                        show_search_overlay(
+                                            &deps.writer_tx,
+                                            state.theme,
+                                            cols,
+                                            &state.search_query,
+                                            &state.scrollback,
+                                        );
+                                    }
+                                }
+                                // Keys edit the queue; a lone Esc closes unless it cancels an edit.
+                                (OverlayMode::Queue, InputEvent::Bytes(bytes))
+                                    if bytes.as_slice() != [0x1b] || state.queue_panel.is_editing() =>
+                                {
+                                    let change =
+                                        state.queue_panel.apply_input(&mut state.pending_transcripts, &bytes);
+                                    apply_queue_change(state, timers, deps, change);
+                                }
+                                (OverlayMode::Queue, InputEvent::EnterKey) => {
+                                    let change = state.queue_panel.enter(&mut state.pending_transcripts);
+                                    apply_queue_change(state, timers, deps, change);
+                                }
+                                // Backend mouse reports must not close the overlay.
+                                (_, InputEvent::MouseReport { bytes, y }) => {
+                                    if !forward_mouse_report(state, deps, bytes, y) {
+                                        running = false;
+                                    }
+                                }
+                                // With voice navigation the hotkey captures the next command
+                                // instead of closing the overlay.
+                                (_, InputEvent::VoiceTrigger)
+                                    if deps.voice_navigation.is_enabled() =>
+                                {
+                                    if let Err(err) = start_voice_capture(
+                                        &mut deps.voice_manager,
+                                        VoiceCaptureTrigger::Manual,
+                                        &deps.writer_tx,
+                                        &mut timers.status_clear_deadline,
+                                        &mut state.current_status,
+                                        &mut state.status_state,
+                                    ) {
+                                        log_debug(&format!("voice capture failed: {err:#}"));
+                                    } else {
+                                        timers.recording_started_at = Some(Instant::now());
+                                        reset_capture_visuals(
+                                            &mut state.status_state,
+                                            &mut timers.preview_clear_deadline,
+                                            &mut timers.last_meter_update,
+                                        );
+                                    }
+                                }
                                 (_, _) => {
                                     state.overlay_mode = OverlayMode::None;
                                     let _ = deps.writer_tx.send(WriterMessage::ClearOverlay);
@@ -1150,6 +1861,63 @@ pub(crate) fn run_event_loop(
                             }
                             continue;
                         }
+                        if state.urgent_hold.is_some() {
+                            let confirmed = match &evt {
+                                InputEvent::EnterKey => Some(true),
+                                InputEvent::Bytes(bytes) if bytes.as_slice() == [0x1b] => {
+                                    Some(false)
+                                }
+                                _ => None,
+                            };
+                            if let Some(confirmed) = confirmed {
+                                let mut io = TranscriptIo {
+                                    session: &mut deps.transcript_targets.route(&mut deps.session),
+                                    writer_tx: &deps.writer_tx,
+                                    status_clear_deadline: &mut timers.status_clear_deadline,
+                                    current_status: &mut state.current_status,
+                                    status_state: &mut state.status_state,
+                                    multiline: &state.multiline,
+                                };
+                                if resolve_urgent_hold(&mut state.urgent_hold, confirmed, &mut io) {
+                                    timers.last_enter_at = Some(Instant::now());
+                                }
+                                continue;
+                            }
+                        }
+                        // While capturing, Enter keeps its early-stop meaning.
+                        if let Some(draft) = state
+                            .dictation
+                            .as_mut()
+                            .filter(|draft| !draft.is_empty() && deps.voice_manager.is_idle())
+                        {
+                            let confirmed = match &evt {
+                                InputEvent::EnterKey => Some(true),
+                                InputEvent::Bytes(bytes) if bytes.as_slice() == [0x1b] => {
+                                    Some(false)
+                                }
+                                _ => None,
+                            };
+                            if let Some(confirmed) = confirmed {
+                                let mut io = TranscriptIo {
+                                    session: &mut deps.transcript_targets.route(&mut deps.session),
+                                    writer_tx: &deps.writer_tx,
+                                    status_clear_deadline: &mut timers.status_clear_deadline,
+                                    current_status: &mut state.current_status,
+                                    status_state: &mut state.status_state,
+                                    multiline: &state.multiline,
+                                };
+                                if resolve_dictation(draft, confirmed, &mut io) {
+                                    timers.last_enter_at = Some(Instant::now());
+                                }
+                                continue;
+                            }
+                        }
+                        if let Some(batch) = state.batch_window.as_mut().filter(|batch| batch.is_open()) {
+                            if matches!(evt, InputEvent::EnterKey) {
+                                batch.send_now();
+                                continue;
+                            }
+                        }
                         match evt {
                             InputEvent::HelpToggle => {
                                 state.status_state.hud_button_focus = None;
@@ -1164,6 +1932,46 @@ pub(crate) fn run_event_loop(
                                 let cols = resolved_cols(state.terminal_cols);
                                 show_help_overlay(&deps.writer_tx, state.theme, cols);
                             }
+                            InputEvent::LatencyHud => {
+                                state.status_state.hud_button_focus = None;
+                                state.overlay_mode = OverlayMode::Latency;
+                                update_pty_winsize(
+                                    &mut deps.session,
+                                    &mut state.terminal_rows,
+                                    &mut state.terminal_cols,
+                                    state.overlay_mode,
+                                    state.status_state.hud_style,
+                                );
+                                let cols = resolved_cols(state.terminal_cols);
+                                show_latency_overlay(
+                                    &deps.writer_tx,
+                                    state.theme,
+                                    cols,
+                                    &state.session_stats.voice_metrics,
+                                );
+                                state.latency_overlay_revision =
+                                    state.session_stats.voice_metrics.revision();
+                            }
+                            InputEvent::QueuePanel => {
+                                state.status_state.hud_button_focus = None;
+                                state.overlay_mode = OverlayMode::Queue;
+                                update_pty_winsize(
+                                    &mut deps.session,
+                                    &mut state.terminal_rows,
+                                    &mut state.terminal_cols,
+                                    state.overlay_mode,
+                                    state.status_state.hud_style,
+                                );
+                                let cols = resolved_cols(state.terminal_cols);
+                                state.queue_panel = QueuePanel::default();
+                                show_queue_overlay(
+                                    &deps.writer_tx,
+                                    state.theme,
+                                    cols,
+                                    &state.pending_transcripts,
+                                    &mut state.queue_panel,
+                                );
+                            }
                             InputEvent::ThemePicker => {
                                 state.status_state.hud_button_focus = None;
                                 state.overlay_mode = OverlayMode::ThemePicker;
@@ -1278,11 +2086,15 @@ pub(crate) fn run_event_loop(
                                 }
 
                                 state.status_state.hud_button_focus = None;
+                                deps.transcript_targets.forget_last_insert();
                                 if !write_or_queue_pty_input(state, deps, bytes) {
                                     running = false;
                                 }
                             }
-                            InputEvent::VoiceTrigger => {
+                            trigger @ (InputEvent::VoiceTrigger | InputEvent::VoiceTriggerAs(_)) => {
+                                if let InputEvent::VoiceTriggerAs(mode) = trigger {
+                                    deps.voice_manager.set_next_send_mode(Some(mode));
+                                }
                                 if let Err(err) = start_voice_capture(
                                     &mut deps.voice_manager,
                                     VoiceCaptureTrigger::Manual,
@@ -1296,7 +2108,7 @@ pub(crate) fn run_event_loop(
                                         &mut timers.status_clear_deadline,
                                         &mut state.current_status,
                                         &mut state.status_state,
-                                        "Voice capture failed (see log)",
+                                        &VoiceErrorKind::classify(&err).status_message(),
                                         Some(Duration::from_secs(2)),
                                     );
                                     log_debug(&format!("voice capture failed: {err:#}"));
@@ -1415,6 +2227,167 @@ pub(crate) fn run_event_loop(
                                 );
                                 settings_ctx.adjust_sensitivity(-5.0);
                             }
+                            InputEvent::CycleTarget => {
+                                let label = deps.transcript_targets.cycle().to_string();
+                                state
+                                    .multiline
+                                    .set_backend_target(!deps.transcript_targets.pane_active());
+                                state.status_state.transcript_target =
+                                    deps.transcript_targets.status_label();
+                                log_debug(&format!("transcript target switched to {label}"));
+                                set_status(
+                                    &deps.writer_tx,
+                                    &mut timers.status_clear_deadline,
+                                    &mut state.current_status,
+                                    &mut state.status_state,
+                                    &format!("Transcripts go to {label}"),
+                                    Some(Duration::from_secs(2)),
+                                );
+                            }
+                            InputEvent::CycleVadEngine => {
+                                let mut settings_ctx = SettingsActionContext::new(
+                                    &mut state.config,
+                                    &mut state.status_state,
+                                    &mut state.auto_voice_enabled,
+                                    &mut deps.voice_manager,
+                                    &deps.writer_tx,
+                                    &mut timers.status_clear_deadline,
+                                    &mut state.current_status,
+                                    &mut timers.last_auto_trigger_at,
+                                    &mut timers.recording_started_at,
+                                    &mut timers.preview_clear_deadline,
+                                    &mut timers.last_meter_update,
+                                    &deps.button_registry,
+                                    state.overlay_mode,
+                                    &mut state.terminal_rows,
+                                    &mut state.terminal_cols,
+                                    &mut state.theme,
+                                    Some(&mut deps.session),
+                                );
+                                settings_ctx.cycle_vad_engine(1);
+                            }
+                            InputEvent::SwitchModel => {
+                                let mut settings_ctx = SettingsActionContext::new(
+                                    &mut state.config,
+                                    &mut state.status_state,
+                                    &mut state.auto_voice_enabled,
+                                    &mut deps.voice_manager,
+                                    &deps.writer_tx,
+                                    &mut timers.status_clear_deadline,
+                                    &mut state.current_status,
+                                    &mut timers.last_auto_trigger_at,
+                                    &mut timers.recording_started_at,
+                                    &mut timers.preview_clear_deadline,
+                                    &mut timers.last_meter_update,
+                                    &deps.button_registry,
+                                    state.overlay_mode,
+                                    &mut state.terminal_rows,
+                                    &mut state.terminal_cols,
+                                    &mut state.theme,
+                                    Some(&mut deps.session),
+                                );
+                                settings_ctx.switch_model_profile();
+                            }
+                            InputEvent::CycleTheme => {
+                                let mut settings_ctx = SettingsActionContext::new(
+                                    &mut state.config,
+                                    &mut state.status_state,
+                                    &mut state.auto_voice_enabled,
+                                    &mut deps.voice_manager,
+                                    &deps.writer_tx,
+                                    &mut timers.status_clear_deadline,
+                                    &mut state.current_status,
+                                    &mut timers.last_auto_trigger_at,
+                                    &mut timers.recording_started_at,
+                                    &mut timers.preview_clear_deadline,
+                                    &mut timers.last_meter_update,
+                                    &deps.button_registry,
+                                    state.overlay_mode,
+                                    &mut state.terminal_rows,
+                                    &mut state.terminal_cols,
+                                    &mut state.theme,
+                                    Some(&mut deps.session),
+                                );
+                                settings_ctx.cycle_theme(1);
+                            }
+                            InputEvent::Find(query) => {
+                                open_scrollback_search(state, deps, query);
+                            }
+                            InputEvent::FlagTranscript => {
+                                let text = match deps.feedback.flag_last(Instant::now()) {
+                                    Ok(Some(heard)) => {
+                                        log_debug(&format!("transcript flagged: {}", loggable(&heard)));
+                                        "Flagged; say it again to record the correction"
+                                    }
+                                    Ok(None) => "Nothing to flag yet",
+                                    Err(err) => {
+                                        log_debug(&format!("transcript flag failed: {err:#}"));
+                                        "Flag failed (see log)"
+                                    }
+                                };
+                                set_status(
+                                    &deps.writer_tx,
+                                    &mut timers.status_clear_deadline,
+                                    &mut state.current_status,
+                                    &mut state.status_state,
+                                    text,
+                                    Some(Duration::from_secs(3)),
+                                );
+                            }
+                            InputEvent::MuteAutoVoice => {
+                                let muted = state.auto_voice_dnd.toggle_mute(now);
+                                state.auto_voice_dnd.refresh(now, local_minute_of_day());
+                                state.status_state.auto_voice_dnd =
+                                    state.auto_voice_dnd.label().map(str::to_string);
+                                // Muting mid-call should drop the capture that is already listening.
+                                if muted
+                                    && state.auto_voice_enabled
+                                    && deps.voice_manager.cancel_capture()
+                                {
+                                    state.status_state.recording_state = RecordingState::Idle;
+                                    clear_capture_metrics(&mut state.status_state);
+                                    timers.recording_started_at = None;
+                                }
+                                let text = if muted {
+                                    "Auto-voice muted for 15 min"
+                                } else if state.auto_voice_dnd.is_active() {
+                                    "Auto-voice unmuted (quiet hours still on)"
+                                } else {
+                                    "Auto-voice unmuted"
+                                };
+                                set_status(
+                                    &deps.writer_tx,
+                                    &mut timers.status_clear_deadline,
+                                    &mut state.current_status,
+                                    &mut state.status_state,
+                                    text,
+                                    Some(Duration::from_secs(2)),
+                                );
+                            }
+                            InputEvent::UndoTranscript => {
+                                let text = match deps
+                                    .transcript_targets
+                                    .undo_last_insert(&mut deps.session)
+                                {
+                                    Ok(Some(chars)) => {
+                                        log_debug(&format!("erased last transcript ({chars} chars)"));
+                                        "Transcript erased"
+                                    }
+                                    Ok(None) => "Nothing to undo",
+                                    Err(err) => {
+                                        log_debug(&format!("transcript undo failed: {err:#}"));
+                                        "Undo failed (see log)"
+                                    }
+                                };
+                                set_status(
+                                    &deps.writer_tx,
+                                    &mut timers.status_clear_deadline,
+                                    &mut state.current_status,
+                                    &mut state.status_state,
+                                    text,
+                                    Some(Duration::from_secs(2)),
+                                );
+                            }
                             InputEvent::EnterKey => {
                                 if let Some(action) = state.status_state.hud_button_focus {
                                     state.status_state.hud_button_focus = None;
@@ -1458,7 +2431,11 @@ pub(crate) fn run_event_loop(
                                     continue;
                                 }
                                 // In insert mode, Enter stops capture early and sends what was recorded
-                                if state.config.voice_send_mode == VoiceSendMode::Insert && !deps.voice_manager.is_idle() {
+                                let send_mode = deps
+                                    .voice_manager
+                                    .capture_send_mode()
+                                    .unwrap_or(state.config.voice_send_mode);
+                                if send_mode == VoiceSendMode::Insert && !deps.voice_manager.is_idle() {
                                     if deps.voice_manager.active_source() == Some(VoiceCaptureSource::Python) {
                                         let _ = deps.voice_manager.cancel_capture();
                                         state.status_state.recording_state = RecordingState::Idle;
@@ -1489,6 +2466,7 @@ pub(crate) fn run_event_loop(
                                     }
                                 } else {
                                     // Forward Enter to PTY
+                                    deps.transcript_targets.forget_last_insert();
                                     if !write_or_queue_pty_input(state, deps, vec![0x0d]) {
                                         running = false;
                                     } else {
@@ -1499,6 +2477,11 @@ pub(crate) fn run_event_loop(
                             InputEvent::Exit => {
                                 running = false;
                             }
+                            InputEvent::MouseReport { bytes, y } => {
+                                if !forward_mouse_report(state, deps, bytes, y) {
+                                    running = false;
+                                }
+                            }
                             InputEvent::MouseClick { x, y } => {
                                 // Only process clicks if mouse is enabled
                                 if !state.status_state.mouse_enabled {
@@ -1511,6 +2494,9 @@ pub(crate) fn run_event_loop(
                                         OverlayMode::Help => help_overlay_height(),
                                         OverlayMode::ThemePicker => theme_picker_height(),
                                         OverlayMode::Settings => settings_overlay_height(),
+                                        OverlayMode::Latency => latency_overlay_height(),
+                                        OverlayMode::Search => search_overlay_height(),
+                                        OverlayMode::Queue => queue_overlay_height(),
                                         OverlayMode::None => 0,
                                     };
                                     if overlay_height == 0 || state.terminal_rows == 0 {
@@ -1540,6 +2526,21 @@ pub(crate) fn run_event_loop(
                                             settings_overlay_inner_width_for_terminal(cols),
                                             SETTINGS_OVERLAY_FOOTER,
                                         ),
+                                        OverlayMode::Latency => (
+                                            latency_overlay_width_for_terminal(cols),
+                                            latency_overlay_inner_width_for_terminal(cols),
+                                            LATENCY_OVERLAY_FOOTER,
+                                        ),
+                                        OverlayMode::Search => (
+                                            search_overlay_width_for_terminal(cols),
+                                            search_overlay_inner_width_for_terminal(cols),
+                                            search_overlay_footer(cols),
+                                        ),
+                                        OverlayMode::Queue => (
+                                            queue_overlay_width_for_terminal(cols),
+                                            queue_overlay_inner_width_for_terminal(cols),
+                                            QUEUE_OVERLAY_FOOTER,
+                                        ),
                                         OverlayMode::None => (0, 0, ""),
                                     };
 
@@ -1585,14 +2586,14 @@ pub(crate) fn run_event_loop(
                                     if state.overlay_mode == OverlayMode::ThemePicker {
                                         let options_start = THEME_PICKER_OPTION_START_ROW;
                                         let options_end =
-                                            options_start.saturating_add(THEME_OPTIONS.len().saturating_sub(1));
+                                            options_start.saturating_add(theme_options().len().saturating_sub(1));
                                         if overlay_row >= options_start
                                             && overlay_row <= options_end
                                             && x > 1
                                             && (x as usize) < overlay_width
                                         {
                                             let idx = overlay_row.saturating_sub(options_start);
-                                            if let Some((_, name, _)) = THEME_OPTIONS.get(idx) {
+                                            if let Some((_, name, _)) = theme_options().get(idx) {
                                                 if let Some(requested) = Theme::from_name(name) {
                                                     state.theme = apply_theme_selection(
                                                         &mut state.config,
@@ -1685,23 +2686,56 @@ pub(crate) fn run_event_loop(
                         if !data.is_empty() {
                             state.suppress_startup_escape_input = false;
                         }
+                        state.prompt_tracker.resize_screen(
+                            backend_rows(
+                                state.terminal_rows,
+                                state.terminal_cols,
+                                state.overlay_mode,
+                                state.status_state.hud_style,
+                            ),
+                            state.terminal_cols,
+                        );
                         state.prompt_tracker.feed_output(&data);
-                        {
+                        state.backend_modes.observe(&data);
+                        state.scrollback.observe(&data);
+                        if state.output_preview.observe(
+                            &state.prompt_tracker,
+                            &mut state.status_state,
+                            now,
+                        ) {
+                            send_enhanced_status_with_buttons(
+                                &deps.writer_tx,
+                                &deps.button_registry,
+                                &state.status_state,
+                                state.overlay_mode,
+                                state.terminal_cols,
+                                state.theme,
+                            );
+                        }
+                        state.multiline.observe_output(&data);
+                        deps.transcript_targets.observe_output(&data);
+                        record_session_event(|| SessionEvent::PtyOutput {
+                            bytes: data.clone(),
+                        });
+                        if state.overlay_mode != OverlayMode::Queue {
                             let mut io = TranscriptIo {
-                                session: &mut deps.session,
+                                session: &mut deps.transcript_targets.route(&mut deps.session),
                                 writer_tx: &deps.writer_tx,
                                 status_clear_deadline: &mut timers.status_clear_deadline,
                                 current_status: &mut state.current_status,
                                 status_state: &mut state.status_state,
+                                multiline: &state.multiline,
                             };
-                            try_flush_pending(
+                            if let Some(waited) = try_flush_pending(
                                 &mut state.pending_transcripts,
                                 &state.prompt_tracker,
                                 &mut timers.last_enter_at,
                                 &mut io,
                                 now,
                                 deps.transcript_idle_timeout,
-                            );
+                            ) {
+                                state.session_stats.voice_metrics.record_delivery(waited);
+                            }
                         }
                         match deps.writer_tx.try_send(WriterMessage::PtyOutput(data)) {
                             Ok(()) => {}
@@ -1717,13 +2751,28 @@ pub(crate) fn run_event_loop(
                             &mut deps.voice_manager,
                             &state.config,
                             &deps.voice_macros,
-                            &mut deps.session,
+                            &deps.postprocessor,
+                            &mut deps.feedback,
+                            &deps.uncertain_words,
+                            &deps.urgent,
+                            &deps.secrets,
+                            &deps.translator,
+                            &deps.prefix,
+                            &mut state.urgent_hold,
+                            state.dictation.as_mut(),
+                            state.batch_window.as_mut(),
+                            &mut state.redictation,
+                            &deps.voice_navigation,
+                            state.overlay_mode,
+                            &mut deps.transcript_targets.route(&mut deps.session),
                             &deps.writer_tx,
                             &mut timers.status_clear_deadline,
                             &mut state.current_status,
                             &mut state.status_state,
                             &mut state.session_stats,
+                            &state.multiline,
                             &mut state.pending_transcripts,
+                            deps.queue_policy,
                             &mut state.prompt_tracker,
                             &mut timers.last_enter_at,
                             now,
@@ -1732,16 +2781,18 @@ pub(crate) fn run_event_loop(
                             &mut timers.preview_clear_deadline,
                             &mut timers.last_meter_update,
                             &mut timers.last_auto_trigger_at,
-                            state.auto_voice_enabled,
+                            state.auto_voice_enabled && !state.auto_voice_dnd.is_active(),
                             deps.sound_on_complete,
                             deps.sound_on_error,
                         );
                         if output_disconnected && state.pending_pty_output.is_none() {
-                            running = false;
+                            let status = deps.session.wait_exit(EXIT_STATUS_WAIT);
+                            handle_backend_exit(state, timers, deps, status);
                         }
                     }
                     Err(_) => {
-                        running = false;
+                        let status = deps.session.wait_exit(EXIT_STATUS_WAIT);
+                        handle_backend_exit(state, timers, deps, status);
                     }
                 }
             }
@@ -1759,16 +2810,26 @@ mod tests {
     use std::collections::VecDeque;
     use std::io;
     use voiceterm::pty_session::PtyOverlaySession;
+    use voiceterm::VoiceJobMessage;
 
+    use crate::backend_exit::BackendLauncher;
     use crate::buttons::ButtonRegistry;
-    use crate::config::OverlayConfig;
+    use crate::config::{OverlayConfig, SecretsConfig};
+    use crate::input::BackendModes;
+    use crate::output_preview::OutputPreview;
     use crate::prompt::{PromptLogger, PromptTracker};
+    use crate::scrollback::Scrollback;
     use crate::session_stats::SessionStats;
     use crate::settings::SettingsMenuState;
     use crate::status_line::{Pipeline, StatusLineState, VoiceMode};
     use crate::theme::Theme;
     use crate::theme_ops::theme_index_from_theme;
-    use crate::voice_control::VoiceManager;
+    use crate::transcript::{
+        MultilineDelivery, QueuePolicy, SecretsFilter, TranscriptFeedback, TranscriptPostProcessor,
+        TranscriptPrefix, TranscriptTargets, TranscriptTranslator, UncertainWordMarker,
+        UrgentKeywords,
+    };
+    use crate::voice_control::{AutoVoiceDnd, Redictation, VoiceManager, VoiceNavigation};
     use crate::voice_macros::VoiceMacros;
 
     thread_local! {
@@ -1938,7 +2999,7 @@ mod tests {
         status_state.mouse_enabled = true;
 
         let theme = Theme::Codex;
-        let prompt_tracker = PromptTracker::new(None, true, PromptLogger::new(None));
+        let prompt_tracker = PromptTracker::new(Vec::new(), true, PromptLogger::new(None));
         let voice_manager = VoiceManager::new(config.app.clone());
         let live_meter = voice_manager.meter();
         let arg_vec: Vec<String> = args.iter().map(|arg| (*arg).to_string()).collect();
@@ -1952,6 +3013,7 @@ mod tests {
             config,
             status_state,
             auto_voice_enabled,
+            auto_voice_dnd: AutoVoiceDnd::default(),
             theme,
             overlay_mode: OverlayMode::None,
             settings_menu: SettingsMenuState::new(),
@@ -1960,8 +3022,19 @@ mod tests {
             theme_picker_digits: String::new(),
             current_status: None,
             pending_transcripts: VecDeque::new(),
+            urgent_hold: None,
+            dictation: None,
+            batch_window: None,
+            redictation: Redictation::default(),
+            prompt_replay: None,
             session_stats: SessionStats::new(),
+            latency_overlay_revision: 0,
             prompt_tracker,
+            output_preview: OutputPreview::default(),
+            scrollback: Scrollback::default(),
+            search_query: String::new(),
+            queue_panel: QueuePanel::default(),
+            backend_modes: BackendModes::default(),
             terminal_rows: 24,
             terminal_cols: 80,
             last_recording_duration: 0.0,
@@ -1971,6 +3044,8 @@ mod tests {
             pending_pty_input_offset: 0,
             pending_pty_input_bytes: 0,
             suppress_startup_escape_input: false,
+            backend_exit: None,
+            multiline: MultilineDelivery::default(),
         };
 
         let now = Instant::now();
@@ -1985,10 +3060,12 @@ mod tests {
             last_processing_tick: now,
             last_heartbeat_tick: now,
             last_meter_update: now,
+            backend_started_at: now,
         };
 
         let deps = EventLoopDeps {
             session,
+            launcher: BackendLauncher::new(cmd, &arg_vec, ".", "xterm-256color"),
             voice_manager,
             writer_tx,
             input_rx,
@@ -2000,7 +3077,20 @@ mod tests {
             meter_update_ms: 50,
             auto_idle_timeout: Duration::from_millis(300),
             transcript_idle_timeout: Duration::from_millis(100),
+            queue_policy: QueuePolicy::default(),
             voice_macros: VoiceMacros::default(),
+            postprocessor: TranscriptPostProcessor::default(),
+            feedback: TranscriptFeedback::default(),
+            uncertain_words: UncertainWordMarker::default(),
+            control: None,
+            web_ui: None,
+            ws_bridge: None,
+            transcript_targets: TranscriptTargets::backend_only("cat"),
+            urgent: UrgentKeywords::default(),
+            secrets: SecretsFilter::default(),
+            translator: TranscriptTranslator::default(),
+            prefix: TranscriptPrefix::default(),
+            voice_navigation: VoiceNavigation::default(),
         };
 
         (state, timers, deps, writer_rx, input_tx)
@@ -2202,6 +3292,56 @@ mod tests {
         assert!(timers.theme_picker_digit_deadline.is_none());
     }
 
+    #[test]
+    fn held_secret_is_reported_as_delivered_only_once_confirmed() {
+        let delivered = crate::session_events::delivered_transcripts();
+        let (mut state, mut timers, mut deps, _writer_rx, _input_tx) =
+            build_harness("cat", &[], 64);
+        deps.secrets = SecretsFilter::from_config(&SecretsConfig::default()).unwrap();
+        let was_delivered = |needle: &str| {
+            delivered
+                .lock()
+                .unwrap()
+                .iter()
+                .any(|text| text.contains(needle))
+        };
+        let mut hold = |state: &mut EventLoopState, deps: &mut EventLoopDeps, key: &str| {
+            deps.voice_manager
+                .finish_test_capture(VoiceJobMessage::Transcript {
+                    text: format!("my key is {key}"),
+                    source: VoiceCaptureSource::Native,
+                    confidence: None,
+                    words: Vec::new(),
+                    metrics: None,
+                });
+            run_periodic_tasks(state, &mut timers, deps, Instant::now());
+            assert!(state.urgent_hold.is_some(), "{key} is held");
+        };
+        let resolve = |state: &mut EventLoopState, deps: &mut EventLoopDeps, confirmed| {
+            let mut io = TranscriptIo {
+                session: &mut deps.session,
+                writer_tx: &deps.writer_tx,
+                status_clear_deadline: &mut None,
+                current_status: &mut state.current_status,
+                status_state: &mut state.status_state,
+                multiline: &state.multiline,
+            };
+            resolve_urgent_hold(&mut state.urgent_hold, confirmed, &mut io);
+        };
+
+        let discarded = "sk-discardedDiscardedDiscarded01";
+        hold(&mut state, &mut deps, discarded);
+        assert!(!was_delivered(discarded));
+        resolve(&mut state, &mut deps, false);
+        assert!(!was_delivered(discarded));
+
+        let confirmed = "sk-confirmedConfirmedConfirmed01";
+        hold(&mut state, &mut deps, confirmed);
+        assert!(!was_delivered(confirmed));
+        resolve(&mut state, &mut deps, true);
+        assert!(was_delivered(confirmed));
+    }
+
     #[test]
     fn take_sigwinch_flag_uses_installed_hook_value() {
         let _hooks = install_sigwinch_hooks(hook_take_sigwinch_false, hook_terminal_size_80x24);
@@ -2591,6 +3731,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn backend_exit_waits_for_relaunch_or_quit() {
+        let (mut state, mut timers, mut deps, _writer_rx, _input_tx) =
+            build_harness("sh", &["-c", "exit 3"], 64);
+        state.auto_voice_enabled = true;
+        let status = deps.session.wait_exit(Duration::from_secs(2));
+        handle_backend_exit(&mut state, &mut timers, &mut deps, status);
+        let exit = state.backend_exit.expect("backend marked as exited");
+        assert_eq!(exit.status.and_then(|status| status.code()), Some(3));
+        assert!(state
+            .current_status
+            .as_deref()
+            .is_some_and(|status| status.starts_with("test exited with code 3 after")));
+
+        let key = |bytes: &[u8]| InputEvent::Bytes(bytes.to_vec());
+        assert!(handle_exited_backend_input(
+            key(b"x"),
+            &mut state,
+            &mut timers,
+            &mut deps
+        ));
+        assert!(state.backend_exit.is_some());
+        assert!(handle_exited_backend_input(
+            key(b"r"),
+            &mut state,
+            &mut timers,
+            &mut deps
+        ));
+        assert!(state.backend_exit.is_none());
+        assert!(state.auto_voice_enabled);
+
+        // The relaunched command exits the same way; `q` then ends the session.
+        let status = deps.session.wait_exit(Duration::from_secs(2));
+        assert_eq!(status.and_then(|status| status.code()), Some(3));
+        handle_backend_exit(&mut state, &mut timers, &mut deps, status);
+        assert!(!handle_exited_backend_input(
+            key(b"q"),
+            &mut state,
+            &mut timers,
+            &mut deps
+        ));
+    }
+
     #[test]
     fn run_event_loop_does_not_run_periodic_before_first_tick() {
         let (mut state, mut timers, mut deps, _writer_rx, input_tx) = build_harness("cat", &[], 8);
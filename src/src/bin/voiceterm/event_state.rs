@@ -7,24 +7,36 @@ use crossbeam_channel::{Receiver, Sender};
 use voiceterm::audio;
 use voiceterm::pty_session::PtyOverlaySession;
 
+use crate::backend_exit::{BackendExit, BackendLauncher};
 use crate::buttons::ButtonRegistry;
 use crate::config::OverlayConfig;
-use crate::input::InputEvent;
+use crate::control::ControlServer;
+use crate::input::{BackendModes, InputEvent};
+use crate::output_preview::OutputPreview;
 use crate::overlays::OverlayMode;
 use crate::prompt::PromptTracker;
+use crate::queue_overlay::QueuePanel;
+use crate::scrollback::Scrollback;
 use crate::session_stats::SessionStats;
 use crate::settings::SettingsMenuState;
 use crate::status_line::StatusLineState;
 use crate::theme::Theme;
-use crate::transcript::PendingTranscript;
-use crate::voice_control::VoiceManager;
+use crate::transcript::{
+    BatchWindow, DictationBuffer, HeldTranscript, MultilineDelivery, PendingTranscript,
+    PromptReplay, QueuePolicy, SecretsFilter, TranscriptFeedback, TranscriptPostProcessor,
+    TranscriptPrefix, TranscriptTargets, TranscriptTranslator, UncertainWordMarker, UrgentKeywords,
+};
+use crate::voice_control::{AutoVoiceDnd, Redictation, VoiceManager, VoiceNavigation};
 use crate::voice_macros::VoiceMacros;
+use crate::web_ui::{WebUiServer, WsBridge};
 use crate::writer::WriterMessage;
 
 pub(crate) struct EventLoopState {
     pub(crate) config: OverlayConfig,
     pub(crate) status_state: StatusLineState,
     pub(crate) auto_voice_enabled: bool,
+    /// `--auto-voice-quiet-hours` and the `mute_auto_voice` hotkey.
+    pub(crate) auto_voice_dnd: AutoVoiceDnd,
     pub(crate) theme: Theme,
     pub(crate) overlay_mode: OverlayMode,
     pub(crate) settings_menu: SettingsMenuState,
@@ -33,8 +45,31 @@ pub(crate) struct EventLoopState {
     pub(crate) theme_picker_digits: String,
     pub(crate) current_status: Option<String>,
     pub(crate) pending_transcripts: VecDeque<PendingTranscript>,
+    /// Urgent or possibly secret transcript waiting for Enter (send) or Esc (discard).
+    pub(crate) urgent_hold: Option<HeldTranscript>,
+    /// Insert-mode draft when `--dictation-buffer` is set.
+    pub(crate) dictation: Option<DictationBuffer>,
+    /// Auto-send batching window when `--batch-window-ms` is set.
+    pub(crate) batch_window: Option<BatchWindow>,
+    /// Re-dictation policy and consecutive retry count.
+    pub(crate) redictation: Redictation,
+    pub(crate) prompt_replay: Option<PromptReplay>,
     pub(crate) session_stats: SessionStats,
+    /// Metrics revision last drawn by the latency overlay.
+    pub(crate) latency_overlay_revision: u64,
     pub(crate) prompt_tracker: PromptTracker,
+    /// Terminal modes the backend turned on; mouse reporting lets SGR reports over its rows reach it.
+    pub(crate) backend_modes: BackendModes,
+    /// Backend output snippet shown in the idle status lane during long answers.
+    pub(crate) output_preview: OutputPreview,
+    /// Backend output kept for the find overlay.
+    pub(crate) scrollback: Scrollback,
+    /// Phrase typed or spoken into the find overlay.
+    pub(crate) search_query: String,
+    /// Selection and edit state of the queue overlay.
+    pub(crate) queue_panel: QueuePanel,
+    /// Multi-line transcript encoding plus the backend's bracketed-paste state.
+    pub(crate) multiline: MultilineDelivery,
     pub(crate) terminal_rows: u16,
     pub(crate) terminal_cols: u16,
     pub(crate) last_recording_duration: f32,
@@ -44,6 +79,8 @@ pub(crate) struct EventLoopState {
     pub(crate) pending_pty_input_offset: usize,
     pub(crate) pending_pty_input_bytes: usize,
     pub(crate) suppress_startup_escape_input: bool,
+    /// Set while the backend is down and the overlay waits for a relaunch or quit.
+    pub(crate) backend_exit: Option<BackendExit>,
 }
 
 pub(crate) struct EventLoopTimers {
@@ -57,10 +94,14 @@ pub(crate) struct EventLoopTimers {
     pub(crate) last_processing_tick: Instant,
     pub(crate) last_heartbeat_tick: Instant,
     pub(crate) last_meter_update: Instant,
+    /// When the running backend was launched, for the exit summary.
+    pub(crate) backend_started_at: Instant,
 }
 
 pub(crate) struct EventLoopDeps {
     pub(crate) session: PtyOverlaySession,
+    /// Backend command line, kept to relaunch `session` after it exits.
+    pub(crate) launcher: BackendLauncher,
     pub(crate) voice_manager: VoiceManager,
     pub(crate) writer_tx: Sender<WriterMessage>,
     pub(crate) input_rx: Receiver<InputEvent>,
@@ -72,5 +113,28 @@ pub(crate) struct EventLoopDeps {
     pub(crate) meter_update_ms: u64,
     pub(crate) auto_idle_timeout: Duration,
     pub(crate) transcript_idle_timeout: Duration,
+    /// `--transcript-queue-size` and `--transcript-queue-policy`.
+    pub(crate) queue_policy: QueuePolicy,
     pub(crate) voice_macros: VoiceMacros,
+    pub(crate) postprocessor: TranscriptPostProcessor,
+    /// Flagged transcripts and what was learned from their corrections.
+    pub(crate) feedback: TranscriptFeedback,
+    pub(crate) uncertain_words: UncertainWordMarker,
+    pub(crate) urgent: UrgentKeywords,
+    /// Credential patterns that hold a transcript for confirmation.
+    pub(crate) secrets: SecretsFilter,
+    /// `--translate-to` command run on each transcript before delivery.
+    pub(crate) translator: TranscriptTranslator,
+    /// `project.prefix` put in front of dictated transcripts.
+    pub(crate) prefix: TranscriptPrefix,
+    /// Control socket for `voiceterm send`, when enabled.
+    pub(crate) control: Option<ControlServer>,
+    /// Localhost status page, when enabled.
+    pub(crate) web_ui: Option<WebUiServer>,
+    /// WebSocket bridge for IDE extensions and dashboards, when enabled.
+    pub(crate) ws_bridge: Option<WsBridge>,
+    /// Wrapped backend plus any `--target` tmux panes; one of them receives transcripts.
+    pub(crate) transcript_targets: TranscriptTargets,
+    /// Spoken overlay commands, when `--voice-navigation` is on.
+    pub(crate) voice_navigation: VoiceNavigation,
 }
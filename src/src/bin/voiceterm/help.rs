@@ -28,7 +28,7 @@ pub const SHORTCUTS: &[Shortcut] = &[
     },
     Shortcut {
         key: "Ctrl+T",
-        description: "Toggle send mode (auto/insert)",
+        description: "Cycle send mode (auto/insert/clip)",
     },
     Shortcut {
         key: "Ctrl+Y",
@@ -38,20 +38,24 @@ pub const SHORTCUTS: &[Shortcut] = &[
         key: "Ctrl+O",
         description: "Settings menu (use arrows)",
     },
+    Shortcut {
+        key: "Ctrl+S",
+        description: "Latency metrics",
+    },
     Shortcut {
         key: "Ctrl+U",
         description: "Cycle HUD style (full/min/hidden)",
     },
     Shortcut {
-        key: "Ctrl+]",
-        description: "Less sensitive (+5 dB)",
+        key: "Ctrl+X",
+        description: "Next tmux pane target (--target)",
     },
     Shortcut {
-        key: "Ctrl+\\",
-        description: "More sensitive (-5 dB)",
+        key: "Ctrl+]",
+        description: "Less sensitive (+5 dB)",
     },
     Shortcut {
-        key: "Ctrl+/",
+        key: "Ctrl+^",
         description: "More sensitive (-5 dB)",
     },
     Shortcut {
@@ -123,7 +127,11 @@ pub fn format_help_overlay(theme: Theme, width: usize) -> String {
     lines.join("\n")
 }
 
-fn format_box_top(colors: &ThemeColors, borders: &crate::theme::BorderSet, width: usize) -> String {
+pub(crate) fn format_box_top(
+    colors: &ThemeColors,
+    borders: &crate::theme::BorderSet,
+    width: usize,
+) -> String {
     // width is the total box width including corners
     // Inner horizontal chars = width - 2 (for the two corners)
     let inner_width = width.saturating_sub(2);
@@ -134,7 +142,7 @@ fn format_box_top(colors: &ThemeColors, borders: &crate::theme::BorderSet, width
     )
 }
 
-fn format_box_bottom(
+pub(crate) fn format_box_bottom(
     colors: &ThemeColors,
     borders: &crate::theme::BorderSet,
     width: usize,
@@ -147,7 +155,7 @@ fn format_box_bottom(
     )
 }
 
-fn format_separator(
+pub(crate) fn format_separator(
     colors: &ThemeColors,
     borders: &crate::theme::BorderSet,
     width: usize,
@@ -160,7 +168,7 @@ fn format_separator(
     )
 }
 
-fn format_title_line(
+pub(crate) fn format_title_line(
     colors: &ThemeColors,
     borders: &crate::theme::BorderSet,
     title: &str,
@@ -1,15 +1,33 @@
 //! Semantic input events so the event loop does not depend on raw key bytes.
 
+use crate::config::VoiceSendMode;
+
 #[derive(Debug, PartialEq, Eq)]
 pub(crate) enum InputEvent {
     Bytes(Vec<u8>),
     VoiceTrigger,
+    /// Manual capture whose transcript uses this send mode instead of the global one.
+    VoiceTriggerAs(VoiceSendMode),
     ToggleAutoVoice,
+    /// Suspend auto-voice for 15 minutes, or end the mute early.
+    MuteAutoVoice,
     ToggleSendMode,
     IncreaseSensitivity,
     DecreaseSensitivity,
+    CycleVadEngine,
+    SwitchModel,
+    FlagTranscript,
+    /// Erase the last transcript inserted without Enter.
+    UndoTranscript,
+    CycleTarget,
     HelpToggle,
+    LatencyHud,
+    /// Open the pending transcript queue panel.
+    QueuePanel,
     ThemePicker,
+    CycleTheme,
+    /// Open scrollback search, pre-filled with the phrase (empty from the hotkey).
+    Find(String),
     SettingsToggle,
     ToggleHudStyle,
     EnterKey,
@@ -19,4 +37,9 @@ pub(crate) enum InputEvent {
         x: u16,
         y: u16,
     },
+    /// SGR mouse report kept byte-for-byte, forwarded when it lands on the backend's rows.
+    MouseReport {
+        bytes: Vec<u8>,
+        y: u16,
+    },
 }
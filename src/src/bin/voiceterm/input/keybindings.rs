@@ -0,0 +1,665 @@
+//! Overlay keybinding table so hotkeys can be remapped from the config file.
+//!
+//! Defaults reproduce the historical hard-coded control bytes, except that
+//! sensitivity-down moved off Ctrl+\ and Ctrl+_ (SIGQUIT / undo), which now pass
+//! through to the backend. User overrides replace an action's keys wholesale;
+//! duplicate or reserved keys are rejected, and keys the backend CLI is known to
+//! use produce startup warnings.
+
+use anyhow::{bail, Result};
+use std::collections::BTreeMap;
+
+use crate::config::{KeySpecList, VoiceSendMode};
+use crate::input::event::InputEvent;
+
+/// Overlay actions that can be bound to a key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum OverlayAction {
+    VoiceTrigger,
+    VoiceTriggerSend,
+    VoiceTriggerInsert,
+    ToggleAutoVoice,
+    MuteAutoVoice,
+    ToggleSendMode,
+    IncreaseSensitivity,
+    DecreaseSensitivity,
+    CycleVadEngine,
+    SwitchModel,
+    FlagTranscript,
+    UndoTranscript,
+    CycleTarget,
+    HelpToggle,
+    LatencyHud,
+    QueuePanel,
+    ThemePicker,
+    CycleTheme,
+    FindScrollback,
+    SettingsToggle,
+    ToggleHudStyle,
+    Exit,
+}
+
+impl OverlayAction {
+    pub(crate) const ALL: [OverlayAction; 22] = [
+        OverlayAction::VoiceTrigger,
+        OverlayAction::VoiceTriggerSend,
+        OverlayAction::VoiceTriggerInsert,
+        OverlayAction::ToggleAutoVoice,
+        OverlayAction::MuteAutoVoice,
+        OverlayAction::ToggleSendMode,
+        OverlayAction::IncreaseSensitivity,
+        OverlayAction::DecreaseSensitivity,
+        OverlayAction::CycleVadEngine,
+        OverlayAction::SwitchModel,
+        OverlayAction::FlagTranscript,
+        OverlayAction::UndoTranscript,
+        OverlayAction::CycleTarget,
+        OverlayAction::HelpToggle,
+        OverlayAction::LatencyHud,
+        OverlayAction::QueuePanel,
+        OverlayAction::ThemePicker,
+        OverlayAction::CycleTheme,
+        OverlayAction::FindScrollback,
+        OverlayAction::SettingsToggle,
+        OverlayAction::ToggleHudStyle,
+        OverlayAction::Exit,
+    ];
+
+    /// Name used for this action in the `keybindings` config section.
+    pub(crate) fn config_key(self) -> &'static str {
+        match self {
+            OverlayAction::VoiceTrigger => "voice_trigger",
+            OverlayAction::VoiceTriggerSend => "voice_trigger_send",
+            OverlayAction::VoiceTriggerInsert => "voice_trigger_insert",
+            OverlayAction::ToggleAutoVoice => "toggle_auto_voice",
+            OverlayAction::MuteAutoVoice => "mute_auto_voice",
+            OverlayAction::ToggleSendMode => "toggle_send_mode",
+            OverlayAction::IncreaseSensitivity => "increase_sensitivity",
+            OverlayAction::DecreaseSensitivity => "decrease_sensitivity",
+            OverlayAction::CycleVadEngine => "cycle_vad_engine",
+            OverlayAction::SwitchModel => "switch_model",
+            OverlayAction::FlagTranscript => "flag_transcript",
+            OverlayAction::UndoTranscript => "undo_transcript",
+            OverlayAction::CycleTarget => "cycle_target",
+            OverlayAction::HelpToggle => "help",
+            OverlayAction::LatencyHud => "latency_hud",
+            OverlayAction::QueuePanel => "queue_panel",
+            OverlayAction::ThemePicker => "theme_picker",
+            OverlayAction::CycleTheme => "cycle_theme",
+            OverlayAction::FindScrollback => "find_scrollback",
+            OverlayAction::SettingsToggle => "settings",
+            OverlayAction::ToggleHudStyle => "toggle_hud_style",
+            OverlayAction::Exit => "exit",
+        }
+    }
+
+    fn from_config_key(key: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|action| action.config_key() == key.trim())
+    }
+
+    fn default_keys(self) -> &'static [u8] {
+        match self {
+            OverlayAction::VoiceTrigger => &[0x12],
+            // Unbound by default; most Ctrl keys are already claimed.
+            OverlayAction::VoiceTriggerSend => &[],
+            OverlayAction::VoiceTriggerInsert => &[],
+            OverlayAction::ToggleAutoVoice => &[0x16],
+            // Unbound by default; most Ctrl keys are already claimed.
+            OverlayAction::MuteAutoVoice => &[],
+            OverlayAction::ToggleSendMode => &[0x14],
+            OverlayAction::IncreaseSensitivity => &[0x1d],
+            OverlayAction::DecreaseSensitivity => &[0x1e],
+            // Unbound by default; most Ctrl keys are already claimed.
+            OverlayAction::CycleVadEngine => &[],
+            OverlayAction::SwitchModel => &[],
+            OverlayAction::FlagTranscript => &[],
+            OverlayAction::UndoTranscript => &[],
+            // Only claimed when `--target` adds something to switch to (see `release`).
+            OverlayAction::CycleTarget => &[0x18],
+            OverlayAction::HelpToggle => b"?",
+            OverlayAction::LatencyHud => &[0x13],
+            OverlayAction::QueuePanel => &[],
+            OverlayAction::ThemePicker => &[0x19],
+            OverlayAction::CycleTheme => &[],
+            OverlayAction::FindScrollback => &[],
+            OverlayAction::SettingsToggle => &[0x0f],
+            OverlayAction::ToggleHudStyle => &[0x15],
+            OverlayAction::Exit => &[0x11],
+        }
+    }
+
+    pub(crate) fn event(self) -> InputEvent {
+        match self {
+            OverlayAction::VoiceTrigger => InputEvent::VoiceTrigger,
+            OverlayAction::VoiceTriggerSend => InputEvent::VoiceTriggerAs(VoiceSendMode::Auto),
+            OverlayAction::VoiceTriggerInsert => InputEvent::VoiceTriggerAs(VoiceSendMode::Insert),
+            OverlayAction::ToggleAutoVoice => InputEvent::ToggleAutoVoice,
+            OverlayAction::MuteAutoVoice => InputEvent::MuteAutoVoice,
+            OverlayAction::ToggleSendMode => InputEvent::ToggleSendMode,
+            OverlayAction::IncreaseSensitivity => InputEvent::IncreaseSensitivity,
+            OverlayAction::DecreaseSensitivity => InputEvent::DecreaseSensitivity,
+            OverlayAction::CycleVadEngine => InputEvent::CycleVadEngine,
+            OverlayAction::SwitchModel => InputEvent::SwitchModel,
+            OverlayAction::FlagTranscript => InputEvent::FlagTranscript,
+            OverlayAction::UndoTranscript => InputEvent::UndoTranscript,
+            OverlayAction::CycleTarget => InputEvent::CycleTarget,
+            OverlayAction::HelpToggle => InputEvent::HelpToggle,
+            OverlayAction::LatencyHud => InputEvent::LatencyHud,
+            OverlayAction::QueuePanel => InputEvent::QueuePanel,
+            OverlayAction::ThemePicker => InputEvent::ThemePicker,
+            OverlayAction::CycleTheme => InputEvent::CycleTheme,
+            OverlayAction::FindScrollback => InputEvent::Find(String::new()),
+            OverlayAction::SettingsToggle => InputEvent::SettingsToggle,
+            OverlayAction::ToggleHudStyle => InputEvent::ToggleHudStyle,
+            OverlayAction::Exit => InputEvent::Exit,
+        }
+    }
+}
+
+/// Bytes the overlay must never intercept because terminals/backends depend on them.
+const RESERVED_KEYS: &[(u8, &str)] = &[
+    (0x03, "interrupt (Ctrl+C)"),
+    (0x08, "backspace"),
+    (0x09, "tab"),
+    (0x0a, "enter"),
+    (0x0d, "enter"),
+    (0x1b, "escape"),
+    (0x7f, "backspace"),
+];
+
+/// Keys commonly used by shells/readline and the supported backend CLIs.
+/// An empty backend label applies to every backend.
+const BACKEND_KEYS: &[(&str, u8, &str)] = &[
+    ("", 0x01, "readline line start"),
+    ("", 0x04, "EOF / exit"),
+    ("", 0x05, "readline line end"),
+    ("", 0x0b, "readline kill line"),
+    ("", 0x0c, "clear screen"),
+    ("", 0x15, "readline kill to line start"),
+    ("", 0x17, "readline delete word"),
+    ("", 0x1a, "suspend (SIGTSTP)"),
+    ("", 0x1c, "SIGQUIT in many terminals"),
+    ("", 0x1f, "undo in readline/zsh"),
+    ("codex", 0x14, "Codex transcript view"),
+];
+
+/// Resolved byte -> action table consumed by the input parser.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct KeyBindings {
+    by_byte: BTreeMap<u8, OverlayAction>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        let mut by_byte = BTreeMap::new();
+        for action in OverlayAction::ALL {
+            for &byte in action.default_keys() {
+                by_byte.insert(byte, action);
+            }
+        }
+        Self { by_byte }
+    }
+}
+
+impl KeyBindings {
+    /// Apply config overrides to the defaults.
+    ///
+    /// Returns the bindings plus human-readable warnings for user-chosen keys
+    /// that collide with keys the backend (or the shell) commonly uses.
+    pub(crate) fn from_config(
+        overrides: &BTreeMap<String, KeySpecList>,
+        backend_label: &str,
+    ) -> Result<(Self, Vec<String>)> {
+        let mut keys_by_action: BTreeMap<OverlayAction, Vec<u8>> = OverlayAction::ALL
+            .into_iter()
+            .map(|action| (action, action.default_keys().to_vec()))
+            .collect();
+        let mut user_chosen: Vec<(OverlayAction, u8)> = Vec::new();
+
+        for (name, specs) in overrides {
+            let Some(action) = OverlayAction::from_config_key(name) else {
+                let valid: Vec<&str> = OverlayAction::ALL
+                    .iter()
+                    .map(|action| action.config_key())
+                    .collect();
+                bail!(
+                    "unknown keybinding action '{name}' (expected one of: {})",
+                    valid.join(", ")
+                );
+            };
+            let mut bytes = Vec::new();
+            for spec in specs.specs() {
+                let Some(byte) = parse_key_spec(spec)? else {
+                    continue;
+                };
+                if let Some((_, reason)) = RESERVED_KEYS.iter().find(|(key, _)| *key == byte) {
+                    bail!(
+                        "keybinding {name} = {spec} uses a reserved key ({reason}); choose another key"
+                    );
+                }
+                if !bytes.contains(&byte) {
+                    bytes.push(byte);
+                    user_chosen.push((action, byte));
+                }
+            }
+            keys_by_action.insert(action, bytes);
+        }
+
+        let mut by_byte: BTreeMap<u8, OverlayAction> = BTreeMap::new();
+        for (action, bytes) in &keys_by_action {
+            for &byte in bytes {
+                if let Some(existing) = by_byte.insert(byte, *action) {
+                    bail!(
+                        "keybinding conflict: {} is bound to both {} and {}",
+                        key_label(byte),
+                        existing.config_key(),
+                        action.config_key()
+                    );
+                }
+            }
+        }
+
+        let warnings = user_chosen
+            .into_iter()
+            .filter_map(|(action, byte)| {
+                backend_conflict(byte, backend_label).map(|usage| {
+                    format!(
+                        "keybinding {} = {} may shadow {usage}",
+                        action.config_key(),
+                        key_label(byte)
+                    )
+                })
+            })
+            .collect();
+
+        Ok((Self { by_byte }, warnings))
+    }
+
+    pub(crate) fn action_for_byte(&self, byte: u8) -> Option<OverlayAction> {
+        self.by_byte.get(&byte).copied()
+    }
+
+    /// Unbind an action that has nothing to do this session, so its keys reach the backend.
+    pub(crate) fn release(&mut self, action: OverlayAction) {
+        self.by_byte.retain(|_, bound| *bound != action);
+    }
+
+    pub(crate) fn is_bound(&self, action: OverlayAction) -> bool {
+        self.by_byte.values().any(|bound| *bound == action)
+    }
+
+    /// Keys bound to an action, in ascending byte order.
+    #[cfg(test)]
+    pub(crate) fn keys_for(&self, action: OverlayAction) -> Vec<u8> {
+        self.by_byte
+            .iter()
+            .filter(|(_, bound)| **bound == action)
+            .map(|(byte, _)| *byte)
+            .collect()
+    }
+}
+
+fn backend_conflict(byte: u8, backend_label: &str) -> Option<&'static str> {
+    let backend = backend_label.to_ascii_lowercase();
+    BACKEND_KEYS
+        .iter()
+        .find(|(label, key, _)| *key == byte && (label.is_empty() || backend.contains(label)))
+        .map(|(_, _, usage)| *usage)
+}
+
+/// Parse a key spec such as `ctrl+r`, `C-]`, `^\`, `ctrl+backslash`, or `?`.
+///
+/// Returns `Ok(None)` for `none`/`off`, which leaves the action unbound.
+pub(crate) fn parse_key_spec(spec: &str) -> Result<Option<u8>> {
+    let trimmed = spec.trim();
+    let lower = trimmed.to_ascii_lowercase();
+    if lower == "none" || lower == "off" || lower == "disabled" {
+        return Ok(None);
+    }
+    let ctrl_key = lower
+        .strip_prefix("ctrl+")
+        .or_else(|| lower.strip_prefix("ctrl-"))
+        .or_else(|| lower.strip_prefix("c-"))
+        .or_else(|| lower.strip_prefix('^').filter(|rest| !rest.is_empty()));
+    if let Some(key) = ctrl_key {
+        let ch = match key {
+            "backslash" => '\\',
+            "bracketleft" => '[',
+            "bracketright" => ']',
+            "caret" => '^',
+            "underscore" => '_',
+            "slash" => '/',
+            "space" => ' ',
+            other => {
+                let mut chars = other.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(ch), None) => ch,
+                    _ => bail!("unsupported key spec '{spec}'"),
+                }
+            }
+        };
+        return control_byte(ch)
+            .map(Some)
+            .ok_or_else(|| anyhow::anyhow!("unsupported key spec '{spec}'"));
+    }
+    let mut chars = trimmed.chars();
+    match (chars.next(), chars.next()) {
+        (Some(ch), None) if ch.is_ascii_punctuation() => Ok(Some(ch as u8)),
+        (Some(ch), None) if ch.is_ascii_alphanumeric() || ch == ' ' => {
+            bail!("key spec '{spec}' would intercept normal typing; use a ctrl+ combination")
+        }
+        _ => bail!("unsupported key spec '{spec}'"),
+    }
+}
+
+/// Control byte produced by Ctrl+<ch> in a classic terminal.
+pub(crate) fn control_byte(ch: char) -> Option<u8> {
+    match ch.to_ascii_lowercase() {
+        'a'..='z' => Some(ch.to_ascii_lowercase() as u8 - b'a' + 1),
+        '@' | ' ' | '2' => Some(0x00),
+        '[' | '3' => Some(0x1b),
+        '\\' | '4' => Some(0x1c),
+        ']' | '5' => Some(0x1d),
+        '^' | '6' => Some(0x1e),
+        '_' | '/' | '7' | '-' => Some(0x1f),
+        _ => None,
+    }
+}
+
+/// Display label for a bound key, e.g. `Ctrl+R` or `?`.
+pub(crate) fn key_label(byte: u8) -> String {
+    match byte {
+        0x01..=0x1a => format!("Ctrl+{}", (b'A' + byte - 1) as char),
+        0x00 => "Ctrl+@".to_string(),
+        0x1b => "Ctrl+[".to_string(),
+        0x1c => "Ctrl+\\".to_string(),
+        0x1d => "Ctrl+]".to_string(),
+        0x1e => "Ctrl+^".to_string(),
+        0x1f => "Ctrl+_".to_string(),
+        other => (other as char).to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn overrides(pairs: &[(&str, &str)]) -> BTreeMap<String, KeySpecList> {
+        pairs
+            .iter()
+            .map(|(action, spec)| (action.to_string(), KeySpecList::One(spec.to_string())))
+            .collect()
+    }
+
+    #[test]
+    fn defaults_match_historical_control_bytes() {
+        let bindings = KeyBindings::default();
+        assert_eq!(
+            bindings.action_for_byte(0x12),
+            Some(OverlayAction::VoiceTrigger)
+        );
+        assert_eq!(bindings.action_for_byte(0x11), Some(OverlayAction::Exit));
+        assert_eq!(
+            bindings.action_for_byte(b'?'),
+            Some(OverlayAction::HelpToggle)
+        );
+        assert_eq!(
+            bindings.keys_for(OverlayAction::DecreaseSensitivity),
+            vec![0x1e]
+        );
+        assert_eq!(
+            bindings.action_for_byte(0x13),
+            Some(OverlayAction::LatencyHud)
+        );
+        assert_eq!(bindings.action_for_byte(b'a'), None);
+    }
+
+    #[test]
+    fn default_sensitivity_keys_leave_terminal_control_bytes_alone() {
+        let bindings = KeyBindings::default();
+        assert_eq!(bindings.action_for_byte(0x1c), None, "Ctrl+\\ is SIGQUIT");
+        assert_eq!(bindings.action_for_byte(0x1f), None, "Ctrl+_ is undo");
+        for action in [
+            OverlayAction::IncreaseSensitivity,
+            OverlayAction::DecreaseSensitivity,
+        ] {
+            for byte in bindings.keys_for(action) {
+                assert_eq!(backend_conflict(byte, "codex"), None);
+                assert_eq!(backend_conflict(byte, "claude"), None);
+            }
+        }
+    }
+
+    #[test]
+    fn from_config_warns_when_sensitivity_is_rebound_to_sigquit() {
+        let (bindings, warnings) = KeyBindings::from_config(
+            &overrides(&[("decrease_sensitivity", "ctrl+backslash")]),
+            "claude",
+        )
+        .expect("bindings should resolve");
+        assert_eq!(
+            bindings.action_for_byte(0x1c),
+            Some(OverlayAction::DecreaseSensitivity)
+        );
+        assert_eq!(bindings.action_for_byte(0x1e), None);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("SIGQUIT"), "{}", warnings[0]);
+    }
+
+    #[test]
+    fn parse_key_spec_accepts_common_forms() {
+        assert_eq!(parse_key_spec("ctrl+r").unwrap(), Some(0x12));
+        assert_eq!(parse_key_spec("Ctrl+R").unwrap(), Some(0x12));
+        assert_eq!(parse_key_spec("C-]").unwrap(), Some(0x1d));
+        assert_eq!(parse_key_spec("^\\").unwrap(), Some(0x1c));
+        assert_eq!(parse_key_spec("ctrl+backslash").unwrap(), Some(0x1c));
+        assert_eq!(parse_key_spec("ctrl+/").unwrap(), Some(0x1f));
+        assert_eq!(parse_key_spec("?").unwrap(), Some(b'?'));
+        assert_eq!(parse_key_spec("none").unwrap(), None);
+        assert!(parse_key_spec("a").is_err());
+        assert!(parse_key_spec("ctrl+f1").is_err());
+    }
+
+    #[test]
+    fn from_config_remaps_and_unbinds_actions() {
+        let (bindings, warnings) = KeyBindings::from_config(
+            &overrides(&[("voice_trigger", "ctrl+g"), ("help", "none")]),
+            "codex",
+        )
+        .expect("bindings should resolve");
+        assert_eq!(
+            bindings.action_for_byte(0x07),
+            Some(OverlayAction::VoiceTrigger)
+        );
+        assert_eq!(bindings.action_for_byte(0x12), None);
+        assert_eq!(bindings.action_for_byte(b'?'), None);
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn released_cycle_target_key_passes_through() {
+        let mut bindings = KeyBindings::default();
+        assert_eq!(
+            bindings.action_for_byte(0x18),
+            Some(OverlayAction::CycleTarget)
+        );
+        bindings.release(OverlayAction::CycleTarget);
+        assert_eq!(bindings.action_for_byte(0x18), None);
+        assert_eq!(
+            bindings.action_for_byte(0x12),
+            Some(OverlayAction::VoiceTrigger)
+        );
+    }
+
+    #[test]
+    fn cycle_vad_engine_is_unbound_until_configured() {
+        assert!(KeyBindings::default()
+            .keys_for(OverlayAction::CycleVadEngine)
+            .is_empty());
+        let (bindings, _) =
+            KeyBindings::from_config(&overrides(&[("cycle_vad_engine", "ctrl+g")]), "codex")
+                .expect("bindings should resolve");
+        assert_eq!(
+            bindings.action_for_byte(0x07),
+            Some(OverlayAction::CycleVadEngine)
+        );
+        assert_eq!(
+            OverlayAction::CycleVadEngine.event(),
+            InputEvent::CycleVadEngine
+        );
+    }
+
+    #[test]
+    fn switch_model_is_unbound_until_configured() {
+        assert!(KeyBindings::default()
+            .keys_for(OverlayAction::SwitchModel)
+            .is_empty());
+        let (bindings, _) =
+            KeyBindings::from_config(&overrides(&[("switch_model", "ctrl+b")]), "codex")
+                .expect("bindings should resolve");
+        assert_eq!(
+            bindings.action_for_byte(0x02),
+            Some(OverlayAction::SwitchModel)
+        );
+    }
+
+    #[test]
+    fn cycle_theme_is_unbound_until_configured() {
+        assert!(KeyBindings::default()
+            .keys_for(OverlayAction::CycleTheme)
+            .is_empty());
+        let (bindings, _) =
+            KeyBindings::from_config(&overrides(&[("cycle_theme", "ctrl+p")]), "codex")
+                .expect("bindings should resolve");
+        assert_eq!(
+            bindings.action_for_byte(0x10),
+            Some(OverlayAction::CycleTheme)
+        );
+        assert_eq!(OverlayAction::CycleTheme.event(), InputEvent::CycleTheme);
+    }
+
+    #[test]
+    fn pinned_voice_triggers_are_unbound_until_configured() {
+        let defaults = KeyBindings::default();
+        assert!(defaults
+            .keys_for(OverlayAction::VoiceTriggerSend)
+            .is_empty());
+        assert!(defaults
+            .keys_for(OverlayAction::VoiceTriggerInsert)
+            .is_empty());
+        let (bindings, _) = KeyBindings::from_config(
+            &overrides(&[
+                ("voice_trigger_send", "ctrl+g"),
+                ("voice_trigger_insert", "ctrl+b"),
+            ]),
+            "codex",
+        )
+        .expect("bindings should resolve");
+        assert_eq!(
+            bindings.action_for_byte(0x07),
+            Some(OverlayAction::VoiceTriggerSend)
+        );
+        assert_eq!(
+            bindings.action_for_byte(0x02),
+            Some(OverlayAction::VoiceTriggerInsert)
+        );
+        assert_eq!(
+            bindings.action_for_byte(0x12),
+            Some(OverlayAction::VoiceTrigger)
+        );
+        assert_eq!(
+            OverlayAction::VoiceTriggerInsert.event(),
+            InputEvent::VoiceTriggerAs(VoiceSendMode::Insert)
+        );
+    }
+
+    #[test]
+    fn find_scrollback_is_unbound_until_configured() {
+        assert!(KeyBindings::default()
+            .keys_for(OverlayAction::FindScrollback)
+            .is_empty());
+        let (bindings, _) =
+            KeyBindings::from_config(&overrides(&[("find_scrollback", "ctrl+g")]), "codex")
+                .expect("bindings should resolve");
+        assert_eq!(
+            bindings.action_for_byte(0x07),
+            Some(OverlayAction::FindScrollback)
+        );
+        assert_eq!(
+            OverlayAction::FindScrollback.event(),
+            InputEvent::Find(String::new())
+        );
+    }
+
+    #[test]
+    fn mute_auto_voice_is_unbound_until_configured() {
+        assert!(KeyBindings::default()
+            .keys_for(OverlayAction::MuteAutoVoice)
+            .is_empty());
+        let (bindings, _) =
+            KeyBindings::from_config(&overrides(&[("mute_auto_voice", "ctrl+g")]), "codex")
+                .expect("bindings should resolve");
+        assert_eq!(
+            bindings.action_for_byte(0x07),
+            Some(OverlayAction::MuteAutoVoice)
+        );
+        assert_eq!(
+            OverlayAction::MuteAutoVoice.event(),
+            InputEvent::MuteAutoVoice
+        );
+    }
+
+    #[test]
+    fn flag_transcript_is_unbound_until_configured() {
+        assert!(KeyBindings::default()
+            .keys_for(OverlayAction::FlagTranscript)
+            .is_empty());
+        let (bindings, _) =
+            KeyBindings::from_config(&overrides(&[("flag_transcript", "ctrl+n")]), "codex")
+                .expect("bindings should resolve");
+        assert_eq!(
+            bindings.action_for_byte(0x0e),
+            Some(OverlayAction::FlagTranscript)
+        );
+    }
+
+    #[test]
+    fn from_config_rejects_duplicate_keys() {
+        let err = KeyBindings::from_config(&overrides(&[("voice_trigger", "ctrl+v")]), "codex")
+            .expect_err("ctrl+v is already toggle_auto_voice");
+        let message = err.to_string();
+        assert!(message.contains("Ctrl+V"), "{message}");
+        assert!(message.contains("toggle_auto_voice"), "{message}");
+    }
+
+    #[test]
+    fn from_config_rejects_reserved_and_unknown_keys() {
+        assert!(KeyBindings::from_config(&overrides(&[("exit", "ctrl+c")]), "codex").is_err());
+        assert!(KeyBindings::from_config(&overrides(&[("launch", "ctrl+g")]), "codex").is_err());
+    }
+
+    #[test]
+    fn from_config_warns_about_backend_keys() {
+        let (_, warnings) = KeyBindings::from_config(
+            &overrides(&[("voice_trigger", "ctrl+l"), ("toggle_send_mode", "none")]),
+            "claude",
+        )
+        .expect("bindings should resolve");
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("clear screen"));
+
+        let (_, warnings) =
+            KeyBindings::from_config(&overrides(&[("toggle_send_mode", "ctrl+t")]), "codex")
+                .expect("bindings should resolve");
+        assert!(warnings[0].contains("Codex transcript view"));
+    }
+
+    #[test]
+    fn key_label_formats_control_and_printable_keys() {
+        assert_eq!(key_label(0x12), "Ctrl+R");
+        assert_eq!(key_label(0x1c), "Ctrl+\\");
+        assert_eq!(key_label(b'?'), "?");
+    }
+}
@@ -1,9 +1,13 @@
 //! Input subsystem wiring that turns raw terminal bytes into overlay events.
 
 mod event;
+mod keybindings;
+mod modes;
 mod mouse;
 mod parser;
 mod spawn;
 
 pub(crate) use event::InputEvent;
+pub(crate) use keybindings::{KeyBindings, OverlayAction};
+pub(crate) use modes::{BackendModes, KittyKeyboard};
 pub(crate) use spawn::spawn_input_thread;
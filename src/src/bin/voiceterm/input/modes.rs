@@ -0,0 +1,304 @@
+//! Terminal modes the backend turns on in its output, so input is read the way it expects.
+//!
+//! The backend's output reaches the real terminal unchanged, so modes it sets
+//! there change what the user's keys produce: application cursor and keypad
+//! modes (`ESC [ ? 1 h`, `ESC =`) switch keys to `ESC O` sequences, mouse
+//! tracking makes the terminal send reports, bracketed paste wraps pastes in
+//! markers, and the kitty keyboard protocol (`ESC [ > flags u`) reports keys
+//! as `ESC [ … u`. [`BackendModes`] follows those requests, including across
+//! output chunks, and shares the keyboard protocol with the input thread via
+//! [`KittyKeyboard`]. When the session ends, [`BackendModes::reset_sequence`]
+//! turns off whatever the backend left on, so a crashed backend does not leave
+//! the shell reading mouse reports or escape-coded keys.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use voiceterm::log_debug;
+use vte::{Params, Parser as VteParser, Perform};
+
+/// DEC private modes that make the terminal send mouse reports.
+const MOUSE_TRACKING_MODES: [u16; 3] = [1000, 1002, 1003];
+/// DEC private modes that switch to the alternate screen.
+const ALTERNATE_SCREEN_MODES: [u16; 3] = [47, 1047, 1049];
+const APPLICATION_CURSOR_MODE: u16 = 1;
+const BRACKETED_PASTE_MODE: u16 = 2004;
+/// Deepest kitty keyboard stack kept; terminals cap theirs too.
+const MAX_KITTY_STACK: usize = 16;
+
+/// Whether the backend enabled the kitty keyboard protocol, shared with the input thread.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct KittyKeyboard(Arc<AtomicBool>);
+
+impl KittyKeyboard {
+    pub(crate) fn active(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    fn set(&self, active: bool) {
+        self.0.store(active, Ordering::Relaxed);
+    }
+}
+
+#[derive(Debug, Default)]
+struct ModeState {
+    application_cursor: bool,
+    application_keypad: bool,
+    alternate_screen: bool,
+    bracketed_paste: bool,
+    mouse_tracking: bool,
+    /// Kitty keyboard flag stack; the last entry is in effect.
+    kitty_flags: Vec<u16>,
+    kitty: KittyKeyboard,
+}
+
+impl ModeState {
+    fn set_private_mode(&mut self, mode: u16, on: bool) {
+        let (name, flag) = match mode {
+            APPLICATION_CURSOR_MODE => ("application_cursor", &mut self.application_cursor),
+            BRACKETED_PASTE_MODE => ("bracketed_paste", &mut self.bracketed_paste),
+            mode if ALTERNATE_SCREEN_MODES.contains(&mode) => {
+                ("alternate_screen", &mut self.alternate_screen)
+            }
+            mode if MOUSE_TRACKING_MODES.contains(&mode) => {
+                ("mouse_tracking", &mut self.mouse_tracking)
+            }
+            _ => return,
+        };
+        if *flag != on {
+            *flag = on;
+            log_debug(&format!("backend mode {name} {}", on_off(on)));
+        }
+    }
+
+    /// `ESC [ > flags u` pushes, `ESC [ < n u` pops, `ESC [ = flags ; how u` edits the top.
+    fn update_kitty_flags(&mut self, marker: u8, first: Option<u16>, second: Option<u16>) {
+        match marker {
+            b'>' => {
+                if self.kitty_flags.len() == MAX_KITTY_STACK {
+                    self.kitty_flags.remove(0);
+                }
+                self.kitty_flags.push(first.unwrap_or(0));
+            }
+            b'<' => {
+                let count = usize::from(first.unwrap_or(1).max(1)).min(self.kitty_flags.len());
+                self.kitty_flags.truncate(self.kitty_flags.len() - count);
+            }
+            b'=' => {
+                let flags = first.unwrap_or(0);
+                if self.kitty_flags.is_empty() {
+                    self.kitty_flags.push(0);
+                }
+                if let Some(top) = self.kitty_flags.last_mut() {
+                    *top = match second.unwrap_or(1) {
+                        2 => *top | flags,
+                        3 => *top & !flags,
+                        _ => flags,
+                    };
+                }
+            }
+            _ => return,
+        }
+        let active = self.kitty_flags.last().is_some_and(|flags| *flags != 0);
+        if active != self.kitty.active() {
+            self.kitty.set(active);
+            log_debug(&format!("backend mode kitty_keyboard {}", on_off(active)));
+        }
+    }
+
+    /// `ESC c` resets the terminal, and every mode with it.
+    fn reset(&mut self) {
+        let kitty = self.kitty.clone();
+        kitty.set(false);
+        *self = Self {
+            kitty,
+            ..Self::default()
+        };
+        log_debug("backend reset the terminal modes");
+    }
+}
+
+impl Perform for ModeState {
+    fn csi_dispatch(&mut self, params: &Params, intermediates: &[u8], _ignore: bool, action: char) {
+        let mut values = params.iter().map(|param| param.first().copied());
+        match (intermediates, action) {
+            (b"?", 'h' | 'l') => {
+                for mode in values.flatten() {
+                    self.set_private_mode(mode, action == 'h');
+                }
+            }
+            ([marker @ (b'>' | b'<' | b'=')], 'u') => {
+                let first = values.next().flatten();
+                let second = values.next().flatten();
+                self.update_kitty_flags(*marker, first, second);
+            }
+            _ => {}
+        }
+    }
+
+    fn esc_dispatch(&mut self, intermediates: &[u8], _ignore: bool, byte: u8) {
+        if !intermediates.is_empty() {
+            return;
+        }
+        let keypad = match byte {
+            b'=' => true,
+            b'>' => false,
+            b'c' => {
+                self.reset();
+                return;
+            }
+            _ => return,
+        };
+        if self.application_keypad != keypad {
+            self.application_keypad = keypad;
+            log_debug(&format!(
+                "backend mode application_keypad {}",
+                on_off(keypad)
+            ));
+        }
+    }
+}
+
+fn on_off(on: bool) -> &'static str {
+    if on {
+        "on"
+    } else {
+        "off"
+    }
+}
+
+/// Terminal modes requested by the backend, from the sequences in its output.
+pub(crate) struct BackendModes {
+    parser: VteParser,
+    state: ModeState,
+}
+
+impl Default for BackendModes {
+    fn default() -> Self {
+        Self::new(KittyKeyboard::default())
+    }
+}
+
+impl BackendModes {
+    /// Track modes, publishing the kitty keyboard protocol state to `kitty`.
+    pub(crate) fn new(kitty: KittyKeyboard) -> Self {
+        Self {
+            parser: VteParser::new(),
+            state: ModeState {
+                kitty,
+                ..ModeState::default()
+            },
+        }
+    }
+
+    pub(crate) fn observe(&mut self, bytes: &[u8]) {
+        self.parser.advance(&mut self.state, bytes);
+    }
+
+    /// Whether the backend asked for mouse reports.
+    pub(crate) fn mouse_tracking(&self) -> bool {
+        self.state.mouse_tracking
+    }
+
+    /// Sequences that turn off every mode the backend still has on.
+    pub(crate) fn reset_sequence(&self) -> Vec<u8> {
+        let state = &self.state;
+        let mut reset = Vec::new();
+        if !state.kitty_flags.is_empty() {
+            reset.extend_from_slice(format!("\x1b[<{}u", state.kitty_flags.len()).as_bytes());
+        }
+        if state.mouse_tracking {
+            reset.extend_from_slice(b"\x1b[?1003l\x1b[?1002l\x1b[?1000l\x1b[?1006l");
+        }
+        if state.bracketed_paste {
+            reset.extend_from_slice(b"\x1b[?2004l");
+        }
+        if state.application_cursor {
+            reset.extend_from_slice(b"\x1b[?1l");
+        }
+        if state.application_keypad {
+            reset.extend_from_slice(b"\x1b>");
+        }
+        if state.alternate_screen {
+            reset.extend_from_slice(b"\x1b[?1049l");
+        }
+        reset
+    }
+
+    /// Forget the exited backend's modes, returning the sequences that turn them off.
+    pub(crate) fn reset(&mut self) -> Vec<u8> {
+        let reset = self.reset_sequence();
+        self.parser = VteParser::new();
+        self.state.reset();
+        reset
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mouse_tracking_follows_modes_across_chunks() {
+        let mut modes = BackendModes::default();
+        modes.observe(b"\x1b[?25l\x1b[?1049h");
+        assert!(!modes.mouse_tracking());
+        modes.observe(b"menu\x1b[?10");
+        assert!(!modes.mouse_tracking());
+        modes.observe(b"02;1006h");
+        assert!(modes.mouse_tracking());
+        modes.observe(b"\x1b[?1006l");
+        assert!(modes.mouse_tracking());
+        modes.observe(b"bye\x1b[?1002l");
+        assert!(!modes.mouse_tracking());
+    }
+
+    #[test]
+    fn kitty_keyboard_follows_the_flag_stack() {
+        let kitty = KittyKeyboard::default();
+        let mut modes = BackendModes::new(kitty.clone());
+        modes.observe(b"\x1b[>1u");
+        assert!(kitty.active());
+        modes.observe(b"\x1b[>0u");
+        assert!(!kitty.active());
+        modes.observe(b"\x1b[<u");
+        assert!(kitty.active());
+        modes.observe(b"\x1b[=1;3u");
+        assert!(!kitty.active());
+        modes.observe(b"\x1b[=9;2u\x1b[<");
+        assert!(kitty.active());
+        modes.observe(b"5u");
+        assert!(!kitty.active());
+        // `ESC [ < u` with nothing pushed and a full stack both stay bounded.
+        modes.observe(b"\x1b[<u");
+        for _ in 0..40 {
+            modes.observe(b"\x1b[>1u");
+        }
+        assert_eq!(modes.state.kitty_flags.len(), MAX_KITTY_STACK);
+    }
+
+    #[test]
+    fn reset_sequence_undoes_only_modes_left_on() {
+        let mut modes = BackendModes::default();
+        assert!(modes.reset_sequence().is_empty());
+        modes.observe(b"\x1b[?1h\x1b=\x1b[?2004h\x1b[?1049h\x1b[>1u\x1b[?1000;1006h");
+        modes.observe(b"\x1b[?1049l");
+        assert_eq!(
+            modes.reset_sequence(),
+            b"\x1b[<1u\x1b[?1003l\x1b[?1002l\x1b[?1000l\x1b[?1006l\x1b[?2004l\x1b[?1l\x1b>"
+        );
+        modes.observe(b"\x1bc");
+        assert!(modes.reset_sequence().is_empty());
+    }
+
+    #[test]
+    fn reset_forgets_modes_and_releases_the_keyboard_protocol() {
+        let kitty = KittyKeyboard::default();
+        let mut modes = BackendModes::new(kitty.clone());
+        modes.observe(b"\x1b[>1u\x1b[?2004h");
+        assert!(kitty.active());
+        assert_eq!(modes.reset(), b"\x1b[<1u\x1b[?2004l");
+        assert!(!kitty.active());
+        assert!(modes.reset_sequence().is_empty());
+    }
+}
@@ -133,6 +133,18 @@ pub(crate) fn is_mouse_sequence(buffer: &[u8]) -> bool {
     is_sgr_mouse_sequence(buffer) || is_urxvt_mouse_sequence(buffer) || is_x10_mouse_prefix(buffer)
 }
 
+/// Row of any SGR mouse report (any button, wheel, motion, press or release).
+pub(crate) fn sgr_mouse_row(buffer: &[u8]) -> Option<u16> {
+    if !is_sgr_mouse_sequence(buffer) {
+        return None;
+    }
+    let params = std::str::from_utf8(&buffer[3..buffer.len() - 1]).ok()?;
+    let mut parts = params.split(';');
+    parts.next()?.parse::<u16>().ok()?;
+    parts.next()?.parse::<u16>().ok()?;
+    parts.next()?.parse().ok()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -161,6 +173,13 @@ mod tests {
         assert_eq!(release, Some((MouseEventKind::Release, 10, 5)));
     }
 
+    #[test]
+    fn sgr_mouse_row_reads_any_report() {
+        assert_eq!(sgr_mouse_row(b"\x1b[<64;10;5M"), Some(5));
+        assert_eq!(sgr_mouse_row(b"\x1b[<35;1;22m"), Some(22));
+        assert_eq!(sgr_mouse_row(b"\x1b[32;10;5M"), None);
+    }
+
     #[test]
     fn parse_mouse_event_accepts_all_supported_protocols() {
         assert!(parse_mouse_event(b"\x1b[<0;10;5M").is_some());
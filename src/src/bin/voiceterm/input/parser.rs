@@ -1,29 +1,63 @@
 //! Byte-stream parser so terminal escape sequences become stable overlay events.
+//!
+//! Hotkeys are single ASCII bytes, so bytes of multi-byte UTF-8 characters
+//! (dead-key accents, IME commits) always pass through as text. Keyboards in
+//! kitty/CSI-u mode may report composed text as `ESC [ <codepoint> u`; plain
+//! or shifted printable keys are decoded back into UTF-8 text, unless the
+//! backend itself turned the kitty keyboard protocol on, in which case keys the
+//! overlay does not bind reach it in that form. Bracketed pastes pass through
+//! untouched, so pasted newlines and control bytes are never hotkeys.
 
 use crate::input::event::InputEvent;
+use crate::input::keybindings::{control_byte, KeyBindings, OverlayAction};
+use crate::input::modes::KittyKeyboard;
 use crate::input::mouse::{
-    is_mouse_sequence, is_x10_mouse_prefix, parse_mouse_event, MouseEventKind,
+    is_mouse_sequence, is_x10_mouse_prefix, parse_mouse_event, sgr_mouse_row, MouseEventKind,
 };
 
+const PASTE_START: &[u8] = b"\x1b[200~";
+const PASTE_END: &[u8] = b"\x1b[201~";
+
 pub(crate) struct InputParser {
     pending: Vec<u8>,
     skip_lf: bool,
     esc_buffer: Option<Vec<u8>>,
     mouse_press_seen: bool,
+    /// Bytes of the paste end marker seen so far, while inside a bracketed paste.
+    paste_end_matched: Option<usize>,
+    bindings: KeyBindings,
+    kitty_keyboard: KittyKeyboard,
 }
 
 impl InputParser {
+    #[cfg(test)]
     pub(crate) fn new() -> Self {
+        Self::with_bindings(KeyBindings::default())
+    }
+
+    pub(crate) fn with_bindings(bindings: KeyBindings) -> Self {
         Self {
             pending: Vec::new(),
             skip_lf: false,
             esc_buffer: None,
             mouse_press_seen: false,
+            paste_end_matched: None,
+            bindings,
+            kitty_keyboard: KittyKeyboard::default(),
         }
     }
 
+    /// Follow the backend's kitty keyboard protocol state when decoding CSI-u keys.
+    pub(crate) fn with_kitty_keyboard(mut self, kitty_keyboard: KittyKeyboard) -> Self {
+        self.kitty_keyboard = kitty_keyboard;
+        self
+    }
+
     pub(crate) fn consume_bytes(&mut self, bytes: &[u8], out: &mut Vec<InputEvent>) {
         for &byte in bytes {
+            if self.consume_paste(byte, out) {
+                continue;
+            }
             if self.consume_escape(byte, out) {
                 continue;
             }
@@ -35,51 +69,18 @@ impl InputParser {
                 self.skip_lf = false;
             }
 
+            if !byte.is_ascii() {
+                self.pending.push(byte);
+                continue;
+            }
+
+            if let Some(action) = self.bindings.action_for_byte(byte) {
+                self.flush_pending(out);
+                out.push(action.event());
+                continue;
+            }
+
             match byte {
-                0x11 => {
-                    self.flush_pending(out);
-                    out.push(InputEvent::Exit);
-                }
-                0x12 => {
-                    self.flush_pending(out);
-                    out.push(InputEvent::VoiceTrigger);
-                }
-                0x16 => {
-                    self.flush_pending(out);
-                    out.push(InputEvent::ToggleAutoVoice);
-                }
-                0x14 => {
-                    self.flush_pending(out);
-                    out.push(InputEvent::ToggleSendMode);
-                }
-                0x1d => {
-                    self.flush_pending(out);
-                    out.push(InputEvent::IncreaseSensitivity);
-                }
-                0x1c => {
-                    self.flush_pending(out);
-                    out.push(InputEvent::DecreaseSensitivity);
-                }
-                0x1f => {
-                    self.flush_pending(out);
-                    out.push(InputEvent::DecreaseSensitivity);
-                }
-                0x19 => {
-                    self.flush_pending(out);
-                    out.push(InputEvent::ThemePicker);
-                }
-                0x0f => {
-                    self.flush_pending(out);
-                    out.push(InputEvent::SettingsToggle);
-                }
-                0x15 => {
-                    self.flush_pending(out);
-                    out.push(InputEvent::ToggleHudStyle);
-                }
-                b'?' => {
-                    self.flush_pending(out);
-                    out.push(InputEvent::HelpToggle);
-                }
                 0x0d | 0x0a => {
                     self.flush_pending(out);
                     out.push(InputEvent::EnterKey);
@@ -98,6 +99,18 @@ impl InputParser {
         }
     }
 
+    /// Flush at the end of a read, keeping a character split across reads for the next one.
+    pub(crate) fn flush_complete_chars(&mut self, out: &mut Vec<InputEvent>) {
+        let split = incomplete_utf8_tail(&self.pending);
+        if split == 0 {
+            self.flush_pending(out);
+            return;
+        }
+        let tail = self.pending.split_off(self.pending.len() - split);
+        self.flush_pending(out);
+        self.pending = tail;
+    }
+
     fn emit_mouse_click(
         &mut self,
         out: &mut Vec<InputEvent>,
@@ -121,16 +134,55 @@ impl InputParser {
         }
     }
 
+    /// Keep bracketed paste contents as text until the end marker, marker included.
+    fn consume_paste(&mut self, byte: u8, out: &mut Vec<InputEvent>) -> bool {
+        let Some(matched) = self.paste_end_matched else {
+            return false;
+        };
+        self.pending.push(byte);
+        let matched = if byte == PASTE_END[matched] {
+            matched + 1
+        } else {
+            usize::from(byte == PASTE_END[0])
+        };
+        if matched == PASTE_END.len() {
+            self.paste_end_matched = None;
+            self.flush_pending(out);
+        } else {
+            self.paste_end_matched = Some(matched);
+        }
+        true
+    }
+
     fn consume_escape(&mut self, byte: u8, out: &mut Vec<InputEvent>) -> bool {
         const MAX_CSI_LEN: usize = 32;
 
         if let Some(ref mut buffer) = self.esc_buffer {
             buffer.push(byte);
-            if buffer.len() == 2 && buffer[1] != b'[' {
+            // SS3 keys (`ESC O A`), sent in application cursor/keypad mode, stay whole.
+            if buffer.len() == 2 && buffer[1] != b'[' && buffer[1] != b'O' {
                 self.pending.extend_from_slice(buffer);
                 self.esc_buffer = None;
                 return true;
             }
+            if buffer.len() == 3 && buffer[1] == b'O' {
+                if !is_csi_final(byte) {
+                    // `ESC O` was Alt+Shift+O; the next byte is a key of its own.
+                    self.pending.extend_from_slice(&buffer[..2]);
+                    self.esc_buffer = None;
+                    return self.consume_escape(byte, out);
+                }
+                let keypad_enter = byte == b'M';
+                if !keypad_enter {
+                    self.pending.extend_from_slice(buffer);
+                }
+                self.esc_buffer = None;
+                if keypad_enter {
+                    self.flush_pending(out);
+                    out.push(InputEvent::EnterKey);
+                }
+                return true;
+            }
 
             if buffer.len() >= 2 && buffer[1] == b'[' {
                 // X10 mouse protocol: ESC [ M Cb Cx Cy (fixed 6-byte sequence).
@@ -153,29 +205,58 @@ impl InputParser {
                     return true;
                 }
 
+                if buffer.as_slice() == PASTE_START {
+                    self.pending.extend_from_slice(PASTE_START);
+                    self.esc_buffer = None;
+                    self.paste_end_matched = Some(0);
+                    self.skip_lf = false;
+                    return true;
+                }
                 if buffer.len() >= 3 && is_csi_final(byte) {
                     let (is_csi_u, event) = {
                         let is_csi_u = is_csi_u_numeric(buffer);
                         let event = if is_csi_u {
-                            parse_csi_u_event(buffer)
+                            parse_csi_u_event(buffer, &self.bindings)
                         } else {
                             None
                         };
                         (is_csi_u, event)
                     };
                     if is_csi_u {
+                        // With the backend in kitty mode, keys the overlay does not
+                        // bind go to it as sent; otherwise only text survives.
+                        let kitty = self.kitty_keyboard.active();
+                        let event =
+                            event.or_else(|| kitty.then(|| csi_u_overlay_key(buffer)).flatten());
+                        let bytes = match event {
+                            Some(_) => None,
+                            None if kitty => Some(buffer.clone()),
+                            None => csi_u_text(buffer).map(String::into_bytes),
+                        };
                         self.esc_buffer = None;
                         if let Some(event) = event {
                             self.flush_pending(out);
                             out.push(event);
+                        } else if let Some(bytes) = bytes {
+                            self.pending.extend_from_slice(&bytes);
                         }
-                    } else if let Some((kind, x, y)) = parse_mouse_event(buffer) {
-                        // Mouse click across supported protocols (SGR, URXVT, X10).
+                    } else if let Some(event) = parse_modify_other_keys(buffer, &self.bindings) {
                         self.esc_buffer = None;
-                        self.emit_mouse_click(out, kind, x, y);
+                        self.flush_pending(out);
+                        out.push(event);
                     } else if is_mouse_sequence(buffer) {
-                        // Ignore non-click mouse events (scroll/motion) to avoid flooding output.
+                        // Clicks work across supported protocols (SGR, URXVT, X10); every
+                        // SGR report, clicks included, is also kept whole for the backend.
+                        let click = parse_mouse_event(buffer);
+                        let report = sgr_mouse_row(buffer).map(|y| (buffer.clone(), y));
                         self.esc_buffer = None;
+                        if let Some((kind, x, y)) = click {
+                            self.emit_mouse_click(out, kind, x, y);
+                        }
+                        if let Some((bytes, y)) = report {
+                            self.flush_pending(out);
+                            out.push(InputEvent::MouseReport { bytes, y });
+                        }
                     } else {
                         self.pending.extend_from_slice(buffer);
                         self.esc_buffer = None;
@@ -230,7 +311,7 @@ fn is_csi_u_numeric(buffer: &[u8]) -> bool {
 
 /// Parse a CSI-u keyboard event (e.g., ESC [ 114 ; 5 u for Ctrl+R).
 #[inline]
-fn parse_csi_u_event(buffer: &[u8]) -> Option<InputEvent> {
+fn parse_csi_u_event(buffer: &[u8], bindings: &KeyBindings) -> Option<InputEvent> {
     // Minimum valid: ESC [ <digit> u = 4 bytes
     if buffer.len() < 4
         || buffer[0] != 0x1b
@@ -249,21 +330,108 @@ fn parse_csi_u_event(buffer: &[u8]) -> Option<InputEvent> {
         return None;
     }
 
-    let ch = std::char::from_u32(code)?;
-    let key = ch.to_ascii_lowercase();
-    match key {
-        'r' => Some(InputEvent::VoiceTrigger),
-        'v' => Some(InputEvent::ToggleAutoVoice),
-        't' => Some(InputEvent::ToggleSendMode),
-        'y' => Some(InputEvent::ThemePicker),
-        'o' => Some(InputEvent::SettingsToggle),
-        'u' => Some(InputEvent::ToggleHudStyle),
-        '?' => Some(InputEvent::HelpToggle),
-        'q' => Some(InputEvent::Exit),
+    ctrl_key_event(std::char::from_u32(code)?, bindings)
+}
+
+/// Parse an xterm `modifyOtherKeys` key (`ESC [ 27 ; 5 ; 114 ~` for Ctrl+R).
+fn parse_modify_other_keys(buffer: &[u8], bindings: &KeyBindings) -> Option<InputEvent> {
+    let params = buffer.strip_prefix(b"\x1b[27;")?.strip_suffix(b"~")?;
+    let mut parts = params.split(|b| *b == b';');
+    let modifiers = parts.next().and_then(parse_csi_u_number)?;
+    let code = parts.next().and_then(parse_csi_u_number)?;
+    // xterm encodes modifiers as 1 + bitmask, with 4 for Ctrl.
+    if modifiers.checked_sub(1)? & 4 == 0 || parts.next().is_some() {
+        return None;
+    }
+    ctrl_key_event(std::char::from_u32(code)?, bindings)
+}
+
+/// Overlay event for a Ctrl+`ch` key reported without a legacy control byte.
+fn ctrl_key_event(ch: char, bindings: &KeyBindings) -> Option<InputEvent> {
+    // Ctrl+, has no control byte either; terminals that report it open settings
+    // (the conventional preferences shortcut) unless the settings key was released.
+    if ch == ',' {
+        return bindings
+            .is_bound(OverlayAction::SettingsToggle)
+            .then_some(InputEvent::SettingsToggle);
+    }
+    // Ctrl+? has no legacy control byte; treat it like the bare `?` binding.
+    let byte = if ch == '?' { b'?' } else { control_byte(ch)? };
+    bindings.action_for_byte(byte).map(|action| action.event())
+}
+
+/// Enter and Escape in kitty form, which overlays and delivery still read as legacy keys.
+fn csi_u_overlay_key(buffer: &[u8]) -> Option<InputEvent> {
+    const ENTER: u32 = 13;
+    const ESCAPE: u32 = 27;
+    const KEYPAD_ENTER: u32 = 57414;
+    const LOCKS: u32 = 64 | 128;
+
+    let params = &buffer[2..buffer.len() - 1];
+    let mut parts = params.split(|b| *b == b';');
+    let code = parts.next().and_then(parse_csi_u_number)?;
+    let modifiers = match parts.next() {
+        None | Some([]) => 1,
+        Some(field) => parse_csi_u_number(field)?,
+    };
+    if modifiers.checked_sub(1)? & !LOCKS != 0 {
+        return None;
+    }
+    match code {
+        ENTER | KEYPAD_ENTER => Some(InputEvent::EnterKey),
+        ESCAPE => Some(InputEvent::Bytes(vec![0x1b])),
         _ => None,
     }
 }
 
+/// Text typed as a plain or shifted printable key in CSI-u form (`ESC [ 233 u` is `é`).
+///
+/// Uses the associated-text field (`ESC [ 101 ; 1 ; 233 u`) when the terminal
+/// reports one. Control keys, modified keys, and kitty's private-use codes for
+/// functional keys yield `None` and stay dropped.
+fn csi_u_text(buffer: &[u8]) -> Option<String> {
+    const SHIFT: u32 = 1;
+    const CAPS_LOCK: u32 = 64;
+    const NUM_LOCK: u32 = 128;
+
+    let params = &buffer[2..buffer.len() - 1];
+    let mut parts = params.split(|b| *b == b';');
+    let code = parts.next().and_then(parse_csi_u_number)?;
+    // Kitty encodes modifiers as 1 + bitmask; an empty field means none.
+    let modifiers = match parts.next() {
+        None | Some([]) => 1,
+        Some(field) => parse_csi_u_number(field)?,
+    };
+    let mask = modifiers.checked_sub(1)?;
+    if mask & !(SHIFT | CAPS_LOCK | NUM_LOCK) != 0 {
+        return None;
+    }
+    let code = parts.next().and_then(parse_csi_u_number).unwrap_or(code);
+    let ch = char::from_u32(code)?;
+    if ch.is_control() || ('\u{e000}'..='\u{f8ff}').contains(&ch) {
+        return None;
+    }
+    Some(ch.to_string())
+}
+
+/// Length of a trailing UTF-8 sequence that is still waiting for continuation bytes.
+fn incomplete_utf8_tail(bytes: &[u8]) -> usize {
+    for back in 1..=bytes.len().min(3) {
+        let byte = bytes[bytes.len() - back];
+        if byte & 0b1100_0000 == 0b1000_0000 {
+            continue;
+        }
+        let needed = match byte {
+            0xc2..=0xdf => 2,
+            0xe0..=0xef => 3,
+            0xf0..=0xf4 => 4,
+            _ => return 0,
+        };
+        return if back < needed { back } else { 0 };
+    }
+    0
+}
+
 fn parse_csi_u_number(bytes: &[u8]) -> Option<u32> {
     if bytes.is_empty() {
         return None;
@@ -281,6 +449,7 @@ fn parse_csi_u_number(bytes: &[u8]) -> Option<u32> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::modes::BackendModes;
 
     #[test]
     fn input_parser_emits_bytes_and_controls() {
@@ -298,7 +467,7 @@ mod tests {
     fn input_parser_maps_control_keys() {
         let mut parser = InputParser::new();
         let mut out = Vec::new();
-        parser.consume_bytes(&[0x11, 0x16, 0x14, 0x1d, 0x1c, 0x1f, 0x0f], &mut out);
+        parser.consume_bytes(&[0x11, 0x16, 0x14, 0x1d, 0x1e, 0x0f], &mut out);
         parser.flush_pending(&mut out);
         assert_eq!(
             out,
@@ -308,12 +477,20 @@ mod tests {
                 InputEvent::ToggleSendMode,
                 InputEvent::IncreaseSensitivity,
                 InputEvent::DecreaseSensitivity,
-                InputEvent::DecreaseSensitivity,
                 InputEvent::SettingsToggle,
             ]
         );
     }
 
+    #[test]
+    fn input_parser_forwards_sigquit_and_undo_bytes() {
+        let mut parser = InputParser::new();
+        let mut out = Vec::new();
+        parser.consume_bytes(&[0x1c, 0x1f], &mut out);
+        parser.flush_pending(&mut out);
+        assert_eq!(out, vec![InputEvent::Bytes(vec![0x1c, 0x1f])]);
+    }
+
     #[test]
     fn input_parser_maps_theme_picker() {
         let mut parser = InputParser::new();
@@ -369,6 +546,80 @@ mod tests {
         assert!(out.is_empty());
     }
 
+    #[test]
+    fn input_parser_passes_dead_key_and_ime_text_through() {
+        let mut parser = InputParser::new();
+        let mut out = Vec::new();
+        // Dead-key accents, a Japanese IME commit, Hangul, and an emoji.
+        let text = "café naïve 日本語 한국어 🎤";
+        parser.consume_bytes(text.as_bytes(), &mut out);
+        parser.flush_pending(&mut out);
+        assert_eq!(out, vec![InputEvent::Bytes(text.as_bytes().to_vec())]);
+    }
+
+    #[test]
+    fn input_parser_never_maps_utf8_bytes_to_hotkeys() {
+        // Bytes such as 0x91 and 0x98 are C1 controls in Latin-1; inside UTF-8
+        // they must neither trigger an action nor split the text.
+        let mut parser = InputParser::new();
+        let mut out = Vec::new();
+        let text = "‘quoted’ ž ґ";
+        parser.consume_bytes(text.as_bytes(), &mut out);
+        parser.consume_bytes(b"\x12", &mut out);
+        parser.flush_pending(&mut out);
+        assert_eq!(
+            out,
+            vec![
+                InputEvent::Bytes(text.as_bytes().to_vec()),
+                InputEvent::VoiceTrigger
+            ]
+        );
+    }
+
+    #[test]
+    fn input_parser_keeps_characters_split_across_reads_whole() {
+        let mut parser = InputParser::new();
+        let mut out = Vec::new();
+        let bytes = "aé語".as_bytes();
+        // Split inside `é` (2 bytes) and again inside `語` (3 bytes).
+        parser.consume_bytes(&bytes[..2], &mut out);
+        parser.flush_complete_chars(&mut out);
+        parser.consume_bytes(&bytes[2..4], &mut out);
+        parser.flush_complete_chars(&mut out);
+        parser.consume_bytes(&bytes[4..], &mut out);
+        parser.flush_complete_chars(&mut out);
+        assert_eq!(
+            out,
+            vec![
+                InputEvent::Bytes(b"a".to_vec()),
+                InputEvent::Bytes("é".as_bytes().to_vec()),
+                InputEvent::Bytes("語".as_bytes().to_vec()),
+            ]
+        );
+    }
+
+    #[test]
+    fn input_parser_passes_alt_with_non_ascii_key() {
+        let mut parser = InputParser::new();
+        let mut out = Vec::new();
+        parser.consume_bytes("\x1bé".as_bytes(), &mut out);
+        parser.flush_complete_chars(&mut out);
+        assert_eq!(out, vec![InputEvent::Bytes("\x1bé".as_bytes().to_vec())]);
+    }
+
+    #[test]
+    fn input_parser_decodes_csi_u_text_keys() {
+        let mut parser = InputParser::new();
+        let mut out = Vec::new();
+        // Plain é, shifted É, associated-text form, then an unbound Ctrl key and Escape.
+        parser.consume_bytes(
+            b"\x1b[233u\x1b[201;2u\x1b[101;1;233u\x1b[97;5u\x1b[27u",
+            &mut out,
+        );
+        parser.flush_pending(&mut out);
+        assert_eq!(out, vec![InputEvent::Bytes("éÉé".as_bytes().to_vec())]);
+    }
+
     #[test]
     fn input_parser_preserves_arrow_sequences() {
         let mut parser = InputParser::new();
@@ -389,6 +640,105 @@ mod tests {
         assert_eq!(out, vec![InputEvent::VoiceTrigger]);
     }
 
+    #[test]
+    fn input_parser_forwards_csi_u_keys_when_backend_enables_kitty_keyboard() {
+        let kitty = KittyKeyboard::default();
+        BackendModes::new(kitty.clone()).observe(b"\x1b[>1u");
+        let mut parser = InputParser::new().with_kitty_keyboard(kitty);
+        let mut out = Vec::new();
+        // Unbound Ctrl+A, bound Ctrl+R, Enter, Escape, then a plain key.
+        parser.consume_bytes(b"\x1b[97;5u\x1b[114;5u\x1b[13u\x1b[27u\x1b[233u", &mut out);
+        parser.flush_pending(&mut out);
+        assert_eq!(
+            out,
+            vec![
+                InputEvent::Bytes(b"\x1b[97;5u".to_vec()),
+                InputEvent::VoiceTrigger,
+                InputEvent::EnterKey,
+                InputEvent::Bytes(vec![0x1b]),
+                InputEvent::Bytes(b"\x1b[233u".to_vec()),
+            ]
+        );
+    }
+
+    #[test]
+    fn input_parser_maps_modify_other_keys_and_keeps_ss3_keys_whole() {
+        let mut parser = InputParser::new();
+        let mut out = Vec::new();
+        parser.consume_bytes(b"\x1b[27;5;114~\x1b[27;5;97~\x1bOA\x1bOM\x1bO\r", &mut out);
+        parser.flush_pending(&mut out);
+        assert_eq!(
+            out,
+            vec![
+                InputEvent::VoiceTrigger,
+                InputEvent::Bytes(b"\x1b[27;5;97~\x1bOA".to_vec()),
+                InputEvent::EnterKey,
+                InputEvent::Bytes(b"\x1bO".to_vec()),
+                InputEvent::EnterKey,
+            ]
+        );
+    }
+
+    #[test]
+    fn input_parser_passes_bracketed_paste_through() {
+        let mut parser = InputParser::new();
+        let mut out = Vec::new();
+        parser.consume_bytes(b"\x1b[200~ls\x12\r\n?\x1b[20", &mut out);
+        parser.flush_complete_chars(&mut out);
+        parser.consume_bytes(b"1~\x12", &mut out);
+        parser.flush_complete_chars(&mut out);
+        assert_eq!(
+            out,
+            vec![
+                InputEvent::Bytes(b"\x1b[200~ls\x12\r\n?\x1b[20".to_vec()),
+                InputEvent::Bytes(b"1~".to_vec()),
+                InputEvent::VoiceTrigger,
+            ]
+        );
+    }
+
+    #[test]
+    fn input_parser_maps_csi_u_ctrl_comma_to_settings() {
+        let mut parser = InputParser::new();
+        let mut out = Vec::new();
+        parser.consume_bytes(b"\x1b[44;5u", &mut out);
+        parser.flush_pending(&mut out);
+        assert_eq!(out, vec![InputEvent::SettingsToggle]);
+
+        let mut bindings = KeyBindings::default();
+        bindings.release(OverlayAction::SettingsToggle);
+        let mut parser = InputParser::with_bindings(bindings);
+        let mut out = Vec::new();
+        parser.consume_bytes(b"\x1b[44;5u", &mut out);
+        parser.flush_pending(&mut out);
+        assert!(out.is_empty(), "{out:?}");
+    }
+
+    #[test]
+    fn input_parser_uses_custom_bindings() {
+        let overrides = [(
+            "voice_trigger".to_string(),
+            crate::config::KeySpecList::One("ctrl+g".to_string()),
+        )]
+        .into_iter()
+        .collect();
+        let (bindings, _) =
+            KeyBindings::from_config(&overrides, "codex").expect("bindings should resolve");
+        let mut parser = InputParser::with_bindings(bindings);
+        let mut out = Vec::new();
+        parser.consume_bytes(&[0x12, 0x07], &mut out);
+        parser.consume_bytes(b"\x1b[103;5u", &mut out);
+        parser.flush_pending(&mut out);
+        assert_eq!(
+            out,
+            vec![
+                InputEvent::Bytes(vec![0x12]),
+                InputEvent::VoiceTrigger,
+                InputEvent::VoiceTrigger
+            ]
+        );
+    }
+
     #[test]
     fn input_parser_handles_truncated_csi_u_sequences() {
         let mut parser = InputParser::new();
@@ -407,7 +757,16 @@ mod tests {
         let mut out = Vec::new();
         parser.consume_bytes(b"\x1b[<0;10;5m", &mut out);
         parser.flush_pending(&mut out);
-        assert_eq!(out, vec![InputEvent::MouseClick { x: 10, y: 5 }]);
+        assert_eq!(
+            out,
+            vec![
+                InputEvent::MouseClick { x: 10, y: 5 },
+                InputEvent::MouseReport {
+                    bytes: b"\x1b[<0;10;5m".to_vec(),
+                    y: 5
+                },
+            ]
+        );
     }
 
     #[test]
@@ -416,16 +775,27 @@ mod tests {
         let mut out = Vec::new();
         parser.consume_bytes(b"\x1b[<0;10;5M\x1b[<0;10;5m", &mut out);
         parser.flush_pending(&mut out);
-        assert_eq!(out, vec![InputEvent::MouseClick { x: 10, y: 5 }]);
+        let clicks = out
+            .iter()
+            .filter(|event| matches!(event, InputEvent::MouseClick { .. }))
+            .count();
+        assert_eq!(clicks, 1);
+        assert_eq!(out[0], InputEvent::MouseClick { x: 10, y: 5 });
     }
 
     #[test]
-    fn input_parser_ignores_sgr_mouse_wheel() {
+    fn input_parser_reports_sgr_mouse_wheel_without_click() {
         let mut parser = InputParser::new();
         let mut out = Vec::new();
         parser.consume_bytes(b"\x1b[<64;10;5M", &mut out);
         parser.flush_pending(&mut out);
-        assert!(out.is_empty());
+        assert_eq!(
+            out,
+            vec![InputEvent::MouseReport {
+                bytes: b"\x1b[<64;10;5M".to_vec(),
+                y: 5
+            }]
+        );
     }
 
     #[test]
@@ -437,7 +807,13 @@ mod tests {
         assert!(out.is_empty());
         parser.consume_bytes(b"5M", &mut out);
         parser.flush_pending(&mut out);
-        assert!(out.is_empty());
+        assert_eq!(
+            out,
+            vec![InputEvent::MouseReport {
+                bytes: b"\x1b[<64;10;5M".to_vec(),
+                y: 5
+            }]
+        );
     }
 
     #[test]
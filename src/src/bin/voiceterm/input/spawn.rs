@@ -7,6 +7,8 @@ use voiceterm::log_debug;
 
 use crate::arrow_keys::is_arrow_escape_noise;
 use crate::input::event::InputEvent;
+use crate::input::keybindings::KeyBindings;
+use crate::input::modes::KittyKeyboard;
 use crate::input::parser::InputParser;
 
 const INPUT_DEBUG_ENV: &str = "VOICETERM_DEBUG_INPUT";
@@ -30,11 +32,15 @@ fn format_debug_bytes(bytes: &[u8]) -> String {
     out
 }
 
-pub(crate) fn spawn_input_thread(tx: Sender<InputEvent>) -> thread::JoinHandle<()> {
+pub(crate) fn spawn_input_thread(
+    tx: Sender<InputEvent>,
+    bindings: KeyBindings,
+    kitty_keyboard: KittyKeyboard,
+) -> thread::JoinHandle<()> {
     thread::spawn(move || {
         let mut stdin = io::stdin();
         let mut buf = [0u8; 1024];
-        let mut parser = InputParser::new();
+        let mut parser = InputParser::with_bindings(bindings).with_kitty_keyboard(kitty_keyboard);
         let debug_input = input_debug_enabled();
         loop {
             let n = match stdin.read(&mut buf) {
@@ -54,7 +60,7 @@ pub(crate) fn spawn_input_thread(tx: Sender<InputEvent>) -> thread::JoinHandle<(
             }
             let mut events = Vec::new();
             parser.consume_bytes(&buf[..n], &mut events);
-            parser.flush_pending(&mut events);
+            parser.flush_complete_chars(&mut events);
             if debug_input && !events.is_empty() {
                 log_debug(&format!("input events: {events:?}"));
             }
@@ -0,0 +1,149 @@
+//! Latency overlay that shows live per-phase capture timings for the current session.
+//!
+//! Columns are the last sample, the rolling average of recent captures, and the
+//! session p95, so a slow capture stands out against the running baseline.
+
+use crate::help::{format_box_bottom, format_box_top, format_separator, format_title_line};
+use crate::theme::{Theme, ThemeColors};
+use crate::voice_metrics::{format_latency_ms, LatencyPhase, VoiceMetrics, ROLLING_WINDOW};
+
+pub const LATENCY_OVERLAY_FOOTER: &str = "[×] close · any key";
+
+pub fn latency_overlay_width_for_terminal(width: usize) -> usize {
+    width.clamp(30, 50)
+}
+
+pub fn latency_overlay_inner_width_for_terminal(width: usize) -> usize {
+    latency_overlay_width_for_terminal(width).saturating_sub(2)
+}
+
+/// Format the latency overlay as a string.
+pub fn format_latency_overlay(metrics: &VoiceMetrics, theme: Theme, width: usize) -> String {
+    let colors = theme.colors();
+    let borders = &colors.borders;
+    let content_width = latency_overlay_width_for_terminal(width);
+    let avg_header = format!("Avg{ROLLING_WINDOW}");
+
+    let mut lines = vec![
+        format_box_top(&colors, borders, content_width),
+        format_title_line(&colors, borders, "VoiceTerm - Latency", content_width),
+        format_separator(&colors, borders, content_width),
+        format_row(
+            &colors,
+            colors.info,
+            &format_columns("Phase", "Last", &avg_header, "p95"),
+            content_width,
+        ),
+    ];
+    for phase in LatencyPhase::ALL {
+        let label = |value: Option<u64>| value.map_or_else(|| "--".to_string(), format_latency_ms);
+        let p95 = metrics.summary(phase).map(|summary| summary.p95_ms);
+        lines.push(format_row(
+            &colors,
+            "",
+            &format_columns(
+                phase.label(),
+                &label(metrics.last(phase)),
+                &label(metrics.rolling_avg(phase)),
+                &label(p95),
+            ),
+            content_width,
+        ));
+    }
+    let captures = match metrics.captures() {
+        0 => "No captures yet".to_string(),
+        1 => "1 capture this session".to_string(),
+        count => format!("{count} captures this session"),
+    };
+    lines.push(format_row(
+        &colors,
+        colors.dim,
+        &format!("  {captures}"),
+        content_width,
+    ));
+    lines.push(format_separator(&colors, borders, content_width));
+    lines.push(format_title_line(
+        &colors,
+        borders,
+        LATENCY_OVERLAY_FOOTER,
+        content_width,
+    ));
+    lines.push(format_box_bottom(&colors, borders, content_width));
+    lines.join("\n")
+}
+
+fn format_columns(phase: &str, last: &str, avg: &str, p95: &str) -> String {
+    format!("  {phase:<12}{last:>8}{avg:>8}{p95:>8}")
+}
+
+fn format_row(colors: &ThemeColors, text_color: &str, text: &str, width: usize) -> String {
+    let borders = &colors.borders;
+    let inner_width = width.saturating_sub(2);
+    let truncated: String = text.chars().take(inner_width).collect();
+    let padded = format!("{truncated:<inner_width$}");
+    let reset = if text_color.is_empty() {
+        ""
+    } else {
+        colors.reset
+    };
+    format!(
+        "{}{}{}{}{}{}{}{}{}",
+        colors.border,
+        borders.vertical,
+        colors.reset,
+        text_color,
+        padded,
+        reset,
+        colors.border,
+        borders.vertical,
+        colors.reset
+    )
+}
+
+/// Calculate the height of the latency overlay.
+pub fn latency_overlay_height() -> usize {
+    // Top border + title + separator + header + phases + captures + separator + footer + bottom
+    3 + 1 + LatencyPhase::ALL.len() + 1 + 3
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn latency_overlay_lines_match_height_and_width() {
+        let mut metrics = VoiceMetrics::default();
+        metrics.record_delivery(Duration::from_millis(40));
+        let overlay = format_latency_overlay(&metrics, Theme::None, 80);
+        let lines: Vec<&str> = overlay.lines().collect();
+        assert_eq!(lines.len(), latency_overlay_height());
+        let width = latency_overlay_width_for_terminal(80);
+        for line in &lines {
+            assert_eq!(line.chars().count(), width, "{line:?}");
+        }
+    }
+
+    #[test]
+    fn latency_overlay_shows_placeholders_until_measured() {
+        let overlay = format_latency_overlay(&VoiceMetrics::default(), Theme::None, 80);
+        assert!(overlay.contains("No captures yet"));
+        assert!(overlay.contains("--"));
+
+        let mut metrics = VoiceMetrics::default();
+        metrics.record_delivery(Duration::from_millis(1200));
+        let overlay = format_latency_overlay(&metrics, Theme::None, 80);
+        let delivery = overlay
+            .lines()
+            .find(|line| line.contains("Delivery"))
+            .expect("delivery row");
+        assert!(delivery.contains("1.2s"), "{delivery}");
+    }
+
+    #[test]
+    fn latency_overlay_truncates_rows_on_narrow_terminals() {
+        let overlay = format_latency_overlay(&VoiceMetrics::default(), Theme::None, 10);
+        let width = latency_overlay_width_for_terminal(10);
+        assert!(overlay.lines().all(|line| line.chars().count() == width));
+    }
+}
@@ -12,39 +12,60 @@
 
 mod arrow_keys;
 mod audio_meter;
+mod backend_exit;
 mod banner;
 mod button_handlers;
 mod buttons;
 mod cli_utils;
 mod color_mode;
 mod config;
+mod control;
+mod daemon;
 mod event_loop;
 mod event_state;
 mod help;
 mod hud;
 mod icons;
 mod input;
+mod latency_overlay;
+mod mcp;
+mod output_preview;
 mod overlays;
+mod pipe;
+mod profile;
 mod progress;
 mod prompt;
+mod queue_overlay;
+mod scrollback;
+mod search_overlay;
+mod session_events;
+mod session_record;
 mod session_stats;
 mod settings;
 mod settings_handlers;
+mod startup;
 mod status_line;
 mod status_style;
 mod terminal;
+mod terminal_caps;
 mod theme;
 mod theme_ops;
 mod theme_picker;
+mod transcribe;
 mod transcript;
+mod tty_lock;
+mod unix_socket;
+mod vocab;
 mod voice_control;
+mod voice_enroll;
 mod voice_macros;
+mod voice_metrics;
+mod web_ui;
 mod writer;
 
 pub(crate) use overlays::OverlayMode;
 
-use anyhow::Result;
-use clap::Parser;
+use anyhow::{bail, Result};
 use crossbeam_channel::bounded;
 use crossterm::terminal::size as terminal_size;
 use std::collections::VecDeque;
@@ -52,29 +73,70 @@ use std::env;
 use std::io::{self, Write};
 use std::path::Path;
 use std::time::{Duration, Instant};
-use voiceterm::pty_session::PtyOverlaySession;
 use voiceterm::{
-    auth::run_login_command, doctor::base_doctor_report, init_logging, log_debug, log_file_path,
-    terminal_restore::TerminalRestoreGuard, VoiceCaptureTrigger,
+    audio,
+    auth::run_login_command,
+    doctor::{append_bench_report, base_doctor_report},
+    init_logging, log_debug, log_file_path,
+    terminal_restore::TerminalRestoreGuard,
+    VoiceCaptureTrigger,
 };
 
+use crate::backend_exit::BackendLauncher;
 use crate::banner::{should_skip_banner, show_startup_splash, BannerConfig};
 use crate::button_handlers::send_enhanced_status_with_buttons;
 use crate::buttons::ButtonRegistry;
-use crate::cli_utils::{list_input_devices, resolve_sound_flag, should_print_stats};
-use crate::config::{HudStyle, OverlayConfig};
+use crate::cli_utils::{
+    list_input_devices, resolve_sound_flag, resolve_working_dir, should_print_stats,
+};
+use crate::config::{
+    find_preset, parse_overlay_config, project_root, run_migrate_config, HudStyle, OverlayCommand,
+};
+use crate::control::{run_events, run_send, ControlServer};
+use crate::daemon::run_daemon;
 use crate::event_loop::run_event_loop;
 use crate::event_state::{EventLoopDeps, EventLoopState, EventLoopTimers};
 use crate::hud::HudRegistry;
-use crate::input::spawn_input_thread;
-use crate::prompt::{resolve_prompt_log, resolve_prompt_regex, PromptLogger, PromptTracker};
+use crate::input::{spawn_input_thread, BackendModes, KeyBindings, KittyKeyboard, OverlayAction};
+use crate::mcp::run_mcp;
+use crate::output_preview::OutputPreview;
+use crate::pipe::{run_capture, run_pipe};
+use crate::profile::run_profile;
+use crate::prompt::{
+    resolve_prompt_log, resolve_prompt_regex, ExternalPromptDetector, PromptLogger, PromptTracker,
+};
+use crate::queue_overlay::QueuePanel;
+use crate::scrollback::Scrollback;
+use crate::session_events::{
+    close_session_events, emit_session_event, subscribe_session_events, SessionHook,
+    VoiceSessionEvent,
+};
+use crate::session_record::{
+    run_session_replay, send_mode_key, start_session_recording, SessionEvent,
+};
 use crate::session_stats::{format_session_stats, SessionStats};
 use crate::settings::SettingsMenuState;
+use crate::startup::StartupProfile;
 use crate::status_line::{Pipeline, StatusLineState, VoiceMode, METER_HISTORY_MAX};
 use crate::terminal::{apply_pty_winsize, install_sigwinch_handler};
+use crate::theme::register_custom_themes;
 use crate::theme_ops::theme_index_from_theme;
-use crate::voice_control::{reset_capture_visuals, start_voice_capture, VoiceManager};
+use crate::transcribe::run_transcribe;
+use crate::transcript::{
+    resolve_type_delay, run_export_feedback, set_redact_transcripts, BatchWindow, DictationBuffer,
+    MultilineDelivery, PromptReplay, QueuePolicy, SecretsFilter, TranscriptFeedback,
+    TranscriptHistoryWriter, TranscriptPostProcessor, TranscriptPrefix, TranscriptTargets,
+    TranscriptTranslator, UncertainWordMarker, UrgentKeywords,
+};
+use crate::tty_lock::TtyLock;
+use crate::vocab::ProjectVocabulary;
+use crate::voice_control::{
+    reset_capture_visuals, start_voice_capture, AutoVoiceDnd, Redictation, VoiceManager,
+    VoiceNavigation, MODEL_WARMUP_STATUS,
+};
 use crate::voice_macros::VoiceMacros;
+use crate::voice_metrics::SessionEventMetrics;
+use crate::web_ui::{WebUiServer, WsBridge};
 use crate::writer::{set_status, spawn_writer_thread, WriterMessage};
 
 /// Max pending messages for the output writer thread.
@@ -144,13 +206,29 @@ fn resolved_meter_update_ms(hud_registry: &HudRegistry) -> u64 {
 }
 
 fn main() -> Result<()> {
-    let mut config = OverlayConfig::parse();
+    let started_at = Instant::now();
+    let (mut config, user_config) = parse_overlay_config(std::env::args_os().collect())?;
+    if let Some(name) = config.dump_preset.as_deref() {
+        print!("{}", find_preset(name)?.yaml);
+        return Ok(());
+    }
+    if config.migrate_config {
+        return run_migrate_config(config.config_path.as_deref());
+    }
+    register_custom_themes(&user_config.themes)?;
+    match config.command.as_ref() {
+        Some(OverlayCommand::Send(args)) => return run_send(args),
+        Some(OverlayCommand::Events(args)) => return run_events(args),
+        Some(OverlayCommand::ExportFeedback(args)) => return run_export_feedback(&config, args),
+        Some(OverlayCommand::Profile(args)) => return run_profile(&config, args),
+        _ => {}
+    }
     let sound_on_complete = resolve_sound_flag(config.app.sounds, config.app.sound_on_complete);
     let sound_on_error = resolve_sound_flag(config.app.sounds, config.app.sound_on_error);
     let backend = config.resolve_backend();
     let backend_label = backend.label.clone();
     let theme = config.theme_for_backend(&backend_label);
-    if config.app.doctor {
+    if config.app.doctor || config.app.doctor_bench {
         let mut report = base_doctor_report(&config.app, "voiceterm");
         report.section("Overlay");
         report.push_kv("backend", backend.label);
@@ -161,6 +239,10 @@ fn main() -> Result<()> {
             "prompt_regex",
             config.prompt_regex.as_deref().unwrap_or("auto"),
         );
+        report.push_kv(
+            "prompt_detector_cmd",
+            config.prompt_detector_cmd.as_deref().unwrap_or("none"),
+        );
         report.push_kv(
             "prompt_log",
             config
@@ -169,18 +251,57 @@ fn main() -> Result<()> {
                 .map(|path| path.display().to_string())
                 .unwrap_or_else(|| "disabled".to_string()),
         );
+        report.push_kv(
+            "config_file",
+            config
+                .config_path
+                .clone()
+                .or_else(crate::config::default_config_path)
+                .map(|path| path.display().to_string())
+                .unwrap_or_else(|| "none".to_string()),
+        );
+        report.push_kv("preset", config.preset.as_deref().unwrap_or("none"));
         report.push_kv("theme", config.theme_name.as_deref().unwrap_or("coral"));
         report.push_kv("no_color", config.no_color);
         report.push_kv("auto_voice", config.auto_voice);
+        report.push_kv(
+            "transcript_targets",
+            if config.target.is_empty() {
+                "backend".to_string()
+            } else {
+                config.target.join(", ")
+            },
+        );
         report.push_kv(
             "voice_send_mode",
             format!("{:?}", config.voice_send_mode).to_lowercase(),
         );
+        report.push_kv("render_mode", config.render_mode.describe());
+        report.push_kv(
+            "audio_cues",
+            if !config.app.audio_cues {
+                "off".to_string()
+            } else {
+                let device = config.app.output_device.as_deref().unwrap_or("default");
+                match audio::CuePlayer::new(config.app.output_device.as_deref(), 0) {
+                    Ok(_) => format!("on ({device}, {}%)", config.app.audio_cue_volume),
+                    Err(err) => format!("unavailable ({err:#})"),
+                }
+            },
+        );
         report.push_kv(
             "latency_display",
             format!("{:?}", config.latency_display).to_lowercase(),
         );
+        let missed = if config.app.doctor_bench {
+            append_bench_report(&mut report, &config.app)
+        } else {
+            0
+        };
         println!("{}", report.render());
+        if missed > 0 {
+            bail!("doctor benchmark missed {missed} latency target(s)");
+        }
         return Ok(());
     }
     if config.app.list_input_devices {
@@ -193,28 +314,209 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
+    if config.calibrate {
+        audio_meter::run_calibration(&config, theme)?;
+        return Ok(());
+    }
+
+    if config.app.enroll_voice {
+        voice_enroll::run_voice_enrollment(&config.app)?;
+        return Ok(());
+    }
+
+    if let Some(OverlayCommand::ReplaySession(args)) = config.command.as_ref() {
+        return run_session_replay(&config, args, &user_config.prompt);
+    }
+
+    if config.redact_transcripts {
+        // Privacy mode overrides --log-content, which would log transcript words.
+        config.app.log_content = false;
+        set_redact_transcripts(true);
+    }
     config.app.validate()?;
     init_logging(&config.app);
     let log_path = log_file_path();
     log_debug("=== VoiceTerm Overlay Started ===");
     log_debug(&format!("Log file: {log_path:?}"));
+    let mut startup = StartupProfile::new(started_at);
+    startup.lap("config", Instant::now());
+
+    if let Some(path) = user_config.source_path.as_ref() {
+        log_debug(&format!("config file: {}", path.display()));
+    }
+    if let Some(path) = user_config.project_path.as_ref() {
+        log_debug(&format!("project config file: {}", path.display()));
+    }
+    if let Some(OverlayCommand::Daemon(args)) = config.command.as_ref() {
+        return run_daemon(&config, args, &user_config);
+    }
+    if let Some(OverlayCommand::Mcp(args)) = config.command.as_ref() {
+        return run_mcp(&config, args, &user_config);
+    }
+    if let Some(OverlayCommand::Transcribe(args)) = config.command.as_ref() {
+        return run_transcribe(&config, args);
+    }
+    if let Some(OverlayCommand::Capture(args)) = config.command.as_ref() {
+        // Scripts branch on the exit code, so it is set directly instead of via Err.
+        std::process::exit(run_capture(&config, args, &user_config));
+    }
+    if config.pipe || config.once {
+        return run_pipe(&config, &user_config);
+    }
+    let _tty_lock = if config.allow_same_tty {
+        None
+    } else {
+        TtyLock::acquire()?
+    };
+    let (mut key_bindings, keybinding_warnings) =
+        KeyBindings::from_config(&user_config.keybindings, &backend_label)?;
+    for warning in &keybinding_warnings {
+        eprintln!("voiceterm: warning: {warning}");
+        log_debug(&format!("keybinding warning: {warning}"));
+    }
+    let postprocessor = TranscriptPostProcessor::from_config(&user_config.postprocess)?;
+    if !postprocessor.is_empty() {
+        log_debug(&format!(
+            "transcript post-processing: {} rules",
+            postprocessor.rule_count()
+        ));
+    }
+    if let Some(vocab) = ProjectVocabulary::discover(Path::new(&resolve_working_dir())) {
+        log_debug(&format!(
+            "vocabulary: {} terms from {}",
+            vocab.term_count(),
+            vocab.path().display()
+        ));
+        config.app.whisper_prompt = vocab.whisper_prompt(config.app.whisper_prompt.as_deref());
+    }
+    let feedback = TranscriptFeedback::from_config(&config).unwrap_or_else(|err| {
+        log_debug(&format!("transcript feedback unavailable: {err:#}"));
+        TranscriptFeedback::default()
+    });
+    if feedback.flagged_count() > 0 {
+        log_debug(&format!(
+            "transcript feedback: {} flagged, {} learned replacements",
+            feedback.flagged_count(),
+            feedback.replacement_count()
+        ));
+    }
+    config.app.whisper_prompt = feedback.whisper_prompt();
+    let urgent = UrgentKeywords::from_config(&user_config.urgent)?;
+    if !urgent.is_empty() {
+        log_debug(&format!(
+            "urgent keywords: {} (confirm={})",
+            urgent.keyword_count(),
+            urgent.requires_confirm()
+        ));
+    }
+    let secrets = SecretsFilter::from_config(&user_config.secrets)?;
+    log_debug(&format!("secrets patterns: {}", secrets.pattern_count()));
+    let translator = TranscriptTranslator::from_config(&config)?;
+    if translator.is_enabled() {
+        log_debug(&format!(
+            "translating transcripts to {}",
+            translator.target()
+        ));
+    }
+    let redictation = Redictation::from_config(&config)?;
+    let queue_policy = QueuePolicy::from_config(&config)?;
+    let uncertain_words = UncertainWordMarker::from_config(&config)?;
+    // A busy socket path should not keep the session from starting.
+    let control = ControlServer::from_config(&config).unwrap_or_else(|err| {
+        eprintln!("voiceterm: warning: control socket disabled: {err:#}");
+        log_debug(&format!("control socket disabled: {err:#}"));
+        None
+    });
+    if let Some(control) = control.as_ref() {
+        log_debug(&format!("control socket: {}", control.path().display()));
+    }
+    let web_ui = WebUiServer::from_config(&config).unwrap_or_else(|err| {
+        eprintln!("voiceterm: warning: web ui disabled: {err:#}");
+        log_debug(&format!("web ui disabled: {err:#}"));
+        None
+    });
+    if let Some(web_ui) = web_ui.as_ref() {
+        log_debug(&format!("web ui: {}", web_ui.url()));
+    }
+    let ws_bridge = WsBridge::from_config(&config).unwrap_or_else(|err| {
+        eprintln!("voiceterm: warning: websocket bridge disabled: {err:#}");
+        log_debug(&format!("websocket bridge disabled: {err:#}"));
+        None
+    });
+    if let Some(ws_bridge) = ws_bridge.as_ref() {
+        log_debug(&format!("websocket bridge: {}", ws_bridge.url()));
+    }
+    let type_delay = resolve_type_delay(&config, &user_config.backends, &backend_label)?;
+    if let Some(delay) = type_delay {
+        log_debug(&format!(
+            "typing transcripts {}ms per key",
+            delay.as_millis()
+        ));
+    }
+    let transcript_targets = TranscriptTargets::from_config(&config, &backend_label)?
+        .with_type_delay(type_delay)
+        .with_echo_check(config.echo_check_ms.map(Duration::from_millis));
+    for pane_id in transcript_targets.pane_ids() {
+        log_debug(&format!("transcript target: tmux pane {pane_id}"));
+    }
+    if !transcript_targets.is_switchable() {
+        key_bindings.release(OverlayAction::CycleTarget);
+    }
+
+    let prompt_replay = match config.command.as_ref() {
+        Some(OverlayCommand::ReplayPrompts(args)) => {
+            let replay = PromptReplay::from_args(args, Instant::now())?;
+            log_debug(&format!(
+                "prompt replay: {} prompts from {}",
+                replay.remaining(),
+                args.file.display()
+            ));
+            Some(replay)
+        }
+        _ => None,
+    };
+
+    if let Some(path) = config.session_record.as_deref() {
+        start_session_recording(
+            path,
+            SessionEvent::SessionStart {
+                backend: backend_label.clone(),
+                voice_send_mode: send_mode_key(config.voice_send_mode).to_string(),
+                auto_voice_idle_ms: config.auto_voice_idle_ms,
+                transcript_idle_ms: config.transcript_idle_ms,
+            },
+        )?;
+        log_debug(&format!("session recording: {}", path.display()));
+    }
+    subscribe_session_events(Box::new(SessionEventMetrics::new()));
+    if let Some(path) = config.transcript_history.clone() {
+        subscribe_session_events(Box::new(
+            TranscriptHistoryWriter::new(path)
+                .with_encrypt_cmd(config.transcript_history_encrypt_cmd.clone()),
+        ));
+    }
+    if let Some(control) = control.as_ref() {
+        subscribe_session_events(Box::new(control.session_events()));
+    }
+    if let Some(ws_bridge) = ws_bridge.as_ref() {
+        subscribe_session_events(Box::new(ws_bridge.session_events()));
+    }
+    if let Some(raw) = config.session_hook.as_deref() {
+        let hook = SessionHook::spawn(raw)?;
+        log_debug(&format!("session hook started: {}", hook.program()));
+        subscribe_session_events(Box::new(hook));
+    }
 
     if config.login {
         log_debug(&format!("Running login for backend: {}", backend.label));
         run_login_command(&backend.command)
             .map_err(|err| anyhow::anyhow!("{} login failed: {err}", backend.label))?;
+        startup.lap("login", Instant::now());
     }
 
     install_sigwinch_handler()?;
 
-    let working_dir = env::var("VOICETERM_CWD")
-        .ok()
-        .or_else(|| {
-            env::current_dir()
-                .ok()
-                .map(|dir| dir.to_string_lossy().to_string())
-        })
-        .unwrap_or_else(|| ".".to_string());
+    let working_dir = resolve_working_dir();
     let voice_macros = VoiceMacros::load_for_project(Path::new(&working_dir));
     if let Some(path) = voice_macros.source_path() {
         log_debug(&format!(
@@ -223,6 +525,11 @@ fn main() -> Result<()> {
             voice_macros.len()
         ));
     }
+    let prefix =
+        TranscriptPrefix::from_config(&user_config.project, &project_root(Path::new(&working_dir)));
+    if let Some(prefix) = prefix.prefix() {
+        log_debug(&format!("transcript prefix: {prefix:?}"));
+    }
 
     // Backend command and args already resolved
 
@@ -232,48 +539,83 @@ fn main() -> Result<()> {
         resolve_prompt_log(&config)
     };
     let prompt_logger = PromptLogger::new(prompt_log_path);
-    let prompt_regex = resolve_prompt_regex(&config, backend.prompt_pattern.as_deref())?;
-    let prompt_tracker = PromptTracker::new(
-        prompt_regex.regex,
-        prompt_regex.allow_auto_learn,
+    let prompt_regex = resolve_prompt_regex(
+        &config,
+        &user_config.prompt,
+        backend.prompt_pattern.as_deref(),
+    )?;
+    // A detector knows the REPL better than prompt learning, so learning is off when one is set.
+    let mut prompt_tracker = PromptTracker::new(
+        prompt_regex.patterns,
+        prompt_regex.allow_auto_learn && config.prompt_detector_cmd.is_none(),
         prompt_logger,
-    );
+    )
+    .with_never_patterns(prompt_regex.never_patterns);
+    if let Some(raw) = config.prompt_detector_cmd.as_deref() {
+        let detector = ExternalPromptDetector::spawn(raw)?;
+        log_debug(&format!("prompt detector started: {}", detector.program()));
+        prompt_tracker = prompt_tracker.with_detector(detector);
+    }
+
+    // The model load and device probe do not depend on the splash or the PTY, so
+    // they run in the background while those finish.
+    let mut voice_manager = VoiceManager::new(config.app.clone());
+    voice_manager.watch_devices(user_config.devices.clone());
+    voice_manager.start_preload();
+    startup.lap("setup", Instant::now());
 
     let banner_config = BannerConfig {
         auto_voice: config.auto_voice,
         theme: theme.to_string(),
         pipeline: "Rust".to_string(),
+        stt_device: config.app.stt_device.label(),
         sensitivity_db: config.app.voice_vad_threshold_db,
         backend: backend.label.clone(),
     };
     let no_startup_banner = env::var("VOICETERM_NO_STARTUP_BANNER").is_ok();
-    let skip_banner = should_skip_banner(no_startup_banner);
+    let plain_render = config.render_mode.is_plain();
+    let skip_banner = should_skip_banner(no_startup_banner) || plain_render;
 
     if !skip_banner {
         show_startup_splash(&banner_config, theme)?;
+        startup.lap("splash", Instant::now());
     }
 
     let terminal_guard = TerminalRestoreGuard::new();
     terminal_guard.enable_raw_mode()?;
 
-    let mut session = PtyOverlaySession::new(
+    let launcher = BackendLauncher::new(
         &backend.command,
-        &working_dir,
         &backend.args,
+        &working_dir,
         &config.app.term_value,
-    )?;
+    );
+    let mut session = launcher.spawn()?;
+    log_debug(&format!("backend pid: {}", session.child_pid()));
+    emit_session_event(|| VoiceSessionEvent::SessionStarted {
+        backend: backend_label.clone(),
+    });
+    startup.lap("pty_spawn", Instant::now());
 
     let (writer_tx, writer_rx) = bounded(WRITER_CHANNEL_CAPACITY);
     let _writer_handle = spawn_writer_thread(writer_rx);
 
     // Set the color theme for the status line
     let _ = writer_tx.send(WriterMessage::SetTheme(theme));
+    log_debug(&format!("render mode: {}", config.render_mode.describe()));
+    if plain_render {
+        let _ = writer_tx.send(WriterMessage::SetPlainStatus(true));
+    } else if !config.no_scroll_region {
+        let _ = writer_tx.send(WriterMessage::SetScrollRegion(true));
+    }
 
     // Button registry for tracking clickable button positions (mouse is on by default)
     let button_registry = ButtonRegistry::new();
 
     // Compute initial HUD style (handle --minimal-hud shorthand)
-    let initial_hud_style = if config.minimal_hud {
+    let initial_hud_style = if plain_render {
+        HudStyle::Hidden
+    } else if config.minimal_hud {
         HudStyle::Minimal
     } else {
         config.hud_style
@@ -295,21 +637,24 @@ fn main() -> Result<()> {
     }
 
     let (input_tx, input_rx) = bounded(INPUT_CHANNEL_CAPACITY);
-    let _input_handle = spawn_input_thread(input_tx);
+    let voice_navigation = VoiceNavigation::new(config.voice_navigation, input_tx.clone());
+    let kitty_keyboard = KittyKeyboard::default();
+    let _input_handle = spawn_input_thread(input_tx, key_bindings, kitty_keyboard.clone());
 
     let auto_idle_timeout = Duration::from_millis(config.auto_voice_idle_ms.max(100));
     let transcript_idle_timeout = Duration::from_millis(config.transcript_idle_ms.max(50));
     let hud_registry = HudRegistry::with_defaults();
     let meter_update_ms = resolved_meter_update_ms(&hud_registry);
-    let voice_manager = VoiceManager::new(config.app.clone());
     let live_meter = voice_manager.meter();
     let auto_voice_enabled = config.auto_voice;
     let mut status_state = StatusLineState::new();
     status_state.sensitivity_db = config.app.voice_vad_threshold_db;
     status_state.auto_voice_enabled = auto_voice_enabled;
     status_state.send_mode = config.voice_send_mode;
+    status_state.transcript_target = transcript_targets.status_label();
     status_state.latency_display = config.latency_display;
     status_state.macros_enabled = true;
+    status_state.code_mode = config.code_mode;
     status_state.hud_right_panel = config.hud_right_panel;
     status_state.hud_border_style = config.hud_border_style;
     status_state.hud_right_panel_recording_only = config.hud_right_panel_recording_only;
@@ -320,12 +665,26 @@ fn main() -> Result<()> {
         VoiceMode::Manual
     };
     status_state.pipeline = Pipeline::Rust;
-    status_state.mouse_enabled = true; // Mouse enabled by default for clickable buttons
-    let _ = writer_tx.send(WriterMessage::EnableMouse);
+    // Mouse is on by default for clickable buttons, except where the HUD is not drawn.
+    status_state.mouse_enabled = !plain_render;
+    if !plain_render {
+        let _ = writer_tx.send(WriterMessage::EnableMouse);
+    }
+    let mut multiline = MultilineDelivery::new(config.multiline_mode, &backend_label)
+        .with_bracketed_paste(config.bracketed_paste);
+    multiline.set_backend_target(!transcript_targets.pane_active());
+    let dictation = config.dictation_buffer.then(DictationBuffer::default);
+    let batch_window = BatchWindow::from_ms(config.batch_window_ms);
+    let output_preview = OutputPreview::new(!config.no_output_preview);
+    if let Some(quiet_hours) = config.auto_voice_quiet_hours {
+        log_debug(&format!("auto-voice quiet hours: {quiet_hours}"));
+    }
+    let auto_voice_dnd = AutoVoiceDnd::new(config.auto_voice_quiet_hours);
     let mut state = EventLoopState {
         config,
         status_state,
         auto_voice_enabled,
+        auto_voice_dnd,
         theme,
         overlay_mode: OverlayMode::None,
         settings_menu: SettingsMenuState::new(),
@@ -334,8 +693,19 @@ fn main() -> Result<()> {
         theme_picker_digits: String::new(),
         current_status: None,
         pending_transcripts: VecDeque::new(),
+        urgent_hold: None,
+        dictation,
+        batch_window,
+        redictation,
+        prompt_replay,
         session_stats: SessionStats::new(),
+        latency_overlay_revision: 0,
         prompt_tracker,
+        output_preview,
+        scrollback: Scrollback::default(),
+        search_query: String::new(),
+        queue_panel: QueuePanel::default(),
+        backend_modes: BackendModes::new(kitty_keyboard),
         terminal_rows,
         terminal_cols,
         last_recording_duration: 0.0_f32,
@@ -345,6 +715,8 @@ fn main() -> Result<()> {
         pending_pty_input_offset: 0,
         pending_pty_input_bytes: 0,
         suppress_startup_escape_input: true,
+        backend_exit: None,
+        multiline,
     };
     let mut timers = EventLoopTimers {
         theme_picker_digit_deadline: None,
@@ -357,9 +729,11 @@ fn main() -> Result<()> {
         last_processing_tick: Instant::now(),
         last_heartbeat_tick: Instant::now(),
         last_meter_update: Instant::now(),
+        backend_started_at: Instant::now(),
     };
     let mut deps = EventLoopDeps {
         session,
+        launcher,
         voice_manager,
         writer_tx,
         input_rx,
@@ -371,8 +745,22 @@ fn main() -> Result<()> {
         meter_update_ms,
         auto_idle_timeout,
         transcript_idle_timeout,
+        queue_policy,
         voice_macros,
+        postprocessor,
+        feedback,
+        uncertain_words,
+        control,
+        web_ui,
+        ws_bridge,
+        urgent,
+        secrets,
+        translator,
+        prefix,
+        transcript_targets,
+        voice_navigation,
     };
+    startup.lap("ui_init", Instant::now());
 
     if state.auto_voice_enabled {
         set_status(
@@ -404,6 +792,18 @@ fn main() -> Result<()> {
                 );
             }
         }
+        // Starting a capture waits for any model load still in flight.
+        startup.lap("auto_voice", Instant::now());
+    }
+    if deps.voice_manager.preload_pending() {
+        set_status(
+            &deps.writer_tx,
+            &mut timers.status_clear_deadline,
+            &mut state.current_status,
+            &mut state.status_state,
+            MODEL_WARMUP_STATUS,
+            None,
+        );
     }
 
     // Ensure the HUD/launcher is visible immediately, before any user input arrives.
@@ -417,15 +817,37 @@ fn main() -> Result<()> {
     );
 
     run_event_loop(&mut state, &mut timers, &mut deps);
+    emit_session_event(|| VoiceSessionEvent::SessionEnded);
+    close_session_events();
 
     let _ = deps.writer_tx.send(WriterMessage::ClearStatus);
     let _ = deps.writer_tx.send(WriterMessage::Shutdown);
     terminal_guard.restore();
+    // A backend that exited mid-session can leave mouse reports or escape-coded keys on.
+    let mode_reset = state.backend_modes.reset_sequence();
+    if !mode_reset.is_empty() {
+        let mut stdout = io::stdout();
+        let _ = stdout.write_all(&mode_reset);
+        let _ = stdout.flush();
+    }
     let stats_output = format_session_stats(&state.session_stats, state.theme);
     if should_print_stats(&stats_output) {
         print!("{stats_output}");
         let _ = io::stdout().flush();
     }
+    if state.config.startup_profile {
+        for timing in deps.voice_manager.preload_timings() {
+            startup.background(timing.phase, timing.elapsed);
+        }
+        startup.log();
+        print!("{}", startup.report());
+        let _ = io::stdout().flush();
+    }
+    let pty_stats = deps.session.stats();
+    log_debug(&format!(
+        "pty stats: read {} bytes in {} chunks, wrote {} bytes, {} resizes",
+        pty_stats.bytes_read, pty_stats.read_chunks, pty_stats.bytes_written, pty_stats.resizes
+    ));
     log_debug("=== VoiceTerm Overlay Exiting ===");
     Ok(())
 }
@@ -0,0 +1,12 @@
+//! MCP (Model Context Protocol) server so agents can ask the user for voice input.
+//!
+//! `voiceterm mcp` reads newline-delimited JSON-RPC from stdin and answers on
+//! stdout, offering `capture_voice` (record until silence and return the
+//! transcript), `get_last_transcript`, and `speak` (read text aloud). Like the
+//! daemon it needs no PTY; the agent framework starts it as a tool server.
+
+mod protocol;
+mod server;
+mod speech;
+
+pub(crate) use server::run_mcp;
@@ -0,0 +1,76 @@
+//! JSON-RPC 2.0 envelopes and tool definitions for the MCP server.
+
+use serde_json::{json, Value};
+
+/// Protocol revisions this server speaks, newest first.
+const SUPPORTED_PROTOCOL_VERSIONS: [&str; 3] = ["2025-06-18", "2025-03-26", "2024-11-05"];
+
+pub(super) const PARSE_ERROR: i64 = -32700;
+pub(super) const INVALID_REQUEST: i64 = -32600;
+pub(super) const METHOD_NOT_FOUND: i64 = -32601;
+pub(super) const INVALID_PARAMS: i64 = -32602;
+
+pub(super) fn result_response(id: Value, result: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
+
+pub(super) fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+/// `initialize` result; echoes the client's protocol version when it is one we speak.
+pub(super) fn initialize_result(params: &Value) -> Value {
+    let requested = params.get("protocolVersion").and_then(Value::as_str);
+    let version = requested
+        .filter(|version| SUPPORTED_PROTOCOL_VERSIONS.contains(version))
+        .unwrap_or(SUPPORTED_PROTOCOL_VERSIONS[0]);
+    json!({
+        "protocolVersion": version,
+        "capabilities": { "tools": {} },
+        "serverInfo": { "name": "voiceterm", "version": env!("CARGO_PKG_VERSION") },
+    })
+}
+
+/// Result of a `tools/call`; tool failures are reported in-band so the agent can read them.
+pub(super) fn tool_result(text: &str, is_error: bool) -> Value {
+    json!({
+        "content": [{ "type": "text", "text": text }],
+        "isError": is_error,
+    })
+}
+
+/// `tools/list` entries.
+pub(super) fn tool_definitions() -> Value {
+    json!([
+        {
+            "name": "capture_voice",
+            "description": "Record the user's voice until they stop speaking and return the transcript. Use it to ask the user a question out loud and get a spoken answer.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "timeout_secs": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "description": "Stop recording after this many seconds even if the user is still talking."
+                    }
+                }
+            }
+        },
+        {
+            "name": "get_last_transcript",
+            "description": "Return the transcript from the most recent capture_voice call.",
+            "inputSchema": { "type": "object", "properties": {} }
+        },
+        {
+            "name": "speak",
+            "description": "Read text aloud to the user with the system speech synthesizer. Returns once speaking has finished.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "text": { "type": "string", "description": "What to say." }
+                },
+                "required": ["text"]
+            }
+        }
+    ])
+}
@@ -0,0 +1,351 @@
+//! MCP stdio loop and the voice tools behind it.
+
+use anyhow::{anyhow, bail, Result};
+use serde_json::{json, Value};
+use std::io::{self, BufRead, Write};
+use std::thread;
+use std::time::{Duration, Instant};
+use voiceterm::{log_info, VoiceCaptureTrigger, VoiceJobMessage};
+
+use crate::config::{McpArgs, OverlayConfig, UserConfig};
+use crate::transcript::TranscriptPostProcessor;
+use crate::voice_control::VoiceManager;
+
+use super::protocol::{
+    error_response, initialize_result, result_response, tool_definitions, tool_result,
+    INVALID_PARAMS, INVALID_REQUEST, METHOD_NOT_FOUND, PARSE_ERROR,
+};
+use super::speech::speak;
+
+/// How often a capture polls the voice worker.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Voice operations behind the tools, so request handling can be tested without audio.
+trait VoiceTools {
+    /// Record until silence or `timeout`; `Ok(None)` means no speech was heard.
+    fn capture(&mut self, timeout: Option<Duration>) -> Result<Option<String>>;
+    /// Say `text` aloud and return the speech command used.
+    fn speak(&mut self, text: &str) -> Result<&'static str>;
+}
+
+/// The local microphone, Whisper model, and speech command.
+struct LocalVoice {
+    manager: VoiceManager,
+    postprocessor: TranscriptPostProcessor,
+    default_timeout: Duration,
+}
+
+impl VoiceTools for LocalVoice {
+    fn capture(&mut self, timeout: Option<Duration>) -> Result<Option<String>> {
+        if !self
+            .manager
+            .start_headless_capture(VoiceCaptureTrigger::Manual)?
+        {
+            bail!("a capture is already running");
+        }
+        let deadline = Instant::now() + timeout.unwrap_or(self.default_timeout);
+        let mut stop_requested = false;
+        loop {
+            let _ = self.manager.poll_preload();
+            if let Some(message) = self.manager.poll_message() {
+                return match message {
+                    VoiceJobMessage::Transcript { text, .. } => {
+                        let text = self.postprocessor.apply(text.trim());
+                        Ok((!text.is_empty()).then_some(text))
+                    }
+                    VoiceJobMessage::Empty { .. } => Ok(None),
+                    VoiceJobMessage::Error(err) => Err(anyhow!(err.message)),
+                };
+            }
+            if !stop_requested && Instant::now() >= deadline {
+                self.manager.request_early_stop();
+                stop_requested = true;
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    fn speak(&mut self, text: &str) -> Result<&'static str> {
+        speak(text)
+    }
+}
+
+/// Session state shared across requests.
+#[derive(Default)]
+struct McpServer {
+    last_transcript: Option<String>,
+}
+
+impl McpServer {
+    /// Answer one JSON-RPC line; notifications and blank lines get no reply.
+    fn handle_line(&mut self, line: &str, tools: &mut impl VoiceTools) -> Option<Value> {
+        if line.trim().is_empty() {
+            return None;
+        }
+        let request: Value = match serde_json::from_str(line) {
+            Ok(request) => request,
+            Err(err) => {
+                return Some(error_response(
+                    Value::Null,
+                    PARSE_ERROR,
+                    &format!("parse error: {err}"),
+                ))
+            }
+        };
+        // Requests without an id are notifications (e.g. `notifications/initialized`).
+        let id = request.get("id").cloned()?;
+        let Some(method) = request.get("method").and_then(Value::as_str) else {
+            return Some(error_response(id, INVALID_REQUEST, "missing method"));
+        };
+        let params = request.get("params").cloned().unwrap_or(Value::Null);
+        let result = match method {
+            "initialize" => Ok(initialize_result(&params)),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(json!({ "tools": tool_definitions() })),
+            "tools/call" => self.call_tool(&params, tools),
+            other => Err((METHOD_NOT_FOUND, format!("unknown method {other}"))),
+        };
+        Some(match result {
+            Ok(result) => result_response(id, result),
+            Err((code, message)) => error_response(id, code, &message),
+        })
+    }
+
+    fn call_tool(
+        &mut self,
+        params: &Value,
+        tools: &mut impl VoiceTools,
+    ) -> Result<Value, (i64, String)> {
+        let arguments = params.get("arguments").unwrap_or(&Value::Null);
+        match params.get("name").and_then(Value::as_str) {
+            Some("capture_voice") => {
+                let timeout = arguments
+                    .get("timeout_secs")
+                    .and_then(Value::as_u64)
+                    .filter(|secs| *secs > 0)
+                    .map(Duration::from_secs);
+                Ok(match tools.capture(timeout) {
+                    Ok(Some(text)) => {
+                        log_info("mcp capture_voice returned a transcript");
+                        self.last_transcript = Some(text.clone());
+                        tool_result(&text, false)
+                    }
+                    Ok(None) => tool_result("No speech detected", true),
+                    Err(err) => tool_result(&format!("Voice capture failed: {err:#}"), true),
+                })
+            }
+            Some("get_last_transcript") => Ok(match &self.last_transcript {
+                Some(text) => tool_result(text, false),
+                None => tool_result("No transcript yet; call capture_voice first", true),
+            }),
+            Some("speak") => {
+                let text = arguments
+                    .get("text")
+                    .and_then(Value::as_str)
+                    .map(str::trim)
+                    .filter(|text| !text.is_empty())
+                    .ok_or((INVALID_PARAMS, "speak needs non-empty `text`".to_string()))?;
+                Ok(match tools.speak(text) {
+                    Ok(program) => tool_result(
+                        &format!("Spoke {} characters with {program}", text.chars().count()),
+                        false,
+                    ),
+                    Err(err) => tool_result(&format!("Speaking failed: {err:#}"), true),
+                })
+            }
+            Some(other) => Err((INVALID_PARAMS, format!("unknown tool {other}"))),
+            None => Err((INVALID_PARAMS, "tools/call needs a tool `name`".to_string())),
+        }
+    }
+}
+
+/// Run `voiceterm mcp`, answering requests on stdin until it closes.
+pub(crate) fn run_mcp(
+    config: &OverlayConfig,
+    args: &McpArgs,
+    user_config: &UserConfig,
+) -> Result<()> {
+    let postprocessor = TranscriptPostProcessor::from_config(&user_config.postprocess)?;
+    let mut manager = VoiceManager::new(config.app.clone());
+    manager.watch_devices(user_config.devices.clone());
+    // Without --warm-up the model loads on the first capture_voice call.
+    if config.app.warm_up {
+        manager.start_preload();
+    }
+    let mut tools = LocalVoice {
+        manager,
+        postprocessor,
+        default_timeout: Duration::from_secs(args.capture_timeout_secs),
+    };
+    let mut server = McpServer::default();
+    log_info("mcp server started on stdio");
+    let mut stdout = io::stdout().lock();
+    for line in io::stdin().lock().lines() {
+        if let Some(reply) = server.handle_line(&line?, &mut tools) {
+            writeln!(stdout, "{reply}")?;
+            stdout.flush()?;
+        }
+    }
+    tools.manager.cancel_capture();
+    log_info("mcp server stdin closed");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct FakeVoice {
+        transcript: Option<String>,
+        timeouts: Vec<Option<Duration>>,
+        spoken: Vec<String>,
+    }
+
+    impl VoiceTools for FakeVoice {
+        fn capture(&mut self, timeout: Option<Duration>) -> Result<Option<String>> {
+            self.timeouts.push(timeout);
+            Ok(self.transcript.clone())
+        }
+
+        fn speak(&mut self, text: &str) -> Result<&'static str> {
+            self.spoken.push(text.to_string());
+            Ok("espeak-ng")
+        }
+    }
+
+    fn call(server: &mut McpServer, voice: &mut FakeVoice, request: Value) -> Value {
+        server
+            .handle_line(&request.to_string(), voice)
+            .expect("reply")
+    }
+
+    fn tool_call(name: &str, arguments: Value) -> Value {
+        json!({
+            "jsonrpc": "2.0",
+            "id": 7,
+            "method": "tools/call",
+            "params": { "name": name, "arguments": arguments }
+        })
+    }
+
+    #[test]
+    fn mcp_subcommand_parses_capture_timeout() {
+        use crate::config::OverlayCommand;
+        use clap::Parser;
+
+        let config =
+            OverlayConfig::parse_from(["voiceterm", "mcp", "--capture-timeout-secs", "30"]);
+        let Some(OverlayCommand::Mcp(args)) = config.command else {
+            panic!("expected mcp subcommand");
+        };
+        assert_eq!(args.capture_timeout_secs, 30);
+    }
+
+    #[test]
+    fn initialize_and_tools_list_describe_the_server() {
+        let mut server = McpServer::default();
+        let mut voice = FakeVoice::default();
+        let reply = call(
+            &mut server,
+            &mut voice,
+            json!({"jsonrpc": "2.0", "id": 1, "method": "initialize",
+                   "params": {"protocolVersion": "2024-11-05", "capabilities": {}}}),
+        );
+        assert_eq!(reply["id"], 1);
+        assert_eq!(reply["result"]["protocolVersion"], "2024-11-05");
+        assert_eq!(reply["result"]["serverInfo"]["name"], "voiceterm");
+
+        let initialized = json!({"jsonrpc": "2.0", "method": "notifications/initialized"});
+        assert!(server
+            .handle_line(&initialized.to_string(), &mut voice)
+            .is_none());
+
+        let reply = call(
+            &mut server,
+            &mut voice,
+            json!({"jsonrpc": "2.0", "id": "list", "method": "tools/list"}),
+        );
+        let names: Vec<&str> = reply["result"]["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|tool| tool["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, ["capture_voice", "get_last_transcript", "speak"]);
+    }
+
+    #[test]
+    fn capture_voice_result_is_kept_for_get_last_transcript() {
+        let mut server = McpServer::default();
+        let mut voice = FakeVoice::default();
+        let reply = call(
+            &mut server,
+            &mut voice,
+            tool_call("get_last_transcript", json!({})),
+        );
+        assert_eq!(reply["result"]["isError"], true);
+
+        voice.transcript = Some("use the staging database".to_string());
+        let reply = call(
+            &mut server,
+            &mut voice,
+            tool_call("capture_voice", json!({"timeout_secs": 20})),
+        );
+        assert_eq!(reply["result"]["isError"], false);
+        assert_eq!(
+            reply["result"]["content"][0]["text"],
+            "use the staging database"
+        );
+        assert_eq!(voice.timeouts, [Some(Duration::from_secs(20))]);
+
+        let reply = call(
+            &mut server,
+            &mut voice,
+            tool_call("get_last_transcript", json!({})),
+        );
+        assert_eq!(
+            reply["result"]["content"][0]["text"],
+            "use the staging database"
+        );
+
+        voice.transcript = None;
+        let reply = call(
+            &mut server,
+            &mut voice,
+            tool_call("capture_voice", json!({})),
+        );
+        assert_eq!(reply["result"]["isError"], true);
+        assert_eq!(reply["result"]["content"][0]["text"], "No speech detected");
+    }
+
+    #[test]
+    fn speak_requires_text_and_bad_requests_get_errors() {
+        let mut server = McpServer::default();
+        let mut voice = FakeVoice::default();
+        let reply = call(
+            &mut server,
+            &mut voice,
+            tool_call("speak", json!({"text": " Tests passed. "})),
+        );
+        assert_eq!(
+            reply["result"]["content"][0]["text"],
+            "Spoke 13 characters with espeak-ng"
+        );
+        assert_eq!(voice.spoken, ["Tests passed."]);
+
+        let reply = call(&mut server, &mut voice, tool_call("speak", json!({})));
+        assert_eq!(reply["error"]["code"], INVALID_PARAMS);
+        let reply = call(&mut server, &mut voice, tool_call("dance", json!({})));
+        assert_eq!(reply["error"]["code"], INVALID_PARAMS);
+        let reply = call(
+            &mut server,
+            &mut voice,
+            json!({"jsonrpc": "2.0", "id": 3, "method": "resources/list"}),
+        );
+        assert_eq!(reply["error"]["code"], METHOD_NOT_FOUND);
+        let reply = server.handle_line("{not json", &mut voice).unwrap();
+        assert_eq!(reply["error"]["code"], PARSE_ERROR);
+        assert!(reply["id"].is_null());
+    }
+}
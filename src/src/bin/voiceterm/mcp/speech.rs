@@ -0,0 +1,73 @@
+//! Text-to-speech for the `speak` tool through the platform speech command.
+//!
+//! Uses `say` on macOS and `espeak-ng`, `espeak`, or `spd-say` elsewhere. Text
+//! is written to the command's stdin so it is never read as a flag.
+
+use anyhow::{bail, Context, Result};
+use std::io::{ErrorKind, Write};
+use std::process::{Command, Stdio};
+use voiceterm::audio::PlaybackGuard;
+
+/// Speech commands to try, in order, for the current platform.
+fn speech_commands(macos: bool) -> Vec<(&'static str, &'static [&'static str])> {
+    let mut commands: Vec<(&'static str, &'static [&'static str])> = Vec::new();
+    if macos {
+        commands.push(("say", &["-f", "-"]));
+    }
+    commands.push(("espeak-ng", &["--stdin"]));
+    commands.push(("espeak", &["--stdin"]));
+    // `-w` waits until speech finishes; `-e` reads the text from stdin.
+    commands.push(("spd-say", &["-w", "-e"]));
+    commands
+}
+
+/// Speak `text` with the first available speech command and return its name.
+///
+/// Blocks until speaking finishes, so the agent hears back only afterwards.
+pub(crate) fn speak(text: &str) -> Result<&'static str> {
+    for (program, args) in speech_commands(cfg!(target_os = "macos")) {
+        let mut child = match Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        {
+            Ok(child) => child,
+            Err(err) if err.kind() == ErrorKind::NotFound => continue,
+            Err(err) => return Err(err).with_context(|| format!("failed to start {program}")),
+        };
+        // Keeps the spoken reply out of any capture running meanwhile (`--echo-mode`).
+        let _playback = PlaybackGuard::start();
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(text.as_bytes())
+                .with_context(|| format!("failed to write to {program}"))?;
+        }
+        let status = child
+            .wait()
+            .with_context(|| format!("failed to wait for {program}"))?;
+        if !status.success() {
+            bail!("{program} exited with {status}");
+        }
+        return Ok(program);
+    }
+    bail!("no speech command found (install espeak-ng or speech-dispatcher)")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn speech_commands_prefer_say_on_macos() {
+        let names = |macos| -> Vec<&str> {
+            speech_commands(macos)
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect()
+        };
+        assert_eq!(names(true), vec!["say", "espeak-ng", "espeak", "spd-say"]);
+        assert_eq!(names(false), vec!["espeak-ng", "espeak", "spd-say"]);
+    }
+}
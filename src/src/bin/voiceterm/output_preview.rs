@@ -0,0 +1,154 @@
+//! Rolling snippet of backend output for the status line during long answers.
+//!
+//! Once the backend has been writing for [`STREAM_DELAY`], the idle status lane
+//! shows its last completed output line (truncated) instead of `Ready`, so a
+//! long answer's progress is visible without scrolling. The snippet refreshes
+//! at most every [`REFRESH_INTERVAL`] and disappears once output has been quiet
+//! for [`QUIET_TIMEOUT`]. `--no-output-preview` turns it off.
+
+use std::time::{Duration, Instant};
+
+use crate::prompt::PromptTracker;
+use crate::status_line::StatusLineState;
+
+/// Output must keep arriving this long before it counts as a long answer.
+const STREAM_DELAY: Duration = Duration::from_secs(1);
+/// Minimum time between snippet redraws while output streams.
+const REFRESH_INTERVAL: Duration = Duration::from_millis(250);
+/// Quiet time after which the answer is treated as finished.
+const QUIET_TIMEOUT: Duration = Duration::from_secs(2);
+/// Longest snippet kept, in characters; the HUD truncates further to fit.
+const SNIPPET_MAX_CHARS: usize = 80;
+
+#[derive(Debug, Default)]
+pub(crate) struct OutputPreview {
+    enabled: bool,
+    /// When the current burst of output started.
+    streaming_since: Option<Instant>,
+    last_refresh: Option<Instant>,
+}
+
+impl OutputPreview {
+    pub(crate) fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            ..Self::default()
+        }
+    }
+
+    /// Note PTY output at `now`; returns true when the status line needs a redraw.
+    pub(crate) fn observe(
+        &mut self,
+        tracker: &PromptTracker,
+        status_state: &mut StatusLineState,
+        now: Instant,
+    ) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        let since = *self.streaming_since.get_or_insert(now);
+        if now.duration_since(since) < STREAM_DELAY
+            || self
+                .last_refresh
+                .is_some_and(|last| now.duration_since(last) < REFRESH_INTERVAL)
+        {
+            return false;
+        }
+        let snippet = tracker.last_line().and_then(format_snippet);
+        if snippet.is_none() || snippet == status_state.output_snippet {
+            return false;
+        }
+        self.last_refresh = Some(now);
+        status_state.output_snippet = snippet;
+        true
+    }
+
+    /// Drop the snippet once output has gone quiet; returns true when it was cleared.
+    pub(crate) fn expire(
+        &mut self,
+        tracker: &PromptTracker,
+        status_state: &mut StatusLineState,
+        now: Instant,
+    ) -> bool {
+        if self.streaming_since.is_none() {
+            return false;
+        }
+        let streaming = tracker
+            .last_pty_output_at()
+            .is_some_and(|last| now.duration_since(last) < QUIET_TIMEOUT);
+        if streaming {
+            return false;
+        }
+        self.streaming_since = None;
+        self.last_refresh = None;
+        status_state.output_snippet.take().is_some()
+    }
+}
+
+/// Collapse whitespace and cap length; `None` for blank lines.
+fn format_snippet(line: &str) -> Option<String> {
+    let collapsed = line.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.is_empty() {
+        return None;
+    }
+    if collapsed.chars().count() <= SNIPPET_MAX_CHARS {
+        return Some(collapsed);
+    }
+    let mut snippet: String = collapsed.chars().take(SNIPPET_MAX_CHARS - 1).collect();
+    snippet.push('…');
+    Some(snippet)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prompt::PromptLogger;
+
+    #[test]
+    fn snippet_appears_during_long_output_and_clears_when_quiet() {
+        let mut tracker = PromptTracker::new(Vec::new(), false, PromptLogger::new(None));
+        let mut status_state = StatusLineState::new();
+        let mut preview = OutputPreview::new(true);
+        let start = Instant::now();
+
+        tracker.feed_output_at(b"Reading   src/main.rs\n", start);
+        assert!(!preview.observe(&tracker, &mut status_state, start));
+        assert_eq!(status_state.output_snippet, None);
+
+        let later = start + STREAM_DELAY;
+        tracker.feed_output_at(b"\x1b[1mWriting\x1b[0m tests\n", later);
+        assert!(preview.observe(&tracker, &mut status_state, later));
+        assert_eq!(
+            status_state.output_snippet.as_deref(),
+            Some("Writing tests")
+        );
+
+        // Refreshes are rate-limited while output keeps streaming.
+        tracker.feed_output_at(b"Done\n", later + Duration::from_millis(10));
+        assert!(!preview.observe(
+            &tracker,
+            &mut status_state,
+            later + Duration::from_millis(10)
+        ));
+
+        assert!(!preview.expire(&tracker, &mut status_state, later + Duration::from_secs(1)));
+        assert!(preview.expire(&tracker, &mut status_state, later + Duration::from_secs(3)));
+        assert_eq!(status_state.output_snippet, None);
+    }
+
+    #[test]
+    fn disabled_preview_and_long_lines() {
+        let mut tracker = PromptTracker::new(Vec::new(), false, PromptLogger::new(None));
+        let mut status_state = StatusLineState::new();
+        let mut preview = OutputPreview::new(false);
+        let start = Instant::now();
+        tracker.feed_output_at(b"output\n", start);
+        assert!(!preview.observe(&tracker, &mut status_state, start + STREAM_DELAY));
+        assert_eq!(status_state.output_snippet, None);
+
+        let snippet = format_snippet(&"word ".repeat(40)).unwrap();
+        assert_eq!(snippet.chars().count(), SNIPPET_MAX_CHARS);
+        assert!(snippet.ends_with('…'));
+        assert_eq!(format_snippet("   "), None);
+    }
+}
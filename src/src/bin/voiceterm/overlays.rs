@@ -1,15 +1,23 @@
 //! Help/settings overlay rendering so panel layout stays centralized and consistent.
 
+use std::collections::VecDeque;
+
 use crossbeam_channel::Sender;
 
 use crate::config::OverlayConfig;
 use crate::help::{format_help_overlay, help_overlay_height};
+use crate::latency_overlay::{format_latency_overlay, latency_overlay_height};
+use crate::queue_overlay::{format_queue_overlay, queue_overlay_height, QueuePanel};
+use crate::scrollback::Scrollback;
+use crate::search_overlay::{format_search_overlay, search_overlay_height};
 use crate::settings::{
     format_settings_overlay, settings_overlay_height, SettingsMenuState, SettingsView,
 };
 use crate::status_line::StatusLineState;
 use crate::theme::Theme;
 use crate::theme_picker::{format_theme_picker, theme_picker_height};
+use crate::transcript::PendingTranscript;
+use crate::voice_metrics::VoiceMetrics;
 use crate::writer::WriterMessage;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -18,6 +26,9 @@ pub(crate) enum OverlayMode {
     Help,
     ThemePicker,
     Settings,
+    Latency,
+    Search,
+    Queue,
 }
 
 pub(crate) fn show_settings_overlay(
@@ -34,7 +45,11 @@ pub(crate) fn show_settings_overlay(
         auto_voice_enabled: status_state.auto_voice_enabled,
         send_mode: config.voice_send_mode,
         macros_enabled: status_state.macros_enabled,
+        code_mode: status_state.code_mode,
         sensitivity_db: status_state.sensitivity_db,
+        silence_tail_ms: config.app.voice_silence_tail_ms,
+        vad_engine: config.app.voice_vad_engine,
+        whisper_model: config.app.whisper_model_path.as_deref(),
         theme,
         hud_style: status_state.hud_style,
         hud_border_style: config.hud_border_style,
@@ -67,6 +82,41 @@ pub(crate) fn show_help_overlay(writer_tx: &Sender<WriterMessage>, theme: Theme,
     let _ = writer_tx.send(WriterMessage::ShowOverlay { content, height });
 }
 
+pub(crate) fn show_latency_overlay(
+    writer_tx: &Sender<WriterMessage>,
+    theme: Theme,
+    cols: u16,
+    metrics: &VoiceMetrics,
+) {
+    let content = format_latency_overlay(metrics, theme, cols as usize);
+    let height = latency_overlay_height();
+    let _ = writer_tx.send(WriterMessage::ShowOverlay { content, height });
+}
+
+pub(crate) fn show_search_overlay(
+    writer_tx: &Sender<WriterMessage>,
+    theme: Theme,
+    cols: u16,
+    query: &str,
+    scrollback: &Scrollback,
+) {
+    let content = format_search_overlay(scrollback, query, theme, cols as usize);
+    let height = search_overlay_height();
+    let _ = writer_tx.send(WriterMessage::ShowOverlay { content, height });
+}
+
+pub(crate) fn show_queue_overlay(
+    writer_tx: &Sender<WriterMessage>,
+    theme: Theme,
+    cols: u16,
+    pending: &VecDeque<PendingTranscript>,
+    panel: &mut QueuePanel,
+) {
+    let content = format_queue_overlay(pending, panel, theme, cols as usize);
+    let height = queue_overlay_height();
+    let _ = writer_tx.send(WriterMessage::ShowOverlay { content, height });
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -132,4 +182,55 @@ mod tests {
             other => panic!("unexpected writer message: {other:?}"),
         }
     }
+
+    #[test]
+    fn show_latency_overlay_sends_overlay() {
+        let (writer_tx, writer_rx) = bounded(4);
+        show_latency_overlay(&writer_tx, Theme::Coral, 80, &VoiceMetrics::default());
+        match writer_rx
+            .recv_timeout(std::time::Duration::from_millis(200))
+            .expect("overlay message")
+        {
+            WriterMessage::ShowOverlay { content, height } => {
+                assert_eq!(height, latency_overlay_height());
+                assert!(content.contains("Latency"));
+            }
+            other => panic!("unexpected writer message: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn show_search_overlay_sends_overlay() {
+        let (writer_tx, writer_rx) = bounded(4);
+        let mut scrollback = Scrollback::default();
+        scrollback.observe(b"Edited src/main.rs\r\n");
+        show_search_overlay(&writer_tx, Theme::Coral, 80, "main", &scrollback);
+        match writer_rx
+            .recv_timeout(std::time::Duration::from_millis(200))
+            .expect("overlay message")
+        {
+            WriterMessage::ShowOverlay { content, height } => {
+                assert_eq!(height, search_overlay_height());
+                assert!(content.contains("1 match"));
+            }
+            other => panic!("unexpected writer message: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn show_queue_overlay_sends_overlay() {
+        let (writer_tx, writer_rx) = bounded(4);
+        let mut panel = QueuePanel::default();
+        show_queue_overlay(&writer_tx, Theme::Coral, 80, &VecDeque::new(), &mut panel);
+        match writer_rx
+            .recv_timeout(std::time::Duration::from_millis(200))
+            .expect("overlay message")
+        {
+            WriterMessage::ShowOverlay { content, height } => {
+                assert_eq!(height, queue_overlay_height());
+                assert!(content.contains("No transcripts waiting"));
+            }
+            other => panic!("unexpected writer message: {other:?}"),
+        }
+    }
 }
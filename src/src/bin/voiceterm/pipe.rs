@@ -0,0 +1,279 @@
+//! Transcripts on stdout for shell pipelines and editors, with no PTY or backend.
+//!
+//! `--pipe` prints every transcript, `--once` prints one and exits, and
+//! `voiceterm capture` prints one with distinct exit codes for scripts. Each
+//! capture runs until the silence tail like any voice capture (or until
+//! `capture --timeout`), and its transcript, after `postprocess` rules, the
+//! project vocabulary, and any `--translate-to` step, is written as one line,
+//! or as one JSON object per line with `--pipe-json` / `capture --json`. Only
+//! transcripts go to stdout, so `voiceterm --once | xargs git commit -m`
+//! works; notices go to stderr.
+
+use anyhow::{anyhow, bail, Result};
+use serde::Serialize;
+use std::io::{self, ErrorKind, Write};
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+use voiceterm::{log_debug, log_info, VoiceCaptureTrigger, VoiceJobMessage};
+
+use crate::cli_utils::resolve_working_dir;
+use crate::config::{CaptureArgs, OverlayConfig, UserConfig};
+use crate::session_record::source_key;
+use crate::transcript::{TranscriptPostProcessor, TranscriptTranslator};
+use crate::vocab::ProjectVocabulary;
+use crate::voice_control::VoiceManager;
+
+/// How often a capture polls the voice worker.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// `voiceterm capture` exit code after printing a transcript.
+pub(crate) const EXIT_TRANSCRIPT: i32 = 0;
+/// `voiceterm capture` exit code when recording or transcription failed.
+pub(crate) const EXIT_ERROR: i32 = 1;
+/// `voiceterm capture` exit code when the recording held no speech (2 is taken by argument errors).
+pub(crate) const EXIT_NO_SPEECH: i32 = 3;
+
+/// One transcript as printed; also the `--pipe-json` / `capture --json` line.
+#[derive(Debug, Serialize)]
+struct Heard {
+    text: String,
+    source: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    confidence: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    speech_ms: Option<u64>,
+}
+
+/// The stdout line for a transcript; plain text is flattened so one transcript is one line.
+fn format_line(heard: &Heard, json: bool) -> Result<String> {
+    if json {
+        return Ok(serde_json::to_string(heard)?);
+    }
+    Ok(heard.text.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+/// Parse a `capture --timeout` value: `10s`, `1500ms`, `2m`, or bare seconds.
+pub(crate) fn parse_timeout(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let split = value
+        .find(|ch: char| !ch.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let number: u64 = number
+        .parse()
+        .map_err(|_| format!("invalid timeout {value:?} (use e.g. 10s, 1500ms, 2m)"))?;
+    let timeout = match unit.trim() {
+        "" | "s" => Duration::from_secs(number),
+        "ms" => Duration::from_millis(number),
+        "m" => Duration::from_secs(number.saturating_mul(60)),
+        _ => {
+            return Err(format!(
+                "invalid timeout {value:?} (use e.g. 10s, 1500ms, 2m)"
+            ))
+        }
+    };
+    if timeout.is_zero() {
+        return Err("timeout must be greater than zero".to_string());
+    }
+    Ok(timeout)
+}
+
+/// Microphone, Whisper model, and transcript rules shared by the stdout modes.
+struct StdoutCapture {
+    manager: VoiceManager,
+    postprocessor: TranscriptPostProcessor,
+    translator: TranscriptTranslator,
+}
+
+impl StdoutCapture {
+    fn new(config: &OverlayConfig, user_config: &UserConfig) -> Result<Self> {
+        let postprocessor = TranscriptPostProcessor::from_config(&user_config.postprocess)?;
+        let translator = TranscriptTranslator::from_config(config)?;
+        let mut app = config.app.clone();
+        if let Some(vocab) = ProjectVocabulary::discover(Path::new(&resolve_working_dir())) {
+            app.whisper_prompt = vocab.whisper_prompt(app.whisper_prompt.as_deref());
+        }
+        let mut manager = VoiceManager::new(app);
+        manager.watch_devices(user_config.devices.clone());
+        Ok(Self {
+            manager,
+            postprocessor,
+            translator,
+        })
+    }
+
+    /// Record one utterance, stopping early at `timeout`; `Ok(None)` means no speech was heard.
+    fn next(&mut self, timeout: Option<Duration>) -> Result<Option<Heard>> {
+        if !self
+            .manager
+            .start_headless_capture(VoiceCaptureTrigger::Manual)?
+        {
+            bail!("a capture is already running");
+        }
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        let mut stop_requested = false;
+        let message = loop {
+            let _ = self.manager.poll_preload();
+            if let Some(message) = self.manager.poll_message() {
+                break message;
+            }
+            if !stop_requested && deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                self.manager.request_early_stop();
+                stop_requested = true;
+            }
+            thread::sleep(POLL_INTERVAL);
+        };
+        let (text, source, confidence, metrics) = match message {
+            VoiceJobMessage::Transcript {
+                text,
+                source,
+                confidence,
+                metrics,
+                ..
+            } => (text, source, confidence, metrics),
+            VoiceJobMessage::Empty { .. } => return Ok(None),
+            VoiceJobMessage::Error(err) => return Err(anyhow!(err.message)),
+        };
+        let text = self.postprocessor.apply(text.trim());
+        if text.trim().is_empty() {
+            return Ok(None);
+        }
+        let text = if self.translator.is_enabled() {
+            self.translator.translate(&text).unwrap_or_else(|err| {
+                eprintln!("voiceterm: translation failed, printing the original: {err:#}");
+                text
+            })
+        } else {
+            text
+        };
+        Ok(Some(Heard {
+            text,
+            source: source_key(source),
+            confidence,
+            speech_ms: metrics.as_ref().map(|metrics| metrics.speech_ms),
+        }))
+    }
+}
+
+/// Tell a person at the terminal that the mic is live; stays quiet when stderr is captured.
+fn announce_listening(suffix: &str) {
+    // SAFETY: isatty only inspects the descriptor.
+    if unsafe { libc::isatty(libc::STDERR_FILENO) } == 1 {
+        eprintln!("voiceterm: listening{suffix}");
+    }
+}
+
+/// Run `--pipe` (until Ctrl+C or the reader closes stdout) or `--once` (one transcript).
+pub(crate) fn run_pipe(config: &OverlayConfig, user_config: &UserConfig) -> Result<()> {
+    let mut capture = StdoutCapture::new(config, user_config)?;
+    announce_listening(if config.once { "" } else { " (Ctrl+C to stop)" });
+    log_info(&format!("pipe mode started|once={}", config.once));
+    let mut stdout = io::stdout().lock();
+    loop {
+        let Some(heard) = capture.next(None)? else {
+            if config.once {
+                bail!("no speech detected");
+            }
+            continue;
+        };
+        let line = format_line(&heard, config.pipe_json)?;
+        if let Err(err) = writeln!(stdout, "{line}").and_then(|()| stdout.flush()) {
+            // The reader went away (e.g. `| head -1`), which ends the pipe normally.
+            if err.kind() == ErrorKind::BrokenPipe {
+                log_debug("pipe mode stdout closed");
+                break;
+            }
+            return Err(err.into());
+        }
+        if config.once {
+            break;
+        }
+    }
+    capture.manager.cancel_capture();
+    Ok(())
+}
+
+/// Run `voiceterm capture`; returns the process exit code.
+pub(crate) fn run_capture(
+    config: &OverlayConfig,
+    args: &CaptureArgs,
+    user_config: &UserConfig,
+) -> i32 {
+    let heard = StdoutCapture::new(config, user_config).and_then(|mut capture| {
+        announce_listening("");
+        let heard = capture.next(Some(args.timeout));
+        capture.manager.cancel_capture();
+        heard
+    });
+    let printed = match heard {
+        Ok(Some(heard)) => format_line(&heard, args.json).and_then(|line| {
+            let mut stdout = io::stdout().lock();
+            writeln!(stdout, "{line}")?;
+            stdout.flush()?;
+            Ok(())
+        }),
+        Ok(None) => {
+            log_info("capture command heard no speech");
+            eprintln!("voiceterm: no speech detected");
+            return EXIT_NO_SPEECH;
+        }
+        Err(err) => Err(err),
+    };
+    match printed {
+        Ok(()) => {
+            log_info("capture command printed a transcript");
+            EXIT_TRANSCRIPT
+        }
+        Err(err) => {
+            log_debug(&format!("capture command failed: {err:#}"));
+            eprintln!("voiceterm: capture failed: {err:#}");
+            EXIT_ERROR
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_line_keeps_each_transcript_on_one_line() {
+        let heard = Heard {
+            text: " fix the\nflaky   test ".to_string(),
+            source: "native",
+            confidence: Some(0.5),
+            speech_ms: None,
+        };
+        assert_eq!(format_line(&heard, false).unwrap(), "fix the flaky test");
+        assert_eq!(
+            format_line(&heard, true).unwrap(),
+            r#"{"text":" fix the\nflaky   test ","source":"native","confidence":0.5}"#
+        );
+    }
+
+    #[test]
+    fn capture_subcommand_parses_timeout_and_json() {
+        use crate::config::OverlayCommand;
+        use clap::Parser;
+
+        let config =
+            OverlayConfig::parse_from(["voiceterm", "capture", "--timeout", "10s", "--json"]);
+        let Some(OverlayCommand::Capture(args)) = config.command else {
+            panic!("expected capture subcommand");
+        };
+        assert_eq!(args.timeout, Duration::from_secs(10));
+        assert!(args.json);
+    }
+
+    #[test]
+    fn parse_timeout_accepts_units_and_bare_seconds() {
+        assert_eq!(parse_timeout("10s"), Ok(Duration::from_secs(10)));
+        assert_eq!(parse_timeout("10"), Ok(Duration::from_secs(10)));
+        assert_eq!(parse_timeout("1500ms"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_timeout(" 2m "), Ok(Duration::from_secs(120)));
+        for bad in ["", "0s", "s", "10h", "-5s", "1.5s"] {
+            assert!(parse_timeout(bad).is_err(), "{bad}");
+        }
+    }
+}
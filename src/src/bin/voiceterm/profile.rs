@@ -0,0 +1,341 @@
+//! `voiceterm profile export|import` so user state moves to a new machine as one file.
+//!
+//! The archive is a JSON document holding the text of each piece of user
+//! state: the config file (keybindings, replacements, backend profiles, and
+//! settings), the current project's `.voiceterm/macros.yaml`, the flagged
+//! transcripts that learned replacements and prompt hints are rebuilt from,
+//! and the `--speaker-filter` voice print. Config settings whose names look
+//! like credentials are left out, and captured audio stays behind. Import
+//! refuses to replace existing files unless `--force` is given, and then keeps
+//! each replaced file as `<name>.bak`.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+
+use crate::cli_utils::resolve_working_dir;
+use crate::config::{
+    default_config_path, write_atomic, OverlayConfig, ProfileAction, ProfileArgs, UserConfig,
+};
+use crate::transcript::flagged_transcripts_path;
+use crate::voice_macros::project_macros_path;
+
+const PROFILE_FORMAT: &str = "voiceterm-profile";
+const PROFILE_VERSION: u64 = 1;
+/// Config `settings` keys containing one of these are treated as credentials.
+const SECRET_MARKERS: &[&str] = &["token", "secret", "password", "api-key", "apikey"];
+
+/// One piece of user state carried in the archive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum ProfileItem {
+    Config,
+    Macros,
+    Feedback,
+    VoicePrint,
+}
+
+impl ProfileItem {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Config => "config file",
+            Self::Macros => "voice macros",
+            Self::Feedback => "flagged transcripts",
+            Self::VoicePrint => "voice print",
+        }
+    }
+
+    /// Reject archive contents this build could not load.
+    fn validate(self, contents: &str) -> Result<()> {
+        match self {
+            Self::Config => UserConfig::parse(contents).map(drop),
+            Self::Macros => serde_yaml::from_str::<Value>(contents)
+                .map(drop)
+                .map_err(Into::into),
+            Self::Feedback => contents
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .try_for_each(|line| serde_json::from_str::<serde_json::Value>(line).map(drop))
+                .map_err(Into::into),
+            Self::VoicePrint => serde_json::from_str::<serde_json::Value>(contents)
+                .map(drop)
+                .map_err(Into::into),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ProfileArchive {
+    format: String,
+    version: u64,
+    /// Config settings left out because they looked like credentials.
+    #[serde(default)]
+    excluded: Vec<String>,
+    files: BTreeMap<ProfileItem, String>,
+}
+
+/// Where each item lives on this machine; items without a location are skipped.
+fn profile_paths(config: &OverlayConfig) -> BTreeMap<ProfileItem, PathBuf> {
+    [
+        (
+            ProfileItem::Config,
+            config.config_path.clone().or_else(default_config_path),
+        ),
+        (
+            ProfileItem::Macros,
+            Some(project_macros_path(Path::new(&resolve_working_dir()))),
+        ),
+        (ProfileItem::Feedback, flagged_transcripts_path(config)),
+        (ProfileItem::VoicePrint, config.app.voice_print_path()),
+    ]
+    .into_iter()
+    .filter_map(|(item, path)| Some((item, path?)))
+    .collect()
+}
+
+fn export_profile(paths: &BTreeMap<ProfileItem, PathBuf>) -> Result<ProfileArchive> {
+    let mut archive = ProfileArchive {
+        format: PROFILE_FORMAT.to_string(),
+        version: PROFILE_VERSION,
+        excluded: Vec::new(),
+        files: BTreeMap::new(),
+    };
+    for (&item, path) in paths {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(err) => {
+                return Err(err)
+                    .with_context(|| format!("failed to read {} {}", item.label(), path.display()))
+            }
+        };
+        let contents = if item == ProfileItem::Config {
+            without_secrets(&contents, &mut archive.excluded)
+                .with_context(|| format!("invalid config file {}", path.display()))?
+        } else {
+            contents
+        };
+        archive.files.insert(item, contents);
+    }
+    Ok(archive)
+}
+
+/// Drop config `settings` whose names look like credentials, recording them in `excluded`.
+///
+/// The file is only re-serialized (losing comments) when something was dropped.
+fn without_secrets(raw: &str, excluded: &mut Vec<String>) -> Result<String> {
+    let mut root = match serde_yaml::from_str::<Value>(raw)? {
+        Value::Mapping(root) => root,
+        Value::Null => return Ok(raw.to_string()),
+        _ => bail!("config file is not a YAML mapping"),
+    };
+    let Some(Value::Mapping(settings)) = root.get_mut("settings") else {
+        return Ok(raw.to_string());
+    };
+    let secret: Vec<Value> = settings
+        .keys()
+        .filter(|key| {
+            key.as_str().is_some_and(|name| {
+                let name = name.to_ascii_lowercase();
+                SECRET_MARKERS.iter().any(|marker| name.contains(marker))
+            })
+        })
+        .cloned()
+        .collect();
+    if secret.is_empty() {
+        return Ok(raw.to_string());
+    }
+    for key in secret {
+        settings.remove(&key);
+        excluded.extend(key.as_str().map(str::to_string));
+    }
+    Ok(serde_yaml::to_string(&root)?)
+}
+
+/// Write each archived item to its location; returns what was restored where.
+fn import_profile(
+    archive: &ProfileArchive,
+    paths: &BTreeMap<ProfileItem, PathBuf>,
+    force: bool,
+) -> Result<Vec<(ProfileItem, PathBuf)>> {
+    if archive.format != PROFILE_FORMAT {
+        bail!("not a voiceterm profile archive");
+    }
+    if archive.version > PROFILE_VERSION {
+        bail!(
+            "profile archive v{} is newer than this voiceterm supports (v{PROFILE_VERSION}); upgrade voiceterm",
+            archive.version
+        );
+    }
+    let mut targets = Vec::with_capacity(archive.files.len());
+    for (&item, contents) in &archive.files {
+        let Some(path) = paths.get(&item) else {
+            bail!("nowhere to restore the {}: set HOME", item.label());
+        };
+        item.validate(contents)
+            .with_context(|| format!("archive has an invalid {}", item.label()))?;
+        targets.push((item, path, contents));
+    }
+    let existing: Vec<String> = targets
+        .iter()
+        .filter(|(_, path, _)| path.exists())
+        .map(|(_, path, _)| path.display().to_string())
+        .collect();
+    if !force && !existing.is_empty() {
+        bail!(
+            "would replace {}; rerun with --force to overwrite (old files are kept as .bak)",
+            existing.join(", ")
+        );
+    }
+    let mut restored = Vec::with_capacity(targets.len());
+    for (item, path, contents) in targets {
+        if path.exists() {
+            let backup = backup_path(path);
+            fs::copy(path, &backup)
+                .with_context(|| format!("failed to back up {}", path.display()))?;
+        }
+        write_atomic(path, contents)?;
+        restored.push((item, path.clone()));
+    }
+    Ok(restored)
+}
+
+/// Where `import --force` keeps a replaced file, e.g. `config.yaml.bak`.
+fn backup_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".bak");
+    path.with_file_name(name)
+}
+
+/// `voiceterm profile export OUT` / `voiceterm profile import FILE`.
+pub(crate) fn run_profile(config: &OverlayConfig, args: &ProfileArgs) -> Result<()> {
+    let paths = profile_paths(config);
+    match &args.action {
+        ProfileAction::Export(args) => {
+            let archive = export_profile(&paths)?;
+            if archive.files.is_empty() {
+                bail!("no user state found to export");
+            }
+            write_atomic(&args.out, &serde_json::to_string_pretty(&archive)?)?;
+            // Transcripts and the voice print are personal; keep the archive private.
+            fs::set_permissions(&args.out, fs::Permissions::from_mode(0o600))
+                .with_context(|| format!("failed to restrict {}", args.out.display()))?;
+            let items: Vec<&str> = archive.files.keys().map(|item| item.label()).collect();
+            println!("Exported {} to {}", items.join(", "), args.out.display());
+            if !archive.excluded.is_empty() {
+                println!(
+                    "Left out credential settings: {}",
+                    archive.excluded.join(", ")
+                );
+            }
+        }
+        ProfileAction::Import(args) => {
+            let raw = fs::read_to_string(&args.file)
+                .with_context(|| format!("failed to read {}", args.file.display()))?;
+            let archive: ProfileArchive = serde_json::from_str(&raw)
+                .with_context(|| format!("{} is not a profile archive", args.file.display()))?;
+            for (item, path) in import_profile(&archive, &paths, args.force)? {
+                println!("Restored {} to {}", item.label(), path.display());
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        env::temp_dir().join(format!("voiceterm-profile-{name}-{unique}"))
+    }
+
+    fn paths_under(dir: &Path) -> BTreeMap<ProfileItem, PathBuf> {
+        BTreeMap::from([
+            (ProfileItem::Config, dir.join("config.yaml")),
+            (ProfileItem::Macros, dir.join(".voiceterm/macros.yaml")),
+            (ProfileItem::Feedback, dir.join("feedback/flagged.jsonl")),
+            (ProfileItem::VoicePrint, dir.join("voice-print.json")),
+        ])
+    }
+
+    #[test]
+    fn export_then_import_restores_files_without_credentials() {
+        let old = temp_dir("old");
+        let old_paths = paths_under(&old);
+        fs::create_dir_all(&old).unwrap();
+        fs::write(
+            &old_paths[&ProfileItem::Config],
+            "version: 1\nsettings:\n  voice-send-mode: insert\n  web-ui-token: hunter2\n",
+        )
+        .unwrap();
+        fs::create_dir_all(old.join("feedback")).unwrap();
+        fs::write(
+            &old_paths[&ProfileItem::Feedback],
+            "{\"id\":1,\"heard\":\"get hub\",\"corrected\":\"GitHub\"}\n",
+        )
+        .unwrap();
+
+        let archive = export_profile(&old_paths).unwrap();
+        assert_eq!(archive.excluded, ["web-ui-token"]);
+        assert_eq!(
+            archive.files.keys().copied().collect::<Vec<_>>(),
+            [ProfileItem::Config, ProfileItem::Feedback]
+        );
+        assert!(!archive.files[&ProfileItem::Config].contains("hunter2"));
+        let archive: ProfileArchive =
+            serde_json::from_str(&serde_json::to_string(&archive).unwrap()).unwrap();
+
+        let new = temp_dir("new");
+        let new_paths = paths_under(&new);
+        let restored = import_profile(&archive, &new_paths, false).unwrap();
+        assert_eq!(restored.len(), 2);
+        let config =
+            UserConfig::parse(&fs::read_to_string(&new_paths[&ProfileItem::Config]).unwrap())
+                .unwrap();
+        assert_eq!(
+            config.settings.get("voice-send-mode"),
+            Some(&Value::from("insert"))
+        );
+        assert!(new_paths[&ProfileItem::Feedback].is_file());
+
+        let err = import_profile(&archive, &new_paths, false).unwrap_err();
+        assert!(err.to_string().contains("--force"), "{err}");
+        import_profile(&archive, &new_paths, true).unwrap();
+        assert!(new.join("config.yaml.bak").is_file());
+
+        let _ = fs::remove_dir_all(&old);
+        let _ = fs::remove_dir_all(&new);
+    }
+
+    #[test]
+    fn import_rejects_foreign_newer_or_invalid_archives() {
+        let paths = paths_under(&temp_dir("reject"));
+        let archive = |format: &str, version: u64, config: &str| ProfileArchive {
+            format: format.to_string(),
+            version,
+            excluded: Vec::new(),
+            files: BTreeMap::from([(ProfileItem::Config, config.to_string())]),
+        };
+        assert!(import_profile(&archive("other", 1, ""), &paths, false).is_err());
+        let newer = import_profile(&archive(PROFILE_FORMAT, 9, ""), &paths, false).unwrap_err();
+        assert!(newer.to_string().contains("upgrade voiceterm"), "{newer}");
+        let invalid =
+            import_profile(&archive(PROFILE_FORMAT, 1, "settings: [1"), &paths, false).unwrap_err();
+        assert!(
+            invalid.to_string().contains("invalid config file"),
+            "{invalid}"
+        );
+        assert!(!paths[&ProfileItem::Config].exists());
+    }
+}
@@ -0,0 +1,149 @@
+//! Busy detection so transcripts and auto-voice wait while the backend is generating.
+//!
+//! Two signals mark the backend busy. A working indicator on one of the rows
+//! near the cursor or the bottom of the screen (a spinner glyph leading the
+//! row, `Working…`/`Thinking…`, or an `esc to interrupt` hint) counts while it
+//! stays on screen, for up to [`MARKER_HOLD`] after the last output that showed
+//! it. A token stream, output chunks arriving less than [`STREAM_GAP`] apart
+//! for at least [`STREAM_MIN`], counts until the stream pauses.
+
+use std::time::{Duration, Instant};
+
+/// Longest a working indicator keeps the backend busy without new output.
+const MARKER_HOLD: Duration = Duration::from_secs(3);
+/// Output chunks closer together than this belong to one stream.
+const STREAM_GAP: Duration = Duration::from_millis(250);
+/// How long a stream has to run before it counts as generation.
+const STREAM_MIN: Duration = Duration::from_secs(1);
+
+/// Leading glyphs of animated spinners (braille dots, quarter circles, stars).
+const SPINNER_GLYPHS: &[char] = &['◐', '◓', '◑', '◒', '✢', '✳', '✶', '✻', '✽'];
+/// Status words that lead a working row, as in `Working (3s • esc to interrupt)`.
+const WORKING_WORDS: &[&str] = &["Working", "Thinking"];
+/// Interrupt hints that only appear while a request is running.
+const INTERRUPT_HINTS: &[&str] = &["esc to interrupt", "esc to cancel"];
+
+#[derive(Debug, Default)]
+pub(super) struct BusyDetector {
+    /// Last output that left a working indicator on screen.
+    marker_at: Option<Instant>,
+    stream_started_at: Option<Instant>,
+    last_chunk_at: Option<Instant>,
+}
+
+impl BusyDetector {
+    /// Note an output chunk at `now`; `rows` are the screen rows to check for indicators.
+    pub(super) fn observe(&mut self, rows: &[String], now: Instant) {
+        self.marker_at = rows.iter().any(|row| is_working_row(row)).then_some(now);
+        let continues = self
+            .last_chunk_at
+            .is_some_and(|last| now.duration_since(last) < STREAM_GAP);
+        if !continues {
+            self.stream_started_at = Some(now);
+        }
+        self.last_chunk_at = Some(now);
+    }
+
+    pub(super) fn is_busy(&self, now: Instant) -> bool {
+        let marker = self
+            .marker_at
+            .is_some_and(|at| now.duration_since(at) < MARKER_HOLD);
+        let streaming = match (self.stream_started_at, self.last_chunk_at) {
+            (Some(started), Some(last)) => {
+                now.duration_since(last) < STREAM_GAP && last.duration_since(started) >= STREAM_MIN
+            }
+            _ => false,
+        };
+        marker || streaming
+    }
+}
+
+fn is_working_row(row: &str) -> bool {
+    let row = row.trim();
+    let lower = row.to_lowercase();
+    if INTERRUPT_HINTS.iter().any(|hint| lower.contains(hint)) {
+        return true;
+    }
+    let mut chars = row.chars();
+    if chars.next().is_some_and(is_spinner_glyph) && chars.as_str().starts_with(' ') {
+        return true;
+    }
+    let words = row.trim_start_matches(|ch: char| !ch.is_alphanumeric());
+    WORKING_WORDS.iter().any(|word| {
+        words.strip_prefix(word).is_some_and(|tail| {
+            tail.starts_with('…') || tail.starts_with("...") || tail.starts_with(" (")
+        })
+    })
+}
+
+fn is_spinner_glyph(ch: char) -> bool {
+    // U+2800 is the blank braille cell, which some UIs use as padding.
+    ('\u{2801}'..='\u{28FF}').contains(&ch) || SPINNER_GLYPHS.contains(&ch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn working_indicators_are_recognized() {
+        for row in [
+            "⠋ Loading model",
+            "✻ Thinking… (12s)",
+            "• Working (3s • esc to interrupt)",
+            "Working...",
+            "  ◐ compiling",
+            "Press Esc to cancel",
+        ] {
+            assert!(is_working_row(row), "{row}");
+        }
+        for row in [
+            "› ",
+            "working tree clean",
+            "Working directory: /tmp",
+            "• Edited src/main.rs",
+            "⠀ padded",
+            "",
+        ] {
+            assert!(!is_working_row(row), "{row}");
+        }
+    }
+
+    #[test]
+    fn indicator_counts_while_on_screen_and_expires_without_output() {
+        let mut busy = BusyDetector::default();
+        let start = Instant::now();
+        busy.observe(&rows(&["› ", "⠙ Working (1s • esc to interrupt)"]), start);
+        assert!(busy.is_busy(start + Duration::from_secs(1)));
+        assert!(!busy.is_busy(start + MARKER_HOLD));
+
+        // The indicator is gone once the row is repainted without it.
+        let later = start + Duration::from_secs(1);
+        busy.observe(&rows(&["› "]), later);
+        assert!(!busy.is_busy(later + Duration::from_millis(10)));
+    }
+
+    #[test]
+    fn steady_output_counts_as_a_stream_until_it_pauses() {
+        let mut busy = BusyDetector::default();
+        let start = Instant::now();
+        let mut now = start;
+        while now < start + STREAM_MIN {
+            busy.observe(&rows(&["tokens"]), now);
+            assert!(!busy.is_busy(now), "short bursts are not a stream");
+            now += Duration::from_millis(100);
+        }
+        busy.observe(&rows(&["tokens"]), now);
+        assert!(busy.is_busy(now));
+        assert!(!busy.is_busy(now + STREAM_GAP));
+
+        // A pause starts a new stream.
+        let resumed = now + Duration::from_secs(1);
+        busy.observe(&rows(&["tokens"]), resumed);
+        assert!(!busy.is_busy(resumed));
+    }
+}
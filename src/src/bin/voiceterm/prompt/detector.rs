@@ -0,0 +1,148 @@
+//! External prompt detector so custom REPLs can report readiness themselves.
+//!
+//! `--prompt-detector-cmd` starts a helper that receives ANSI-stripped output
+//! lines on stdin, one per line. Every line it prints on stdout counts as a
+//! "prompt seen" event. A partial line left on screen when output goes idle
+//! (most prompts have no trailing newline) is sent once as well.
+
+use anyhow::{anyhow, bail, Context, Result};
+use crossbeam_channel::{bounded, Receiver, Sender, TrySendError};
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use voiceterm::{log_debug, log_warn};
+
+/// Lines buffered for the detector before new output is dropped.
+const DETECTOR_LINE_CAPACITY: usize = 256;
+/// Prompt events buffered between event-loop ticks.
+const DETECTOR_EVENT_CAPACITY: usize = 64;
+
+/// Running detector process plus the channels feeding it.
+pub(crate) struct ExternalPromptDetector {
+    program: String,
+    child: Child,
+    line_tx: Sender<String>,
+    prompt_rx: Receiver<String>,
+}
+
+impl ExternalPromptDetector {
+    /// Start the detector; `raw` is split like a shell command line.
+    pub(crate) fn spawn(raw: &str) -> Result<Self> {
+        let parts = shell_words::split(raw.trim())
+            .with_context(|| format!("invalid prompt detector command: {raw}"))?;
+        let Some((program, args)) = parts.split_first() else {
+            bail!("prompt detector command is empty");
+        };
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            // Stderr would scribble over the overlay.
+            .stderr(Stdio::null())
+            .spawn()
+            .with_context(|| format!("failed to start prompt detector {program}"))?;
+        let mut stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| anyhow!("prompt detector stdin unavailable"))?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| anyhow!("prompt detector stdout unavailable"))?;
+
+        let (line_tx, line_rx) = bounded::<String>(DETECTOR_LINE_CAPACITY);
+        thread::spawn(move || {
+            for line in line_rx {
+                if let Err(err) = writeln!(stdin, "{line}").and_then(|()| stdin.flush()) {
+                    log_warn(&format!("prompt detector stdin closed: {err}"));
+                    break;
+                }
+            }
+        });
+
+        let (prompt_tx, prompt_rx) = bounded::<String>(DETECTOR_EVENT_CAPACITY);
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                let line = line.trim().to_string();
+                if line.is_empty() {
+                    continue;
+                }
+                // A full queue already means "prompt seen"; extra events add nothing.
+                if let Err(TrySendError::Disconnected(_)) = prompt_tx.try_send(line) {
+                    return;
+                }
+            }
+            log_warn("prompt detector closed stdout; external prompt events stopped");
+        });
+
+        Ok(Self {
+            program: program.clone(),
+            child,
+            line_tx,
+            prompt_rx,
+        })
+    }
+
+    pub(crate) fn program(&self) -> &str {
+        &self.program
+    }
+
+    /// Queue one cleaned output line; dropped when the detector falls behind.
+    pub(crate) fn send_line(&self, line: &str) {
+        if let Err(TrySendError::Full(_)) = self.line_tx.try_send(line.to_string()) {
+            log_debug("prompt detector input full; dropping output line");
+        }
+    }
+
+    /// Next line the detector reported, if any.
+    pub(crate) fn try_recv_prompt(&self) -> Option<String> {
+        self.prompt_rx.try_recv().ok()
+    }
+}
+
+impl Drop for ExternalPromptDetector {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    fn recv_prompt(detector: &ExternalPromptDetector) -> Option<String> {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while Instant::now() < deadline {
+            if let Some(line) = detector.try_recv_prompt() {
+                return Some(line);
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        None
+    }
+
+    #[test]
+    fn spawn_rejects_empty_and_missing_commands() {
+        assert!(ExternalPromptDetector::spawn("  ").is_err());
+        assert!(ExternalPromptDetector::spawn("'unterminated").is_err());
+        assert!(ExternalPromptDetector::spawn("voiceterm-no-such-detector").is_err());
+    }
+
+    #[test]
+    fn detector_output_lines_become_prompt_events() {
+        let detector = ExternalPromptDetector::spawn(
+            r#"sh -c 'while read -r line; do case "$line" in "repl>"*) echo "seen $line";; esac; done'"#,
+        )
+        .expect("spawn detector");
+        assert_eq!(detector.program(), "sh");
+        detector.send_line("loading...");
+        detector.send_line("repl> ");
+        assert_eq!(recv_prompt(&detector).as_deref(), Some("seen repl>"));
+        assert_eq!(detector.try_recv_prompt(), None);
+    }
+}
@@ -1,10 +1,15 @@
 //! Prompt subsystem wiring so readiness detection and logging share one policy.
 
+mod busy;
+mod detector;
 mod logger;
 mod regex;
-mod strip;
+mod screen;
 mod tracker;
+mod ui_mode;
 
+pub(crate) use detector::ExternalPromptDetector;
 pub(crate) use logger::{resolve_prompt_log, PromptLogger};
 pub(crate) use regex::resolve_prompt_regex;
 pub(crate) use tracker::{should_auto_trigger, PromptTracker};
+pub(crate) use ui_mode::{ApprovalKeys, UiMode};
@@ -4,25 +4,36 @@ use anyhow::{Context, Result};
 use regex::Regex;
 use std::env;
 
-use crate::config::OverlayConfig;
+use crate::config::{OverlayConfig, PromptConfig};
 
 pub(crate) struct PromptRegexConfig {
-    pub(crate) regex: Option<Regex>,
+    /// Prompt patterns in priority order.
+    pub(crate) patterns: Vec<Regex>,
+    /// Patterns for lines that are never a prompt.
+    pub(crate) never_patterns: Vec<Regex>,
     pub(crate) allow_auto_learn: bool,
 }
 
+/// Resolve prompt patterns: `--prompt-regex` then the config file's `prompt.patterns`,
+/// or the backend's default when neither is set.
 pub(crate) fn resolve_prompt_regex(
     config: &OverlayConfig,
+    prompt: &PromptConfig,
     backend_fallback: Option<&str>,
 ) -> Result<PromptRegexConfig> {
+    let never_patterns = compile_all(&prompt.never_patterns)?;
     let user_override = config
         .prompt_regex
         .clone()
         .or_else(|| env::var("VOICETERM_PROMPT_REGEX").ok());
-    if let Some(raw) = user_override {
-        let regex = Regex::new(&raw).with_context(|| format!("invalid prompt regex: {raw}"))?;
+    let user_patterns: Vec<String> = user_override
+        .into_iter()
+        .chain(prompt.patterns.iter().cloned())
+        .collect();
+    if !user_patterns.is_empty() {
         return Ok(PromptRegexConfig {
-            regex: Some(regex),
+            patterns: compile_all(&user_patterns)?,
+            never_patterns,
             allow_auto_learn: false,
         });
     }
@@ -31,23 +42,34 @@ pub(crate) fn resolve_prompt_regex(
         .map(str::trim)
         .filter(|pattern| !pattern.is_empty())
     {
-        let regex = Regex::new(raw).with_context(|| format!("invalid prompt regex: {raw}"))?;
         return Ok(PromptRegexConfig {
-            regex: Some(regex),
+            patterns: compile_all(&[raw.to_string()])?,
+            never_patterns,
             allow_auto_learn: true,
         });
     }
 
     Ok(PromptRegexConfig {
-        regex: None,
+        patterns: Vec::new(),
+        never_patterns,
         allow_auto_learn: true,
     })
 }
 
+fn compile_all(patterns: &[String]) -> Result<Vec<Regex>> {
+    patterns
+        .iter()
+        .map(|raw| Regex::new(raw).with_context(|| format!("invalid prompt regex: {raw}")))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::{OverlayConfig, VoiceSendMode};
+    use crate::config::{
+        BracketedPasteMode, MultilineMode, OverlayConfig, PromptConfig, TranscriptQueuePolicy,
+        VoiceSendMode,
+    };
     use clap::Parser;
     use voiceterm::config::AppConfig;
 
@@ -57,10 +79,20 @@ mod tests {
             app: AppConfig::parse_from(["test"]),
             prompt_regex: Some("^codex> $".to_string()),
             prompt_log: None,
+            prompt_detector_cmd: None,
             auto_voice: false,
             auto_voice_idle_ms: 1200,
+            auto_voice_quiet_hours: None,
             transcript_idle_ms: 250,
+            batch_window_ms: 0,
+            min_confidence: 0.0,
+            redictate_attempts: 1,
+            mark_low_confidence: 0.0,
             voice_send_mode: VoiceSendMode::Auto,
+            dictation_buffer: false,
+            voice_navigation: false,
+            code_mode: false,
+            multiline_mode: MultilineMode::Paste,
             theme_name: None,
             no_color: false,
             hud_right_panel: crate::config::HudRightPanel::Ribbon,
@@ -69,14 +101,60 @@ mod tests {
             hud_style: crate::config::HudStyle::Full,
             latency_display: crate::config::LatencyDisplayMode::Short,
             minimal_hud: false,
+            no_scroll_region: false,
+            no_output_preview: false,
+            render_mode: crate::config::RenderMode::Auto,
+            allow_same_tty: false,
+            pipe: false,
+            once: false,
+            pipe_json: false,
             backend: "codex".to_string(),
             codex: false,
             claude: false,
             gemini: false,
             login: false,
+            config_path: None,
+            project_config: false,
+            preset: None,
+            turbo: false,
+            dump_preset: None,
+            migrate_config: false,
+            calibrate: false,
+            transcript_history: None,
+            transcript_history_encrypt_cmd: None,
+            redact_transcripts: false,
+            translate_to: None,
+            translate_cmd: None,
+            command: None,
+            session_record: None,
+            session_hook: None,
+            startup_profile: false,
+            target: Vec::new(),
+            feedback_dir: None,
+            type_delay_ms: None,
+            echo_check_ms: None,
+            transcript_queue_size: 5,
+            transcript_queue_policy: TranscriptQueuePolicy::DropOldest,
+            bracketed_paste: BracketedPasteMode::Multiline,
+            web_ui: false,
+            web_ui_port: None,
+            ws_listen: None,
+            control: false,
+            control_socket: None,
+        };
+        let resolved = resolve_prompt_regex(&config, &PromptConfig::default(), None)
+            .expect("regex should compile");
+        assert_eq!(resolved.patterns.len(), 1);
+        assert!(!resolved.allow_auto_learn);
+
+        let prompt = PromptConfig {
+            patterns: vec!["^› ".to_string()],
+            never_patterns: vec![r"\(y/n\) >$".to_string()],
         };
-        let resolved = resolve_prompt_regex(&config, None).expect("regex should compile");
-        assert!(resolved.regex.is_some());
+        let resolved = resolve_prompt_regex(&config, &prompt, Some("^> $")).unwrap();
+        let patterns: Vec<&str> = resolved.patterns.iter().map(Regex::as_str).collect();
+        assert_eq!(patterns, ["^codex> $", "^› "]);
+        assert_eq!(resolved.never_patterns.len(), 1);
         assert!(!resolved.allow_auto_learn);
     }
 
@@ -86,10 +164,20 @@ mod tests {
             app: AppConfig::parse_from(["test"]),
             prompt_regex: Some("[".to_string()),
             prompt_log: None,
+            prompt_detector_cmd: None,
             auto_voice: false,
             auto_voice_idle_ms: 1200,
+            auto_voice_quiet_hours: None,
             transcript_idle_ms: 250,
+            batch_window_ms: 0,
+            min_confidence: 0.0,
+            redictate_attempts: 1,
+            mark_low_confidence: 0.0,
             voice_send_mode: VoiceSendMode::Auto,
+            dictation_buffer: false,
+            voice_navigation: false,
+            code_mode: false,
+            multiline_mode: MultilineMode::Paste,
             theme_name: None,
             no_color: false,
             hud_right_panel: crate::config::HudRightPanel::Ribbon,
@@ -98,12 +186,57 @@ mod tests {
             hud_style: crate::config::HudStyle::Full,
             latency_display: crate::config::LatencyDisplayMode::Short,
             minimal_hud: false,
+            no_scroll_region: false,
+            no_output_preview: false,
+            render_mode: crate::config::RenderMode::Auto,
+            allow_same_tty: false,
+            pipe: false,
+            once: false,
+            pipe_json: false,
             backend: "codex".to_string(),
             codex: false,
             claude: false,
             gemini: false,
             login: false,
+            config_path: None,
+            project_config: false,
+            preset: None,
+            turbo: false,
+            dump_preset: None,
+            migrate_config: false,
+            calibrate: false,
+            transcript_history: None,
+            transcript_history_encrypt_cmd: None,
+            redact_transcripts: false,
+            translate_to: None,
+            translate_cmd: None,
+            command: None,
+            session_record: None,
+            session_hook: None,
+            startup_profile: false,
+            target: Vec::new(),
+            feedback_dir: None,
+            type_delay_ms: None,
+            echo_check_ms: None,
+            transcript_queue_size: 5,
+            transcript_queue_policy: TranscriptQueuePolicy::DropOldest,
+            bracketed_paste: BracketedPasteMode::Multiline,
+            web_ui: false,
+            web_ui_port: None,
+            ws_listen: None,
+            control: false,
+            control_socket: None,
+        };
+        assert!(resolve_prompt_regex(&config, &PromptConfig::default(), None).is_err());
+
+        let config = OverlayConfig {
+            prompt_regex: None,
+            ..config
+        };
+        let prompt = PromptConfig {
+            patterns: Vec::new(),
+            never_patterns: vec!["(".to_string()],
         };
-        assert!(resolve_prompt_regex(&config, None).is_err());
+        assert!(resolve_prompt_regex(&config, &prompt, Some("^> $")).is_err());
     }
 }
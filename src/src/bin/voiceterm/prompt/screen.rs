@@ -0,0 +1,478 @@
+//! Terminal grid model of the backend's screen for prompt detection.
+//!
+//! Full-screen backends (Codex's TUI) repaint rows in place with cursor moves
+//! instead of printing lines, so a line buffer never sees their prompt. The
+//! grid tracks the visible rows, the cursor, the scroll region, and the
+//! alternate screen, which lets the tracker look at the row under the cursor
+//! and the bottom rows. Attributes are dropped and a line feed also returns
+//! the carriage, as the PTY's `onlcr` does for line-oriented programs.
+
+use unicode_width::UnicodeWidthChar;
+use vte::{Params, Parser as VteParser, Perform};
+
+const DEFAULT_ROWS: usize = 24;
+const DEFAULT_COLS: usize = 80;
+const TAB_WIDTH: usize = 8;
+/// Filler after a double-width character; skipped when reading a row.
+const WIDE_TAIL: char = '\0';
+
+pub(super) struct Screen {
+    parser: VteParser,
+    grid: Grid,
+}
+
+impl Default for Screen {
+    fn default() -> Self {
+        Self {
+            parser: VteParser::new(),
+            grid: Grid::new(DEFAULT_ROWS, DEFAULT_COLS),
+        }
+    }
+}
+
+impl Screen {
+    /// Apply output; returns the rows finished by line feeds, oldest first.
+    pub(super) fn advance(&mut self, bytes: &[u8]) -> Vec<String> {
+        self.parser.advance(&mut self.grid, bytes);
+        std::mem::take(&mut self.grid.completed)
+    }
+
+    /// Match the backend's PTY size; the bottom rows and the cursor are kept.
+    pub(super) fn resize(&mut self, rows: u16, cols: u16) {
+        let (rows, cols) = (usize::from(rows), usize::from(cols));
+        if rows == 0 || cols == 0 || (rows, cols) == (self.grid.rows.len(), self.grid.cols) {
+            return;
+        }
+        self.grid.resize(rows, cols);
+    }
+
+    /// Text of the cursor row, including trailing spaces the backend printed.
+    pub(super) fn cursor_line(&self) -> String {
+        row_text(&self.grid.rows[self.grid.row])
+    }
+
+    /// Up to `count` non-blank rows at or above the cursor or at the bottom,
+    /// cursor row first, then bottom-up.
+    pub(super) fn prompt_rows(&self, count: usize) -> Vec<String> {
+        self.non_blank_rows(0, count)
+    }
+
+    /// Like [`Screen::prompt_rows`], but only the cursor row and the rows below
+    /// it. Rows above the cursor were written before the latest output, so a
+    /// prompt there has scrolled into history.
+    pub(super) fn input_rows(&self, count: usize) -> Vec<String> {
+        self.non_blank_rows(self.grid.row, count)
+    }
+
+    /// Non-blank rows from `first` down, cursor row first, then bottom-up.
+    fn non_blank_rows(&self, first: usize, count: usize) -> Vec<String> {
+        let cursor = std::iter::once(self.grid.row);
+        let bottom = (first..self.grid.rows.len()).rev();
+        let mut seen = Vec::new();
+        let mut rows = Vec::new();
+        for index in cursor.chain(bottom) {
+            if rows.len() == count {
+                break;
+            }
+            if seen.contains(&index) {
+                continue;
+            }
+            seen.push(index);
+            let text = row_text(&self.grid.rows[index]);
+            if !text.trim().is_empty() {
+                rows.push(text);
+            }
+        }
+        rows
+    }
+}
+
+fn row_text(row: &[char]) -> String {
+    row.iter().filter(|&&ch| ch != WIDE_TAIL).collect()
+}
+
+struct Grid {
+    /// Visible rows; each holds only the cells written so far.
+    rows: Vec<Vec<char>>,
+    cols: usize,
+    row: usize,
+    col: usize,
+    /// Scroll region (DECSTBM), inclusive rows.
+    top: usize,
+    bottom: usize,
+    saved_cursor: Option<(usize, usize)>,
+    /// Main screen held while the alternate screen is active.
+    main_screen: Option<(Vec<Vec<char>>, usize, usize)>,
+    completed: Vec<String>,
+}
+
+impl Grid {
+    fn new(rows: usize, cols: usize) -> Self {
+        Self {
+            rows: vec![Vec::new(); rows],
+            cols,
+            row: 0,
+            col: 0,
+            top: 0,
+            bottom: rows - 1,
+            saved_cursor: None,
+            main_screen: None,
+            completed: Vec::new(),
+        }
+    }
+
+    fn resize(&mut self, rows: usize, cols: usize) {
+        let height = self.rows.len();
+        if rows < height {
+            // Drop rows from the top, as terminals do, but never the cursor row.
+            let from_top = (height - rows).min(self.row);
+            self.rows.drain(..from_top);
+            self.rows.truncate(rows);
+            self.row -= from_top;
+        } else {
+            self.rows.resize(rows, Vec::new());
+        }
+        self.cols = cols;
+        for row in &mut self.rows {
+            row.truncate(cols);
+        }
+        self.row = self.row.min(rows - 1);
+        self.col = self.col.min(cols - 1);
+        self.top = 0;
+        self.bottom = rows - 1;
+        self.main_screen = None;
+    }
+
+    fn last_row(&self) -> usize {
+        self.rows.len() - 1
+    }
+
+    fn line_feed(&mut self) {
+        if self.row == self.bottom {
+            self.scroll_up(1);
+        } else if self.row < self.last_row() {
+            self.row += 1;
+        }
+    }
+
+    fn reverse_index(&mut self) {
+        if self.row == self.top {
+            self.scroll_down(1);
+        } else {
+            self.row = self.row.saturating_sub(1);
+        }
+    }
+
+    /// Scroll the region up, adding blank rows at its bottom.
+    fn scroll_up(&mut self, count: usize) {
+        let count = count.min(self.bottom + 1 - self.top);
+        self.rows.drain(self.top..self.top + count);
+        for _ in 0..count {
+            self.rows.insert(self.bottom + 1 - count, Vec::new());
+        }
+    }
+
+    /// Scroll the region down, adding blank rows at its top.
+    fn scroll_down(&mut self, count: usize) {
+        let count = count.min(self.bottom + 1 - self.top);
+        self.rows.drain(self.bottom + 1 - count..=self.bottom);
+        for _ in 0..count {
+            self.rows.insert(self.top, Vec::new());
+        }
+    }
+
+    fn set_cell(&mut self, col: usize, ch: char) {
+        let row = &mut self.rows[self.row];
+        if row.len() <= col {
+            row.resize(col + 1, ' ');
+        }
+        row[col] = ch;
+    }
+
+    fn erase_in_row(&mut self, from: usize, to: usize) {
+        let row = &mut self.rows[self.row];
+        if to >= row.len() {
+            row.truncate(from);
+        } else {
+            row[from..to].fill(' ');
+        }
+    }
+
+    fn erase_display(&mut self, mode: usize) {
+        match mode {
+            0 => {
+                self.erase_in_row(self.col, usize::MAX);
+                for row in &mut self.rows[self.row + 1..] {
+                    row.clear();
+                }
+            }
+            1 => {
+                for row in &mut self.rows[..self.row] {
+                    row.clear();
+                }
+                self.erase_in_row(0, self.col + 1);
+            }
+            2 | 3 => {
+                for row in &mut self.rows {
+                    row.clear();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn move_to(&mut self, row: usize, col: usize) {
+        self.row = row.min(self.last_row());
+        self.col = col.min(self.cols - 1);
+    }
+
+    fn set_alternate_screen(&mut self, enabled: bool) {
+        if enabled && self.main_screen.is_none() {
+            let blank = vec![Vec::new(); self.rows.len()];
+            let main = std::mem::replace(&mut self.rows, blank);
+            self.main_screen = Some((main, self.row, self.col));
+        } else if !enabled {
+            if let Some((main, row, col)) = self.main_screen.take() {
+                self.rows = main;
+                self.move_to(row, col);
+            }
+        }
+    }
+}
+
+impl Perform for Grid {
+    fn print(&mut self, ch: char) {
+        let ch = if ch.is_whitespace() { ' ' } else { ch };
+        let width = ch.width().unwrap_or(0);
+        if width == 0 {
+            return;
+        }
+        if self.col + width > self.cols {
+            // Auto-wrap onto the next row.
+            self.col = 0;
+            self.line_feed();
+        }
+        self.set_cell(self.col, ch);
+        if width == 2 {
+            self.set_cell(self.col + 1, WIDE_TAIL);
+        }
+        // Past the last column the next print wraps onto the next row.
+        self.col += width;
+    }
+
+    fn execute(&mut self, byte: u8) {
+        match byte {
+            b'\n' | 0x0b | 0x0c => {
+                self.completed.push(row_text(&self.rows[self.row]));
+                self.col = 0;
+                self.line_feed();
+            }
+            b'\r' => self.col = 0,
+            0x08 => self.col = self.col.min(self.cols - 1).saturating_sub(1),
+            b'\t' => self.col = ((self.col / TAB_WIDTH + 1) * TAB_WIDTH).min(self.cols - 1),
+            _ => {}
+        }
+    }
+
+    fn csi_dispatch(&mut self, params: &Params, intermediates: &[u8], _ignore: bool, action: char) {
+        let mut values = params
+            .iter()
+            .map(|param| usize::from(param.first().copied().unwrap_or(0)));
+        let first = values.next().unwrap_or(0);
+        let count = first.max(1);
+        if intermediates == b"?" {
+            let alternate = matches!(first, 47 | 1047 | 1049);
+            match action {
+                'h' if alternate => self.set_alternate_screen(true),
+                'l' if alternate => self.set_alternate_screen(false),
+                _ => {}
+            }
+            return;
+        }
+        if !intermediates.is_empty() {
+            return;
+        }
+        let col = self.col.min(self.cols - 1);
+        match action {
+            // Vertical moves stop at the scroll region edge when starting inside it.
+            'A' => {
+                let floor = if self.row >= self.top { self.top } else { 0 };
+                self.row = self.row.saturating_sub(count).max(floor);
+                self.col = col;
+            }
+            'B' => {
+                let ceiling = if self.row <= self.bottom {
+                    self.bottom
+                } else {
+                    self.last_row()
+                };
+                self.row = (self.row + count).min(ceiling);
+                self.col = col;
+            }
+            'C' => self.col = (col + count).min(self.cols - 1),
+            'D' => self.col = col.saturating_sub(count),
+            'E' => self.move_to(self.row + count, 0),
+            'F' => self.move_to(self.row.saturating_sub(count), 0),
+            'G' | '`' => self.col = (count - 1).min(self.cols - 1),
+            'd' => self.row = (count - 1).min(self.last_row()),
+            'H' | 'f' => {
+                let col = values.next().unwrap_or(0).max(1);
+                self.move_to(count - 1, col - 1);
+            }
+            'J' => self.erase_display(first),
+            'K' => match first {
+                0 => self.erase_in_row(col, usize::MAX),
+                1 => self.erase_in_row(0, col + 1),
+                2 => self.rows[self.row].clear(),
+                _ => {}
+            },
+            'X' => self.erase_in_row(col, col + count),
+            'P' => {
+                let row = &mut self.rows[self.row];
+                if col < row.len() {
+                    row.drain(col..(col + count).min(row.len()));
+                }
+            }
+            '@' => {
+                let row = &mut self.rows[self.row];
+                if col < row.len() {
+                    row.splice(col..col, vec![' '; count]);
+                    row.truncate(self.cols);
+                }
+            }
+            'L' | 'M' if (self.top..=self.bottom).contains(&self.row) => {
+                let top = std::mem::replace(&mut self.top, self.row);
+                if action == 'L' {
+                    self.scroll_down(count);
+                } else {
+                    self.scroll_up(count);
+                }
+                self.top = top;
+                self.col = 0;
+            }
+            'S' => self.scroll_up(count),
+            'T' => self.scroll_down(count),
+            'r' => {
+                let bottom = values.next().unwrap_or(0);
+                let bottom = if bottom == 0 {
+                    self.last_row()
+                } else {
+                    bottom - 1
+                };
+                let bottom = bottom.min(self.last_row());
+                if count - 1 < bottom {
+                    self.top = count - 1;
+                    self.bottom = bottom;
+                    self.move_to(0, 0);
+                }
+            }
+            's' => self.saved_cursor = Some((self.row, self.col)),
+            'u' => {
+                if let Some((row, col)) = self.saved_cursor {
+                    self.move_to(row, col);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn esc_dispatch(&mut self, intermediates: &[u8], _ignore: bool, byte: u8) {
+        if !intermediates.is_empty() {
+            return;
+        }
+        match byte {
+            b'7' => self.saved_cursor = Some((self.row, self.col)),
+            b'8' => {
+                if let Some((row, col)) = self.saved_cursor {
+                    self.move_to(row, col);
+                }
+            }
+            b'D' => self.line_feed(),
+            b'E' => {
+                self.col = 0;
+                self.line_feed();
+            }
+            b'M' => self.reverse_index(),
+            b'c' => *self = Grid::new(self.rows.len(), self.cols),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sized(rows: u16, cols: u16) -> Screen {
+        let mut screen = Screen::default();
+        screen.resize(rows, cols);
+        screen
+    }
+
+    #[test]
+    fn repainted_rows_follow_cursor_moves() {
+        let mut screen = sized(6, 40);
+        // A TUI draws its composer at the bottom and parks the cursor there.
+        let completed = screen.advance(
+            b"\x1b[2J\x1b[1;1HWorking (esc to interrupt)\x1b[5;1H\x1b[2K> \x1b[6;1Hctrl+j newline\x1b[5;3H",
+        );
+        assert!(completed.is_empty());
+        assert_eq!(screen.cursor_line(), "> ");
+        assert_eq!(
+            screen.prompt_rows(2),
+            vec!["> ".to_string(), "ctrl+j newline".to_string()]
+        );
+
+        // Repainting the status row replaces it instead of appending a line.
+        screen.advance(b"\x1b[1;1H\x1b[KDone\x1b[5;3H");
+        assert_eq!(screen.grid.rows[0].iter().collect::<String>(), "Done");
+    }
+
+    #[test]
+    fn input_rows_skip_history_above_the_cursor() {
+        let mut screen = sized(6, 40);
+        screen.advance(b"codex> \nworking...\n");
+        assert_eq!(
+            screen.prompt_rows(4),
+            vec!["working...".to_string(), "codex> ".to_string()]
+        );
+        assert!(screen.input_rows(4).is_empty());
+
+        screen.advance(b"codex> ");
+        assert_eq!(screen.input_rows(4), vec!["codex> ".to_string()]);
+    }
+
+    #[test]
+    fn line_feeds_complete_rows_and_scroll_the_region() {
+        let mut screen = sized(3, 20);
+        let completed = screen.advance(b"one\r\ntwo\nthree\nfour");
+        assert_eq!(completed, vec!["one", "two", "three"]);
+        assert_eq!(screen.cursor_line(), "four");
+        assert_eq!(screen.grid.rows[0].iter().collect::<String>(), "two");
+
+        // Rows below a scroll region stay put while the region scrolls.
+        let mut screen = sized(4, 20);
+        screen.advance(b"\x1b[4;1Hstatus\x1b[1;3r\x1b[3;1Ha\nb\nc");
+        assert_eq!(screen.grid.rows[3].iter().collect::<String>(), "status");
+        assert_eq!(screen.cursor_line(), "c");
+    }
+
+    #[test]
+    fn alternate_screen_restores_the_main_screen() {
+        let mut screen = sized(4, 20);
+        screen.advance(b"$ codex");
+        screen.advance(b"\x1b[?1049h\x1b[1;1Hfull screen");
+        assert_eq!(screen.cursor_line(), "full screen");
+        screen.advance(b"\x1b[?1049l");
+        assert_eq!(screen.cursor_line(), "$ codex");
+    }
+
+    #[test]
+    fn wide_characters_and_edits_keep_row_text() {
+        let mut screen = sized(2, 10);
+        screen.advance("日本ab\x1b[4D\x1b[2P".as_bytes());
+        assert_eq!(screen.cursor_line(), "日ab");
+        screen.advance(b"\r0123456789wrapped");
+        assert_eq!(screen.cursor_line(), "wrapped");
+    }
+}
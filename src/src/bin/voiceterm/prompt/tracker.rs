@@ -1,107 +1,195 @@
 //! Prompt-detection state machine so auto-voice and auto-send trigger safely.
+//!
+//! Output runs through a [`Screen`] grid: rows finished by a line feed are
+//! matched as they complete, and once output goes idle the row under the
+//! cursor and the rows below it are checked, which is where full-screen
+//! backends keep their prompt. Rows above the cursor are history: a prompt
+//! that scrolled up under newer output is not matched again. While the backend
+//! shows a working indicator or streams output it is reported busy, and neither
+//! auto-voice nor transcript delivery treats it as ready, even with the prompt
+//! on screen.
+//!
+//! Several prompt patterns can be set, since one regex rarely covers every
+//! state a backend's input line goes through; never-prompt patterns veto a
+//! line first, so a confirmation dialog ending in `>` is neither matched nor
+//! learned as the prompt.
+//!
+//! The same rows also classify the backend's [`UiMode`]: its input prompt, an
+//! approval dialog, or a selection menu.
 
 use regex::Regex;
 use std::time::{Duration, Instant};
 
+use super::busy::BusyDetector;
+use super::detector::ExternalPromptDetector;
 use super::logger::PromptLogger;
-use super::strip::strip_ansi_preserve_controls;
+use super::screen::Screen;
+use super::ui_mode::{classify, UiMode, UI_SCAN_ROWS};
+use crate::transcript::loggable;
+
+/// Non-blank rows (cursor row, then bottom-up to it) checked for a prompt on idle.
+const PROMPT_SCAN_ROWS: usize = 4;
+/// Non-blank rows checked for a spinner or working indicator on each output chunk.
+const BUSY_SCAN_ROWS: usize = 8;
 
 /// Tracks prompt detection state from PTY output to drive auto-voice behavior.
 pub(crate) struct PromptTracker {
-    /// Optional user-supplied prompt regex override.
-    regex: Option<Regex>,
+    /// Prompt patterns in priority order; the first match wins.
+    patterns: Vec<Regex>,
+    /// Lines matching any of these are never treated as a prompt.
+    never_patterns: Vec<Regex>,
     /// Auto-learned prompt string from recent output.
     learned_prompt: Option<String>,
     /// Whether auto-learning is permitted.
     allow_auto_learn: bool,
     /// Last time a prompt was detected.
     last_prompt_seen_at: Option<Instant>,
+    /// How the last prompt was detected (`line_complete`, `idle_match`, ...).
+    last_prompt_reason: Option<&'static str>,
     /// Last time any output (PTY or overlay) was seen.
     last_output_at: Instant,
     /// Last time PTY output was observed.
     last_pty_output_at: Option<Instant>,
     /// Whether any output has been seen yet (startup heuristic).
     has_seen_output: bool,
-    /// Current line buffer (ANSI-stripped) for prompt matching.
-    current_line: Vec<u8>,
+    /// Grid model of the backend's screen; its cursor row is the current line.
+    screen: Screen,
+    /// Spinner and token-stream signals that the backend is still generating.
+    busy: BusyDetector,
+    /// What the screen showed after the last output chunk.
+    ui_mode: UiMode,
     /// Last completed line (ANSI-stripped).
     last_line: Option<String>,
     /// Optional prompt logging sink.
     prompt_logger: PromptLogger,
+    /// Optional external process that reports prompts for us.
+    detector: Option<ExternalPromptDetector>,
+    /// Partial line already sent to the detector, so idle ticks don't resend it.
+    detector_partial: Option<String>,
 }
 
 impl PromptTracker {
     pub(crate) fn new(
-        regex: Option<Regex>,
+        patterns: Vec<Regex>,
         allow_auto_learn: bool,
         prompt_logger: PromptLogger,
     ) -> Self {
         Self {
-            regex,
+            patterns,
+            never_patterns: Vec::new(),
             learned_prompt: None,
             allow_auto_learn,
             last_prompt_seen_at: None,
+            last_prompt_reason: None,
             last_output_at: Instant::now(),
             last_pty_output_at: None,
             has_seen_output: false,
-            current_line: Vec::new(),
+            screen: Screen::default(),
+            busy: BusyDetector::default(),
+            ui_mode: UiMode::default(),
             last_line: None,
             prompt_logger,
+            detector: None,
+            detector_partial: None,
         }
     }
 
+    /// Never treat lines matching `patterns` as a prompt, even the learned one.
+    pub(crate) fn with_never_patterns(mut self, patterns: Vec<Regex>) -> Self {
+        self.never_patterns = patterns;
+        self
+    }
+
+    /// Forward output lines to an external detector and accept its prompt events.
+    pub(crate) fn with_detector(mut self, detector: ExternalPromptDetector) -> Self {
+        self.detector = Some(detector);
+        self
+    }
+
     pub(crate) fn feed_output(&mut self, bytes: &[u8]) {
-        let now = Instant::now();
+        self.feed_output_at(bytes, Instant::now());
+    }
+
+    /// Feed PTY output observed at `now` (session replay drives a simulated clock).
+    pub(crate) fn feed_output_at(&mut self, bytes: &[u8], now: Instant) {
         self.last_output_at = now;
         self.last_pty_output_at = Some(now);
         self.has_seen_output = true;
 
-        let cleaned = strip_ansi_preserve_controls(bytes);
-        for byte in cleaned {
-            match byte {
-                b'\n' => {
-                    self.flush_line("line_complete");
-                }
-                b'\r' => {
-                    self.current_line.clear();
-                }
-                b'\t' => {
-                    self.current_line.push(b' ');
-                }
-                byte if byte.is_ascii_graphic() || byte == b' ' => {
-                    self.current_line.push(byte);
-                }
-                _ => {}
-            }
+        for line in self.screen.advance(bytes) {
+            self.flush_line(now, line, "line_complete");
+        }
+        let rows = self.screen.prompt_rows(BUSY_SCAN_ROWS.max(UI_SCAN_ROWS));
+        self.busy
+            .observe(&rows[..rows.len().min(BUSY_SCAN_ROWS)], now);
+        let ui_mode = classify(&rows[..rows.len().min(UI_SCAN_ROWS)]);
+        if ui_mode != self.ui_mode {
+            self.prompt_logger
+                .log(&format!("ui_mode|mode={}", ui_mode.label()));
+            self.ui_mode = ui_mode;
         }
     }
 
+    /// Whether the backend shows its prompt, an approval dialog, or a selection menu.
+    pub(crate) fn ui_mode(&self) -> &UiMode {
+        &self.ui_mode
+    }
+
+    /// Whether the backend looks mid-generation (a spinner or streaming output) at `now`.
+    pub(crate) fn is_busy(&self, now: Instant) -> bool {
+        self.busy.is_busy(now)
+    }
+
+    /// Match the screen grid to the backend's PTY size.
+    pub(crate) fn resize_screen(&mut self, rows: u16, cols: u16) {
+        self.screen.resize(rows, cols);
+    }
+
+    /// Start over for a relaunched backend, keeping the prompt regex and learned prompt.
+    pub(crate) fn on_backend_restart(&mut self) {
+        self.last_prompt_seen_at = None;
+        self.last_prompt_reason = None;
+        self.last_output_at = Instant::now();
+        self.last_pty_output_at = None;
+        self.has_seen_output = false;
+        self.screen = Screen::default();
+        self.busy = BusyDetector::default();
+        self.ui_mode = UiMode::default();
+        self.last_line = None;
+        self.detector_partial = None;
+    }
+
     pub(crate) fn on_idle(&mut self, now: Instant, idle_timeout: Duration) {
+        self.poll_detector(now);
         if !self.has_seen_output {
             return;
         }
         if now.duration_since(self.last_output_at) < idle_timeout {
             return;
         }
-        let candidate = if !self.current_line.is_empty() {
-            self.current_line_as_string()
+        self.send_partial_to_detector();
+        if let Some(line) = self.visible_prompt() {
+            self.update_prompt_seen(now, &line, "idle_match");
+            return;
+        }
+        let cursor_line = self.screen.cursor_line();
+        let candidate = if !cursor_line.is_empty() {
+            cursor_line
         } else {
             self.last_line.clone().unwrap_or_default()
         };
         if candidate.trim().is_empty() {
             return;
         }
-        if self.allow_auto_learn
-            && self.learned_prompt.is_none()
-            && !self.matches_prompt(&candidate)
-        {
-            if !looks_like_prompt(&candidate) {
+        if self.allow_auto_learn && self.learned_prompt.is_none() {
+            if !looks_like_prompt(&candidate) || self.is_never_prompt(&candidate) {
                 return;
             }
             self.learned_prompt = Some(candidate.clone());
             self.last_prompt_seen_at = Some(now);
+            self.last_prompt_reason = Some("prompt_learned");
             self.prompt_logger
-                .log(&format!("prompt_learned|line={candidate}"));
+                .log(&format!("prompt_learned|line={}", loggable(&candidate)));
             return;
         }
         if self.matches_prompt(&candidate) {
@@ -109,47 +197,108 @@ impl PromptTracker {
         }
     }
 
-    fn flush_line(&mut self, reason: &str) {
-        let line = self.current_line_as_string();
-        self.current_line.clear();
+    /// The cursor row or a row below it matching the known prompt, if one is on screen.
+    fn visible_prompt(&self) -> Option<String> {
+        self.screen
+            .input_rows(PROMPT_SCAN_ROWS)
+            .into_iter()
+            .find(|line| self.matches_prompt(line))
+    }
+
+    /// Whether a prompt pattern or learned prompt exists to check the screen against.
+    pub(crate) fn knows_prompt(&self) -> bool {
+        !self.patterns.is_empty() || self.learned_prompt.is_some()
+    }
+
+    /// Whether the known prompt is on screen now; false when none is known.
+    pub(crate) fn prompt_visible(&self) -> bool {
+        self.visible_prompt().is_some()
+    }
+
+    fn flush_line(&mut self, now: Instant, line: String, reason: &'static str) {
         if line.trim().is_empty() {
             return;
         }
         self.last_line = Some(line.clone());
+        self.detector_partial = None;
+        if let Some(detector) = &self.detector {
+            detector.send_line(&line);
+        }
         if self.matches_prompt(&line) {
-            self.update_prompt_seen(Instant::now(), &line, reason);
+            self.update_prompt_seen(now, &line, reason);
         }
     }
 
-    fn matches_prompt(&self, line: &str) -> bool {
-        let mut matches = false;
-        if let Some(regex) = &self.regex {
-            matches |= regex.is_match(line);
+    fn poll_detector(&mut self, now: Instant) {
+        let Some(detector) = &self.detector else {
+            return;
+        };
+        let mut reported = None;
+        while let Some(line) = detector.try_recv_prompt() {
+            reported = Some(line);
         }
-        if let Some(prompt) = &self.learned_prompt {
-            matches |= line.trim_end() == prompt.trim_end();
+        if let Some(line) = reported {
+            self.update_prompt_seen(now, &line, "external_detector");
         }
-        matches
     }
 
-    fn update_prompt_seen(&mut self, now: Instant, line: &str, reason: &str) {
-        self.last_prompt_seen_at = Some(now);
-        self.prompt_logger
-            .log(&format!("prompt_detected|reason={reason}|line={line}"));
+    fn send_partial_to_detector(&mut self) {
+        let Some(detector) = &self.detector else {
+            return;
+        };
+        let partial = self.screen.cursor_line();
+        if partial.trim().is_empty() || self.detector_partial.as_deref() == Some(partial.as_str()) {
+            return;
+        }
+        detector.send_line(&partial);
+        self.detector_partial = Some(partial);
+    }
+
+    /// Never-prompt patterns are checked first; otherwise the first matching
+    /// prompt pattern, then the learned prompt, marks the line as a prompt.
+    fn matches_prompt(&self, line: &str) -> bool {
+        if self.is_never_prompt(line) {
+            return false;
+        }
+        self.patterns.iter().any(|pattern| pattern.is_match(line))
+            || self
+                .learned_prompt
+                .as_deref()
+                .is_some_and(|prompt| line.trim_end() == prompt.trim_end())
+    }
+
+    fn is_never_prompt(&self, line: &str) -> bool {
+        self.never_patterns
+            .iter()
+            .any(|pattern| pattern.is_match(line))
     }
 
-    fn current_line_as_string(&self) -> String {
-        String::from_utf8_lossy(&self.current_line).to_string()
+    fn update_prompt_seen(&mut self, now: Instant, line: &str, reason: &'static str) {
+        self.last_prompt_seen_at = Some(now);
+        self.last_prompt_reason = Some(reason);
+        self.prompt_logger.log(&format!(
+            "prompt_detected|reason={reason}|line={}",
+            loggable(line)
+        ));
     }
 
     pub(crate) fn last_prompt_seen_at(&self) -> Option<Instant> {
         self.last_prompt_seen_at
     }
 
+    pub(crate) fn last_prompt_reason(&self) -> Option<&'static str> {
+        self.last_prompt_reason
+    }
+
     pub(crate) fn last_output_at(&self) -> Instant {
         self.last_output_at
     }
 
+    /// Most recent completed output line (ANSI-stripped).
+    pub(crate) fn last_line(&self) -> Option<&str> {
+        self.last_line.as_deref()
+    }
+
     pub(crate) fn last_pty_output_at(&self) -> Option<Instant> {
         self.last_pty_output_at
     }
@@ -188,13 +337,26 @@ pub(crate) fn should_auto_trigger(
     if !prompt_tracker.has_seen_output() {
         return last_trigger_at.is_none() && prompt_tracker.idle_ready(now, idle_timeout);
     }
+    if prompt_tracker.is_busy(now) {
+        return false;
+    }
+    // Menus take no dictation; an approval dialog is answered by voice like a prompt.
+    let ui_mode = prompt_tracker.ui_mode();
+    if *ui_mode == UiMode::Picker {
+        return false;
+    }
     if let Some(prompt_at) = prompt_tracker.last_prompt_seen_at() {
         if last_trigger_at.is_none_or(|last| prompt_at > last) {
             return true;
         }
     }
+    // Once the prompt is known, quiet output alone (a paused spinner, a
+    // repainted status row) is not enough; the prompt has to be on screen.
     if prompt_tracker.idle_ready(now, idle_timeout)
         && last_trigger_at.is_none_or(|last| prompt_tracker.last_output_at() > last)
+        && (!prompt_tracker.knows_prompt()
+            || prompt_tracker.prompt_visible()
+            || matches!(ui_mode, UiMode::Approval(_)))
     {
         return true;
     }
@@ -220,7 +382,7 @@ mod tests {
     #[test]
     fn should_auto_trigger_checks_prompt_and_idle() {
         let logger = PromptLogger::new(Some(temp_log_path("prompt_tracker_auto")));
-        let mut tracker = PromptTracker::new(None, true, logger);
+        let mut tracker = PromptTracker::new(Vec::new(), true, logger);
         let now = Instant::now();
         tracker.has_seen_output = true;
         tracker.last_output_at = now - Duration::from_millis(2000);
@@ -259,16 +421,18 @@ mod tests {
     #[test]
     fn prompt_tracker_feed_output_handles_control_bytes() {
         let logger = PromptLogger::new(Some(temp_log_path("prompt_tracker_control")));
-        let mut tracker = PromptTracker::new(None, true, logger);
-        tracker.feed_output(b"ab\rde\tf\n");
-        assert_eq!(tracker.last_line.as_deref(), Some("de f"));
+        let mut tracker = PromptTracker::new(Vec::new(), true, logger);
+        tracker.feed_output(b"abcdef\rXY\tZ\n");
+        assert_eq!(tracker.last_line.as_deref(), Some("XYcdef  Z"));
+        tracker.feed_output(b"done\r\n");
+        assert_eq!(tracker.last_line.as_deref(), Some("done"));
         assert!(tracker.has_seen_output());
     }
 
     #[test]
     fn prompt_tracker_idle_ready_on_threshold() {
         let logger = PromptLogger::new(Some(temp_log_path("prompt_tracker_idle")));
-        let mut tracker = PromptTracker::new(None, true, logger);
+        let mut tracker = PromptTracker::new(Vec::new(), true, logger);
         let now = Instant::now();
         tracker.note_activity(now - Duration::from_millis(1000));
         assert!(tracker.idle_ready(now, Duration::from_millis(1000)));
@@ -277,7 +441,7 @@ mod tests {
     #[test]
     fn prompt_tracker_learns_prompt_on_idle() {
         let logger = PromptLogger::new(Some(env::temp_dir().join("voiceterm_prompt_test.log")));
-        let mut tracker = PromptTracker::new(None, true, logger);
+        let mut tracker = PromptTracker::new(Vec::new(), true, logger);
         tracker.feed_output(b"codex> ");
         let now = tracker.last_output_at() + Duration::from_millis(2000);
         tracker.on_idle(now, Duration::from_millis(1000));
@@ -288,23 +452,115 @@ mod tests {
     fn prompt_tracker_matches_regex() {
         let logger = PromptLogger::new(Some(env::temp_dir().join("voiceterm_prompt_test.log")));
         let regex = Regex::new(r"^codex> $").unwrap();
-        let mut tracker = PromptTracker::new(Some(regex), false, logger);
+        let mut tracker = PromptTracker::new(vec![regex], false, logger);
         tracker.feed_output(b"codex> \n");
         assert!(tracker.last_prompt_seen_at().is_some());
     }
 
     #[test]
-    fn prompt_tracker_ignores_non_graphic_bytes() {
+    fn prompt_tracker_normalizes_unicode_spaces() {
         let logger = PromptLogger::new(Some(temp_log_path("prompt_tracker_non_graphic")));
-        let mut tracker = PromptTracker::new(None, true, logger);
-        tracker.feed_output(b"hi\xC2\xA0there\n");
-        assert_eq!(tracker.last_line.as_deref(), Some("hithere"));
+        let mut tracker = PromptTracker::new(Vec::new(), true, logger);
+        tracker.feed_output(b"hi\xC2\xA0there\x07\n");
+        assert_eq!(tracker.last_line.as_deref(), Some("hi there"));
+    }
+
+    #[test]
+    fn prompt_tracker_finds_repainted_prompt_on_screen() {
+        let regex = Regex::new(r"^› ").unwrap();
+        let mut tracker = PromptTracker::new(vec![regex], false, PromptLogger::new(None));
+        tracker.resize_screen(6, 40);
+        // The composer is drawn with cursor moves; no line is ever completed.
+        tracker.feed_output("\x1b[1;1HWorking\x1b[5;1H› \x1b[6;1H⏎ send\x1b[5;3H".as_bytes());
+        assert_eq!(tracker.last_line(), None);
+        let now = tracker.last_output_at() + Duration::from_millis(1000);
+        tracker.on_idle(now, Duration::from_millis(1000));
+        assert_eq!(tracker.last_prompt_reason(), Some("idle_match"));
+    }
+
+    #[test]
+    fn idle_trigger_waits_for_known_prompt_on_screen() {
+        let regex = Regex::new(r"^› ").unwrap();
+        let mut tracker = PromptTracker::new(vec![regex], false, PromptLogger::new(None));
+        tracker.resize_screen(6, 40);
+        tracker.feed_output("\x1b[5;1H⠋ Working (esc to interrupt)".as_bytes());
+        let idle_timeout = Duration::from_millis(1000);
+        let now = tracker.last_output_at() + idle_timeout;
+        assert!(!tracker.prompt_visible());
+        assert!(!should_auto_trigger(
+            &tracker,
+            now,
+            idle_timeout,
+            Some(now - idle_timeout * 2)
+        ));
+
+        tracker.feed_output("\x1b[5;1H\x1b[2K› ".as_bytes());
+        let now = tracker.last_output_at() + idle_timeout;
+        assert!(tracker.prompt_visible());
+        assert!(should_auto_trigger(
+            &tracker,
+            now,
+            idle_timeout,
+            Some(now - idle_timeout * 2)
+        ));
+    }
+
+    #[test]
+    fn busy_backend_blocks_auto_trigger_with_prompt_on_screen() {
+        let regex = Regex::new(r"^› ").unwrap();
+        let mut tracker = PromptTracker::new(vec![regex], false, PromptLogger::new(None));
+        tracker.resize_screen(6, 40);
+        tracker.feed_output("\x1b[4;1H⠋ Working (esc to interrupt)\x1b[5;1H› ".as_bytes());
+        let idle_timeout = Duration::from_millis(500);
+        let now = tracker.last_output_at() + idle_timeout;
+        tracker.on_idle(now, idle_timeout);
+        assert_eq!(tracker.last_prompt_reason(), Some("idle_match"));
+        assert!(tracker.is_busy(now));
+        assert!(!should_auto_trigger(&tracker, now, idle_timeout, None));
+
+        tracker.feed_output("\x1b[4;1H\x1b[2K".as_bytes());
+        let now = tracker.last_output_at() + idle_timeout;
+        assert!(!tracker.is_busy(now));
+        assert!(should_auto_trigger(&tracker, now, idle_timeout, None));
+    }
+
+    #[test]
+    fn ui_mode_follows_the_screen_and_gates_auto_trigger() {
+        let regex = Regex::new(r"^› $").unwrap();
+        let mut tracker = PromptTracker::new(vec![regex], false, PromptLogger::new(None));
+        tracker.resize_screen(8, 60);
+        tracker.feed_output("\x1b[8;1H› ".as_bytes());
+        assert_eq!(*tracker.ui_mode(), UiMode::Input);
+
+        tracker.feed_output(
+            "\x1b[2J\x1b[3;1HRun cargo test?\x1b[5;1H› 1. Yes (y)\x1b[6;1H  2. No (esc)".as_bytes(),
+        );
+        assert!(matches!(tracker.ui_mode(), UiMode::Approval(_)));
+        let idle_timeout = Duration::from_millis(500);
+        let now = tracker.last_output_at() + idle_timeout;
+        assert!(!tracker.prompt_visible());
+        assert!(should_auto_trigger(
+            &tracker,
+            now,
+            idle_timeout,
+            Some(now - idle_timeout * 2)
+        ));
+
+        tracker.feed_output(
+            "\x1b[2J\x1b[3;1HSelect model\x1b[5;1H› 1. gpt-5\x1b[6;1H  2. gpt-5-mini".as_bytes(),
+        );
+        assert_eq!(*tracker.ui_mode(), UiMode::Picker);
+        let now = tracker.last_output_at() + idle_timeout;
+        assert!(!should_auto_trigger(&tracker, now, idle_timeout, None));
+
+        tracker.on_backend_restart();
+        assert_eq!(*tracker.ui_mode(), UiMode::Input);
     }
 
     #[test]
     fn prompt_tracker_on_idle_triggers_on_threshold() {
         let logger = PromptLogger::new(Some(temp_log_path("prompt_tracker_idle_threshold")));
-        let mut tracker = PromptTracker::new(None, true, logger);
+        let mut tracker = PromptTracker::new(Vec::new(), true, logger);
         tracker.feed_output(b"codex> ");
         let now = tracker.last_output_at() + Duration::from_millis(1000);
         tracker.on_idle(now, Duration::from_millis(1000));
@@ -315,7 +571,7 @@ mod tests {
     fn prompt_tracker_on_idle_skips_when_regex_present() {
         let logger = PromptLogger::new(Some(temp_log_path("prompt_tracker_idle_regex")));
         let regex = Regex::new(r"^codex> $").unwrap();
-        let mut tracker = PromptTracker::new(Some(regex), false, logger);
+        let mut tracker = PromptTracker::new(vec![regex], false, logger);
         tracker.feed_output(b"not a prompt");
         let now = tracker.last_output_at() + Duration::from_millis(1000);
         tracker.on_idle(now, Duration::from_millis(1000));
@@ -326,7 +582,7 @@ mod tests {
     fn prompt_tracker_on_idle_learns_when_auto_learn_enabled() {
         let logger = PromptLogger::new(Some(temp_log_path("prompt_tracker_idle_fallback")));
         let regex = Regex::new(r"^>$").unwrap();
-        let mut tracker = PromptTracker::new(Some(regex), true, logger);
+        let mut tracker = PromptTracker::new(vec![regex], true, logger);
         tracker.feed_output(b"codex> ");
         let now = tracker.last_output_at() + Duration::from_millis(1000);
         tracker.on_idle(now, Duration::from_millis(1000));
@@ -336,30 +592,80 @@ mod tests {
     #[test]
     fn prompt_tracker_matches_learned_prompt() {
         let logger = PromptLogger::new(Some(temp_log_path("prompt_tracker_match")));
-        let mut tracker = PromptTracker::new(None, true, logger);
+        let mut tracker = PromptTracker::new(Vec::new(), true, logger);
         tracker.learned_prompt = Some("codex> ".to_string());
         assert!(tracker.matches_prompt("codex> "));
     }
 
+    #[test]
+    fn prompt_tracker_keeps_learned_prompt_across_backend_restart() {
+        let logger = PromptLogger::new(Some(temp_log_path("prompt_tracker_restart")));
+        let mut tracker = PromptTracker::new(Vec::new(), true, logger);
+        tracker.feed_output(b"codex> ");
+        let now = tracker.last_output_at() + Duration::from_millis(2000);
+        tracker.on_idle(now, Duration::from_millis(1000));
+        assert!(tracker.last_prompt_seen_at().is_some());
+
+        tracker.on_backend_restart();
+        assert!(tracker.last_prompt_seen_at().is_none());
+        assert!(!tracker.has_seen_output());
+        assert!(tracker.knows_prompt());
+        tracker.feed_output(b"codex> \n");
+        assert_eq!(tracker.last_prompt_reason(), Some("line_complete"));
+    }
+
     #[test]
     fn prompt_tracker_rejects_mismatched_prompt() {
         let logger = PromptLogger::new(Some(temp_log_path("prompt_tracker_mismatch")));
-        let mut tracker = PromptTracker::new(None, true, logger);
+        let mut tracker = PromptTracker::new(Vec::new(), true, logger);
         tracker.learned_prompt = Some("codex> ".to_string());
         assert!(!tracker.matches_prompt("nope> "));
     }
 
+    #[test]
+    fn prompt_tracker_checks_never_patterns_before_prompt_patterns() {
+        let patterns = vec![
+            Regex::new(r"^› ").unwrap(),
+            Regex::new(r"^codex> $").unwrap(),
+        ];
+        let never = vec![Regex::new(r"\(y/n\) >$").unwrap()];
+        let tracker =
+            PromptTracker::new(patterns, false, PromptLogger::new(None)).with_never_patterns(never);
+        assert!(tracker.knows_prompt());
+        assert!(tracker.matches_prompt("› "));
+        assert!(tracker.matches_prompt("codex> "));
+        assert!(!tracker.matches_prompt("› Apply this patch? (y/n) >"));
+        assert!(!tracker.matches_prompt("Working"));
+    }
+
+    #[test]
+    fn prompt_tracker_does_not_learn_a_never_prompt() {
+        let never = vec![Regex::new(r"\(y/n\) >$").unwrap()];
+        let mut tracker = PromptTracker::new(Vec::new(), true, PromptLogger::new(None))
+            .with_never_patterns(never);
+        tracker.feed_output(b"Run this command? (y/n) >");
+        let now = tracker.last_output_at() + Duration::from_millis(1000);
+        tracker.on_idle(now, Duration::from_millis(1000));
+        assert!(!tracker.knows_prompt());
+        assert!(tracker.last_prompt_seen_at().is_none());
+
+        tracker.feed_output(b"\r\ncodex> ");
+        let now = tracker.last_output_at() + Duration::from_millis(1000);
+        tracker.on_idle(now, Duration::from_millis(1000));
+        assert!(tracker.matches_prompt("codex> "));
+    }
+
     #[test]
     fn prompt_tracker_has_seen_output_starts_false() {
         let logger = PromptLogger::new(Some(temp_log_path("prompt_tracker_seen")));
-        let tracker = PromptTracker::new(None, true, logger);
+        let tracker = PromptTracker::new(Vec::new(), true, logger);
         assert!(!tracker.has_seen_output());
     }
 
     #[test]
     fn should_auto_trigger_respects_last_trigger_equal_times() {
         let logger = PromptLogger::new(Some(temp_log_path("prompt_tracker_last_trigger")));
-        let mut tracker = PromptTracker::new(None, true, logger);
+        let mut tracker = PromptTracker::new(Vec::new(), true, logger);
         tracker.has_seen_output = true;
         let now = Instant::now();
         tracker.last_prompt_seen_at = Some(now);
@@ -371,4 +677,24 @@ mod tests {
             Some(now)
         ));
     }
+
+    #[test]
+    fn external_detector_reports_idle_partial_prompt() {
+        let detector = crate::prompt::ExternalPromptDetector::spawn(
+            r#"sh -c 'while read -r line; do [ "$line" = "myrepl>" ] && echo ready; done'"#,
+        )
+        .expect("spawn detector");
+        let mut tracker =
+            PromptTracker::new(Vec::new(), false, PromptLogger::new(None)).with_detector(detector);
+        tracker.feed_output(b"\x1b[32mbooting\x1b[0m\r\nmyrepl> ");
+        assert_eq!(tracker.last_line.as_deref(), Some("booting"));
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while tracker.last_prompt_seen_at().is_none() && Instant::now() < deadline {
+            tracker.on_idle(Instant::now(), Duration::ZERO);
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert!(tracker.last_prompt_seen_at().is_some());
+        assert_eq!(tracker.detector_partial.as_deref(), Some("myrepl> "));
+    }
 }
@@ -0,0 +1,240 @@
+//! UI-mode classification so delivery adapts to dialogs and menus, not just the prompt.
+//!
+//! Besides its input prompt, a coding CLI shows approval dialogs (`Run this
+//! command? (y/n)`, or numbered `Yes`/`No` options) and selection menus such
+//! as a model or file picker. Dictated text typed into either would pick
+//! options at random, so after each output chunk the rows near the cursor and
+//! the bottom of the screen are classified: approval dialogs take spoken
+//! yes/no answers as their own keys, and pickers take no transcript text.
+
+/// Non-blank rows (cursor row, then bottom-up) checked for a dialog or menu.
+pub(super) const UI_SCAN_ROWS: usize = 12;
+
+/// Inline yes/no markers of line-oriented confirmations, compared lowercase.
+const YES_NO_MARKERS: &[&str] = &["(y/n)", "[y/n]", "(yes/no)", "[yes/no]"];
+/// Footer hints that only appear while a selection menu is open, compared lowercase.
+const PICKER_HINTS: &[&str] = &[
+    "to navigate",
+    "enter to select",
+    "enter to confirm",
+    "type to search",
+];
+/// Glyphs a menu draws in front of the highlighted option.
+const SELECTION_MARKERS: &[char] = &['›', '❯', '>', '▌', '→', '*'];
+
+/// What the backend is showing right now.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) enum UiMode {
+    /// The input prompt (or ordinary output); transcripts are typed as text.
+    #[default]
+    Input,
+    /// A yes/no approval dialog and the keys that answer it.
+    Approval(ApprovalKeys),
+    /// A selection menu; transcripts wait until it closes.
+    Picker,
+}
+
+impl UiMode {
+    pub(crate) fn label(&self) -> &'static str {
+        match self {
+            UiMode::Input => "input",
+            UiMode::Approval(_) => "approval",
+            UiMode::Picker => "picker",
+        }
+    }
+}
+
+/// Keys that answer an approval dialog.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ApprovalKeys {
+    pub(crate) yes: String,
+    pub(crate) no: String,
+    /// Line-oriented confirmations (`[y/N]`) need Enter after the key.
+    pub(crate) submit: bool,
+}
+
+/// Classify the screen from `rows`, as returned by the screen's prompt-row scan.
+pub(super) fn classify(rows: &[String]) -> UiMode {
+    if rows.iter().any(|row| {
+        let lower = row.to_lowercase();
+        YES_NO_MARKERS.iter().any(|marker| lower.contains(marker))
+    }) {
+        return UiMode::Approval(ApprovalKeys {
+            yes: "y".to_string(),
+            no: "n".to_string(),
+            submit: true,
+        });
+    }
+    let mut options: Vec<MenuOption> = rows
+        .iter()
+        .map(String::as_str)
+        .filter_map(menu_option)
+        .collect();
+    options.sort_by_key(|option| option.number);
+    let first_labelled = |word: &str| {
+        options
+            .iter()
+            .find(|option| starts_with_word(&option.label, word))
+            .map(|option| option.key.clone())
+    };
+    if let (Some(yes), Some(no)) = (first_labelled("yes"), first_labelled("no")) {
+        return UiMode::Approval(ApprovalKeys {
+            yes,
+            no,
+            submit: false,
+        });
+    }
+    let hint = rows.iter().any(|row| {
+        let lower = row.to_lowercase();
+        PICKER_HINTS.iter().any(|hint| lower.contains(hint))
+    });
+    if hint || (options.len() >= 2 && options.iter().any(|option| option.selected)) {
+        return UiMode::Picker;
+    }
+    UiMode::Input
+}
+
+/// One numbered menu row, e.g. `› 1. Yes, proceed (y)`.
+#[derive(Debug)]
+struct MenuOption {
+    number: u32,
+    label: String,
+    /// The key hint in trailing parentheses, or the option number.
+    key: String,
+    /// Whether the row carries the menu's highlight marker.
+    selected: bool,
+}
+
+fn menu_option(row: &str) -> Option<MenuOption> {
+    let trimmed = row.trim_start();
+    let unmarked = trimmed.trim_start_matches(|ch: char| SELECTION_MARKERS.contains(&ch));
+    let selected = unmarked.len() != trimmed.len();
+    let unmarked = unmarked.trim_start();
+    let digits = unmarked.len()
+        - unmarked
+            .trim_start_matches(|ch: char| ch.is_ascii_digit())
+            .len();
+    if digits == 0 {
+        return None;
+    }
+    let number = unmarked[..digits].parse::<u32>().ok()?;
+    let label = unmarked[digits..]
+        .strip_prefix(['.', ')'])?
+        .trim()
+        .to_string();
+    if label.is_empty() {
+        return None;
+    }
+    let key = key_hint(&label).unwrap_or_else(|| number.to_string());
+    Some(MenuOption {
+        number,
+        label,
+        key,
+        selected,
+    })
+}
+
+/// A single-key hint like `(y)` or `(esc)` at the end of an option label.
+fn key_hint(label: &str) -> Option<String> {
+    let inner = label.strip_suffix(')')?.rsplit_once('(')?.1.trim();
+    match inner.to_lowercase().as_str() {
+        "esc" | "escape" => Some("\x1b".to_string()),
+        hint if hint.chars().count() == 1 && hint.chars().all(|ch| ch.is_alphanumeric()) => {
+            Some(hint.to_string())
+        }
+        _ => None,
+    }
+}
+
+fn starts_with_word(label: &str, word: &str) -> bool {
+    let lower = label.to_lowercase();
+    lower
+        .strip_prefix(word)
+        .is_some_and(|tail| !tail.starts_with(char::is_alphanumeric))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn numbered_yes_no_options_are_an_approval_with_their_hint_keys() {
+        let codex = rows(&[
+            "  Press enter to confirm or esc to cancel",
+            "    3. No, and tell Codex what to do differently (esc)",
+            "    2. Yes, and don't ask again for this command (a)",
+            "  › 1. Yes, proceed (y)",
+            "Would you like to run the following command?",
+        ]);
+        assert_eq!(
+            classify(&codex),
+            UiMode::Approval(ApprovalKeys {
+                yes: "y".to_string(),
+                no: "\x1b".to_string(),
+                submit: false,
+            })
+        );
+
+        let numbered = rows(&[
+            "❯ 1. Yes",
+            "  2. Not now",
+            "  3. No",
+            "Do you want to proceed?",
+        ]);
+        assert_eq!(
+            classify(&numbered),
+            UiMode::Approval(ApprovalKeys {
+                yes: "1".to_string(),
+                no: "3".to_string(),
+                submit: false,
+            })
+        );
+    }
+
+    #[test]
+    fn inline_yes_no_prompt_is_answered_with_enter() {
+        assert_eq!(
+            classify(&rows(&["Overwrite config.yaml? [y/N] "])),
+            UiMode::Approval(ApprovalKeys {
+                yes: "y".to_string(),
+                no: "n".to_string(),
+                submit: true,
+            })
+        );
+    }
+
+    #[test]
+    fn menus_without_yes_no_are_pickers() {
+        let models = rows(&[
+            "  Press enter to select",
+            "  2. gpt-5-mini",
+            "› 1. gpt-5",
+            "Select model",
+        ]);
+        assert_eq!(classify(&models), UiMode::Picker);
+        assert_eq!(
+            classify(&rows(&["  2. src/lib.rs", "❯ 1. src/main.rs"])),
+            UiMode::Picker
+        );
+        assert_eq!(
+            classify(&rows(&["↑/↓ to navigate · esc to close", "  notes.md"])),
+            UiMode::Picker
+        );
+    }
+
+    #[test]
+    fn prompt_and_numbered_output_stay_input() {
+        for screen in [
+            rows(&["› ", "2. Run the tests", "1. Read the failing test"]),
+            rows(&["codex> ", "Nothing to do"]),
+            rows(&["› 2024. was a good year"]),
+            rows(&[]),
+        ] {
+            assert_eq!(classify(&screen), UiMode::Input, "{screen:?}");
+        }
+    }
+}
@@ -0,0 +1,473 @@
+//! Pending transcript panel that lists queued transcripts so they can be reviewed before delivery.
+//!
+//! Rows are in delivery order. The selected transcript can be moved, edited, or
+//! dropped; delivery waits while the panel is open so rows do not flush out from
+//! under the selection.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::VecDeque;
+use std::hash::{Hash, Hasher};
+use std::time::Instant;
+
+use crate::arrow_keys::{parse_arrow_keys_only, ArrowKey};
+use crate::config::VoiceSendMode;
+use crate::help::{format_box_bottom, format_box_top, format_separator, format_title_line};
+use crate::search_overlay::apply_search_input;
+use crate::theme::{Theme, ThemeColors};
+use crate::transcript::PendingTranscript;
+
+pub const QUEUE_OVERLAY_FOOTER: &str = "[×] close · ↑↓ · [ ] move · e edit · d drop";
+
+/// Transcript rows shown at once; longer queues scroll with the selection.
+const QUEUE_ROWS: usize = 5;
+
+/// Delete key (`ESC [ 3 ~`).
+const DELETE_KEY: &[u8] = b"\x1b[3~";
+
+pub fn queue_overlay_width_for_terminal(width: usize) -> usize {
+    width.clamp(48, 96)
+}
+
+pub fn queue_overlay_inner_width_for_terminal(width: usize) -> usize {
+    queue_overlay_width_for_terminal(width).saturating_sub(2)
+}
+
+/// What a key did to the queue; every change needs a redraw.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum QueueChange {
+    Selected,
+    Moved,
+    Editing,
+    EditCancelled,
+    Edited,
+    Dropped,
+}
+
+impl QueueChange {
+    /// Status line text for changes worth confirming.
+    pub(crate) fn status(self, remaining: usize) -> Option<String> {
+        match self {
+            QueueChange::Dropped => Some(format!("Dropped queued transcript ({remaining} left)")),
+            QueueChange::Edited => Some("Queued transcript updated".to_string()),
+            _ => None,
+        }
+    }
+}
+
+/// Selection and edit state of the queue panel.
+#[derive(Debug, Default)]
+pub(crate) struct QueuePanel {
+    selected: usize,
+    /// Arrival time of the selected transcript, so the selection follows it when
+    /// a new transcript is queued ahead of it.
+    anchor: Option<Instant>,
+    /// Edit buffer for the selected transcript.
+    editing: Option<String>,
+    /// Fingerprint of the queue as last drawn.
+    shown: u64,
+}
+
+impl QueuePanel {
+    pub(crate) fn is_editing(&self) -> bool {
+        self.editing.is_some()
+    }
+
+    /// Whether the queue changed since the panel was last drawn.
+    pub(crate) fn is_stale(&self, pending: &VecDeque<PendingTranscript>) -> bool {
+        fingerprint(pending) != self.shown
+    }
+
+    /// Re-find the selected transcript; an edit is abandoned if it left the queue.
+    fn sync(&mut self, pending: &VecDeque<PendingTranscript>) {
+        let found = self.anchor.and_then(|anchor| {
+            pending
+                .iter()
+                .position(|queued| queued.received_at == anchor)
+        });
+        match found {
+            Some(index) => self.selected = index,
+            None => {
+                self.editing = None;
+                self.selected = self.selected.min(pending.len().saturating_sub(1));
+            }
+        }
+        self.anchor = pending.get(self.selected).map(|queued| queued.received_at);
+    }
+
+    fn select(&mut self, pending: &VecDeque<PendingTranscript>, index: usize) {
+        self.selected = index;
+        self.anchor = pending.get(index).map(|queued| queued.received_at);
+    }
+
+    /// Apply typed bytes; returns what changed, if anything.
+    ///
+    /// While editing, typing edits the text and Esc cancels. Otherwise arrows
+    /// select, `[`/`]` move the selected transcript earlier/later, `e` edits it,
+    /// and `d`, Delete, or Backspace drop it.
+    pub(crate) fn apply_input(
+        &mut self,
+        pending: &mut VecDeque<PendingTranscript>,
+        bytes: &[u8],
+    ) -> Option<QueueChange> {
+        self.sync(pending);
+        if let Some(buffer) = self.editing.as_mut() {
+            if bytes == [0x1b] {
+                self.editing = None;
+                return Some(QueueChange::EditCancelled);
+            }
+            return apply_search_input(buffer, bytes).then_some(QueueChange::Editing);
+        }
+        if pending.is_empty() {
+            return None;
+        }
+        if let Some(keys) = parse_arrow_keys_only(bytes) {
+            let mut selected = self.selected;
+            for key in keys {
+                selected = match key {
+                    ArrowKey::Up => selected.saturating_sub(1),
+                    ArrowKey::Down => (selected + 1).min(pending.len() - 1),
+                    ArrowKey::Left | ArrowKey::Right => selected,
+                };
+            }
+            if selected == self.selected {
+                return None;
+            }
+            self.select(pending, selected);
+            return Some(QueueChange::Selected);
+        }
+        if bytes == DELETE_KEY {
+            return self.drop_selected(pending);
+        }
+        match bytes {
+            b"[" if self.selected > 0 => {
+                pending.swap(self.selected, self.selected - 1);
+                self.select(pending, self.selected - 1);
+                Some(QueueChange::Moved)
+            }
+            b"]" if self.selected + 1 < pending.len() => {
+                pending.swap(self.selected, self.selected + 1);
+                self.select(pending, self.selected + 1);
+                Some(QueueChange::Moved)
+            }
+            b"e" => self.start_edit(pending),
+            b"d" | b"\x7f" | b"\x08" => self.drop_selected(pending),
+            _ => None,
+        }
+    }
+
+    /// Enter starts editing the selected transcript, or saves the edit.
+    ///
+    /// Saving an empty edit drops the transcript.
+    pub(crate) fn enter(
+        &mut self,
+        pending: &mut VecDeque<PendingTranscript>,
+    ) -> Option<QueueChange> {
+        self.sync(pending);
+        let Some(buffer) = self.editing.take() else {
+            return self.start_edit(pending);
+        };
+        if buffer.trim().is_empty() {
+            return self.drop_selected(pending);
+        }
+        let queued = pending.get_mut(self.selected)?;
+        queued.text = buffer;
+        Some(QueueChange::Edited)
+    }
+
+    fn start_edit(&mut self, pending: &VecDeque<PendingTranscript>) -> Option<QueueChange> {
+        let queued = pending.get(self.selected)?;
+        self.editing = Some(queued.text.clone());
+        Some(QueueChange::Editing)
+    }
+
+    fn drop_selected(&mut self, pending: &mut VecDeque<PendingTranscript>) -> Option<QueueChange> {
+        pending.remove(self.selected)?;
+        let selected = self.selected.min(pending.len().saturating_sub(1));
+        self.select(pending, selected);
+        Some(QueueChange::Dropped)
+    }
+}
+
+/// Format the queue overlay as a string, remembering what was drawn in `panel`.
+pub(crate) fn format_queue_overlay(
+    pending: &VecDeque<PendingTranscript>,
+    panel: &mut QueuePanel,
+    theme: Theme,
+    width: usize,
+) -> String {
+    panel.sync(pending);
+    panel.shown = fingerprint(pending);
+    let colors = theme.colors();
+    let borders = &colors.borders;
+    let content_width = queue_overlay_width_for_terminal(width);
+    let inner_width = content_width.saturating_sub(2);
+
+    let header = match (&panel.editing, pending.len()) {
+        (Some(buffer), _) => format_edit(buffer, inner_width),
+        (None, 0) => "  No transcripts waiting".to_string(),
+        (None, count) => format!("  {count} waiting · delivery paused while open"),
+    };
+    let header_color = if panel.is_editing() {
+        colors.info
+    } else {
+        colors.dim
+    };
+    let mut lines = vec![
+        format_box_top(&colors, borders, content_width),
+        format_title_line(&colors, borders, "VoiceTerm - Queue", content_width),
+        format_separator(&colors, borders, content_width),
+        format_row(&colors, header_color, &header, content_width),
+    ];
+    let first = panel
+        .selected
+        .saturating_sub(QUEUE_ROWS - 1)
+        .min(pending.len().saturating_sub(QUEUE_ROWS));
+    for (index, queued) in pending.iter().enumerate().skip(first).take(QUEUE_ROWS) {
+        let selected = index == panel.selected;
+        let text_color = if selected { colors.info } else { "" };
+        lines.push(format_row(
+            &colors,
+            text_color,
+            &format_item(index, queued, selected, inner_width),
+            content_width,
+        ));
+    }
+    while lines.len() < 4 + QUEUE_ROWS {
+        lines.push(format_row(&colors, "", "", content_width));
+    }
+    lines.push(format_separator(&colors, borders, content_width));
+    lines.push(format_title_line(
+        &colors,
+        borders,
+        QUEUE_OVERLAY_FOOTER,
+        content_width,
+    ));
+    lines.push(format_box_bottom(&colors, borders, content_width));
+    lines.join("\n")
+}
+
+/// ` › 1. <text>` with the source and send mode right-aligned.
+fn format_item(
+    index: usize,
+    queued: &PendingTranscript,
+    selected: bool,
+    inner_width: usize,
+) -> String {
+    let marker = if selected { '›' } else { ' ' };
+    let prefix = format!(" {marker} {}. ", index + 1);
+    let tag = format!("{} · {}", queued.label, send_mode_label(queued.mode));
+    let tag_len = tag.chars().count();
+    let budget = inner_width.saturating_sub(prefix.chars().count() + tag_len + 3);
+    let text: String = queued
+        .text
+        .chars()
+        .map(|ch| if ch.is_control() { ' ' } else { ch })
+        .collect();
+    let shown: String = if text.chars().count() > budget {
+        text.chars()
+            .take(budget.saturating_sub(1))
+            .chain(std::iter::once('…'))
+            .collect()
+    } else {
+        text
+    };
+    let left = format!("{prefix}{shown}");
+    let gap = inner_width.saturating_sub(left.chars().count() + tag_len + 1);
+    format!("{left}{:gap$}{tag} ", "")
+}
+
+/// `  Edit: <text>` keeping the tail, where the cursor is.
+fn format_edit(buffer: &str, inner_width: usize) -> String {
+    let prefix = "  Edit: ";
+    let budget = inner_width.saturating_sub(prefix.len() + 2);
+    let chars: Vec<char> = buffer.chars().collect();
+    let shown: String = if chars.len() >= budget {
+        std::iter::once('…')
+            .chain(
+                chars[chars.len().saturating_sub(budget.saturating_sub(2))..]
+                    .iter()
+                    .copied(),
+            )
+            .chain(std::iter::once('▏'))
+            .collect()
+    } else {
+        chars.iter().copied().chain(std::iter::once('▏')).collect()
+    };
+    format!("{prefix}{shown}")
+}
+
+fn send_mode_label(mode: VoiceSendMode) -> &'static str {
+    match mode {
+        VoiceSendMode::Auto => "send",
+        VoiceSendMode::Insert => "insert",
+        VoiceSendMode::Clipboard => "clipboard",
+    }
+}
+
+fn fingerprint(pending: &VecDeque<PendingTranscript>) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for queued in pending {
+        queued.text.hash(&mut hasher);
+        queued.received_at.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+fn format_row(colors: &ThemeColors, text_color: &str, text: &str, width: usize) -> String {
+    let borders = &colors.borders;
+    let inner_width = width.saturating_sub(2);
+    let truncated: String = text.chars().take(inner_width).collect();
+    let padded = format!("{truncated:<inner_width$}");
+    let reset = if text_color.is_empty() {
+        ""
+    } else {
+        colors.reset
+    };
+    format!(
+        "{}{}{}{}{}{}{}{}{}",
+        colors.border,
+        borders.vertical,
+        colors.reset,
+        text_color,
+        padded,
+        reset,
+        colors.border,
+        borders.vertical,
+        colors.reset
+    )
+}
+
+/// Calculate the height of the queue overlay.
+pub fn queue_overlay_height() -> usize {
+    // Top border + title + separator + header + rows + separator + footer + bottom
+    3 + 1 + QUEUE_ROWS + 3
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn queue(texts: &[&str]) -> VecDeque<PendingTranscript> {
+        let start = Instant::now();
+        texts
+            .iter()
+            .enumerate()
+            .map(|(index, text)| PendingTranscript {
+                text: text.to_string(),
+                label: "native",
+                mode: VoiceSendMode::Auto,
+                received_at: start + Duration::from_millis(index as u64),
+                seq: None,
+            })
+            .collect()
+    }
+
+    fn texts(pending: &VecDeque<PendingTranscript>) -> Vec<&str> {
+        pending.iter().map(|queued| queued.text.as_str()).collect()
+    }
+
+    #[test]
+    fn queue_overlay_lines_match_height_and_width() {
+        let long = "word ".repeat(40);
+        for (items, cols) in [
+            (vec![], 80),
+            (vec!["one", "two"], 20),
+            (vec![long.as_str(); 5], 200),
+            (vec!["queued"; 12], 80),
+        ] {
+            let pending = queue(&items);
+            let mut panel = QueuePanel::default();
+            let overlay = format_queue_overlay(&pending, &mut panel, Theme::None, cols);
+            let lines: Vec<&str> = overlay.lines().collect();
+            assert_eq!(lines.len(), queue_overlay_height());
+            let width = queue_overlay_width_for_terminal(cols);
+            for line in &lines {
+                assert_eq!(line.chars().count(), width, "{line:?}");
+            }
+            assert!(!panel.is_stale(&pending));
+        }
+    }
+
+    #[test]
+    fn queue_overlay_scrolls_to_the_selected_transcript() {
+        let items: Vec<String> = (1..=8).map(|i| format!("item {i}")).collect();
+        let items: Vec<&str> = items.iter().map(String::as_str).collect();
+        let mut pending = queue(&items);
+        let mut panel = QueuePanel::default();
+        for _ in 0..6 {
+            panel.apply_input(&mut pending, b"\x1b[B");
+        }
+        let overlay = format_queue_overlay(&pending, &mut panel, Theme::None, 80);
+        assert!(overlay.contains("item 7"), "{overlay}");
+        assert!(overlay.contains("item 3"), "{overlay}");
+        assert!(!overlay.contains("item 2"), "{overlay}");
+    }
+
+    #[test]
+    fn queue_panel_moves_and_drops_the_selected_transcript() {
+        let mut pending = queue(&["first", "second", "third"]);
+        let mut panel = QueuePanel::default();
+        assert_eq!(panel.apply_input(&mut pending, b"["), None);
+        assert_eq!(
+            panel.apply_input(&mut pending, b"\x1b[B"),
+            Some(QueueChange::Selected)
+        );
+        assert_eq!(
+            panel.apply_input(&mut pending, b"]"),
+            Some(QueueChange::Moved)
+        );
+        assert_eq!(texts(&pending), ["first", "third", "second"]);
+        assert_eq!(panel.apply_input(&mut pending, b"]"), None);
+
+        // The selection stays on "second" when a transcript is queued ahead of it.
+        let mut ahead = queue(&["urgent"]);
+        pending.push_front(ahead.pop_front().unwrap());
+        assert!(panel.is_stale(&pending));
+        assert_eq!(
+            panel.apply_input(&mut pending, b"d"),
+            Some(QueueChange::Dropped)
+        );
+        assert_eq!(texts(&pending), ["urgent", "first", "third"]);
+        assert_eq!(
+            panel.apply_input(&mut pending, b"\x1b[3~"),
+            Some(QueueChange::Dropped)
+        );
+        assert_eq!(texts(&pending), ["urgent", "first"]);
+        assert_eq!(
+            QueueChange::Dropped.status(pending.len()).as_deref(),
+            Some("Dropped queued transcript (2 left)")
+        );
+    }
+
+    #[test]
+    fn queue_panel_edits_until_enter_or_escape() {
+        let mut pending = queue(&["git stats", "run tests"]);
+        let mut panel = QueuePanel::default();
+        assert_eq!(
+            panel.apply_input(&mut pending, b"e"),
+            Some(QueueChange::Editing)
+        );
+        assert!(panel.is_editing());
+        // Keys are text while editing.
+        panel.apply_input(&mut pending, b"\x7fus d");
+        let overlay = format_queue_overlay(&pending, &mut panel, Theme::None, 80);
+        assert!(overlay.contains("Edit: git status d▏"), "{overlay}");
+        assert_eq!(
+            panel.apply_input(&mut pending, b"\x1b"),
+            Some(QueueChange::EditCancelled)
+        );
+        assert_eq!(texts(&pending), ["git stats", "run tests"]);
+
+        panel.enter(&mut pending);
+        panel.apply_input(&mut pending, b"\x7fus");
+        assert_eq!(panel.enter(&mut pending), Some(QueueChange::Edited));
+        assert_eq!(texts(&pending), ["git status", "run tests"]);
+
+        // Clearing the text drops the transcript.
+        panel.enter(&mut pending);
+        panel.apply_input(&mut pending, &[0x7f; 10]);
+        assert_eq!(panel.enter(&mut pending), Some(QueueChange::Dropped));
+        assert_eq!(texts(&pending), ["run tests"]);
+    }
+}
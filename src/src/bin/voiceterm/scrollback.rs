@@ -0,0 +1,212 @@
+//! Searchable history of backend output so earlier answers can be found by phrase.
+//!
+//! PTY output runs through a small line-screen model: printable text overwrites
+//! the current row at the cursor column, `\r`, backspace, tabs, erase-in-line,
+//! and horizontal cursor moves edit that row, and a line feed or vertical
+//! cursor move finishes it. Spinners and progress bars that redraw one row in
+//! place therefore leave only their final text, unlike the prompt tracker's
+//! plain line buffer. The last [`SCROLLBACK_MAX_LINES`] non-blank rows are kept
+//! in memory for the session.
+
+use std::collections::VecDeque;
+
+use vte::{Params, Parser as VteParser, Perform};
+
+/// Finished rows kept for search.
+const SCROLLBACK_MAX_LINES: usize = 5000;
+const TAB_WIDTH: usize = 8;
+
+/// A scrollback row containing the query, with the match as char offsets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ScrollbackMatch {
+    pub(crate) line: String,
+    pub(crate) start: usize,
+    pub(crate) len: usize,
+}
+
+pub(crate) struct Scrollback {
+    parser: VteParser,
+    screen: LineScreen,
+}
+
+impl Default for Scrollback {
+    fn default() -> Self {
+        Self {
+            parser: VteParser::new(),
+            screen: LineScreen::default(),
+        }
+    }
+}
+
+impl Scrollback {
+    pub(crate) fn observe(&mut self, bytes: &[u8]) {
+        self.parser.advance(&mut self.screen, bytes);
+    }
+
+    /// Up to `limit` rows containing `query` (case-insensitive), newest first.
+    pub(crate) fn search(&self, query: &str, limit: usize) -> Vec<ScrollbackMatch> {
+        let needle: Vec<char> = query.trim().chars().map(fold_case).collect();
+        if needle.is_empty() {
+            return Vec::new();
+        }
+        let current = self.screen.current_line();
+        current
+            .iter()
+            .chain(self.screen.lines.iter().rev())
+            .filter_map(|line| {
+                let haystack: Vec<char> = line.chars().map(fold_case).collect();
+                let start = haystack
+                    .windows(needle.len())
+                    .position(|window| window == needle.as_slice())?;
+                Some(ScrollbackMatch {
+                    line: line.clone(),
+                    start,
+                    len: needle.len(),
+                })
+            })
+            .take(limit)
+            .collect()
+    }
+}
+
+/// Lowercase one char to one char, so match offsets stay valid in the original row.
+fn fold_case(ch: char) -> char {
+    ch.to_lowercase().next().unwrap_or(ch)
+}
+
+#[derive(Debug, Default)]
+struct LineScreen {
+    lines: VecDeque<String>,
+    row: Vec<char>,
+    col: usize,
+}
+
+impl LineScreen {
+    fn current_line(&self) -> Option<String> {
+        let line: String = self.row.iter().collect();
+        let line = line.trim_end();
+        (!line.trim().is_empty()).then(|| line.to_string())
+    }
+
+    fn finish_row(&mut self) {
+        if let Some(line) = self.current_line() {
+            if self.lines.len() == SCROLLBACK_MAX_LINES {
+                self.lines.pop_front();
+            }
+            self.lines.push_back(line);
+        }
+        self.row.clear();
+        self.col = 0;
+    }
+}
+
+impl Perform for LineScreen {
+    fn print(&mut self, ch: char) {
+        if self.col < self.row.len() {
+            self.row[self.col] = ch;
+        } else {
+            self.row.resize(self.col, ' ');
+            self.row.push(ch);
+        }
+        self.col += 1;
+    }
+
+    fn execute(&mut self, byte: u8) {
+        match byte {
+            b'\n' => self.finish_row(),
+            b'\r' => self.col = 0,
+            0x08 => self.col = self.col.saturating_sub(1),
+            b'\t' => self.col = (self.col / TAB_WIDTH + 1) * TAB_WIDTH,
+            _ => {}
+        }
+    }
+
+    fn csi_dispatch(&mut self, params: &Params, intermediates: &[u8], _ignore: bool, action: char) {
+        if !intermediates.is_empty() {
+            return;
+        }
+        let mut values = params
+            .iter()
+            .map(|param| param.first().copied().unwrap_or(0));
+        let first = usize::from(values.next().unwrap_or(0));
+        match action {
+            'K' => match first {
+                0 => self.row.truncate(self.col),
+                1 => {
+                    let end = (self.col + 1).min(self.row.len());
+                    self.row[..end].fill(' ');
+                }
+                2 => self.row.clear(),
+                _ => {}
+            },
+            'C' => self.col += first.max(1),
+            'D' => self.col = self.col.saturating_sub(first.max(1)),
+            'G' => self.col = first.max(1) - 1,
+            // Moving to another row finishes this one.
+            'A' | 'B' | 'd' | 'E' | 'F' => self.finish_row(),
+            'H' | 'f' => {
+                self.finish_row();
+                self.col = usize::from(values.next().unwrap_or(1).max(1)) - 1;
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(scrollback: &Scrollback, query: &str) -> Vec<String> {
+        scrollback
+            .search(query, 10)
+            .into_iter()
+            .map(|found| found.line)
+            .collect()
+    }
+
+    #[test]
+    fn rows_redrawn_in_place_keep_only_their_final_text() {
+        let mut scrollback = Scrollback::default();
+        scrollback.observe(b"Working 10%\rWorking 100%\r\n");
+        scrollback.observe(b"\x1b[32mEdited\x1b[0m src/main.rs\r\n");
+        scrollback.observe(b"typo\x08\x08\x08\x08fixed\x1b[K\r\n");
+        scrollback.observe(b"spinner |\x1b[1D/\x1b[2K\rDone: updated config.rs");
+
+        assert_eq!(lines(&scrollback, "working"), vec!["Working 100%"]);
+        assert_eq!(lines(&scrollback, "fixed"), vec!["fixed"]);
+        assert!(lines(&scrollback, "typo").is_empty());
+        assert!(lines(&scrollback, "spinner").is_empty());
+        // The unfinished row is searchable too.
+        assert_eq!(
+            lines(&scrollback, "updated"),
+            vec!["Done: updated config.rs"]
+        );
+    }
+
+    #[test]
+    fn search_is_case_insensitive_newest_first_with_char_offsets() {
+        let mut scrollback = Scrollback::default();
+        scrollback.observe("First: Cargo.toml\r\nthen «cargo.toml» again\r\n".as_bytes());
+        let found = scrollback.search("CARGO.TOML", 10);
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].line, "then «cargo.toml» again");
+        assert_eq!((found[0].start, found[0].len), (6, 10));
+        assert_eq!(found[1].start, 7);
+        assert_eq!(scrollback.search("cargo", 1).len(), 1);
+        assert!(scrollback.search("  ", 10).is_empty());
+    }
+
+    #[test]
+    fn history_is_capped() {
+        let mut scrollback = Scrollback::default();
+        for index in 0..SCROLLBACK_MAX_LINES + 5 {
+            scrollback.observe(format!("line {index}\n").as_bytes());
+        }
+        assert_eq!(scrollback.screen.lines.len(), SCROLLBACK_MAX_LINES);
+        assert_eq!(
+            scrollback.screen.lines.front().map(String::as_str),
+            Some("line 5")
+        );
+    }
+}
@@ -0,0 +1,310 @@
+//! Scrollback search overlay that lists earlier backend output matching a phrase.
+//!
+//! Rows are newest first, each windowed around its first match with the match
+//! highlighted, so a hit deep in a long line is still visible.
+
+use crate::help::{format_box_bottom, format_box_top, format_separator, format_title_line};
+use crate::scrollback::{Scrollback, ScrollbackMatch};
+use crate::theme::{Theme, ThemeColors};
+
+const SEARCH_OVERLAY_FOOTER: &str = "[×] close · type to search · Enter";
+/// Footer for overlays too narrow for [`SEARCH_OVERLAY_FOOTER`].
+const SEARCH_OVERLAY_FOOTER_NARROW: &str = "[×] close · Enter";
+
+/// Result rows shown below the query.
+const SEARCH_RESULT_ROWS: usize = 8;
+
+pub fn search_overlay_width_for_terminal(width: usize) -> usize {
+    width.clamp(30, 96)
+}
+
+pub fn search_overlay_inner_width_for_terminal(width: usize) -> usize {
+    search_overlay_width_for_terminal(width).saturating_sub(2)
+}
+
+/// Footer that fits the overlay at terminal `width`.
+pub fn search_overlay_footer(width: usize) -> &'static str {
+    if SEARCH_OVERLAY_FOOTER.chars().count() <= search_overlay_inner_width_for_terminal(width) {
+        SEARCH_OVERLAY_FOOTER
+    } else {
+        SEARCH_OVERLAY_FOOTER_NARROW
+    }
+}
+
+/// Format the search overlay for `query` as a string.
+pub fn format_search_overlay(
+    scrollback: &Scrollback,
+    query: &str,
+    theme: Theme,
+    width: usize,
+) -> String {
+    let colors = theme.colors();
+    let borders = &colors.borders;
+    let content_width = search_overlay_width_for_terminal(width);
+    let inner_width = content_width.saturating_sub(2);
+    let matches = scrollback.search(query, usize::MAX);
+
+    let count = match (query.trim().is_empty(), matches.len()) {
+        (true, _) => String::new(),
+        (false, 0) => "No matches".to_string(),
+        (false, 1) => "1 match".to_string(),
+        (false, count) => format!("{count} matches"),
+    };
+    let mut lines = vec![
+        format_box_top(&colors, borders, content_width),
+        format_title_line(&colors, borders, "VoiceTerm - Find", content_width),
+        format_separator(&colors, borders, content_width),
+        format_row(
+            &colors,
+            colors.info,
+            &format_query(query, &count, inner_width),
+            content_width,
+        ),
+    ];
+    let hint = if query.trim().is_empty() {
+        Some("  Type a phrase to search earlier output")
+    } else if matches.is_empty() {
+        Some("  Nothing in this session's output matches")
+    } else {
+        None
+    };
+    if let Some(hint) = hint {
+        lines.push(format_row(&colors, colors.dim, hint, content_width));
+    }
+    for found in matches
+        .iter()
+        .take(SEARCH_RESULT_ROWS - usize::from(hint.is_some()))
+    {
+        lines.push(format_match_row(&colors, found, content_width));
+    }
+    while lines.len() < 4 + SEARCH_RESULT_ROWS {
+        lines.push(format_row(&colors, "", "", content_width));
+    }
+    lines.push(format_separator(&colors, borders, content_width));
+    lines.push(format_title_line(
+        &colors,
+        borders,
+        search_overlay_footer(width),
+        content_width,
+    ));
+    lines.push(format_box_bottom(&colors, borders, content_width));
+    lines.join("\n")
+}
+
+/// `  Find: <query>` with the match count right-aligned; long queries keep their tail.
+fn format_query(query: &str, count: &str, inner_width: usize) -> String {
+    let prefix = "  Find: ";
+    let count_len = count.chars().count();
+    let budget = inner_width
+        .saturating_sub(prefix.len() + count_len + 2)
+        .max(1);
+    let query_chars: Vec<char> = query.chars().collect();
+    let shown: String = if query_chars.len() > budget {
+        std::iter::once('…')
+            .chain(
+                query_chars[query_chars.len() - (budget - 1)..]
+                    .iter()
+                    .copied(),
+            )
+            .collect()
+    } else {
+        query.to_string()
+    };
+    let left = format!("{prefix}{shown}");
+    let gap = inner_width.saturating_sub(left.chars().count() + count_len + 1);
+    format!("{left}{:gap$}{count} ", "")
+}
+
+fn format_row(colors: &ThemeColors, text_color: &str, text: &str, width: usize) -> String {
+    let borders = &colors.borders;
+    let inner_width = width.saturating_sub(2);
+    let truncated: String = text.chars().take(inner_width).collect();
+    let padded = format!("{truncated:<inner_width$}");
+    let reset = if text_color.is_empty() {
+        ""
+    } else {
+        colors.reset
+    };
+    format!(
+        "{}{}{}{}{}{}{}{}{}",
+        colors.border,
+        borders.vertical,
+        colors.reset,
+        text_color,
+        padded,
+        reset,
+        colors.border,
+        borders.vertical,
+        colors.reset
+    )
+}
+
+fn format_match_row(colors: &ThemeColors, found: &ScrollbackMatch, width: usize) -> String {
+    let borders = &colors.borders;
+    let inner_width = width.saturating_sub(2);
+    let (before, hit, after) = window_match(found, inner_width.saturating_sub(2));
+    let used = 2 + before.chars().count() + hit.chars().count() + after.chars().count();
+    let pad = inner_width.saturating_sub(used);
+    format!(
+        "{}{}{}  {}{}{}{}{}{:pad$}{}{}{}",
+        colors.border,
+        borders.vertical,
+        colors.reset,
+        before,
+        colors.warning,
+        hit,
+        colors.reset,
+        after,
+        "",
+        colors.border,
+        borders.vertical,
+        colors.reset
+    )
+}
+
+/// Split a matching line into (before, match, after), cut to `avail` chars
+/// around the match with `…` marking trimmed ends.
+fn window_match(found: &ScrollbackMatch, avail: usize) -> (String, String, String) {
+    let chars: Vec<char> = found.line.chars().collect();
+    let hit_end = (found.start + found.len).min(chars.len());
+    let (from, to) = if chars.len() <= avail {
+        (0, chars.len())
+    } else {
+        // Keep some context before the match, then fill the rest of the row.
+        let from = found.start.saturating_sub(avail / 3);
+        let to = (from + avail).min(chars.len());
+        (to.saturating_sub(avail), to)
+    };
+    let mut window: Vec<char> = chars[from..to].to_vec();
+    let mut hit_start = found.start.max(from);
+    let mut hit_stop = hit_end.min(to);
+    if from > 0 {
+        window[0] = '…';
+        hit_start = hit_start.max(from + 1);
+    }
+    if to < chars.len() {
+        if let Some(last) = window.last_mut() {
+            *last = '…';
+        }
+        hit_stop = hit_stop.min(to - 1);
+    }
+    let hit_stop = hit_stop.max(hit_start);
+    let (hit_start, hit_stop) = (hit_start - from, hit_stop - from);
+    (
+        window[..hit_start].iter().collect(),
+        window[hit_start..hit_stop].iter().collect(),
+        window[hit_stop..].iter().collect(),
+    )
+}
+
+/// Apply typed bytes to the query; returns true when it changed.
+///
+/// Backspace deletes, printable text appends, and escape sequences such as
+/// arrow keys are ignored.
+pub fn apply_search_input(query: &mut String, bytes: &[u8]) -> bool {
+    if bytes.first() == Some(&0x1b) {
+        return false;
+    }
+    let mut changed = false;
+    for ch in String::from_utf8_lossy(bytes).chars() {
+        match ch {
+            '\u{7f}' | '\u{8}' => changed |= query.pop().is_some(),
+            ch if !ch.is_control() => {
+                query.push(ch);
+                changed = true;
+            }
+            _ => {}
+        }
+    }
+    changed
+}
+
+/// Calculate the height of the search overlay.
+pub fn search_overlay_height() -> usize {
+    // Top border + title + separator + query + results + separator + footer + bottom
+    3 + 1 + SEARCH_RESULT_ROWS + 3
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_scrollback() -> Scrollback {
+        let mut scrollback = Scrollback::default();
+        scrollback.observe(b"Edited src/main.rs\r\n");
+        scrollback.observe(b"Running cargo test\r\n");
+        scrollback.observe(
+            format!(
+                "{} then main.rs again {}\r\n",
+                "x".repeat(80),
+                "y".repeat(80)
+            )
+            .as_bytes(),
+        );
+        scrollback
+    }
+
+    #[test]
+    fn search_overlay_lines_match_height_and_width() {
+        let scrollback = sample_scrollback();
+        for (query, cols) in [("", 80), ("main.rs", 80), ("missing", 200), ("main", 10)] {
+            let overlay = format_search_overlay(&scrollback, query, Theme::None, cols);
+            let lines: Vec<&str> = overlay.lines().collect();
+            assert_eq!(lines.len(), search_overlay_height(), "{query}");
+            let width = search_overlay_width_for_terminal(cols);
+            assert!(lines[lines.len() - 2].contains(search_overlay_footer(cols)));
+            for line in &lines {
+                assert_eq!(line.chars().count(), width, "{line:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn search_overlay_lists_matches_newest_first_with_count() {
+        let overlay = format_search_overlay(&sample_scrollback(), "MAIN.RS", Theme::None, 80);
+        assert!(overlay.contains("Find: MAIN.RS"));
+        assert!(overlay.contains("2 matches"));
+        let rows: Vec<&str> = overlay
+            .lines()
+            .filter(|line| line.contains("main.rs"))
+            .collect();
+        assert_eq!(rows.len(), 2);
+        // The long line is windowed around its match.
+        assert!(rows[0].contains("…x"), "{}", rows[0]);
+        assert!(rows[0].contains("y…"), "{}", rows[0]);
+        assert!(rows[1].contains("Edited src/main.rs"));
+
+        let overlay = format_search_overlay(&sample_scrollback(), "nothing", Theme::None, 80);
+        assert!(overlay.contains("No matches"));
+    }
+
+    #[test]
+    fn search_input_edits_the_query() {
+        let mut query = String::new();
+        assert!(apply_search_input(&mut query, b"mainx"));
+        assert!(apply_search_input(&mut query, b"\x7f.rs"));
+        assert_eq!(query, "main.rs");
+        assert!(!apply_search_input(&mut query, b"\x1b[A"));
+        assert!(!apply_search_input(&mut query, b"\x01"));
+        assert_eq!(query, "main.rs");
+    }
+
+    #[test]
+    fn window_match_highlights_only_the_match() {
+        let found = ScrollbackMatch {
+            line: "abc needle xyz".to_string(),
+            start: 4,
+            len: 6,
+        };
+        assert_eq!(
+            window_match(&found, 40),
+            ("abc ".to_string(), "needle".to_string(), " xyz".to_string())
+        );
+        let (before, hit, after) = window_match(&found, 8);
+        assert_eq!(
+            before.chars().count() + hit.chars().count() + after.chars().count(),
+            8
+        );
+        assert!(before.starts_with('…') && after.ends_with('…'));
+    }
+}
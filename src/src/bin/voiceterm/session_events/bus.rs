@@ -0,0 +1,165 @@
+//! Process-wide event bus so capture, delivery, and PTY paths can emit without plumbing.
+
+use serde::{Deserialize, Serialize};
+use std::sync::{Mutex, OnceLock};
+
+/// One step in the life of a voice session, tagged by `"event"` when serialized.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub(crate) enum VoiceSessionEvent {
+    /// The backend is running under the overlay.
+    SessionStarted { backend: String },
+    /// Recording began; `trigger` is `manual` or `auto`, `source` the pipeline.
+    CaptureStarted { trigger: String, source: String },
+    /// A dictation draft grew; `text` is the whole draft so far.
+    Partial { text: String },
+    /// A dictated transcript was sent to the backend or copied to the clipboard.
+    TranscriptDelivered {
+        text: String,
+        source: String,
+        mode: String,
+    },
+    /// The backend closed its terminal.
+    BackendExited,
+    /// The overlay is shutting down; no events follow.
+    SessionEnded,
+}
+
+impl VoiceSessionEvent {
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Self::SessionStarted { .. } => "session_started",
+            Self::CaptureStarted { .. } => "capture_started",
+            Self::Partial { .. } => "partial",
+            Self::TranscriptDelivered { .. } => "transcript_delivered",
+            Self::BackendExited => "backend_exited",
+            Self::SessionEnded => "session_ended",
+        }
+    }
+}
+
+/// A subscriber; called on the emitting thread, so it must not block.
+pub(crate) trait SessionEventSink: Send {
+    fn on_event(&mut self, event: &VoiceSessionEvent);
+}
+
+#[derive(Default)]
+struct SessionEventBus {
+    sinks: Vec<Box<dyn SessionEventSink>>,
+}
+
+impl SessionEventBus {
+    fn emit(&mut self, event: &VoiceSessionEvent) {
+        for sink in &mut self.sinks {
+            sink.on_event(event);
+        }
+    }
+}
+
+static BUS: OnceLock<Mutex<SessionEventBus>> = OnceLock::new();
+
+/// Add a subscriber for every event emitted from now on.
+pub(crate) fn subscribe_session_events(sink: Box<dyn SessionEventSink>) {
+    let bus = BUS.get_or_init(|| Mutex::new(SessionEventBus::default()));
+    if let Ok(mut bus) = bus.lock() {
+        bus.sinks.push(sink);
+    }
+}
+
+/// Send an event to every subscriber; `build` only runs when there is one.
+pub(crate) fn emit_session_event(build: impl FnOnce() -> VoiceSessionEvent) {
+    let Some(bus) = BUS.get() else {
+        return;
+    };
+    let Ok(mut bus) = bus.lock() else {
+        return;
+    };
+    if bus.sinks.is_empty() {
+        return;
+    }
+    bus.emit(&build());
+}
+
+/// Drop every subscriber so helpers see end of input before the process exits.
+pub(crate) fn close_session_events() {
+    if let Some(Ok(mut bus)) = BUS.get().map(Mutex::lock) {
+        bus.sinks.clear();
+    }
+}
+
+/// Texts of every `transcript_delivered` event on the global bus, for tests of
+/// the delivery paths; tests run in parallel, so each should use unique text.
+#[cfg(test)]
+pub(crate) fn delivered_transcripts() -> std::sync::Arc<Mutex<Vec<String>>> {
+    use std::sync::Arc;
+
+    struct Delivered(Arc<Mutex<Vec<String>>>);
+
+    impl SessionEventSink for Delivered {
+        fn on_event(&mut self, event: &VoiceSessionEvent) {
+            if let VoiceSessionEvent::TranscriptDelivered { text, .. } = event {
+                self.0.lock().unwrap().push(text.clone());
+            }
+        }
+    }
+
+    static DELIVERED: OnceLock<Arc<Mutex<Vec<String>>>> = OnceLock::new();
+    Arc::clone(DELIVERED.get_or_init(|| {
+        let texts = Arc::new(Mutex::new(Vec::new()));
+        subscribe_session_events(Box::new(Delivered(Arc::clone(&texts))));
+        texts
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    struct Collect(Arc<Mutex<Vec<String>>>);
+
+    impl SessionEventSink for Collect {
+        fn on_event(&mut self, event: &VoiceSessionEvent) {
+            self.0.lock().unwrap().push(event.name().to_string());
+        }
+    }
+
+    #[test]
+    fn every_subscriber_sees_events_in_order() {
+        let first = Arc::new(Mutex::new(Vec::new()));
+        let second = Arc::new(Mutex::new(Vec::new()));
+        let mut bus = SessionEventBus::default();
+        bus.sinks.push(Box::new(Collect(Arc::clone(&first))));
+        bus.sinks.push(Box::new(Collect(Arc::clone(&second))));
+        bus.emit(&VoiceSessionEvent::SessionStarted {
+            backend: "codex".to_string(),
+        });
+        bus.emit(&VoiceSessionEvent::BackendExited);
+        bus.emit(&VoiceSessionEvent::SessionEnded);
+        let expected = vec!["session_started", "backend_exited", "session_ended"];
+        assert_eq!(*first.lock().unwrap(), expected);
+        assert_eq!(*second.lock().unwrap(), expected);
+    }
+
+    #[test]
+    fn events_serialize_with_their_name_as_the_tag() {
+        let event = VoiceSessionEvent::TranscriptDelivered {
+            text: "run the tests".to_string(),
+            source: "native".to_string(),
+            mode: "auto".to_string(),
+        };
+        let line = serde_json::to_string(&event).unwrap();
+        assert_eq!(
+            line,
+            r#"{"event":"transcript_delivered","text":"run the tests","source":"native","mode":"auto"}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<VoiceSessionEvent>(&line).unwrap(),
+            event
+        );
+        assert_eq!(
+            serde_json::to_string(&VoiceSessionEvent::SessionEnded).unwrap(),
+            r#"{"event":"session_ended"}"#
+        );
+    }
+}
@@ -0,0 +1,141 @@
+//! `--session-hook` helper process that reads one JSON session event per line on stdin.
+
+use anyhow::{anyhow, bail, Context, Result};
+use crossbeam_channel::{bounded, Receiver, Sender, TrySendError};
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::Duration;
+use voiceterm::{log_debug, log_warn};
+
+use super::bus::{SessionEventSink, VoiceSessionEvent};
+
+/// Events buffered for the hook before new ones are dropped.
+const HOOK_EVENT_CAPACITY: usize = 64;
+/// How long exit waits for queued events to reach a slow hook.
+const HOOK_FLUSH_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Running hook process; its stdin closes when the session ends.
+pub(crate) struct SessionHook {
+    program: String,
+    line_tx: Option<Sender<String>>,
+    /// Closed by the writer thread once every queued event is written.
+    flushed_rx: Receiver<()>,
+}
+
+impl SessionHook {
+    /// Start the hook; `raw` is split like a shell command line.
+    pub(crate) fn spawn(raw: &str) -> Result<Self> {
+        let parts = shell_words::split(raw.trim())
+            .with_context(|| format!("invalid session hook command: {raw}"))?;
+        let Some((program, args)) = parts.split_first() else {
+            bail!("session hook command is empty");
+        };
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            // Hook output would scribble over the overlay.
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .with_context(|| format!("failed to start session hook {program}"))?;
+        let mut stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| anyhow!("session hook stdin unavailable"))?;
+
+        let (line_tx, line_rx) = bounded::<String>(HOOK_EVENT_CAPACITY);
+        let (flushed_tx, flushed_rx) = bounded::<()>(0);
+        thread::spawn(move || {
+            for line in line_rx {
+                if let Err(err) = writeln!(stdin, "{line}").and_then(|()| stdin.flush()) {
+                    log_warn(&format!("session hook stdin closed: {err}"));
+                    break;
+                }
+            }
+            // Closing stdin tells the hook the session is over; reap it once it exits.
+            drop(stdin);
+            drop(flushed_tx);
+            let _ = child.wait();
+        });
+        Ok(Self {
+            program: program.clone(),
+            line_tx: Some(line_tx),
+            flushed_rx,
+        })
+    }
+
+    pub(crate) fn program(&self) -> &str {
+        &self.program
+    }
+}
+
+impl SessionEventSink for SessionHook {
+    fn on_event(&mut self, event: &VoiceSessionEvent) {
+        let Some(line_tx) = self.line_tx.as_ref() else {
+            return;
+        };
+        let Ok(line) = serde_json::to_string(event) else {
+            return;
+        };
+        if let Err(TrySendError::Full(_)) = line_tx.try_send(line) {
+            log_debug("session hook input full; dropping event");
+        }
+    }
+}
+
+impl Drop for SessionHook {
+    fn drop(&mut self) {
+        // Let queued events (including `session_ended`) reach the hook before exit.
+        self.line_tx.take();
+        let _ = self.flushed_rx.recv_timeout(HOOK_FLUSH_TIMEOUT);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs;
+    use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn spawn_rejects_empty_and_missing_commands() {
+        assert!(SessionHook::spawn("  ").is_err());
+        assert!(SessionHook::spawn("'unterminated").is_err());
+        assert!(SessionHook::spawn("voiceterm-no-such-hook").is_err());
+    }
+
+    #[test]
+    fn hook_receives_events_as_json_lines() {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        let path = env::temp_dir().join(format!("voiceterm-hook-{unique}.jsonl"));
+        let mut hook =
+            SessionHook::spawn(&format!("sh -c 'cat > {}'", path.display())).expect("spawn hook");
+        assert_eq!(hook.program(), "sh");
+        hook.on_event(&VoiceSessionEvent::CaptureStarted {
+            trigger: "manual".to_string(),
+            source: "native".to_string(),
+        });
+        hook.on_event(&VoiceSessionEvent::SessionEnded);
+        // Dropping closes the hook's stdin, so `cat` finishes the file and exits.
+        drop(hook);
+        let expected =
+            "{\"event\":\"capture_started\",\"trigger\":\"manual\",\"source\":\"native\"}\n\
+                        {\"event\":\"session_ended\"}\n";
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut lines = String::new();
+        while Instant::now() < deadline {
+            lines = fs::read_to_string(&path).unwrap_or_default();
+            if lines == expected {
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(lines, expected);
+        let _ = fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,21 @@
+//! Voice session lifecycle events so every consumer hears about the session the same way.
+//!
+//! The overlay emits a [`VoiceSessionEvent`] when the session starts, a capture
+//! starts, a dictation draft grows, a transcript is sent to the backend or the
+//! clipboard, the backend exits, and the session ends. Queued, held, dropped,
+//! and undelivered draft transcripts emit nothing. Subscribers registered at
+//! startup get every event in order: the transcript history writer, the control
+//! socket's `subscribe` stream, the `voice_session` log event, and
+//! `--session-hook`, a helper process that reads one JSON event per line on stdin.
+
+mod bus;
+mod hook;
+
+pub(crate) use bus::{
+    close_session_events, emit_session_event, subscribe_session_events, SessionEventSink,
+    VoiceSessionEvent,
+};
+pub(crate) use hook::SessionHook;
+
+#[cfg(test)]
+pub(crate) use bus::delivered_transcripts;
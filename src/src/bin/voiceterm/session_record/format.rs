@@ -0,0 +1,145 @@
+//! On-disk session record format (one JSON object per line).
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use voiceterm::VoiceCaptureSource;
+
+use crate::config::VoiceSendMode;
+use crate::input::InputEvent;
+
+/// One recorded event; `t_ms` is relative to the start of the recording.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct SessionRecord {
+    pub(crate) t_ms: u64,
+    #[serde(flatten)]
+    pub(crate) event: SessionEvent,
+}
+
+/// Events captured while the overlay runs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub(crate) enum SessionEvent {
+    /// Settings that affect prompt detection and transcript delivery.
+    SessionStart {
+        backend: String,
+        voice_send_mode: String,
+        auto_voice_idle_ms: u64,
+        transcript_idle_ms: u64,
+    },
+    /// Raw bytes read from the backend PTY.
+    PtyOutput { bytes: Vec<u8> },
+    /// Parsed input event; `bytes` is set for passthrough keystrokes.
+    Input {
+        event: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        bytes: Option<Vec<u8>>,
+    },
+    /// Transcript after macro expansion, with the send mode it will use.
+    VoiceTranscript {
+        text: String,
+        source: String,
+        mode: String,
+    },
+    /// Capture finished without speech.
+    VoiceEmpty { source: String },
+    /// Capture failed.
+    VoiceError { category: String, message: String },
+}
+
+pub(crate) fn send_mode_key(mode: VoiceSendMode) -> &'static str {
+    match mode {
+        VoiceSendMode::Auto => "auto",
+        VoiceSendMode::Insert => "insert",
+        VoiceSendMode::Clipboard => "clipboard",
+    }
+}
+
+pub(crate) fn parse_send_mode(value: &str) -> VoiceSendMode {
+    match value {
+        "insert" => VoiceSendMode::Insert,
+        "clipboard" => VoiceSendMode::Clipboard,
+        _ => VoiceSendMode::Auto,
+    }
+}
+
+pub(crate) fn source_key(source: VoiceCaptureSource) -> &'static str {
+    match source {
+        VoiceCaptureSource::Native => "native",
+        VoiceCaptureSource::Python => "python",
+    }
+}
+
+impl SessionEvent {
+    pub(crate) fn from_input(event: &InputEvent) -> Self {
+        match event {
+            InputEvent::Bytes(bytes) => SessionEvent::Input {
+                event: "bytes".to_string(),
+                bytes: Some(bytes.clone()),
+            },
+            InputEvent::EnterKey => SessionEvent::Input {
+                event: "enter".to_string(),
+                bytes: None,
+            },
+            other => SessionEvent::Input {
+                event: format!("{other:?}"),
+                bytes: None,
+            },
+        }
+    }
+}
+
+/// Read a session record file, skipping blank lines.
+pub(crate) fn read_session_records(path: &Path) -> Result<Vec<SessionRecord>> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("failed to read session record {}", path.display()))?;
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line).with_context(|| {
+                format!("invalid session record at {}:{}", path.display(), index + 1)
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_record_round_trips_as_tagged_json() {
+        let record = SessionRecord {
+            t_ms: 42,
+            event: SessionEvent::PtyOutput {
+                bytes: b"codex> ".to_vec(),
+            },
+        };
+        let line = serde_json::to_string(&record).unwrap();
+        assert!(line.contains("\"kind\":\"pty_output\""));
+        assert!(line.contains("\"t_ms\":42"));
+        let parsed: SessionRecord = serde_json::from_str(&line).unwrap();
+        assert_eq!(parsed, record);
+    }
+
+    #[test]
+    fn from_input_keeps_passthrough_bytes_and_names_actions() {
+        assert_eq!(
+            SessionEvent::from_input(&InputEvent::Bytes(vec![b'a'])),
+            SessionEvent::Input {
+                event: "bytes".to_string(),
+                bytes: Some(vec![b'a']),
+            }
+        );
+        assert_eq!(
+            SessionEvent::from_input(&InputEvent::VoiceTrigger),
+            SessionEvent::Input {
+                event: "VoiceTrigger".to_string(),
+                bytes: None,
+            }
+        );
+    }
+}
@@ -0,0 +1,13 @@
+//! Session recording and replay so prompt-detection bugs can be reproduced offline.
+//!
+//! `--session-record <PATH>` writes PTY output, input events, and voice results
+//! with timestamps as JSON lines. `voiceterm replay-session <PATH>` feeds them back
+//! through `PromptTracker` and the transcript flush logic on a simulated clock.
+
+mod format;
+mod recorder;
+mod replay;
+
+pub(crate) use format::{send_mode_key, source_key, SessionEvent};
+pub(crate) use recorder::{record_session_event, start_session_recording};
+pub(crate) use replay::run_session_replay;
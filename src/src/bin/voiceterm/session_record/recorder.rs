@@ -0,0 +1,66 @@
+//! Process-wide session recorder so PTY, input, and voice paths can log without plumbing.
+
+use anyhow::{Context, Result};
+use std::fs::File;
+use std::io::{LineWriter, Write};
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+use voiceterm::log_debug;
+
+use super::format::{SessionEvent, SessionRecord};
+
+struct SessionRecorder {
+    writer: LineWriter<File>,
+    started_at: Instant,
+}
+
+static RECORDER: OnceLock<Mutex<Option<SessionRecorder>>> = OnceLock::new();
+
+fn recorder_slot() -> &'static Mutex<Option<SessionRecorder>> {
+    RECORDER.get_or_init(|| Mutex::new(None))
+}
+
+/// Start recording to `path`, writing `header` as the first event.
+pub(crate) fn start_session_recording(path: &Path, header: SessionEvent) -> Result<()> {
+    let file = File::create(path)
+        .with_context(|| format!("failed to create session record {}", path.display()))?;
+    let mut recorder = SessionRecorder {
+        writer: LineWriter::new(file),
+        started_at: Instant::now(),
+    };
+    write_record(&mut recorder, header)?;
+    if let Ok(mut slot) = recorder_slot().lock() {
+        *slot = Some(recorder);
+    }
+    Ok(())
+}
+
+/// Record an event if recording is active; `build` only runs when it is.
+pub(crate) fn record_session_event(build: impl FnOnce() -> SessionEvent) {
+    let Some(slot) = RECORDER.get() else {
+        return;
+    };
+    let Ok(mut slot) = slot.lock() else {
+        return;
+    };
+    let Some(recorder) = slot.as_mut() else {
+        return;
+    };
+    if let Err(err) = write_record(recorder, build()) {
+        // Stop after the first failure instead of logging on every PTY chunk.
+        log_debug(&format!("session recording stopped: {err:#}"));
+        *slot = None;
+    }
+}
+
+fn write_record(recorder: &mut SessionRecorder, event: SessionEvent) -> Result<()> {
+    let record = SessionRecord {
+        t_ms: recorder.started_at.elapsed().as_millis() as u64,
+        event,
+    };
+    let mut line = serde_json::to_string(&record)?;
+    line.push('\n');
+    recorder.writer.write_all(line.as_bytes())?;
+    Ok(())
+}
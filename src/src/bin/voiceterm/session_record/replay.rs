@@ -0,0 +1,440 @@
+//! Deterministic replay of a recorded session through prompt detection and transcript flushing.
+
+use anyhow::{bail, Result};
+use crossbeam_channel::{unbounded, Receiver, Sender};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+use voiceterm::VoiceCaptureSource;
+
+use crate::config::{OverlayConfig, PromptConfig, ReplaySessionArgs, VoiceSendMode};
+use crate::prompt::{resolve_prompt_regex, PromptLogger, PromptTracker};
+use crate::status_line::StatusLineState;
+use crate::transcript::{
+    deliver_transcript, push_pending_transcript, transcript_ready, try_flush_pending,
+    MultilineDelivery, PendingTranscript, QueueOutcome, QueuePolicy, TranscriptIo,
+    TranscriptSession,
+};
+use crate::writer::WriterMessage;
+
+use super::format::{parse_send_mode, read_session_records, SessionEvent, SessionRecord};
+
+/// Simulated periodic-task interval; matches the live event loop tick.
+const REPLAY_TICK_MS: u64 = 50;
+/// How long to keep ticking after the last event so queued transcripts can flush.
+const REPLAY_DRAIN_MS: u64 = 10_000;
+
+/// Timing knobs used during replay (taken from the recording header when present).
+///
+/// Each recorded transcript carries its own send mode, so only timing and the
+/// queue policy are configurable.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ReplaySettings {
+    pub(crate) auto_idle_timeout: Duration,
+    pub(crate) transcript_idle_timeout: Duration,
+    pub(crate) queue: QueuePolicy,
+}
+
+impl ReplaySettings {
+    fn from_config(config: &OverlayConfig) -> Result<Self> {
+        Ok(Self {
+            auto_idle_timeout: Duration::from_millis(config.auto_voice_idle_ms.max(100)),
+            transcript_idle_timeout: Duration::from_millis(config.transcript_idle_ms.max(50)),
+            queue: QueuePolicy::from_config(config)?,
+        })
+    }
+}
+
+#[derive(Default)]
+struct CapturedSession {
+    sent: Vec<(String, bool)>,
+}
+
+impl TranscriptSession for CapturedSession {
+    fn send_text(&mut self, text: &str) -> Result<()> {
+        self.sent.push((text.to_string(), false));
+        Ok(())
+    }
+
+    fn send_text_with_newline(&mut self, text: &str) -> Result<()> {
+        self.sent.push((text.to_string(), true));
+        Ok(())
+    }
+}
+
+struct SessionReplay {
+    settings: ReplaySettings,
+    tracker: PromptTracker,
+    multiline: MultilineDelivery,
+    pending: VecDeque<PendingTranscript>,
+    last_enter_at: Option<Instant>,
+    session: CapturedSession,
+    writer_tx: Sender<WriterMessage>,
+    _writer_rx: Receiver<WriterMessage>,
+    status_clear_deadline: Option<Instant>,
+    current_status: Option<String>,
+    status_state: StatusLineState,
+    base: Instant,
+    last_prompt_seen_at: Option<Instant>,
+    last_reported_prompt_at: Option<Instant>,
+    reported_sends: usize,
+    prompts_detected: usize,
+    lines: Vec<String>,
+}
+
+impl SessionReplay {
+    fn new(tracker: PromptTracker, multiline: MultilineDelivery, settings: ReplaySettings) -> Self {
+        let (writer_tx, writer_rx) = unbounded();
+        Self {
+            settings,
+            tracker,
+            multiline,
+            pending: VecDeque::new(),
+            last_enter_at: None,
+            session: CapturedSession::default(),
+            writer_tx,
+            _writer_rx: writer_rx,
+            status_clear_deadline: None,
+            current_status: None,
+            status_state: StatusLineState::new(),
+            base: Instant::now(),
+            last_prompt_seen_at: None,
+            last_reported_prompt_at: None,
+            reported_sends: 0,
+            prompts_detected: 0,
+            lines: Vec::new(),
+        }
+    }
+
+    fn log(&mut self, now: Instant, message: impl AsRef<str>) {
+        let elapsed = now.duration_since(self.base).as_secs_f64();
+        self.lines
+            .push(format!("[{elapsed:>9.3}s] {}", message.as_ref()));
+    }
+
+    fn io(&mut self) -> TranscriptIo<'_, CapturedSession> {
+        TranscriptIo {
+            session: &mut self.session,
+            writer_tx: &self.writer_tx,
+            status_clear_deadline: &mut self.status_clear_deadline,
+            current_status: &mut self.current_status,
+            status_state: &mut self.status_state,
+            multiline: &self.multiline,
+        }
+    }
+
+    fn flush(&mut self, now: Instant) {
+        let mut io = TranscriptIo {
+            session: &mut self.session,
+            writer_tx: &self.writer_tx,
+            status_clear_deadline: &mut self.status_clear_deadline,
+            current_status: &mut self.current_status,
+            status_state: &mut self.status_state,
+            multiline: &self.multiline,
+        };
+        try_flush_pending(
+            &mut self.pending,
+            &self.tracker,
+            &mut self.last_enter_at,
+            &mut io,
+            now,
+            self.settings.transcript_idle_timeout,
+        );
+    }
+
+    fn tick(&mut self, now: Instant) {
+        self.tracker.on_idle(now, self.settings.auto_idle_timeout);
+        self.flush(now);
+        self.report_changes(now);
+    }
+
+    fn report_changes(&mut self, now: Instant) {
+        let prompt_seen = self.tracker.last_prompt_seen_at();
+        if prompt_seen.is_some() && prompt_seen != self.last_prompt_seen_at {
+            // Idle re-matches of the same prompt line refresh the timestamp; only
+            // report a prompt when output or Enter happened since the last one.
+            let activity = self.tracker.last_pty_output_at().max(self.last_enter_at);
+            let is_new = match self.last_reported_prompt_at {
+                None => true,
+                Some(reported) => activity.is_some_and(|activity| activity > reported),
+            };
+            self.last_prompt_seen_at = prompt_seen;
+            if is_new {
+                self.last_reported_prompt_at = prompt_seen;
+                self.prompts_detected += 1;
+                self.log(now, "prompt detected");
+            }
+        }
+        while self.reported_sends < self.session.sent.len() {
+            let (text, newline) = self.session.sent[self.reported_sends].clone();
+            self.reported_sends += 1;
+            let how = if newline { "sent+enter" } else { "inserted" };
+            self.log(now, format!("transcript {how}: {text:?}"));
+        }
+    }
+
+    fn apply(&mut self, event: &SessionEvent, now: Instant) {
+        match event {
+            SessionEvent::SessionStart { backend, .. } => {
+                self.log(now, format!("session start (backend {backend})"));
+            }
+            SessionEvent::PtyOutput { bytes } => {
+                self.tracker.feed_output_at(bytes, now);
+                self.multiline.observe_output(bytes);
+                self.flush(now);
+            }
+            SessionEvent::Input { event, .. } => {
+                if event == "enter" {
+                    self.last_enter_at = Some(now);
+                    self.log(now, "enter key");
+                }
+            }
+            SessionEvent::VoiceTranscript { text, source, mode } => {
+                self.apply_transcript(text, source, parse_send_mode(mode), now);
+            }
+            SessionEvent::VoiceEmpty { source } => {
+                self.log(now, format!("voice capture empty ({source})"));
+            }
+            SessionEvent::VoiceError { category, message } => {
+                self.log(now, format!("voice capture error ({category}): {message}"));
+            }
+        }
+        self.report_changes(now);
+    }
+
+    /// Mirrors the overlay drain path: deliver immediately when ready, else queue.
+    fn apply_transcript(&mut self, text: &str, source: &str, mode: VoiceSendMode, now: Instant) {
+        let source = if source == "python" {
+            VoiceCaptureSource::Python
+        } else {
+            VoiceCaptureSource::Native
+        };
+        let ready = transcript_ready(
+            &self.tracker,
+            self.last_enter_at,
+            now,
+            self.settings.transcript_idle_timeout,
+        );
+        if ready && self.pending.is_empty() {
+            if deliver_transcript(text, source.label(), mode, &mut self.io(), 0, None) {
+                self.last_enter_at = Some(now);
+            }
+            return;
+        }
+        let outcome = push_pending_transcript(
+            &mut self.pending,
+            PendingTranscript {
+                text: text.to_string(),
+                label: source.label(),
+                mode,
+                received_at: now,
+                seq: None,
+            },
+            self.settings.queue,
+        );
+        let depth = self.pending.len();
+        match outcome {
+            QueueOutcome::Queued => self.log(
+                now,
+                format!("transcript queued ({depth} pending): {text:?}"),
+            ),
+            QueueOutcome::Merged => self.log(
+                now,
+                format!("transcript merged ({depth} pending): {text:?}"),
+            ),
+            QueueOutcome::Dropped(dropped) => self.log(
+                now,
+                format!(
+                    "transcript queue full ({depth} pending): dropped {:?}",
+                    dropped.text
+                ),
+            ),
+        }
+        if ready {
+            self.flush(now);
+        }
+    }
+}
+
+/// Replay records on a simulated clock and return a human-readable timeline.
+pub(crate) fn replay_records(
+    records: &[SessionRecord],
+    tracker: PromptTracker,
+    multiline: MultilineDelivery,
+    settings: ReplaySettings,
+) -> Vec<String> {
+    let mut settings = settings;
+    if let Some(SessionEvent::SessionStart {
+        auto_voice_idle_ms,
+        transcript_idle_ms,
+        ..
+    }) = records.first().map(|record| &record.event)
+    {
+        settings.auto_idle_timeout = Duration::from_millis((*auto_voice_idle_ms).max(100));
+        settings.transcript_idle_timeout = Duration::from_millis((*transcript_idle_ms).max(50));
+    }
+    let mut replay = SessionReplay::new(tracker, multiline, settings);
+    let tick = Duration::from_millis(REPLAY_TICK_MS);
+    let mut clock = replay.base;
+
+    for record in records {
+        let at = replay.base + Duration::from_millis(record.t_ms);
+        while clock + tick <= at {
+            clock += tick;
+            replay.tick(clock);
+        }
+        clock = clock.max(at);
+        replay.apply(&record.event, clock);
+    }
+
+    let drain_deadline = clock + Duration::from_millis(REPLAY_DRAIN_MS);
+    while !replay.pending.is_empty() && clock < drain_deadline {
+        clock += tick;
+        replay.tick(clock);
+    }
+
+    let summary = format!(
+        "replayed {} events: {} prompts detected, {} transcripts delivered, {} still queued",
+        records.len(),
+        replay.prompts_detected,
+        replay.session.sent.len(),
+        replay.pending.len()
+    );
+    replay.log(clock, summary);
+    replay.lines
+}
+
+/// Entry point for `voiceterm replay-session FILE`.
+pub(crate) fn run_session_replay(
+    config: &OverlayConfig,
+    args: &ReplaySessionArgs,
+    prompt: &PromptConfig,
+) -> Result<()> {
+    let records = read_session_records(&args.file)?;
+    if records.is_empty() {
+        bail!("session record {} is empty", args.file.display());
+    }
+    let backend = config.resolve_backend();
+    let prompt_regex = resolve_prompt_regex(config, prompt, backend.prompt_pattern.as_deref())?;
+    let tracker = PromptTracker::new(
+        prompt_regex.patterns,
+        prompt_regex.allow_auto_learn,
+        PromptLogger::new(None),
+    )
+    .with_never_patterns(prompt_regex.never_patterns);
+    let multiline = MultilineDelivery::new(config.multiline_mode, &backend.label)
+        .with_bracketed_paste(config.bracketed_paste);
+    for line in replay_records(
+        &records,
+        tracker,
+        multiline,
+        ReplaySettings::from_config(config)?,
+    ) {
+        println!("{line}");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use regex::Regex;
+
+    fn record(t_ms: u64, event: SessionEvent) -> SessionRecord {
+        SessionRecord { t_ms, event }
+    }
+
+    fn settings() -> ReplaySettings {
+        ReplaySettings {
+            auto_idle_timeout: Duration::from_millis(300),
+            transcript_idle_timeout: Duration::from_millis(100),
+            queue: QueuePolicy::default(),
+        }
+    }
+
+    fn transcript(text: &str) -> SessionEvent {
+        SessionEvent::VoiceTranscript {
+            text: text.to_string(),
+            source: "native".to_string(),
+            mode: "auto".to_string(),
+        }
+    }
+
+    #[test]
+    fn replay_queues_transcript_until_prompt_returns() {
+        let tracker = PromptTracker::new(
+            vec![Regex::new(r"^codex> $").unwrap()],
+            false,
+            PromptLogger::new(None),
+        );
+        let records = vec![
+            record(
+                0,
+                SessionEvent::PtyOutput {
+                    bytes: b"codex> \n".to_vec(),
+                },
+            ),
+            record(100, transcript("first")),
+            record(
+                150,
+                SessionEvent::PtyOutput {
+                    bytes: b"working...\n".to_vec(),
+                },
+            ),
+            record(200, transcript("second")),
+            record(
+                220,
+                SessionEvent::PtyOutput {
+                    bytes: b"still working\n".to_vec(),
+                },
+            ),
+            record(
+                2_000,
+                SessionEvent::PtyOutput {
+                    bytes: b"codex> \n".to_vec(),
+                },
+            ),
+        ];
+
+        let lines = replay_records(&records, tracker, MultilineDelivery::default(), settings());
+        let timeline = lines.join("\n");
+
+        let first = timeline.find("sent+enter: \"first\"").expect(&timeline);
+        let queued = timeline
+            .find("transcript queued (1 pending): \"second\"")
+            .expect(&timeline);
+        let second = timeline.find("sent+enter: \"second\"").expect(&timeline);
+        assert!(first < queued && queued < second, "{timeline}");
+        assert!(
+            lines
+                .last()
+                .unwrap()
+                .contains("2 prompts detected, 2 transcripts delivered, 0 still queued"),
+            "{timeline}"
+        );
+    }
+
+    #[test]
+    fn replay_is_deterministic() {
+        let records = vec![
+            record(
+                0,
+                SessionEvent::PtyOutput {
+                    bytes: b"> ".to_vec(),
+                },
+            ),
+            record(400, transcript("hello")),
+            record(
+                450,
+                SessionEvent::Input {
+                    event: "enter".to_string(),
+                    bytes: None,
+                },
+            ),
+        ];
+        let run = || {
+            let tracker = PromptTracker::new(Vec::new(), true, PromptLogger::new(None));
+            replay_records(&records, tracker, MultilineDelivery::default(), settings())
+        };
+        assert_eq!(run(), run());
+    }
+}
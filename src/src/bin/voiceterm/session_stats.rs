@@ -5,6 +5,7 @@
 use std::time::{Duration, Instant};
 
 use crate::theme::{Theme, ThemeColors};
+use crate::voice_metrics::{format_latency_table, VoiceMetrics};
 
 /// Statistics for a voice capture session.
 #[derive(Debug, Clone, Default)]
@@ -17,6 +18,8 @@ pub struct SessionStats {
     pub errors: u32,
     /// Total speech duration in seconds
     pub total_speech_secs: f32,
+    /// Per-capture latency samples
+    pub voice_metrics: VoiceMetrics,
     /// Session start time
     start_time: Option<Instant>,
 }
@@ -120,6 +123,13 @@ pub fn format_session_stats(stats: &SessionStats, theme: Theme) -> String {
     let session_dur = format_duration(stats.session_duration().as_secs_f32());
     lines.push(format_stat_line(&colors, "Session", &session_dur, ""));
 
+    // Latency table (only when captures reported timings)
+    let latency = format_latency_table(&stats.voice_metrics, &colors);
+    if !latency.is_empty() {
+        lines.push(String::new());
+        lines.extend(latency);
+    }
+
     lines.push(String::new()); // Empty line after
 
     lines.join("\n")
@@ -282,6 +292,22 @@ mod tests {
         assert!(output.contains("Errors"));
     }
 
+    #[test]
+    fn format_session_stats_appends_latency_table() {
+        let mut stats = SessionStats::new();
+        stats.record_transcript(1.0);
+        let output = format_session_stats(&stats, Theme::None);
+        assert!(!output.contains("Latency"));
+
+        stats
+            .voice_metrics
+            .record_delivery(Duration::from_millis(30));
+        let output = format_session_stats(&stats, Theme::None);
+        assert!(output.contains("Latency"));
+        assert!(output.contains("Delivery"));
+        assert!(output.contains("30ms"));
+    }
+
     #[test]
     fn format_separator_is_stable() {
         assert_eq!(format_separator(), "───────────────");
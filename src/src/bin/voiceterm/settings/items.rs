@@ -3,13 +3,18 @@
 use crate::config::{HudBorderStyle, HudRightPanel, HudStyle, LatencyDisplayMode, VoiceSendMode};
 use crate::status_line::Pipeline;
 use crate::theme::Theme;
+use voiceterm::config::VadEngineKind;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SettingsItem {
     AutoVoice,
     SendMode,
     Macros,
+    CodeMode,
     Sensitivity,
+    SilenceTail,
+    VadEngine,
+    Model,
     Theme,
     HudStyle,
     HudBorders,
@@ -19,6 +24,7 @@ pub enum SettingsItem {
     Mouse,
     Backend,
     Pipeline,
+    Save,
     Close,
     Quit,
 }
@@ -27,7 +33,11 @@ pub const SETTINGS_ITEMS: &[SettingsItem] = &[
     SettingsItem::AutoVoice,
     SettingsItem::SendMode,
     SettingsItem::Macros,
+    SettingsItem::CodeMode,
     SettingsItem::Sensitivity,
+    SettingsItem::SilenceTail,
+    SettingsItem::VadEngine,
+    SettingsItem::Model,
     SettingsItem::Theme,
     SettingsItem::HudStyle,
     SettingsItem::HudBorders,
@@ -37,6 +47,7 @@ pub const SETTINGS_ITEMS: &[SettingsItem] = &[
     SettingsItem::Mouse,
     SettingsItem::Backend,
     SettingsItem::Pipeline,
+    SettingsItem::Save,
     SettingsItem::Close,
     SettingsItem::Quit,
 ];
@@ -61,7 +72,12 @@ pub struct SettingsView<'a> {
     pub auto_voice_enabled: bool,
     pub send_mode: VoiceSendMode,
     pub macros_enabled: bool,
+    pub code_mode: bool,
     pub sensitivity_db: f32,
+    pub silence_tail_ms: u64,
+    pub vad_engine: VadEngineKind,
+    /// Whisper model file path; `None` when transcription uses the Python fallback.
+    pub whisper_model: Option<&'a str>,
     pub theme: Theme,
     pub hud_style: HudStyle,
     pub hud_border_style: HudBorderStyle,
@@ -69,6 +69,12 @@ fn format_settings_row(
             toggle_button(view.macros_enabled),
             width = LABEL_WIDTH
         ),
+        SettingsItem::CodeMode => format!(
+            "{marker} {:<width$} {}",
+            "Code mode",
+            toggle_button(view.code_mode),
+            width = LABEL_WIDTH
+        ),
         SettingsItem::Sensitivity => {
             let slider = format_slider(view.sensitivity_db, 14);
             format!(
@@ -78,6 +84,24 @@ fn format_settings_row(
                 width = LABEL_WIDTH
             )
         }
+        SettingsItem::SilenceTail => format!(
+            "{marker} {:<width$} {}",
+            "Silence tail",
+            button_label(&format!("{} ms", view.silence_tail_ms)),
+            width = LABEL_WIDTH
+        ),
+        SettingsItem::VadEngine => format!(
+            "{marker} {:<width$} {}",
+            "VAD engine",
+            button_label(view.vad_engine.label()),
+            width = LABEL_WIDTH
+        ),
+        SettingsItem::Model => format!(
+            "{marker} {:<width$} {}",
+            "Model",
+            button_label(&model_label(view.whisper_model)),
+            width = LABEL_WIDTH
+        ),
         SettingsItem::Theme => format!(
             "{marker} {:<width$} {}",
             "Theme",
@@ -132,6 +156,7 @@ fn format_settings_row(
             pipeline_label(view.pipeline),
             width = LABEL_WIDTH
         ),
+        SettingsItem::Save => format!("{marker} {}", button_label("Save to config")),
         SettingsItem::Close => format!("{marker} {}", button_label("Close")),
         SettingsItem::Quit => format!("{marker} {}", button_label("Quit VoiceTerm")),
     };
@@ -139,6 +164,17 @@ fn format_settings_row(
     format_menu_row(colors, width, &row_text, selected)
 }
 
+/// Model file name without the directory, e.g. `ggml-base.en.bin`.
+fn model_label(path: Option<&str>) -> String {
+    match path {
+        Some(path) => std::path::Path::new(path)
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.to_string()),
+        None => "Python".to_string(),
+    }
+}
+
 fn pipeline_label(pipeline: Pipeline) -> &'static str {
     match pipeline {
         Pipeline::Rust => "Rust",
@@ -158,6 +194,7 @@ fn mode_button(mode: VoiceSendMode) -> String {
     match mode {
         VoiceSendMode::Auto => button_label("Auto"),
         VoiceSendMode::Insert => button_label("Edit"),
+        VoiceSendMode::Clipboard => button_label("Clip"),
     }
 }
 
@@ -311,6 +348,7 @@ mod tests {
     use crate::settings::settings_overlay_height;
     use crate::status_line::Pipeline;
     use crate::theme::Theme;
+    use voiceterm::config::VadEngineKind;
 
     #[test]
     fn settings_overlay_height_matches_items() {
@@ -325,7 +363,11 @@ mod tests {
             auto_voice_enabled: false,
             send_mode: VoiceSendMode::Insert,
             macros_enabled: true,
+            code_mode: false,
             sensitivity_db: -35.0,
+            silence_tail_ms: 800,
+            vad_engine: VadEngineKind::Simple,
+            whisper_model: Some("/models/ggml-base.en.bin"),
             theme: Theme::Coral,
             hud_style: HudStyle::Full,
             hud_border_style: HudBorderStyle::Theme,
@@ -340,5 +382,10 @@ mod tests {
         assert!(rendered.contains("Send mode"));
         assert!(rendered.contains("[ Edit ]"));
         assert!(!rendered.contains("[ Insert ]"));
+        assert!(rendered.contains("VAD engine"));
+        assert!(rendered.contains("[ simple ]"));
+        assert!(rendered.contains("[ 800 ms ]"));
+        assert!(rendered.contains("[ ggml-base.en.bin ]"));
+        assert!(rendered.contains("Save to config"));
     }
 }
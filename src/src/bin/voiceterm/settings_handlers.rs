@@ -1,15 +1,21 @@
 //! Settings action handlers so runtime config and HUD state change atomically.
 
+use std::fs;
+use std::path::Path;
 use std::time::{Duration, Instant};
 
+use clap::ValueEnum;
 use crossbeam_channel::Sender;
+use serde_yaml::Value;
+use voiceterm::config::VadEngineKind;
 use voiceterm::pty_session::PtyOverlaySession;
 use voiceterm::VoiceCaptureTrigger;
 
 use crate::button_handlers::update_button_registry;
 use crate::buttons::ButtonRegistry;
 use crate::config::{
-    HudBorderStyle, HudRightPanel, HudStyle, LatencyDisplayMode, OverlayConfig, VoiceSendMode,
+    default_config_path, save_settings, HudBorderStyle, HudRightPanel, HudStyle,
+    LatencyDisplayMode, OverlayConfig, VoiceSendMode,
 };
 use crate::log_debug;
 use crate::overlays::OverlayMode;
@@ -19,6 +25,7 @@ use crate::theme::Theme;
 use crate::theme_ops::{apply_theme_selection, cycle_theme};
 use crate::voice_control::{
     clear_capture_metrics, reset_capture_visuals, start_voice_capture, VoiceManager,
+    MODEL_WARMUP_STATUS,
 };
 use crate::writer::{set_status, WriterMessage};
 
@@ -146,12 +153,14 @@ impl<'a> SettingsActionContext<'a> {
     pub(crate) fn toggle_send_mode(&mut self) {
         self.config.voice_send_mode = match self.config.voice_send_mode {
             VoiceSendMode::Auto => VoiceSendMode::Insert,
-            VoiceSendMode::Insert => VoiceSendMode::Auto,
+            VoiceSendMode::Insert => VoiceSendMode::Clipboard,
+            VoiceSendMode::Clipboard => VoiceSendMode::Auto,
         };
         self.status_state.send_mode = self.config.voice_send_mode;
         let msg = match self.config.voice_send_mode {
             VoiceSendMode::Auto => "Send mode: auto (sends Enter)",
             VoiceSendMode::Insert => "Edit mode: press Enter to send",
+            VoiceSendMode::Clipboard => "Clipboard mode: transcripts are copied, not typed",
         };
         set_status(
             self.writer_tx,
@@ -180,6 +189,23 @@ impl<'a> SettingsActionContext<'a> {
         );
     }
 
+    pub(crate) fn toggle_code_mode(&mut self) {
+        self.status_state.code_mode = !self.status_state.code_mode;
+        let msg = if self.status_state.code_mode {
+            "Code mode: ON (say \"new line\", \"indent\", \"open brace\")"
+        } else {
+            "Code mode: OFF"
+        };
+        set_status(
+            self.writer_tx,
+            self.status_clear_deadline,
+            self.current_status,
+            self.status_state,
+            msg,
+            Some(Duration::from_secs(3)),
+        );
+    }
+
     pub(crate) fn adjust_sensitivity(&mut self, delta_db: f32) {
         let threshold_db = self.voice_manager.adjust_sensitivity(delta_db);
         self.status_state.sensitivity_db = threshold_db;
@@ -199,6 +225,150 @@ impl<'a> SettingsActionContext<'a> {
         );
     }
 
+    pub(crate) fn adjust_silence_tail(&mut self, delta_ms: i64) {
+        let tail_ms = self.voice_manager.adjust_silence_tail(delta_ms);
+        self.config.app.voice_silence_tail_ms = tail_ms;
+        let msg = format!("Silence tail: {tail_ms} ms");
+        set_status(
+            self.writer_tx,
+            self.status_clear_deadline,
+            self.current_status,
+            self.status_state,
+            &msg,
+            Some(Duration::from_secs(3)),
+        );
+    }
+
+    /// Switch to the next Whisper model file in the current model's directory.
+    pub(crate) fn cycle_whisper_model(&mut self, direction: i32) {
+        let Some(current) = self.voice_manager.whisper_model_path().map(str::to_string) else {
+            set_status(
+                self.writer_tx,
+                self.status_clear_deadline,
+                self.current_status,
+                self.status_state,
+                "Model: none configured (Python fallback)",
+                Some(Duration::from_secs(3)),
+            );
+            return;
+        };
+        let models = whisper_models_beside(&current);
+        let next = cycle_whisper_model(&models, &current, direction);
+        if next == current {
+            set_status(
+                self.writer_tx,
+                self.status_clear_deadline,
+                self.current_status,
+                self.status_state,
+                "Model: no other ggml-*.bin models next to the current one",
+                Some(Duration::from_secs(3)),
+            );
+            return;
+        }
+        self.begin_model_switch(next);
+    }
+
+    /// Toggle between `--model-fast` and `--model-accurate` (the `switch_model` key).
+    ///
+    /// Without both flags this steps to the next model beside the current one,
+    /// like the settings menu.
+    pub(crate) fn switch_model_profile(&mut self) {
+        let (Some(fast), Some(accurate)) = (
+            self.config.app.model_fast.clone(),
+            self.config.app.model_accurate.clone(),
+        ) else {
+            self.cycle_whisper_model(1);
+            return;
+        };
+        let current = self.voice_manager.whisper_model_path();
+        let next = toggle_model_profile(current, &fast, &accurate);
+        if current == Some(next) {
+            set_status(
+                self.writer_tx,
+                self.status_clear_deadline,
+                self.current_status,
+                self.status_state,
+                "Model: --model-fast and --model-accurate are the same file",
+                Some(Duration::from_secs(3)),
+            );
+            return;
+        }
+        self.begin_model_switch(next.to_string());
+    }
+
+    fn begin_model_switch(&mut self, next: String) {
+        let (msg, duration) = match self.voice_manager.switch_whisper_model(next.clone()) {
+            Ok(()) => {
+                self.config.app.whisper_model_path = Some(next);
+                (MODEL_WARMUP_STATUS.to_string(), None)
+            }
+            Err(err) => (format!("Model: {err}"), Some(Duration::from_secs(3))),
+        };
+        set_status(
+            self.writer_tx,
+            self.status_clear_deadline,
+            self.current_status,
+            self.status_state,
+            &msg,
+            duration,
+        );
+    }
+
+    /// Write the menu's current values to the config file's `settings` section.
+    pub(crate) fn save_settings(&mut self) {
+        let msg = match self.config.config_path.clone().or_else(default_config_path) {
+            Some(path) => {
+                match save_settings(&path, &persisted_settings(self.config, self.status_state)) {
+                    Ok(()) => format!("Settings saved to {}", path.display()),
+                    Err(err) => {
+                        log_debug(&format!("settings save failed: {err:#}"));
+                        format!("Settings not saved: {err}")
+                    }
+                }
+            }
+            None => "Settings not saved: no config path (set --config or HOME)".to_string(),
+        };
+        set_status(
+            self.writer_tx,
+            self.status_clear_deadline,
+            self.current_status,
+            self.status_state,
+            &msg,
+            Some(Duration::from_secs(4)),
+        );
+    }
+
+    pub(crate) fn cycle_vad_engine(&mut self, direction: i32) {
+        let available = VadEngineKind::available();
+        if available.len() < 2 {
+            set_status(
+                self.writer_tx,
+                self.status_clear_deadline,
+                self.current_status,
+                self.status_state,
+                "VAD engine: only simple is built in",
+                Some(Duration::from_secs(3)),
+            );
+            return;
+        }
+        let next = cycle_vad_engine(self.voice_manager.vad_engine(), direction);
+        self.voice_manager.set_vad_engine(next);
+        self.config.app.voice_vad_engine = next;
+        let msg = if self.voice_manager.is_idle() {
+            format!("VAD engine: {}", next.label())
+        } else {
+            format!("VAD engine: {} (from next capture)", next.label())
+        };
+        set_status(
+            self.writer_tx,
+            self.status_clear_deadline,
+            self.current_status,
+            self.status_state,
+            &msg,
+            Some(Duration::from_secs(3)),
+        );
+    }
+
     pub(crate) fn cycle_theme(&mut self, direction: i32) {
         let next = cycle_theme(*self.theme, direction);
         *self.theme = apply_theme_selection(
@@ -379,6 +549,103 @@ fn cycle_hud_style(current: HudStyle, direction: i32) -> HudStyle {
     OPTIONS[next]
 }
 
+fn cycle_vad_engine(current: VadEngineKind, direction: i32) -> VadEngineKind {
+    let options = VadEngineKind::available();
+    let len = options.len() as i32;
+    let idx = options
+        .iter()
+        .position(|engine| *engine == current)
+        .unwrap_or(0) as i32;
+    let next = (idx + direction).rem_euclid(len) as usize;
+    options[next]
+}
+
+/// Config-file `settings` entries (long flag name -> value) for what the menu can change.
+fn persisted_settings(
+    config: &OverlayConfig,
+    status_state: &StatusLineState,
+) -> Vec<(&'static str, Value)> {
+    let mut settings = vec![
+        ("auto-voice", Value::from(status_state.auto_voice_enabled)),
+        ("voice-send-mode", value_name(config.voice_send_mode)),
+        (
+            "voice-vad-threshold-db",
+            Value::from(f64::from(status_state.sensitivity_db.round())),
+        ),
+        (
+            "voice-silence-tail-ms",
+            Value::from(config.app.voice_silence_tail_ms),
+        ),
+        (
+            "voice-vad-engine",
+            Value::from(config.app.voice_vad_engine.label()),
+        ),
+        ("hud-style", value_name(status_state.hud_style)),
+        ("hud-border-style", value_name(config.hud_border_style)),
+        ("hud-right-panel", value_name(config.hud_right_panel)),
+        ("latency-display", value_name(config.latency_display)),
+    ];
+    if let Some(theme) = &config.theme_name {
+        settings.push(("theme", Value::from(theme.as_str())));
+    }
+    if let Some(model) = &config.app.whisper_model_path {
+        settings.push(("whisper-model-path", Value::from(model.as_str())));
+    }
+    settings
+}
+
+/// The name clap accepts for a value-enum flag, e.g. `minimal` for `HudStyle::Minimal`.
+fn value_name<T: ValueEnum>(value: T) -> Value {
+    value
+        .to_possible_value()
+        .map(|possible| Value::from(possible.get_name()))
+        .unwrap_or(Value::Null)
+}
+
+/// `current` plus the Whisper model files (`ggml-*.bin`) beside it, sorted by name.
+fn whisper_models_beside(current: &str) -> Vec<String> {
+    let dir = Path::new(current).parent().unwrap_or(Path::new(""));
+    let listing = if dir.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        dir
+    };
+    let mut models: Vec<String> = fs::read_dir(listing)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            (name.starts_with("ggml-") && name.ends_with(".bin"))
+                .then(|| dir.join(name).to_string_lossy().into_owned())
+        })
+        .collect();
+    if !models.iter().any(|model| model == current) {
+        models.push(current.to_string());
+    }
+    models.sort();
+    models
+}
+
+/// The accurate model while the fast one is active, otherwise the fast one.
+fn toggle_model_profile<'a>(current: Option<&str>, fast: &'a str, accurate: &'a str) -> &'a str {
+    if current == Some(fast) {
+        accurate
+    } else {
+        fast
+    }
+}
+
+fn cycle_whisper_model(models: &[String], current: &str, direction: i32) -> String {
+    match models.iter().position(|model| model == current) {
+        Some(idx) => {
+            let next = (idx as i32 + direction).rem_euclid(models.len() as i32) as usize;
+            models[next].clone()
+        }
+        None => current.to_string(),
+    }
+}
+
 fn cycle_latency_display(current: LatencyDisplayMode, direction: i32) -> LatencyDisplayMode {
     const OPTIONS: &[LatencyDisplayMode] = &[
         LatencyDisplayMode::Short,
@@ -498,6 +765,38 @@ mod tests {
         status_state.meter_db = Some(-40.0);
         status_state.meter_levels.push(-40.0);
         status_state.transcript_preview = Some("stale preview".to_string());
+        {
+            let mut ctx = make_context(
+                &mut config,
+                &mut voice_manager,
+                &writer_tx,
+                &mut status_clear_deadline,
+                &mut current_status,
+                &mut status_state,
+                &mut auto_voice_enabled,
+                &mut last_auto_trigger_at,
+                &mut recording_started_at,
+                &mut preview_clear_deadline,
+                &mut last_meter_update,
+                &button_registry,
+                &mut terminal_rows,
+                &mut terminal_cols,
+                &mut theme,
+            );
+            ctx.toggle_send_mode();
+        }
+        assert_eq!(config.voice_send_mode, VoiceSendMode::Clipboard);
+        assert_eq!(status_state.send_mode, VoiceSendMode::Clipboard);
+        match writer_rx
+            .recv_timeout(Duration::from_millis(200))
+            .expect("status message")
+        {
+            WriterMessage::EnhancedStatus(state) => {
+                assert!(state.message.contains("Clipboard"));
+            }
+            other => panic!("unexpected writer message: {other:?}"),
+        }
+
         {
             let mut ctx = make_context(
                 &mut config,
@@ -612,6 +911,57 @@ mod tests {
         }
     }
 
+    #[test]
+    fn toggle_code_mode_updates_state_and_status() {
+        let mut config = OverlayConfig::parse_from(["test-app"]);
+        let mut voice_manager = VoiceManager::new(config.app.clone());
+        let (writer_tx, writer_rx) = bounded(4);
+        let mut status_clear_deadline = None;
+        let mut current_status = None;
+        let mut status_state = StatusLineState::new();
+        let mut auto_voice_enabled = false;
+        let mut last_auto_trigger_at = None;
+        let mut recording_started_at = None;
+        let mut preview_clear_deadline = None;
+        let mut last_meter_update = Instant::now();
+        let button_registry = ButtonRegistry::new();
+        let mut terminal_rows = 24;
+        let mut terminal_cols = 80;
+        let mut theme = Theme::Coral;
+        assert!(!status_state.code_mode);
+
+        {
+            let mut ctx = make_context(
+                &mut config,
+                &mut voice_manager,
+                &writer_tx,
+                &mut status_clear_deadline,
+                &mut current_status,
+                &mut status_state,
+                &mut auto_voice_enabled,
+                &mut last_auto_trigger_at,
+                &mut recording_started_at,
+                &mut preview_clear_deadline,
+                &mut last_meter_update,
+                &button_registry,
+                &mut terminal_rows,
+                &mut terminal_cols,
+                &mut theme,
+            );
+            ctx.toggle_code_mode();
+        }
+        assert!(status_state.code_mode);
+        match writer_rx
+            .recv_timeout(Duration::from_millis(200))
+            .expect("status message")
+        {
+            WriterMessage::EnhancedStatus(state) => {
+                assert!(state.message.contains("Code mode: ON"));
+            }
+            other => panic!("unexpected writer message: {other:?}"),
+        }
+    }
+
     #[test]
     fn adjust_sensitivity_updates_threshold_and_message() {
         let mut config = OverlayConfig::parse_from(["test-app"]);
@@ -898,6 +1248,66 @@ mod tests {
         }
     }
 
+    #[test]
+    fn cycle_vad_engine_wraps_available_engines() {
+        for &engine in VadEngineKind::available() {
+            let forward = cycle_vad_engine(engine, 1);
+            assert!(VadEngineKind::available().contains(&forward));
+            assert_eq!(cycle_vad_engine(forward, -1), engine);
+        }
+    }
+
+    #[cfg(feature = "vad_earshot")]
+    #[test]
+    fn cycle_vad_engine_updates_manager_config_and_status() {
+        let mut config = OverlayConfig::parse_from(["test-app", "--voice-vad-engine", "simple"]);
+        let mut voice_manager = VoiceManager::new(config.app.clone());
+        let (writer_tx, writer_rx) = bounded(4);
+        let mut status_clear_deadline = None;
+        let mut current_status = None;
+        let mut status_state = StatusLineState::new();
+        let mut auto_voice_enabled = false;
+        let mut last_auto_trigger_at = None;
+        let mut recording_started_at = None;
+        let mut preview_clear_deadline = None;
+        let mut last_meter_update = Instant::now();
+        let button_registry = ButtonRegistry::new();
+        let mut terminal_rows = 24;
+        let mut terminal_cols = 80;
+        let mut theme = Theme::Coral;
+
+        let mut ctx = make_context(
+            &mut config,
+            &mut voice_manager,
+            &writer_tx,
+            &mut status_clear_deadline,
+            &mut current_status,
+            &mut status_state,
+            &mut auto_voice_enabled,
+            &mut last_auto_trigger_at,
+            &mut recording_started_at,
+            &mut preview_clear_deadline,
+            &mut last_meter_update,
+            &button_registry,
+            &mut terminal_rows,
+            &mut terminal_cols,
+            &mut theme,
+        );
+
+        ctx.cycle_vad_engine(1);
+        assert_eq!(config.app.voice_vad_engine, VadEngineKind::Earshot);
+        assert_eq!(voice_manager.vad_engine(), VadEngineKind::Earshot);
+        match writer_rx
+            .recv_timeout(Duration::from_millis(200))
+            .expect("status message")
+        {
+            WriterMessage::EnhancedStatus(state) => {
+                assert_eq!(state.message, "VAD engine: earshot");
+            }
+            other => panic!("unexpected writer message: {other:?}"),
+        }
+    }
+
     #[test]
     fn update_hud_style_updates_state_and_status() {
         let mut config = OverlayConfig::parse_from(["test-app"]);
@@ -1271,4 +1681,81 @@ mod tests {
             other => panic!("unexpected writer message: {other:?}"),
         }
     }
+
+    #[test]
+    fn toggle_model_profile_alternates_fast_and_accurate() {
+        assert_eq!(
+            toggle_model_profile(Some("/m/tiny.bin"), "/m/tiny.bin", "/m/medium.bin"),
+            "/m/medium.bin"
+        );
+        assert_eq!(
+            toggle_model_profile(Some("/m/medium.bin"), "/m/tiny.bin", "/m/medium.bin"),
+            "/m/tiny.bin"
+        );
+        assert_eq!(
+            toggle_model_profile(Some("/m/base.bin"), "/m/tiny.bin", "/m/medium.bin"),
+            "/m/tiny.bin",
+            "a third model switches to fast first"
+        );
+        assert_eq!(
+            toggle_model_profile(None, "/m/tiny.bin", "/m/medium.bin"),
+            "/m/tiny.bin"
+        );
+    }
+
+    #[test]
+    fn whisper_models_beside_lists_ggml_files_and_cycles() {
+        let dir = std::env::temp_dir().join(format!("voiceterm-models-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).expect("create model dir");
+        for name in ["ggml-small.bin", "ggml-base.en.bin", "notes.txt"] {
+            fs::write(dir.join(name), b"").expect("write model");
+        }
+        let path = |name: &str| dir.join(name).to_string_lossy().into_owned();
+        let models = whisper_models_beside(&path("ggml-base.en.bin"));
+        assert_eq!(models, [path("ggml-base.en.bin"), path("ggml-small.bin")]);
+        assert_eq!(
+            cycle_whisper_model(&models, &path("ggml-base.en.bin"), 1),
+            path("ggml-small.bin")
+        );
+        assert_eq!(
+            cycle_whisper_model(&models, &path("ggml-base.en.bin"), -1),
+            path("ggml-small.bin")
+        );
+
+        let custom = path("custom-model.bin");
+        let models = whisper_models_beside(&custom);
+        assert_eq!(models.len(), 3, "current model stays in the cycle");
+        assert_eq!(
+            cycle_whisper_model(&models, &custom, 1),
+            path("ggml-base.en.bin")
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn persisted_settings_use_flag_names_and_values() {
+        let mut config = OverlayConfig::parse_from(["test-app", "--theme", "dracula"]);
+        config.app.voice_silence_tail_ms = 750;
+        config.app.whisper_model_path = Some("/models/ggml-base.bin".to_string());
+        let mut status_state = StatusLineState::new();
+        status_state.sensitivity_db = -42.4;
+        status_state.hud_style = HudStyle::Minimal;
+        let settings = persisted_settings(&config, &status_state);
+        let get = |name: &str| {
+            settings
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.clone())
+        };
+        assert_eq!(get("voice-send-mode"), Some(Value::from("auto")));
+        assert_eq!(get("voice-vad-threshold-db"), Some(Value::from(-42.0)));
+        assert_eq!(get("voice-silence-tail-ms"), Some(Value::from(750u64)));
+        assert_eq!(get("hud-style"), Some(Value::from("minimal")));
+        assert_eq!(get("theme"), Some(Value::from("dracula")));
+        assert_eq!(
+            get("whisper-model-path"),
+            Some(Value::from("/models/ggml-base.bin"))
+        );
+    }
 }
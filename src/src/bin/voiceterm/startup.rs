@@ -0,0 +1,144 @@
+//! Startup phase timing for `--startup-profile`.
+//!
+//! Foreground phases are recorded as laps on the main thread, so together they
+//! add up to the time until the overlay accepts input. Model warm-up and the
+//! audio device probe run on background threads alongside the splash and PTY
+//! spawn; they are reported separately because they overlap the foreground.
+
+use serde_json::{json, Map, Value};
+use std::time::{Duration, Instant};
+use voiceterm::config::LogLevel;
+use voiceterm::log_event;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct StartupPhase {
+    name: &'static str,
+    elapsed: Duration,
+    background: bool,
+}
+
+/// Per-phase startup timings collected from process start to first input.
+pub(crate) struct StartupProfile {
+    started: Instant,
+    last_lap: Instant,
+    phases: Vec<StartupPhase>,
+}
+
+impl StartupProfile {
+    pub(crate) fn new(started: Instant) -> Self {
+        Self {
+            started,
+            last_lap: started,
+            phases: Vec::new(),
+        }
+    }
+
+    /// Close the current foreground phase at `now`.
+    pub(crate) fn lap(&mut self, name: &'static str, now: Instant) {
+        self.phases.push(StartupPhase {
+            name,
+            elapsed: now.saturating_duration_since(self.last_lap),
+            background: false,
+        });
+        self.last_lap = now;
+    }
+
+    /// Record a phase that ran on another thread.
+    pub(crate) fn background(&mut self, name: &'static str, elapsed: Duration) {
+        self.phases.push(StartupPhase {
+            name,
+            elapsed,
+            background: true,
+        });
+    }
+
+    /// Time from process start to the last foreground lap.
+    pub(crate) fn ready_after(&self) -> Duration {
+        self.last_lap.saturating_duration_since(self.started)
+    }
+
+    /// Human-readable table printed on exit.
+    pub(crate) fn report(&self) -> String {
+        let width = self
+            .phases
+            .iter()
+            .map(|phase| phase.name.len())
+            .max()
+            .unwrap_or(0);
+        let mut out = format!(
+            "Startup profile (ready in {} ms)\n",
+            self.ready_after().as_millis()
+        );
+        for phase in &self.phases {
+            let note = if phase.background {
+                "  (background)"
+            } else {
+                ""
+            };
+            out.push_str(&format!(
+                "  {:<width$}  {:>6} ms{note}\n",
+                phase.name,
+                phase.elapsed.as_millis()
+            ));
+        }
+        out
+    }
+
+    /// Emit the timings as a `startup_profile` log event.
+    pub(crate) fn log(&self) {
+        let mut phases = Map::new();
+        let mut background = Map::new();
+        for phase in &self.phases {
+            let target = if phase.background {
+                &mut background
+            } else {
+                &mut phases
+            };
+            target.insert(
+                phase.name.to_string(),
+                json!(phase.elapsed.as_millis() as u64),
+            );
+        }
+        log_event(
+            LogLevel::Info,
+            "startup_profile",
+            &[
+                ("ready_ms", json!(self.ready_after().as_millis() as u64)),
+                ("phases_ms", Value::Object(phases)),
+                ("background_ms", Value::Object(background)),
+            ],
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn laps_measure_from_the_previous_lap() {
+        let start = Instant::now();
+        let mut profile = StartupProfile::new(start);
+        profile.lap("config", start + Duration::from_millis(5));
+        profile.lap("pty_spawn", start + Duration::from_millis(45));
+        profile.background("model_load", Duration::from_millis(900));
+
+        assert_eq!(profile.ready_after(), Duration::from_millis(45));
+        let report = profile.report();
+        assert!(report.starts_with("Startup profile (ready in 45 ms)\n"));
+        assert!(report.contains("  config           5 ms\n"), "{report}");
+        assert!(report.contains("  pty_spawn       40 ms\n"), "{report}");
+        assert!(
+            report.contains("  model_load     900 ms  (background)\n"),
+            "{report}"
+        );
+    }
+
+    #[test]
+    fn background_phases_do_not_move_ready_time() {
+        let start = Instant::now();
+        let mut profile = StartupProfile::new(start);
+        profile.background("device_probe", Duration::from_secs(3));
+        assert_eq!(profile.ready_after(), Duration::ZERO);
+    }
+}
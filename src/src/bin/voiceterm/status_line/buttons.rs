@@ -6,6 +6,10 @@ use crate::status_style::StatusType;
 use crate::theme::{BorderSet, Theme, ThemeColors};
 
 use super::animation::{get_processing_spinner, get_recording_indicator, heartbeat_glyph};
+use super::format::{
+    format_duration_section, format_idle_draft, format_meter_section, format_mode_indicator,
+    format_output_snippet,
+};
 use super::layout::breakpoints;
 use super::state::{ButtonPosition, RecordingState, StatusLineState, VoiceMode};
 use super::text::{display_width, truncate_display};
@@ -45,6 +49,40 @@ pub fn get_button_positions(
     }
 }
 
+/// Click targets on the Full HUD main row (row 3 from bottom): the mode segment
+/// toggles send mode and the mic (dB) segment starts a capture. They are not
+/// buttons, so arrow-key focus skips them.
+pub fn get_status_row_positions(
+    state: &StatusLineState,
+    theme: Theme,
+    width: usize,
+) -> Vec<ButtonPosition> {
+    if state.hud_style != HudStyle::Full || width < breakpoints::COMPACT {
+        return Vec::new();
+    }
+    let colors = theme.colors();
+    let mode_width = display_width(&format_mode_indicator(state, &colors)) as u16;
+    let duration_width = display_width(&format_duration_section(state, &colors)) as u16;
+    let meter_width = display_width(&format_meter_section(state, &colors)) as u16;
+    // Sections start after the left border and are joined by a one-column `│`.
+    let mode_start = 2;
+    let meter_start = mode_start + mode_width + 1 + duration_width + 1;
+    vec![
+        ButtonPosition {
+            start_x: mode_start,
+            end_x: mode_start + mode_width.saturating_sub(1),
+            row: 3,
+            action: ButtonAction::ToggleSendMode,
+        },
+        ButtonPosition {
+            start_x: meter_start,
+            end_x: meter_start + meter_width.saturating_sub(1),
+            row: 3,
+            action: ButtonAction::VoiceTrigger,
+        },
+    ]
+}
+
 fn minimal_strip_text(state: &StatusLineState, colors: &ThemeColors) -> String {
     // Use animated indicators for recording and processing states
     // Minimal mode: theme-colored indicators for all states
@@ -66,6 +104,11 @@ fn minimal_strip_text(state: &StatusLineState, colors: &ThemeColors) -> String {
 
     match state.recording_state {
         RecordingState::Recording => {
+            // Elapsed clock beside REC, as in the full HUD's duration lane.
+            if let Some(duration) = state.recording_duration {
+                line.push(' ');
+                line.push_str(&format!("{duration:.1}s"));
+            }
             if let Some(db) = state.meter_db {
                 line.push(' ');
                 line.push_str(colors.dim);
@@ -77,7 +120,15 @@ fn minimal_strip_text(state: &StatusLineState, colors: &ThemeColors) -> String {
                 line.push_str(colors.reset);
             }
         }
-        RecordingState::Processing | RecordingState::Idle => {}
+        RecordingState::Processing => {}
+        // Insert mode is the non-default send mode, so only it gets a segment.
+        RecordingState::Idle if state.send_mode == VoiceSendMode::Insert => {
+            line.push(' ');
+            line.push_str(colors.dim);
+            line.push_str("· insert");
+            line.push_str(colors.reset);
+        }
+        RecordingState::Idle => {}
     }
 
     if let Some(panel) = minimal_right_panel(state, colors) {
@@ -104,6 +155,12 @@ fn minimal_strip_text(state: &StatusLineState, colors: &ThemeColors) -> String {
 }
 
 fn minimal_status_text(state: &StatusLineState, colors: &ThemeColors) -> Option<String> {
+    if let Some(draft) = format_idle_draft(state, colors) {
+        return Some(draft);
+    }
+    if let Some(snippet) = format_output_snippet(state, colors) {
+        return Some(snippet);
+    }
     if state.queue_depth > 0 {
         return Some(format!(
             "{}Queued {}{}",
@@ -442,6 +499,7 @@ fn get_button_defs(state: &StatusLineState) -> Vec<ButtonDef> {
     let send_label = match state.send_mode {
         VoiceSendMode::Auto => "send",
         VoiceSendMode::Insert => "edit",
+        VoiceSendMode::Clipboard => "clip",
     };
 
     vec![
@@ -516,6 +574,7 @@ fn format_button_row_with_positions(
             ButtonAction::ToggleSendMode => match state.send_mode {
                 VoiceSendMode::Auto => colors.success,
                 VoiceSendMode::Insert => colors.warning,
+                VoiceSendMode::Clipboard => colors.info,
             },
             // Static buttons use border/accent color to pop
             ButtonAction::SettingsToggle
@@ -548,6 +607,12 @@ fn format_button_row_with_positions(
             colors.warning, state.queue_depth, colors.reset
         ));
     }
+    if let Some(badge) = target_badge(state, colors) {
+        items.push(badge);
+    }
+    if let Some(badge) = dnd_badge(state, colors) {
+        items.push(badge);
+    }
 
     // Ready badge (not clickable)
     let ready_badge = if show_ready_badge
@@ -623,6 +688,7 @@ fn format_button_row_with_positions(
             ButtonAction::ToggleSendMode => match state.send_mode {
                 VoiceSendMode::Auto => colors.success,
                 VoiceSendMode::Insert => colors.warning,
+                VoiceSendMode::Clipboard => colors.info,
             },
             ButtonAction::SettingsToggle
             | ButtonAction::ToggleHudStyle
@@ -652,11 +718,34 @@ fn format_button_row_with_positions(
             colors.warning, state.queue_depth, colors.reset
         ));
     }
+    if let Some(badge) = target_badge(state, colors) {
+        compact_items.push(badge);
+    }
+    if let Some(badge) = dnd_badge(state, colors) {
+        compact_items.push(badge);
+    }
 
     let compact_row = truncate_display(&compact_items.join(" "), inner_width);
     (compact_row, compact_positions)
 }
 
+/// Active transcript destination (not clickable), shown only when `--target` gives a choice.
+fn target_badge(state: &StatusLineState, colors: &ThemeColors) -> Option<String> {
+    state
+        .transcript_target
+        .as_ref()
+        .map(|label| format!("{}→ {label}{}", colors.info, colors.reset))
+}
+
+/// Auto-voice do-not-disturb state (not clickable), shown only while auto-voice is on.
+fn dnd_badge(state: &StatusLineState, colors: &ThemeColors) -> Option<String> {
+    state
+        .auto_voice_dnd
+        .as_ref()
+        .filter(|_| state.auto_voice_enabled)
+        .map(|label| format!("{}DND {label}{}", colors.warning, colors.reset))
+}
+
 fn format_button_row(state: &StatusLineState, colors: &ThemeColors, inner_width: usize) -> String {
     let (row, _) = format_button_row_with_positions(state, colors, inner_width, 2, true, false);
     row
@@ -686,10 +775,11 @@ fn format_button_row_legacy(
     };
     items.push(format_button(colors, voice_label, voice_color, false));
 
-    // send mode: auto/insert - green when auto-send, yellow when insert
+    // send mode: auto/insert/clipboard - green when auto-send, yellow when insert
     let (send_label, send_color) = match state.send_mode {
         VoiceSendMode::Auto => ("send", colors.success), // green = auto-send
         VoiceSendMode::Insert => ("edit", colors.warning), // yellow = insert/edit mode
+        VoiceSendMode::Clipboard => ("clip", colors.info), // copied, never typed
     };
     items.push(format_button(colors, send_label, send_color, false));
 
@@ -706,6 +796,12 @@ fn format_button_row_legacy(
             colors.warning, state.queue_depth, colors.reset
         ));
     }
+    if let Some(badge) = target_badge(state, colors) {
+        items.push(badge);
+    }
+    if let Some(badge) = dnd_badge(state, colors) {
+        items.push(badge);
+    }
 
     // Latency badge if available
     if let Some(latency) = state.last_latency_ms {
@@ -740,6 +836,12 @@ fn format_button_row_legacy(
             colors.warning, state.queue_depth, colors.reset
         ));
     }
+    if let Some(badge) = target_badge(state, colors) {
+        compact.push(badge);
+    }
+    if let Some(badge) = dnd_badge(state, colors) {
+        compact.push(badge);
+    }
     truncate_display(&compact.join(" "), inner_width)
 }
 
@@ -806,6 +908,7 @@ fn format_shortcut_colored(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::status_line::format_status_banner;
     use crate::status_line::layout::breakpoints;
 
     fn count_substring(haystack: &str, needle: &str) -> usize {
@@ -831,6 +934,31 @@ mod tests {
         assert_eq!(positions[0].row, 2);
     }
 
+    #[test]
+    fn status_row_positions_cover_mode_and_mic_segments() {
+        let mut state = StatusLineState::new();
+        state.hud_style = HudStyle::Full;
+        state.voice_mode = VoiceMode::Manual;
+        let banner = format_status_banner(&state, Theme::None, 80);
+        let row: Vec<char> = banner.lines[1].chars().collect();
+        let positions = get_status_row_positions(&state, Theme::None, 80);
+        assert_eq!(positions.len(), 2);
+
+        let segment = |pos: &ButtonPosition| -> String {
+            row[pos.start_x as usize - 1..pos.end_x as usize]
+                .iter()
+                .collect()
+        };
+        assert_eq!(positions[0].action, ButtonAction::ToggleSendMode);
+        assert!(segment(&positions[0]).contains("PTT"));
+        assert_eq!(positions[1].action, ButtonAction::VoiceTrigger);
+        assert_eq!(segment(&positions[1]).trim(), "--dB");
+        assert!(positions.iter().all(|pos| pos.row == 3));
+
+        state.hud_style = HudStyle::Minimal;
+        assert!(get_status_row_positions(&state, Theme::None, 80).is_empty());
+    }
+
     #[test]
     fn get_button_positions_minimal_has_back_button() {
         let mut state = StatusLineState::new();
@@ -923,6 +1051,44 @@ mod tests {
         assert!(!line.contains("Transcript ready"));
     }
 
+    #[test]
+    fn minimal_strip_shows_recording_clock_and_insert_mode() {
+        let colors = Theme::None.colors();
+        let mut state = StatusLineState::new();
+        state.recording_state = RecordingState::Recording;
+        state.recording_duration = Some(3.24);
+        state.meter_db = Some(-40.0);
+        let line = minimal_strip_text(&state, &colors);
+        assert!(line.contains("REC 3.2s · -40dB"), "{line}");
+
+        state.recording_state = RecordingState::Idle;
+        state.voice_mode = VoiceMode::Manual;
+        state.send_mode = VoiceSendMode::Insert;
+        let line = minimal_strip_text(&state, &colors);
+        assert!(line.starts_with("● PTT · insert · "), "{line}");
+        state.send_mode = VoiceSendMode::Auto;
+        assert!(!minimal_strip_text(&state, &colors).contains("insert"));
+    }
+
+    #[test]
+    fn minimal_strip_idle_shows_backend_output_snippet() {
+        let colors = Theme::None.colors();
+        let mut state = StatusLineState::new();
+        state.recording_state = RecordingState::Idle;
+        state.voice_mode = VoiceMode::Auto;
+        state.output_snippet = Some("Compiling voiceterm".to_string());
+        let line = minimal_strip_text(&state, &colors);
+        assert!(line.contains("› Compiling voiceterm"), "{line}");
+        assert!(!line.contains("Ready"));
+
+        // Warnings and recording still take the lane.
+        state.message = "Auto-voice disabled (capture cancelled)".to_string();
+        assert!(!minimal_strip_text(&state, &colors).contains("Compiling"));
+        state.message.clear();
+        state.recording_state = RecordingState::Recording;
+        assert!(!minimal_strip_text(&state, &colors).contains("Compiling"));
+    }
+
     #[test]
     fn minimal_strip_idle_shows_queue_state() {
         let colors = Theme::None.colors();
@@ -1023,6 +1189,31 @@ mod tests {
         assert!(!row.contains("199ms"));
     }
 
+    #[test]
+    fn button_row_shows_transcript_target_only_when_set() {
+        let colors = Theme::None.colors();
+        let mut state = StatusLineState::new();
+        state.hud_style = HudStyle::Full;
+        assert!(!format_button_row(&state, &colors, 200).contains('→'));
+
+        state.transcript_target = Some("tmux:%3".to_string());
+        assert!(format_button_row(&state, &colors, 200).contains("→ tmux:%3"));
+        assert!(format_button_row_legacy(&state, &colors, 200).contains("→ tmux:%3"));
+    }
+
+    #[test]
+    fn button_row_shows_dnd_badge_only_in_auto_voice() {
+        let colors = Theme::None.colors();
+        let mut state = StatusLineState::new();
+        state.hud_style = HudStyle::Full;
+        state.auto_voice_dnd = Some("quiet until 08:00".to_string());
+        assert!(!format_button_row(&state, &colors, 200).contains("DND"));
+
+        state.auto_voice_enabled = true;
+        assert!(format_button_row(&state, &colors, 200).contains("DND quiet until 08:00"));
+        assert!(format_button_row_legacy(&state, &colors, 200).contains("DND quiet until 08:00"));
+    }
+
     #[test]
     fn shortcuts_row_stays_within_banner_width() {
         let colors = Theme::Coral.colors();
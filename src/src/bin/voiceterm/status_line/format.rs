@@ -210,7 +210,7 @@ fn format_brand_label(colors: &ThemeColors) -> String {
     )
 }
 
-fn format_duration_section(state: &StatusLineState, colors: &ThemeColors) -> String {
+pub(super) fn format_duration_section(state: &StatusLineState, colors: &ThemeColors) -> String {
     let width = MAIN_ROW_DURATION_PLACEHOLDER.len();
     if let Some(dur) = state.recording_duration {
         let text = format!("{dur:.1}s");
@@ -253,7 +253,7 @@ fn format_panel_brackets(content: &str, colors: &ThemeColors) -> String {
     result
 }
 
-fn format_meter_section(state: &StatusLineState, colors: &ThemeColors) -> String {
+pub(super) fn format_meter_section(state: &StatusLineState, colors: &ThemeColors) -> String {
     let recording_active = state.recording_state == RecordingState::Recording;
     let db_text = if let Some(db) = state.meter_db {
         format!("{:>4.0}dB", db)
@@ -328,7 +328,38 @@ fn format_main_row(
     )
 }
 
+/// Idle dictation draft; it replaces the idle status so the text being edited stays visible.
+pub(super) fn format_idle_draft(state: &StatusLineState, colors: &ThemeColors) -> Option<String> {
+    if state.recording_state != RecordingState::Idle {
+        return None;
+    }
+    let draft = state.dictation.as_ref()?;
+    Some(format!("{}✎ {draft}{}", colors.info, colors.reset))
+}
+
+/// Backend output snippet; it replaces an idle `Ready` while a long answer streams.
+pub(super) fn format_output_snippet(
+    state: &StatusLineState,
+    colors: &ThemeColors,
+) -> Option<String> {
+    if state.recording_state != RecordingState::Idle || state.queue_depth > 0 {
+        return None;
+    }
+    if !state.message.is_empty() && StatusType::from_message(&state.message) != StatusType::Success
+    {
+        return None;
+    }
+    let snippet = state.output_snippet.as_ref()?;
+    Some(format!("{}› {snippet}{}", colors.dim, colors.reset))
+}
+
 fn format_full_hud_message(state: &StatusLineState, colors: &ThemeColors) -> String {
+    if let Some(draft) = format_idle_draft(state, colors) {
+        return draft;
+    }
+    if let Some(snippet) = format_output_snippet(state, colors) {
+        return snippet;
+    }
     if state.recording_state != RecordingState::Idle {
         if state.message.is_empty() {
             return String::new();
@@ -488,7 +519,7 @@ fn format_heartbeat_panel(state: &StatusLineState, colors: &ThemeColors) -> Stri
 /// Format the mode indicator with appropriate color and symbol.
 /// Uses animated indicators for recording (pulsing) and processing (spinning).
 #[inline]
-fn format_mode_indicator(state: &StatusLineState, colors: &ThemeColors) -> String {
+pub(super) fn format_mode_indicator(state: &StatusLineState, colors: &ThemeColors) -> String {
     let pipeline_tag = pipeline_tag_short(state.pipeline);
 
     let mut result = String::with_capacity(32);
@@ -697,7 +728,9 @@ fn format_compact_mode(parts: &CompactModeParts<'_>, colors: &ThemeColors) -> St
 fn format_minimal(state: &StatusLineState, colors: &ThemeColors, width: usize) -> String {
     let indicator = format_compact_indicator(&compact_mode_parts(state, colors), colors);
 
-    let msg = if state.message.is_empty() {
+    let msg = if let Some(snippet) = format_output_snippet(state, colors) {
+        snippet
+    } else if state.message.is_empty() {
         if state.voice_mode == VoiceMode::Auto {
             "auto".to_string()
         } else {
@@ -881,13 +914,25 @@ fn format_message(
     theme: Theme,
     width: usize,
 ) -> String {
+    if state.dictation.is_none() && state.transcript_preview.is_none() {
+        if let Some(snippet) = format_output_snippet(state, colors) {
+            return snippet;
+        }
+    }
+
     let mut message = if state.message.is_empty() {
         String::new()
     } else {
         state.message.clone()
     };
 
-    if let Some(preview) = state.transcript_preview.as_ref() {
+    if let Some(draft) = state.dictation.as_ref() {
+        if message.is_empty() {
+            message = format!("✎ {draft}");
+        } else {
+            message = format!("{message} ✎ {draft}");
+        }
+    } else if let Some(preview) = state.transcript_preview.as_ref() {
         if message.is_empty() {
             message = preview.clone();
         } else {
@@ -1229,6 +1274,22 @@ mod tests {
         assert!(banner.lines[1].contains("▁"));
     }
 
+    #[test]
+    fn format_status_banner_full_mode_shows_idle_dictation_draft() {
+        let mut state = StatusLineState::new();
+        state.hud_style = HudStyle::Full;
+        state.recording_state = RecordingState::Idle;
+        state.message = "Transcript ready (Rust pipeline)".to_string();
+        state.dictation = Some("fix the parser".to_string());
+
+        let banner = format_status_banner(&state, Theme::Coral, 96);
+        assert!(banner.lines[1].contains("✎ fix the parser"));
+
+        state.recording_state = RecordingState::Recording;
+        let banner = format_status_banner(&state, Theme::Coral, 96);
+        assert!(!banner.lines[1].contains("fix the parser"));
+    }
+
     #[test]
     fn format_status_banner_full_mode_collapses_idle_success_to_ready() {
         let mut state = StatusLineState::new();
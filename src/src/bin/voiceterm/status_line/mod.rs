@@ -15,7 +15,7 @@ mod text;
 
 #[allow(unused_imports)]
 pub(crate) use animation::state_transition_progress;
-pub use buttons::get_button_positions;
+pub use buttons::{get_button_positions, get_status_row_positions};
 pub use format::format_status_banner;
 pub use layout::status_banner_height;
 #[allow(unused_imports)]
@@ -1,5 +1,7 @@
 //! Shared status-line state so rendering and interactions read one source of truth.
 
+use std::time::{Duration, Instant};
+
 use crate::buttons::ButtonAction;
 use crate::config::{HudBorderStyle, HudRightPanel, HudStyle, LatencyDisplayMode, VoiceSendMode};
 
@@ -134,12 +136,18 @@ pub struct StatusLineState {
     pub recording_duration: Option<f32>,
     /// Whether auto-voice is enabled
     pub auto_voice_enabled: bool,
+    /// Why auto-voice is suspended (quiet hours or the mute hotkey), when it is.
+    pub auto_voice_dnd: Option<String>,
     /// Recent audio meter samples in dBFS for waveform display (capped at METER_HISTORY_MAX)
     pub meter_levels: Vec<f32>,
     /// Latest audio meter level in dBFS
     pub meter_db: Option<f32>,
     /// Optional transcript preview snippet
     pub transcript_preview: Option<String>,
+    /// Dictation draft awaiting Enter; shown instead of the preview.
+    pub dictation: Option<String>,
+    /// Latest backend output line while a long answer streams; shown in place of `Ready`.
+    pub output_snippet: Option<String>,
     /// Number of pending transcripts in queue
     pub queue_depth: usize,
     /// Last measured transcription latency in milliseconds
@@ -150,8 +158,12 @@ pub struct StatusLineState {
     pub latency_history_ms: Vec<u32>,
     /// Current voice send mode
     pub send_mode: VoiceSendMode,
+    /// Transcript destination label, set only when `--target` gives a choice.
+    pub transcript_target: Option<String>,
     /// Whether macro expansion from `.voiceterm/macros.yaml` is enabled.
     pub macros_enabled: bool,
+    /// Whether spoken structure words are formatted into code before delivery.
+    pub code_mode: bool,
     /// Right-side HUD panel mode
     pub hud_right_panel: HudRightPanel,
     /// Border style override for full HUD mode
@@ -167,6 +179,8 @@ pub struct StatusLineState {
     /// Ephemeral transition progress (0.0-1.0) for state-change animations.
     /// This is populated by the writer thread at render time.
     pub transition_progress: f32,
+    /// Back-to-back repeats of the current status message.
+    pub status_repeat: StatusRepeat,
 }
 
 impl StatusLineState {
@@ -190,10 +204,75 @@ impl StatusLineState {
     }
 }
 
+/// Identical statuses within this window of each other collapse into one with a count.
+const STATUS_REPEAT_WINDOW: Duration = Duration::from_secs(30);
+
+/// Coalesces repeated statuses (e.g. "No speech detected") into `text ×N`.
+#[derive(Debug, Clone, Default)]
+pub struct StatusRepeat {
+    text: String,
+    count: u32,
+    last_at: Option<Instant>,
+}
+
+impl StatusRepeat {
+    /// Record `text` being shown at `now` and return the message to display.
+    pub fn record(&mut self, text: &str, now: Instant) -> String {
+        let repeated = !text.is_empty()
+            && self.text == text
+            && self
+                .last_at
+                .is_some_and(|last| now.saturating_duration_since(last) < STATUS_REPEAT_WINDOW);
+        if repeated {
+            self.count += 1;
+        } else {
+            self.text = text.to_string();
+            self.count = 1;
+        }
+        self.last_at = Some(now);
+        if self.count > 1 {
+            format!("{text} ×{}", self.count)
+        } else {
+            text.to_string()
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn status_repeat_counts_back_to_back_messages_within_window() {
+        let start = Instant::now();
+        let mut repeat = StatusRepeat::default();
+        assert_eq!(
+            repeat.record("No speech detected", start),
+            "No speech detected"
+        );
+        let later = start + Duration::from_secs(5);
+        assert_eq!(
+            repeat.record("No speech detected", later),
+            "No speech detected ×2"
+        );
+        let later = later + Duration::from_secs(5);
+        assert_eq!(
+            repeat.record("No speech detected", later),
+            "No speech detected ×3"
+        );
+        // A different message resets the count, and so does a long gap.
+        assert_eq!(repeat.record("Transcript ready", later), "Transcript ready");
+        assert_eq!(
+            repeat.record("No speech detected", later),
+            "No speech detected"
+        );
+        let much_later = later + STATUS_REPEAT_WINDOW;
+        assert_eq!(
+            repeat.record("No speech detected", much_later),
+            "No speech detected"
+        );
+    }
+
     #[test]
     fn voice_mode_labels() {
         assert_eq!(VoiceMode::Auto.label(), "AUTO");
@@ -38,21 +38,34 @@ impl StatusType {
         }
 
         // Error states
-        if lower.contains("failed") || lower.contains("error") {
+        if lower.contains("failed")
+            || lower.contains("error")
+            || lower.contains("mic busy")
+            || lower.contains("mic unavailable")
+            || lower.contains("clipboard unavailable")
+            || lower.contains("permission denied")
+            || lower.contains("no microphone")
+        {
             return Self::Error;
         }
 
         // Warning states
         if lower.contains("no speech")
+            || lower.contains("appears muted")
             || lower.contains("queue full")
             || lower.contains("cancelled")
             || lower.contains("dropped")
+            || lower.contains("urgent")
+            || lower.contains("secret")
         {
             return Self::Warning;
         }
 
         // Success states
-        if lower.contains("transcript ready") {
+        if lower.contains("transcript ready")
+            || lower.contains("transcript copied")
+            || lower.contains("terminal clipboard")
+        {
             return Self::Success;
         }
 
@@ -170,10 +183,22 @@ mod tests {
             StatusType::from_message("Voice capture failed"),
             StatusType::Error
         );
+        assert_eq!(
+            StatusType::from_message("Clipboard unavailable (see log)"),
+            StatusType::Error
+        );
         assert_eq!(
             StatusType::from_message("Voice capture error (see log)"),
             StatusType::Error
         );
+        assert_eq!(
+            StatusType::from_message("Mic busy (in use by zoom)"),
+            StatusType::Error
+        );
+        assert_eq!(
+            StatusType::from_message("Mic permission denied (check OS privacy settings)"),
+            StatusType::Error
+        );
     }
 
     #[test]
@@ -190,6 +215,14 @@ mod tests {
             StatusType::from_message("Capture cancelled"),
             StatusType::Warning
         );
+        assert_eq!(
+            StatusType::from_message("Mic appears muted (check headset mute switch)"),
+            StatusType::Warning
+        );
+        assert_eq!(
+            StatusType::from_message("Transcript ready (Rust, urgent 'stop')"),
+            StatusType::Warning
+        );
     }
 
     #[test]
@@ -198,6 +231,10 @@ mod tests {
             StatusType::from_message("Transcript ready (Rust pipeline)"),
             StatusType::Success
         );
+        assert_eq!(
+            StatusType::from_message("Transcript copied to clipboard (Rust pipeline)"),
+            StatusType::Success
+        );
     }
 
     #[test]
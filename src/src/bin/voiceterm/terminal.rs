@@ -8,6 +8,9 @@ use voiceterm::pty_session::PtyOverlaySession;
 
 use crate::config::HudStyle;
 use crate::help::help_overlay_height;
+use crate::latency_overlay::latency_overlay_height;
+use crate::queue_overlay::queue_overlay_height;
+use crate::search_overlay::search_overlay_height;
 use crate::settings::settings_overlay_height;
 use crate::status_line::status_banner_height;
 use crate::theme_picker::theme_picker_height;
@@ -63,6 +66,9 @@ pub(crate) fn reserved_rows_for_mode(mode: OverlayMode, cols: u16, hud_style: Hu
         OverlayMode::Help => help_overlay_height(),
         OverlayMode::ThemePicker => theme_picker_height(),
         OverlayMode::Settings => settings_overlay_height(),
+        OverlayMode::Latency => latency_overlay_height(),
+        OverlayMode::Search => search_overlay_height(),
+        OverlayMode::Queue => queue_overlay_height(),
     }
 }
 
@@ -76,11 +82,16 @@ pub(crate) fn apply_pty_winsize(
     if rows == 0 || cols == 0 {
         return;
     }
-    let reserved = reserved_rows_for_mode(mode, cols, hud_style) as u16;
-    let pty_rows = rows.saturating_sub(reserved).max(1);
+    let pty_rows = backend_rows(rows, cols, mode, hud_style);
     let _ = session.set_winsize(pty_rows, cols);
 }
 
+/// Rows above the HUD or overlay that belong to the backend.
+pub(crate) fn backend_rows(rows: u16, cols: u16, mode: OverlayMode, hud_style: HudStyle) -> u16 {
+    let reserved = reserved_rows_for_mode(mode, cols, hud_style) as u16;
+    rows.saturating_sub(reserved).max(1)
+}
+
 pub(crate) fn update_pty_winsize(
     session: &mut PtyOverlaySession,
     terminal_rows: &mut u16,
@@ -150,6 +161,18 @@ mod tests {
             reserved_rows_for_mode(OverlayMode::Settings, cols, HudStyle::Full),
             settings_overlay_height()
         );
+        assert_eq!(
+            reserved_rows_for_mode(OverlayMode::Latency, cols, HudStyle::Full),
+            latency_overlay_height()
+        );
+        assert_eq!(
+            reserved_rows_for_mode(OverlayMode::Search, cols, HudStyle::Full),
+            search_overlay_height()
+        );
+        assert_eq!(
+            reserved_rows_for_mode(OverlayMode::Queue, cols, HudStyle::Full),
+            queue_overlay_height()
+        );
     }
 
     #[cfg(all(unix, feature = "mutants"))]
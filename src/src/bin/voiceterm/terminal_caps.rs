@@ -0,0 +1,316 @@
+//! Terminal capability probing so dumb terminals get plain-text output instead of escape garbage.
+//!
+//! The HUD, splash, and mouse support all assume cursor addressing and
+//! save/restore cursor. At startup the compiled terminfo entry for `$TERM` is
+//! read; when it lacks those capabilities (or `$TERM` is `dumb` or unset), the
+//! overlay switches to plain mode: status changes print as plain lines and
+//! themes are capped at 16 colors. An unknown terminal is assumed capable.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use crate::color_mode::ColorMode;
+use crate::config::RenderMode;
+
+/// Legacy terminfo magic (16-bit numbers) and the ncurses 6.1 extended magic (32-bit numbers).
+const TERMINFO_MAGIC: u16 = 0o432;
+const TERMINFO_MAGIC_32BIT: u16 = 0o1036;
+/// Capability indexes from `term.h`.
+const NUM_MAX_COLORS: usize = 13;
+const STR_CURSOR_ADDRESS: usize = 10;
+const STR_RESTORE_CURSOR: usize = 126;
+const STR_SAVE_CURSOR: usize = 128;
+
+/// Capabilities the overlay cares about from one terminfo entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct TerminfoCaps {
+    pub(crate) cursor_address: bool,
+    pub(crate) save_restore_cursor: bool,
+    pub(crate) max_colors: Option<i32>,
+}
+
+/// Probe result for `$TERM`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TerminalCaps {
+    term: String,
+    terminfo: Option<TerminfoCaps>,
+}
+
+impl TerminalCaps {
+    /// Probe once per process; the environment does not change under us.
+    pub(crate) fn probe() -> &'static Self {
+        static CAPS: OnceLock<TerminalCaps> = OnceLock::new();
+        CAPS.get_or_init(|| {
+            let term = env::var("TERM").unwrap_or_default();
+            let terminfo = find_terminfo(&term).and_then(|bytes| parse_terminfo(&bytes));
+            Self { term, terminfo }
+        })
+    }
+
+    /// Why plain mode is needed, or `None` for a capable terminal.
+    pub(crate) fn degraded_reason(&self) -> Option<String> {
+        if self.term.is_empty() {
+            return Some("TERM is unset".to_string());
+        }
+        if self.term == "dumb" {
+            return Some("TERM=dumb".to_string());
+        }
+        let caps = self.terminfo?;
+        if !caps.cursor_address {
+            Some(format!("TERM={} has no cursor addressing", self.term))
+        } else if !caps.save_restore_cursor {
+            Some(format!("TERM={} cannot save/restore the cursor", self.term))
+        } else {
+            None
+        }
+    }
+
+    /// Richest color mode plain mode may use.
+    fn color_limit(&self) -> ColorMode {
+        match self.terminfo.and_then(|caps| caps.max_colors) {
+            Some(colors) if colors >= 8 => ColorMode::Ansi16,
+            Some(_) => ColorMode::None,
+            // Without terminfo, keep the 16 colors nearly every terminal has.
+            None if self.terminfo.is_none() => ColorMode::Ansi16,
+            None => ColorMode::None,
+        }
+    }
+}
+
+impl RenderMode {
+    /// Whether to use plain-text statuses instead of the cursor-addressed HUD.
+    pub(crate) fn is_plain(self) -> bool {
+        match self {
+            Self::Auto => TerminalCaps::probe().degraded_reason().is_some(),
+            Self::Full => false,
+            Self::Plain => true,
+        }
+    }
+
+    /// Cap `detected` to what the render mode allows.
+    pub(crate) fn limit_colors(self, detected: ColorMode) -> ColorMode {
+        if !self.is_plain() {
+            return detected;
+        }
+        let limit = match self {
+            Self::Plain => ColorMode::Ansi16,
+            _ => TerminalCaps::probe().color_limit(),
+        };
+        detected.capped(limit)
+    }
+
+    /// Doctor/log summary of the resolved mode.
+    pub(crate) fn describe(self) -> String {
+        match (self, TerminalCaps::probe().degraded_reason()) {
+            (Self::Auto, Some(reason)) => format!("plain (auto: {reason})"),
+            (Self::Auto, None) => "full (auto)".to_string(),
+            (Self::Full, _) => "full".to_string(),
+            (Self::Plain, _) => "plain".to_string(),
+        }
+    }
+}
+
+/// Compiled entry for `term` from the usual terminfo search path.
+fn find_terminfo(term: &str) -> Option<Vec<u8>> {
+    if term.is_empty() || term.contains('/') {
+        return None;
+    }
+    let first = term.chars().next()?;
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    if let Some(dir) = env::var_os("TERMINFO") {
+        dirs.push(PathBuf::from(dir));
+    }
+    if let Some(home) = env::var_os("HOME") {
+        dirs.push(PathBuf::from(home).join(".terminfo"));
+    }
+    if let Some(list) = env::var_os("TERMINFO_DIRS") {
+        dirs.extend(env::split_paths(&list).filter(|dir| !dir.as_os_str().is_empty()));
+    }
+    dirs.extend(
+        ["/etc/terminfo", "/lib/terminfo", "/usr/share/terminfo"]
+            .iter()
+            .map(PathBuf::from),
+    );
+    // Linux groups entries by first letter, macOS by its hex code.
+    let subdirs = [first.to_string(), format!("{:x}", first as u32)];
+    dirs.iter()
+        .flat_map(|dir| subdirs.iter().map(move |sub| dir.join(sub).join(term)))
+        .find_map(|path| fs::read(path).ok())
+}
+
+/// Parse the header, numbers, and string offsets of a compiled terminfo entry.
+pub(crate) fn parse_terminfo(bytes: &[u8]) -> Option<TerminfoCaps> {
+    let word = |offset: usize| -> Option<i16> {
+        bytes
+            .get(offset..offset + 2)
+            .map(|raw| i16::from_le_bytes([raw[0], raw[1]]))
+    };
+    let number_width = match word(0)? as u16 {
+        TERMINFO_MAGIC => 2,
+        TERMINFO_MAGIC_32BIT => 4,
+        _ => return None,
+    };
+    let count = |offset: usize| word(offset).and_then(|value| usize::try_from(value).ok());
+    let (names_len, bools_len, numbers_len, strings_len) =
+        (count(2)?, count(4)?, count(6)?, count(8)?);
+    let mut offset = 12 + names_len + bools_len;
+    // Numbers start on an even byte.
+    offset += offset % 2;
+    let max_colors = (NUM_MAX_COLORS < numbers_len)
+        .then(|| {
+            let at = offset + NUM_MAX_COLORS * number_width;
+            let raw = bytes.get(at..at + number_width)?;
+            Some(if number_width == 4 {
+                i32::from_le_bytes([raw[0], raw[1], raw[2], raw[3]])
+            } else {
+                i32::from(i16::from_le_bytes([raw[0], raw[1]]))
+            })
+        })
+        .flatten()
+        .filter(|colors| *colors >= 0);
+    let strings_at = offset + numbers_len * number_width;
+    if bytes.len() < strings_at + strings_len * 2 {
+        return None;
+    }
+    let has_string = |index: usize| {
+        index < strings_len && word(strings_at + index * 2).is_some_and(|offset| offset >= 0)
+    };
+    Some(TerminfoCaps {
+        cursor_address: has_string(STR_CURSOR_ADDRESS),
+        save_restore_cursor: has_string(STR_SAVE_CURSOR) && has_string(STR_RESTORE_CURSOR),
+        max_colors,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Minimal compiled entry; string offsets only need to be non-negative to count as present.
+    fn entry(magic: u16, numbers: &[i32], strings: &[usize]) -> Vec<u8> {
+        let names = b"test|test terminal\0";
+        let string_count = strings.iter().max().map_or(0, |max| max + 1);
+        let mut bytes = Vec::new();
+        for value in [
+            magic as i16,
+            names.len() as i16,
+            1,
+            numbers.len() as i16,
+            string_count as i16,
+            2,
+        ] {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        bytes.extend_from_slice(names);
+        bytes.push(1);
+        if bytes.len() % 2 == 1 {
+            bytes.push(0);
+        }
+        for &number in numbers {
+            if magic == TERMINFO_MAGIC_32BIT {
+                bytes.extend_from_slice(&number.to_le_bytes());
+            } else {
+                bytes.extend_from_slice(&(number as i16).to_le_bytes());
+            }
+        }
+        for index in 0..string_count {
+            let offset: i16 = if strings.contains(&index) { 0 } else { -1 };
+            bytes.extend_from_slice(&offset.to_le_bytes());
+        }
+        bytes.extend_from_slice(b"x\0");
+        bytes
+    }
+
+    fn caps(term: &str, terminfo: Option<TerminfoCaps>) -> TerminalCaps {
+        TerminalCaps {
+            term: term.to_string(),
+            terminfo,
+        }
+    }
+
+    #[test]
+    fn parses_capable_and_dumb_entries() {
+        let mut numbers = vec![-1; 14];
+        numbers[NUM_MAX_COLORS] = 256;
+        let xterm = entry(
+            TERMINFO_MAGIC_32BIT,
+            &numbers,
+            &[STR_CURSOR_ADDRESS, STR_RESTORE_CURSOR, STR_SAVE_CURSOR],
+        );
+        assert_eq!(
+            parse_terminfo(&xterm),
+            Some(TerminfoCaps {
+                cursor_address: true,
+                save_restore_cursor: true,
+                max_colors: Some(256),
+            })
+        );
+
+        let dumb = entry(TERMINFO_MAGIC, &[80], &[1]);
+        assert_eq!(
+            parse_terminfo(&dumb),
+            Some(TerminfoCaps {
+                cursor_address: false,
+                save_restore_cursor: false,
+                max_colors: None,
+            })
+        );
+        assert!(parse_terminfo(b"not terminfo").is_none());
+        assert!(parse_terminfo(&xterm[..20]).is_none());
+    }
+
+    #[test]
+    fn degraded_reason_needs_cursor_control() {
+        let full = TerminfoCaps {
+            cursor_address: true,
+            save_restore_cursor: true,
+            max_colors: Some(8),
+        };
+        assert!(caps("linux", Some(full)).degraded_reason().is_none());
+        assert!(
+            caps("mystery", None).degraded_reason().is_none(),
+            "unknown terminals are assumed capable"
+        );
+        assert_eq!(
+            caps("", None).degraded_reason().as_deref(),
+            Some("TERM is unset")
+        );
+        assert_eq!(
+            caps("dumb", None).degraded_reason().as_deref(),
+            Some("TERM=dumb")
+        );
+        let no_save = TerminfoCaps {
+            save_restore_cursor: false,
+            ..full
+        };
+        assert!(caps("adm3a", Some(no_save))
+            .degraded_reason()
+            .is_some_and(|reason| reason.contains("save/restore")));
+    }
+
+    #[test]
+    fn plain_mode_caps_colors_at_sixteen() {
+        let eight = TerminfoCaps {
+            cursor_address: false,
+            save_restore_cursor: false,
+            max_colors: Some(8),
+        };
+        assert_eq!(caps("glass", Some(eight)).color_limit(), ColorMode::Ansi16);
+        let mono = TerminfoCaps {
+            max_colors: None,
+            ..eight
+        };
+        assert_eq!(caps("glass", Some(mono)).color_limit(), ColorMode::None);
+        assert_eq!(caps("dumb", None).color_limit(), ColorMode::Ansi16);
+        assert_eq!(
+            RenderMode::Plain.limit_colors(ColorMode::TrueColor),
+            ColorMode::Ansi16
+        );
+        assert_eq!(
+            RenderMode::Full.limit_colors(ColorMode::TrueColor),
+            ColorMode::TrueColor
+        );
+    }
+}
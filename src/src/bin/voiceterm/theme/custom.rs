@@ -0,0 +1,133 @@
+//! User-defined palettes from the config file's `themes` section.
+//!
+//! Each palette starts from a built-in theme (`base`, default coral) and
+//! replaces the colors it lists. Palettes are registered once at startup,
+//! before anything renders, and from then on behave like built-ins: `--theme`,
+//! the theme picker, and theme cycling all include them.
+
+use anyhow::{bail, Context, Result};
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+use super::{Theme, ThemeColors};
+use crate::config::ThemePalette;
+
+static CUSTOM_THEMES: OnceLock<Vec<CustomTheme>> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CustomTheme {
+    pub name: &'static str,
+    pub colors: ThemeColors,
+}
+
+/// Make the config file's palettes selectable; later calls are ignored.
+pub(crate) fn register_custom_themes(palettes: &BTreeMap<String, ThemePalette>) -> Result<()> {
+    let themes = build_custom_themes(palettes)?;
+    let _ = CUSTOM_THEMES.set(themes);
+    Ok(())
+}
+
+/// Registered palettes; `Theme::Custom(n)` is the `n`th.
+pub(super) fn custom_themes() -> &'static [CustomTheme] {
+    CUSTOM_THEMES.get().map(Vec::as_slice).unwrap_or(&[])
+}
+
+fn build_custom_themes(palettes: &BTreeMap<String, ThemePalette>) -> Result<Vec<CustomTheme>> {
+    palettes
+        .iter()
+        .map(|(name, palette)| {
+            let name = name.trim().to_lowercase();
+            if name.is_empty() || Theme::builtin_from_name(&name).is_some() {
+                bail!("custom theme '{name}' needs a name that is not a built-in theme");
+            }
+            let colors = palette_colors(palette)
+                .with_context(|| format!("invalid custom theme '{name}'"))?;
+            Ok(CustomTheme {
+                name: leak(name),
+                colors,
+            })
+        })
+        .collect()
+}
+
+fn palette_colors(palette: &ThemePalette) -> Result<ThemeColors> {
+    let base = match palette.base.as_deref() {
+        Some(name) => Theme::builtin_from_name(name)
+            .with_context(|| format!("unknown base theme '{name}'"))?,
+        None => Theme::Coral,
+    };
+    let mut colors = base.colors();
+    for (value, slot) in [
+        (&palette.recording, &mut colors.recording),
+        (&palette.processing, &mut colors.processing),
+        (&palette.success, &mut colors.success),
+        (&palette.warning, &mut colors.warning),
+        (&palette.error, &mut colors.error),
+        (&palette.info, &mut colors.info),
+        (&palette.dim, &mut colors.dim),
+        (&palette.border, &mut colors.border),
+    ] {
+        if let Some(hex) = value {
+            let (r, g, b) = parse_hex(hex)?;
+            *slot = leak(format!("\x1b[38;2;{r};{g};{b}m"));
+        }
+    }
+    if let Some(hex) = &palette.status_bg {
+        let (r, g, b) = parse_hex(hex)?;
+        let background = leak(format!("\x1b[48;2;{r};{g};{b}m"));
+        colors.bg_primary = background;
+        colors.bg_secondary = background;
+        // Rendering resets after every colored span; keep the background through them.
+        colors.reset = leak(format!("\x1b[0m{background}"));
+    }
+    Ok(colors)
+}
+
+/// `#rrggbb` (the `#` is optional) as RGB components.
+fn parse_hex(value: &str) -> Result<(u8, u8, u8)> {
+    let digits = value.trim().trim_start_matches('#');
+    if digits.len() != 6 || !digits.chars().all(|ch| ch.is_ascii_hexdigit()) {
+        bail!("'{value}' is not a #rrggbb color");
+    }
+    let component = |range: std::ops::Range<usize>| {
+        u8::from_str_radix(&digits[range], 16).expect("validated hex digits")
+    };
+    Ok((component(0..2), component(2..4), component(4..6)))
+}
+
+/// Palettes live for the whole run, and `ThemeColors` holds `&'static str`.
+fn leak(text: String) -> &'static str {
+    Box::leak(text.into_boxed_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn palettes(yaml: &str) -> BTreeMap<String, ThemePalette> {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn palettes_override_their_base_with_hex_colors() {
+        let themes = build_custom_themes(&palettes(
+            "Ocean:\n  base: nord\n  info: '#0A1B2c'\n  status_bg: '101010'\n",
+        ))
+        .unwrap();
+        let ocean = themes[0];
+        assert_eq!(ocean.name, "ocean");
+        assert_eq!(ocean.colors.info, "\x1b[38;2;10;27;44m");
+        assert_eq!(ocean.colors.bg_primary, "\x1b[48;2;16;16;16m");
+        assert_eq!(ocean.colors.reset, "\x1b[0m\x1b[48;2;16;16;16m");
+        assert_eq!(ocean.colors.success, Theme::Nord.colors().success);
+        assert_eq!(ocean.colors.borders, Theme::Nord.colors().borders);
+    }
+
+    #[test]
+    fn palettes_reject_bad_colors_bases_and_names() {
+        assert!(build_custom_themes(&palettes("mine:\n  info: '#12345'\n")).is_err());
+        assert!(build_custom_themes(&palettes("mine:\n  success: '#zzzzzz'\n")).is_err());
+        assert!(build_custom_themes(&palettes("mine:\n  base: neon\n")).is_err());
+        assert!(build_custom_themes(&palettes("Dracula:\n  info: '#ffffff'\n")).is_err());
+    }
+}
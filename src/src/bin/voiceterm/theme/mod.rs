@@ -4,6 +4,7 @@
 
 mod borders;
 mod colors;
+mod custom;
 mod detect;
 mod palettes;
 
@@ -11,11 +12,13 @@ pub use borders::{BorderSet, BORDER_DOUBLE, BORDER_HEAVY, BORDER_ROUNDED, BORDER
 #[allow(unused_imports)]
 pub use borders::{BORDER_DOTTED, BORDER_NONE};
 pub use colors::ThemeColors;
+pub(crate) use custom::register_custom_themes;
 pub use palettes::{
     THEME_ANSI, THEME_CATPPUCCIN, THEME_CHATGPT, THEME_CLAUDE, THEME_CODEX, THEME_CORAL,
-    THEME_DRACULA, THEME_GRUVBOX, THEME_NONE, THEME_NORD, THEME_TOKYONIGHT,
+    THEME_DRACULA, THEME_GRUVBOX, THEME_NONE, THEME_NORD, THEME_SOLARIZED, THEME_TOKYONIGHT,
 };
 
+use self::custom::{custom_themes, CustomTheme};
 use self::detect::is_warp_terminal;
 
 /// Available color themes.
@@ -40,15 +43,30 @@ pub enum Theme {
     TokyoNight,
     /// Gruvbox - warm retro earthy colors
     Gruvbox,
+    /// Solarized Dark - precise low-contrast accents
+    Solarized,
     /// ANSI 16-color fallback for older terminals
     Ansi,
     /// No colors - plain text
     None,
+    /// Palette from the config file's `themes` section, by registration order
+    Custom(usize),
 }
 
 impl Theme {
-    /// Parse theme name from string.
+    /// Parse theme name from string, including custom palettes from the config file.
     pub fn from_name(name: &str) -> Option<Self> {
+        Self::builtin_from_name(name).or_else(|| {
+            let name = name.to_lowercase();
+            custom_themes()
+                .iter()
+                .position(|theme| theme.name == name)
+                .map(Self::Custom)
+        })
+    }
+
+    /// Parse a built-in theme name.
+    pub fn builtin_from_name(name: &str) -> Option<Self> {
         match name.to_lowercase().as_str() {
             "coral" | "default" => Some(Self::Coral),
             "claude" | "anthropic" => Some(Self::Claude),
@@ -59,6 +77,7 @@ impl Theme {
             "nord" => Some(Self::Nord),
             "tokyonight" | "tokyo-night" | "tokyo" => Some(Self::TokyoNight),
             "gruvbox" | "gruv" => Some(Self::Gruvbox),
+            "solarized" | "solarized-dark" => Some(Self::Solarized),
             "ansi" | "ansi16" | "basic" => Some(Self::Ansi),
             "none" | "plain" => Some(Self::None),
             _ => None,
@@ -77,10 +96,16 @@ impl Theme {
             Self::Nord => THEME_NORD,
             Self::TokyoNight => THEME_TOKYONIGHT,
             Self::Gruvbox => THEME_GRUVBOX,
+            Self::Solarized => THEME_SOLARIZED,
             Self::Ansi => THEME_ANSI,
             Self::None => THEME_NONE,
+            Self::Custom(_) => self.custom().map_or(THEME_CORAL, |custom| custom.colors),
         };
         if is_warp_terminal() {
+            if !colors.bg_primary.is_empty() {
+                // Custom backgrounds ride on `reset`; drop them there too.
+                colors.reset = THEME_CORAL.reset;
+            }
             colors.bg_primary = "";
             colors.bg_secondary = "";
         }
@@ -99,6 +124,7 @@ impl Theme {
             "dracula",
             "gruvbox",
             "nord",
+            "solarized",
             "tokyonight",
             "ansi",
             "none",
@@ -117,9 +143,23 @@ impl Theme {
                 | Self::Nord
                 | Self::TokyoNight
                 | Self::Gruvbox
+                | Self::Solarized
+                | Self::Custom(_)
         )
     }
 
+    /// Names of the config file's custom palettes, in `Custom(n)` order.
+    pub fn custom_names() -> impl Iterator<Item = &'static str> {
+        custom_themes().iter().map(|custom| custom.name)
+    }
+
+    fn custom(&self) -> Option<&'static CustomTheme> {
+        match self {
+            Self::Custom(index) => custom_themes().get(*index),
+            _ => None,
+        }
+    }
+
     /// Get a fallback theme for terminals without truecolor support.
     pub fn fallback_for_ansi(&self) -> Self {
         if self.is_truecolor() {
@@ -142,8 +182,14 @@ impl std::fmt::Display for Theme {
             Self::Nord => write!(f, "nord"),
             Self::TokyoNight => write!(f, "tokyonight"),
             Self::Gruvbox => write!(f, "gruvbox"),
+            Self::Solarized => write!(f, "solarized"),
             Self::Ansi => write!(f, "ansi"),
             Self::None => write!(f, "none"),
+            Self::Custom(_) => write!(
+                f,
+                "{}",
+                self.custom().map_or("custom", |custom| custom.name)
+            ),
         }
     }
 }
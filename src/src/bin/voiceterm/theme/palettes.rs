@@ -194,6 +194,27 @@ pub const THEME_GRUVBOX: ThemeColors = ThemeColors {
     indicator_idle: "□",
 };
 
+/// Solarized Dark theme - precise low-contrast accents
+/// https://ethanschoonover.com/solarized/
+pub const THEME_SOLARIZED: ThemeColors = ThemeColors {
+    recording: "\x1b[38;2;220;50;47m",  // Red #dc322f
+    processing: "\x1b[38;2;181;137;0m", // Yellow #b58900
+    success: "\x1b[38;2;133;153;0m",    // Green #859900
+    warning: "\x1b[38;2;203;75;22m",    // Orange #cb4b16
+    error: "\x1b[38;2;220;50;47m",      // Red #dc322f
+    info: "\x1b[38;2;38;139;210m",      // Blue #268bd2
+    reset: "\x1b[0m",
+    dim: "\x1b[38;2;88;110;117m",    // Base01 #586e75
+    bg_primary: "",                  // Transparent
+    bg_secondary: "",                // Transparent
+    border: "\x1b[38;2;42;161;152m", // Cyan #2aa198
+    borders: BORDER_SINGLE,
+    indicator_rec: "◆",
+    indicator_auto: "◈",
+    indicator_manual: "▸",
+    indicator_idle: "◇",
+};
+
 /// ANSI 16-color theme - works on all color terminals
 /// Uses standard ANSI escape codes (30-37, 90-97)
 /// Uses transparent backgrounds for best compatibility across terminals
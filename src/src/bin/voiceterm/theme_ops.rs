@@ -10,24 +10,25 @@ use crate::overlays::OverlayMode;
 use crate::status_line::StatusLineState;
 use crate::terminal::update_pty_winsize;
 use crate::theme::Theme;
-use crate::theme_picker::THEME_OPTIONS;
+use crate::theme_picker::theme_options;
 use crate::writer::{set_status, WriterMessage};
 
 pub(crate) fn cycle_theme(current: Theme, direction: i32) -> Theme {
-    let len = THEME_OPTIONS.len() as i32;
+    let options = theme_options();
+    let len = options.len() as i32;
     if len == 0 {
         return current;
     }
-    let idx = THEME_OPTIONS
+    let idx = options
         .iter()
         .position(|(theme, _, _)| *theme == current)
         .unwrap_or(0) as i32;
     let next = (idx + direction).rem_euclid(len) as usize;
-    THEME_OPTIONS[next].0
+    options[next].0
 }
 
 pub(crate) fn theme_index_from_theme(theme: Theme) -> usize {
-    THEME_OPTIONS
+    theme_options()
         .iter()
         .position(|(candidate, _, _)| *candidate == theme)
         .unwrap_or(0)
@@ -98,7 +99,7 @@ pub(crate) fn apply_theme_picker_index(
     terminal_cols: &mut u16,
     overlay_mode: &mut OverlayMode,
 ) -> bool {
-    let Some((_, name, _)) = THEME_OPTIONS.get(idx) else {
+    let Some(&(_, name, _)) = theme_options().get(idx) else {
         return false;
     };
     let Some(requested) = Theme::from_name(name) else {
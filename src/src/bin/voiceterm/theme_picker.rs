@@ -16,10 +16,22 @@ pub const THEME_OPTIONS: &[(Theme, &str, &str)] = &[
     (Theme::Nord, "nord", "Rounded arctic blue"),
     (Theme::TokyoNight, "tokyonight", "Elegant purple/blue"),
     (Theme::Gruvbox, "gruvbox", "Warm retro earthy"),
+    (Theme::Solarized, "solarized", "Precise low contrast"),
     (Theme::Ansi, "ansi", "16-color compatible"),
     (Theme::None, "none", "No color styling"),
 ];
 
+/// Built-in options followed by the config file's custom palettes.
+pub fn theme_options() -> Vec<(Theme, &'static str, &'static str)> {
+    let mut options = THEME_OPTIONS.to_vec();
+    options.extend(
+        Theme::custom_names()
+            .enumerate()
+            .map(|(index, name)| (Theme::Custom(index), name, "Custom palette")),
+    );
+    options
+}
+
 pub const THEME_PICKER_FOOTER: &str = "[×] close · ↑/↓ move · Enter select";
 pub const THEME_PICKER_OPTION_START_ROW: usize = 4;
 
@@ -63,7 +75,7 @@ pub fn format_theme_picker(current_theme: Theme, selected_idx: usize, width: usi
     ));
 
     // Theme options with visual preview
-    for (idx, (theme, name, desc)) in THEME_OPTIONS.iter().enumerate() {
+    for (idx, (theme, name, desc)) in theme_options().iter().enumerate() {
         let theme_colors = theme.colors();
         let is_current = *theme == current_theme;
         let is_selected = idx == selected_idx;
@@ -189,7 +201,7 @@ fn format_option_line_with_preview(
 
 pub fn theme_picker_height() -> usize {
     // Top border + title + separator + options + separator + footer + bottom border
-    1 + 1 + 1 + THEME_OPTIONS.len() + 1 + 1 + 1
+    1 + 1 + 1 + theme_options().len() + 1 + 1 + 1
 }
 
 #[cfg(test)]
@@ -200,7 +212,7 @@ mod tests {
     fn theme_picker_contains_options() {
         let output = format_theme_picker(Theme::Coral, 0, 60);
         assert!(output.contains("1. chatgpt"));
-        assert!(output.contains("11. none")); // 11 themes total now
+        assert!(output.contains("12. none")); // 12 themes total now
     }
 
     #[test]
@@ -228,10 +240,10 @@ mod tests {
 
     #[test]
     fn theme_picker_none_theme_uses_neutral_preview_rows() {
-        let output = format_theme_picker(Theme::None, 10, 60);
+        let output = format_theme_picker(Theme::None, 11, 60);
         assert!(!output.contains("\x1b["));
         assert!(!output.contains("◉"));
         assert!(!output.contains("⏺"));
-        assert!(output.contains("11. none"));
+        assert!(output.contains("12. none"));
     }
 }
@@ -0,0 +1,300 @@
+//! `voiceterm transcribe FILE`: the live capture pipeline run over an audio file.
+//!
+//! The file is cut into utterances by the same VAD and silence-tail logic a
+//! microphone capture uses, and each utterance goes to the configured STT
+//! backend, so a recording reproduces what dictation would have typed.
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Serialize;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use voiceterm::audio::{
+    decode_wav, offline_capture_from_pcm, resample_to_target_rate, VadConfig, VadEngine,
+    TARGET_RATE,
+};
+use voiceterm::config::{AppConfig, SttBackend};
+use voiceterm::log_debug;
+use voiceterm::stt::{RemoteTranscriber, Transcriber, Transcription};
+use voiceterm::voice::create_vad_engine;
+
+use crate::config::{OverlayConfig, TranscribeArgs};
+
+/// Speech the VAD found, positioned within the file.
+struct Utterance {
+    start_ms: u64,
+    end_ms: u64,
+    audio: Vec<f32>,
+    stop_reason: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+struct Segment {
+    start_ms: u64,
+    end_ms: u64,
+    text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    confidence: Option<f32>,
+    /// Why the capture ended (`vad_silence`, `max_duration`).
+    stop_reason: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+struct FileTranscript {
+    file: String,
+    duration_ms: u64,
+    text: String,
+    segments: Vec<Segment>,
+}
+
+enum Stt {
+    Local(Transcriber),
+    Remote(RemoteTranscriber),
+}
+
+impl Stt {
+    fn from_config(config: &AppConfig) -> Result<Self> {
+        if config.stt_backend != SttBackend::Local {
+            return RemoteTranscriber::from_config(config)
+                .map(Stt::Remote)
+                .ok_or_else(|| anyhow!("remote STT is not configured"));
+        }
+        let model_path = config
+            .whisper_model_path
+            .as_deref()
+            .ok_or_else(|| anyhow!("no Whisper model found (set --whisper-model-path)"))?;
+        Ok(Stt::Local(Transcriber::with_device(
+            model_path,
+            config.stt_device,
+        )?))
+    }
+
+    fn transcribe(&self, samples: &[f32], config: &AppConfig) -> Result<Transcription> {
+        match self {
+            Stt::Local(transcriber) => transcriber.transcribe_detailed(samples, config),
+            Stt::Remote(remote) => remote.transcribe(samples, TARGET_RATE, &config.lang),
+        }
+    }
+}
+
+/// Run `voiceterm transcribe`, printing one line per utterance or a JSON document.
+pub(crate) fn run_transcribe(config: &OverlayConfig, args: &TranscribeArgs) -> Result<()> {
+    let (samples, sample_rate) = load_audio(&args.file)?;
+    let samples = resample_to_target_rate(&samples, sample_rate);
+    let pipeline = config.app.voice_pipeline_config();
+    // The recorder hands the VAD audio already resampled to Whisper's rate.
+    let mut vad_config: VadConfig = (&pipeline).into();
+    vad_config.sample_rate = TARGET_RATE;
+    let mut vad = create_vad_engine(&pipeline);
+    let utterances = split_utterances(&samples, &vad_config, vad.as_mut());
+    log_debug(&format!(
+        "transcribe: {} utterance(s) in {}",
+        utterances.len(),
+        args.file.display()
+    ));
+
+    let mut segments = Vec::with_capacity(utterances.len());
+    if !utterances.is_empty() {
+        let stt = Stt::from_config(&config.app)?;
+        for utterance in utterances {
+            let transcription = stt.transcribe(&utterance.audio, &config.app)?;
+            segments.push(Segment {
+                start_ms: utterance.start_ms,
+                end_ms: utterance.end_ms,
+                text: transcription.text.trim().to_string(),
+                confidence: transcription.confidence,
+                stop_reason: utterance.stop_reason,
+            });
+        }
+    }
+
+    if args.json {
+        let text = segments
+            .iter()
+            .map(|segment| segment.text.as_str())
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let transcript = FileTranscript {
+            file: args.file.display().to_string(),
+            duration_ms: samples_to_ms(samples.len(), TARGET_RATE),
+            text,
+            segments,
+        };
+        println!("{}", serde_json::to_string_pretty(&transcript)?);
+    } else if segments.is_empty() {
+        eprintln!("No speech detected in {}", args.file.display());
+    } else {
+        for segment in &segments {
+            println!(
+                "[{} - {}] {}",
+                format_timestamp(segment.start_ms),
+                format_timestamp(segment.end_ms),
+                segment.text
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Mono samples and their rate; WAV is decoded in-process, anything else through ffmpeg.
+fn load_audio(path: &Path) -> Result<(Vec<f32>, u32)> {
+    let is_wav = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("wav"));
+    if is_wav {
+        let bytes = fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+        return decode_wav(&bytes).with_context(|| format!("failed to decode {}", path.display()));
+    }
+    let output = match Command::new("ffmpeg")
+        .args(["-nostdin", "-v", "error", "-i"])
+        .arg(path)
+        .args(["-f", "f32le", "-ac", "1", "-ar"])
+        .arg(TARGET_RATE.to_string())
+        .arg("-")
+        .stdin(Stdio::null())
+        .output()
+    {
+        Ok(output) => output,
+        Err(err) if err.kind() == ErrorKind::NotFound => bail!(
+            "decoding {} needs ffmpeg on PATH (or convert it to WAV first)",
+            path.display()
+        ),
+        Err(err) => return Err(err).context("failed to start ffmpeg"),
+    };
+    if !output.status.success() {
+        bail!(
+            "ffmpeg could not decode {}: {}",
+            path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok((f32le_samples(&output.stdout), TARGET_RATE))
+}
+
+fn f32le_samples(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|bytes| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+        .collect()
+}
+
+/// Run the capture state machine back to back until the file is used up,
+/// keeping each capture that heard speech.
+fn split_utterances(samples: &[f32], cfg: &VadConfig, vad: &mut dyn VadEngine) -> Vec<Utterance> {
+    let frame_samples = ((u64::from(cfg.sample_rate) * cfg.frame_ms) / 1000).max(1) as usize;
+    let mut utterances = Vec::new();
+    let mut offset = 0;
+    while offset < samples.len() {
+        vad.reset();
+        let capture = offline_capture_from_pcm(&samples[offset..], cfg, vad);
+        let consumed = capture.metrics.frames_processed * frame_samples;
+        if consumed == 0 {
+            break;
+        }
+        let metrics = &capture.metrics;
+        if metrics.speech_ms > 0 && !capture.audio.is_empty() {
+            // Speech ends where the silence tail began and spans the voiced part.
+            let end_ms = samples_to_ms(offset, cfg.sample_rate)
+                + metrics.capture_ms.saturating_sub(metrics.silence_tail_ms);
+            utterances.push(Utterance {
+                start_ms: end_ms.saturating_sub(metrics.voiced_span_ms),
+                end_ms,
+                audio: capture.audio,
+                stop_reason: metrics.early_stop_reason.label(),
+            });
+        }
+        offset += consumed;
+    }
+    utterances
+}
+
+fn samples_to_ms(samples: usize, sample_rate: u32) -> u64 {
+    samples as u64 * 1000 / u64::from(sample_rate.max(1))
+}
+
+/// `mm:ss.mmm`, with hours only when needed.
+fn format_timestamp(ms: u64) -> String {
+    let (hours, minutes, seconds) = (ms / 3_600_000, ms / 60_000 % 60, ms / 1000 % 60);
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}.{:03}", ms % 1000)
+    } else {
+        format!("{minutes:02}:{seconds:02}.{:03}", ms % 1000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+    use voiceterm::audio::SimpleThresholdVad;
+
+    fn tone(ms: u64) -> Vec<f32> {
+        (0..TARGET_RATE as u64 * ms / 1000)
+            .map(|index| 0.3 * (2.0 * PI * 220.0 * index as f32 / TARGET_RATE as f32).sin())
+            .collect()
+    }
+
+    fn silence(ms: u64) -> Vec<f32> {
+        vec![0.0; (TARGET_RATE as u64 * ms / 1000) as usize]
+    }
+
+    #[test]
+    fn transcribe_subcommand_parses_file_and_json() {
+        use crate::config::OverlayCommand;
+        use clap::Parser;
+
+        let config = OverlayConfig::parse_from(["voiceterm", "transcribe", "memo.mp3", "--json"]);
+        let Some(OverlayCommand::Transcribe(args)) = config.command else {
+            panic!("expected transcribe subcommand");
+        };
+        assert_eq!(args.file, Path::new("memo.mp3"));
+        assert!(args.json);
+    }
+
+    #[test]
+    fn split_utterances_finds_each_utterance_with_its_position() {
+        let mut samples = silence(300);
+        samples.extend(tone(1_000));
+        samples.extend(silence(1_000));
+        samples.extend(tone(800));
+        samples.extend(silence(900));
+        let mut vad = SimpleThresholdVad::new(-40.0);
+        let utterances = split_utterances(&samples, &VadConfig::default(), &mut vad);
+
+        let spans: Vec<(u64, u64)> = utterances
+            .iter()
+            .map(|utterance| (utterance.start_ms, utterance.end_ms))
+            .collect();
+        assert_eq!(spans.len(), 2, "{spans:?}");
+        for ((start, end), (want_start, want_end)) in
+            spans.into_iter().zip([(300, 1300), (2300, 3100)])
+        {
+            assert!(
+                start.abs_diff(want_start) <= 100,
+                "start {start} vs {want_start}"
+            );
+            assert!(end.abs_diff(want_end) <= 100, "end {end} vs {want_end}");
+        }
+        assert!(
+            utterances
+                .iter()
+                .all(|utterance| utterance.stop_reason == "vad_silence"
+                    && !utterance.audio.is_empty())
+        );
+    }
+
+    #[test]
+    fn helpers_decode_raw_floats_and_format_timestamps() {
+        let bytes: Vec<u8> = [0.5f32, -1.0]
+            .iter()
+            .flat_map(|s| s.to_le_bytes())
+            .collect();
+        assert_eq!(f32le_samples(&bytes), [0.5, -1.0]);
+        assert_eq!(format_timestamp(83_042), "01:23.042");
+        assert_eq!(format_timestamp(3_723_004), "1:02:03.004");
+        assert_eq!(samples_to_ms(24_000, TARGET_RATE), 1_500);
+    }
+}
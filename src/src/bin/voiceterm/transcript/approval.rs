@@ -0,0 +1,116 @@
+//! Spoken answers to approval dialogs so "yes"/"no" press the dialog's own keys.
+//!
+//! While the backend shows an approval dialog (see [`UiMode::Approval`]), a
+//! queued transcript that is only a yes or no answer is sent as the key the
+//! dialog expects instead of as text. Anything else stays queued until the
+//! dialog closes, so a dictated sentence never lands in the dialog.
+//!
+//! [`UiMode::Approval`]: crate::prompt::UiMode::Approval
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+use voiceterm::{log_debug, log_info};
+
+use super::delivery::TranscriptIo;
+use super::queue::PendingTranscript;
+use super::session::TranscriptSession;
+use crate::prompt::ApprovalKeys;
+
+const YES_PHRASES: &[&str] = &[
+    "yes", "yeah", "yep", "yup", "sure", "ok", "okay", "approve", "allow", "go ahead", "do it",
+    "proceed", "confirm",
+];
+const NO_PHRASES: &[&str] = &[
+    "no", "nope", "nah", "deny", "reject", "decline", "cancel", "do not", "don't", "stop",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ApprovalAnswer {
+    Yes,
+    No,
+}
+
+/// The answer a whole transcript gives, e.g. "Yes." or "no thanks"; `None` for anything longer.
+pub(crate) fn approval_answer(text: &str) -> Option<ApprovalAnswer> {
+    let lower = text.to_lowercase();
+    let words: Vec<&str> = lower
+        .split(|ch: char| !(ch.is_alphanumeric() || ch == '\''))
+        .filter(|word| !word.is_empty())
+        .filter(|word| !matches!(*word, "please" | "thanks" | "thank" | "you"))
+        .collect();
+    let phrase = words.join(" ");
+    if YES_PHRASES.contains(&phrase.as_str()) {
+        Some(ApprovalAnswer::Yes)
+    } else if NO_PHRASES.contains(&phrase.as_str()) {
+        Some(ApprovalAnswer::No)
+    } else {
+        None
+    }
+}
+
+/// Answer the dialog with the oldest queued transcript when it is a yes/no answer.
+///
+/// Returns how long the answer waited in the queue, or `None` when the queue
+/// is empty or starts with something other than an answer.
+pub(crate) fn answer_approval<S: TranscriptSession>(
+    pending: &mut VecDeque<PendingTranscript>,
+    keys: &ApprovalKeys,
+    last_enter_at: &mut Option<Instant>,
+    io: &mut TranscriptIo<'_, S>,
+    now: Instant,
+) -> Option<Duration> {
+    let answer = approval_answer(&pending.front()?.text)?;
+    let transcript = pending.pop_front()?;
+    io.status_state.queue_depth = pending.len();
+    let (key, label) = match answer {
+        ApprovalAnswer::Yes => (&keys.yes, "yes"),
+        ApprovalAnswer::No => (&keys.no, "no"),
+    };
+    let sent = if keys.submit {
+        io.session.send_text_with_newline(key)
+    } else {
+        io.session.send_text(key)
+    };
+    match sent {
+        Ok(()) => {
+            log_info(&format!("approval answered by voice|answer={label}"));
+            io.set_status(
+                &format!("Approval answered: {label}"),
+                Some(Duration::from_secs(2)),
+            );
+            // The backend resumes work, so queued text waits for the next prompt.
+            *last_enter_at = Some(now);
+        }
+        Err(err) => {
+            log_debug(&format!("failed to answer approval: {err:#}"));
+            io.set_status(
+                "Failed to answer approval (see log)",
+                Some(Duration::from_secs(2)),
+            );
+        }
+    }
+    Some(now.saturating_duration_since(transcript.received_at))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_yes_and_no_phrases_are_answers() {
+        for text in ["Yes.", " yeah please", "Go ahead!", "OK", "approve"] {
+            assert_eq!(approval_answer(text), Some(ApprovalAnswer::Yes), "{text}");
+        }
+        for text in ["No.", "nope", "Don't.", "no thanks", "Cancel"] {
+            assert_eq!(approval_answer(text), Some(ApprovalAnswer::No), "{text}");
+        }
+        for text in [
+            "no, use the other file",
+            "yes and also add tests",
+            "",
+            "yesterday",
+        ] {
+            assert_eq!(approval_answer(text), None, "{text}");
+        }
+    }
+}
@@ -0,0 +1,139 @@
+//! Batching window so rapid-fire auto-send transcripts become one backend turn.
+//!
+//! With `--batch-window-ms`, auto-send transcripts wait in the pending queue
+//! instead of going out at once. Each new transcript restarts the window, a
+//! capture in progress keeps it open, and when it closes the queue merges the
+//! held transcripts into a single submission. Enter closes it early.
+
+use std::time::{Duration, Instant};
+
+/// Open/closed state of the batching window.
+#[derive(Debug, Clone)]
+pub(crate) struct BatchWindow {
+    window: Duration,
+    deadline: Option<Instant>,
+    send_now: bool,
+    shown_secs: Option<u64>,
+}
+
+impl BatchWindow {
+    /// `None` when `window_ms` is 0 (batching off).
+    pub(crate) fn from_ms(window_ms: u64) -> Option<Self> {
+        (window_ms > 0).then(|| Self {
+            window: Duration::from_millis(window_ms),
+            deadline: None,
+            send_now: false,
+            shown_secs: None,
+        })
+    }
+
+    /// Whether transcripts are being held.
+    pub(crate) fn is_open(&self) -> bool {
+        self.deadline.is_some()
+    }
+
+    /// Hold another transcript, restarting the window.
+    pub(crate) fn extend(&mut self, now: Instant) {
+        self.deadline = Some(now + self.window);
+        self.shown_secs = None;
+    }
+
+    /// Close the window on the next check, even mid-capture.
+    pub(crate) fn send_now(&mut self) {
+        if self.is_open() {
+            self.send_now = true;
+        }
+    }
+
+    /// Whether transcripts are still held, closing the window once it is due.
+    pub(crate) fn holding(&mut self, now: Instant, capturing: bool) -> bool {
+        let Some(deadline) = self.deadline else {
+            return false;
+        };
+        if !self.send_now && (now < deadline || capturing) {
+            return true;
+        }
+        self.deadline = None;
+        self.send_now = false;
+        self.shown_secs = None;
+        false
+    }
+
+    /// Countdown status, only when the whole seconds left have changed since last shown.
+    pub(crate) fn countdown_status(&mut self, held: usize, now: Instant) -> Option<String> {
+        let deadline = self.deadline?;
+        let left = deadline.saturating_duration_since(now);
+        let secs = left.as_millis().div_ceil(1000) as u64;
+        if self.shown_secs == Some(secs) {
+            return None;
+        }
+        self.shown_secs = Some(secs);
+        Some(format!(
+            "Batching {held} • sends in {secs}s • Enter sends now"
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_window_disables_batching() {
+        assert!(BatchWindow::from_ms(0).is_none());
+        assert!(!BatchWindow::from_ms(3000).unwrap().is_open());
+    }
+
+    #[test]
+    fn window_restarts_on_each_transcript_and_waits_for_capture() {
+        let start = Instant::now();
+        let mut batch = BatchWindow::from_ms(3000).unwrap();
+        assert!(
+            !batch.holding(start, false),
+            "nothing held before a transcript"
+        );
+
+        batch.extend(start);
+        batch.extend(start + Duration::from_secs(2));
+        assert!(batch.holding(start + Duration::from_secs(4), false));
+        assert!(
+            batch.holding(start + Duration::from_secs(6), true),
+            "a capture in progress can still join"
+        );
+        assert!(!batch.holding(start + Duration::from_secs(6), false));
+        assert!(!batch.is_open());
+    }
+
+    #[test]
+    fn send_now_closes_the_window_even_mid_capture() {
+        let start = Instant::now();
+        let mut batch = BatchWindow::from_ms(3000).unwrap();
+        batch.send_now();
+        batch.extend(start);
+        assert!(
+            batch.holding(start, false),
+            "send_now before a transcript is ignored"
+        );
+        batch.send_now();
+        assert!(!batch.holding(start, true));
+        assert!(!batch.is_open());
+    }
+
+    #[test]
+    fn countdown_status_changes_once_per_second() {
+        let start = Instant::now();
+        let mut batch = BatchWindow::from_ms(3000).unwrap();
+        assert!(batch.countdown_status(1, start).is_none());
+        batch.extend(start);
+        assert_eq!(
+            batch.countdown_status(2, start).as_deref(),
+            Some("Batching 2 • sends in 3s • Enter sends now")
+        );
+        assert!(batch
+            .countdown_status(2, start + Duration::from_millis(500))
+            .is_none());
+        assert!(batch
+            .countdown_status(2, start + Duration::from_millis(1500))
+            .is_some_and(|status| status.contains("2s")));
+    }
+}
@@ -0,0 +1,142 @@
+//! System clipboard destination so transcripts can be pasted outside the backend session.
+//!
+//! Uses the platform clipboard tool (`pbcopy`, `wl-copy`, `xclip`, `xsel`). When
+//! none is available (for example over SSH), callers fall back to an OSC 52
+//! sequence that asks the outer terminal to set its clipboard.
+
+use anyhow::{bail, Context, Result};
+use std::env;
+use std::io::{ErrorKind, Write};
+use std::process::{Command, Stdio};
+
+/// Largest payload sent through OSC 52; many terminals ignore longer sequences.
+const OSC52_MAX_BYTES: usize = 74_994;
+
+/// Clipboard commands to try, in order, for the current platform and display.
+fn clipboard_commands(
+    macos: bool,
+    wayland: bool,
+    x11: bool,
+) -> Vec<(&'static str, &'static [&'static str])> {
+    let mut commands: Vec<(&'static str, &'static [&'static str])> = Vec::new();
+    if macos {
+        commands.push(("pbcopy", &[]));
+    }
+    if wayland {
+        commands.push(("wl-copy", &[]));
+    }
+    if x11 {
+        commands.push(("xclip", &["-selection", "clipboard"]));
+        commands.push(("xsel", &["--clipboard", "--input"]));
+    }
+    commands
+}
+
+/// Copy `text` with the first available clipboard tool and return the tool name.
+pub(crate) fn copy_to_clipboard(text: &str) -> Result<&'static str> {
+    let has_env = |name: &str| env::var_os(name).is_some_and(|value| !value.is_empty());
+    let commands = clipboard_commands(
+        cfg!(target_os = "macos"),
+        has_env("WAYLAND_DISPLAY"),
+        has_env("DISPLAY"),
+    );
+    for (program, args) in commands {
+        let mut child = match Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        {
+            Ok(child) => child,
+            Err(err) if err.kind() == ErrorKind::NotFound => continue,
+            Err(err) => return Err(err).with_context(|| format!("failed to start {program}")),
+        };
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(text.as_bytes())
+                .with_context(|| format!("failed to write to {program}"))?;
+        }
+        let status = child
+            .wait()
+            .with_context(|| format!("failed to wait for {program}"))?;
+        if !status.success() {
+            bail!("{program} exited with {status}");
+        }
+        return Ok(program);
+    }
+    bail!("no clipboard tool found (install wl-clipboard, xclip, or xsel)")
+}
+
+/// OSC 52 "set clipboard" sequence, or `None` when the text is too large to send.
+pub(crate) fn osc52_sequence(text: &str) -> Option<Vec<u8>> {
+    let encoded = base64_encode(text.as_bytes());
+    if encoded.len() > OSC52_MAX_BYTES {
+        return None;
+    }
+    let mut sequence = Vec::with_capacity(encoded.len() + 8);
+    sequence.extend_from_slice(b"\x1b]52;c;");
+    sequence.extend_from_slice(encoded.as_bytes());
+    sequence.push(0x07);
+    Some(sequence)
+}
+
+pub(crate) fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for (index, shift) in [18u32, 12, 6, 0].into_iter().enumerate() {
+            if index <= chunk.len() {
+                out.push(ALPHABET[((n >> shift) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clipboard_commands_follow_platform_and_display() {
+        fn names(commands: Vec<(&'static str, &'static [&'static str])>) -> Vec<&'static str> {
+            commands.into_iter().map(|(name, _)| name).collect()
+        }
+        assert_eq!(
+            names(clipboard_commands(true, false, false)),
+            vec!["pbcopy"]
+        );
+        assert_eq!(
+            names(clipboard_commands(false, true, true)),
+            vec!["wl-copy", "xclip", "xsel"]
+        );
+        assert!(clipboard_commands(false, false, false).is_empty());
+    }
+
+    #[test]
+    fn base64_encode_pads_partial_chunks() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode(b"hello world"), "aGVsbG8gd29ybGQ=");
+    }
+
+    #[test]
+    fn osc52_sequence_wraps_encoded_text() {
+        assert_eq!(
+            osc52_sequence("hi").unwrap(),
+            b"\x1b]52;c;aGk=\x07".to_vec()
+        );
+        assert!(osc52_sequence(&"x".repeat(OSC52_MAX_BYTES)).is_none());
+    }
+}
@@ -0,0 +1,193 @@
+//! Code dictation so spoken structure words become formatted multi-line text.
+//!
+//! With code mode on (`--code-mode` or the settings toggle), words like "new
+//! line", "indent", and "open brace" are turned into line breaks, indentation,
+//! and symbols before delivery, so a dictated snippet reaches the backend as
+//! code rather than a run-on sentence. Other words pass through unchanged.
+
+/// Indentation added per level.
+const INDENT_UNIT: &str = "    ";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Spoken {
+    NewLine,
+    Indent,
+    Dedent,
+    OpenBlock,
+    CloseBlock,
+    /// A symbol and whether it attaches to the text before and after it.
+    Symbol(&'static str, bool, bool),
+}
+
+/// Spoken forms, longest first so "open brace" wins over a lone word.
+const SPOKEN: &[(&[&str], Spoken)] = &[
+    (&["new", "line"], Spoken::NewLine),
+    (&["next", "line"], Spoken::NewLine),
+    (&["newline"], Spoken::NewLine),
+    (&["indent"], Spoken::Indent),
+    (&["dedent"], Spoken::Dedent),
+    (&["outdent"], Spoken::Dedent),
+    (&["unindent"], Spoken::Dedent),
+    (&["open", "brace"], Spoken::OpenBlock),
+    (&["open", "curly"], Spoken::OpenBlock),
+    (&["close", "brace"], Spoken::CloseBlock),
+    (&["close", "curly"], Spoken::CloseBlock),
+    (&["open", "paren"], Spoken::Symbol("(", true, true)),
+    (&["close", "paren"], Spoken::Symbol(")", true, false)),
+    (&["open", "bracket"], Spoken::Symbol("[", true, true)),
+    (&["close", "bracket"], Spoken::Symbol("]", true, false)),
+    (&["semicolon"], Spoken::Symbol(";", true, false)),
+    (&["colon"], Spoken::Symbol(":", true, false)),
+    (&["comma"], Spoken::Symbol(",", true, false)),
+    (&["dot"], Spoken::Symbol(".", true, true)),
+    (&["equals"], Spoken::Symbol("=", false, false)),
+    (&["arrow"], Spoken::Symbol("->", false, false)),
+];
+
+/// Lines built so far plus the line being written.
+#[derive(Default)]
+struct CodeWriter {
+    lines: Vec<String>,
+    indent: usize,
+    line_indent: usize,
+    line: String,
+    glue_next: bool,
+}
+
+impl CodeWriter {
+    fn push(&mut self, text: &str, glue_left: bool, glue_right: bool) {
+        if !self.line.is_empty() && !self.glue_next && !glue_left {
+            self.line.push(' ');
+        }
+        self.line.push_str(text);
+        self.glue_next = glue_right;
+    }
+
+    fn new_line(&mut self) {
+        let line = std::mem::take(&mut self.line);
+        self.lines
+            .push(format!("{}{line}", INDENT_UNIT.repeat(self.line_indent)));
+        self.line_indent = self.indent;
+        self.glue_next = false;
+    }
+
+    fn set_indent(&mut self, indent: usize) {
+        self.indent = indent;
+        if self.line.is_empty() {
+            self.line_indent = indent;
+        }
+    }
+
+    fn apply(&mut self, spoken: Spoken) {
+        match spoken {
+            Spoken::NewLine => self.new_line(),
+            Spoken::Indent => self.set_indent(self.indent + 1),
+            Spoken::Dedent => self.set_indent(self.indent.saturating_sub(1)),
+            Spoken::OpenBlock => {
+                self.push("{", false, false);
+                self.set_indent(self.indent + 1);
+                self.new_line();
+            }
+            Spoken::CloseBlock => {
+                if !self.line.is_empty() {
+                    self.new_line();
+                }
+                self.set_indent(self.indent.saturating_sub(1));
+                self.push("}", false, false);
+            }
+            Spoken::Symbol(symbol, glue_left, glue_right) => {
+                self.push(symbol, glue_left, glue_right);
+            }
+        }
+    }
+
+    fn finish(mut self) -> String {
+        if !self.line.is_empty() {
+            self.new_line();
+        }
+        while self.lines.last().is_some_and(|line| line.trim().is_empty()) {
+            self.lines.pop();
+        }
+        self.lines.join("\n")
+    }
+}
+
+/// Rewrite spoken structure words in `text` into formatted code.
+pub(crate) fn format_code_dictation(text: &str) -> String {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    // Whisper punctuates and capitalizes; compare structure words without that.
+    let keys: Vec<String> = words
+        .iter()
+        .map(|word| {
+            word.trim_matches(|ch: char| matches!(ch, '.' | ',' | '!' | '?'))
+                .to_lowercase()
+        })
+        .collect();
+    let mut writer = CodeWriter::default();
+    let mut index = 0;
+    while index < words.len() {
+        let matched = SPOKEN.iter().find(|(phrase, _)| {
+            keys.get(index..index + phrase.len())
+                .is_some_and(|window| window.iter().zip(phrase.iter()).all(|(a, b)| a == b))
+        });
+        match matched {
+            Some((phrase, spoken)) => {
+                writer.apply(*spoken);
+                index += phrase.len();
+            }
+            None => {
+                writer.push(words[index], false, false);
+                index += 1;
+            }
+        }
+    }
+    writer.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn braces_open_indented_blocks() {
+        let spoken = "fn main open paren close paren open brace \
+                      println open paren x close paren semicolon close brace";
+        assert_eq!(
+            format_code_dictation(spoken),
+            "fn main() {\n    println(x);\n}"
+        );
+    }
+
+    #[test]
+    fn new_line_and_indent_shape_python() {
+        let spoken = "If ready colon. New line. Indent run dot start open paren close paren. \
+                      New line. Dedent. Print open paren done close paren.";
+        assert_eq!(
+            format_code_dictation(spoken),
+            "If ready:\n    run.start()\nPrint(done)"
+        );
+    }
+
+    #[test]
+    fn plain_text_passes_through() {
+        assert_eq!(
+            format_code_dictation("rename the helper to parse_args"),
+            "rename the helper to parse_args"
+        );
+        assert_eq!(format_code_dictation(""), "");
+    }
+
+    #[test]
+    fn operators_keep_their_spacing_and_dedent_stops_at_zero() {
+        assert_eq!(
+            format_code_dictation(
+                "dedent let x equals items open bracket 0 close bracket semicolon"
+            ),
+            "let x = items[0];"
+        );
+        assert_eq!(
+            format_code_dictation("fn f arrow u32 new line new line"),
+            "fn f -> u32"
+        );
+    }
+}
@@ -1,24 +1,39 @@
 //! Transcript delivery flow so queued text reaches PTY sessions with clear status updates.
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use crossbeam_channel::Sender;
 use std::collections::VecDeque;
 use std::time::{Duration, Instant};
-use voiceterm::{log_debug, VoiceCaptureSource};
+use voiceterm::log_debug;
 
-use crate::config::VoiceSendMode;
-use crate::prompt::PromptTracker;
+use voiceterm::VoiceCaptureSource;
+
+use crate::config::{TranscriptQueuePolicy, VoiceSendMode};
+use crate::prompt::{PromptTracker, UiMode};
+use crate::session_events::{emit_session_event, VoiceSessionEvent};
+use crate::session_record::send_mode_key;
 use crate::status_line::StatusLineState;
 use crate::writer::{set_status, WriterMessage};
 
+use super::approval::answer_approval;
+use super::clipboard::{copy_to_clipboard, osc52_sequence};
+use super::dictation::DRAFT_LABEL;
 use super::idle::transcript_ready;
-use super::queue::PendingTranscript;
+use super::multiline::MultilineDelivery;
+use super::queue::{
+    push_pending_transcript, queue_full_status, PendingTranscript, QueueOutcome, QueuePolicy,
+    MIXED_LABEL,
+};
 use super::session::TranscriptSession;
 
+/// Delivery label for text pushed in by another program (e.g. `voiceterm send`).
+pub(crate) const EXTERNAL_LABEL: &str = "external";
+
 struct PendingBatch {
     text: String,
     label: String,
     mode: VoiceSendMode,
+    received_at: Instant,
 }
 
 /// Context bundle for transcript delivery and status updates.
@@ -33,6 +48,8 @@ pub(crate) struct TranscriptIo<'a, S: TranscriptSession> {
     pub(crate) current_status: &'a mut Option<String>,
     /// Current status-line state for overlay rendering.
     pub(crate) status_state: &'a mut StatusLineState,
+    /// Encoding for transcripts that contain newlines.
+    pub(crate) multiline: &'a MultilineDelivery,
 }
 
 impl<'a, S: TranscriptSession> TranscriptIo<'a, S> {
@@ -48,6 +65,11 @@ impl<'a, S: TranscriptSession> TranscriptIo<'a, S> {
     }
 }
 
+/// Deliver queued transcripts once the backend is ready.
+///
+/// While an approval dialog is open, a queued yes/no answer is sent as the
+/// dialog's key instead. Returns how long the oldest delivered transcript
+/// waited in the queue.
 pub(crate) fn try_flush_pending<S: TranscriptSession>(
     pending: &mut VecDeque<PendingTranscript>,
     prompt_tracker: &PromptTracker,
@@ -55,29 +77,35 @@ pub(crate) fn try_flush_pending<S: TranscriptSession>(
     io: &mut TranscriptIo<'_, S>,
     now: Instant,
     transcript_idle_timeout: Duration,
-) {
+) -> Option<Duration> {
+    if let UiMode::Approval(keys) = prompt_tracker.ui_mode() {
+        if prompt_tracker.is_busy(now) {
+            return None;
+        }
+        return answer_approval(pending, keys, last_enter_at, io, now);
+    }
     if pending.is_empty()
         || !transcript_ready(prompt_tracker, *last_enter_at, now, transcript_idle_timeout)
     {
-        return;
+        return None;
     }
-    let Some(batch) = merge_pending_transcripts(pending) else {
-        return;
-    };
+    let batch = merge_pending_transcripts(pending)?;
     let remaining = pending.len();
     io.status_state.queue_depth = remaining;
     let sent_newline =
         deliver_transcript(&batch.text, &batch.label, batch.mode, io, remaining, None);
     if sent_newline {
-        *last_enter_at = Some(Instant::now());
+        *last_enter_at = Some(now);
     }
+    Some(now.saturating_duration_since(batch.received_at))
 }
 
 fn merge_pending_transcripts(pending: &mut VecDeque<PendingTranscript>) -> Option<PendingBatch> {
     // Batch consecutive transcripts with the same send mode to avoid mixing auto/insert.
     let mode = pending.front()?.mode;
+    let received_at = pending.front()?.received_at;
     let mut parts: Vec<String> = Vec::new();
-    let mut sources: Vec<VoiceCaptureSource> = Vec::new();
+    let mut labels: Vec<&'static str> = Vec::new();
     while let Some(next) = pending.front() {
         if next.mode != mode {
             break;
@@ -88,45 +116,124 @@ fn merge_pending_transcripts(pending: &mut VecDeque<PendingTranscript>) -> Optio
         let trimmed = next.text.trim();
         if !trimmed.is_empty() {
             parts.push(trimmed.to_string());
-            sources.push(next.source);
+            labels.push(next.label);
         }
     }
     if parts.is_empty() {
         return None;
     }
-    let label = if sources.iter().all(|source| *source == sources[0]) {
-        sources[0].label().to_string()
+    let label = if labels.iter().all(|label| *label == labels[0]) {
+        labels[0].to_string()
     } else {
-        "Mixed pipelines".to_string()
+        MIXED_LABEL.to_string()
     };
     Some(PendingBatch {
         text: parts.join(" "),
         label,
         mode,
+        received_at,
     })
 }
 
+/// Deliver text from another program now, or queue it behind dictated transcripts.
+///
+/// `ready` is whether the backend can take input (see `transcript_ready`). Returns
+/// the 1-based queue position, or `None` when the text was delivered right away;
+/// fails when a full `drop-newest` queue turned the text away.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn submit_external_transcript<S: TranscriptSession>(
+    text: &str,
+    mode: VoiceSendMode,
+    ready: bool,
+    pending: &mut VecDeque<PendingTranscript>,
+    queue: QueuePolicy,
+    last_enter_at: &mut Option<Instant>,
+    io: &mut TranscriptIo<'_, S>,
+    now: Instant,
+) -> Result<Option<usize>> {
+    if mode == VoiceSendMode::Clipboard || (ready && pending.is_empty()) {
+        if deliver_transcript(text, EXTERNAL_LABEL, mode, io, 0, None) {
+            *last_enter_at = Some(now);
+        }
+        return Ok(None);
+    }
+    let outcome = push_pending_transcript(
+        pending,
+        PendingTranscript {
+            text: text.to_string(),
+            label: EXTERNAL_LABEL,
+            mode,
+            received_at: now,
+            seq: None,
+        },
+        queue,
+    );
+    io.status_state.queue_depth = pending.len();
+    match outcome {
+        QueueOutcome::Dropped(dropped) => {
+            io.set_status(&queue_full_status(&dropped), Some(Duration::from_secs(2)));
+            // Under drop-newest the text just submitted is the one dropped.
+            if queue.overflow == TranscriptQueuePolicy::DropNewest {
+                bail!("transcript queue is full ({} pending)", pending.len());
+            }
+        }
+        QueueOutcome::Merged => io.set_status(
+            &format!(
+                "Transcript queued ({}, {EXTERNAL_LABEL}, merged)",
+                pending.len()
+            ),
+            None,
+        ),
+        QueueOutcome::Queued => io.set_status(
+            &format!("Transcript queued ({}, {EXTERNAL_LABEL})", pending.len()),
+            None,
+        ),
+    }
+    Ok(Some(pending.len()))
+}
+
 pub(crate) fn send_transcript(
     session: &mut impl TranscriptSession,
     text: &str,
     mode: VoiceSendMode,
+    multiline: &MultilineDelivery,
 ) -> Result<bool> {
     let trimmed = text.trim();
     if trimmed.is_empty() {
         return Ok(false);
     }
+    let encoded = multiline.encode(trimmed);
     match mode {
         VoiceSendMode::Auto => {
-            session.send_text_with_newline(trimmed)?;
+            session.send_text_with_newline(&encoded)?;
             Ok(true)
         }
         VoiceSendMode::Insert => {
-            session.send_text(trimmed)?;
+            session.send_text(&encoded)?;
             Ok(false)
         }
+        VoiceSendMode::Clipboard => {
+            copy_to_clipboard(trimmed)?;
+            Ok(false)
+        }
+    }
+}
+
+/// `source` of the `transcript_delivered` event for a delivery label.
+///
+/// Only dictated text is reported; text from other programs, prompt replays,
+/// and echo retries (a resend of text already reported) give `None`.
+pub(super) fn delivered_source(label: &str) -> Option<&'static str> {
+    match label {
+        label if label == VoiceCaptureSource::Native.label() => Some("native"),
+        label if label == VoiceCaptureSource::Python.label() => Some("python"),
+        MIXED_LABEL => Some("mixed"),
+        DRAFT_LABEL => Some(DRAFT_LABEL),
+        _ => None,
     }
 }
 
+/// Send `text` and emit `transcript_delivered` once it has left the overlay.
 pub(crate) fn deliver_transcript<S: TranscriptSession>(
     text: &str,
     label: &str,
@@ -135,19 +242,39 @@ pub(crate) fn deliver_transcript<S: TranscriptSession>(
     queued_remaining: usize,
     drop_note: Option<&str>,
 ) -> bool {
+    let emit_delivered = |text: &str| {
+        if let Some(source) = delivered_source(label) {
+            emit_session_event(|| VoiceSessionEvent::TranscriptDelivered {
+                text: text.trim().to_string(),
+                source: source.to_string(),
+                mode: send_mode_key(mode).to_string(),
+            });
+        }
+    };
     let mut label = label.to_string();
     if let Some(note) = drop_note {
         label.push_str(", ");
         label.push_str(note);
     }
+    if mode == VoiceSendMode::Clipboard {
+        if deliver_to_clipboard(text, &label, io) {
+            emit_delivered(text);
+        }
+        return false;
+    }
     let status = if queued_remaining > 0 {
         format!("Transcript ready ({label}) • queued {queued_remaining}")
     } else {
         format!("Transcript ready ({label})")
     };
     io.set_status(&status, Some(Duration::from_secs(2)));
-    match send_transcript(io.session, text, mode) {
-        Ok(sent_newline) => sent_newline,
+    match send_transcript(io.session, text, mode, io.multiline) {
+        Ok(sent_newline) => {
+            if !text.trim().is_empty() {
+                emit_delivered(text);
+            }
+            sent_newline
+        }
         Err(err) => {
             log_debug(&format!("failed to send transcript: {err:#}"));
             io.set_status(
@@ -159,13 +286,51 @@ pub(crate) fn deliver_transcript<S: TranscriptSession>(
     }
 }
 
+/// Copy to the system clipboard, falling back to OSC 52 through the terminal.
+///
+/// Returns whether the text reached a clipboard.
+fn deliver_to_clipboard<S: TranscriptSession>(
+    text: &str,
+    label: &str,
+    io: &mut TranscriptIo<'_, S>,
+) -> bool {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return false;
+    }
+    let mut copied = true;
+    let status = match copy_to_clipboard(trimmed) {
+        Ok(tool) => {
+            log_debug(&format!("transcript copied to clipboard via {tool}"));
+            format!("Transcript copied to clipboard ({label})")
+        }
+        Err(err) => {
+            log_debug(&format!("clipboard copy failed: {err:#}; trying OSC 52"));
+            let sent = osc52_sequence(trimmed).is_some_and(|sequence| {
+                io.writer_tx
+                    .send(WriterMessage::PtyOutput(sequence))
+                    .is_ok()
+            });
+            if sent {
+                format!("Transcript sent to terminal clipboard ({label})")
+            } else {
+                copied = false;
+                "Clipboard unavailable (see log)".to_string()
+            }
+        }
+    };
+    io.set_status(&status, Some(Duration::from_secs(2)));
+    copied
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::MultilineMode;
     use crate::prompt::{PromptLogger, PromptTracker};
-    use crate::transcript::push_pending_transcript;
     use crossbeam_channel::Receiver;
     use regex::Regex;
+    use voiceterm::VoiceCaptureSource;
 
     #[derive(Default)]
     struct StubSession {
@@ -199,18 +364,55 @@ mod tests {
         false
     }
 
+    #[test]
+    fn send_transcript_pastes_multiline_text_as_one_message() {
+        let mut multiline = MultilineDelivery::new(MultilineMode::Paste, "codex");
+        multiline.observe_output(b"\x1b[?2004h");
+        let mut session = StubSession::default();
+        let sent = send_transcript(
+            &mut session,
+            "line one\nline two\n",
+            VoiceSendMode::Auto,
+            &multiline,
+        )
+        .unwrap();
+        assert!(sent);
+        assert_eq!(
+            session.sent_with_newline,
+            vec!["\x1b[200~line one\nline two\x1b[201~"]
+        );
+    }
+
     #[test]
     fn send_transcript_respects_mode_and_trims() {
         let mut session = StubSession::default();
-        let sent = send_transcript(&mut session, " hello ", VoiceSendMode::Auto).unwrap();
+        let sent = send_transcript(
+            &mut session,
+            " hello ",
+            VoiceSendMode::Auto,
+            &MultilineDelivery::default(),
+        )
+        .unwrap();
         assert!(sent);
         assert_eq!(session.sent_with_newline, vec!["hello"]);
 
-        let sent = send_transcript(&mut session, " hi ", VoiceSendMode::Insert).unwrap();
+        let sent = send_transcript(
+            &mut session,
+            " hi ",
+            VoiceSendMode::Insert,
+            &MultilineDelivery::default(),
+        )
+        .unwrap();
         assert!(!sent);
         assert_eq!(session.sent, vec!["hi"]);
 
-        let sent = send_transcript(&mut session, "   ", VoiceSendMode::Insert).unwrap();
+        let sent = send_transcript(
+            &mut session,
+            "   ",
+            VoiceSendMode::Insert,
+            &MultilineDelivery::default(),
+        )
+        .unwrap();
         assert!(!sent);
         assert_eq!(session.sent.len(), 1);
     }
@@ -222,21 +424,27 @@ mod tests {
             &mut pending,
             PendingTranscript {
                 text: "hello".to_string(),
-                source: VoiceCaptureSource::Native,
+                label: VoiceCaptureSource::Native.label(),
                 mode: VoiceSendMode::Auto,
+                received_at: Instant::now(),
+                seq: None,
             },
+            QueuePolicy::default(),
         );
         push_pending_transcript(
             &mut pending,
             PendingTranscript {
                 text: "world".to_string(),
-                source: VoiceCaptureSource::Native,
+                label: VoiceCaptureSource::Native.label(),
                 mode: VoiceSendMode::Auto,
+                received_at: Instant::now(),
+                seq: None,
             },
+            QueuePolicy::default(),
         );
 
         let logger = PromptLogger::new(None);
-        let mut tracker = PromptTracker::new(None, true, logger);
+        let mut tracker = PromptTracker::new(Vec::new(), true, logger);
         let now = Instant::now();
         tracker.note_activity(now);
 
@@ -251,10 +459,11 @@ mod tests {
             status_clear_deadline: &mut deadline,
             current_status: &mut current_status,
             status_state: &mut status_state,
+            multiline: &MultilineDelivery::default(),
         };
         let idle_timeout = Duration::from_millis(50);
         let mut last_enter_at = None;
-        try_flush_pending(
+        let waited = try_flush_pending(
             &mut pending,
             &tracker,
             &mut last_enter_at,
@@ -262,10 +471,113 @@ mod tests {
             now + idle_timeout + Duration::from_millis(1),
             idle_timeout,
         );
+        assert!(waited.is_some_and(|waited| waited >= idle_timeout));
         assert_eq!(session.sent_with_newline, vec!["hello world"]);
         assert!(pending.is_empty());
     }
 
+    #[test]
+    fn try_flush_pending_answers_approval_dialogs_and_holds_other_text() {
+        let mut pending = VecDeque::new();
+        for text in ["Yes.", "now add a test"] {
+            pending.push_back(PendingTranscript {
+                text: text.to_string(),
+                label: VoiceCaptureSource::Native.label(),
+                mode: VoiceSendMode::Auto,
+                received_at: Instant::now(),
+                seq: None,
+            });
+        }
+        let mut tracker = PromptTracker::new(Vec::new(), true, PromptLogger::new(None));
+        tracker.feed_output(
+            "Run cargo test?\r\n› 1. Yes, proceed (y)\r\n  2. No, tell Codex (esc)".as_bytes(),
+        );
+
+        let (writer_tx, _writer_rx) = crossbeam_channel::bounded(8);
+        let mut session = StubSession::default();
+        let mut deadline = None;
+        let mut current_status = None;
+        let mut status_state = crate::status_line::StatusLineState::new();
+        let mut io = TranscriptIo {
+            session: &mut session,
+            writer_tx: &writer_tx,
+            status_clear_deadline: &mut deadline,
+            current_status: &mut current_status,
+            status_state: &mut status_state,
+            multiline: &MultilineDelivery::default(),
+        };
+        let idle_timeout = Duration::from_millis(50);
+        let now = tracker.last_output_at() + idle_timeout;
+        let mut last_enter_at = None;
+        for _ in 0..2 {
+            try_flush_pending(
+                &mut pending,
+                &tracker,
+                &mut last_enter_at,
+                &mut io,
+                now,
+                idle_timeout,
+            );
+        }
+        assert_eq!(io.current_status.as_deref(), Some("Approval answered: yes"));
+        assert_eq!(last_enter_at, Some(now));
+        assert_eq!(session.sent, vec!["y"]);
+        assert!(session.sent_with_newline.is_empty());
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].text, "now add a test");
+    }
+
+    #[test]
+    fn try_flush_pending_delivers_overlapping_pipelines_in_capture_order() {
+        let mut pending = VecDeque::new();
+        // Capture 1 fell back to python and finished after native capture 2.
+        for (text, source, seq) in [
+            ("then commit", VoiceCaptureSource::Native, 2),
+            ("run the tests", VoiceCaptureSource::Python, 1),
+        ] {
+            push_pending_transcript(
+                &mut pending,
+                PendingTranscript {
+                    text: text.to_string(),
+                    label: source.label(),
+                    mode: VoiceSendMode::Auto,
+                    received_at: Instant::now(),
+                    seq: Some(seq),
+                },
+                QueuePolicy::default(),
+            );
+        }
+
+        let logger = PromptLogger::new(None);
+        let mut tracker = PromptTracker::new(Vec::new(), true, logger);
+        let now = Instant::now();
+        tracker.note_activity(now);
+        let (writer_tx, _writer_rx) = crossbeam_channel::bounded(8);
+        let mut session = StubSession::default();
+        let mut deadline = None;
+        let mut current_status = None;
+        let mut status_state = crate::status_line::StatusLineState::new();
+        let mut io = TranscriptIo {
+            session: &mut session,
+            writer_tx: &writer_tx,
+            status_clear_deadline: &mut deadline,
+            current_status: &mut current_status,
+            status_state: &mut status_state,
+            multiline: &MultilineDelivery::default(),
+        };
+        let idle_timeout = Duration::from_millis(50);
+        let mut last_enter_at = None;
+        try_flush_pending(
+            &mut pending,
+            &tracker,
+            &mut last_enter_at,
+            &mut io,
+            now + idle_timeout + Duration::from_millis(1),
+            idle_timeout,
+        );
+        assert_eq!(session.sent_with_newline, vec!["run the tests then commit"]);
+    }
+
     #[test]
     fn try_flush_pending_waits_for_prompt_when_busy() {
         let mut pending = VecDeque::new();
@@ -273,14 +585,17 @@ mod tests {
             &mut pending,
             PendingTranscript {
                 text: "hello".to_string(),
-                source: VoiceCaptureSource::Native,
+                label: VoiceCaptureSource::Native.label(),
                 mode: VoiceSendMode::Auto,
+                received_at: Instant::now(),
+                seq: None,
             },
+            QueuePolicy::default(),
         );
 
         let logger = PromptLogger::new(None);
         let regex = Regex::new(r"^> $").unwrap();
-        let mut tracker = PromptTracker::new(Some(regex), false, logger);
+        let mut tracker = PromptTracker::new(vec![regex], false, logger);
 
         let (writer_tx, _writer_rx) = crossbeam_channel::bounded(8);
         let mut session = StubSession::default();
@@ -297,8 +612,9 @@ mod tests {
                 status_clear_deadline: &mut deadline,
                 current_status: &mut current_status,
                 status_state: &mut status_state,
+                multiline: &MultilineDelivery::default(),
             };
-            try_flush_pending(
+            let waited = try_flush_pending(
                 &mut pending,
                 &tracker,
                 &mut last_enter_at,
@@ -306,6 +622,7 @@ mod tests {
                 now,
                 Duration::from_secs(2),
             );
+            assert_eq!(waited, None);
         }
         assert!(!pending.is_empty());
         assert!(session.sent_with_newline.is_empty());
@@ -319,6 +636,7 @@ mod tests {
                 status_clear_deadline: &mut deadline,
                 current_status: &mut current_status,
                 status_state: &mut status_state,
+                multiline: &MultilineDelivery::default(),
             };
             try_flush_pending(
                 &mut pending,
@@ -333,6 +651,172 @@ mod tests {
         assert_eq!(session.sent_with_newline, vec!["hello"]);
     }
 
+    #[test]
+    fn transcript_delivered_fires_only_when_queued_text_is_sent() {
+        let delivered = crate::session_events::delivered_transcripts();
+        let policy = QueuePolicy {
+            size: 1,
+            overflow: TranscriptQueuePolicy::DropOldest,
+        };
+        let mut pending = VecDeque::new();
+        for text in ["dropped before delivery", "queued until the prompt"] {
+            push_pending_transcript(
+                &mut pending,
+                PendingTranscript {
+                    text: text.to_string(),
+                    label: VoiceCaptureSource::Native.label(),
+                    mode: VoiceSendMode::Auto,
+                    received_at: Instant::now(),
+                    seq: None,
+                },
+                policy,
+            );
+        }
+
+        let logger = PromptLogger::new(None);
+        let regex = Regex::new(r"^> $").unwrap();
+        let mut tracker = PromptTracker::new(vec![regex], false, logger);
+        let (writer_tx, _writer_rx) = crossbeam_channel::bounded(8);
+        let mut session = StubSession::default();
+        let mut deadline = None;
+        let mut current_status = None;
+        let mut status_state = crate::status_line::StatusLineState::new();
+        let mut io = TranscriptIo {
+            session: &mut session,
+            writer_tx: &writer_tx,
+            status_clear_deadline: &mut deadline,
+            current_status: &mut current_status,
+            status_state: &mut status_state,
+            multiline: &MultilineDelivery::default(),
+        };
+        let mut last_enter_at = Some(Instant::now());
+        let was_delivered = |text: &str| delivered.lock().unwrap().iter().any(|t| t == text);
+
+        tracker.feed_output(b"working...\n");
+        try_flush_pending(
+            &mut pending,
+            &tracker,
+            &mut last_enter_at,
+            &mut io,
+            Instant::now(),
+            Duration::from_secs(2),
+        );
+        assert!(!was_delivered("queued until the prompt"));
+
+        tracker.feed_output(b"> \n");
+        try_flush_pending(
+            &mut pending,
+            &tracker,
+            &mut last_enter_at,
+            &mut io,
+            Instant::now(),
+            Duration::from_secs(2),
+        );
+        assert!(was_delivered("queued until the prompt"));
+        assert!(!was_delivered("dropped before delivery"));
+
+        // Text from other programs is not a dictated transcript.
+        deliver_transcript(
+            "typed by another program",
+            EXTERNAL_LABEL,
+            VoiceSendMode::Insert,
+            &mut io,
+            0,
+            None,
+        );
+        assert!(!was_delivered("typed by another program"));
+        deliver_transcript(
+            "dictated draft text",
+            DRAFT_LABEL,
+            VoiceSendMode::Insert,
+            &mut io,
+            0,
+            None,
+        );
+        assert!(was_delivered("dictated draft text"));
+    }
+
+    #[test]
+    fn submit_external_transcript_delivers_when_ready_and_queues_otherwise() {
+        let (writer_tx, _writer_rx) = crossbeam_channel::bounded(8);
+        let mut session = StubSession::default();
+        let mut deadline = None;
+        let mut current_status = None;
+        let mut status_state = crate::status_line::StatusLineState::new();
+        let mut pending = VecDeque::new();
+        let mut last_enter_at = None;
+        let now = Instant::now();
+        let mut io = TranscriptIo {
+            session: &mut session,
+            writer_tx: &writer_tx,
+            status_clear_deadline: &mut deadline,
+            current_status: &mut current_status,
+            status_state: &mut status_state,
+            multiline: &MultilineDelivery::default(),
+        };
+        let position = submit_external_transcript(
+            "run the tests",
+            VoiceSendMode::Auto,
+            true,
+            &mut pending,
+            QueuePolicy::default(),
+            &mut last_enter_at,
+            &mut io,
+            now,
+        );
+        assert_eq!(position.unwrap(), None);
+        assert_eq!(last_enter_at, Some(now));
+
+        for text in ["first", "second"] {
+            submit_external_transcript(
+                text,
+                VoiceSendMode::Insert,
+                false,
+                &mut pending,
+                QueuePolicy::default(),
+                &mut last_enter_at,
+                &mut io,
+                now,
+            )
+            .expect("queued");
+        }
+        // Once something is queued, later text waits its turn even if the backend is ready.
+        let position = submit_external_transcript(
+            "third",
+            VoiceSendMode::Insert,
+            true,
+            &mut pending,
+            QueuePolicy::default(),
+            &mut last_enter_at,
+            &mut io,
+            now,
+        );
+        assert_eq!(position.unwrap(), Some(3));
+        assert_eq!(io.status_state.queue_depth, 3);
+        assert_eq!(io.status_state.message, "Transcript queued (3, external)");
+        // A full drop-newest queue turns the sender away.
+        let full = QueuePolicy {
+            size: 3,
+            overflow: TranscriptQueuePolicy::DropNewest,
+        };
+        let result = submit_external_transcript(
+            "fourth",
+            VoiceSendMode::Insert,
+            false,
+            &mut pending,
+            full,
+            &mut last_enter_at,
+            &mut io,
+            now,
+        );
+        assert!(result.is_err());
+        assert_eq!(io.status_state.message, "Queue full; dropped \"fourth\"");
+        assert_eq!(pending.len(), 3);
+        assert_eq!(session.sent_with_newline, vec!["run the tests"]);
+        assert!(session.sent.is_empty());
+        assert!(pending.iter().all(|queued| queued.label == EXTERNAL_LABEL));
+    }
+
     #[test]
     fn deliver_transcript_injects_into_pty() {
         let mut session =
@@ -348,6 +832,7 @@ mod tests {
             status_clear_deadline: &mut deadline,
             current_status: &mut current_status,
             status_state: &mut status_state,
+            multiline: &MultilineDelivery::default(),
         };
         let sent_newline =
             deliver_transcript("hello", "Rust", VoiceSendMode::Auto, &mut io, 0, None);
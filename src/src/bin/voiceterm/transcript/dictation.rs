@@ -0,0 +1,252 @@
+//! Dictation buffer so insert-mode transcripts can be edited by voice before they are typed.
+//!
+//! With `--dictation-buffer`, insert-mode transcripts collect in a draft shown on
+//! the status row instead of going to the backend. Spoken edits change the draft
+//! ("scratch that" drops the last phrase, "replace X with Y" rewrites the most
+//! recent X, "clear all" empties it), Enter types the draft, and Esc discards it.
+
+use regex::Regex;
+use std::sync::OnceLock;
+use std::time::Duration;
+use voiceterm::log_debug;
+
+use super::delivery::{deliver_transcript, TranscriptIo};
+use super::redact::loggable;
+use super::session::TranscriptSession;
+use crate::config::VoiceSendMode;
+
+/// Delivery label of a typed draft.
+pub(super) const DRAFT_LABEL: &str = "draft";
+/// Characters of the draft's tail shown on the status row.
+const DICTATION_PREVIEW_MAX: usize = 80;
+const SCRATCH_COMMANDS: &[&str] = &["scratch that", "delete that", "undo that"];
+const CLEAR_COMMANDS: &[&str] = &["clear all", "scratch all"];
+
+/// Result of applying one transcript to the draft.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum DictationEdit {
+    Appended,
+    Scratched,
+    NothingToScratch,
+    Replaced { from: String, to: String },
+    NotFound(String),
+    Cleared,
+}
+
+impl DictationEdit {
+    pub(crate) fn status_message(&self) -> String {
+        match self {
+            Self::Appended => "Draft: Enter types it, Esc discards".to_string(),
+            Self::Scratched => "Scratched last phrase".to_string(),
+            Self::NothingToScratch => "Draft is empty".to_string(),
+            Self::Replaced { from, to } => {
+                format!("Replaced '{}' with '{}'", loggable(from), loggable(to))
+            }
+            Self::NotFound(from) => format!("'{}' not in draft", loggable(from)),
+            Self::Cleared => "Draft cleared".to_string(),
+        }
+    }
+}
+
+/// Draft text kept as the phrases it was dictated in, so edits can undo a whole phrase.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DictationBuffer {
+    phrases: Vec<String>,
+}
+
+impl DictationBuffer {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.phrases.is_empty()
+    }
+
+    pub(crate) fn text(&self) -> String {
+        self.phrases.join(" ")
+    }
+
+    /// Treat `transcript` as an edit command when it is one, otherwise append it.
+    pub(crate) fn apply(&mut self, transcript: &str) -> DictationEdit {
+        let edit = self.edit(transcript);
+        log_debug(&format!("dictation edit|{}", edit.status_message()));
+        edit
+    }
+
+    fn edit(&mut self, transcript: &str) -> DictationEdit {
+        let transcript = transcript.trim();
+        let command = normalize_command(transcript);
+        if SCRATCH_COMMANDS.contains(&command.as_str()) {
+            return match self.phrases.pop() {
+                Some(_) => DictationEdit::Scratched,
+                None => DictationEdit::NothingToScratch,
+            };
+        }
+        if CLEAR_COMMANDS.contains(&command.as_str()) {
+            self.phrases.clear();
+            return DictationEdit::Cleared;
+        }
+        if let Some(captures) = replace_pattern().captures(transcript) {
+            let from = trim_spoken(&captures[1]).to_string();
+            let to = trim_spoken(&captures[2]).to_string();
+            return if self.replace_last(&from, &to) {
+                DictationEdit::Replaced { from, to }
+            } else {
+                DictationEdit::NotFound(from)
+            };
+        }
+        if !transcript.is_empty() {
+            self.phrases.push(transcript.to_string());
+        }
+        DictationEdit::Appended
+    }
+
+    /// Take the draft, leaving the buffer empty.
+    pub(crate) fn take(&mut self) -> String {
+        let text = self.text();
+        self.phrases.clear();
+        text
+    }
+
+    /// Tail of the draft for the status row, or `None` when empty.
+    pub(crate) fn preview(&self) -> Option<String> {
+        if self.is_empty() {
+            return None;
+        }
+        let text = self.text();
+        let len = text.chars().count();
+        if len <= DICTATION_PREVIEW_MAX {
+            return Some(text);
+        }
+        let tail: String = text
+            .chars()
+            .skip(len - (DICTATION_PREVIEW_MAX - 1))
+            .collect();
+        Some(format!("…{tail}"))
+    }
+
+    /// Replace the most recent whole-word match of `from`, searching newest phrase first.
+    fn replace_last(&mut self, from: &str, to: &str) -> bool {
+        let words: Vec<String> = from.split_whitespace().map(regex::escape).collect();
+        if words.is_empty() || to.is_empty() {
+            return false;
+        }
+        let Ok(pattern) = Regex::new(&format!(r"(?i)\b{}\b", words.join(r"\s+"))) else {
+            return false;
+        };
+        for phrase in self.phrases.iter_mut().rev() {
+            if let Some(found) = pattern.find_iter(phrase).last() {
+                phrase.replace_range(found.range(), to);
+                return true;
+            }
+        }
+        false
+    }
+}
+
+fn replace_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(r"(?is)^replace\s+(.+?)\s+with\s+(.+)$").expect("replace pattern compiles")
+    })
+}
+
+/// Whisper punctuates short utterances ("Scratch that."), so compare without it.
+pub(crate) fn normalize_command(text: &str) -> String {
+    trim_spoken(text)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+fn trim_spoken(text: &str) -> &str {
+    text.trim()
+        .trim_matches(|ch: char| matches!(ch, '.' | ',' | '!' | '?' | '"' | '\''))
+        .trim()
+}
+
+/// Type or drop the draft; returns true when a newline was sent.
+pub(crate) fn resolve_dictation<S: TranscriptSession>(
+    buffer: &mut DictationBuffer,
+    confirmed: bool,
+    io: &mut TranscriptIo<'_, S>,
+) -> bool {
+    let text = buffer.take();
+    io.status_state.dictation = None;
+    if confirmed {
+        deliver_transcript(&text, DRAFT_LABEL, VoiceSendMode::Insert, io, 0, None)
+    } else {
+        io.set_status("Draft discarded", Some(Duration::from_secs(2)));
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn buffer(phrases: &[&str]) -> DictationBuffer {
+        let mut buffer = DictationBuffer::default();
+        for phrase in phrases {
+            assert_eq!(buffer.apply(phrase), DictationEdit::Appended);
+        }
+        buffer
+    }
+
+    #[test]
+    fn phrases_append_and_scratch_that_drops_the_last_one() {
+        let mut draft = buffer(&["Fix the parser.", "Then run the tests."]);
+        assert_eq!(draft.text(), "Fix the parser. Then run the tests.");
+        assert_eq!(draft.apply("Scratch that."), DictationEdit::Scratched);
+        assert_eq!(draft.text(), "Fix the parser.");
+        assert_eq!(draft.apply("delete that"), DictationEdit::Scratched);
+        assert_eq!(draft.apply("undo that!"), DictationEdit::NothingToScratch);
+        assert!(draft.is_empty());
+    }
+
+    #[test]
+    fn replace_rewrites_the_most_recent_whole_word_match() {
+        let mut draft = buffer(&["add a test for the parser", "and the parser docs"]);
+        assert_eq!(
+            draft.apply("Replace parser with lexer."),
+            DictationEdit::Replaced {
+                from: "parser".to_string(),
+                to: "lexer".to_string(),
+            }
+        );
+        assert_eq!(draft.text(), "add a test for the parser and the lexer docs");
+        assert_eq!(
+            draft.apply("replace PARSE with x"),
+            DictationEdit::NotFound("PARSE".to_string())
+        );
+        assert_eq!(
+            draft.apply("replace a test with two tests"),
+            DictationEdit::Replaced {
+                from: "a test".to_string(),
+                to: "two tests".to_string(),
+            }
+        );
+        assert_eq!(
+            draft.text(),
+            "add two tests for the parser and the lexer docs"
+        );
+    }
+
+    #[test]
+    fn clear_all_empties_and_take_resets() {
+        let mut draft = buffer(&["one", "two"]);
+        assert_eq!(draft.apply("Clear all."), DictationEdit::Cleared);
+        assert!(draft.preview().is_none());
+        let mut draft = buffer(&["one", "two"]);
+        assert_eq!(draft.take(), "one two");
+        assert!(draft.is_empty());
+    }
+
+    #[test]
+    fn preview_keeps_the_tail_of_long_drafts() {
+        let long = "word ".repeat(40);
+        let draft = buffer(&[long.trim(), "the end"]);
+        let preview = draft.preview().unwrap();
+        assert_eq!(preview.chars().count(), DICTATION_PREVIEW_MAX);
+        assert!(preview.starts_with('…'));
+        assert!(preview.ends_with("word the end"));
+    }
+}
@@ -0,0 +1,195 @@
+//! Echo check so transcripts swallowed by a backend modal do not vanish silently.
+//!
+//! With `--echo-check-ms`, every transcript typed into the wrapped backend is
+//! expected to show up in the backend's output within that window. The check
+//! compares letters and digits only, so line wrapping, box borders, prompt
+//! glyphs, and escape sequences between the echoed characters do not matter;
+//! the first [`PROBE_CHARS`] of them identify the transcript. A transcript that
+//! is not echoed in time is retried once, then reported. Bracketed pastes and
+//! multi-line text are not checked, since backends often show them as a
+//! placeholder (`[Pasted text]`) rather than the text itself.
+
+use std::time::{Duration, Instant};
+
+use vte::{Parser as VteParser, Perform};
+
+use crate::config::VoiceSendMode;
+
+/// Leading letters/digits of a transcript that must appear in the echo.
+const PROBE_CHARS: usize = 16;
+
+/// Delivery label for a transcript re-sent after its echo was missing.
+pub(crate) const ECHO_RETRY_LABEL: &str = "echo retry";
+
+/// A transcript the backend did not echo within the window.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct EchoMiss {
+    /// Text as it was sent, to re-send on retry.
+    pub(crate) text: String,
+    /// `Auto` when the text was sent with Enter, else `Insert`.
+    pub(crate) mode: VoiceSendMode,
+    /// False once the retry was not echoed either.
+    pub(crate) retry: bool,
+}
+
+struct PendingEcho {
+    text: String,
+    mode: VoiceSendMode,
+    probe: Vec<char>,
+    deadline: Instant,
+}
+
+#[derive(Default)]
+struct EchoWatch {
+    pending: Option<PendingEcho>,
+    /// Most recent letters/digits of output, at most one probe long.
+    window: Vec<char>,
+    /// Probe of a transcript re-sent after a miss; a second miss is not retried.
+    retrying: Option<Vec<char>>,
+}
+
+impl Perform for EchoWatch {
+    fn print(&mut self, ch: char) {
+        let Some(pending) = self.pending.as_ref() else {
+            return;
+        };
+        if !ch.is_alphanumeric() {
+            return;
+        }
+        self.window.extend(ch.to_lowercase());
+        let excess = self.window.len().saturating_sub(pending.probe.len());
+        self.window.drain(..excess);
+        if self.window == pending.probe {
+            self.pending = None;
+            self.retrying = None;
+        }
+    }
+}
+
+/// Tracks the last transcript typed into the wrapped backend until its echo appears.
+pub(crate) struct EchoCheck {
+    timeout: Option<Duration>,
+    parser: VteParser,
+    watch: EchoWatch,
+}
+
+impl EchoCheck {
+    /// `None` (or zero) turns the check off.
+    pub(crate) fn new(timeout: Option<Duration>) -> Self {
+        Self {
+            timeout: timeout.filter(|timeout| !timeout.is_zero()),
+            parser: VteParser::new(),
+            watch: EchoWatch::default(),
+        }
+    }
+
+    /// Start watching for `text`, just written to the backend (with Enter when `newline`).
+    pub(crate) fn expect(&mut self, text: &str, newline: bool, now: Instant) {
+        let Some(timeout) = self.timeout else {
+            return;
+        };
+        if text.contains(['\n', '\r', '\x1b']) {
+            return;
+        }
+        let probe: Vec<char> = text
+            .chars()
+            .filter(|ch| ch.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .take(PROBE_CHARS)
+            .collect();
+        if probe.is_empty() {
+            return;
+        }
+        self.watch.window.clear();
+        self.watch.pending = Some(PendingEcho {
+            text: text.to_string(),
+            mode: if newline {
+                VoiceSendMode::Auto
+            } else {
+                VoiceSendMode::Insert
+            },
+            probe,
+            deadline: now + timeout,
+        });
+    }
+
+    pub(crate) fn observe(&mut self, bytes: &[u8]) {
+        if self.watch.pending.is_some() {
+            self.parser.advance(&mut self.watch, bytes);
+        }
+    }
+
+    /// Stop watching, e.g. because the transcript was erased again.
+    pub(crate) fn cancel(&mut self) {
+        self.watch.pending = None;
+        self.watch.retrying = None;
+    }
+
+    /// The watched transcript, once its window passed without an echo.
+    pub(crate) fn poll(&mut self, now: Instant) -> Option<EchoMiss> {
+        if self.watch.pending.as_ref()?.deadline > now {
+            return None;
+        }
+        let pending = self.watch.pending.take()?;
+        let retry = self.watch.retrying.as_ref() != Some(&pending.probe);
+        self.watch.retrying = retry.then_some(pending.probe);
+        Some(EchoMiss {
+            text: pending.text,
+            mode: pending.mode,
+            retry,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WINDOW: Duration = Duration::from_millis(500);
+
+    #[test]
+    fn echo_matches_through_wrapping_and_escape_sequences() {
+        let mut check = EchoCheck::new(Some(WINDOW));
+        let start = Instant::now();
+        check.expect("Run the test suite, please", true, start);
+        check.observe(b"\x1b[2K\x1b[1;3H\xe2\x94\x82 \xe2\x80\xba Run the te");
+        check.observe(b"\x1b[1m\xe2\x94\x82\r\n\xe2\x94\x82 st suite, please");
+        assert_eq!(check.poll(start + WINDOW), None);
+    }
+
+    #[test]
+    fn missing_echo_is_retried_once_then_reported() {
+        let mut check = EchoCheck::new(Some(WINDOW));
+        let start = Instant::now();
+        check.expect("git status", false, start);
+        check.observe(b"Do you trust this folder? (y/n)");
+        assert_eq!(check.poll(start + Duration::from_millis(100)), None);
+        let miss = check.poll(start + WINDOW).expect("first miss");
+        assert_eq!(miss.mode, VoiceSendMode::Insert);
+        assert!(miss.retry);
+        assert_eq!(check.poll(start + WINDOW * 2), None);
+
+        check.expect(&miss.text, false, start + WINDOW);
+        let miss = check.poll(start + WINDOW * 2).expect("second miss");
+        assert!(!miss.retry);
+
+        // An echoed transcript is never reported.
+        check.expect("git status", true, start + WINDOW * 2);
+        check.observe(b"> git status");
+        assert_eq!(check.poll(start + WINDOW * 3), None);
+    }
+
+    #[test]
+    fn disabled_or_multiline_transcripts_are_not_watched() {
+        let start = Instant::now();
+        let mut off = EchoCheck::new(Some(Duration::ZERO));
+        off.expect("hello", true, start);
+        assert_eq!(off.poll(start + WINDOW), None);
+
+        let mut check = EchoCheck::new(Some(WINDOW));
+        check.expect("first line\nsecond line", true, start);
+        check.expect("\x1b[200~pasted\x1b[201~", true, start);
+        check.expect("...", true, start);
+        assert_eq!(check.poll(start + WINDOW), None);
+    }
+}
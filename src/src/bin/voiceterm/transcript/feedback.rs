@@ -0,0 +1,589 @@
+//! Transcript feedback loop so misheard words turn into hints and replacements.
+//!
+//! The `flag_transcript` key marks the last delivered transcript as wrong; the
+//! next transcript (re-dictated within two minutes) is saved as its correction
+//! in `flagged.jsonl` under `--feedback-dir`, with a copy of the capture's
+//! `--dump-audio` file when there is one. The words that differ in each pair
+//! become Whisper prompt hints, and a substitution seen twice becomes a
+//! replacement applied to later transcripts. `voiceterm export-feedback DIR`
+//! turns the saved pairs into an evaluation set.
+
+use anyhow::{Context, Result};
+use regex::{NoExpand, Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use super::redact::redact_transcripts;
+use crate::config::{ExportFeedbackArgs, OverlayConfig};
+use voiceterm::log_debug;
+
+const FLAGGED_FILE: &str = "flagged.jsonl";
+const AUDIO_DIR: &str = "audio";
+const MANIFEST_FILE: &str = "manifest.jsonl";
+/// How long after flagging the next transcript still counts as the correction.
+const CORRECTION_WINDOW: Duration = Duration::from_secs(120);
+/// Corrections needed before a substitution is applied automatically.
+const LEARN_MIN_COUNT: usize = 2;
+/// Longest word span treated as a substitution; longer diffs are rephrasings.
+const MAX_SPAN_WORDS: usize = 3;
+const MAX_HINT_WORDS: usize = 24;
+
+/// One line of `flagged.jsonl`; a later line with the same `id` supersedes earlier ones.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct FlaggedTranscript {
+    /// Flag time (milliseconds since epoch); doubles as the entry key.
+    pub(crate) id: u64,
+    /// Transcript as delivered.
+    pub(crate) heard: String,
+    /// Re-dictated text, once the user has said it again.
+    #[serde(default)]
+    pub(crate) corrected: Option<String>,
+    /// Audio copy, relative to the feedback directory.
+    #[serde(default)]
+    pub(crate) audio: Option<String>,
+}
+
+/// One evaluation-set line written by `export-feedback`.
+#[derive(Debug, Serialize)]
+struct EvalEntry<'a> {
+    audio: String,
+    reference: &'a str,
+    hypothesis: &'a str,
+}
+
+#[derive(Debug, Clone)]
+struct LearnedReplacement {
+    pattern: Regex,
+    replace: String,
+}
+
+#[derive(Debug, Clone)]
+struct Delivered {
+    text: String,
+    audio_path: Option<PathBuf>,
+}
+
+/// What a saved correction changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CorrectionSaved {
+    /// Substitution that just reached the learning threshold, as `(heard, corrected)`.
+    pub(crate) learned: Option<(String, String)>,
+    /// Whether the Whisper prompt hints changed.
+    pub(crate) hints_changed: bool,
+}
+
+/// Flagged transcripts plus the hints and replacements learned from them.
+#[derive(Debug, Default)]
+pub(crate) struct TranscriptFeedback {
+    dir: Option<PathBuf>,
+    /// The user's `--whisper-prompt`, which learned hints are appended to.
+    base_prompt: Option<String>,
+    entries: Vec<FlaggedTranscript>,
+    replacements: Vec<LearnedReplacement>,
+    hints: Vec<String>,
+    last: Option<Delivered>,
+    pending: Option<(FlaggedTranscript, Instant)>,
+}
+
+impl TranscriptFeedback {
+    /// Load earlier flags from `--feedback-dir` (or the default data directory).
+    pub(crate) fn from_config(config: &OverlayConfig) -> Result<Self> {
+        let mut feedback = match config.feedback_dir.clone().or_else(default_feedback_dir) {
+            Some(dir) => Self::load(dir)?,
+            None => Self::default(),
+        };
+        feedback.base_prompt = config.app.whisper_prompt.clone();
+        Ok(feedback)
+    }
+
+    fn load(dir: PathBuf) -> Result<Self> {
+        let entries = load_entries(&dir)?;
+        let mut feedback = Self {
+            dir: Some(dir),
+            entries,
+            ..Self::default()
+        };
+        feedback.relearn();
+        Ok(feedback)
+    }
+
+    pub(crate) fn flagged_count(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub(crate) fn replacement_count(&self) -> usize {
+        self.replacements.len()
+    }
+
+    /// Apply learned replacements to a transcript.
+    pub(crate) fn apply(&self, text: &str) -> String {
+        self.replacements
+            .iter()
+            .fold(text.to_string(), |text, rule| {
+                rule.pattern
+                    .replace_all(&text, NoExpand(&rule.replace))
+                    .into_owned()
+            })
+    }
+
+    /// Whisper initial prompt: the user's `--whisper-prompt` followed by learned hint words.
+    pub(crate) fn whisper_prompt(&self) -> Option<String> {
+        let base = self
+            .base_prompt
+            .as_deref()
+            .map(str::trim)
+            .filter(|base| !base.is_empty());
+        match (base, self.hints.is_empty()) {
+            (None, true) => None,
+            (Some(base), true) => Some(base.to_string()),
+            (None, false) => Some(self.hints.join(", ")),
+            (Some(base), false) => Some(format!("{base} {}", self.hints.join(", "))),
+        }
+    }
+
+    /// Flag the last delivered transcript; returns its text.
+    ///
+    /// Flags save the transcript and its correction as text, so they are
+    /// refused under `--redact-transcripts`.
+    pub(crate) fn flag_last(&mut self, now: Instant) -> Result<Option<String>> {
+        if redact_transcripts() {
+            anyhow::bail!(
+                "flagging saves transcript text, which --redact-transcripts keeps off disk"
+            );
+        }
+        let Some(dir) = self.dir.clone() else {
+            anyhow::bail!("no feedback directory (set --feedback-dir or HOME)");
+        };
+        let Some(last) = self.last.take() else {
+            return Ok(None);
+        };
+        // Ids key the JSONL lines, so two flags in the same millisecond must not collide.
+        let id = unix_ms().max(self.entries.last().map_or(0, |entry| entry.id + 1));
+        let audio = match last.audio_path.as_deref() {
+            Some(source) => copy_audio(&dir, id, source)
+                .map_err(|err| log_debug(&format!("feedback audio copy failed: {err:#}")))
+                .ok(),
+            None => None,
+        };
+        let entry = FlaggedTranscript {
+            id,
+            heard: last.text.clone(),
+            corrected: None,
+            audio,
+        };
+        append_entry(&dir, &entry)?;
+        self.entries.push(entry.clone());
+        self.pending = Some((entry, now));
+        Ok(Some(last.text))
+    }
+
+    /// Record a delivered transcript; it completes a pending flag as the correction.
+    pub(crate) fn note_delivered(
+        &mut self,
+        text: &str,
+        audio_path: Option<PathBuf>,
+        now: Instant,
+    ) -> Option<CorrectionSaved> {
+        let text = text.trim();
+        if text.is_empty() {
+            return None;
+        }
+        self.last = Some(Delivered {
+            text: text.to_string(),
+            audio_path,
+        });
+        let (mut entry, flagged_at) = self.pending.take()?;
+        if now.duration_since(flagged_at) > CORRECTION_WINDOW {
+            return None;
+        }
+        entry.corrected = Some(text.to_string());
+        if let Some(dir) = self.dir.as_deref() {
+            if let Err(err) = append_entry(dir, &entry) {
+                log_debug(&format!("feedback correction write failed: {err:#}"));
+            }
+        }
+        if let Some(existing) = self.entries.iter_mut().find(|item| item.id == entry.id) {
+            *existing = entry.clone();
+        }
+        let hints_before = self.hints.clone();
+        let replacements_before = self.replacements.len();
+        self.relearn();
+        let learned = (self.replacements.len() > replacements_before)
+            .then(|| substitution(&entry.heard, text))
+            .flatten();
+        Some(CorrectionSaved {
+            learned,
+            hints_changed: self.hints != hints_before,
+        })
+    }
+
+    fn relearn(&mut self) {
+        let (replacements, hints) = learn(&self.entries);
+        self.replacements = replacements;
+        self.hints = hints;
+    }
+}
+
+/// Default feedback directory: `$XDG_DATA_HOME/voiceterm/feedback` or `~/.local/share/voiceterm/feedback`.
+fn default_feedback_dir() -> Option<PathBuf> {
+    let base = env::var_os("XDG_DATA_HOME")
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            env::var_os("HOME")
+                .filter(|value| !value.is_empty())
+                .map(|home| PathBuf::from(home).join(".local").join("share"))
+        })?;
+    Some(base.join("voiceterm").join("feedback"))
+}
+
+/// Flagged-transcript log that learned replacements and prompt hints are rebuilt from.
+pub(crate) fn flagged_transcripts_path(config: &OverlayConfig) -> Option<PathBuf> {
+    config
+        .feedback_dir
+        .clone()
+        .or_else(default_feedback_dir)
+        .map(|dir| dir.join(FLAGGED_FILE))
+}
+
+/// `voiceterm export-feedback OUT`: build an evaluation set from the flagged transcripts.
+pub(crate) fn run_export_feedback(config: &OverlayConfig, args: &ExportFeedbackArgs) -> Result<()> {
+    let Some(dir) = config.feedback_dir.clone().or_else(default_feedback_dir) else {
+        anyhow::bail!("no feedback directory (set --feedback-dir or HOME)");
+    };
+    let (exported, skipped) = export_feedback(&dir, &args.out)?;
+    println!(
+        "Exported {exported} corrected transcript(s) to {} ({skipped} skipped without a correction or audio)",
+        args.out.join(MANIFEST_FILE).display()
+    );
+    Ok(())
+}
+
+fn unix_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn load_entries(dir: &Path) -> Result<Vec<FlaggedTranscript>> {
+    let path = dir.join(FLAGGED_FILE);
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => {
+            return Err(err).with_context(|| format!("failed to read {}", path.display()));
+        }
+    };
+    let mut by_id: BTreeMap<u64, FlaggedTranscript> = BTreeMap::new();
+    for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+        match serde_json::from_str::<FlaggedTranscript>(line) {
+            Ok(entry) => {
+                by_id.insert(entry.id, entry);
+            }
+            Err(err) => log_debug(&format!("skipping bad feedback line: {err}")),
+        }
+    }
+    Ok(by_id.into_values().collect())
+}
+
+fn append_entry(dir: &Path, entry: &FlaggedTranscript) -> Result<()> {
+    fs::create_dir_all(dir)
+        .with_context(|| format!("failed to create feedback dir {}", dir.display()))?;
+    let path = dir.join(FLAGGED_FILE);
+    let mut line = serde_json::to_string(entry)?;
+    line.push('\n');
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .and_then(|mut file| file.write_all(line.as_bytes()))
+        .with_context(|| format!("failed to write {}", path.display()))
+}
+
+/// Copy a dumped capture next to the flags so `--dump-audio` pruning cannot drop it.
+fn copy_audio(dir: &Path, id: u64, source: &Path) -> Result<String> {
+    let ext = source
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("wav");
+    let relative = format!("{AUDIO_DIR}/{id}.{ext}");
+    let target = dir.join(&relative);
+    fs::create_dir_all(dir.join(AUDIO_DIR))?;
+    fs::copy(source, &target).with_context(|| format!("failed to copy {}", source.display()))?;
+    Ok(relative)
+}
+
+/// Words compared case-insensitively, with surrounding punctuation stripped.
+fn words(text: &str) -> Vec<&str> {
+    text.split_whitespace()
+        .map(|word| word.trim_matches(|ch: char| !ch.is_alphanumeric()))
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+/// The differing word span between a transcript and its correction, as
+/// `(heard, corrected)`, once the shared prefix and suffix are trimmed.
+fn substitution(heard: &str, corrected: &str) -> Option<(String, String)> {
+    let heard = words(heard);
+    let corrected = words(corrected);
+    let prefix = heard
+        .iter()
+        .zip(&corrected)
+        .take_while(|(a, b)| a.eq_ignore_ascii_case(b))
+        .count();
+    let max_suffix = heard.len().min(corrected.len()) - prefix;
+    let suffix = heard
+        .iter()
+        .rev()
+        .zip(corrected.iter().rev())
+        .take(max_suffix)
+        .take_while(|(a, b)| a.eq_ignore_ascii_case(b))
+        .count();
+    let heard_span = &heard[prefix..heard.len() - suffix];
+    let corrected_span = &corrected[prefix..corrected.len() - suffix];
+    if heard_span.is_empty()
+        || corrected_span.is_empty()
+        || heard_span.len() > MAX_SPAN_WORDS
+        || corrected_span.len() > MAX_SPAN_WORDS
+    {
+        return None;
+    }
+    Some((
+        heard_span.join(" ").to_lowercase(),
+        corrected_span.join(" "),
+    ))
+}
+
+fn learn(entries: &[FlaggedTranscript]) -> (Vec<LearnedReplacement>, Vec<String>) {
+    let mut substitutions: BTreeMap<(String, String), usize> = BTreeMap::new();
+    let mut hint_counts: Vec<(String, usize)> = Vec::new();
+    for entry in entries {
+        let Some(corrected) = entry.corrected.as_deref() else {
+            continue;
+        };
+        let Some((heard, fixed)) = substitution(&entry.heard, corrected) else {
+            continue;
+        };
+        for word in words(&fixed) {
+            if heard
+                .split(' ')
+                .any(|heard| heard.eq_ignore_ascii_case(word))
+            {
+                continue;
+            }
+            match hint_counts
+                .iter_mut()
+                .find(|(hint, _)| hint.eq_ignore_ascii_case(word))
+            {
+                Some((_, count)) => *count += 1,
+                None => hint_counts.push((word.to_string(), 1)),
+            }
+        }
+        *substitutions.entry((heard, fixed)).or_default() += 1;
+    }
+    // Most frequent correction per heard phrase; ties keep the first seen.
+    let mut best: BTreeMap<&str, (&str, usize)> = BTreeMap::new();
+    for ((heard, fixed), &count) in &substitutions {
+        if count < LEARN_MIN_COUNT {
+            continue;
+        }
+        let slot = best.entry(heard.as_str()).or_insert((fixed.as_str(), 0));
+        if count > slot.1 {
+            *slot = (fixed.as_str(), count);
+        }
+    }
+    let replacements = best
+        .into_iter()
+        .filter_map(|(heard, (fixed, _))| {
+            let pattern = heard
+                .split(' ')
+                .map(regex::escape)
+                .collect::<Vec<_>>()
+                .join(r"\s+");
+            RegexBuilder::new(&format!(r"\b{pattern}\b"))
+                .case_insensitive(true)
+                .build()
+                .ok()
+                .map(|pattern| LearnedReplacement {
+                    pattern,
+                    replace: fixed.to_string(),
+                })
+        })
+        .collect();
+    // Stable sort keeps first-seen order among equal counts.
+    hint_counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    let hints = hint_counts
+        .into_iter()
+        .take(MAX_HINT_WORDS)
+        .map(|(word, _)| word)
+        .collect();
+    (replacements, hints)
+}
+
+/// Write corrected pairs that have audio as `manifest.jsonl` plus audio files in `out`.
+///
+/// Returns `(exported, skipped)`; pairs without a correction or audio are skipped.
+fn export_feedback(feedback_dir: &Path, out: &Path) -> Result<(usize, usize)> {
+    let entries = load_entries(feedback_dir)?;
+    fs::create_dir_all(out.join(AUDIO_DIR))
+        .with_context(|| format!("failed to create {}", out.display()))?;
+    let mut manifest = String::new();
+    let mut exported = 0;
+    for entry in &entries {
+        let (Some(reference), Some(audio)) = (entry.corrected.as_deref(), entry.audio.as_deref())
+        else {
+            continue;
+        };
+        let source = feedback_dir.join(audio);
+        if !source.is_file() {
+            continue;
+        }
+        let file_name = Path::new(audio)
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| format!("{}.wav", entry.id));
+        let relative = format!("{AUDIO_DIR}/{file_name}");
+        fs::copy(&source, out.join(&relative))
+            .with_context(|| format!("failed to copy {}", source.display()))?;
+        manifest.push_str(&serde_json::to_string(&EvalEntry {
+            audio: relative,
+            reference,
+            hypothesis: &entry.heard,
+        })?);
+        manifest.push('\n');
+        exported += 1;
+    }
+    let manifest_path = out.join(MANIFEST_FILE);
+    fs::write(&manifest_path, manifest)
+        .with_context(|| format!("failed to write {}", manifest_path.display()))?;
+    Ok((exported, entries.len() - exported))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        env::temp_dir().join(format!("voiceterm-feedback-{name}-{unique}"))
+    }
+
+    #[test]
+    fn substitution_trims_shared_words_and_rejects_rephrasing() {
+        assert_eq!(
+            substitution("Run cargo clipping now.", "run cargo clippy now"),
+            Some(("clipping".to_string(), "clippy".to_string()))
+        );
+        assert_eq!(
+            substitution("open the read me", "open the README"),
+            Some(("read me".to_string(), "README".to_string()))
+        );
+        assert_eq!(substitution("run tests", "Run tests."), None);
+        assert_eq!(substitution("run tests", "run tests again"), None);
+        assert_eq!(
+            substitution("a b c d e", "v w x y z"),
+            None,
+            "whole rewrites are not substitutions"
+        );
+    }
+
+    #[test]
+    fn corrections_grow_hints_then_replacements() {
+        let dir = temp_dir("learn");
+        let mut feedback = TranscriptFeedback::load(dir.clone()).unwrap();
+        let start = Instant::now();
+        assert_eq!(
+            feedback.flag_last(start).unwrap(),
+            None,
+            "nothing to flag yet"
+        );
+
+        feedback.note_delivered("run cargo clipping", None, start);
+        assert_eq!(
+            feedback.flag_last(start).unwrap().as_deref(),
+            Some("run cargo clipping")
+        );
+        let saved = feedback
+            .note_delivered("run cargo clippy", None, start)
+            .expect("correction saved");
+        assert_eq!(saved.learned, None, "one correction only adds a hint");
+        assert!(saved.hints_changed);
+        assert_eq!(feedback.whisper_prompt().as_deref(), Some("clippy"));
+        feedback.base_prompt = Some("Rust CLI.".to_string());
+        assert_eq!(
+            feedback.whisper_prompt().as_deref(),
+            Some("Rust CLI. clippy")
+        );
+
+        feedback.note_delivered("cargo clipping fix", None, start);
+        feedback.flag_last(start).unwrap();
+        let saved = feedback
+            .note_delivered("cargo clippy fix", None, start)
+            .expect("correction saved");
+        assert_eq!(
+            saved.learned,
+            Some(("clipping".to_string(), "clippy".to_string()))
+        );
+        assert_eq!(feedback.apply("Clipping again"), "clippy again");
+
+        let reloaded = TranscriptFeedback::load(dir.clone()).unwrap();
+        assert_eq!(reloaded.flagged_count(), 2);
+        assert_eq!(reloaded.replacement_count(), 1);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn late_transcripts_do_not_count_as_corrections() {
+        let mut feedback = TranscriptFeedback::load(temp_dir("late")).unwrap();
+        let start = Instant::now();
+        feedback.note_delivered("git stash pop", None, start);
+        feedback.flag_last(start).unwrap();
+        let late = start + CORRECTION_WINDOW + Duration::from_secs(1);
+        assert_eq!(feedback.note_delivered("git stash", None, late), None);
+        if let Some(dir) = feedback.dir.as_deref() {
+            let _ = fs::remove_dir_all(dir);
+        }
+    }
+
+    #[test]
+    fn export_writes_manifest_for_corrected_pairs_with_audio() {
+        let dir = temp_dir("export-src");
+        let out = temp_dir("export-out");
+        let clip = temp_dir("clip").with_extension("wav");
+        fs::write(&clip, b"RIFF").unwrap();
+        let mut feedback = TranscriptFeedback::load(dir.clone()).unwrap();
+        let now = Instant::now();
+        feedback.note_delivered("make tests", Some(clip.clone()), now);
+        feedback.flag_last(now).unwrap();
+        feedback.note_delivered("make test", None, now);
+        feedback.note_delivered("no audio here", None, now);
+        feedback.flag_last(now).unwrap();
+
+        let (exported, skipped) = export_feedback(&dir, &out).unwrap();
+        assert_eq!((exported, skipped), (1, 1));
+        let manifest = fs::read_to_string(out.join(MANIFEST_FILE)).unwrap();
+        assert!(
+            manifest.contains("\"reference\":\"make test\""),
+            "{manifest}"
+        );
+        assert!(
+            manifest.contains("\"hypothesis\":\"make tests\""),
+            "{manifest}"
+        );
+        let entry: serde_json::Value = serde_json::from_str(manifest.trim()).unwrap();
+        assert!(out.join(entry["audio"].as_str().unwrap()).is_file());
+        for path in [&dir, &out] {
+            let _ = fs::remove_dir_all(path);
+        }
+        let _ = fs::remove_file(&clip);
+    }
+}
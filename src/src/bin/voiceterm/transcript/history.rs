@@ -0,0 +1,362 @@
+//! Transcript history file so voice-driven sessions can be reviewed or replayed later.
+//!
+//! The writer subscribes to voice session events and appends each delivered
+//! transcript as one JSON object per line. Readers also
+//! accept plain-text files (one prompt per line, `#` comments ignored) so
+//! hand-written demo scripts can be replayed the same way.
+//!
+//! With `--transcript-history-encrypt-cmd`, each transcript is piped through
+//! that command (e.g. `age -a -r <recipient>`) and only its ASCII-armored
+//! output is stored; `replay-prompts --decrypt-cmd` reverses it. Without a
+//! command, `--redact-transcripts` stores each transcript's length and hash.
+//! Entries are written on a worker thread so a slow command never stalls the
+//! event loop, and a command that runs past `FILTER_TIMEOUT` is killed.
+
+use anyhow::{anyhow, bail, Context, Result};
+use crossbeam_channel::{bounded, Receiver, Sender, TrySendError};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use voiceterm::log_debug;
+
+use super::redact::{redact_transcripts, text_hash};
+use crate::session_events::{SessionEventSink, VoiceSessionEvent};
+
+/// Longest an encrypt or decrypt command may run before it is killed.
+const FILTER_TIMEOUT: Duration = Duration::from_secs(10);
+const FILTER_POLL: Duration = Duration::from_millis(10);
+/// Transcripts buffered for the history worker before new ones are dropped.
+const HISTORY_QUEUE_CAPACITY: usize = 64;
+/// How long exit waits for queued transcripts to be written.
+const HISTORY_FLUSH_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// One line of the transcript history file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct HistoryEntry {
+    /// Wall-clock time the transcript was produced (milliseconds since epoch).
+    #[serde(default)]
+    pub(crate) ts_unix_ms: u64,
+    /// Pipeline label (`native`, `python`).
+    #[serde(default)]
+    pub(crate) source: String,
+    /// Transcript text after macro expansion; empty when encrypted or redacted.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub(crate) text: String,
+    /// ASCII-armored output of `--transcript-history-encrypt-cmd`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) ciphertext: Option<String>,
+    /// Character count of a redacted transcript.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) chars: Option<usize>,
+    /// FNV-1a hash of a redacted transcript.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) hash: Option<String>,
+}
+
+/// `--transcript-history` writer, fed by `transcript_delivered` events.
+pub(crate) struct TranscriptHistoryWriter {
+    path: PathBuf,
+    encrypt_cmd: Option<String>,
+    /// Started on the first transcript; `(text, source)` pairs in delivery order.
+    entry_tx: Option<Sender<(String, String)>>,
+    /// Closed by the worker once every queued transcript is written.
+    flushed_rx: Option<Receiver<()>>,
+}
+
+impl TranscriptHistoryWriter {
+    pub(crate) fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            encrypt_cmd: None,
+            entry_tx: None,
+            flushed_rx: None,
+        }
+    }
+
+    /// Store each transcript as the output of this command instead of as text.
+    pub(crate) fn with_encrypt_cmd(mut self, command: Option<String>) -> Self {
+        self.encrypt_cmd = command;
+        self
+    }
+
+    fn worker(&mut self) -> &Sender<(String, String)> {
+        self.entry_tx.get_or_insert_with(|| {
+            let (entry_tx, entry_rx) = bounded::<(String, String)>(HISTORY_QUEUE_CAPACITY);
+            let (flushed_tx, flushed_rx) = bounded::<()>(0);
+            let path = self.path.clone();
+            let encrypt_cmd = self.encrypt_cmd.clone();
+            thread::spawn(move || {
+                for (text, source) in entry_rx {
+                    if let Err(err) =
+                        append_history_entry(&path, &text, &source, encrypt_cmd.as_deref())
+                    {
+                        log_debug(&format!("transcript history write failed: {err:#}"));
+                    }
+                }
+                drop(flushed_tx);
+            });
+            self.flushed_rx = Some(flushed_rx);
+            entry_tx
+        })
+    }
+}
+
+impl SessionEventSink for TranscriptHistoryWriter {
+    fn on_event(&mut self, event: &VoiceSessionEvent) {
+        let VoiceSessionEvent::TranscriptDelivered { text, source, .. } = event else {
+            return;
+        };
+        if let Err(TrySendError::Full(_)) = self.worker().try_send((text.clone(), source.clone())) {
+            log_debug("transcript history queue full; dropping transcript");
+        }
+    }
+}
+
+impl Drop for TranscriptHistoryWriter {
+    fn drop(&mut self) {
+        // Let queued transcripts reach the file before exit.
+        self.entry_tx.take();
+        if let Some(flushed_rx) = self.flushed_rx.take() {
+            let _ = flushed_rx.recv_timeout(HISTORY_FLUSH_TIMEOUT);
+        }
+    }
+}
+
+/// Append a transcript to the history file, creating it if needed.
+fn append_history_entry(
+    path: &Path,
+    text: &str,
+    source: &str,
+    encrypt_cmd: Option<&str>,
+) -> Result<()> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(());
+    }
+    let mut entry = HistoryEntry {
+        ts_unix_ms: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis() as u64)
+            .unwrap_or(0),
+        source: source.to_string(),
+        text: String::new(),
+        ciphertext: None,
+        chars: None,
+        hash: None,
+    };
+    if let Some(command) = encrypt_cmd {
+        let armored = String::from_utf8(run_filter(command, text.as_bytes())?).map_err(|_| {
+            anyhow!("encrypt command printed binary output; use its ASCII-armor option")
+        })?;
+        entry.ciphertext = Some(armored);
+    } else if redact_transcripts() {
+        entry.chars = Some(text.chars().count());
+        entry.hash = Some(text_hash(text));
+    } else {
+        entry.text = text.to_string();
+    }
+    let mut line = serde_json::to_string(&entry)?;
+    line.push('\n');
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("failed to open transcript history {}", path.display()))?;
+    file.write_all(line.as_bytes())
+        .with_context(|| format!("failed to write transcript history {}", path.display()))?;
+    Ok(())
+}
+
+/// Pipe `input` through a shell-style command line and return its stdout.
+fn run_filter(command: &str, input: &[u8]) -> Result<Vec<u8>> {
+    run_filter_within(command, input, FILTER_TIMEOUT)
+}
+
+/// [`run_filter`], killing the command if it runs past `timeout`.
+fn run_filter_within(command: &str, input: &[u8], timeout: Duration) -> Result<Vec<u8>> {
+    let parts = shell_words::split(command.trim())
+        .with_context(|| format!("invalid command: {command}"))?;
+    let Some((program, args)) = parts.split_first() else {
+        bail!("empty command");
+    };
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .with_context(|| format!("failed to start {program}"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(input)
+            .with_context(|| format!("failed to write to {program}"))?;
+    }
+    let mut stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| anyhow!("no stdout from {program}"))?;
+    let reader = thread::spawn(move || {
+        let mut out = Vec::new();
+        stdout.read_to_end(&mut out).map(|_| out)
+    });
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child
+            .try_wait()
+            .with_context(|| format!("failed to wait for {program}"))?
+        {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            bail!("{program} timed out after {}ms", timeout.as_millis());
+        }
+        thread::sleep(FILTER_POLL);
+    };
+    if !status.success() {
+        bail!("{program} exited with {status}");
+    }
+    reader
+        .join()
+        .map_err(|_| anyhow!("reading {program} output panicked"))?
+        .with_context(|| format!("failed to read {program} output"))
+}
+
+/// Read prompt texts from a history file (JSON lines or plain text).
+///
+/// Encrypted entries are decrypted with `decrypt_cmd`; redacted entries have
+/// no text and are skipped.
+pub(crate) fn load_history_prompts(path: &Path, decrypt_cmd: Option<&str>) -> Result<Vec<String>> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("failed to read transcript history {}", path.display()))?;
+    parse_history_prompts(&contents, |ciphertext| {
+        let command = decrypt_cmd
+            .ok_or_else(|| anyhow!("history is encrypted; pass --decrypt-cmd to replay it"))?;
+        let plain = run_filter(command, ciphertext.as_bytes())?;
+        String::from_utf8(plain).context("decrypted history entry is not UTF-8")
+    })
+}
+
+fn parse_history_prompts(
+    contents: &str,
+    mut decrypt: impl FnMut(&str) -> Result<String>,
+) -> Result<Vec<String>> {
+    let mut prompts = Vec::new();
+    for line in contents.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let entry = if line.starts_with('{') {
+            serde_json::from_str::<HistoryEntry>(line).ok()
+        } else {
+            None
+        };
+        let text = match entry {
+            Some(HistoryEntry {
+                ciphertext: Some(ciphertext),
+                ..
+            }) => decrypt(&ciphertext)?,
+            Some(entry) => entry.text,
+            None => line.to_string(),
+        };
+        let text = text.trim();
+        if !text.is_empty() {
+            prompts.push(text.to_string());
+        }
+    }
+    Ok(prompts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn parse_history_prompts_reads_json_and_plain_lines() {
+        let contents = r#"
+# demo script
+{"ts_unix_ms":1,"source":"native","text":"list the files"}
+run the tests
+{"text":"  commit it  "}
+"#;
+        assert_eq!(
+            parse_history_prompts(contents, |_| unreachable!()).unwrap(),
+            vec!["list the files", "run the tests", "commit it"]
+        );
+    }
+
+    #[test]
+    fn parse_history_prompts_decrypts_and_skips_redacted_entries() {
+        let contents = r#"
+{"ts_unix_ms":1,"source":"native","chars":13,"hash":"0123456789abcdef"}
+{"ts_unix_ms":2,"source":"native","ciphertext":"-----BEGIN-----\nstuvw\n"}
+"#;
+        let prompts = parse_history_prompts(contents, |ciphertext| {
+            assert_eq!(ciphertext, "-----BEGIN-----\nstuvw\n");
+            Ok("open the log".to_string())
+        })
+        .unwrap();
+        assert_eq!(prompts, vec!["open the log"]);
+        assert!(parse_history_prompts(contents, |_| bail!("no key")).is_err());
+    }
+
+    #[test]
+    fn append_history_entry_round_trips_through_loader() {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        let path = env::temp_dir().join(format!("voiceterm-history-{unique}.jsonl"));
+        append_history_entry(&path, "first prompt", "native", None).unwrap();
+        append_history_entry(&path, "   ", "native", None).unwrap();
+        let mut writer = TranscriptHistoryWriter::new(path.clone());
+        writer.on_event(&VoiceSessionEvent::Partial {
+            text: "draft only".to_string(),
+        });
+        writer.on_event(&VoiceSessionEvent::TranscriptDelivered {
+            text: "second prompt".to_string(),
+            source: "python".to_string(),
+            mode: "auto".to_string(),
+        });
+        // Dropping waits for the worker to write the queued transcript.
+        drop(writer);
+        let prompts = load_history_prompts(&path, None).unwrap();
+        assert_eq!(prompts, vec!["first prompt", "second prompt"]);
+        let raw = fs::read_to_string(&path).unwrap();
+        assert!(raw.contains("\"source\":\"python\""));
+        let _ = fs::remove_file(&path);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn encrypt_cmd_stores_only_the_command_output() {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        let path = env::temp_dir().join(format!("voiceterm-history-enc-{unique}.jsonl"));
+        append_history_entry(&path, "secret prompt", "native", Some("tr a-z n-za-m")).unwrap();
+        let raw = fs::read_to_string(&path).unwrap();
+        assert!(!raw.contains("secret"));
+        assert!(raw.contains("\"ciphertext\":\"frperg cebzcg\""));
+        let prompts = load_history_prompts(&path, Some("tr a-z n-za-m")).unwrap();
+        assert_eq!(prompts, vec!["secret prompt"]);
+        assert!(load_history_prompts(&path, None).is_err());
+        let _ = fs::remove_file(&path);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn run_filter_kills_a_command_that_hangs() {
+        let started = Instant::now();
+        let err = run_filter_within("sleep 30", b"prompt", Duration::from_millis(100)).unwrap_err();
+        assert!(err.to_string().contains("timed out"), "{err:#}");
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+}
@@ -2,7 +2,7 @@
 
 use std::time::{Duration, Instant};
 
-use crate::prompt::PromptTracker;
+use crate::prompt::{PromptTracker, UiMode};
 
 fn prompt_ready(prompt_tracker: &PromptTracker, last_enter_at: Option<Instant>) -> bool {
     match (prompt_tracker.last_prompt_seen_at(), last_enter_at) {
@@ -12,14 +12,48 @@ fn prompt_ready(prompt_tracker: &PromptTracker, last_enter_at: Option<Instant>)
     }
 }
 
+/// Readiness verdict plus the signal that decided it, for `subscribe` clients.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Readiness {
+    pub(crate) ready: bool,
+    /// A prompt-detection reason (`line_complete`, `idle_match`, `external_detector`,
+    /// `prompt_learned`), an output-timing one (`output_idle`, `output_active`,
+    /// `output_idle_after_enter`, `awaiting_prompt`), `backend_busy`, or a UI mode
+    /// that takes no text (`approval_pending`, `picker_open`).
+    pub(crate) reason: &'static str,
+}
+
 pub(crate) fn transcript_ready(
     prompt_tracker: &PromptTracker,
     last_enter_at: Option<Instant>,
     now: Instant,
     transcript_idle_timeout: Duration,
 ) -> bool {
+    transcript_readiness(prompt_tracker, last_enter_at, now, transcript_idle_timeout).ready
+}
+
+pub(crate) fn transcript_readiness(
+    prompt_tracker: &PromptTracker,
+    last_enter_at: Option<Instant>,
+    now: Instant,
+    transcript_idle_timeout: Duration,
+) -> Readiness {
+    let verdict = |ready, reason| Readiness { ready, reason };
+    // A visible prompt does not count while the backend is still generating.
+    if prompt_tracker.is_busy(now) {
+        return verdict(false, "backend_busy");
+    }
+    // Text typed into a dialog or menu would pick options; answers go through `answer_approval`.
+    match prompt_tracker.ui_mode() {
+        UiMode::Approval(_) => return verdict(false, "approval_pending"),
+        UiMode::Picker => return verdict(false, "picker_open"),
+        UiMode::Input => {}
+    }
     if prompt_ready(prompt_tracker, last_enter_at) {
-        return true;
+        return verdict(
+            true,
+            prompt_tracker.last_prompt_reason().unwrap_or("prompt"),
+        );
     }
     let idle_ready = if let Some(last_output_at) = prompt_tracker.last_pty_output_at() {
         now.duration_since(last_output_at) >= transcript_idle_timeout
@@ -27,16 +61,20 @@ pub(crate) fn transcript_ready(
         prompt_tracker.idle_ready(now, transcript_idle_timeout)
     };
     if prompt_tracker.last_prompt_seen_at().is_none() {
-        return idle_ready;
+        return if idle_ready {
+            verdict(true, "output_idle")
+        } else {
+            verdict(false, "output_active")
+        };
     }
     if let (Some(enter_at), Some(last_output_at)) =
         (last_enter_at, prompt_tracker.last_pty_output_at())
     {
         if last_output_at >= enter_at && idle_ready {
-            return true;
+            return verdict(true, "output_idle_after_enter");
         }
     }
-    false
+    verdict(false, "awaiting_prompt")
 }
 
 #[cfg(test)]
@@ -49,7 +87,7 @@ mod tests {
     fn transcript_ready_falls_back_after_output_idle_since_enter() {
         let logger = PromptLogger::new(None);
         let regex = Regex::new(r"^> $").unwrap();
-        let mut tracker = PromptTracker::new(Some(regex), false, logger);
+        let mut tracker = PromptTracker::new(vec![regex], false, logger);
 
         tracker.feed_output(b"> \n");
         let last_enter_at = Some(Instant::now());
@@ -58,5 +96,63 @@ mod tests {
         let idle_timeout = Duration::from_millis(10);
         let now = Instant::now() + idle_timeout + Duration::from_millis(1);
         assert!(transcript_ready(&tracker, last_enter_at, now, idle_timeout));
+        assert_eq!(
+            transcript_readiness(&tracker, last_enter_at, now, idle_timeout).reason,
+            "output_idle_after_enter"
+        );
+    }
+
+    #[test]
+    fn readiness_reports_the_deciding_signal() {
+        let logger = PromptLogger::new(None);
+        let regex = Regex::new(r"^> $").unwrap();
+        let mut tracker = PromptTracker::new(vec![regex], false, logger);
+        let idle_timeout = Duration::from_secs(60);
+
+        tracker.feed_output(b"starting up\n");
+        let now = Instant::now();
+        assert_eq!(
+            transcript_readiness(&tracker, None, now, idle_timeout),
+            Readiness {
+                ready: false,
+                reason: "output_active"
+            }
+        );
+
+        tracker.feed_output(b"> \n");
+        assert_eq!(
+            transcript_readiness(&tracker, None, Instant::now(), idle_timeout),
+            Readiness {
+                ready: true,
+                reason: "line_complete"
+            }
+        );
+
+        let enter_at = Some(Instant::now() + Duration::from_millis(1));
+        assert_eq!(
+            transcript_readiness(&tracker, enter_at, Instant::now(), idle_timeout),
+            Readiness {
+                ready: false,
+                reason: "awaiting_prompt"
+            }
+        );
+
+        tracker.feed_output(b"\r\n> 1. gpt-5\r\n  2. gpt-5-mini");
+        assert_eq!(
+            transcript_readiness(&tracker, None, Instant::now(), idle_timeout),
+            Readiness {
+                ready: false,
+                reason: "picker_open"
+            }
+        );
+
+        tracker.feed_output("\r\n⠹ Thinking…".as_bytes());
+        assert_eq!(
+            transcript_readiness(&tracker, None, Instant::now(), idle_timeout),
+            Readiness {
+                ready: false,
+                reason: "backend_busy"
+            }
+        );
     }
 }
@@ -1,11 +1,55 @@
 //! Transcript queue/delivery wiring so capture output reaches the CLI in order.
 
+mod approval;
+mod batch;
+mod clipboard;
+mod code_mode;
 mod delivery;
+mod dictation;
+mod echo;
+mod feedback;
+mod history;
 mod idle;
+mod multiline;
+mod pacing;
+mod postprocess;
+mod prefix;
 mod queue;
+mod redact;
+mod replay;
+mod secrets;
 mod session;
+mod targets;
+mod tmux;
+mod translate;
+mod uncertain;
+mod urgent;
 
-pub(crate) use delivery::{deliver_transcript, send_transcript, try_flush_pending, TranscriptIo};
-pub(crate) use idle::transcript_ready;
-pub(crate) use queue::{push_pending_transcript, PendingTranscript};
+pub(crate) use approval::approval_answer;
+pub(crate) use batch::BatchWindow;
+pub(crate) use clipboard::base64_encode;
+pub(crate) use code_mode::format_code_dictation;
+pub(crate) use delivery::{
+    deliver_transcript, send_transcript, submit_external_transcript, try_flush_pending,
+    TranscriptIo,
+};
+pub(crate) use dictation::{normalize_command, resolve_dictation, DictationBuffer};
+pub(crate) use echo::ECHO_RETRY_LABEL;
+pub(crate) use feedback::{flagged_transcripts_path, run_export_feedback, TranscriptFeedback};
+pub(crate) use history::TranscriptHistoryWriter;
+pub(crate) use idle::{transcript_readiness, transcript_ready, Readiness};
+pub(crate) use multiline::MultilineDelivery;
+pub(crate) use pacing::resolve_type_delay;
+pub(crate) use postprocess::TranscriptPostProcessor;
+pub(crate) use prefix::TranscriptPrefix;
+pub(crate) use queue::{
+    push_pending_transcript, queue_full_status, PendingTranscript, QueueOutcome, QueuePolicy,
+};
+pub(crate) use redact::{loggable, redact_transcripts, redacted, set_redact_transcripts};
+pub(crate) use replay::PromptReplay;
+pub(crate) use secrets::SecretsFilter;
 pub(crate) use session::TranscriptSession;
+pub(crate) use targets::TranscriptTargets;
+pub(crate) use translate::{original_note, TranscriptTranslator};
+pub(crate) use uncertain::UncertainWordMarker;
+pub(crate) use urgent::{resolve_urgent_hold, HeldTranscript, HoldReason, UrgentKeywords};
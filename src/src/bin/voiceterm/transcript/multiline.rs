@@ -0,0 +1,241 @@
+//! Newline-aware transcript encoding so multi-line dictation arrives as one backend message.
+//!
+//! A raw newline inside a transcript is read by the backend as Enter and submits
+//! the first line early. Multi-line text is instead wrapped in bracketed paste
+//! (when the backend has enabled it), sent with the backend's soft-newline
+//! sequence, or flattened to a single line. With `--bracketed-paste all`,
+//! single-line transcripts are pasted too, so characters the backend binds as
+//! shortcuts (like `?` in an empty prompt) arrive as text.
+
+use voiceterm::log_debug;
+
+use crate::config::{BracketedPasteMode, MultilineMode};
+
+const BRACKETED_PASTE_ON: &[u8] = b"\x1b[?2004h";
+const BRACKETED_PASTE_OFF: &[u8] = b"\x1b[?2004l";
+pub(super) const PASTE_START: &str = "\x1b[200~";
+const PASTE_END: &str = "\x1b[201~";
+
+/// Backend input sequence that inserts a line break without submitting.
+fn soft_newline_for_backend(backend_label: &str) -> Option<&'static str> {
+    let backend = backend_label.to_ascii_lowercase();
+    if backend.contains("claude") {
+        // Claude Code treats a backslash before Enter as a line continuation.
+        Some("\\\n")
+    } else {
+        None
+    }
+}
+
+/// Multi-line delivery settings plus the backend's live bracketed-paste state.
+#[derive(Debug, Clone)]
+pub(crate) struct MultilineDelivery {
+    mode: MultilineMode,
+    paste_mode: BracketedPasteMode,
+    soft_newline: Option<&'static str>,
+    bracketed_paste: bool,
+    /// False while transcripts go to a tmux pane, whose paste mode the overlay cannot see.
+    backend_target: bool,
+    /// Trailing bytes of the last output chunk, so split mode sequences are still seen.
+    tail: Vec<u8>,
+}
+
+impl Default for MultilineDelivery {
+    fn default() -> Self {
+        Self::new(MultilineMode::default(), "")
+    }
+}
+
+impl MultilineDelivery {
+    pub(crate) fn new(mode: MultilineMode, backend_label: &str) -> Self {
+        Self {
+            mode,
+            paste_mode: BracketedPasteMode::default(),
+            soft_newline: soft_newline_for_backend(backend_label),
+            bracketed_paste: false,
+            backend_target: true,
+            tail: Vec::new(),
+        }
+    }
+
+    /// Track `ESC[?2004h` / `ESC[?2004l` in backend output.
+    pub(crate) fn observe_output(&mut self, bytes: &[u8]) {
+        let mut window = std::mem::take(&mut self.tail);
+        window.extend_from_slice(bytes);
+        for candidate in window.windows(BRACKETED_PASTE_ON.len()) {
+            if candidate == BRACKETED_PASTE_ON {
+                self.bracketed_paste = true;
+            } else if candidate == BRACKETED_PASTE_OFF {
+                self.bracketed_paste = false;
+            }
+        }
+        let keep = BRACKETED_PASTE_ON.len() - 1;
+        let start = window.len().saturating_sub(keep);
+        self.tail = window.split_off(start);
+    }
+
+    /// Choose which transcripts are wrapped in bracketed paste (`--bracketed-paste`).
+    pub(crate) fn with_bracketed_paste(mut self, paste_mode: BracketedPasteMode) -> Self {
+        self.paste_mode = paste_mode;
+        self
+    }
+
+    /// Switch between encoding for the wrapped backend and for another destination.
+    ///
+    /// Backend output keeps being tracked either way, so switching back restores
+    /// its bracketed-paste state.
+    pub(crate) fn set_backend_target(&mut self, backend_target: bool) {
+        self.backend_target = backend_target;
+    }
+
+    /// Encode `text` so embedded newlines do not submit early.
+    ///
+    /// Single-line text is unchanged unless `--bracketed-paste all` is set and the
+    /// backend has bracketed paste enabled.
+    pub(crate) fn encode(&self, text: &str) -> String {
+        let normalized = text.replace("\r\n", "\n").replace('\r', "\n");
+        let paste = || {
+            // Drop ESC so transcript text can never terminate the paste early.
+            let body: String = normalized.chars().filter(|ch| *ch != '\x1b').collect();
+            format!("{PASTE_START}{body}{PASTE_END}")
+        };
+        let bracketed_paste = self.bracketed_paste
+            && self.backend_target
+            && self.paste_mode != BracketedPasteMode::Off;
+        if !normalized.contains('\n') {
+            return if bracketed_paste && self.paste_mode == BracketedPasteMode::All {
+                paste()
+            } else {
+                text.to_string()
+            };
+        }
+        let soft_newline = self.soft_newline.filter(|_| self.backend_target);
+        match self.mode {
+            MultilineMode::Paste if bracketed_paste => paste(),
+            MultilineMode::Paste => match soft_newline {
+                Some(soft) => normalized.replace('\n', soft),
+                None => {
+                    log_debug("bracketed paste not enabled by backend; joining transcript lines");
+                    join_lines(&normalized)
+                }
+            },
+            MultilineMode::SoftNewline => match soft_newline {
+                Some(soft) => normalized.replace('\n', soft),
+                None if bracketed_paste => paste(),
+                None => {
+                    log_debug("no soft-newline sequence for backend; joining transcript lines");
+                    join_lines(&normalized)
+                }
+            },
+            MultilineMode::Join => join_lines(&normalized),
+        }
+    }
+}
+
+fn join_lines(text: &str) -> String {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_line_text_is_unchanged() {
+        let mut delivery = MultilineDelivery::new(MultilineMode::Paste, "codex");
+        delivery.observe_output(BRACKETED_PASTE_ON);
+        assert_eq!(delivery.encode("hello world"), "hello world");
+    }
+
+    #[test]
+    fn paste_mode_wraps_when_backend_enabled_bracketed_paste() {
+        let mut delivery = MultilineDelivery::new(MultilineMode::Paste, "codex");
+        delivery.observe_output(b"banner\x1b[?2004hcodex> ");
+        assert_eq!(
+            delivery.encode("first line\r\nsecond\x1b line"),
+            "\x1b[200~first line\nsecond line\x1b[201~"
+        );
+    }
+
+    #[test]
+    fn observe_output_tracks_sequences_split_across_chunks() {
+        let mut delivery = MultilineDelivery::new(MultilineMode::Paste, "codex");
+        delivery.observe_output(b"out\x1b[?20");
+        delivery.observe_output(b"04h more");
+        assert!(delivery.bracketed_paste);
+        delivery.observe_output(b"\x1b[?2004");
+        delivery.observe_output(b"l");
+        assert!(!delivery.bracketed_paste);
+    }
+
+    #[test]
+    fn paste_mode_falls_back_to_soft_newline_then_join() {
+        let claude = MultilineDelivery::new(MultilineMode::Paste, "claude");
+        assert_eq!(claude.encode("one\ntwo"), "one\\\ntwo");
+
+        let codex = MultilineDelivery::new(MultilineMode::Paste, "codex");
+        assert_eq!(codex.encode("one\n\n two \n"), "one two");
+    }
+
+    #[test]
+    fn soft_newline_mode_prefers_backend_sequence() {
+        let mut claude = MultilineDelivery::new(MultilineMode::SoftNewline, "claude");
+        claude.observe_output(BRACKETED_PASTE_ON);
+        assert_eq!(claude.encode("one\ntwo"), "one\\\ntwo");
+
+        let mut codex = MultilineDelivery::new(MultilineMode::SoftNewline, "codex");
+        codex.observe_output(BRACKETED_PASTE_ON);
+        assert_eq!(codex.encode("one\ntwo"), "\x1b[200~one\ntwo\x1b[201~");
+    }
+
+    #[test]
+    fn join_mode_always_flattens() {
+        let mut delivery = MultilineDelivery::new(MultilineMode::Join, "claude");
+        delivery.observe_output(BRACKETED_PASTE_ON);
+        assert_eq!(delivery.encode("one\ntwo"), "one two");
+    }
+
+    #[test]
+    fn other_targets_ignore_backend_paste_state_until_switched_back() {
+        let mut delivery = MultilineDelivery::new(MultilineMode::Paste, "claude");
+        delivery.observe_output(BRACKETED_PASTE_ON);
+        delivery.set_backend_target(false);
+        assert_eq!(delivery.encode("first\nsecond"), "first second");
+        delivery.set_backend_target(true);
+        assert_eq!(
+            delivery.encode("first\nsecond"),
+            "\x1b[200~first\nsecond\x1b[201~"
+        );
+    }
+
+    #[test]
+    fn bracketed_paste_all_wraps_single_lines_only_when_backend_enabled_it() {
+        let mut delivery = MultilineDelivery::new(MultilineMode::Paste, "codex")
+            .with_bracketed_paste(BracketedPasteMode::All);
+        assert_eq!(delivery.encode("what is this?"), "what is this?");
+        delivery.observe_output(BRACKETED_PASTE_ON);
+        assert_eq!(
+            delivery.encode("what is this?"),
+            "\x1b[200~what is this?\x1b[201~"
+        );
+        delivery.observe_output(BRACKETED_PASTE_OFF);
+        assert_eq!(delivery.encode("what is this?"), "what is this?");
+    }
+
+    #[test]
+    fn bracketed_paste_off_uses_the_fallbacks() {
+        let mut claude = MultilineDelivery::new(MultilineMode::Paste, "claude")
+            .with_bracketed_paste(BracketedPasteMode::Off);
+        claude.observe_output(BRACKETED_PASTE_ON);
+        assert_eq!(claude.encode("one\ntwo"), "one\\\ntwo");
+
+        let mut codex = MultilineDelivery::new(MultilineMode::SoftNewline, "codex")
+            .with_bracketed_paste(BracketedPasteMode::Off);
+        codex.observe_output(BRACKETED_PASTE_ON);
+        assert_eq!(codex.encode("one\ntwo"), "one two");
+    }
+}
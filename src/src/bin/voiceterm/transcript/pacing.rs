@@ -0,0 +1,127 @@
+//! Per-character transcript typing for backends that debounce programmatic input.
+//!
+//! Some TUIs drop keys that arrive faster than their debounce window, so a
+//! transcript written in one burst loses characters. With `--type-delay-ms` (or
+//! `type_delay_ms` in the config file's `backends.<label>` profile) transcripts
+//! sent to the wrapped backend are typed one character at a time instead. Tmux
+//! targets are unaffected, and a bracketed paste is still written whole since
+//! the backend reads it as a single paste.
+
+use std::collections::BTreeMap;
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+
+use crate::config::{BackendProfile, OverlayConfig};
+
+use super::multiline::PASTE_START;
+use super::session::TranscriptSession;
+
+/// Upper bound on the inter-key delay; typing blocks the event loop while it runs.
+const MAX_TYPE_DELAY_MS: u64 = 100;
+
+/// Inter-key delay for the wrapped backend: `--type-delay-ms` wins over the backend's profile.
+///
+/// `None` (or 0) keeps the default single-write delivery.
+pub(crate) fn resolve_type_delay(
+    config: &OverlayConfig,
+    profiles: &BTreeMap<String, BackendProfile>,
+    backend_label: &str,
+) -> Result<Option<Duration>> {
+    let delay_ms = config.type_delay_ms.or_else(|| {
+        profiles
+            .iter()
+            .find(|(label, _)| label.eq_ignore_ascii_case(backend_label))
+            .and_then(|(_, profile)| profile.type_delay_ms)
+    });
+    match delay_ms {
+        Some(ms) if ms > MAX_TYPE_DELAY_MS => {
+            bail!("type delay must be at most {MAX_TYPE_DELAY_MS} ms (got {ms})")
+        }
+        Some(ms) if ms > 0 => Ok(Some(Duration::from_millis(ms))),
+        _ => Ok(None),
+    }
+}
+
+/// Send `text` one character at a time, `delay` apart, then a newline when `newline` is set.
+pub(super) fn type_paced(
+    session: &mut impl TranscriptSession,
+    text: &str,
+    delay: Duration,
+    newline: bool,
+) -> Result<()> {
+    let mut keys: Vec<String> = if text.starts_with(PASTE_START) {
+        vec![text.to_string()]
+    } else {
+        text.chars().map(String::from).collect()
+    };
+    if newline && !text.ends_with('\n') {
+        keys.push("\n".to_string());
+    }
+    for (index, key) in keys.iter().enumerate() {
+        if index > 0 {
+            thread::sleep(delay);
+        }
+        session.send_text(key)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::UserConfig;
+    use clap::Parser;
+
+    #[derive(Default)]
+    struct RecordingSession {
+        writes: Vec<String>,
+    }
+
+    impl TranscriptSession for RecordingSession {
+        fn send_text(&mut self, text: &str) -> Result<()> {
+            self.writes.push(text.to_string());
+            Ok(())
+        }
+
+        fn send_text_with_newline(&mut self, text: &str) -> Result<()> {
+            self.writes.push(format!("{text}\n"));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn type_paced_writes_each_character_but_keeps_pastes_whole() {
+        let mut session = RecordingSession::default();
+        type_paced(&mut session, "hé!", Duration::ZERO, true).unwrap();
+        assert_eq!(session.writes, ["h", "é", "!", "\n"]);
+
+        let mut session = RecordingSession::default();
+        let paste = "\x1b[200~a\nb\x1b[201~";
+        type_paced(&mut session, paste, Duration::ZERO, false).unwrap();
+        assert_eq!(session.writes, [paste]);
+    }
+
+    #[test]
+    fn resolve_type_delay_prefers_the_flag_over_the_backend_profile() {
+        let user = UserConfig::parse("backends:\n  Codex:\n    type_delay_ms: 8\n").unwrap();
+        let config = OverlayConfig::parse_from(["test"]);
+        assert_eq!(
+            resolve_type_delay(&config, &user.backends, "codex").unwrap(),
+            Some(Duration::from_millis(8))
+        );
+        assert_eq!(
+            resolve_type_delay(&config, &user.backends, "claude").unwrap(),
+            None
+        );
+
+        let config = OverlayConfig::parse_from(["test", "--type-delay-ms", "0"]);
+        assert_eq!(
+            resolve_type_delay(&config, &user.backends, "codex").unwrap(),
+            None
+        );
+        let config = OverlayConfig::parse_from(["test", "--type-delay-ms", "500"]);
+        assert!(resolve_type_delay(&config, &user.backends, "codex").is_err());
+    }
+}
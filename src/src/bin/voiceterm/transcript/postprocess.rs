@@ -0,0 +1,241 @@
+//! Transcript post-processing so filler words and dictation quirks are cleaned before delivery.
+//!
+//! Rules come from the `postprocess` section of the user config file and run in
+//! a fixed order: filler removal, regex replacements, then case normalization.
+
+use anyhow::{Context, Result};
+use regex::{Regex, RegexBuilder};
+
+use crate::config::{PostprocessConfig, TranscriptCase};
+
+/// Fillers removed when `strip_fillers` is on and no custom list is given.
+const DEFAULT_FILLERS: &[&str] = &["um", "umm", "uh", "uhm", "er", "erm", "ah", "hmm"];
+
+#[derive(Debug, Clone)]
+struct Replacement {
+    pattern: Regex,
+    replace: String,
+}
+
+/// Compiled post-processing rules applied to every transcript.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TranscriptPostProcessor {
+    fillers: Option<Regex>,
+    replacements: Vec<Replacement>,
+    case: TranscriptCase,
+}
+
+impl TranscriptPostProcessor {
+    /// Compile config rules; invalid regexes are reported with the offending pattern.
+    pub(crate) fn from_config(config: &PostprocessConfig) -> Result<Self> {
+        let fillers = if config.strip_fillers {
+            let words: Vec<String> = match &config.fillers {
+                Some(custom) => custom.clone(),
+                None => DEFAULT_FILLERS
+                    .iter()
+                    .map(|word| word.to_string())
+                    .collect(),
+            };
+            filler_regex(&words)?
+        } else {
+            None
+        };
+        let replacements = config
+            .replacements
+            .iter()
+            .map(|rule| {
+                let pattern = RegexBuilder::new(&rule.pattern)
+                    .case_insensitive(rule.ignore_case)
+                    .build()
+                    .with_context(|| {
+                        format!("invalid postprocess replacement pattern '{}'", rule.pattern)
+                    })?;
+                Ok(Replacement {
+                    pattern,
+                    replace: rule.replace.clone(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            fillers,
+            replacements,
+            case: config.case,
+        })
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.fillers.is_none() && self.replacements.is_empty() && self.case == TranscriptCase::None
+    }
+
+    pub(crate) fn rule_count(&self) -> usize {
+        usize::from(self.fillers.is_some())
+            + self.replacements.len()
+            + usize::from(self.case != TranscriptCase::None)
+    }
+
+    /// Apply all rules; returns the input unchanged when no rules are configured.
+    pub(crate) fn apply(&self, text: &str) -> String {
+        if self.is_empty() {
+            return text.to_string();
+        }
+        let mut out = text.to_string();
+        if let Some(fillers) = &self.fillers {
+            out = fillers.replace_all(&out, "").into_owned();
+            out = tidy_spacing(&out);
+        }
+        for rule in &self.replacements {
+            out = rule
+                .pattern
+                .replace_all(&out, rule.replace.as_str())
+                .into_owned();
+        }
+        match self.case {
+            TranscriptCase::None => out,
+            TranscriptCase::Lower => out.to_lowercase(),
+            TranscriptCase::Sentence => sentence_case(&out),
+        }
+    }
+}
+
+/// Whole-word filler matcher that also eats a trailing comma so "um, run" becomes "run".
+fn filler_regex(words: &[String]) -> Result<Option<Regex>> {
+    let alternatives: Vec<String> = words
+        .iter()
+        .map(|word| word.trim())
+        .filter(|word| !word.is_empty())
+        .map(regex::escape)
+        .collect();
+    if alternatives.is_empty() {
+        return Ok(None);
+    }
+    let pattern = format!(r"(?i)\b(?:{})\b[,;]?", alternatives.join("|"));
+    Regex::new(&pattern)
+        .map(Some)
+        .context("invalid postprocess filler list")
+}
+
+/// Collapse runs of spaces and drop spaces left before punctuation.
+fn tidy_spacing(text: &str) -> String {
+    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let mut out = String::with_capacity(collapsed.len());
+    for ch in collapsed.chars() {
+        if matches!(ch, ',' | '.' | '!' | '?' | ';' | ':') && out.ends_with(' ') {
+            out.pop();
+        }
+        out.push(ch);
+    }
+    out.trim_start_matches([',', ';', ' ']).to_string()
+}
+
+/// Lowercase everything, then capitalize the first letter of each sentence.
+fn sentence_case(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut capitalize = true;
+    for ch in text.to_lowercase().chars() {
+        if capitalize && ch.is_alphabetic() {
+            out.extend(ch.to_uppercase());
+            capitalize = false;
+        } else {
+            out.push(ch);
+        }
+        if matches!(ch, '.' | '!' | '?') {
+            capitalize = true;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{PostprocessConfig, ReplacementRule};
+
+    fn replacement(pattern: &str, replace: &str) -> ReplacementRule {
+        ReplacementRule {
+            pattern: pattern.to_string(),
+            replace: replace.to_string(),
+            ignore_case: true,
+        }
+    }
+
+    #[test]
+    fn default_config_leaves_text_untouched() {
+        let processor = TranscriptPostProcessor::from_config(&PostprocessConfig::default())
+            .expect("default config compiles");
+        assert!(processor.is_empty());
+        assert_eq!(processor.apply("Um, hello  World"), "Um, hello  World");
+    }
+
+    #[test]
+    fn strip_fillers_removes_whole_words_only() {
+        let processor = TranscriptPostProcessor::from_config(&PostprocessConfig {
+            strip_fillers: true,
+            ..PostprocessConfig::default()
+        })
+        .unwrap();
+        assert_eq!(
+            processor.apply("Um, run the, uh, umbrella tests uh."),
+            "run the, umbrella tests."
+        );
+    }
+
+    #[test]
+    fn custom_filler_list_replaces_defaults() {
+        let processor = TranscriptPostProcessor::from_config(&PostprocessConfig {
+            strip_fillers: true,
+            fillers: Some(vec!["you know".to_string()]),
+            ..PostprocessConfig::default()
+        })
+        .unwrap();
+        assert_eq!(processor.apply("um it, you know, works"), "um it, works");
+    }
+
+    #[test]
+    fn replacements_run_in_order_with_capture_groups() {
+        let processor = TranscriptPostProcessor::from_config(&PostprocessConfig {
+            replacements: vec![
+                replacement(r"\s*\bopen paren\b\s*", "("),
+                replacement(r"\s*\bclose paren\b", ")"),
+                replacement(r"\bparens\b", "()"),
+                replacement(r"\bfile (\w+) dot rs\b", "$1.rs"),
+            ],
+            ..PostprocessConfig::default()
+        })
+        .unwrap();
+        assert_eq!(
+            processor.apply("call foo open paren x close paren in file main dot rs"),
+            "call foo(x) in main.rs"
+        );
+        assert_eq!(processor.apply("add Parens"), "add ()");
+    }
+
+    #[test]
+    fn case_normalization_modes() {
+        let lower = TranscriptPostProcessor::from_config(&PostprocessConfig {
+            case: TranscriptCase::Lower,
+            ..PostprocessConfig::default()
+        })
+        .unwrap();
+        assert_eq!(lower.apply("Run The TESTS"), "run the tests");
+
+        let sentence = TranscriptPostProcessor::from_config(&PostprocessConfig {
+            case: TranscriptCase::Sentence,
+            ..PostprocessConfig::default()
+        })
+        .unwrap();
+        assert_eq!(
+            sentence.apply("RUN the tests. then COMMIT! ok?"),
+            "Run the tests. Then commit! Ok?"
+        );
+    }
+
+    #[test]
+    fn invalid_replacement_pattern_is_reported() {
+        let err = TranscriptPostProcessor::from_config(&PostprocessConfig {
+            replacements: vec![replacement("(unclosed", "x")],
+            ..PostprocessConfig::default()
+        })
+        .expect_err("pattern should fail to compile");
+        assert!(format!("{err:#}").contains("(unclosed"));
+    }
+}
@@ -0,0 +1,75 @@
+//! `project.prefix` so each transcript tells the backend which repository it is about.
+//!
+//! The template comes from the `project` section of the config file (often a
+//! shared `--project-config` file), e.g. `In repo {repo}: `, where `{repo}` is
+//! the name of the repository root VoiceTerm was started in.
+
+use std::path::Path;
+
+use crate::config::ProjectConfig;
+
+/// Rendered transcript prefix, or none.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TranscriptPrefix {
+    prefix: Option<String>,
+}
+
+impl TranscriptPrefix {
+    pub(crate) fn from_config(config: &ProjectConfig, project_root: &Path) -> Self {
+        let repo = project_root
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let prefix = config
+            .prefix
+            .as_deref()
+            .filter(|template| !template.trim().is_empty())
+            .map(|template| template.replace("{repo}", &repo));
+        Self { prefix }
+    }
+
+    pub(crate) fn prefix(&self) -> Option<&str> {
+        self.prefix.as_deref()
+    }
+
+    /// `text` with the prefix in front; blank or already-prefixed text is returned unchanged.
+    pub(crate) fn apply(&self, text: String) -> String {
+        match self.prefix.as_deref() {
+            Some(prefix) if !text.trim().is_empty() && !text.starts_with(prefix) => {
+                format!("{prefix}{text}")
+            }
+            _ => text,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prefix(template: Option<&str>) -> TranscriptPrefix {
+        TranscriptPrefix::from_config(
+            &ProjectConfig {
+                prefix: template.map(str::to_string),
+            },
+            Path::new("/home/dev/codex-voice"),
+        )
+    }
+
+    #[test]
+    fn prefix_names_the_repo_and_skips_blank_text() {
+        let repo = prefix(Some("In repo {repo}: "));
+        assert_eq!(repo.prefix(), Some("In repo codex-voice: "));
+        assert_eq!(
+            repo.apply("run the tests".to_string()),
+            "In repo codex-voice: run the tests"
+        );
+        assert_eq!(
+            repo.apply("In repo codex-voice: done".to_string()),
+            "In repo codex-voice: done"
+        );
+        assert_eq!(repo.apply("  ".to_string()), "  ");
+        assert_eq!(prefix(None).apply("hi".to_string()), "hi");
+        assert_eq!(prefix(Some(" ")).prefix(), None);
+    }
+}
@@ -1,65 +1,313 @@
 //! Transcript queue management so auto-send/order guarantees remain predictable.
+//!
+//! The queue holds `--transcript-queue-size` transcripts; `--transcript-queue-policy`
+//! decides what happens to one that arrives when it is full.
 
+use anyhow::{bail, Result};
 use std::collections::VecDeque;
-use voiceterm::{log_debug, VoiceCaptureSource};
+use std::time::Instant;
+use voiceterm::log_debug;
 
-use crate::config::VoiceSendMode;
+use super::redact::redact_transcripts;
+use crate::config::{OverlayConfig, TranscriptQueuePolicy, VoiceSendMode};
 
-pub(crate) const MAX_PENDING_TRANSCRIPTS: usize = 5;
+/// Default `--transcript-queue-size`.
+pub(crate) const DEFAULT_QUEUE_SIZE: usize = 5;
+/// Upper bound for `--transcript-queue-size`.
+const MAX_QUEUE_SIZE: usize = 50;
+/// Characters of a dropped transcript named in the queue-full status.
+const DROPPED_PREVIEW_CHARS: usize = 24;
+/// Label of a queued transcript merged from different sources.
+pub(super) const MIXED_LABEL: &str = "Mixed pipelines";
 
 /// Transcript queued while the CLI is busy.
 pub(crate) struct PendingTranscript {
     /// Raw transcript text.
     pub(crate) text: String,
-    /// Pipeline that produced the transcript.
-    pub(crate) source: VoiceCaptureSource,
+    /// Where the transcript came from (pipeline or external sender), shown in delivery status.
+    pub(crate) label: &'static str,
     /// Send mode to apply when flushing.
     pub(crate) mode: VoiceSendMode,
+    /// When the transcript arrived, for delivery latency metrics.
+    pub(crate) received_at: Instant,
+    /// Capture sequence number assigned at capture start; `None` for text that
+    /// did not come from a capture (control socket, replay).
+    pub(crate) seq: Option<u64>,
 }
 
+/// Queue size and overflow policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct QueuePolicy {
+    pub(crate) size: usize,
+    pub(crate) overflow: TranscriptQueuePolicy,
+}
+
+impl Default for QueuePolicy {
+    fn default() -> Self {
+        Self {
+            size: DEFAULT_QUEUE_SIZE,
+            overflow: TranscriptQueuePolicy::DropOldest,
+        }
+    }
+}
+
+impl QueuePolicy {
+    pub(crate) fn from_config(config: &OverlayConfig) -> Result<Self> {
+        if !(1..=MAX_QUEUE_SIZE).contains(&config.transcript_queue_size) {
+            bail!(
+                "--transcript-queue-size must be between 1 and {MAX_QUEUE_SIZE}, got {}",
+                config.transcript_queue_size
+            );
+        }
+        Ok(Self {
+            size: config.transcript_queue_size,
+            overflow: config.transcript_queue_policy,
+        })
+    }
+
+    /// Whether new captures wait because `queued` transcripts fill the queue under `block`.
+    pub(crate) fn blocks_capture(self, queued: usize) -> bool {
+        self.overflow == TranscriptQueuePolicy::Block && queued >= self.size
+    }
+}
+
+/// What the queue did with a transcript.
+pub(crate) enum QueueOutcome {
+    Queued,
+    /// Appended to the newest queued transcript (`merge-immediately`).
+    Merged,
+    /// The queue was full; this transcript (the oldest, or the new one under
+    /// `drop-newest`) was discarded.
+    Dropped(PendingTranscript),
+}
+
+/// Queue a transcript, keeping captures in the order they were started.
+///
+/// A capture can finish after a later one (a python-fallback capture is slower
+/// than a native one), so a transcript is placed ahead of queued captures with
+/// a higher sequence number. Text without a sequence number keeps its arrival
+/// position and is never reordered past.
+///
+/// Under `merge-immediately` a transcript that belongs at the end is appended to
+/// the newest queued one when both use the same send mode. A full queue drops
+/// the oldest transcript (also the fallback for `merge-immediately`), drops the
+/// new one under `drop-newest`, and grows past its size under `block`, which
+/// stops new captures instead (see [`QueuePolicy::blocks_capture`]).
 pub(crate) fn push_pending_transcript(
     pending: &mut VecDeque<PendingTranscript>,
     transcript: PendingTranscript,
-) -> bool {
-    if pending.len() >= MAX_PENDING_TRANSCRIPTS {
-        pending.pop_front();
-        log_debug("pending transcript queue full; dropping oldest transcript");
-        pending.push_back(transcript);
-        return true;
-    }
-    pending.push_back(transcript);
-    false
+    policy: QueuePolicy,
+) -> QueueOutcome {
+    let position = queue_position(pending, &transcript);
+    if policy.overflow == TranscriptQueuePolicy::MergeImmediately && position == pending.len() {
+        if let Some(newest) = pending
+            .back_mut()
+            .filter(|newest| newest.mode == transcript.mode)
+        {
+            merge_into(newest, transcript);
+            return QueueOutcome::Merged;
+        }
+    }
+    let mut outcome = QueueOutcome::Queued;
+    if pending.len() >= policy.size {
+        match policy.overflow {
+            TranscriptQueuePolicy::Block => {
+                log_debug("pending transcript queue full; keeping transcript past the limit");
+            }
+            TranscriptQueuePolicy::DropNewest => {
+                log_debug("pending transcript queue full; dropping newest transcript");
+                return QueueOutcome::Dropped(transcript);
+            }
+            TranscriptQueuePolicy::DropOldest | TranscriptQueuePolicy::MergeImmediately => {
+                log_debug("pending transcript queue full; dropping oldest transcript");
+                if let Some(oldest) = pending.pop_front() {
+                    outcome = QueueOutcome::Dropped(oldest);
+                }
+            }
+        }
+    }
+    let position = queue_position(pending, &transcript);
+    if position < pending.len() {
+        log_debug(&format!(
+            "transcript from capture {:?} queued ahead of {} later capture(s)",
+            transcript.seq,
+            pending.len() - position
+        ));
+    }
+    pending.insert(position, transcript);
+    outcome
+}
+
+/// Index `transcript` belongs at, behind every earlier capture and unsequenced text.
+fn queue_position(pending: &VecDeque<PendingTranscript>, transcript: &PendingTranscript) -> usize {
+    pending
+        .iter()
+        .rposition(|queued| match (queued.seq, transcript.seq) {
+            (Some(queued), Some(seq)) => queued < seq,
+            _ => true,
+        })
+        .map_or(0, |index| index + 1)
+}
+
+fn merge_into(newest: &mut PendingTranscript, transcript: PendingTranscript) {
+    let text = transcript.text.trim();
+    if !text.is_empty() {
+        newest.text = format!("{} {text}", newest.text.trim_end());
+    }
+    if newest.label != transcript.label {
+        newest.label = MIXED_LABEL;
+    }
+    if let (Some(queued), Some(seq)) = (newest.seq, transcript.seq) {
+        newest.seq = Some(queued.max(seq));
+    }
+}
+
+/// Status naming the transcript dropped from a full queue.
+pub(crate) fn queue_full_status(dropped: &PendingTranscript) -> String {
+    let text = dropped.text.trim();
+    if redact_transcripts() {
+        return format!(
+            "Queue full; dropped a transcript ({} chars)",
+            text.chars().count()
+        );
+    }
+    let preview: String = if text.chars().count() > DROPPED_PREVIEW_CHARS {
+        text.chars()
+            .take(DROPPED_PREVIEW_CHARS - 1)
+            .chain(std::iter::once('…'))
+            .collect()
+    } else {
+        text.to_string()
+    };
+    format!("Queue full; dropped \"{preview}\"")
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use clap::Parser;
+    use voiceterm::VoiceCaptureSource;
+
+    fn capture(text: &str, seq: Option<u64>) -> PendingTranscript {
+        PendingTranscript {
+            text: text.to_string(),
+            label: VoiceCaptureSource::Native.label(),
+            mode: VoiceSendMode::Auto,
+            received_at: Instant::now(),
+            seq,
+        }
+    }
+
+    fn texts(pending: &VecDeque<PendingTranscript>) -> Vec<&str> {
+        pending.iter().map(|queued| queued.text.as_str()).collect()
+    }
+
+    fn policy(size: usize, overflow: TranscriptQueuePolicy) -> QueuePolicy {
+        QueuePolicy { size, overflow }
+    }
+
+    fn push(pending: &mut VecDeque<PendingTranscript>, text: &str) -> QueueOutcome {
+        push_pending_transcript(pending, capture(text, None), QueuePolicy::default())
+    }
 
     #[test]
     fn push_pending_transcript_drops_oldest_when_full() {
         let mut pending = VecDeque::new();
-        for i in 0..MAX_PENDING_TRANSCRIPTS {
-            let dropped = push_pending_transcript(
-                &mut pending,
-                PendingTranscript {
-                    text: format!("t{i}"),
-                    source: VoiceCaptureSource::Native,
-                    mode: VoiceSendMode::Auto,
-                },
-            );
-            assert!(!dropped);
+        for i in 0..DEFAULT_QUEUE_SIZE {
+            let outcome = push(&mut pending, &format!("t{i}"));
+            assert!(matches!(outcome, QueueOutcome::Queued));
         }
-        let dropped = push_pending_transcript(
-            &mut pending,
-            PendingTranscript {
-                text: "last".to_string(),
-                source: VoiceCaptureSource::Native,
-                mode: VoiceSendMode::Auto,
-            },
-        );
-        assert!(dropped);
-        assert_eq!(pending.len(), MAX_PENDING_TRANSCRIPTS);
+        let QueueOutcome::Dropped(dropped) = push(&mut pending, "last") else {
+            panic!("oldest transcript should be dropped");
+        };
+        assert_eq!(dropped.text, "t0");
+        assert_eq!(queue_full_status(&dropped), "Queue full; dropped \"t0\"");
+        assert_eq!(pending.len(), DEFAULT_QUEUE_SIZE);
         assert_eq!(pending.front().unwrap().text, "t1");
         assert_eq!(pending.back().unwrap().text, "last");
     }
+
+    #[test]
+    fn full_queue_follows_the_overflow_policy() {
+        let mut pending = VecDeque::new();
+        let newest = policy(2, TranscriptQueuePolicy::DropNewest);
+        push_pending_transcript(&mut pending, capture("a", None), newest);
+        push_pending_transcript(&mut pending, capture("b", None), newest);
+        let outcome = push_pending_transcript(&mut pending, capture("c", None), newest);
+        assert!(matches!(outcome, QueueOutcome::Dropped(dropped) if dropped.text == "c"));
+        assert_eq!(texts(&pending), ["a", "b"]);
+
+        let block = policy(2, TranscriptQueuePolicy::Block);
+        assert!(block.blocks_capture(pending.len()));
+        let outcome = push_pending_transcript(&mut pending, capture("c", None), block);
+        assert!(matches!(outcome, QueueOutcome::Queued));
+        assert_eq!(texts(&pending), ["a", "b", "c"]);
+        assert!(!newest.blocks_capture(pending.len()));
+    }
+
+    #[test]
+    fn merge_immediately_appends_to_the_newest_same_mode_transcript() {
+        let merge = policy(2, TranscriptQueuePolicy::MergeImmediately);
+        let mut pending = VecDeque::new();
+        push_pending_transcript(&mut pending, capture("first part", Some(1)), merge);
+        let outcome =
+            push_pending_transcript(&mut pending, capture(" second part ", Some(2)), merge);
+        assert!(matches!(outcome, QueueOutcome::Merged));
+        assert_eq!(texts(&pending), ["first part second part"]);
+        assert_eq!(pending[0].seq, Some(2));
+
+        // A different send mode or an earlier capture is queued on its own, and
+        // a full queue still drops its oldest transcript.
+        let mut insert = capture("insert me", Some(3));
+        insert.mode = VoiceSendMode::Insert;
+        push_pending_transcript(&mut pending, insert, merge);
+        let outcome = push_pending_transcript(&mut pending, capture("late", Some(0)), merge);
+        assert!(
+            matches!(outcome, QueueOutcome::Dropped(dropped) if dropped.text == "first part second part")
+        );
+        assert_eq!(texts(&pending), ["late", "insert me"]);
+    }
+
+    #[test]
+    fn queue_size_is_validated() {
+        let config = OverlayConfig::parse_from(["voiceterm", "--transcript-queue-size", "0"]);
+        assert!(QueuePolicy::from_config(&config).is_err());
+        let config = OverlayConfig::parse_from([
+            "voiceterm",
+            "--transcript-queue-size",
+            "20",
+            "--transcript-queue-policy",
+            "merge-immediately",
+        ]);
+        assert_eq!(
+            QueuePolicy::from_config(&config).unwrap(),
+            policy(20, TranscriptQueuePolicy::MergeImmediately)
+        );
+    }
+
+    #[test]
+    fn late_capture_is_queued_ahead_of_later_captures() {
+        let mut pending = VecDeque::new();
+        let queue = QueuePolicy::default();
+        // Native capture 3 finished before python captures 1 and 2.
+        push_pending_transcript(&mut pending, capture("third", Some(3)), queue);
+        push_pending_transcript(&mut pending, capture("second", Some(2)), queue);
+        push_pending_transcript(&mut pending, capture("first", Some(1)), queue);
+        push_pending_transcript(&mut pending, capture("fourth", Some(4)), queue);
+        assert_eq!(texts(&pending), ["first", "second", "third", "fourth"]);
+    }
+
+    #[test]
+    fn unsequenced_text_is_a_barrier_for_reordering() {
+        let mut pending = VecDeque::new();
+        let queue = QueuePolicy::default();
+        push_pending_transcript(&mut pending, capture("capture 2", Some(2)), queue);
+        push_pending_transcript(&mut pending, capture("sent text", None), queue);
+        push_pending_transcript(&mut pending, capture("capture 1", Some(1)), queue);
+        push_pending_transcript(&mut pending, capture("more sent text", None), queue);
+        assert_eq!(
+            texts(&pending),
+            ["capture 2", "sent text", "capture 1", "more sent text"]
+        );
+    }
 }
@@ -0,0 +1,134 @@
+//! `--redact-transcripts` privacy mode so spoken text never reaches a log file.
+//!
+//! With redaction on, the debug log, the prompt log, the transcript history,
+//! and status messages carry a transcript's length and a stable fingerprint
+//! instead of its words. The fingerprint is FNV-1a, so the same text gives
+//! the same hash across runs and repeated prompts can still be spotted.
+
+use std::borrow::Cow;
+#[cfg(not(test))]
+use std::sync::atomic::{AtomicBool, Ordering};
+
+#[cfg(not(test))]
+static REDACT_TRANSCRIPTS: AtomicBool = AtomicBool::new(false);
+
+// Tests run in parallel, so each test thread gets its own switch.
+#[cfg(test)]
+thread_local! {
+    static REDACT_TRANSCRIPTS: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+#[cfg(not(test))]
+pub(crate) fn set_redact_transcripts(enabled: bool) {
+    REDACT_TRANSCRIPTS.store(enabled, Ordering::Relaxed);
+}
+
+#[cfg(not(test))]
+pub(crate) fn redact_transcripts() -> bool {
+    REDACT_TRANSCRIPTS.load(Ordering::Relaxed)
+}
+
+#[cfg(test)]
+pub(crate) fn set_redact_transcripts(enabled: bool) {
+    REDACT_TRANSCRIPTS.with(|flag| flag.set(enabled));
+}
+
+#[cfg(test)]
+pub(crate) fn redact_transcripts() -> bool {
+    REDACT_TRANSCRIPTS.with(|flag| flag.get())
+}
+
+/// Stable 64-bit FNV-1a hash of `text`, as 16 hex digits.
+pub(crate) fn text_hash(text: &str) -> String {
+    let hash = text.bytes().fold(FNV_OFFSET, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    });
+    format!("{hash:016x}")
+}
+
+/// Length and hash standing in for `text`, e.g. `<redacted chars=12 hash=…>`.
+pub(crate) fn redacted(text: &str) -> String {
+    format!(
+        "<redacted chars={} hash={}>",
+        text.chars().count(),
+        text_hash(text)
+    )
+}
+
+/// `text` as it may be written to a log: unchanged unless redaction is on.
+pub(crate) fn loggable(text: &str) -> Cow<'_, str> {
+    if redact_transcripts() {
+        Cow::Owned(redacted(text))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacted_keeps_length_and_a_stable_hash_only() {
+        assert_eq!(text_hash(""), "cbf29ce484222325");
+        assert_eq!(text_hash("a"), "af63dc4c8601ec8c");
+        let line = redacted("run the tests");
+        assert_eq!(
+            line,
+            format!("<redacted chars=13 hash={}>", text_hash("run the tests"))
+        );
+        assert!(!line.contains("tests"));
+        assert_eq!(
+            redacted("héllo"),
+            format!("<redacted chars=5 hash={}>", text_hash("héllo"))
+        );
+    }
+
+    #[test]
+    fn debug_log_stays_clean_with_redaction_on() {
+        use super::super::dictation::DictationBuffer;
+        use super::super::feedback::TranscriptFeedback;
+        use crate::config::OverlayConfig;
+        use clap::Parser;
+        use std::time::Instant;
+        use voiceterm::{init_logging, log_file_path};
+
+        let dir = std::env::temp_dir().join(format!("voiceterm-redact-log-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).expect("create log dir");
+        let config = OverlayConfig::parse_from([
+            "voiceterm".as_ref(),
+            "--logs".as_ref(),
+            "--log-dir".as_ref(),
+            dir.as_os_str(),
+            "--feedback-dir".as_ref(),
+            dir.join("feedback").as_os_str(),
+        ]);
+        init_logging(&config.app);
+        set_redact_transcripts(true);
+
+        let mut draft = DictationBuffer::default();
+        let mut statuses = vec![draft.apply("zanzibar quokka").status_message()];
+        statuses.push(draft.apply("replace quokka with wombat").status_message());
+        statuses.push(draft.apply("replace platypus with wombat").status_message());
+        let mut feedback = TranscriptFeedback::from_config(&config).unwrap();
+        feedback.note_delivered("zanzibar quokka", None, Instant::now());
+        assert!(feedback.flag_last(Instant::now()).is_err());
+
+        let log = std::fs::read_to_string(log_file_path()).expect("debug log written");
+        init_logging(&OverlayConfig::parse_from(["voiceterm"]).app);
+        set_redact_transcripts(false);
+        assert!(log.contains("dictation edit|Replaced '<redacted chars=6"));
+        for word in ["zanzibar", "quokka", "wombat", "platypus"] {
+            assert!(!log.contains(word), "{word} leaked into the log:\n{log}");
+            for status in &statuses {
+                assert!(!status.contains(word), "{word} leaked into {status:?}");
+            }
+        }
+        assert!(!dir.join("feedback").join("flagged.jsonl").exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,189 @@
+//! Prompt replay scheduling so recorded transcripts can be re-sent to a fresh backend.
+//!
+//! `voiceterm replay-prompts FILE` loads prompts from a transcript history file and
+//! injects the selected entries one at a time, waiting for the backend to be ready
+//! and for the configured pacing delay between prompts.
+
+use anyhow::{anyhow, bail, Result};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::config::ReplayPromptsArgs;
+
+use super::history::load_history_prompts;
+
+/// A prompt ready to be injected, with its position in the replay.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ReplayPrompt {
+    /// 1-based position within the selected prompts.
+    pub(crate) position: usize,
+    /// Number of selected prompts.
+    pub(crate) total: usize,
+    pub(crate) text: String,
+}
+
+/// Paced queue of prompts replayed into the backend session.
+#[derive(Debug)]
+pub(crate) struct PromptReplay {
+    prompts: VecDeque<String>,
+    total: usize,
+    pace: Duration,
+    next_due: Instant,
+}
+
+impl PromptReplay {
+    pub(crate) fn new(prompts: Vec<String>, pace: Duration, now: Instant) -> Self {
+        Self {
+            total: prompts.len(),
+            prompts: prompts.into(),
+            pace,
+            // Give the backend one pacing interval to boot before the first prompt.
+            next_due: now + pace,
+        }
+    }
+
+    /// Load and select prompts for the `replay-prompts` subcommand.
+    pub(crate) fn from_args(args: &ReplayPromptsArgs, now: Instant) -> Result<Self> {
+        let prompts = load_history_prompts(&args.file, args.decrypt_cmd.as_deref())?;
+        if prompts.is_empty() {
+            bail!("no prompts found in {}", args.file.display());
+        }
+        let selected = match args.select.as_deref() {
+            Some(spec) => select_prompts(&prompts, spec)?,
+            None => prompts,
+        };
+        Ok(Self::new(
+            selected,
+            Duration::from_millis(args.pace_ms),
+            now,
+        ))
+    }
+
+    pub(crate) fn remaining(&self) -> usize {
+        self.prompts.len()
+    }
+
+    pub(crate) fn is_finished(&self) -> bool {
+        self.prompts.is_empty()
+    }
+
+    /// Next prompt to inject, if pacing allows and the backend is ready for input.
+    pub(crate) fn poll(&mut self, now: Instant, backend_ready: bool) -> Option<ReplayPrompt> {
+        if !backend_ready || now < self.next_due {
+            return None;
+        }
+        let text = self.prompts.pop_front()?;
+        self.next_due = now + self.pace;
+        Some(ReplayPrompt {
+            position: self.total - self.prompts.len(),
+            total: self.total,
+            text,
+        })
+    }
+}
+
+/// Pick prompts by 1-based index list, e.g. `1,3-5`.
+fn select_prompts(prompts: &[String], spec: &str) -> Result<Vec<String>> {
+    let parse_index = |raw: &str| -> Result<usize> {
+        let index: usize = raw
+            .trim()
+            .parse()
+            .map_err(|_| anyhow!("invalid prompt index '{}' in --select", raw.trim()))?;
+        if index == 0 || index > prompts.len() {
+            bail!(
+                "prompt index {index} is out of range (history has {} prompts)",
+                prompts.len()
+            );
+        }
+        Ok(index)
+    };
+    let mut selected = Vec::new();
+    for part in spec.split(',').filter(|part| !part.trim().is_empty()) {
+        let (start, end) = match part.split_once('-') {
+            Some((start, end)) => (parse_index(start)?, parse_index(end)?),
+            None => {
+                let index = parse_index(part)?;
+                (index, index)
+            }
+        };
+        if start > end {
+            bail!("invalid prompt range '{}' in --select", part.trim());
+        }
+        selected.extend(prompts[start - 1..end].iter().cloned());
+    }
+    if selected.is_empty() {
+        bail!("--select did not match any prompts");
+    }
+    Ok(selected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{OverlayCommand, OverlayConfig};
+    use clap::Parser;
+
+    fn prompts(count: usize) -> Vec<String> {
+        (1..=count).map(|i| format!("p{i}")).collect()
+    }
+
+    #[test]
+    fn select_prompts_accepts_indices_and_ranges() {
+        let selected = select_prompts(&prompts(6), "1, 3-4,6").unwrap();
+        assert_eq!(selected, vec!["p1", "p3", "p4", "p6"]);
+    }
+
+    #[test]
+    fn select_prompts_rejects_bad_specs() {
+        assert!(select_prompts(&prompts(3), "0").is_err());
+        assert!(select_prompts(&prompts(3), "4").is_err());
+        assert!(select_prompts(&prompts(3), "3-1").is_err());
+        assert!(select_prompts(&prompts(3), "two").is_err());
+        assert!(select_prompts(&prompts(3), ",").is_err());
+    }
+
+    #[test]
+    fn poll_waits_for_pacing_and_backend_readiness() {
+        let start = Instant::now();
+        let pace = Duration::from_millis(500);
+        let mut replay = PromptReplay::new(prompts(2), pace, start);
+
+        assert_eq!(
+            replay.poll(start, true),
+            None,
+            "first prompt waits one pace"
+        );
+        assert_eq!(replay.poll(start + pace, false), None, "backend busy");
+
+        let first = replay.poll(start + pace, true).expect("first prompt");
+        assert_eq!((first.position, first.total), (1, 2));
+        assert_eq!(first.text, "p1");
+        assert_eq!(replay.poll(start + pace, true), None);
+
+        let second = replay.poll(start + pace * 2, true).expect("second prompt");
+        assert_eq!(second.position, 2);
+        assert!(replay.is_finished());
+        assert_eq!(replay.poll(start + pace * 3, true), None);
+    }
+
+    #[test]
+    fn replay_prompts_subcommand_parses_after_overlay_flags() {
+        let config = OverlayConfig::parse_from([
+            "voiceterm",
+            "--claude",
+            "replay-prompts",
+            "history.jsonl",
+            "--select",
+            "2-3",
+            "--pace-ms",
+            "250",
+        ]);
+        assert!(config.claude);
+        let Some(OverlayCommand::ReplayPrompts(args)) = config.command else {
+            panic!("expected replay-prompts subcommand");
+        };
+        assert_eq!(args.file, std::path::PathBuf::from("history.jsonl"));
+        assert_eq!(args.select.as_deref(), Some("2-3"));
+        assert_eq!(args.pace_ms, 250);
+    }
+}
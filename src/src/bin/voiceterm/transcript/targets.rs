@@ -0,0 +1,308 @@
+//! Transcript destinations so dictation can switch between the wrapped backend and tmux panes.
+//!
+//! The wrapped backend is always the first target; each `--target` adds a tmux
+//! pane after it. The cycle-target hotkey moves delivery to the next one, and
+//! queued transcripts go wherever delivery points when they are flushed.
+//! Text typed into the wrapped backend is watched for its echo (see `echo`).
+//! The last transcript inserted without Enter can be erased again with the
+//! `undo_transcript` key, until the user types something else.
+
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use voiceterm::pty_session::PtyOverlaySession;
+
+use crate::config::OverlayConfig;
+
+use super::echo::{EchoCheck, EchoMiss};
+use super::pacing::type_paced;
+use super::session::TranscriptSession;
+use super::tmux::TmuxPane;
+
+/// Every place transcripts can go, plus which one currently receives them.
+pub(crate) struct TranscriptTargets {
+    backend_label: String,
+    panes: Vec<TmuxPane>,
+    /// 0 is the wrapped backend; `n` is `panes[n - 1]`.
+    active: usize,
+    /// Inter-key delay for the wrapped backend; `None` writes each transcript at once.
+    type_delay: Option<Duration>,
+    echo: EchoCheck,
+    last_insert: Option<LastInsert>,
+}
+
+/// A transcript still sitting on a target's input line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct LastInsert {
+    /// Index into the targets, as in `active`.
+    target: usize,
+    chars: usize,
+}
+
+impl TranscriptTargets {
+    /// Resolve every `--target`; delivery starts on the first pane when any are given.
+    pub(crate) fn from_config(config: &OverlayConfig, backend_label: &str) -> Result<Self> {
+        let panes = config
+            .target
+            .iter()
+            .map(|target| {
+                TmuxPane::from_target(target)
+                    .with_context(|| format!("invalid --target {target:?}"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self::new(backend_label, panes))
+    }
+
+    fn new(backend_label: &str, panes: Vec<TmuxPane>) -> Self {
+        let active = usize::from(!panes.is_empty());
+        Self {
+            backend_label: backend_label.to_string(),
+            panes,
+            active,
+            type_delay: None,
+            echo: EchoCheck::new(None),
+            last_insert: None,
+        }
+    }
+
+    /// Type transcripts into the wrapped backend one character at a time, `delay` apart.
+    pub(crate) fn with_type_delay(mut self, delay: Option<Duration>) -> Self {
+        self.type_delay = delay;
+        self
+    }
+
+    /// Expect transcripts typed into the wrapped backend to be echoed within `timeout`.
+    pub(crate) fn with_echo_check(mut self, timeout: Option<Duration>) -> Self {
+        self.echo = EchoCheck::new(timeout);
+        self
+    }
+
+    /// Feed wrapped-backend output to the echo check.
+    pub(crate) fn observe_output(&mut self, bytes: &[u8]) {
+        self.echo.observe(bytes);
+    }
+
+    /// The last transcript typed into the wrapped backend, once its echo is overdue.
+    pub(crate) fn poll_echo(&mut self, now: Instant) -> Option<EchoMiss> {
+        self.echo.poll(now)
+    }
+
+    /// Keys typed since the last insert make it no longer safe to erase.
+    pub(crate) fn forget_last_insert(&mut self) {
+        self.last_insert = None;
+    }
+
+    /// Erase the last inserted transcript from the target it went to.
+    ///
+    /// Returns the number of characters erased, or `None` when there is nothing to undo.
+    pub(crate) fn undo_last_insert(
+        &mut self,
+        pty: &mut PtyOverlaySession,
+    ) -> Result<Option<usize>> {
+        let Some(LastInsert { target, chars }) = self.last_insert.take() else {
+            return Ok(None);
+        };
+        match target {
+            0 => {
+                self.echo.cancel();
+                let erase = vec![pty.erase_byte(); chars];
+                pty.send_bytes(&erase)?;
+            }
+            n => self.panes[n - 1].erase(chars)?,
+        }
+        Ok(Some(chars))
+    }
+
+    /// Only the wrapped backend, as in a session without `--target`.
+    #[cfg(test)]
+    pub(crate) fn backend_only(backend_label: &str) -> Self {
+        Self::new(backend_label, Vec::new())
+    }
+
+    /// Whether there is more than one target to switch between.
+    pub(crate) fn is_switchable(&self) -> bool {
+        !self.panes.is_empty()
+    }
+
+    /// Whether transcripts currently go to a tmux pane rather than the wrapped backend.
+    pub(crate) fn pane_active(&self) -> bool {
+        self.active > 0
+    }
+
+    pub(crate) fn active_label(&self) -> &str {
+        match self.active {
+            0 => &self.backend_label,
+            n => self.panes[n - 1].label(),
+        }
+    }
+
+    /// Status-line label, shown only when there is a choice of target.
+    pub(crate) fn status_label(&self) -> Option<String> {
+        self.is_switchable()
+            .then(|| self.active_label().to_string())
+    }
+
+    /// Move delivery to the next target, wrapping back to the wrapped backend.
+    pub(crate) fn cycle(&mut self) -> &str {
+        self.active = (self.active + 1) % (self.panes.len() + 1);
+        self.active_label()
+    }
+
+    /// Session handle that sends to the active target.
+    pub(crate) fn route<'a>(&'a mut self, pty: &'a mut PtyOverlaySession) -> TranscriptRoute<'a> {
+        let pane = match self.active {
+            0 => None,
+            n => self.panes.get_mut(n - 1),
+        };
+        TranscriptRoute {
+            pty,
+            pane,
+            target: self.active,
+            type_delay: self.type_delay,
+            echo: &mut self.echo,
+            last_insert: &mut self.last_insert,
+        }
+    }
+
+    /// Resolved pane ids, for the startup log.
+    pub(crate) fn pane_ids(&self) -> impl Iterator<Item = &str> {
+        self.panes.iter().map(TmuxPane::pane_id)
+    }
+}
+
+/// Where delivered transcripts go: the active tmux pane when one is selected, else the wrapped backend.
+pub(crate) struct TranscriptRoute<'a> {
+    pty: &'a mut PtyOverlaySession,
+    pane: Option<&'a mut TmuxPane>,
+    target: usize,
+    type_delay: Option<Duration>,
+    echo: &'a mut EchoCheck,
+    last_insert: &'a mut Option<LastInsert>,
+}
+
+impl TranscriptRoute<'_> {
+    fn send_to_backend(&mut self, text: &str, newline: bool) -> Result<()> {
+        match (self.type_delay, newline) {
+            (Some(delay), _) => type_paced(self.pty, text, delay, newline)?,
+            (None, false) => TranscriptSession::send_text(self.pty, text)?,
+            (None, true) => TranscriptSession::send_text_with_newline(self.pty, text)?,
+        }
+        self.echo.expect(text, newline, Instant::now());
+        Ok(())
+    }
+}
+
+impl TranscriptSession for TranscriptRoute<'_> {
+    fn send_text(&mut self, text: &str) -> Result<()> {
+        *self.last_insert = None;
+        match self.pane.as_deref_mut() {
+            Some(pane) => pane.send_text(text)?,
+            None => self.send_to_backend(text, false)?,
+        }
+        // Pastes and text with control keys may not map to one backspace per character.
+        if !text.is_empty() && !text.chars().any(char::is_control) {
+            *self.last_insert = Some(LastInsert {
+                target: self.target,
+                chars: text.chars().count(),
+            });
+        }
+        Ok(())
+    }
+
+    fn send_text_with_newline(&mut self, text: &str) -> Result<()> {
+        *self.last_insert = None;
+        match self.pane.as_deref_mut() {
+            Some(pane) => pane.send_text_with_newline(text),
+            None => self.send_to_backend(text, true),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn backend_only_targets_are_not_switchable() {
+        let mut targets = TranscriptTargets::backend_only("codex");
+        assert!(!targets.is_switchable());
+        assert!(!targets.pane_active());
+        assert_eq!(targets.status_label(), None);
+        assert_eq!(targets.cycle(), "codex");
+        assert!(!targets.pane_active());
+    }
+
+    #[test]
+    fn delivery_starts_on_the_first_pane_and_cycles_through_the_backend() {
+        let mut targets = TranscriptTargets::new(
+            "codex",
+            vec![
+                TmuxPane::resolved("tmux:%3", "%3"),
+                TmuxPane::resolved("tmux:work:1.0", "%7"),
+            ],
+        );
+        assert!(targets.is_switchable());
+        assert!(targets.pane_active());
+        assert_eq!(targets.status_label().as_deref(), Some("tmux:%3"));
+        assert_eq!(targets.cycle(), "tmux:work:1.0");
+        assert_eq!(targets.cycle(), "codex");
+        assert!(!targets.pane_active());
+        assert_eq!(targets.status_label().as_deref(), Some("codex"));
+        assert_eq!(targets.cycle(), "tmux:%3");
+        assert_eq!(targets.pane_ids().collect::<Vec<_>>(), ["%3", "%7"]);
+    }
+
+    #[test]
+    fn route_uses_the_pty_when_the_backend_is_active() {
+        let mut targets = TranscriptTargets::backend_only("cat")
+            .with_echo_check(Some(Duration::from_millis(500)));
+        let mut pty =
+            PtyOverlaySession::new("cat", ".", &[], "xterm-256color").expect("pty session");
+        targets
+            .route(&mut pty)
+            .send_text_with_newline("ping")
+            .expect("send to pty");
+        let deadline = Instant::now() + Duration::from_secs(2);
+        let mut seen = String::new();
+        while Instant::now() < deadline && !seen.contains("ping") {
+            if let Ok(chunk) = pty.output_rx.recv_timeout(Duration::from_millis(50)) {
+                targets.observe_output(&chunk);
+                seen.push_str(&String::from_utf8_lossy(&chunk));
+            }
+        }
+        assert!(seen.contains("ping"), "{seen:?}");
+        // The tty echo satisfies the echo check.
+        assert_eq!(targets.poll_echo(deadline + Duration::from_secs(1)), None);
+    }
+
+    #[test]
+    fn undo_erases_the_last_insert_from_the_input_line() {
+        let mut targets = TranscriptTargets::backend_only("cat");
+        let mut pty =
+            PtyOverlaySession::new("cat", ".", &[], "xterm-256color").expect("pty session");
+        targets.route(&mut pty).send_text("hello").expect("send");
+        assert_eq!(targets.undo_last_insert(&mut pty).unwrap(), Some(5));
+        assert_eq!(targets.undo_last_insert(&mut pty).unwrap(), None);
+        targets
+            .route(&mut pty)
+            .send_text_with_newline("ok")
+            .expect("send");
+        // `cat` prints the line it read after the tty echo; it holds only "ok".
+        let deadline = Instant::now() + Duration::from_millis(500);
+        let mut seen = String::new();
+        while Instant::now() < deadline && !seen.contains("ok\r\nok") {
+            if let Ok(chunk) = pty.output_rx.recv_timeout(Duration::from_millis(50)) {
+                seen.push_str(&String::from_utf8_lossy(&chunk));
+            }
+        }
+        assert!(seen.contains("ok\r\nok"), "{seen:?}");
+        assert!(!seen.contains("hellook"), "{seen:?}");
+
+        targets.route(&mut pty).send_text("a\tb").expect("send");
+        assert_eq!(targets.undo_last_insert(&mut pty).unwrap(), None);
+        targets.route(&mut pty).send_text("typed").expect("send");
+        targets.forget_last_insert();
+        assert_eq!(targets.undo_last_insert(&mut pty).unwrap(), None);
+    }
+}
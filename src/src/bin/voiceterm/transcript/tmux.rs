@@ -0,0 +1,141 @@
+//! tmux pane destination for `--target tmux:<pane>`, so an overlay in one pane can dictate into another.
+//!
+//! Text is typed with `tmux send-keys -l` (literal, so words like `Enter` are
+//! not read as key names) and auto-send mode follows it with a separate `Enter`
+//! key. Prompt detection still watches the wrapped backend, not the pane.
+
+use anyhow::{bail, Context, Result};
+use std::io::ErrorKind;
+use std::process::Command;
+
+use super::session::TranscriptSession;
+
+const TMUX_TARGET_PREFIX: &str = "tmux:";
+
+/// A tmux pane that receives transcripts instead of the wrapped backend.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TmuxPane {
+    /// The `--target` value as given, shown in the status line.
+    label: String,
+    pane_id: String,
+}
+
+impl TmuxPane {
+    /// Resolve one `--target` value to a pane.
+    ///
+    /// The pane is looked up once so a typo fails at startup and window renames
+    /// do not matter later.
+    pub(crate) fn from_target(target: &str) -> Result<Self> {
+        let pane = parse_target(target)?;
+        let output = run_tmux(&["display-message", "-p", "-t", pane, "#{pane_id}"])?;
+        let pane_id = output.trim();
+        if pane_id.is_empty() {
+            bail!("tmux pane {pane} not found");
+        }
+        Ok(Self {
+            label: target.trim().to_string(),
+            pane_id: pane_id.to_string(),
+        })
+    }
+
+    pub(crate) fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub(crate) fn pane_id(&self) -> &str {
+        &self.pane_id
+    }
+
+    #[cfg(test)]
+    pub(super) fn resolved(label: &str, pane_id: &str) -> Self {
+        Self {
+            label: label.to_string(),
+            pane_id: pane_id.to_string(),
+        }
+    }
+
+    /// Erase the last `chars` characters typed into the pane.
+    pub(crate) fn erase(&self, chars: usize) -> Result<()> {
+        run_tmux(&erase_args(&self.pane_id, chars)).map(|_| ())
+    }
+
+    fn type_text(&self, text: &str) -> Result<()> {
+        if text.is_empty() {
+            return Ok(());
+        }
+        run_tmux(&send_text_args(&self.pane_id, text)).map(|_| ())
+    }
+}
+
+impl TranscriptSession for TmuxPane {
+    fn send_text(&mut self, text: &str) -> Result<()> {
+        self.type_text(text)
+    }
+
+    fn send_text_with_newline(&mut self, text: &str) -> Result<()> {
+        self.type_text(text)?;
+        run_tmux(&["send-keys", "-t", &self.pane_id, "Enter"]).map(|_| ())
+    }
+}
+
+fn parse_target(value: &str) -> Result<&str> {
+    match value.strip_prefix(TMUX_TARGET_PREFIX).map(str::trim) {
+        Some(pane) if !pane.is_empty() => Ok(pane),
+        _ => bail!("unsupported --target {value:?} (expected tmux:<pane>, e.g. tmux:%3)"),
+    }
+}
+
+fn send_text_args<'a>(pane_id: &'a str, text: &'a str) -> [&'a str; 6] {
+    // `--` keeps text that starts with `-` from being read as a flag.
+    ["send-keys", "-t", pane_id, "-l", "--", text]
+}
+
+fn erase_args(pane_id: &str, chars: usize) -> Vec<&str> {
+    let mut args = vec!["send-keys", "-t", pane_id];
+    args.extend(std::iter::repeat_n("BSpace", chars));
+    args
+}
+
+fn run_tmux(args: &[&str]) -> Result<String> {
+    let output = match Command::new("tmux").args(args).output() {
+        Ok(output) => output,
+        Err(err) if err.kind() == ErrorKind::NotFound => bail!("tmux not found on PATH"),
+        Err(err) => return Err(err).context("failed to run tmux"),
+    };
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("tmux {} failed: {}", args[0], stderr.trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_target_accepts_tmux_panes_only() {
+        assert_eq!(parse_target("tmux:%3").unwrap(), "%3");
+        assert_eq!(parse_target("tmux: work:1.2 ").unwrap(), "work:1.2");
+        for bad in ["tmux:", "tmux:  ", "%3", "screen:1"] {
+            let err = parse_target(bad).expect_err(bad);
+            assert!(err.to_string().contains("expected tmux:<pane>"), "{err}");
+        }
+    }
+
+    #[test]
+    fn send_text_args_are_literal_and_end_options() {
+        assert_eq!(
+            send_text_args("%3", "-rf Enter"),
+            ["send-keys", "-t", "%3", "-l", "--", "-rf Enter"]
+        );
+    }
+
+    #[test]
+    fn erase_args_press_backspace_once_per_character() {
+        assert_eq!(
+            erase_args("%3", 2),
+            ["send-keys", "-t", "%3", "BSpace", "BSpace"]
+        );
+    }
+}
@@ -0,0 +1,173 @@
+//! `--translate-to` stage so speech in one language reaches the backend in another.
+//!
+//! Translation runs through `--translate-cmd`, a command that reads the
+//! transcript on stdin and prints the translation, so either a local model
+//! (argos-translate, a llama.cpp script) or an API wrapper can do the work.
+//! `{to}` in the command is replaced by the target language. The original
+//! text is kept for the delivery status so the translation can be checked.
+
+use anyhow::{anyhow, bail, Context, Result};
+use std::io::{Read, Write};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::config::OverlayConfig;
+
+/// Longest a translation may take before the original text is sent instead.
+const TRANSLATE_TIMEOUT: Duration = Duration::from_secs(10);
+const TRANSLATE_POLL: Duration = Duration::from_millis(10);
+/// Characters of the original shown in the delivery status.
+const ORIGINAL_PREVIEW_CHARS: usize = 40;
+
+/// Configured translation command, or disabled.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TranscriptTranslator {
+    command: Vec<String>,
+    target: String,
+}
+
+impl TranscriptTranslator {
+    pub(crate) fn from_config(config: &OverlayConfig) -> Result<Self> {
+        let Some(target) = config.translate_to.as_deref().map(str::trim) else {
+            return Ok(Self::default());
+        };
+        if target.is_empty() {
+            bail!("--translate-to needs a language code such as en");
+        }
+        let template = config.translate_cmd.as_deref().unwrap_or_default();
+        let command: Vec<String> = shell_words::split(template.trim())
+            .with_context(|| format!("invalid --translate-cmd: {template}"))?
+            .into_iter()
+            .map(|arg| arg.replace("{to}", target))
+            .collect();
+        if command.is_empty() {
+            bail!("--translate-to needs --translate-cmd");
+        }
+        Ok(Self {
+            command,
+            target: target.to_string(),
+        })
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        !self.command.is_empty()
+    }
+
+    pub(crate) fn target(&self) -> &str {
+        &self.target
+    }
+
+    /// Translated `text`, trimmed; errors when the command fails, times out, or prints nothing.
+    pub(crate) fn translate(&self, text: &str) -> Result<String> {
+        let (program, args) = self
+            .command
+            .split_first()
+            .ok_or_else(|| anyhow!("translation is disabled"))?;
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .with_context(|| format!("failed to start {program}"))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(text.as_bytes())
+                .with_context(|| format!("failed to write to {program}"))?;
+        }
+        let mut stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| anyhow!("no stdout from {program}"))?;
+        let reader = thread::spawn(move || {
+            let mut out = String::new();
+            stdout.read_to_string(&mut out).map(|_| out)
+        });
+        let deadline = Instant::now() + TRANSLATE_TIMEOUT;
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break status;
+            }
+            if Instant::now() >= deadline {
+                let _ = child.kill();
+                let _ = child.wait();
+                bail!("{program} timed out after {}s", TRANSLATE_TIMEOUT.as_secs());
+            }
+            thread::sleep(TRANSLATE_POLL);
+        };
+        if !status.success() {
+            bail!("{program} exited with {status}");
+        }
+        let out = reader
+            .join()
+            .map_err(|_| anyhow!("reading {program} output panicked"))?
+            .with_context(|| format!("{program} printed invalid UTF-8"))?;
+        let translated = out.trim();
+        if translated.is_empty() {
+            bail!("{program} printed no translation");
+        }
+        Ok(translated.to_string())
+    }
+}
+
+/// Delivery-status note carrying the original text, e.g. `from "Führe die Tests aus"`.
+pub(crate) fn original_note(original: &str) -> String {
+    let original = original.trim();
+    if original.chars().count() <= ORIGINAL_PREVIEW_CHARS {
+        return format!("from \"{original}\"");
+    }
+    let preview: String = original.chars().take(ORIGINAL_PREVIEW_CHARS - 1).collect();
+    format!("from \"{}…\"", preview.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    fn translator(args: &[&str]) -> Result<TranscriptTranslator> {
+        let mut argv = vec!["voiceterm"];
+        argv.extend_from_slice(args);
+        TranscriptTranslator::from_config(&OverlayConfig::parse_from(argv))
+    }
+
+    #[test]
+    fn translate_to_requires_a_command() {
+        assert!(!translator(&[]).unwrap().is_enabled());
+        assert!(translator(&["--translate-to", "en", "--translate-cmd", " "]).is_err());
+        assert!(translator(&["--translate-to", " ", "--translate-cmd", "cat"]).is_err());
+        let sed = translator(&[
+            "--translate-to",
+            "en",
+            "--translate-cmd",
+            "sed -e s/Hallo/Hello/ -e s/$/.{to}/",
+        ])
+        .unwrap();
+        assert!(sed.is_enabled());
+        assert_eq!(sed.target(), "en");
+        assert_eq!(sed.translate("Hallo Welt\n").unwrap(), "Hello Welt.en");
+    }
+
+    #[test]
+    fn failing_or_silent_commands_are_errors() {
+        let failing = translator(&["--translate-to", "en", "--translate-cmd", "false"]).unwrap();
+        assert!(failing.translate("Hallo").is_err());
+        let silent = translator(&["--translate-to", "en", "--translate-cmd", "true"]).unwrap();
+        assert!(silent.translate("Hallo").is_err());
+    }
+
+    #[test]
+    fn original_note_truncates_long_text() {
+        assert_eq!(
+            original_note(" Führe die Tests aus "),
+            "from \"Führe die Tests aus\""
+        );
+        let note = original_note(&"wort ".repeat(20));
+        assert!(note.ends_with("…\""));
+        assert_eq!(
+            note.chars().count(),
+            "from \"\"".len() + ORIGINAL_PREVIEW_CHARS
+        );
+    }
+}
@@ -0,0 +1,174 @@
+//! Low-confidence word marking so shaky dictation is easy to fix before pressing Enter.
+//!
+//! With `--mark-low-confidence <0-1>`, insert-mode transcripts wrap each word
+//! Whisper scored below the threshold in `??`, e.g. `run ??clippy?? now`. Words
+//! are matched against the final (post-processed) text in order, so words that
+//! cleanup removed or rewrote are simply left unmarked.
+
+use anyhow::{bail, Result};
+use voiceterm::stt::WordConfidence;
+
+use crate::config::OverlayConfig;
+
+const UNCERTAIN_MARKER: &str = "??";
+
+/// Threshold-based marker for low-confidence words.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct UncertainWordMarker {
+    threshold: f32,
+}
+
+impl UncertainWordMarker {
+    pub(crate) fn from_config(config: &OverlayConfig) -> Result<Self> {
+        if !(0.0..=1.0).contains(&config.mark_low_confidence) {
+            bail!(
+                "--mark-low-confidence must be between 0.0 and 1.0, got {}",
+                config.mark_low_confidence
+            );
+        }
+        Ok(Self {
+            threshold: config.mark_low_confidence,
+        })
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.threshold > 0.0
+    }
+
+    /// Wrap low-confidence words found in `text`; returns the text and the number marked.
+    pub(crate) fn mark(&self, text: &str, words: &[WordConfidence]) -> (String, usize) {
+        if !self.is_enabled() {
+            return (text.to_string(), 0);
+        }
+        let mut spans = Vec::new();
+        let mut cursor = 0;
+        for word in words {
+            let needle = word.word.trim_matches(|ch: char| !ch.is_alphanumeric());
+            if needle.is_empty() {
+                continue;
+            }
+            // Every word advances the cursor so repeated words map in order.
+            let Some(start) = find_word(text, needle, cursor) else {
+                continue;
+            };
+            let end = start + needle.len();
+            if word.confidence < self.threshold {
+                spans.push((start, end));
+            }
+            cursor = end;
+        }
+        if spans.is_empty() {
+            return (text.to_string(), 0);
+        }
+        let mut marked =
+            String::with_capacity(text.len() + spans.len() * UNCERTAIN_MARKER.len() * 2);
+        let mut last = 0;
+        for &(start, end) in &spans {
+            marked.push_str(&text[last..start]);
+            marked.push_str(UNCERTAIN_MARKER);
+            marked.push_str(&text[start..end]);
+            marked.push_str(UNCERTAIN_MARKER);
+            last = end;
+        }
+        marked.push_str(&text[last..]);
+        (marked, spans.len())
+    }
+}
+
+/// Byte offset of the first whole-word, ASCII-case-insensitive match at or after `from`.
+fn find_word(text: &str, needle: &str, from: usize) -> Option<usize> {
+    let is_word_char = |ch: Option<char>| ch.is_some_and(char::is_alphanumeric);
+    (from..=text.len().checked_sub(needle.len())?)
+        .filter(|&start| {
+            text.is_char_boundary(start) && text.is_char_boundary(start + needle.len())
+        })
+        .find(|&start| {
+            let end = start + needle.len();
+            text[start..end].eq_ignore_ascii_case(needle)
+                && !is_word_char(text[..start].chars().next_back())
+                && !is_word_char(text[end..].chars().next())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn marker(threshold: f32) -> UncertainWordMarker {
+        UncertainWordMarker { threshold }
+    }
+
+    fn words(list: &[(&str, f32)]) -> Vec<WordConfidence> {
+        list.iter()
+            .map(|(word, confidence)| WordConfidence {
+                word: word.to_string(),
+                confidence: *confidence,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn from_config_validates_threshold() {
+        use clap::Parser;
+        let parse = |value: &str| {
+            UncertainWordMarker::from_config(&OverlayConfig::parse_from([
+                "voiceterm",
+                "--mark-low-confidence",
+                value,
+            ]))
+        };
+        assert!(parse("1.2").is_err());
+        assert!(parse("0.5").unwrap().is_enabled());
+        assert!(!parse("0").unwrap().is_enabled());
+    }
+
+    #[test]
+    fn mark_wraps_only_low_confidence_words() {
+        let (text, count) = marker(0.5).mark(
+            "run cargo clippy. now",
+            &words(&[
+                ("run", 0.9),
+                ("cargo", 0.95),
+                ("clippy.", 0.3),
+                ("now", 0.6),
+            ]),
+        );
+        assert_eq!(text, "run cargo ??clippy??. now");
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn mark_follows_postprocessed_text_in_order() {
+        // Filler stripping dropped "um" and sentence casing capitalized "Fix".
+        let (text, count) = marker(0.5).mark(
+            "Fix the test then fix the build",
+            &words(&[
+                ("um", 0.2),
+                ("fix", 0.9),
+                ("the", 0.9),
+                ("test", 0.9),
+                ("then", 0.9),
+                ("fix", 0.4),
+                ("the", 0.9),
+                ("build", 0.45),
+            ]),
+        );
+        assert_eq!(text, "Fix the test then ??fix?? the ??build??");
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn mark_requires_whole_word_matches() {
+        let (text, count) =
+            marker(0.5).mark("rebuild the app", &words(&[("build", 0.1), ("app", 0.2)]));
+        assert_eq!(text, "rebuild the ??app??");
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn disabled_marker_leaves_text_untouched() {
+        let (text, count) = marker(0.0).mark("hello", &words(&[("hello", 0.01)]));
+        assert_eq!(text, "hello");
+        assert_eq!(count, 0);
+    }
+}
@@ -0,0 +1,203 @@
+//! Urgent-keyword matching so risky voice commands stand out before they reach the backend.
+//!
+//! Keywords come from the `urgent` section of the user config file. A match
+//! renders the delivery status in the theme's warning color and, when
+//! `confirm` is set, holds the transcript until Enter confirms or Esc discards.
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::time::Duration;
+
+use super::delivery::{deliver_transcript, delivered_source, TranscriptIo};
+use super::queue::PendingTranscript;
+use super::secrets::secret_hold_status;
+use super::session::TranscriptSession;
+use crate::config::UrgentConfig;
+use crate::session_record::{record_session_event, send_mode_key, SessionEvent};
+
+/// Compiled urgent-keyword matcher.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct UrgentKeywords {
+    pattern: Option<Regex>,
+    keyword_count: usize,
+    confirm: bool,
+}
+
+impl UrgentKeywords {
+    pub(crate) fn from_config(config: &UrgentConfig) -> Result<Self> {
+        let alternatives: Vec<String> = config
+            .keywords
+            .iter()
+            .map(|keyword| keyword.split_whitespace().collect::<Vec<_>>())
+            .filter(|words| !words.is_empty())
+            .map(|words| {
+                words
+                    .into_iter()
+                    .map(regex::escape)
+                    .collect::<Vec<_>>()
+                    .join(r"\s+")
+            })
+            .collect();
+        if alternatives.is_empty() {
+            return Ok(Self::default());
+        }
+        let pattern = Regex::new(&format!(r"(?i)\b(?:{})\b", alternatives.join("|")))
+            .context("invalid urgent keyword list")?;
+        Ok(Self {
+            pattern: Some(pattern),
+            keyword_count: alternatives.len(),
+            confirm: config.confirm,
+        })
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.pattern.is_none()
+    }
+
+    pub(crate) fn keyword_count(&self) -> usize {
+        self.keyword_count
+    }
+
+    /// Whether matching transcripts must be confirmed before delivery.
+    pub(crate) fn requires_confirm(&self) -> bool {
+        self.confirm
+    }
+
+    /// First keyword found in `text`, lowercased with whitespace collapsed.
+    pub(crate) fn find(&self, text: &str) -> Option<String> {
+        let found = self.pattern.as_ref()?.find(text)?;
+        Some(
+            found
+                .as_str()
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+                .to_lowercase(),
+        )
+    }
+}
+
+/// Status shown while a transcript waits for confirmation.
+pub(crate) fn urgent_hold_status(keyword: &str) -> String {
+    format!("Urgent '{keyword}' held: Enter sends, Esc discards")
+}
+
+/// Why a transcript is waiting for Enter or Esc.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum HoldReason {
+    /// An urgent keyword with `confirm` set, as returned by [`UrgentKeywords::find`].
+    Urgent(String),
+    /// A `secrets` pattern matched; the label says what kind, never the text.
+    Secret(&'static str),
+}
+
+impl HoldReason {
+    pub(crate) fn status(&self) -> String {
+        match self {
+            HoldReason::Urgent(keyword) => urgent_hold_status(keyword),
+            HoldReason::Secret(label) => secret_hold_status(label),
+        }
+    }
+}
+
+/// A transcript held back from delivery until the user confirms it.
+pub(crate) struct HeldTranscript {
+    pub(crate) transcript: PendingTranscript,
+    pub(crate) reason: HoldReason,
+}
+
+/// Deliver or drop the held transcript; returns true when a newline was sent.
+///
+/// Held text is only recorded and reported once it is confirmed and sent.
+pub(crate) fn resolve_urgent_hold<S: TranscriptSession>(
+    hold: &mut Option<HeldTranscript>,
+    confirmed: bool,
+    io: &mut TranscriptIo<'_, S>,
+) -> bool {
+    let Some(HeldTranscript { transcript, reason }) = hold.take() else {
+        return false;
+    };
+    let (note, discarded) = match reason {
+        HoldReason::Urgent(_) => ("urgent confirmed", "Urgent transcript discarded"),
+        HoldReason::Secret(_) => ("secret confirmed", "Possible secret discarded"),
+    };
+    if confirmed {
+        if let Some(source) = delivered_source(transcript.label) {
+            record_session_event(|| SessionEvent::VoiceTranscript {
+                text: transcript.text.clone(),
+                source: source.to_string(),
+                mode: send_mode_key(transcript.mode).to_string(),
+            });
+        }
+        deliver_transcript(
+            &transcript.text,
+            transcript.label,
+            transcript.mode,
+            io,
+            0,
+            Some(note),
+        )
+    } else {
+        io.set_status(discarded, Some(Duration::from_secs(2)));
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::status_style::StatusType;
+
+    fn keywords(list: &[&str], confirm: bool) -> UrgentKeywords {
+        UrgentKeywords::from_config(&UrgentConfig {
+            keywords: list.iter().map(|keyword| keyword.to_string()).collect(),
+            confirm,
+        })
+        .expect("keywords compile")
+    }
+
+    #[test]
+    fn empty_config_never_matches() {
+        let urgent = keywords(&[], true);
+        assert!(urgent.is_empty());
+        assert!(!urgent.requires_confirm());
+        assert_eq!(urgent.find("stop everything"), None);
+        assert!(keywords(&["  "], false).is_empty());
+    }
+
+    #[test]
+    fn find_matches_whole_words_case_insensitively() {
+        let urgent = keywords(&["stop", "rm -rf"], false);
+        assert_eq!(urgent.keyword_count(), 2);
+        assert_eq!(urgent.find("please STOP now"), Some("stop".to_string()));
+        assert_eq!(urgent.find("unstoppable tests"), None);
+        assert_eq!(urgent.find("run rm -rf build"), Some("rm -rf".to_string()));
+    }
+
+    #[test]
+    fn find_matches_phrases_across_whitespace() {
+        let urgent = keywords(&["cancel the deploy"], true);
+        assert!(urgent.requires_confirm());
+        assert_eq!(
+            urgent.find("ok Cancel  the\tdeploy right away"),
+            Some("cancel the deploy".to_string())
+        );
+        assert_eq!(urgent.find("cancel the deployment"), None);
+    }
+
+    #[test]
+    fn urgent_statuses_render_as_warnings() {
+        assert_eq!(
+            StatusType::from_message(&urgent_hold_status("stop")),
+            StatusType::Warning
+        );
+        assert_eq!(
+            StatusType::from_message("Transcript ready (Rust, urgent 'stop')"),
+            StatusType::Warning
+        );
+        assert_eq!(
+            StatusType::from_message("Urgent transcript discarded"),
+            StatusType::Warning
+        );
+    }
+}
@@ -0,0 +1,157 @@
+//! Same-terminal guard so two overlays never fight over raw mode and input.
+//!
+//! At startup the overlay writes its pid to a lock file named after its
+//! terminal device (e.g. `voiceterm-tty-dev-pts-3.lock` next to the control
+//! socket). A second overlay on that terminal finds the first one alive and
+//! refuses to start; a lock left behind by a crashed overlay is taken over.
+
+use anyhow::{bail, Context, Result};
+use std::ffi::CStr;
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use voiceterm::log_debug;
+
+use crate::unix_socket::runtime_path;
+
+/// Lock held for the overlay's lifetime; the file is removed on drop.
+#[derive(Debug)]
+pub(crate) struct TtyLock {
+    path: PathBuf,
+}
+
+impl TtyLock {
+    /// Lock stdin's terminal, or `None` when stdin is not a terminal.
+    pub(crate) fn acquire() -> Result<Option<Self>> {
+        let Some(tty) = stdin_tty_name() else {
+            return Ok(None);
+        };
+        Self::acquire_at(lock_path_for(&tty), &tty, std::process::id()).map(Some)
+    }
+
+    fn acquire_at(path: PathBuf, tty: &str, pid: u32) -> Result<Self> {
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("failed to create lock directory {}", dir.display()))?;
+        }
+        // Two tries: the second follows removing a stale lock.
+        for _ in 0..2 {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(mut file) => {
+                    writeln!(file, "{pid}")
+                        .with_context(|| format!("failed to write {}", path.display()))?;
+                    return Ok(Self { path });
+                }
+                Err(err) if err.kind() == ErrorKind::AlreadyExists => match read_owner(&path) {
+                    Some(owner) if owner != pid && process_alive(owner) => bail!(
+                        "VoiceTerm is already running on {tty} (pid {owner}).\n\
+                             Switch back to it (if it is suspended, run `fg`) or quit it first; \
+                             pass --allow-same-tty to start a second overlay anyway."
+                    ),
+                    owner => {
+                        log_debug(&format!(
+                            "removing stale tty lock {} (pid {owner:?})",
+                            path.display()
+                        ));
+                        let _ = fs::remove_file(&path);
+                    }
+                },
+                Err(err) => {
+                    return Err(err)
+                        .with_context(|| format!("failed to create lock {}", path.display()))
+                }
+            }
+        }
+        bail!("could not take the terminal lock {}", path.display())
+    }
+}
+
+impl Drop for TtyLock {
+    fn drop(&mut self) {
+        // Only remove the lock if it is still ours.
+        if read_owner(&self.path) == Some(std::process::id()) {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+fn stdin_tty_name() -> Option<String> {
+    let mut buf = [0 as libc::c_char; 256];
+    // SAFETY: buf is writable for buf.len() bytes and ttyname_r NUL-terminates on success.
+    let status = unsafe { libc::ttyname_r(libc::STDIN_FILENO, buf.as_mut_ptr(), buf.len()) };
+    if status != 0 {
+        return None;
+    }
+    // SAFETY: ttyname_r succeeded, so buf holds a NUL-terminated string.
+    let name = unsafe { CStr::from_ptr(buf.as_ptr()) };
+    Some(name.to_string_lossy().into_owned())
+}
+
+/// `/dev/pts/3` becomes `voiceterm-tty-dev-pts-3.lock` in the runtime dir.
+fn lock_path_for(tty: &str) -> PathBuf {
+    let key: String = tty
+        .trim_start_matches('/')
+        .chars()
+        .map(|ch| if ch.is_ascii_alphanumeric() { ch } else { '-' })
+        .collect();
+    runtime_path(&format!("voiceterm-tty-{key}"), "lock")
+}
+
+fn read_owner(path: &Path) -> Option<u32> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+fn process_alive(pid: u32) -> bool {
+    let Ok(pid) = libc::pid_t::try_from(pid) else {
+        return false;
+    };
+    // SAFETY: signal 0 only checks that the process exists; nothing is delivered.
+    if unsafe { libc::kill(pid, 0) } == 0 {
+        return true;
+    }
+    // EPERM means it exists but belongs to someone else.
+    std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    fn temp_lock(name: &str) -> PathBuf {
+        env::temp_dir().join(format!(
+            "voiceterm-tty-lock-{name}-{}.lock",
+            std::process::id()
+        ))
+    }
+
+    #[test]
+    fn lock_name_is_keyed_by_tty_device() {
+        let name = lock_path_for("/dev/pts/3");
+        let name = name.file_name().unwrap().to_string_lossy();
+        assert!(name.starts_with("voiceterm-tty-dev-pts-3"), "{name}");
+        assert!(name.ends_with(".lock"), "{name}");
+    }
+
+    #[test]
+    fn live_owner_blocks_and_stale_owner_is_replaced() {
+        let path = temp_lock("owner");
+        let _ = fs::remove_file(&path);
+        // Our parent is alive and is not us.
+        // SAFETY: getppid has no preconditions and cannot fail.
+        let parent = unsafe { libc::getppid() } as u32;
+        fs::write(&path, format!("{parent}\n")).unwrap();
+        let err = TtyLock::acquire_at(path.clone(), "/dev/pts/9", std::process::id())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("already running on /dev/pts/9"), "{err}");
+        assert!(err.contains("--allow-same-tty"), "{err}");
+
+        fs::write(&path, "not a pid\n").unwrap();
+        let lock = TtyLock::acquire_at(path.clone(), "/dev/pts/9", std::process::id())
+            .expect("stale lock replaced");
+        assert_eq!(read_owner(&path), Some(std::process::id()));
+        drop(lock);
+        assert!(!path.exists(), "lock removed on drop");
+    }
+}
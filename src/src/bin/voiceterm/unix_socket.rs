@@ -0,0 +1,107 @@
+//! Owner-only Unix socket helpers shared by the daemon and the overlay control socket.
+
+use anyhow::{bail, Context, Result};
+use std::env;
+use std::fs;
+use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+
+/// `$XDG_RUNTIME_DIR/<stem>.sock`, or a per-user `<stem>-<uid>.sock` in the temp dir.
+pub(crate) fn runtime_socket_path(stem: &str) -> PathBuf {
+    runtime_path(stem, "sock")
+}
+
+/// `$XDG_RUNTIME_DIR/<stem>.<ext>`, or a per-user `<stem>-<uid>.<ext>` in the temp dir.
+pub(crate) fn runtime_path(stem: &str, ext: &str) -> PathBuf {
+    match env::var_os("XDG_RUNTIME_DIR").filter(|dir| !dir.is_empty()) {
+        Some(dir) => PathBuf::from(dir).join(format!("{stem}.{ext}")),
+        None => {
+            // SAFETY: getuid has no preconditions and cannot fail.
+            let uid = unsafe { libc::getuid() };
+            env::temp_dir().join(format!("{stem}-{uid}.{ext}"))
+        }
+    }
+}
+
+/// Bind the socket with owner-only permissions, replacing a stale socket file.
+pub(crate) fn bind_socket(path: &Path) -> Result<UnixListener> {
+    if let Ok(meta) = fs::symlink_metadata(path) {
+        if !meta.file_type().is_socket() {
+            bail!("{} exists and is not a socket", path.display());
+        }
+        if UnixStream::connect(path).is_ok() {
+            bail!(
+                "another voiceterm is already listening on {}",
+                path.display()
+            );
+        }
+        fs::remove_file(path)
+            .with_context(|| format!("failed to remove stale socket {}", path.display()))?;
+    }
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("failed to create socket directory {}", dir.display()))?;
+    }
+    // bind creates the socket file with the process umask, so narrow the umask
+    // around it; chmod-ing afterwards leaves a window where anyone could connect.
+    // SAFETY: umask only swaps the process file-mode mask and cannot fail.
+    let previous_umask = unsafe { libc::umask(0o177) };
+    let bound = UnixListener::bind(path);
+    // SAFETY: as above; restores the mask saved before the bind.
+    unsafe { libc::umask(previous_umask) };
+    let listener = bound.with_context(|| format!("failed to bind socket {}", path.display()))?;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o600))
+        .with_context(|| format!("failed to restrict permissions on {}", path.display()))?;
+    Ok(listener)
+}
+
+/// Removes the socket file when its owner exits normally.
+pub(crate) struct SocketCleanup(pub(crate) PathBuf);
+
+impl Drop for SocketCleanup {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_socket(name: &str) -> PathBuf {
+        env::temp_dir().join(format!(
+            "voiceterm-socket-{name}-{}.sock",
+            std::process::id()
+        ))
+    }
+
+    #[test]
+    fn runtime_socket_path_uses_stem() {
+        let path = runtime_socket_path("voiceterm-control");
+        let name = path.file_name().unwrap().to_string_lossy();
+        assert!(name.starts_with("voiceterm-control"), "{name}");
+        assert!(name.ends_with(".sock"), "{name}");
+    }
+
+    #[test]
+    fn bind_socket_replaces_stale_socket_and_refuses_live_or_regular_files() {
+        let path = temp_socket("bind");
+        let _ = fs::remove_file(&path);
+        let listener = bind_socket(&path).expect("bind fresh socket");
+        let mode = fs::metadata(&path).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode, 0o600);
+        let err = bind_socket(&path).expect_err("live socket is not replaced");
+        assert!(err.to_string().contains("already listening"));
+        drop(listener);
+        let _listener = bind_socket(&path).expect("stale socket is replaced");
+        let _ = fs::remove_file(&path);
+
+        let file = temp_socket("regular");
+        fs::write(&file, b"keep me").unwrap();
+        let err = bind_socket(&file).expect_err("regular file is not removed");
+        assert!(err.to_string().contains("not a socket"));
+        assert_eq!(fs::read(&file).unwrap(), b"keep me");
+        let _ = fs::remove_file(&file);
+    }
+}
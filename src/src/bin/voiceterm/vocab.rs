@@ -0,0 +1,152 @@
+//! Per-project vocabulary so Whisper spells project names and jargon the way the code does.
+//!
+//! A `.voiceterm-vocab` file (or the older `.voxterm-vocab`) in the working
+//! directory or any parent lists one term per line. The terms are added to
+//! Whisper's initial prompt after `--whisper-prompt`, which biases decoding
+//! toward those spellings, so "PtyOverlaySession" is no longer heard as
+//! "tea overlay session".
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use voiceterm::log_debug;
+
+/// File names looked for in each directory, current name first.
+const VOCAB_FILES: &[&str] = &[".voiceterm-vocab", ".voxterm-vocab"];
+/// Whisper only keeps the tail of a long prompt, so the term list is capped.
+const MAX_VOCAB_CHARS: usize = 600;
+
+/// Terms from the nearest vocabulary file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ProjectVocabulary {
+    path: PathBuf,
+    terms: Vec<String>,
+}
+
+impl ProjectVocabulary {
+    /// Load the vocabulary file nearest to `project_dir`; unreadable files are logged and skipped.
+    pub(crate) fn discover(project_dir: &Path) -> Option<Self> {
+        let path = project_dir
+            .ancestors()
+            .flat_map(|dir| VOCAB_FILES.iter().map(move |name| dir.join(name)))
+            .find(|path| path.is_file())?;
+        match fs::read_to_string(&path) {
+            Ok(contents) => Some(Self {
+                terms: parse_terms(&contents),
+                path,
+            }),
+            Err(err) => {
+                log_debug(&format!(
+                    "vocabulary file unreadable ({}): {err}",
+                    path.display()
+                ));
+                None
+            }
+        }
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub(crate) fn term_count(&self) -> usize {
+        self.terms.len()
+    }
+
+    /// `base` (the `--whisper-prompt`) followed by as many terms as fit under the cap.
+    pub(crate) fn whisper_prompt(&self, base: Option<&str>) -> Option<String> {
+        let mut terms = String::new();
+        for term in &self.terms {
+            if !terms.is_empty() && terms.len() + term.len() + 2 > MAX_VOCAB_CHARS {
+                log_debug(&format!(
+                    "vocabulary truncated to {MAX_VOCAB_CHARS} characters ({})",
+                    self.path.display()
+                ));
+                break;
+            }
+            if !terms.is_empty() {
+                terms.push_str(", ");
+            }
+            terms.push_str(term);
+        }
+        let base = base.map(str::trim).filter(|base| !base.is_empty());
+        match (base, terms.is_empty()) {
+            (None, true) => None,
+            (Some(base), true) => Some(base.to_string()),
+            (None, false) => Some(terms),
+            (Some(base), false) => Some(format!("{base} {terms}")),
+        }
+    }
+}
+
+/// One term per line; blank lines and `#` comments are skipped, duplicates dropped.
+fn parse_terms(contents: &str) -> Vec<String> {
+    let mut terms: Vec<String> = Vec::new();
+    for line in contents.lines() {
+        let term = line.trim();
+        if term.is_empty() || term.starts_with('#') || terms.iter().any(|seen| seen == term) {
+            continue;
+        }
+        terms.push(term.to_string());
+    }
+    terms
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn temp_dir() -> PathBuf {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock")
+            .as_nanos();
+        std::env::temp_dir().join(format!(
+            "voiceterm-vocab-{now}-{}",
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ))
+    }
+
+    #[test]
+    fn discover_finds_the_nearest_file_in_a_parent() {
+        let root = temp_dir();
+        let nested = root.join("crates").join("overlay");
+        fs::create_dir_all(&nested).expect("create dirs");
+        fs::write(
+            root.join(".voxterm-vocab"),
+            "# project terms\nPtyOverlaySession\n\nVoiceManager\nPtyOverlaySession\n",
+        )
+        .expect("write vocab");
+
+        let vocab = ProjectVocabulary::discover(&nested).expect("vocab found");
+        assert!(vocab.path().ends_with(".voxterm-vocab"));
+        assert_eq!(vocab.term_count(), 2);
+        assert_eq!(
+            vocab.whisper_prompt(Some("Rust code.")).as_deref(),
+            Some("Rust code. PtyOverlaySession, VoiceManager")
+        );
+
+        fs::write(nested.join(".voiceterm-vocab"), "whisper-rs\n").expect("write vocab");
+        let nearest = ProjectVocabulary::discover(&nested).expect("vocab found");
+        assert_eq!(nearest.whisper_prompt(None).as_deref(), Some("whisper-rs"));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn whisper_prompt_caps_long_term_lists() {
+        let vocab = ProjectVocabulary {
+            path: PathBuf::from(".voiceterm-vocab"),
+            terms: (0..200).map(|index| format!("Term{index:03}")).collect(),
+        };
+        let prompt = vocab.whisper_prompt(None).expect("prompt");
+        assert!(prompt.len() <= MAX_VOCAB_CHARS);
+        assert!(prompt.starts_with("Term000, Term001"));
+        assert_eq!(
+            ProjectVocabulary::default().whisper_prompt(Some("  ")),
+            None
+        );
+    }
+}
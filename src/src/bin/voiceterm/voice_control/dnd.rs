@@ -0,0 +1,190 @@
+//! Do-not-disturb for auto-voice so it stays off the mic during calls and at night.
+//!
+//! `--auto-voice-quiet-hours 22:00-08:00` suspends auto-voice for a daily
+//! local-time window (wrapping past midnight), and the `mute_auto_voice`
+//! hotkey suspends it for [`MUTE_DURATION`] from any moment. While either is
+//! active, auto-voice neither triggers on prompts nor re-arms after a capture;
+//! push-to-talk still works, and the HUD shows why the mic is quiet.
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// How long the mute hotkey suspends auto-voice.
+pub(crate) const MUTE_DURATION: Duration = Duration::from_secs(15 * 60);
+
+const MINUTES_PER_DAY: u16 = 24 * 60;
+
+/// Daily local-time window, `HH:MM-HH:MM`; the end is exclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct QuietHours {
+    start: u16,
+    end: u16,
+}
+
+impl QuietHours {
+    /// Whether `minute` (minutes past local midnight) falls inside the window.
+    pub(crate) fn contains(&self, minute: u16) -> bool {
+        if self.start <= self.end {
+            (self.start..self.end).contains(&minute)
+        } else {
+            minute >= self.start || minute < self.end
+        }
+    }
+}
+
+impl FromStr for QuietHours {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (start, end) = value
+            .split_once('-')
+            .ok_or_else(|| format!("expected HH:MM-HH:MM, got {value:?}"))?;
+        let (start, end) = (parse_clock(start)?, parse_clock(end)?);
+        if start == end {
+            return Err("quiet hours must start and end at different times".to_string());
+        }
+        Ok(Self { start, end })
+    }
+}
+
+impl fmt::Display for QuietHours {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", clock(self.start), clock(self.end))
+    }
+}
+
+fn parse_clock(value: &str) -> Result<u16, String> {
+    let invalid = || format!("invalid time {value:?} (use 24-hour HH:MM)");
+    let (hours, minutes) = value.trim().split_once(':').ok_or_else(invalid)?;
+    let hours: u16 = hours.parse().map_err(|_| invalid())?;
+    let minutes: u16 = minutes.parse().map_err(|_| invalid())?;
+    if hours > 23 || minutes > 59 {
+        return Err(invalid());
+    }
+    Ok(hours * 60 + minutes)
+}
+
+fn clock(minute: u16) -> String {
+    format!("{:02}:{:02}", minute / 60, minute % 60)
+}
+
+/// Minutes past local midnight, or `None` when the local time is unavailable.
+pub(crate) fn local_minute_of_day() -> Option<u16> {
+    // SAFETY: time(NULL) only reads the clock.
+    let now = unsafe { libc::time(std::ptr::null_mut()) };
+    // SAFETY: tm is plain data, and localtime_r writes only into the buffer it is given.
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+    if unsafe { libc::localtime_r(&now, &mut tm) }.is_null() {
+        return None;
+    }
+    u16::try_from(tm.tm_hour * 60 + tm.tm_min)
+        .ok()
+        .filter(|minute| *minute < MINUTES_PER_DAY)
+}
+
+/// Quiet hours plus the hotkey mute, and the HUD label of whichever is active.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct AutoVoiceDnd {
+    quiet_hours: Option<QuietHours>,
+    muted_until: Option<Instant>,
+    label: Option<String>,
+}
+
+impl AutoVoiceDnd {
+    pub(crate) fn new(quiet_hours: Option<QuietHours>) -> Self {
+        Self {
+            quiet_hours,
+            ..Self::default()
+        }
+    }
+
+    /// Start a [`MUTE_DURATION`] mute, or end the current one. Returns whether muted now.
+    pub(crate) fn toggle_mute(&mut self, now: Instant) -> bool {
+        let muted = self.muted_until.is_some_and(|until| now < until);
+        self.muted_until = if muted {
+            None
+        } else {
+            Some(now + MUTE_DURATION)
+        };
+        !muted
+    }
+
+    /// Recompute the active state; returns true when the label changed and the HUD needs a redraw.
+    pub(crate) fn refresh(&mut self, now: Instant, minute_of_day: Option<u16>) -> bool {
+        if self.muted_until.is_some_and(|until| now >= until) {
+            self.muted_until = None;
+        }
+        let label = if let Some(until) = self.muted_until {
+            let remaining = until.saturating_duration_since(now).as_secs();
+            Some(format!("muted {}m", remaining.div_ceil(60).max(1)))
+        } else {
+            self.quiet_hours
+                .filter(|quiet| minute_of_day.is_some_and(|minute| quiet.contains(minute)))
+                .map(|quiet| format!("quiet until {}", clock(quiet.end)))
+        };
+        if label == self.label {
+            return false;
+        }
+        self.label = label;
+        true
+    }
+
+    /// Whether auto-voice is suspended as of the last [`refresh`](Self::refresh).
+    pub(crate) fn is_active(&self) -> bool {
+        self.label.is_some()
+    }
+
+    pub(crate) fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quiet_hours_parse_and_wrap_past_midnight() {
+        let night: QuietHours = "22:00-08:00".parse().unwrap();
+        assert_eq!(night.to_string(), "22:00-08:00");
+        assert!(night.contains(23 * 60));
+        assert!(night.contains(0));
+        assert!(night.contains(7 * 60 + 59));
+        assert!(!night.contains(8 * 60));
+        assert!(!night.contains(12 * 60));
+
+        let lunch: QuietHours = "12:00-13:30".parse().unwrap();
+        assert!(lunch.contains(12 * 60 + 45));
+        assert!(!lunch.contains(13 * 60 + 30));
+        assert!(!lunch.contains(11 * 60));
+
+        for bad in ["22:00", "24:00-08:00", "22:00-08:60", "9-5", "08:00-08:00"] {
+            assert!(bad.parse::<QuietHours>().is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn mute_outranks_quiet_hours_and_expires() {
+        let now = Instant::now();
+        let mut dnd = AutoVoiceDnd::new(Some("22:00-08:00".parse().unwrap()));
+        let noon = Some(12 * 60);
+        assert!(!dnd.refresh(now, noon));
+        assert!(!dnd.is_active());
+
+        assert!(dnd.toggle_mute(now));
+        assert!(dnd.refresh(now, noon));
+        assert_eq!(dnd.label(), Some("muted 15m"));
+        assert!(dnd.refresh(now + Duration::from_secs(5 * 60 + 30), noon));
+        assert_eq!(dnd.label(), Some("muted 10m"));
+        assert!(dnd.refresh(now + MUTE_DURATION, Some(23 * 60)));
+        assert_eq!(dnd.label(), Some("quiet until 08:00"));
+        assert!(dnd.refresh(now + MUTE_DURATION, noon));
+        assert!(!dnd.is_active());
+
+        assert!(dnd.toggle_mute(now));
+        assert!(!dnd.toggle_mute(now + Duration::from_secs(60)));
+        dnd.refresh(now + Duration::from_secs(60), noon);
+        assert!(!dnd.is_active());
+    }
+}
@@ -1,23 +1,39 @@
 //! Voice-job drain logic so capture results integrate safely with transcript queues.
 
 use crossbeam_channel::Sender;
+use serde_json::json;
 use std::collections::VecDeque;
 use std::time::{Duration, Instant};
-use voiceterm::{log_debug, VoiceCaptureSource, VoiceCaptureTrigger, VoiceJobMessage};
+use voiceterm::audio::{MIC_MUTED_STATUS, SPEAKER_REJECTED_STATUS, UTTERANCE_REJECTED_STATUS};
+use voiceterm::config::LogLevel;
+use voiceterm::{
+    log_debug, log_debug_throttled, log_event, log_info, VoiceCaptureSource, VoiceCaptureTrigger,
+    VoiceJobMessage,
+};
 
 use crate::config::{OverlayConfig, VoiceSendMode};
+use crate::overlays::OverlayMode;
 use crate::prompt::PromptTracker;
+use crate::session_events::{emit_session_event, VoiceSessionEvent};
+use crate::session_record::{record_session_event, send_mode_key, source_key, SessionEvent};
 use crate::session_stats::SessionStats;
 use crate::status_line::{Pipeline, RecordingState, StatusLineState};
 use crate::transcript::{
-    deliver_transcript, push_pending_transcript, send_transcript, transcript_ready,
-    try_flush_pending, PendingTranscript, TranscriptIo, TranscriptSession,
+    approval_answer, deliver_transcript, format_code_dictation, loggable, original_note,
+    push_pending_transcript, queue_full_status, redact_transcripts, redacted, resolve_dictation,
+    resolve_urgent_hold, send_transcript, transcript_ready, try_flush_pending, BatchWindow,
+    DictationBuffer, HeldTranscript, HoldReason, MultilineDelivery, PendingTranscript,
+    QueueOutcome, QueuePolicy, SecretsFilter, TranscriptFeedback, TranscriptIo,
+    TranscriptPostProcessor, TranscriptPrefix, TranscriptSession, TranscriptTranslator,
+    UncertainWordMarker, UrgentKeywords,
 };
 use crate::voice_macros::VoiceMacros;
 use crate::writer::{set_status, WriterMessage};
 
 use super::manager::{start_voice_capture, VoiceManager};
+use super::navigation::{NavigationCommand, NavigationContext, VoiceNavigation};
 use super::pipeline::pipeline_status_label;
+use super::redictate::{RedictateReason, Redictation, REDICTATE_STATUS};
 use super::{PREVIEW_CLEAR_MS, STATUS_TOAST_SECS, TRANSCRIPT_PREVIEW_MAX};
 
 fn apply_macro_mode(
@@ -29,7 +45,11 @@ fn apply_macro_mode(
     if !macros_enabled {
         return (text.to_string(), default_mode, None);
     }
-    let expanded = voice_macros.apply(text, default_mode);
+    let mut expanded = voice_macros.apply(text, default_mode);
+    if default_mode == VoiceSendMode::Clipboard {
+        // Clipboard mode is a destination, not a send style; macros must not type into the PTY.
+        expanded.mode = VoiceSendMode::Clipboard;
+    }
     let macro_note = expanded
         .matched_trigger
         .as_ref()
@@ -56,6 +76,7 @@ pub(crate) fn handle_voice_message(
         status_state,
         session_stats,
         auto_voice_enabled,
+        multiline,
     } = ctx;
     let auto_voice_enabled = *auto_voice_enabled;
     match message {
@@ -63,12 +84,16 @@ pub(crate) fn handle_voice_message(
             text,
             source,
             metrics,
+            ..
         } => {
             let duration_secs = metrics
                 .as_ref()
                 .map(|metrics| metrics.speech_ms as f32 / 1000.0)
                 .unwrap_or(0.0);
             session_stats.record_transcript(duration_secs);
+            if let Some(metrics) = metrics.as_ref() {
+                session_stats.voice_metrics.record_capture(metrics);
+            }
             status_state.recording_state = RecordingState::Idle;
             clear_capture_metrics(status_state);
             status_state.pipeline = match source {
@@ -93,7 +118,7 @@ pub(crate) fn handle_voice_message(
                 &status,
                 Some(Duration::from_secs(STATUS_TOAST_SECS)),
             );
-            if let Err(err) = send_transcript(*session, &text, config.voice_send_mode) {
+            if let Err(err) = send_transcript(*session, &text, config.voice_send_mode, multiline) {
                 log_debug(&format!("failed to send transcript: {err:#}"));
                 set_status(
                     writer_tx,
@@ -118,8 +143,65 @@ pub(crate) fn handle_voice_message(
                 .as_ref()
                 .filter(|metrics| metrics.frames_dropped > 0)
                 .map(|metrics| format!("dropped {} frames", metrics.frames_dropped));
-            if auto_voice_enabled {
-                log_debug(&format!("auto voice capture detected no speech ({label})"));
+            let mic_muted = metrics
+                .as_ref()
+                .is_some_and(|metrics| metrics.mic_appears_muted());
+            let speaker_rejected = metrics
+                .as_ref()
+                .is_some_and(|metrics| metrics.speaker_rejected);
+            if speaker_rejected {
+                // Shown in auto-voice too, so a nearby talker is not mistaken for a dead mic.
+                log_debug_throttled(
+                    "voice_speaker_rejected",
+                    &format!("voice capture did not match the voice print ({label})"),
+                );
+                set_status(
+                    writer_tx,
+                    status_clear_deadline,
+                    current_status,
+                    status_state,
+                    SPEAKER_REJECTED_STATUS,
+                    Some(Duration::from_secs(STATUS_TOAST_SECS)),
+                );
+            } else if metrics
+                .as_ref()
+                .is_some_and(|metrics| metrics.utterance_rejected)
+            {
+                log_debug_throttled(
+                    "voice_utterance_rejected",
+                    &format!("voice capture below utterance thresholds ({label})"),
+                );
+                // Auto-voice re-arms quietly; a manual capture says why nothing was typed.
+                if !auto_voice_enabled {
+                    set_status(
+                        writer_tx,
+                        status_clear_deadline,
+                        current_status,
+                        status_state,
+                        UTTERANCE_REJECTED_STATUS,
+                        Some(Duration::from_secs(STATUS_TOAST_SECS)),
+                    );
+                }
+            } else if mic_muted {
+                // Surface muting even in auto-voice so hands-free users are not left
+                // "listening" to a dead mic.
+                log_debug_throttled(
+                    "voice_mic_muted",
+                    &format!("voice capture got digital silence ({label})"),
+                );
+                set_status(
+                    writer_tx,
+                    status_clear_deadline,
+                    current_status,
+                    status_state,
+                    MIC_MUTED_STATUS,
+                    Some(Duration::from_secs(STATUS_TOAST_SECS)),
+                );
+            } else if auto_voice_enabled {
+                log_debug_throttled(
+                    "voice_no_speech",
+                    &format!("auto voice capture detected no speech ({label})"),
+                );
                 // Don't show redundant "Auto-voice enabled" - the mode indicator shows it
                 // Only show a note if frames were dropped
                 if let Some(note) = drop_note {
@@ -149,7 +231,7 @@ pub(crate) fn handle_voice_message(
                 );
             }
         }
-        VoiceJobMessage::Error(message) => {
+        VoiceJobMessage::Error(error) => {
             session_stats.record_error();
             status_state.recording_state = RecordingState::Idle;
             clear_capture_metrics(status_state);
@@ -158,10 +240,17 @@ pub(crate) fn handle_voice_message(
                 status_clear_deadline,
                 current_status,
                 status_state,
-                "Voice capture error (see log)",
+                &error.status_message(),
                 Some(Duration::from_secs(STATUS_TOAST_SECS)),
             );
-            log_debug(&format!("voice capture error: {message}"));
+            log_debug_throttled(
+                &format!("voice_error_{}", error.kind.label()),
+                &format!(
+                    "voice capture error|kind={}|{}",
+                    error.kind.label(),
+                    error.message
+                ),
+            );
         }
     }
 }
@@ -175,6 +264,7 @@ pub(crate) struct VoiceMessageContext<'a, S: TranscriptSession> {
     pub status_state: &'a mut StatusLineState,
     pub session_stats: &'a mut SessionStats,
     pub auto_voice_enabled: bool,
+    pub multiline: &'a MultilineDelivery,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -182,13 +272,28 @@ pub(crate) fn drain_voice_messages<S: TranscriptSession>(
     voice_manager: &mut VoiceManager,
     config: &OverlayConfig,
     voice_macros: &VoiceMacros,
+    postprocessor: &TranscriptPostProcessor,
+    feedback: &mut TranscriptFeedback,
+    uncertain_words: &UncertainWordMarker,
+    urgent: &UrgentKeywords,
+    secrets: &SecretsFilter,
+    translator: &TranscriptTranslator,
+    prefix: &TranscriptPrefix,
+    urgent_hold: &mut Option<HeldTranscript>,
+    dictation: Option<&mut DictationBuffer>,
+    batch_window: Option<&mut BatchWindow>,
+    redictation: &mut Redictation,
+    navigation: &VoiceNavigation,
+    overlay_mode: OverlayMode,
     session: &mut S,
     writer_tx: &Sender<WriterMessage>,
     status_clear_deadline: &mut Option<Instant>,
     current_status: &mut Option<String>,
     status_state: &mut StatusLineState,
     session_stats: &mut SessionStats,
+    multiline: &MultilineDelivery,
     pending_transcripts: &mut VecDeque<PendingTranscript>,
+    queue_policy: QueuePolicy,
     prompt_tracker: &mut PromptTracker,
     last_enter_at: &mut Option<Instant>,
     now: Instant,
@@ -204,25 +309,183 @@ pub(crate) fn drain_voice_messages<S: TranscriptSession>(
     let Some(message) = voice_manager.poll_message() else {
         return;
     };
+    let capture_seq = voice_manager.capture_seq();
     let rearm_auto = matches!(
         message,
         VoiceJobMessage::Empty { .. } | VoiceJobMessage::Error(_)
     );
+    match &message {
+        VoiceJobMessage::Empty { source, .. } => {
+            record_session_event(|| SessionEvent::VoiceEmpty {
+                source: source_key(*source).to_string(),
+            });
+        }
+        VoiceJobMessage::Error(error) => {
+            record_session_event(|| SessionEvent::VoiceError {
+                category: error.kind.label().to_string(),
+                message: error.message.clone(),
+            });
+        }
+        // Transcripts are recorded after macro expansion below.
+        VoiceJobMessage::Transcript { .. } => {}
+    }
+    if let Some(reason) = redictation.check(&message) {
+        match &reason {
+            RedictateReason::LowConfidence(confidence) => {
+                log_info(&format!("transcript discarded|confidence={confidence:.2}"));
+            }
+            RedictateReason::TranscriptionFailed => {
+                log_debug("transcription failed; re-dictation eligible");
+            }
+        }
+        let rearmed = redictation.try_rearm() && voice_manager.is_idle() && {
+            // A re-dictation keeps the send mode its trigger pinned.
+            voice_manager.set_next_send_mode(voice_manager.capture_send_mode());
+            match start_voice_capture(
+                voice_manager,
+                VoiceCaptureTrigger::Auto,
+                writer_tx,
+                status_clear_deadline,
+                current_status,
+                status_state,
+            ) {
+                Ok(()) => status_state.recording_state == RecordingState::Recording,
+                Err(err) => {
+                    log_debug(&format!("re-dictation capture failed: {err:#}"));
+                    false
+                }
+            }
+        };
+        if rearmed {
+            *recording_started_at = Some(now);
+            reset_capture_visuals(status_state, preview_clear_deadline, last_meter_update);
+        } else {
+            status_state.recording_state = RecordingState::Idle;
+            clear_capture_metrics(status_state);
+            *recording_started_at = None;
+        }
+        // Out of retries, a failure falls through to the regular error status.
+        if rearmed || matches!(reason, RedictateReason::LowConfidence(_)) {
+            set_status(
+                writer_tx,
+                status_clear_deadline,
+                current_status,
+                status_state,
+                REDICTATE_STATUS,
+                Some(Duration::from_secs(STATUS_TOAST_SECS)),
+            );
+            return;
+        }
+    } else {
+        redictation.reset();
+    }
     match message {
         VoiceJobMessage::Transcript {
             text,
             source,
             metrics,
+            words,
+            ..
         } => {
+            // Clean fillers/replacements first so macro triggers match the cleaned text.
+            let text = postprocessor.apply(&text);
+            let confirm_pending =
+                urgent_hold.is_some() || dictation.as_ref().is_some_and(|draft| !draft.is_empty());
+            let context = NavigationContext::current(overlay_mode, confirm_pending);
+            if let Some(command) = navigation.command(&text, context) {
+                log_debug(&format!(
+                    "voice navigation|{}",
+                    loggable(&format!("{command:?}"))
+                ));
+                status_state.recording_state = RecordingState::Idle;
+                clear_capture_metrics(status_state);
+                *recording_started_at = None;
+                match command {
+                    NavigationCommand::Key(key) => {
+                        if !navigation.send_key(key) {
+                            log_debug("voice navigation key dropped: input queue full");
+                        }
+                    }
+                    NavigationCommand::Confirm(confirmed) => {
+                        let mut io = TranscriptIo {
+                            session,
+                            writer_tx,
+                            status_clear_deadline,
+                            current_status,
+                            status_state,
+                            multiline,
+                        };
+                        // Urgent holds answer Enter/Esc before drafts, as they do for keys.
+                        let sent_newline = if urgent_hold.is_some() {
+                            resolve_urgent_hold(urgent_hold, confirmed, &mut io)
+                        } else if let Some(draft) = dictation {
+                            resolve_dictation(draft, confirmed, &mut io)
+                        } else {
+                            false
+                        };
+                        if sent_newline {
+                            *last_enter_at = Some(now);
+                        }
+                    }
+                }
+                // Listen for the next command without waiting for backend output.
+                if auto_voice_enabled {
+                    prompt_tracker.note_activity(now);
+                }
+                return;
+            }
+            // Spoken commands match before translation; macros and secrets see the translated text.
+            let (text, translation_note) = if translator.is_enabled() && !text.trim().is_empty() {
+                match translator.translate(&text) {
+                    Ok(translated) => {
+                        let note = if redact_transcripts() {
+                            format!("translated to {}", translator.target())
+                        } else {
+                            original_note(&text)
+                        };
+                        (translated, Some(note))
+                    }
+                    Err(err) => {
+                        log_debug(&format!("translation failed: {err:#}"));
+                        (text, Some("translation failed, sent original".to_string()))
+                    }
+                }
+            } else {
+                (text, None)
+            };
+            let text = feedback.apply(&text);
             let (text, transcript_mode, macro_note) = apply_macro_mode(
                 &text,
-                config.voice_send_mode,
+                voice_manager
+                    .capture_send_mode()
+                    .unwrap_or(config.voice_send_mode),
                 status_state.macros_enabled,
                 voice_macros,
             );
+            // Macro expansions are already final text, so only plain dictation is reformatted.
+            let text = if status_state.code_mode && macro_note.is_none() {
+                format_code_dictation(&text)
+            } else {
+                text
+            };
+            let audio_path = metrics
+                .as_ref()
+                .and_then(|metrics| metrics.audio_path.clone());
+            let correction = feedback.note_delivered(&text, audio_path, now);
+            if correction.as_ref().is_some_and(|saved| saved.hints_changed) {
+                voice_manager.set_whisper_prompt(feedback.whisper_prompt());
+            }
+            // Markers are for the user to fix before Enter, so only insert mode gets them.
+            let (text, uncertain_count) =
+                if transcript_mode == VoiceSendMode::Insert && uncertain_words.is_enabled() {
+                    uncertain_words.mark(&text, &words)
+                } else {
+                    (text, 0)
+                };
             update_last_latency(status_state, *recording_started_at, metrics.as_ref(), now);
-            let ready =
-                transcript_ready(prompt_tracker, *last_enter_at, now, transcript_idle_timeout);
+            // Delivery waits while the queue panel is open so rows stay put.
+            let ready = overlay_mode != OverlayMode::Queue
+                && transcript_ready(prompt_tracker, *last_enter_at, now, transcript_idle_timeout);
             if auto_voice_enabled {
                 prompt_tracker.note_activity(now);
             }
@@ -232,7 +495,11 @@ pub(crate) fn drain_voice_messages<S: TranscriptSession>(
                 VoiceCaptureSource::Native => Pipeline::Rust,
                 VoiceCaptureSource::Python => Pipeline::Python,
             };
-            let preview = format_transcript_preview(&text, TRANSCRIPT_PREVIEW_MAX);
+            let preview = if redact_transcripts() && !text.trim().is_empty() {
+                redacted(text.trim())
+            } else {
+                format_transcript_preview(&text, TRANSCRIPT_PREVIEW_MAX)
+            };
             if preview.is_empty() {
                 status_state.transcript_preview = None;
                 *preview_clear_deadline = None;
@@ -240,17 +507,44 @@ pub(crate) fn drain_voice_messages<S: TranscriptSession>(
                 status_state.transcript_preview = Some(preview);
                 *preview_clear_deadline = Some(now + Duration::from_millis(PREVIEW_CLEAR_MS));
             }
+            // Macros and spoken approval answers go out as-is; drafts are typed without a prefix.
+            let text = if macro_note.is_none()
+                && approval_answer(&text).is_none()
+                && !(transcript_mode == VoiceSendMode::Insert && dictation.is_some())
+            {
+                prefix.apply(text)
+            } else {
+                text
+            };
             let drop_note = metrics
                 .as_ref()
                 .filter(|metrics| metrics.frames_dropped > 0)
                 .map(|metrics| format!("dropped {} frames", metrics.frames_dropped));
-            let mut notes = Vec::with_capacity(2);
+            let urgent_keyword = urgent.find(&text);
+            let mut notes = Vec::with_capacity(6);
             if let Some(note) = drop_note {
                 notes.push(note);
             }
+            if let Some(note) = translation_note {
+                notes.push(note);
+            }
+            if uncertain_count > 0 {
+                notes.push(format!("{uncertain_count} unsure"));
+            }
             if let Some(note) = macro_note {
                 notes.push(note);
             }
+            if let Some(keyword) = &urgent_keyword {
+                notes.push(format!("urgent '{keyword}'"));
+            }
+            if let Some(saved) = correction {
+                notes.push(match saved.learned {
+                    Some((heard, fixed)) => {
+                        format!("learned '{}' -> '{}'", loggable(&heard), loggable(&fixed))
+                    }
+                    None => "correction saved".to_string(),
+                });
+            }
             let delivery_note = if notes.is_empty() {
                 None
             } else {
@@ -261,17 +555,87 @@ pub(crate) fn drain_voice_messages<S: TranscriptSession>(
                 .map(|metrics| metrics.speech_ms as f32 / 1000.0)
                 .unwrap_or(0.0);
             session_stats.record_transcript(duration_secs);
+            if let Some(metrics) = metrics.as_ref() {
+                session_stats.voice_metrics.record_capture(metrics);
+            }
             let queued_suffix = delivery_note
                 .as_ref()
                 .map(|note| format!(", {note}"))
                 .unwrap_or_default();
-            if ready && pending_transcripts.is_empty() {
+            let batch_window = batch_window.filter(|_| transcript_mode == VoiceSendMode::Auto);
+            // Clipboard copies do not touch the PTY, so they never wait for the prompt.
+            let immediate = transcript_mode == VoiceSendMode::Clipboard
+                || (batch_window.is_none() && ready && pending_transcripts.is_empty());
+            // A possible secret outranks an urgent keyword; clipboard copies never reach the backend.
+            let hold_reason = secrets
+                .find(&text)
+                .map(HoldReason::Secret)
+                .or_else(|| {
+                    urgent_keyword
+                        .filter(|_| urgent.requires_confirm())
+                        .map(HoldReason::Urgent)
+                })
+                .filter(|_| transcript_mode != VoiceSendMode::Clipboard);
+            // Held text is recorded only once confirmed, so a discarded secret never hits disk.
+            if hold_reason.is_none() {
+                record_session_event(|| SessionEvent::VoiceTranscript {
+                    text: text.clone(),
+                    source: source_key(source).to_string(),
+                    mode: send_mode_key(transcript_mode).to_string(),
+                });
+            }
+            let draft = dictation.filter(|_| transcript_mode == VoiceSendMode::Insert);
+            if let Some(draft) = draft {
+                let edit = draft.apply(&text);
+                emit_session_event(|| VoiceSessionEvent::Partial { text: draft.text() });
+                status_state.dictation = draft.preview();
+                set_status(
+                    writer_tx,
+                    status_clear_deadline,
+                    current_status,
+                    status_state,
+                    &edit.status_message(),
+                    Some(Duration::from_secs(STATUS_TOAST_SECS)),
+                );
+            } else if let Some(reason) = hold_reason {
+                if urgent_hold.is_some() {
+                    log_debug("replacing unconfirmed held transcript");
+                }
+                match &reason {
+                    HoldReason::Urgent(keyword) => {
+                        log_info(&format!("urgent transcript held|keyword={keyword}"))
+                    }
+                    HoldReason::Secret(kind) => {
+                        log_info(&format!("secret transcript held|kind={kind}"))
+                    }
+                }
+                let status = reason.status();
+                *urgent_hold = Some(HeldTranscript {
+                    transcript: PendingTranscript {
+                        text,
+                        label: source.label(),
+                        mode: transcript_mode,
+                        received_at: now,
+                        seq: Some(capture_seq),
+                    },
+                    reason,
+                });
+                set_status(
+                    writer_tx,
+                    status_clear_deadline,
+                    current_status,
+                    status_state,
+                    &status,
+                    None,
+                );
+            } else if immediate {
                 let mut io = TranscriptIo {
                     session,
                     writer_tx,
                     status_clear_deadline,
                     current_status,
                     status_state,
+                    multiline,
                 };
                 let sent_newline = deliver_transcript(
                     &text,
@@ -281,50 +645,65 @@ pub(crate) fn drain_voice_messages<S: TranscriptSession>(
                     0,
                     delivery_note.as_deref(),
                 );
+                session_stats
+                    .voice_metrics
+                    .record_delivery(Instant::now().saturating_duration_since(now));
                 if sent_newline {
                     *last_enter_at = Some(now);
                 }
             } else {
-                let dropped = push_pending_transcript(
+                let outcome = push_pending_transcript(
                     pending_transcripts,
                     PendingTranscript {
                         text,
-                        source,
+                        label: source.label(),
                         mode: transcript_mode,
+                        received_at: now,
+                        seq: Some(capture_seq),
                     },
+                    queue_policy,
                 );
                 status_state.queue_depth = pending_transcripts.len();
-                if dropped {
+                if let QueueOutcome::Dropped(dropped) = &outcome {
                     set_status(
                         writer_tx,
                         status_clear_deadline,
                         current_status,
                         status_state,
-                        "Transcript queue full (oldest dropped)",
+                        &queue_full_status(dropped),
                         Some(Duration::from_secs(2)),
                     );
                 }
-                if ready {
+                if let Some(batch) = batch_window {
+                    batch.extend(now);
+                } else if ready {
                     let mut io = TranscriptIo {
                         session,
                         writer_tx,
                         status_clear_deadline,
                         current_status,
                         status_state,
+                        multiline,
                     };
-                    try_flush_pending(
+                    if let Some(waited) = try_flush_pending(
                         pending_transcripts,
                         prompt_tracker,
                         last_enter_at,
                         &mut io,
                         now,
                         transcript_idle_timeout,
-                    );
-                } else if !dropped {
+                    ) {
+                        session_stats.voice_metrics.record_delivery(waited);
+                    }
+                } else if !matches!(outcome, QueueOutcome::Dropped(_)) {
+                    let merged = if matches!(outcome, QueueOutcome::Merged) {
+                        ", merged"
+                    } else {
+                        ""
+                    };
                     let status = format!(
-                        "Transcript queued ({}{})",
+                        "Transcript queued ({}{merged}{queued_suffix})",
                         pending_transcripts.len(),
-                        queued_suffix
                     );
                     set_status(
                         writer_tx,
@@ -337,8 +716,9 @@ pub(crate) fn drain_voice_messages<S: TranscriptSession>(
                 }
             }
             if auto_voice_enabled
-                && transcript_mode == VoiceSendMode::Insert
+                && transcript_mode != VoiceSendMode::Auto
                 && pending_transcripts.is_empty()
+                && urgent_hold.is_none()
                 && voice_manager.is_idle()
             {
                 if let Err(err) = start_voice_capture(
@@ -371,6 +751,7 @@ pub(crate) fn drain_voice_messages<S: TranscriptSession>(
                 status_state,
                 session_stats,
                 auto_voice_enabled,
+                multiline,
             };
             handle_voice_message(VoiceJobMessage::Empty { source, metrics }, &mut ctx);
         }
@@ -387,6 +768,7 @@ pub(crate) fn drain_voice_messages<S: TranscriptSession>(
                 status_state,
                 session_stats,
                 auto_voice_enabled,
+                multiline,
             };
             handle_voice_message(other, &mut ctx);
         }
@@ -439,18 +821,16 @@ fn update_last_latency(
         status_state.push_latency_sample(sample);
     }
 
-    let display_field = latency_ms
-        .map(|v| v.to_string())
-        .unwrap_or_else(|| "na".to_string());
-    let capture_field = capture_ms
-        .map(|v| v.to_string())
-        .unwrap_or_else(|| "na".to_string());
-    let stt_field = stt_ms
-        .map(|v| v.to_string())
-        .unwrap_or_else(|| "na".to_string());
-    log_debug(&format!(
-        "latency_audit|display_ms={display_field}|elapsed_ms={elapsed_ms}|capture_ms={capture_field}|stt_ms={stt_field}"
-    ));
+    log_event(
+        LogLevel::Info,
+        "latency_audit",
+        &[
+            ("display_ms", json!(latency_ms)),
+            ("elapsed_ms", json!(elapsed_ms)),
+            ("capture_ms", json!(capture_ms)),
+            ("stt_ms", json!(stt_ms)),
+        ],
+    );
 }
 
 fn format_transcript_preview(text: &str, max_len: usize) -> String {
@@ -495,7 +875,7 @@ pub(crate) fn reset_capture_visuals(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::VoiceSendMode;
+    use crate::config::{BracketedPasteMode, MultilineMode, TranscriptQueuePolicy, VoiceSendMode};
     use crate::transcript::TranscriptSession;
     use clap::Parser;
     use std::fs;
@@ -503,6 +883,7 @@ mod tests {
     use std::time::{SystemTime, UNIX_EPOCH};
     use voiceterm::audio::CaptureMetrics;
     use voiceterm::config::AppConfig;
+    use voiceterm::voice_error::{VoiceError, VoiceErrorKind};
 
     #[derive(Default)]
     struct StubSession {
@@ -557,6 +938,29 @@ macros:
         let _ = fs::remove_dir_all(&dir);
     }
 
+    #[test]
+    fn apply_macro_mode_keeps_clipboard_destination() {
+        let dir = write_test_macros_file(
+            r#"
+macros:
+  commit with message:
+    template: "git commit -m '{TRANSCRIPT}'"
+    mode: auto
+"#,
+        );
+        let voice_macros = VoiceMacros::load_for_project(&dir);
+        let (text, mode, note) = apply_macro_mode(
+            "commit with message fix typo",
+            VoiceSendMode::Clipboard,
+            true,
+            &voice_macros,
+        );
+        assert_eq!(text, "git commit -m 'fix typo'");
+        assert_eq!(mode, VoiceSendMode::Clipboard);
+        assert!(note.is_some());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn apply_macro_mode_skips_macros_when_disabled() {
         let dir = write_test_macros_file(
@@ -580,10 +984,20 @@ macros:
             app: AppConfig::parse_from(["test"]),
             prompt_regex: None,
             prompt_log: None,
+            prompt_detector_cmd: None,
             auto_voice: false,
             auto_voice_idle_ms: 1200,
+            auto_voice_quiet_hours: None,
             transcript_idle_ms: 250,
+            batch_window_ms: 0,
+            min_confidence: 0.0,
+            redictate_attempts: 1,
+            mark_low_confidence: 0.0,
             voice_send_mode: VoiceSendMode::Auto,
+            dictation_buffer: false,
+            voice_navigation: false,
+            code_mode: false,
+            multiline_mode: MultilineMode::Paste,
             theme_name: None,
             no_color: false,
             hud_right_panel: crate::config::HudRightPanel::Ribbon,
@@ -592,11 +1006,46 @@ macros:
             hud_style: crate::config::HudStyle::Full,
             latency_display: crate::config::LatencyDisplayMode::Short,
             minimal_hud: false,
+            no_scroll_region: false,
+            no_output_preview: false,
+            render_mode: crate::config::RenderMode::Auto,
+            allow_same_tty: false,
+            pipe: false,
+            once: false,
+            pipe_json: false,
             backend: "codex".to_string(),
             codex: false,
             claude: false,
             gemini: false,
             login: false,
+            config_path: None,
+            project_config: false,
+            preset: None,
+            turbo: false,
+            dump_preset: None,
+            migrate_config: false,
+            calibrate: false,
+            transcript_history: None,
+            transcript_history_encrypt_cmd: None,
+            redact_transcripts: false,
+            translate_to: None,
+            translate_cmd: None,
+            command: None,
+            session_record: None,
+            session_hook: None,
+            startup_profile: false,
+            target: Vec::new(),
+            feedback_dir: None,
+            type_delay_ms: None,
+            echo_check_ms: None,
+            transcript_queue_size: 5,
+            transcript_queue_policy: TranscriptQueuePolicy::DropOldest,
+            bracketed_paste: BracketedPasteMode::Multiline,
+            web_ui: false,
+            web_ui_port: None,
+            ws_listen: None,
+            control: false,
+            control_socket: None,
         };
         let mut session = StubSession::default();
         let (writer_tx, writer_rx) = crossbeam_channel::unbounded();
@@ -613,12 +1062,15 @@ macros:
             status_state: &mut status_state,
             session_stats: &mut session_stats,
             auto_voice_enabled: false,
+            multiline: &MultilineDelivery::default(),
         };
 
         handle_voice_message(
             VoiceJobMessage::Transcript {
                 text: " hello ".to_string(),
                 source: VoiceCaptureSource::Native,
+                confidence: None,
+                words: Vec::new(),
                 metrics: None,
             },
             &mut ctx,
@@ -636,6 +1088,141 @@ macros:
         assert_eq!(session.sent_with_newline, vec!["hello"]);
     }
 
+    #[test]
+    fn handle_voice_message_shows_error_category_in_status() {
+        let config = OverlayConfig::parse_from(["test"]);
+        let mut session = StubSession::default();
+        let (writer_tx, writer_rx) = crossbeam_channel::unbounded();
+        let mut deadline = None;
+        let mut current_status = None;
+        let mut status_state = StatusLineState::new();
+        let mut session_stats = SessionStats::new();
+        let mut ctx = VoiceMessageContext {
+            config: &config,
+            session: &mut session,
+            writer_tx: &writer_tx,
+            status_clear_deadline: &mut deadline,
+            current_status: &mut current_status,
+            status_state: &mut status_state,
+            session_stats: &mut session_stats,
+            auto_voice_enabled: false,
+            multiline: &MultilineDelivery::default(),
+        };
+
+        handle_voice_message(
+            VoiceJobMessage::Error(VoiceError::new(
+                VoiceErrorKind::MicBusy {
+                    owner: Some("zoom".to_string()),
+                },
+                "ALSA: Device or resource busy",
+            )),
+            &mut ctx,
+        );
+
+        let msg = writer_rx
+            .recv_timeout(Duration::from_millis(200))
+            .expect("status message");
+        match msg {
+            WriterMessage::EnhancedStatus(state) => {
+                assert_eq!(state.message, "Mic busy (in use by zoom)");
+            }
+            _ => panic!("unexpected writer message"),
+        }
+        assert!(session.sent_with_newline.is_empty());
+    }
+
+    #[test]
+    fn handle_voice_message_reports_muted_mic_in_auto_mode() {
+        let config = OverlayConfig::parse_from(["test"]);
+        let mut session = StubSession::default();
+        let (writer_tx, writer_rx) = crossbeam_channel::unbounded();
+        let mut deadline = None;
+        let mut current_status = None;
+        let mut status_state = StatusLineState::new();
+        let mut session_stats = SessionStats::new();
+        let mut ctx = VoiceMessageContext {
+            config: &config,
+            session: &mut session,
+            writer_tx: &writer_tx,
+            status_clear_deadline: &mut deadline,
+            current_status: &mut current_status,
+            status_state: &mut status_state,
+            session_stats: &mut session_stats,
+            auto_voice_enabled: true,
+            multiline: &MultilineDelivery::default(),
+        };
+
+        handle_voice_message(
+            VoiceJobMessage::Empty {
+                source: VoiceCaptureSource::Native,
+                metrics: Some(CaptureMetrics {
+                    capture_ms: 3000,
+                    digital_silence_ms: 3000,
+                    ..Default::default()
+                }),
+            },
+            &mut ctx,
+        );
+
+        let msg = writer_rx
+            .recv_timeout(Duration::from_millis(200))
+            .expect("status message");
+        match msg {
+            WriterMessage::EnhancedStatus(state) => {
+                assert_eq!(state.message, MIC_MUTED_STATUS);
+            }
+            _ => panic!("unexpected writer message"),
+        }
+    }
+
+    #[test]
+    fn handle_voice_message_explains_rejected_utterance_only_in_manual_mode() {
+        let config = OverlayConfig::parse_from(["test"]);
+        for auto_voice_enabled in [false, true] {
+            let mut session = StubSession::default();
+            let (writer_tx, writer_rx) = crossbeam_channel::unbounded();
+            let mut deadline = None;
+            let mut current_status = None;
+            let mut status_state = StatusLineState::new();
+            let mut session_stats = SessionStats::new();
+            let mut ctx = VoiceMessageContext {
+                config: &config,
+                session: &mut session,
+                writer_tx: &writer_tx,
+                status_clear_deadline: &mut deadline,
+                current_status: &mut current_status,
+                status_state: &mut status_state,
+                session_stats: &mut session_stats,
+                auto_voice_enabled,
+                multiline: &MultilineDelivery::default(),
+            };
+
+            handle_voice_message(
+                VoiceJobMessage::Empty {
+                    source: VoiceCaptureSource::Native,
+                    metrics: Some(CaptureMetrics {
+                        speech_ms: 120,
+                        utterance_rejected: true,
+                        ..Default::default()
+                    }),
+                },
+                &mut ctx,
+            );
+
+            let status = writer_rx.try_iter().find_map(|msg| match msg {
+                WriterMessage::EnhancedStatus(state) if !state.message.is_empty() => {
+                    Some(state.message)
+                }
+                _ => None,
+            });
+            if auto_voice_enabled {
+                assert_eq!(status, None);
+            } else {
+                assert_eq!(status.as_deref(), Some(UTTERANCE_REJECTED_STATUS));
+            }
+        }
+    }
+
     #[test]
     fn update_last_latency_prefers_stt_metrics_when_available() {
         let mut status_state = StatusLineState::new();
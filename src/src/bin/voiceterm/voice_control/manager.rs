@@ -2,13 +2,21 @@
 
 use anyhow::{anyhow, Result};
 use crossbeam_channel::Sender;
+use std::collections::BTreeMap;
 use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
 use std::time::{Duration, Instant};
 use voiceterm::{
-    audio, config::AppConfig, log_debug, stt, voice, VoiceCaptureSource, VoiceCaptureTrigger,
-    VoiceJobMessage,
+    audio,
+    config::{AppConfig, VadEngineKind},
+    log_debug, stt, voice,
+    voice_error::{VoiceError, VoiceErrorKind},
+    VoiceCaptureSource, VoiceCaptureTrigger, VoiceJobMessage,
 };
 
+use crate::config::{DeviceProfile, VoiceSendMode};
+use crate::session_events::{emit_session_event, VoiceSessionEvent};
+use crate::session_record::{send_mode_key, source_key};
 use crate::status_line::{Pipeline, RecordingState, StatusLineState};
 use crate::writer::{send_enhanced_status, set_status, WriterMessage};
 
@@ -22,26 +30,330 @@ struct VoiceStartInfo {
     fallback_note: Option<String>,
 }
 
+/// Status shown while the Whisper model loads in the background.
+pub(crate) const MODEL_WARMUP_STATUS: &str = "Warming up model…";
+
+/// Silence fed through a freshly loaded model by `--warm-up`; whisper.cpp wants at least a second.
+const WARM_UP_SILENCE_MS: u32 = 1_000;
+/// How long `--warm-up` keeps the microphone stream open.
+const WARM_UP_STREAM: Duration = Duration::from_millis(100);
+
+/// How often `watch_devices` rescans the input devices.
+const DEVICE_SCAN_INTERVAL: Duration = Duration::from_secs(2);
+
+type Timed<T> = (Result<T>, Duration);
+
+/// Model load and device probe started at launch so the first capture does not pay for them.
+///
+/// A model switch reuses this with no device probe.
+struct Preload {
+    model: JoinHandle<Timed<stt::Transcriber>>,
+    device: Option<JoinHandle<Timed<audio::Recorder>>>,
+    /// Model path in use before a switch, restored if the new model fails to load.
+    previous_path: Option<String>,
+}
+
+/// How long one preload phase took, whether or not it succeeded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct PreloadTiming {
+    pub(crate) phase: &'static str,
+    pub(crate) elapsed: Duration,
+}
+
+/// Input device switch or per-device profile change for the UI to pick up.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct DeviceChange {
+    /// Status-line notice when the recorder moved to another device.
+    pub(crate) notice: Option<String>,
+    /// A device profile changed the VAD threshold or silence tail.
+    pub(crate) levels_changed: bool,
+}
+
+/// Why the recorder should leave its current device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DeviceSwitch {
+    /// The device was unplugged or its stream failed.
+    Lost,
+    /// The preferred device (`--input-device`, or the system default) is available again.
+    Preferred,
+}
+
 pub(crate) struct VoiceManager {
     config: AppConfig,
     recorder: Option<Arc<Mutex<audio::Recorder>>>,
     transcriber: Option<Arc<Mutex<stt::Transcriber>>>,
+    preload: Option<Preload>,
+    preload_timings: Vec<PreloadTiming>,
     job: Option<voice::VoiceJob>,
+    /// Sequence number of the most recently started capture.
+    capture_seq: u64,
     cancel_pending: bool,
     active_source: Option<VoiceCaptureSource>,
+    /// Send mode requested by a pinned trigger for the next capture that starts.
+    next_send_mode: Option<VoiceSendMode>,
+    /// Send mode pinned for the latest capture's transcript, overriding `--voice-send-mode`.
+    capture_send_mode: Option<VoiceSendMode>,
     live_meter: audio::LiveMeter,
+    /// `--audio-cues` player, `None` when cues are off or the output device is missing.
+    cues: Option<audio::CuePlayer>,
+    /// Whether the current capture already played its stop cue.
+    stop_cued: bool,
+    /// When the last capture ended (or the manager started), for `--mic-release-secs`.
+    idle_since: Instant,
+    /// The recorder was dropped by `--mic-release-secs` and the next capture reopens it.
+    mic_released: bool,
+    /// A full queue under `--transcript-queue-policy block` holds new captures.
+    capture_blocked: bool,
+    /// `devices` levels from the config file, keyed by input device name.
+    device_profiles: BTreeMap<String, DeviceProfile>,
+    /// Threshold and silence tail for devices without a profile.
+    base_levels: (f32, u64),
+    /// Device whose profile levels are in effect.
+    profiled_device: Option<String>,
+    /// Device the open recorder records from.
+    active_device: Option<String>,
+    /// Background device rescans, started by `watch_devices`.
+    device_watcher: Option<audio::DeviceWatcher>,
+    /// Scan the last device check compared against.
+    seen_devices: Option<audio::InputDevices>,
+    /// Not yet reported by `poll_device_change`.
+    device_change: Option<DeviceChange>,
 }
 
 impl VoiceManager {
     pub(crate) fn new(config: AppConfig) -> Self {
+        let cues = config
+            .audio_cues
+            .then(|| {
+                audio::CuePlayer::new(config.output_device.as_deref(), config.audio_cue_volume)
+            })
+            .and_then(|player| {
+                player
+                    .map_err(|err| log_debug(&format!("audio cues disabled: {err:#}")))
+                    .ok()
+            });
+        let base_levels = (config.voice_vad_threshold_db, config.voice_silence_tail_ms);
         Self {
             config,
             recorder: None,
             transcriber: None,
+            preload: None,
+            preload_timings: Vec::new(),
             job: None,
+            capture_seq: 0,
             cancel_pending: false,
             active_source: None,
+            next_send_mode: None,
+            capture_send_mode: None,
             live_meter: audio::LiveMeter::new(),
+            cues,
+            stop_cued: false,
+            idle_since: Instant::now(),
+            mic_released: false,
+            capture_blocked: false,
+            device_profiles: BTreeMap::new(),
+            base_levels,
+            profiled_device: None,
+            active_device: None,
+            device_watcher: None,
+            seen_devices: None,
+            device_change: None,
+        }
+    }
+
+    /// Follow input devices as they are plugged in and out, applying `profiles` per device.
+    ///
+    /// Between captures the recorder moves off a device that disappears (to the
+    /// system default) and back to `--input-device` when it returns;
+    /// `poll_device_change` reports each switch.
+    pub(crate) fn watch_devices(&mut self, profiles: BTreeMap<String, DeviceProfile>) {
+        self.device_profiles = profiles;
+        if voiceterm::VOICE_CAPTURE_BUILT && self.device_watcher.is_none() {
+            self.device_watcher = Some(audio::DeviceWatcher::spawn(DEVICE_SCAN_INTERVAL));
+        }
+    }
+
+    /// Device switch or profile change since the last call.
+    ///
+    /// Nothing is switched while a capture or preload is running; the check
+    /// waits for the next call after it finishes.
+    pub(crate) fn poll_device_change(&mut self) -> Option<DeviceChange> {
+        if self.job.is_none() && self.preload.is_none() {
+            self.follow_devices();
+        }
+        self.device_change.take()
+    }
+
+    /// Load the Whisper model and probe the input device on background threads.
+    ///
+    /// With `--warm-up`, each thread also does one throwaway run: the model
+    /// transcribes a second of silence (first inference pays for buffer and GPU
+    /// kernel setup) and the device opens a short stream (driver start-up and the
+    /// OS microphone permission prompt). Returns false when no native model is
+    /// configured, since the Python fallback loads its own model per capture.
+    /// Failures are only logged; the first capture retries the load and reports
+    /// the error as before.
+    pub(crate) fn start_preload(&mut self) -> bool {
+        if !voiceterm::VOICE_CAPTURE_BUILT || self.preload.is_some() || self.transcriber.is_some() {
+            return false;
+        }
+        let Some(model_path) = self.config.whisper_model_path.clone() else {
+            return false;
+        };
+        let device_name = self.config.input_device.clone();
+        let stt_device = self.config.stt_device;
+        let warm_up = self.config.warm_up.then(|| self.config.clone());
+        let warm_up_stream = warm_up.is_some();
+        let keep_open_ms = self.keep_open_ms();
+        let model = thread::spawn(move || {
+            timed(|| {
+                let transcriber = stt::Transcriber::with_device(&model_path, stt_device)?;
+                if let Some(config) = warm_up {
+                    let silence =
+                        vec![0.0; (audio::TARGET_RATE * WARM_UP_SILENCE_MS / 1000) as usize];
+                    if let Err(err) = transcriber.transcribe(&silence, &config) {
+                        log_debug(&format!("model warm-up transcription failed: {err:#}"));
+                    }
+                }
+                Ok(transcriber)
+            })
+        });
+        let device = thread::spawn(move || {
+            timed(|| {
+                let recorder = open_recorder(device_name.as_deref(), keep_open_ms)?;
+                // A kept-open stream is already running, which is all the warm-up stream does.
+                if warm_up_stream && keep_open_ms.is_none() {
+                    if let Err(err) = recorder.record_for(WARM_UP_STREAM) {
+                        log_debug(&format!("microphone warm-up failed: {err:#}"));
+                    }
+                }
+                Ok(recorder)
+            })
+        });
+        self.preload = Some(Preload {
+            model,
+            device: Some(device),
+            previous_path: None,
+        });
+        log_debug(if warm_up_stream {
+            "voice preload started (model load + warm-up, device probe + stream open)"
+        } else {
+            "voice preload started (model load, device probe)"
+        });
+        true
+    }
+
+    /// Pre-roll to buffer between captures, when `--keep-mic-open` is set.
+    fn keep_open_ms(&self) -> Option<u64> {
+        self.config
+            .keep_mic_open
+            .then_some(self.config.voice_lookback_ms)
+    }
+
+    pub(crate) fn whisper_model_path(&self) -> Option<&str> {
+        self.config.whisper_model_path.as_deref()
+    }
+
+    /// Load a different Whisper model in the background for subsequent captures.
+    ///
+    /// The current model stays loaded and keeps serving captures until the new
+    /// one is ready, so a switch never stalls the UI; if the load fails, the
+    /// previous model stays active. The switch is refused while another load
+    /// is still running so at most two models are resident at once;
+    /// `poll_preload` reports when the new one is ready.
+    pub(crate) fn switch_whisper_model(&mut self, model_path: String) -> Result<()> {
+        if self.preload.is_some() {
+            return Err(anyhow!("model still loading"));
+        }
+        log_debug(&format!(
+            "whisper model switched: {} -> {model_path}",
+            self.config.whisper_model_path.as_deref().unwrap_or("none")
+        ));
+        let previous_path = self.config.whisper_model_path.replace(model_path.clone());
+        let stt_device = self.config.stt_device;
+        let model =
+            thread::spawn(move || timed(|| stt::Transcriber::with_device(&model_path, stt_device)));
+        self.preload = Some(Preload {
+            model,
+            device: None,
+            previous_path,
+        });
+        Ok(())
+    }
+
+    /// Whether a Whisper model is loaded and ready for the next capture.
+    pub(crate) fn model_loaded(&self) -> bool {
+        self.transcriber.is_some()
+    }
+
+    pub(crate) fn preload_pending(&self) -> bool {
+        self.preload.is_some()
+    }
+
+    /// Adopt the preload results once both threads are done; returns whether the new model loaded.
+    pub(crate) fn poll_preload(&mut self) -> Option<bool> {
+        let finished = self.preload.as_ref().is_some_and(|preload| {
+            preload.model.is_finished()
+                && preload
+                    .device
+                    .as_ref()
+                    .is_none_or(|device| device.is_finished())
+        });
+        if !finished {
+            return None;
+        }
+        Some(self.finish_preload())
+    }
+
+    /// Timings for the preload phases that have completed.
+    pub(crate) fn preload_timings(&self) -> &[PreloadTiming] {
+        &self.preload_timings
+    }
+
+    /// Wait for any preload still running and keep what it loaded; returns whether the model loaded.
+    fn finish_preload(&mut self) -> bool {
+        let Some(preload) = self.preload.take() else {
+            return false;
+        };
+        let loaded = match self.join_preload("model_load", preload.model) {
+            Some(transcriber) => {
+                self.transcriber = Some(Arc::new(Mutex::new(transcriber)));
+                true
+            }
+            None => {
+                if self.transcriber.is_some() {
+                    log_debug("whisper model switch failed; keeping the previous model");
+                    self.config.whisper_model_path = preload.previous_path;
+                }
+                false
+            }
+        };
+        if let Some(recorder) = preload
+            .device
+            .and_then(|device| self.join_preload("device_probe", device))
+        {
+            self.adopt_recorder(recorder);
+        }
+        loaded
+    }
+
+    fn join_preload<T>(&mut self, phase: &'static str, handle: JoinHandle<Timed<T>>) -> Option<T> {
+        let (result, elapsed) = handle
+            .join()
+            .unwrap_or_else(|_| (Err(anyhow!("{phase} thread panicked")), Duration::ZERO));
+        self.preload_timings.push(PreloadTiming { phase, elapsed });
+        match result {
+            Ok(value) => {
+                log_debug(&format!(
+                    "voice preload {phase} took {} ms",
+                    elapsed.as_millis()
+                ));
+                Some(value)
+            }
+            Err(err) => {
+                log_debug(&format!("voice preload {phase} failed: {err:#}"));
+                None
+            }
         }
     }
 
@@ -54,14 +366,106 @@ impl VoiceManager {
         next
     }
 
+    /// Nudge the trailing silence that ends a capture; applies from the next capture.
+    pub(crate) fn adjust_silence_tail(&mut self, delta_ms: i64) -> u64 {
+        const MIN_MS: u64 = 200;
+        const MAX_MS: u64 = 5000;
+        let max_ms = MAX_MS.min(self.config.voice_max_capture_ms).max(MIN_MS);
+        let next = self
+            .config
+            .voice_silence_tail_ms
+            .saturating_add_signed(delta_ms)
+            .clamp(MIN_MS, max_ms);
+        self.config.voice_silence_tail_ms = next;
+        next
+    }
+
+    pub(crate) fn vad_threshold_db(&self) -> f32 {
+        self.config.voice_vad_threshold_db
+    }
+
+    pub(crate) fn silence_tail_ms(&self) -> u64 {
+        self.config.voice_silence_tail_ms
+    }
+
+    pub(crate) fn vad_engine(&self) -> VadEngineKind {
+        self.config.voice_vad_engine
+    }
+
+    /// Select the VAD engine for subsequent captures.
+    ///
+    /// Each capture builds its engine on the worker thread from the config it
+    /// started with, so a capture in flight keeps its engine and the new one is
+    /// constructed off the UI thread when the next capture starts.
+    pub(crate) fn set_vad_engine(&mut self, engine: VadEngineKind) {
+        if self.config.voice_vad_engine != engine {
+            log_debug(&format!(
+                "vad engine switched: {} -> {}",
+                self.config.voice_vad_engine.label(),
+                engine.label()
+            ));
+        }
+        self.config.voice_vad_engine = engine;
+    }
+
+    /// Replace the Whisper initial prompt; captures already in flight keep theirs.
+    pub(crate) fn set_whisper_prompt(&mut self, prompt: Option<String>) {
+        self.config.whisper_prompt = prompt;
+    }
+
+    /// Drop the recorder once captures have been idle for `--mic-release-secs`.
+    ///
+    /// Closes a `--keep-mic-open` stream so other apps can take the microphone;
+    /// the next capture reopens it. Returns true when the microphone was released.
+    pub(crate) fn release_idle_mic(&mut self, now: Instant) -> bool {
+        if self.recorder.is_none()
+            || self.job.is_some()
+            || self.preload.is_some()
+            || !mic_release_due(self.config.mic_release_secs, self.idle_since, now)
+        {
+            return false;
+        }
+        self.recorder = None;
+        self.mic_released = true;
+        log_debug("microphone released while idle");
+        true
+    }
+
     pub(crate) fn is_idle(&self) -> bool {
         self.job.is_none()
     }
 
+    /// Sequence number of the latest capture, counting from 1 at each start.
+    ///
+    /// Only one capture runs at a time, so right after `poll_message` this
+    /// identifies the capture that produced the message.
+    pub(crate) fn capture_seq(&self) -> u64 {
+        self.capture_seq
+    }
+
     pub(crate) fn active_source(&self) -> Option<VoiceCaptureSource> {
         self.active_source
     }
 
+    /// Pin the send mode of the next capture that starts; a running capture is unaffected.
+    pub(crate) fn set_next_send_mode(&mut self, mode: Option<VoiceSendMode>) {
+        self.next_send_mode = mode;
+    }
+
+    /// Hold new captures until the transcript queue has room; a running capture is unaffected.
+    pub(crate) fn set_capture_blocked(&mut self, blocked: bool) {
+        self.capture_blocked = blocked;
+    }
+
+    pub(crate) fn capture_blocked(&self) -> bool {
+        self.capture_blocked
+    }
+
+    /// Send mode pinned for the latest capture, or `None` to use the overlay's mode.
+    pub(crate) fn capture_send_mode(&self) -> Option<VoiceSendMode> {
+        self.capture_send_mode
+    }
+
     pub(crate) fn meter(&self) -> audio::LiveMeter {
         self.live_meter.clone()
     }
@@ -71,6 +475,7 @@ impl VoiceManager {
         if let Some(ref job) = self.job {
             job.request_stop();
             self.cancel_pending = true;
+            self.cue_stop();
             log_debug("voice capture cancel requested");
             true
         } else {
@@ -83,6 +488,7 @@ impl VoiceManager {
     pub(crate) fn request_early_stop(&mut self) -> bool {
         if let Some(ref job) = self.job {
             job.request_stop();
+            self.cue_stop();
             log_debug("voice capture early stop requested");
             true
         } else {
@@ -91,10 +497,24 @@ impl VoiceManager {
     }
 
     fn start_capture(&mut self, trigger: VoiceCaptureTrigger) -> Result<Option<VoiceStartInfo>> {
+        let send_mode = self.next_send_mode.take();
         if self.job.is_some() {
             return Ok(None);
         }
+        if !voiceterm::VOICE_CAPTURE_BUILT {
+            return Err(anyhow!(
+                "Voice capture is not built into this binary (overlay-only build)"
+            ));
+        }
 
+        // A model switch leaves the previous model loaded, so use it rather than wait.
+        let switching = self
+            .preload
+            .as_ref()
+            .is_some_and(|preload| preload.device.is_none());
+        if !(switching && self.transcriber.is_some()) {
+            self.finish_preload();
+        }
         let transcriber = self.get_transcriber()?;
         if transcriber.is_none() {
             log_debug(
@@ -111,6 +531,10 @@ impl VoiceManager {
         let recorder = if transcriber.is_some() {
             match self.get_recorder() {
                 Ok(recorder) => Some(recorder),
+                // The python fallback would need the same microphone, so report it instead.
+                Err(err) if self.mic_released => {
+                    return Err(err.context("microphone could not be re-acquired after release"));
+                }
                 Err(err) => {
                     if self.config.no_python_fallback {
                         return Err(anyhow!(
@@ -142,8 +566,12 @@ impl VoiceManager {
             Some(self.live_meter.clone()),
         );
         self.job = Some(job);
+        self.capture_seq += 1;
         self.cancel_pending = false;
         self.active_source = Some(source);
+        self.capture_send_mode = send_mode;
+        self.stop_cued = false;
+        self.cue(audio::AudioCue::Start);
 
         let pipeline_label = if using_native {
             "Rust pipeline"
@@ -167,6 +595,23 @@ impl VoiceManager {
         }))
     }
 
+    /// Start a capture without touching the status line; false when one is already running.
+    pub(crate) fn start_headless_capture(&mut self, trigger: VoiceCaptureTrigger) -> Result<bool> {
+        Ok(self.start_capture(trigger)?.is_some())
+    }
+
+    /// Stand in for a capture that has just finished with `message`.
+    #[cfg(test)]
+    pub(crate) fn finish_test_capture(&mut self, message: VoiceJobMessage) {
+        let (tx, rx) = std::sync::mpsc::channel();
+        tx.send(message).expect("receiver is alive");
+        self.job = Some(voice::VoiceJob {
+            receiver: rx,
+            handle: None,
+            stop_flag: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+        });
+    }
+
     pub(crate) fn poll_message(&mut self) -> Option<VoiceJobMessage> {
         let job = self.job.as_mut()?;
         match job.receiver.try_recv() {
@@ -176,11 +621,17 @@ impl VoiceManager {
                 }
                 self.job = None;
                 self.active_source = None;
+                self.idle_since = Instant::now();
                 if self.cancel_pending {
                     self.cancel_pending = false;
                     log_debug("voice capture cancelled; dropping message");
                     None
                 } else {
+                    if matches!(message, VoiceJobMessage::Error(_)) {
+                        self.cue(audio::AudioCue::Error);
+                    } else {
+                        self.cue_stop();
+                    }
                     Some(message)
                 }
             }
@@ -191,24 +642,53 @@ impl VoiceManager {
                 }
                 self.job = None;
                 self.active_source = None;
+                self.idle_since = Instant::now();
                 let was_cancelled = self.cancel_pending;
                 self.cancel_pending = false;
                 if was_cancelled {
                     log_debug("voice capture cancelled; worker disconnected");
                     None
                 } else {
-                    Some(VoiceJobMessage::Error(
-                        "voice capture worker disconnected unexpectedly".to_string(),
-                    ))
+                    self.cue(audio::AudioCue::Error);
+                    Some(VoiceJobMessage::Error(VoiceError::new(
+                        VoiceErrorKind::Unknown,
+                        "voice capture worker disconnected unexpectedly",
+                    )))
                 }
             }
         }
     }
 
+    fn cue(&self, cue: audio::AudioCue) {
+        if let Some(player) = &self.cues {
+            player.play(cue);
+        }
+    }
+
+    /// Stop cue once per capture, whether the user stopped it or it ended on silence.
+    fn cue_stop(&mut self) {
+        if !self.stop_cued {
+            self.stop_cued = true;
+            self.cue(audio::AudioCue::Stop);
+        }
+    }
+
     fn get_recorder(&mut self) -> Result<Arc<Mutex<audio::Recorder>>> {
+        self.follow_devices();
         if self.recorder.is_none() {
-            let recorder = audio::Recorder::new(self.config.input_device.as_deref())?;
-            self.recorder = Some(Arc::new(Mutex::new(recorder)));
+            let preferred = self.config.input_device.as_deref();
+            let target = device_to_open(preferred, self.seen_devices.as_ref());
+            let recorder = open_recorder(target, self.keep_open_ms())?;
+            if let (Some(missing), None) = (preferred, target) {
+                let notice = format!("Mic {missing} not found; using {}", recorder.device_name());
+                log_debug(&notice);
+                self.note_device_change(Some(notice), false);
+            }
+            if self.mic_released {
+                self.mic_released = false;
+                log_debug("microphone re-acquired after release");
+            }
+            self.adopt_recorder(recorder);
         }
         Ok(self
             .recorder
@@ -217,18 +697,205 @@ impl VoiceManager {
             .clone())
     }
 
+    /// Make `recorder` the open recorder and apply its device's profile.
+    fn adopt_recorder(&mut self, recorder: audio::Recorder) {
+        let name = recorder.device_name();
+        self.apply_device_profile(&name);
+        self.active_device = Some(name);
+        self.recorder = Some(Arc::new(Mutex::new(recorder)));
+    }
+
+    /// Reopen the recorder when its device went away or the preferred one came back.
+    ///
+    /// Acts only on a failed stream or a device scan it has not seen yet, so a
+    /// device that will not open is not retried on every call.
+    fn follow_devices(&mut self) {
+        let devices = self
+            .device_watcher
+            .as_ref()
+            .and_then(audio::DeviceWatcher::latest);
+        let rescanned = devices.is_some() && devices != self.seen_devices;
+        if rescanned {
+            self.seen_devices = devices;
+        }
+        let Some(recorder) = self.recorder.as_ref() else {
+            return;
+        };
+        let failed = recorder
+            .lock()
+            .map(|recorder| recorder.stream_failed())
+            .unwrap_or(false);
+        if !failed && !rescanned {
+            return;
+        }
+        let Some(switch) = device_switch(
+            self.active_device.as_deref(),
+            self.config.input_device.as_deref(),
+            failed,
+            self.seen_devices.as_ref(),
+        ) else {
+            return;
+        };
+        let previous = self.active_device.clone();
+        let target = device_to_open(
+            self.config.input_device.as_deref(),
+            self.seen_devices.as_ref(),
+        )
+        .map(str::to_string);
+        if switch == DeviceSwitch::Lost {
+            // Release the old stream before opening a device that may share its driver.
+            self.recorder = None;
+            self.active_device = None;
+        }
+        let notice = match open_recorder(target.as_deref(), self.keep_open_ms()) {
+            Ok(recorder) => {
+                let notice = switch_notice(switch, previous.as_deref(), &recorder.device_name());
+                self.adopt_recorder(recorder);
+                notice
+            }
+            Err(err) => {
+                log_debug(&format!("input device switch failed: {err:#}"));
+                if switch == DeviceSwitch::Preferred {
+                    return;
+                }
+                "Mic disconnected; no input device available".to_string()
+            }
+        };
+        log_debug(&format!(
+            "input device changed from {}: {notice}",
+            previous.as_deref().unwrap_or("none")
+        ));
+        self.note_device_change(Some(notice), false);
+    }
+
+    /// Switch to `device`'s profile levels, or back to the startup levels after a profiled device.
+    fn apply_device_profile(&mut self, device: &str) {
+        let levels = match self.device_profiles.get(device) {
+            Some(profile) => {
+                if self.profiled_device.as_deref() == Some(device) {
+                    return;
+                }
+                self.profiled_device = Some(device.to_string());
+                profile_levels(profile, self.base_levels, self.config.voice_max_capture_ms)
+            }
+            None => {
+                if self.profiled_device.take().is_none() {
+                    return;
+                }
+                self.base_levels
+            }
+        };
+        let current = (
+            self.config.voice_vad_threshold_db,
+            self.config.voice_silence_tail_ms,
+        );
+        if levels == current {
+            return;
+        }
+        (
+            self.config.voice_vad_threshold_db,
+            self.config.voice_silence_tail_ms,
+        ) = levels;
+        log_debug(&format!(
+            "levels for {device}: threshold {:.1} dB, silence tail {} ms",
+            levels.0, levels.1
+        ));
+        self.note_device_change(None, true);
+    }
+
+    fn note_device_change(&mut self, notice: Option<String>, levels_changed: bool) {
+        let change = self.device_change.get_or_insert_with(DeviceChange::default);
+        if notice.is_some() {
+            change.notice = notice;
+        }
+        change.levels_changed |= levels_changed;
+    }
+
     fn get_transcriber(&mut self) -> Result<Option<Arc<Mutex<stt::Transcriber>>>> {
         if self.transcriber.is_none() {
             let Some(model_path) = self.config.whisper_model_path.clone() else {
                 return Ok(None);
             };
-            let transcriber = stt::Transcriber::new(&model_path)?;
+            let transcriber = stt::Transcriber::with_device(&model_path, self.config.stt_device)?;
             self.transcriber = Some(Arc::new(Mutex::new(transcriber)));
         }
         Ok(self.transcriber.as_ref().cloned())
     }
 }
 
+/// Whether captures have been idle long enough to release the microphone.
+fn mic_release_due(release_secs: Option<u64>, idle_since: Instant, now: Instant) -> bool {
+    release_secs
+        .is_some_and(|secs| now.saturating_duration_since(idle_since) >= Duration::from_secs(secs))
+}
+
+/// Why the recorder on `active` should move, given the latest device scan.
+fn device_switch(
+    active: Option<&str>,
+    preferred: Option<&str>,
+    stream_failed: bool,
+    devices: Option<&audio::InputDevices>,
+) -> Option<DeviceSwitch> {
+    if stream_failed {
+        return Some(DeviceSwitch::Lost);
+    }
+    let (active, devices) = (active?, devices?);
+    if !devices.contains(active) {
+        return Some(DeviceSwitch::Lost);
+    }
+    let wanted = preferred.or(devices.default.as_deref())?;
+    (wanted != active && devices.contains(wanted)).then_some(DeviceSwitch::Preferred)
+}
+
+/// `--input-device` unless the latest scan shows it unplugged, in which case the system default.
+fn device_to_open<'a>(
+    preferred: Option<&'a str>,
+    devices: Option<&audio::InputDevices>,
+) -> Option<&'a str> {
+    preferred.filter(|name| devices.is_none_or(|devices| devices.contains(name)))
+}
+
+fn switch_notice(switch: DeviceSwitch, previous: Option<&str>, now: &str) -> String {
+    match switch {
+        _ if previous == Some(now) => format!("Mic reconnected: {now}"),
+        DeviceSwitch::Lost => format!("Mic disconnected; switched to {now}"),
+        DeviceSwitch::Preferred => format!("Mic switched to {now}"),
+    }
+}
+
+/// Threshold and silence tail for a profiled device, clamped like the manual adjustments.
+fn profile_levels(profile: &DeviceProfile, base: (f32, u64), max_capture_ms: u64) -> (f32, u64) {
+    (
+        profile
+            .voice_vad_threshold_db
+            .unwrap_or(base.0)
+            .clamp(-120.0, 0.0),
+        profile
+            .voice_silence_tail_ms
+            .unwrap_or(base.1)
+            .clamp(200, max_capture_ms.max(200)),
+    )
+}
+
+/// Open the input device; with `--keep-mic-open` its stream starts now and runs between captures.
+fn open_recorder(device_name: Option<&str>, keep_open_ms: Option<u64>) -> Result<audio::Recorder> {
+    let mut recorder = audio::Recorder::new(device_name)?;
+    if let Some(lookback_ms) = keep_open_ms {
+        if let Err(err) = recorder.keep_open(lookback_ms) {
+            log_debug(&format!(
+                "keep-mic-open failed; opening the stream per capture: {err:#}"
+            ));
+        }
+    }
+    Ok(recorder)
+}
+
+fn timed<T>(load: impl FnOnce() -> Result<T>) -> Timed<T> {
+    let started = Instant::now();
+    let result = load();
+    (result, started.elapsed())
+}
+
 pub(crate) fn start_voice_capture(
     voice_manager: &mut VoiceManager,
     trigger: VoiceCaptureTrigger,
@@ -237,6 +904,19 @@ pub(crate) fn start_voice_capture(
     current_status: &mut Option<String>,
     status_state: &mut StatusLineState,
 ) -> Result<()> {
+    if voice_manager.capture_blocked() {
+        if trigger == VoiceCaptureTrigger::Manual {
+            set_status(
+                writer_tx,
+                status_clear_deadline,
+                current_status,
+                status_state,
+                "Transcript queue full; waiting for the CLI",
+                Some(Duration::from_secs(STATUS_TOAST_SECS)),
+            );
+        }
+        return Ok(());
+    }
     match voice_manager.start_capture(trigger)? {
         Some(info) => {
             status_state.recording_state = RecordingState::Recording;
@@ -246,6 +926,14 @@ pub(crate) fn start_voice_capture(
                 VoiceCaptureSource::Native => Pipeline::Rust,
                 VoiceCaptureSource::Python => Pipeline::Python,
             };
+            emit_session_event(|| VoiceSessionEvent::CaptureStarted {
+                trigger: match trigger {
+                    VoiceCaptureTrigger::Manual => "manual",
+                    VoiceCaptureTrigger::Auto => "auto",
+                }
+                .to_string(),
+                source: source_key(info.source).to_string(),
+            });
             if trigger == VoiceCaptureTrigger::Auto {
                 status_state.message.clear();
                 send_enhanced_status(writer_tx, status_state);
@@ -255,7 +943,14 @@ pub(crate) fn start_voice_capture(
                 VoiceCaptureTrigger::Manual => "Manual Mode",
                 VoiceCaptureTrigger::Auto => "Auto Mode",
             };
-            let mut status = format!("Listening {mode_label} ({})", info.pipeline_display);
+            let mut status = match voice_manager.capture_send_mode() {
+                Some(mode) => format!(
+                    "Listening {mode_label} ({}, {})",
+                    info.pipeline_display,
+                    send_mode_key(mode)
+                ),
+                None => format!("Listening {mode_label} ({})", info.pipeline_display),
+            };
             if let Some(note) = info.fallback_note {
                 status.push(' ');
                 status.push_str(&note);
@@ -296,6 +991,197 @@ mod tests {
     use std::time::Duration;
     use voiceterm::config::AppConfig;
 
+    #[test]
+    fn voice_manager_switches_vad_engine_for_next_capture() {
+        let mut config = AppConfig::parse_from(["test"]);
+        config.voice_vad_engine = VadEngineKind::Simple;
+        let mut manager = VoiceManager::new(config);
+        assert_eq!(manager.vad_engine(), VadEngineKind::Simple);
+        let engine = *VadEngineKind::available()
+            .first()
+            .expect("at least one engine");
+        manager.set_vad_engine(engine);
+        assert_eq!(manager.vad_engine(), engine);
+        assert_eq!(manager.config.voice_vad_engine, engine);
+    }
+
+    #[test]
+    fn preload_is_skipped_without_a_native_model() {
+        let mut config = AppConfig::parse_from(["test"]);
+        config.whisper_model_path = None;
+        let mut manager = VoiceManager::new(config);
+        assert!(!manager.start_preload());
+        assert!(!manager.preload_pending());
+        assert_eq!(manager.poll_preload(), None);
+        assert!(manager.preload_timings().is_empty());
+    }
+
+    #[test]
+    fn voice_manager_clamps_silence_tail() {
+        let mut config = AppConfig::parse_from(["test"]);
+        config.voice_silence_tail_ms = 1000;
+        config.voice_max_capture_ms = 3000;
+        let mut manager = VoiceManager::new(config);
+        assert_eq!(manager.adjust_silence_tail(-100), 900);
+        assert_eq!(manager.adjust_silence_tail(-5000), 200);
+        assert_eq!(manager.adjust_silence_tail(10_000), 3000);
+    }
+
+    #[test]
+    fn whisper_model_switch_loads_in_background_and_refuses_overlap() {
+        let mut config = AppConfig::parse_from(["test"]);
+        config.whisper_model_path = None;
+        let mut manager = VoiceManager::new(config);
+        manager
+            .switch_whisper_model("/no/such/ggml-base.bin".to_string())
+            .expect("first switch starts");
+        assert_eq!(manager.whisper_model_path(), Some("/no/such/ggml-base.bin"));
+        assert!(manager.preload_pending());
+        assert!(manager
+            .switch_whisper_model("/no/such/ggml-small.bin".to_string())
+            .is_err());
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        let loaded = loop {
+            if let Some(loaded) = manager.poll_preload() {
+                break loaded;
+            }
+            assert!(std::time::Instant::now() < deadline, "model load hung");
+            thread::sleep(Duration::from_millis(10));
+        };
+        assert!(!loaded, "missing model cannot load");
+        assert!(!manager.preload_pending());
+    }
+
+    #[test]
+    fn mic_release_waits_for_the_idle_policy() {
+        let idle_since = Instant::now();
+        assert!(!mic_release_due(
+            None,
+            idle_since,
+            idle_since + Duration::from_secs(3600)
+        ));
+        assert!(mic_release_due(Some(0), idle_since, idle_since));
+        assert!(!mic_release_due(
+            Some(30),
+            idle_since,
+            idle_since + Duration::from_secs(29)
+        ));
+        assert!(mic_release_due(
+            Some(30),
+            idle_since,
+            idle_since + Duration::from_secs(30)
+        ));
+
+        // Nothing to release before the first recorder is opened.
+        let mut config = AppConfig::parse_from(["test"]);
+        config.mic_release_secs = Some(0);
+        let mut manager = VoiceManager::new(config);
+        assert!(!manager.release_idle_mic(Instant::now()));
+        assert!(!manager.mic_released);
+    }
+
+    #[test]
+    fn device_switch_follows_unplugged_and_returning_devices() {
+        let devices = |names: &[&str], default: &str| audio::InputDevices {
+            names: names.iter().map(|name| name.to_string()).collect(),
+            default: Some(default.to_string()),
+        };
+        let both = devices(&["Built-in Mic", "Headset"], "Built-in Mic");
+        let laptop = devices(&["Built-in Mic"], "Built-in Mic");
+
+        assert_eq!(
+            device_switch(Some("Headset"), Some("Headset"), false, Some(&both)),
+            None
+        );
+        assert_eq!(
+            device_switch(Some("Headset"), Some("Headset"), false, Some(&laptop)),
+            Some(DeviceSwitch::Lost)
+        );
+        assert_eq!(device_to_open(Some("Headset"), Some(&laptop)), None);
+        assert_eq!(device_to_open(Some("Headset"), None), Some("Headset"));
+        assert_eq!(
+            device_switch(Some("Built-in Mic"), Some("Headset"), false, Some(&both)),
+            Some(DeviceSwitch::Preferred)
+        );
+        // Without --input-device the recorder follows the system default.
+        let headset_default = devices(&["Built-in Mic", "Headset"], "Headset");
+        assert_eq!(
+            device_switch(Some("Built-in Mic"), None, false, Some(&headset_default)),
+            Some(DeviceSwitch::Preferred)
+        );
+        assert_eq!(
+            device_switch(Some("Headset"), None, true, None),
+            Some(DeviceSwitch::Lost)
+        );
+        assert_eq!(
+            device_switch(None, Some("Headset"), false, Some(&both)),
+            None
+        );
+
+        assert_eq!(
+            switch_notice(DeviceSwitch::Lost, Some("Headset"), "Built-in Mic"),
+            "Mic disconnected; switched to Built-in Mic"
+        );
+        assert_eq!(
+            switch_notice(DeviceSwitch::Lost, Some("Headset"), "Headset"),
+            "Mic reconnected: Headset"
+        );
+        assert_eq!(
+            switch_notice(DeviceSwitch::Preferred, Some("Built-in Mic"), "Headset"),
+            "Mic switched to Headset"
+        );
+    }
+
+    #[test]
+    fn device_profiles_apply_per_device_and_restore_startup_levels() {
+        let mut config = AppConfig::parse_from(["test"]);
+        config.voice_vad_threshold_db = -40.0;
+        config.voice_silence_tail_ms = 1000;
+        config.voice_max_capture_ms = 30_000;
+        let mut manager = VoiceManager::new(config);
+        manager.device_profiles.insert(
+            "Headset".to_string(),
+            DeviceProfile {
+                voice_vad_threshold_db: Some(-48.0),
+                voice_silence_tail_ms: None,
+            },
+        );
+
+        manager.apply_device_profile("Built-in Mic");
+        assert_eq!(manager.poll_device_change(), None);
+
+        manager.apply_device_profile("Headset");
+        assert_eq!(
+            (manager.vad_threshold_db(), manager.silence_tail_ms()),
+            (-48.0, 1000)
+        );
+        assert_eq!(
+            manager.poll_device_change(),
+            Some(DeviceChange {
+                notice: None,
+                levels_changed: true,
+            })
+        );
+
+        // Reopening the same device keeps manual adjustments.
+        manager.adjust_sensitivity(5.0);
+        manager.apply_device_profile("Headset");
+        assert_eq!(manager.vad_threshold_db(), -43.0);
+        assert_eq!(manager.poll_device_change(), None);
+
+        manager.apply_device_profile("Built-in Mic");
+        assert_eq!(
+            (manager.vad_threshold_db(), manager.silence_tail_ms()),
+            (-40.0, 1000)
+        );
+
+        let clamped = DeviceProfile {
+            voice_vad_threshold_db: Some(12.0),
+            voice_silence_tail_ms: Some(50),
+        };
+        assert_eq!(profile_levels(&clamped, (-40.0, 1000), 30_000), (0.0, 200));
+    }
+
     #[test]
     fn voice_manager_clamps_sensitivity() {
         let config = AppConfig::parse_from(["test"]);
@@ -389,6 +1275,24 @@ mod tests {
         assert!(manager.start_capture(VoiceCaptureTrigger::Manual).is_err());
     }
 
+    #[test]
+    fn pinned_send_mode_is_dropped_when_a_capture_is_already_running() {
+        let mut manager = VoiceManager::new(AppConfig::parse_from(["test"]));
+        let (_tx, rx) = mpsc::channel();
+        manager.job = Some(voice::VoiceJob {
+            receiver: rx,
+            handle: None,
+            stop_flag: Arc::new(AtomicBool::new(false)),
+        });
+        manager.set_next_send_mode(Some(VoiceSendMode::Insert));
+        assert!(manager
+            .start_capture(VoiceCaptureTrigger::Manual)
+            .expect("start capture")
+            .is_none());
+        assert_eq!(manager.next_send_mode, None);
+        assert_eq!(manager.capture_send_mode(), None);
+    }
+
     #[test]
     fn voice_manager_get_transcriber_errors_on_missing_model() {
         let mut config = AppConfig::parse_from(["test"]);
@@ -445,4 +1349,30 @@ mod tests {
 
         assert!(writer_rx.try_recv().is_err());
     }
+
+    #[test]
+    fn start_voice_capture_waits_while_queue_blocks_captures() {
+        let mut manager = VoiceManager::new(AppConfig::parse_from(["test"]));
+        manager.set_capture_blocked(true);
+        let (writer_tx, writer_rx) = crossbeam_channel::unbounded();
+        let mut deadline = None;
+        let mut current_status = None;
+        let mut status_state = StatusLineState::new();
+        start_voice_capture(
+            &mut manager,
+            VoiceCaptureTrigger::Manual,
+            &writer_tx,
+            &mut deadline,
+            &mut current_status,
+            &mut status_state,
+        )
+        .expect("blocked capture");
+        assert!(manager.job.is_none());
+        assert_eq!(manager.capture_seq(), 0);
+        assert_eq!(
+            current_status.as_deref(),
+            Some("Transcript queue full; waiting for the CLI")
+        );
+        while writer_rx.try_recv().is_ok() {}
+    }
 }
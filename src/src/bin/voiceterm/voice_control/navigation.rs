@@ -0,0 +1,210 @@
+//! Spoken overlay navigation so menus and confirmations work hands-free.
+//!
+//! With `--voice-navigation`, a transcript that is exactly one of the commands
+//! below acts as the key it names instead of reaching the backend. Which words
+//! count depends on what is on screen: "settings", "open help", "open themes",
+//! "show latency", "show queue", and "find <phrase>" (or "search for <phrase>") work anywhere;
+//! "next", "previous", "left", "right", "select", and "close" only while an
+//! overlay is open; "confirm" and "cancel" only while an urgent transcript or
+//! dictation draft waits for Enter or Esc. Commands are matched like dictation
+//! edits, so "Next." counts as "next".
+
+use crossbeam_channel::Sender;
+
+use crate::input::InputEvent;
+use crate::overlays::OverlayMode;
+use crate::transcript::normalize_command;
+
+const CONFIRM_COMMANDS: &[&str] = &["confirm", "send it", "type it"];
+const CANCEL_COMMANDS: &[&str] = &["cancel", "discard", "never mind"];
+
+/// What is waiting for input, which decides the commands that apply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NavigationContext {
+    /// Nothing is open; only commands that open a panel apply.
+    Terminal,
+    Overlay,
+    /// An urgent transcript or dictation draft is held for Enter or Esc.
+    Confirm,
+}
+
+impl NavigationContext {
+    pub(crate) fn current(overlay_mode: OverlayMode, confirm_pending: bool) -> Self {
+        if overlay_mode != OverlayMode::None {
+            Self::Overlay
+        } else if confirm_pending {
+            Self::Confirm
+        } else {
+            Self::Terminal
+        }
+    }
+}
+
+/// A transcript recognized as a navigation command.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum NavigationCommand {
+    /// Handle this key as if it had been typed.
+    Key(InputEvent),
+    /// Type (true) or discard (false) the held transcript.
+    Confirm(bool),
+}
+
+/// Turns spoken commands into overlay keys when `--voice-navigation` is on.
+#[derive(Debug, Default)]
+pub(crate) struct VoiceNavigation {
+    /// Input loop sender; `None` leaves every transcript to the backend.
+    input_tx: Option<Sender<InputEvent>>,
+}
+
+impl VoiceNavigation {
+    pub(crate) fn new(enabled: bool, input_tx: Sender<InputEvent>) -> Self {
+        Self {
+            input_tx: enabled.then_some(input_tx),
+        }
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.input_tx.is_some()
+    }
+
+    /// The command `transcript` stands for in `context`, if any.
+    pub(crate) fn command(
+        &self,
+        transcript: &str,
+        context: NavigationContext,
+    ) -> Option<NavigationCommand> {
+        if !self.is_enabled() {
+            return None;
+        }
+        let command = normalize_command(transcript);
+        let command = command.as_str();
+        match context {
+            NavigationContext::Confirm if CONFIRM_COMMANDS.contains(&command) => {
+                return Some(NavigationCommand::Confirm(true));
+            }
+            NavigationContext::Confirm if CANCEL_COMMANDS.contains(&command) => {
+                return Some(NavigationCommand::Confirm(false));
+            }
+            NavigationContext::Overlay => {
+                if let Some(key) = overlay_key(command) {
+                    return Some(NavigationCommand::Key(key));
+                }
+            }
+            _ => {}
+        }
+        open_key(command).map(NavigationCommand::Key)
+    }
+
+    /// Queue `key` for the input loop; false when the queue is full or closed.
+    pub(crate) fn send_key(&self, key: InputEvent) -> bool {
+        self.input_tx
+            .as_ref()
+            .is_some_and(|input_tx| input_tx.try_send(key).is_ok())
+    }
+}
+
+/// Commands that open a panel from anywhere.
+fn open_key(command: &str) -> Option<InputEvent> {
+    if let Some(phrase) = ["find ", "search for "]
+        .iter()
+        .find_map(|prefix| command.strip_prefix(prefix))
+    {
+        return Some(InputEvent::Find(phrase.to_string()));
+    }
+    match command {
+        "settings" | "open settings" | "show settings" => Some(InputEvent::SettingsToggle),
+        "open help" | "show help" => Some(InputEvent::HelpToggle),
+        "open themes" | "show themes" | "theme picker" => Some(InputEvent::ThemePicker),
+        "show latency" => Some(InputEvent::LatencyHud),
+        "show queue" => Some(InputEvent::QueuePanel),
+        _ => None,
+    }
+}
+
+/// Commands that move through or close an open overlay.
+fn overlay_key(command: &str) -> Option<InputEvent> {
+    let bytes: &[u8] = match command {
+        "next" | "down" => b"\x1b[B",
+        "previous" | "up" => b"\x1b[A",
+        "left" => b"\x1b[D",
+        "right" => b"\x1b[C",
+        "select" | "enter" => return Some(InputEvent::EnterKey),
+        "close" | "cancel" | "back" | "escape" => b"\x1b",
+        _ => return None,
+    };
+    Some(InputEvent::Bytes(bytes.to_vec()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossbeam_channel::bounded;
+
+    #[test]
+    fn commands_depend_on_what_is_waiting_for_input() {
+        let (input_tx, _input_rx) = bounded(4);
+        let navigation = VoiceNavigation::new(true, input_tx);
+        let terminal = NavigationContext::current(OverlayMode::None, false);
+        let overlay = NavigationContext::current(OverlayMode::Settings, true);
+        let confirm = NavigationContext::current(OverlayMode::None, true);
+
+        assert_eq!(
+            navigation.command("Settings.", terminal),
+            Some(NavigationCommand::Key(InputEvent::SettingsToggle))
+        );
+        // Overlay keys and confirmations only apply while something waits for them.
+        assert_eq!(navigation.command("next", terminal), None);
+        assert_eq!(navigation.command("cancel", terminal), None);
+        assert_eq!(
+            navigation.command("Next!", overlay),
+            Some(NavigationCommand::Key(InputEvent::Bytes(
+                b"\x1b[B".to_vec()
+            )))
+        );
+        assert_eq!(
+            navigation.command("select", overlay),
+            Some(NavigationCommand::Key(InputEvent::EnterKey))
+        );
+        assert_eq!(
+            navigation.command("Cancel.", overlay),
+            Some(NavigationCommand::Key(InputEvent::Bytes(vec![0x1b])))
+        );
+        assert_eq!(
+            navigation.command("open help", overlay),
+            Some(NavigationCommand::Key(InputEvent::HelpToggle))
+        );
+        assert_eq!(
+            navigation.command("Send it.", confirm),
+            Some(NavigationCommand::Confirm(true))
+        );
+        assert_eq!(
+            navigation.command("never mind", confirm),
+            Some(NavigationCommand::Confirm(false))
+        );
+        assert_eq!(navigation.command("cancel the build", confirm), None);
+        assert_eq!(
+            navigation.command("Find cargo.toml.", terminal),
+            Some(NavigationCommand::Key(InputEvent::Find(
+                "cargo.toml".to_string()
+            )))
+        );
+        assert_eq!(
+            navigation.command("search for failing tests", overlay),
+            Some(NavigationCommand::Key(InputEvent::Find(
+                "failing tests".to_string()
+            )))
+        );
+        assert_eq!(navigation.command("find", terminal), None);
+    }
+
+    #[test]
+    fn disabled_navigation_leaves_transcripts_alone() {
+        let navigation = VoiceNavigation::default();
+        assert!(!navigation.is_enabled());
+        assert_eq!(
+            navigation.command("settings", NavigationContext::Terminal),
+            None
+        );
+        assert!(!navigation.send_key(InputEvent::EnterKey));
+    }
+}
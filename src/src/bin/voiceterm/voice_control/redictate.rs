@@ -0,0 +1,169 @@
+//! Automatic re-dictation so a failed or garbled capture re-arms the mic instead of
+//! dropping to an error status.
+//!
+//! Transcripts whose Whisper confidence is below `--min-confidence` are discarded,
+//! and transcription failures are retried, up to `--redictate-attempts` times in a row.
+
+use anyhow::{bail, Result};
+use voiceterm::voice_error::VoiceError;
+use voiceterm::VoiceJobMessage;
+
+use crate::config::OverlayConfig;
+
+/// Status shown when a capture is thrown away and the mic is re-armed.
+pub(crate) const REDICTATE_STATUS: &str = "Didn't catch that — try again";
+/// Upper bound for `--redictate-attempts`; more retries just loop on a bad mic.
+const MAX_REDICTATE_ATTEMPTS: u8 = 3;
+
+/// Why a voice job result was rejected.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum RedictateReason {
+    LowConfidence(f32),
+    TranscriptionFailed,
+}
+
+/// Re-dictation policy plus the count of consecutive retries.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Redictation {
+    min_confidence: f32,
+    max_attempts: u8,
+    attempts: u8,
+}
+
+impl Redictation {
+    pub(crate) fn from_config(config: &OverlayConfig) -> Result<Self> {
+        if !(0.0..=1.0).contains(&config.min_confidence) {
+            bail!(
+                "--min-confidence must be between 0.0 and 1.0, got {}",
+                config.min_confidence
+            );
+        }
+        if config.redictate_attempts > MAX_REDICTATE_ATTEMPTS {
+            bail!(
+                "--redictate-attempts must be between 0 and {MAX_REDICTATE_ATTEMPTS}, got {}",
+                config.redictate_attempts
+            );
+        }
+        Ok(Self {
+            min_confidence: config.min_confidence,
+            max_attempts: config.redictate_attempts,
+            attempts: 0,
+        })
+    }
+
+    /// Reason to discard `message`, if any; `None` means handle it normally.
+    pub(crate) fn check(&self, message: &VoiceJobMessage) -> Option<RedictateReason> {
+        match message {
+            VoiceJobMessage::Transcript {
+                confidence: Some(confidence),
+                ..
+            } if *confidence < self.min_confidence => {
+                Some(RedictateReason::LowConfidence(*confidence))
+            }
+            VoiceJobMessage::Error(VoiceError { kind, .. })
+                if kind.allows_redictation() && self.max_attempts > 0 =>
+            {
+                Some(RedictateReason::TranscriptionFailed)
+            }
+            _ => None,
+        }
+    }
+
+    /// Count one retry; false once the consecutive-attempt budget is spent.
+    pub(crate) fn try_rearm(&mut self) -> bool {
+        if self.attempts >= self.max_attempts {
+            self.attempts = 0;
+            return false;
+        }
+        self.attempts += 1;
+        true
+    }
+
+    /// Forget earlier retries after a capture is accepted.
+    pub(crate) fn reset(&mut self) {
+        self.attempts = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use voiceterm::voice_error::VoiceErrorKind;
+    use voiceterm::VoiceCaptureSource;
+
+    fn redictation(args: &[&str]) -> Result<Redictation> {
+        let mut argv = vec!["voiceterm"];
+        argv.extend_from_slice(args);
+        Redictation::from_config(&OverlayConfig::parse_from(argv))
+    }
+
+    fn transcript(confidence: Option<f32>) -> VoiceJobMessage {
+        VoiceJobMessage::Transcript {
+            text: "hello".to_string(),
+            source: VoiceCaptureSource::Native,
+            confidence,
+            words: Vec::new(),
+            metrics: None,
+        }
+    }
+
+    #[test]
+    fn from_config_validates_ranges() {
+        assert!(redictation(&["--min-confidence", "1.5"]).is_err());
+        let negative = redictation(&["--min-confidence", "-0.1"]).unwrap_err();
+        assert!(negative
+            .to_string()
+            .contains("--min-confidence must be between 0.0 and 1.0"));
+        assert!(redictation(&["--redictate-attempts", "4"]).is_err());
+        assert!(redictation(&["--min-confidence", "0.6", "--redictate-attempts", "3"]).is_ok());
+    }
+
+    #[test]
+    fn check_rejects_low_confidence_only_when_threshold_set() {
+        let default = redictation(&[]).unwrap();
+        assert_eq!(default.check(&transcript(Some(0.1))), None);
+
+        let strict = redictation(&["--min-confidence", "0.5"]).unwrap();
+        assert_eq!(
+            strict.check(&transcript(Some(0.3))),
+            Some(RedictateReason::LowConfidence(0.3))
+        );
+        assert_eq!(strict.check(&transcript(Some(0.8))), None);
+        assert_eq!(strict.check(&transcript(None)), None);
+    }
+
+    #[test]
+    fn check_retries_transcription_errors_but_not_device_errors() {
+        let retrying = redictation(&[]).unwrap();
+        let stt_error = VoiceJobMessage::Error(VoiceError::new(
+            VoiceErrorKind::Transcription,
+            "whisper failed",
+        ));
+        assert_eq!(
+            retrying.check(&stt_error),
+            Some(RedictateReason::TranscriptionFailed)
+        );
+        let mic_error = VoiceJobMessage::Error(VoiceError::new(
+            VoiceErrorKind::NoDevice,
+            "no input devices",
+        ));
+        assert_eq!(retrying.check(&mic_error), None);
+
+        let disabled = redictation(&["--redictate-attempts", "0"]).unwrap();
+        assert_eq!(disabled.check(&stt_error), None);
+    }
+
+    #[test]
+    fn try_rearm_limits_consecutive_attempts() {
+        let mut redictation = redictation(&["--redictate-attempts", "2"]).unwrap();
+        assert!(redictation.try_rearm());
+        assert!(redictation.try_rearm());
+        assert!(!redictation.try_rearm());
+        // The budget starts over after giving up or accepting a capture.
+        assert!(redictation.try_rearm());
+        redictation.reset();
+        assert!(redictation.try_rearm());
+        assert!(redictation.try_rearm());
+    }
+}
@@ -0,0 +1,55 @@
+//! Voice enrollment so `--speaker-filter` knows which voice to accept.
+//!
+//! `--enroll-voice` records one sample of the user reading aloud, saves its
+//! voice print, and reports how consistent the two halves of the sample were
+//! so the user can judge how much headroom `--speaker-threshold` leaves.
+
+use anyhow::{anyhow, bail, Result};
+use std::io::{self, Write};
+use std::time::Duration;
+use voiceterm::audio::{Recorder, VoicePrint};
+use voiceterm::config::AppConfig;
+
+/// Long enough for a couple of sentences, so each half still has plenty of speech.
+const ENROLL_SAMPLE: Duration = Duration::from_secs(8);
+
+pub(crate) fn run_voice_enrollment(config: &AppConfig) -> Result<()> {
+    let path = config
+        .voice_print_path()
+        .ok_or_else(|| anyhow!("HOME is unset; pass --voice-print <PATH>"))?;
+    let recorder = Recorder::new(config.input_device.as_deref())?;
+    println!(
+        "Voice enrollment using input device: {}",
+        recorder.device_name()
+    );
+    println!(
+        "Read a few sentences aloud for {}s, in your normal voice...",
+        ENROLL_SAMPLE.as_secs()
+    );
+    io::stdout().flush().ok();
+    let samples = recorder.record_for(ENROLL_SAMPLE)?;
+
+    let Some(print) = VoicePrint::from_samples(&samples) else {
+        bail!("heard too little speech to enroll; move closer to the mic and try again");
+    };
+    let (first, second) = samples.split_at(samples.len() / 2);
+    let consistency = VoicePrint::from_samples(first)
+        .zip(VoicePrint::from_samples(second))
+        .map(|(first, second)| first.distance(&second));
+    print.save(&path)?;
+
+    println!("\nSaved voice print to {}", path.display());
+    if let Some(distance) = consistency {
+        println!(
+            "Sample consistency: distance {distance:.3} (--speaker-threshold {:.3})",
+            config.speaker_threshold
+        );
+        if distance > config.speaker_threshold {
+            println!(
+                "Note: your own sample varies more than the threshold; raise --speaker-threshold or re-enroll somewhere quieter."
+            );
+        }
+    }
+    println!("Enable with: voiceterm --speaker-filter");
+    Ok(())
+}
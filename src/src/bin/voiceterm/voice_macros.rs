@@ -11,6 +11,11 @@ use crate::config::VoiceSendMode;
 
 const DEFAULT_MACROS_RELATIVE_PATH: &str = ".voiceterm/macros.yaml";
 
+/// Macro file for the project rooted at `project_dir`.
+pub(crate) fn project_macros_path(project_dir: &Path) -> PathBuf {
+    project_dir.join(DEFAULT_MACROS_RELATIVE_PATH)
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct MacroExpansion {
     pub(crate) text: String,
@@ -36,8 +41,7 @@ pub(crate) struct VoiceMacros {
 
 impl VoiceMacros {
     pub(crate) fn load_for_project(project_dir: &Path) -> Self {
-        let path = project_dir.join(DEFAULT_MACROS_RELATIVE_PATH);
-        Self::load_from_path(&path)
+        Self::load_from_path(&project_macros_path(project_dir))
     }
 
     pub(crate) fn load_from_path(path: &Path) -> Self {
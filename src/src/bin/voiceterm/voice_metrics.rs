@@ -0,0 +1,327 @@
+//! Per-capture latency metrics so real sessions can be measured, not just benchmark clips.
+//!
+//! Three phases are tracked for every transcript: record start to speech end,
+//! STT duration, and the wait between the transcript arriving and its delivery
+//! to the backend (or clipboard). A fourth, end to end, adds the trailing
+//! silence, STT, and delivery wait of one utterance: the time from the user
+//! going quiet to the text reaching the prompt. The latency overlay shows
+//! rolling averages and the exit summary prints a per-phase table.
+//! Voice session events are also logged as `voice_session` events with running
+//! capture and transcript counts.
+
+use serde_json::json;
+use std::time::{Duration, Instant};
+use voiceterm::audio::CaptureMetrics;
+use voiceterm::config::LogLevel;
+use voiceterm::log_event;
+use voiceterm::stats::percentile;
+
+use crate::session_events::{SessionEventSink, VoiceSessionEvent};
+use crate::theme::ThemeColors;
+
+/// Samples averaged by the latency overlay.
+pub(crate) const ROLLING_WINDOW: usize = 10;
+
+/// Latency phase of a single capture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LatencyPhase {
+    /// Record start until the end of speech (capture minus trailing silence).
+    SpeechEnd,
+    /// Speech-to-text processing time.
+    Stt,
+    /// Transcript arrival until it was written to the backend.
+    Delivery,
+    /// End of speech until the transcript was written to the backend.
+    EndToEnd,
+}
+
+impl LatencyPhase {
+    pub(crate) const ALL: [LatencyPhase; 4] = [
+        LatencyPhase::SpeechEnd,
+        LatencyPhase::Stt,
+        LatencyPhase::Delivery,
+        LatencyPhase::EndToEnd,
+    ];
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            LatencyPhase::SpeechEnd => "Speech end",
+            LatencyPhase::Stt => "STT",
+            LatencyPhase::Delivery => "Delivery",
+            LatencyPhase::EndToEnd => "End to end",
+        }
+    }
+}
+
+/// Aggregate view of one phase over the whole session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct LatencySummary {
+    pub(crate) count: usize,
+    pub(crate) avg_ms: u64,
+    pub(crate) p50_ms: u64,
+    pub(crate) p95_ms: u64,
+    pub(crate) max_ms: u64,
+}
+
+/// Latency samples collected during the session.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct VoiceMetrics {
+    speech_end_ms: Vec<u64>,
+    stt_ms: Vec<u64>,
+    delivery_ms: Vec<u64>,
+    end_to_end_ms: Vec<u64>,
+    /// Silence tail plus STT of the newest capture, waiting for its delivery.
+    undelivered_ms: Option<u64>,
+    captures: usize,
+    revision: u64,
+}
+
+impl VoiceMetrics {
+    /// Record timings reported by the capture pipeline for one transcript.
+    pub(crate) fn record_capture(&mut self, metrics: &CaptureMetrics) {
+        self.captures += 1;
+        if metrics.capture_ms > 0 {
+            self.speech_end_ms
+                .push(metrics.capture_ms.saturating_sub(metrics.silence_tail_ms));
+        }
+        if metrics.transcribe_ms > 0 {
+            self.stt_ms.push(metrics.transcribe_ms);
+            self.undelivered_ms = Some(metrics.silence_tail_ms + metrics.transcribe_ms);
+        }
+        self.revision += 1;
+    }
+
+    /// Record how long a transcript waited before delivery.
+    pub(crate) fn record_delivery(&mut self, waited: Duration) {
+        let waited_ms = waited.as_millis().min(u128::from(u64::MAX)) as u64;
+        self.delivery_ms.push(waited_ms);
+        // Batched or queued transcripts credit the newest capture.
+        if let Some(undelivered_ms) = self.undelivered_ms.take() {
+            let end_to_end_ms = undelivered_ms.saturating_add(waited_ms);
+            self.end_to_end_ms.push(end_to_end_ms);
+            log_event(
+                LogLevel::Info,
+                "latency_end_to_end",
+                &[
+                    ("end_to_end_ms", json!(end_to_end_ms)),
+                    ("delivery_ms", json!(waited_ms)),
+                ],
+            );
+        }
+        self.revision += 1;
+    }
+
+    pub(crate) fn captures(&self) -> usize {
+        self.captures
+    }
+
+    /// Counter bumped on every new sample so views know when to redraw.
+    pub(crate) fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    fn samples(&self, phase: LatencyPhase) -> &[u64] {
+        match phase {
+            LatencyPhase::SpeechEnd => &self.speech_end_ms,
+            LatencyPhase::Stt => &self.stt_ms,
+            LatencyPhase::Delivery => &self.delivery_ms,
+            LatencyPhase::EndToEnd => &self.end_to_end_ms,
+        }
+    }
+
+    pub(crate) fn last(&self, phase: LatencyPhase) -> Option<u64> {
+        self.samples(phase).last().copied()
+    }
+
+    /// Mean of the most recent `ROLLING_WINDOW` samples.
+    pub(crate) fn rolling_avg(&self, phase: LatencyPhase) -> Option<u64> {
+        let samples = self.samples(phase);
+        let recent = &samples[samples.len().saturating_sub(ROLLING_WINDOW)..];
+        mean(recent)
+    }
+
+    pub(crate) fn summary(&self, phase: LatencyPhase) -> Option<LatencySummary> {
+        let samples = self.samples(phase);
+        let avg_ms = mean(samples)?;
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        Some(LatencySummary {
+            count: sorted.len(),
+            avg_ms,
+            p50_ms: percentile(&sorted, 50),
+            p95_ms: percentile(&sorted, 95),
+            max_ms: sorted[sorted.len() - 1],
+        })
+    }
+}
+
+/// Subscriber that logs each voice session event with session-relative timing.
+pub(crate) struct SessionEventMetrics {
+    started_at: Instant,
+    captures: u64,
+    transcripts: u64,
+}
+
+impl SessionEventMetrics {
+    pub(crate) fn new() -> Self {
+        Self {
+            started_at: Instant::now(),
+            captures: 0,
+            transcripts: 0,
+        }
+    }
+}
+
+impl SessionEventSink for SessionEventMetrics {
+    fn on_event(&mut self, event: &VoiceSessionEvent) {
+        match event {
+            VoiceSessionEvent::CaptureStarted { .. } => self.captures += 1,
+            VoiceSessionEvent::TranscriptDelivered { .. } => self.transcripts += 1,
+            _ => {}
+        }
+        log_event(
+            LogLevel::Info,
+            "voice_session",
+            &[
+                ("event", json!(event.name())),
+                ("t_ms", json!(self.started_at.elapsed().as_millis() as u64)),
+                ("captures", json!(self.captures)),
+                ("transcripts", json!(self.transcripts)),
+            ],
+        );
+    }
+}
+
+fn mean(samples: &[u64]) -> Option<u64> {
+    if samples.is_empty() {
+        return None;
+    }
+    let total: u128 = samples.iter().map(|&ms| u128::from(ms)).sum();
+    Some((total / samples.len() as u128) as u64)
+}
+
+/// Compact latency label: milliseconds below one second, tenths of a second above.
+pub(crate) fn format_latency_ms(ms: u64) -> String {
+    if ms < 1000 {
+        format!("{ms}ms")
+    } else {
+        format!("{:.1}s", ms as f64 / 1000.0)
+    }
+}
+
+/// Per-phase table appended to the exit summary; empty when nothing was measured.
+pub(crate) fn format_latency_table(metrics: &VoiceMetrics, colors: &ThemeColors) -> Vec<String> {
+    let rows: Vec<(LatencyPhase, LatencySummary)> = LatencyPhase::ALL
+        .into_iter()
+        .filter_map(|phase| metrics.summary(phase).map(|summary| (phase, summary)))
+        .collect();
+    if rows.is_empty() {
+        return Vec::new();
+    }
+    let mut lines = vec![format!(
+        "{}{:<12} {:>5} {:>7} {:>7} {:>7} {:>7}{}",
+        colors.info, "Latency", "n", "avg", "p50", "p95", "max", colors.reset
+    )];
+    for (phase, summary) in rows {
+        lines.push(format!(
+            "{:<12} {:>5} {:>7} {:>7} {:>7} {:>7}",
+            phase.label(),
+            summary.count,
+            format_latency_ms(summary.avg_ms),
+            format_latency_ms(summary.p50_ms),
+            format_latency_ms(summary.p95_ms),
+            format_latency_ms(summary.max_ms),
+        ));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::Theme;
+
+    fn capture(capture_ms: u64, silence_tail_ms: u64, transcribe_ms: u64) -> CaptureMetrics {
+        CaptureMetrics {
+            capture_ms,
+            silence_tail_ms,
+            transcribe_ms,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn record_capture_splits_speech_end_and_stt() {
+        let mut metrics = VoiceMetrics::default();
+        assert_eq!(metrics.captures(), 0);
+        metrics.record_capture(&capture(2400, 600, 350));
+        metrics.record_capture(&capture(0, 0, 0));
+        assert_eq!(metrics.captures(), 2);
+        assert_eq!(metrics.last(LatencyPhase::SpeechEnd), Some(1800));
+        assert_eq!(metrics.last(LatencyPhase::Stt), Some(350));
+        assert_eq!(metrics.summary(LatencyPhase::Stt).unwrap().count, 1);
+        assert_eq!(metrics.last(LatencyPhase::Delivery), None);
+        assert_eq!(metrics.revision(), 2);
+    }
+
+    #[test]
+    fn end_to_end_adds_silence_tail_stt_and_delivery_once() {
+        let mut metrics = VoiceMetrics::default();
+        metrics.record_delivery(Duration::from_millis(30));
+        assert_eq!(metrics.last(LatencyPhase::EndToEnd), None);
+        metrics.record_capture(&capture(2400, 500, 350));
+        metrics.record_delivery(Duration::from_millis(40));
+        metrics.record_delivery(Duration::from_millis(60));
+        assert_eq!(metrics.last(LatencyPhase::EndToEnd), Some(890));
+        assert_eq!(metrics.summary(LatencyPhase::EndToEnd).unwrap().count, 1);
+        assert_eq!(metrics.summary(LatencyPhase::Delivery).unwrap().count, 3);
+    }
+
+    #[test]
+    fn rolling_avg_uses_most_recent_window() {
+        let mut metrics = VoiceMetrics::default();
+        for _ in 0..5 {
+            metrics.record_delivery(Duration::from_millis(1000));
+        }
+        for _ in 0..ROLLING_WINDOW {
+            metrics.record_delivery(Duration::from_millis(20));
+        }
+        assert_eq!(metrics.rolling_avg(LatencyPhase::Delivery), Some(20));
+        assert_eq!(metrics.rolling_avg(LatencyPhase::Stt), None);
+    }
+
+    #[test]
+    fn summary_reports_nearest_rank_percentiles() {
+        let mut metrics = VoiceMetrics::default();
+        for ms in 1..=20 {
+            metrics.record_delivery(Duration::from_millis(ms * 10));
+        }
+        let summary = metrics.summary(LatencyPhase::Delivery).unwrap();
+        assert_eq!(summary.count, 20);
+        assert_eq!(summary.avg_ms, 105);
+        assert_eq!(summary.p50_ms, 100);
+        assert_eq!(summary.p95_ms, 190);
+        assert_eq!(summary.max_ms, 200);
+    }
+
+    #[test]
+    fn format_latency_ms_switches_to_seconds() {
+        assert_eq!(format_latency_ms(0), "0ms");
+        assert_eq!(format_latency_ms(999), "999ms");
+        assert_eq!(format_latency_ms(1860), "1.9s");
+    }
+
+    #[test]
+    fn format_latency_table_lists_measured_phases_only() {
+        let colors = Theme::None.colors();
+        assert!(format_latency_table(&VoiceMetrics::default(), &colors).is_empty());
+
+        let mut metrics = VoiceMetrics::default();
+        metrics.record_capture(&capture(2000, 500, 0));
+        let lines = format_latency_table(&metrics, &colors);
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains("p95"));
+        assert!(lines[1].starts_with("Speech end"));
+        assert!(lines[1].contains("1.5s"));
+    }
+}
@@ -0,0 +1,514 @@
+//! WebSocket bridge (`--ws-listen`) so IDE extensions and dashboards can mirror and drive the overlay.
+//!
+//! Every connected client receives the session events as JSON text frames
+//! (`capture_started`, `partial`, `transcript_delivered`, ...) plus a `status`
+//! event whenever the capture state, queue, or session metrics change. Clients
+//! send `{"cmd":"start"}`, `{"cmd":"stop"}`, or `{"cmd":"cancel"}`, which go
+//! through the event loop like the web UI buttons.
+
+use anyhow::{bail, Context, Result};
+use crossbeam_channel::{bounded, Receiver, Sender, TrySendError};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use voiceterm::log_warn;
+
+use crate::config::OverlayConfig;
+use crate::session_events::{SessionEventSink, VoiceSessionEvent};
+use crate::session_record::send_mode_key;
+use crate::session_stats::SessionStats;
+use crate::status_line::StatusLineState;
+
+use super::server::WebUiCommand;
+use super::status::recording_key;
+use super::websocket::{
+    accept_key, encode_frame, read_frame, Frame, OPCODE_CLOSE, OPCODE_PONG, OPCODE_TEXT,
+};
+
+const COMMAND_CHANNEL_CAPACITY: usize = 4;
+/// Frames buffered per client; a client this far behind is disconnected.
+const CLIENT_BUFFER_FRAMES: usize = 256;
+const MAX_HANDSHAKE_BYTES: usize = 8 * 1024;
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// `status` event, sent when any field changes.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename = "status")]
+struct BridgeStatus {
+    recording: &'static str,
+    send_mode: &'static str,
+    auto_voice: bool,
+    queue_depth: usize,
+    last_latency_ms: Option<u32>,
+    transcripts: u32,
+    empty_captures: u32,
+    errors: u32,
+}
+
+impl BridgeStatus {
+    fn new(status_state: &StatusLineState, stats: &SessionStats) -> Self {
+        Self {
+            recording: recording_key(status_state.recording_state),
+            send_mode: send_mode_key(status_state.send_mode),
+            auto_voice: status_state.auto_voice_enabled,
+            queue_depth: status_state.queue_depth,
+            last_latency_ms: status_state.last_latency_ms,
+            transcripts: stats.transcripts,
+            empty_captures: stats.empty_captures,
+            errors: stats.errors,
+        }
+    }
+}
+
+/// A client command frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(tag = "cmd", rename_all = "snake_case")]
+enum BridgeRequest {
+    Start,
+    Stop,
+    Cancel,
+}
+
+impl BridgeRequest {
+    fn command(self) -> WebUiCommand {
+        match self {
+            Self::Start => WebUiCommand::Capture,
+            Self::Stop => WebUiCommand::Stop,
+            Self::Cancel => WebUiCommand::Cancel,
+        }
+    }
+}
+
+/// Connected clients and the last status, so new clients start in sync.
+#[derive(Default)]
+struct Clients {
+    senders: Vec<Sender<Vec<u8>>>,
+    status: Option<Vec<u8>>,
+}
+
+impl Clients {
+    fn broadcast(&mut self, frame: &[u8]) {
+        self.senders
+            .retain(|sender| match sender.try_send(frame.to_vec()) {
+                Ok(()) => true,
+                Err(TrySendError::Full(_)) => {
+                    log_warn("websocket client is not reading; disconnecting it");
+                    false
+                }
+                Err(TrySendError::Disconnected(_)) => false,
+            });
+    }
+}
+
+fn text_frame(value: &impl Serialize) -> Option<Vec<u8>> {
+    serde_json::to_vec(value)
+        .ok()
+        .map(|json| encode_frame(OPCODE_TEXT, &json))
+}
+
+/// Subscriber that forwards session events to every connected client.
+pub(crate) struct WsBridgeEvents(Arc<Mutex<Clients>>);
+
+impl SessionEventSink for WsBridgeEvents {
+    fn on_event(&mut self, event: &VoiceSessionEvent) {
+        let Some(frame) = text_frame(event) else {
+            return;
+        };
+        if let Ok(mut clients) = self.0.lock() {
+            clients.broadcast(&frame);
+        }
+    }
+}
+
+/// Listening bridge; commands are polled and status is published from the event loop.
+pub(crate) struct WsBridge {
+    addr: SocketAddr,
+    commands: Receiver<WebUiCommand>,
+    clients: Arc<Mutex<Clients>>,
+    last_status: Option<BridgeStatus>,
+}
+
+impl WsBridge {
+    /// Start listening when `--ws-listen` is set.
+    pub(crate) fn from_config(config: &OverlayConfig) -> Result<Option<Self>> {
+        config.ws_listen.map(Self::bind).transpose()
+    }
+
+    fn bind(addr: SocketAddr) -> Result<Self> {
+        if !addr.ip().is_loopback() {
+            bail!("--ws-listen must be a loopback address such as 127.0.0.1:7700, not {addr}");
+        }
+        let listener =
+            TcpListener::bind(addr).with_context(|| format!("failed to listen on {addr}"))?;
+        let addr = listener.local_addr()?;
+        let (commands_tx, commands) = bounded(COMMAND_CHANNEL_CAPACITY);
+        let clients = Arc::new(Mutex::new(Clients::default()));
+        let shared = Arc::clone(&clients);
+        thread::spawn(move || accept_clients(listener, addr.port(), commands_tx, shared));
+        Ok(Self {
+            addr,
+            commands,
+            clients,
+            last_status: None,
+        })
+    }
+
+    /// Bridge URL for the debug log.
+    pub(crate) fn url(&self) -> String {
+        format!("ws://{}/", self.addr)
+    }
+
+    /// Subscriber that streams voice session events to connected clients.
+    pub(crate) fn session_events(&self) -> WsBridgeEvents {
+        WsBridgeEvents(Arc::clone(&self.clients))
+    }
+
+    /// Next pending client command, without blocking.
+    pub(crate) fn try_next(&self) -> Option<WebUiCommand> {
+        self.commands.try_recv().ok()
+    }
+
+    /// Send a `status` event when the capture state, queue, or metrics changed.
+    pub(crate) fn publish(&mut self, status_state: &StatusLineState, stats: &SessionStats) {
+        let status = BridgeStatus::new(status_state, stats);
+        if self.last_status.as_ref() == Some(&status) {
+            return;
+        }
+        let Some(frame) = text_frame(&status) else {
+            return;
+        };
+        self.last_status = Some(status);
+        if let Ok(mut clients) = self.clients.lock() {
+            clients.broadcast(&frame);
+            clients.status = Some(frame);
+        }
+    }
+}
+
+fn accept_clients(
+    listener: TcpListener,
+    port: u16,
+    commands: Sender<WebUiCommand>,
+    clients: Arc<Mutex<Clients>>,
+) {
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                let commands = commands.clone();
+                let clients = Arc::clone(&clients);
+                thread::spawn(move || {
+                    if let Err(err) = serve_client(stream, port, &commands, &clients) {
+                        log_warn(&format!("websocket client error: {err:#}"));
+                    }
+                });
+            }
+            Err(err) => log_warn(&format!("websocket accept failed: {err}")),
+        }
+    }
+}
+
+/// Headers of the opening handshake that the bridge checks.
+#[derive(Debug, Default)]
+struct Handshake {
+    host: Option<String>,
+    origin: Option<String>,
+    key: Option<String>,
+    upgrade: bool,
+    version_13: bool,
+}
+
+/// Read the request head, leaving any bytes after it in `reader` for the frame loop.
+fn read_handshake(reader: &mut impl BufRead) -> Result<Handshake> {
+    let mut remaining = MAX_HANDSHAKE_BYTES as u64;
+    let mut next_line = |line: &mut String| -> Result<()> {
+        line.clear();
+        let read = reader.by_ref().take(remaining).read_line(line)?;
+        if read == 0 {
+            bail!("handshake exceeds {MAX_HANDSHAKE_BYTES} bytes or was cut off");
+        }
+        remaining -= read as u64;
+        Ok(())
+    };
+    let mut line = String::new();
+    next_line(&mut line)?;
+    if !line.starts_with("GET ") {
+        bail!("expected a GET upgrade request");
+    }
+    let mut handshake = Handshake::default();
+    loop {
+        next_line(&mut line)?;
+        let header = line.trim_end();
+        if header.is_empty() {
+            return Ok(handshake);
+        }
+        let Some((name, value)) = header.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match name.trim().to_ascii_lowercase().as_str() {
+            "host" => handshake.host = Some(value.to_string()),
+            "origin" => handshake.origin = Some(value.to_string()),
+            "sec-websocket-key" => handshake.key = Some(value.to_string()),
+            "upgrade" => handshake.upgrade = value.eq_ignore_ascii_case("websocket"),
+            "sec-websocket-version" => handshake.version_13 = value == "13",
+            _ => {}
+        }
+    }
+}
+
+fn is_loopback_host(host: &str) -> bool {
+    matches!(host, "127.0.0.1" | "localhost" | "[::1]")
+}
+
+/// Loopback `Host` only, so a rebound DNS name cannot reach the bridge.
+fn host_allowed(host: Option<&str>, port: u16) -> bool {
+    host.and_then(|host| host.rsplit_once(':'))
+        .is_some_and(|(name, host_port)| is_loopback_host(name) && host_port.parse() == Ok(port))
+}
+
+/// Browsers always send `Origin`; only loopback pages and VS Code webviews may connect.
+/// Editor extensions and scripts send none.
+fn origin_allowed(origin: Option<&str>) -> bool {
+    let Some(origin) = origin else {
+        return true;
+    };
+    if origin.starts_with("vscode-webview://") {
+        return true;
+    }
+    let Some(rest) = origin
+        .strip_prefix("http://")
+        .or_else(|| origin.strip_prefix("https://"))
+    else {
+        return false;
+    };
+    let name = match rest.rsplit_once(':') {
+        Some((name, port)) if port.chars().all(|c| c.is_ascii_digit()) => name,
+        _ => rest,
+    };
+    is_loopback_host(name)
+}
+
+fn check_handshake(handshake: &Handshake, port: u16) -> Result<String, &'static str> {
+    if !host_allowed(handshake.host.as_deref(), port) {
+        return Err("403 Forbidden");
+    }
+    if !origin_allowed(handshake.origin.as_deref()) {
+        return Err("403 Forbidden");
+    }
+    match &handshake.key {
+        Some(key) if handshake.upgrade && handshake.version_13 => Ok(accept_key(key)),
+        _ => Err("400 Bad Request"),
+    }
+}
+
+fn serve_client(
+    stream: TcpStream,
+    port: u16,
+    commands: &Sender<WebUiCommand>,
+    clients: &Mutex<Clients>,
+) -> Result<()> {
+    stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+    let mut writer = stream.try_clone()?;
+    let mut reader = BufReader::new(stream);
+    let handshake = read_handshake(&mut reader)?;
+    let accept = match check_handshake(&handshake, port) {
+        Ok(accept) => accept,
+        Err(status) => {
+            writer.write_all(
+                format!("HTTP/1.1 {status}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+                    .as_bytes(),
+            )?;
+            return Ok(());
+        }
+    };
+    writer.write_all(
+        format!(
+            "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {accept}\r\n\r\n"
+        )
+        .as_bytes(),
+    )?;
+    reader.get_ref().set_read_timeout(None)?;
+
+    let (frames_tx, frames_rx) = bounded::<Vec<u8>>(CLIENT_BUFFER_FRAMES);
+    if let Ok(mut clients) = clients.lock() {
+        if let Some(status) = clients.status.clone() {
+            let _ = frames_tx.try_send(status);
+        }
+        clients.senders.push(frames_tx.clone());
+    }
+    thread::spawn(move || {
+        for frame in frames_rx {
+            let closing = frame.first() == Some(&(0x80 | OPCODE_CLOSE));
+            if writer.write_all(&frame).is_err() || closing {
+                break;
+            }
+        }
+        let _ = writer.shutdown(Shutdown::Both);
+    });
+
+    // A read error is the client going away; the writer exits on its next write.
+    while let Ok(frame) = read_frame(&mut reader) {
+        match frame {
+            Frame::Text(text) => {
+                let reply = match serde_json::from_str::<BridgeRequest>(&text) {
+                    Ok(request) => match commands.try_send(request.command()) {
+                        Ok(()) => continue,
+                        Err(_) => "voiceterm is busy".to_string(),
+                    },
+                    Err(err) => format!("invalid command: {err}"),
+                };
+                let error = serde_json::json!({ "event": "error", "message": reply });
+                if let Some(frame) = text_frame(&error) {
+                    let _ = frames_tx.try_send(frame);
+                }
+            }
+            Frame::Ping(payload) => {
+                let _ = frames_tx.try_send(encode_frame(OPCODE_PONG, &payload));
+            }
+            Frame::Pong => {}
+            Frame::Close => {
+                let _ = frames_tx.try_send(encode_frame(OPCODE_CLOSE, &[]));
+                break;
+            }
+        }
+    }
+    let _ = reader.get_ref().shutdown(Shutdown::Read);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::status_line::RecordingState;
+
+    fn handshake(host: &str, origin: Option<&str>) -> Handshake {
+        Handshake {
+            host: Some(host.to_string()),
+            origin: origin.map(str::to_string),
+            key: Some("dGhlIHNhbXBsZSBub25jZQ==".to_string()),
+            upgrade: true,
+            version_13: true,
+        }
+    }
+
+    /// Read one unmasked server frame and return its payload as text.
+    fn read_server_text(stream: &mut impl Read) -> String {
+        let mut head = [0u8; 2];
+        stream.read_exact(&mut head).unwrap();
+        assert_eq!(head[0], 0x80 | OPCODE_TEXT);
+        let len = match head[1] {
+            126 => {
+                let mut bytes = [0u8; 2];
+                stream.read_exact(&mut bytes).unwrap();
+                usize::from(u16::from_be_bytes(bytes))
+            }
+            len => usize::from(len),
+        };
+        let mut payload = vec![0u8; len];
+        stream.read_exact(&mut payload).unwrap();
+        String::from_utf8(payload).unwrap()
+    }
+
+    fn masked_text(text: &str) -> Vec<u8> {
+        let mut frame = vec![0x80 | OPCODE_TEXT, 0x80 | text.len() as u8, 0, 0, 0, 0];
+        frame.extend_from_slice(text.as_bytes());
+        frame
+    }
+
+    #[test]
+    fn handshake_requires_loopback_host_and_origin() {
+        assert_eq!(
+            check_handshake(&handshake("127.0.0.1:7700", None), 7700).unwrap(),
+            "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+        );
+        for origin in [
+            "http://localhost:5173",
+            "https://127.0.0.1",
+            "vscode-webview://abc",
+        ] {
+            assert!(
+                check_handshake(&handshake("localhost:7700", Some(origin)), 7700).is_ok(),
+                "{origin}"
+            );
+        }
+        assert_eq!(
+            check_handshake(&handshake("evil.example:7700", None), 7700),
+            Err("403 Forbidden")
+        );
+        assert_eq!(
+            check_handshake(
+                &handshake("127.0.0.1:7700", Some("https://evil.example")),
+                7700
+            ),
+            Err("403 Forbidden")
+        );
+        let mut plain_http = handshake("127.0.0.1:7700", None);
+        plain_http.upgrade = false;
+        assert_eq!(check_handshake(&plain_http, 7700), Err("400 Bad Request"));
+    }
+
+    #[test]
+    fn bind_rejects_non_loopback_addresses() {
+        let err = WsBridge::bind("0.0.0.0:0".parse().unwrap())
+            .err()
+            .expect("not loopback");
+        assert!(err.to_string().contains("loopback"), "{err}");
+    }
+
+    #[test]
+    fn clients_receive_status_and_send_commands() {
+        let mut bridge = WsBridge::bind("127.0.0.1:0".parse().unwrap()).expect("bind bridge");
+        let mut status_state = StatusLineState::new();
+        status_state.recording_state = RecordingState::Recording;
+        bridge.publish(&status_state, &SessionStats::new());
+
+        let mut stream = TcpStream::connect(bridge.addr).unwrap();
+        stream
+            .write_all(
+                format!(
+                    "GET / HTTP/1.1\r\nHost: 127.0.0.1:{}\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\nSec-WebSocket-Version: 13\r\n\r\n",
+                    bridge.addr.port()
+                )
+                .as_bytes(),
+            )
+            .unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        assert!(line.starts_with("HTTP/1.1 101"), "{line}");
+        while line != "\r\n" {
+            line.clear();
+            reader.read_line(&mut line).unwrap();
+        }
+
+        let status = read_server_text(&mut reader);
+        assert!(
+            status.starts_with(r#"{"event":"status","recording":"recording""#),
+            "{status}"
+        );
+
+        stream.write_all(&masked_text(r#"{"cmd":"stop"}"#)).unwrap();
+        stream.write_all(&masked_text(r#"{"cmd":"nope"}"#)).unwrap();
+        let error = read_server_text(&mut reader);
+        assert!(error.contains("invalid command"), "{error}");
+        assert_eq!(bridge.try_next(), Some(WebUiCommand::Stop));
+
+        status_state.queue_depth = 2;
+        bridge.publish(&status_state, &SessionStats::new());
+        let status = read_server_text(&mut reader);
+        assert!(status.contains("\"queue_depth\":2"), "{status}");
+
+        bridge
+            .session_events()
+            .on_event(&VoiceSessionEvent::Partial {
+                text: "run the".to_string(),
+            });
+        assert_eq!(
+            read_server_text(&mut reader),
+            r#"{"event":"partial","text":"run the"}"#
+        );
+    }
+}
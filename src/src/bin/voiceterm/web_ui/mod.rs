@@ -0,0 +1,16 @@
+//! Localhost web page for watching and driving the overlay from a browser.
+//!
+//! With `--web-ui` (or `--web-ui-port PORT`) the overlay serves a small page on
+//! `127.0.0.1` that polls a JSON status snapshot (recording state, queue, recent
+//! transcripts, session counts) and has buttons to start or cancel a capture.
+//! Button presses go through the event loop just like the keyboard shortcuts.
+//! `--ws-listen` adds a WebSocket bridge that pushes the same state as events
+//! and takes the same commands, for IDE extensions and dashboards.
+
+mod bridge;
+mod server;
+mod status;
+mod websocket;
+
+pub(crate) use bridge::WsBridge;
+pub(crate) use server::{WebUiCommand, WebUiServer};
@@ -0,0 +1,357 @@
+//! Minimal HTTP listener for the web UI, bound to the loopback interface only.
+
+use anyhow::{Context, Result};
+use crossbeam_channel::{bounded, Receiver, Sender, TrySendError};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use voiceterm::log_warn;
+
+use crate::config::OverlayConfig;
+use crate::session_stats::SessionStats;
+use crate::status_line::StatusLineState;
+
+use super::status::{RecentTranscripts, WebUiStatus};
+
+const DEFAULT_WEB_UI_PORT: u16 = 8765;
+const COMMAND_CHANNEL_CAPACITY: usize = 4;
+/// Longest request head accepted; the page never sends a body.
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(5);
+/// Header the page sets on button presses. Cross-site forms cannot set it, and
+/// cross-site `fetch` needs a CORS preflight this server never grants.
+const COMMAND_HEADER: &str = "x-voiceterm";
+const PAGE: &str = include_str!("page.html");
+
+/// A button press waiting for the event loop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum WebUiCommand {
+    /// Start a manual capture, as the record hotkey does.
+    Capture,
+    /// Stop recording and transcribe what was heard (WebSocket bridge `stop`).
+    Stop,
+    /// Cancel the capture in progress.
+    Cancel,
+}
+
+/// Listening web UI; commands are polled and status is published from the event loop.
+pub(crate) struct WebUiServer {
+    addr: SocketAddr,
+    commands: Receiver<WebUiCommand>,
+    status: Arc<Mutex<WebUiStatus>>,
+    recent: RecentTranscripts,
+}
+
+impl WebUiServer {
+    /// Start listening when `--web-ui` or `--web-ui-port` is set.
+    pub(crate) fn from_config(config: &OverlayConfig) -> Result<Option<Self>> {
+        let port = match (config.web_ui_port, config.web_ui) {
+            (Some(port), _) => port,
+            (None, true) => DEFAULT_WEB_UI_PORT,
+            (None, false) => return Ok(None),
+        };
+        Self::bind(port).map(Some)
+    }
+
+    fn bind(port: u16) -> Result<Self> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+            .with_context(|| format!("failed to listen on 127.0.0.1:{port}"))?;
+        let addr = listener.local_addr()?;
+        let (commands_tx, commands) = bounded(COMMAND_CHANNEL_CAPACITY);
+        let status = Arc::new(Mutex::new(WebUiStatus::default()));
+        let shared = Arc::clone(&status);
+        thread::spawn(move || accept_clients(listener, addr.port(), commands_tx, shared));
+        Ok(Self {
+            addr,
+            commands,
+            status,
+            recent: RecentTranscripts::default(),
+        })
+    }
+
+    /// Page URL for the debug log.
+    pub(crate) fn url(&self) -> String {
+        format!("http://{}/", self.addr)
+    }
+
+    /// Next pending button press, without blocking.
+    pub(crate) fn try_next(&self) -> Option<WebUiCommand> {
+        self.commands.try_recv().ok()
+    }
+
+    /// Replace the snapshot served at `/status`.
+    pub(crate) fn publish(&mut self, status_state: &StatusLineState, stats: &SessionStats) {
+        let snapshot = self.recent.snapshot(status_state, stats);
+        if let Ok(mut status) = self.status.lock() {
+            if *status != snapshot {
+                *status = snapshot;
+            }
+        }
+    }
+}
+
+fn accept_clients(
+    listener: TcpListener,
+    port: u16,
+    commands: Sender<WebUiCommand>,
+    status: Arc<Mutex<WebUiStatus>>,
+) {
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                let commands = commands.clone();
+                let status = Arc::clone(&status);
+                thread::spawn(move || {
+                    if let Err(err) = serve_client(stream, port, &commands, &status) {
+                        log_warn(&format!("web ui client error: {err:#}"));
+                    }
+                });
+            }
+            Err(err) => log_warn(&format!("web ui accept failed: {err}")),
+        }
+    }
+}
+
+/// Request line and the headers the router looks at.
+#[derive(Debug, Default)]
+struct Request {
+    method: String,
+    path: String,
+    host: Option<String>,
+    command_header: bool,
+}
+
+struct Response {
+    status: &'static str,
+    content_type: &'static str,
+    body: String,
+}
+
+impl Response {
+    fn text(status: &'static str, body: &str) -> Self {
+        Self {
+            status,
+            content_type: "text/plain; charset=utf-8",
+            body: body.to_string(),
+        }
+    }
+}
+
+fn serve_client(
+    stream: TcpStream,
+    port: u16,
+    commands: &Sender<WebUiCommand>,
+    status: &Mutex<WebUiStatus>,
+) -> Result<()> {
+    stream.set_read_timeout(Some(CLIENT_TIMEOUT))?;
+    let mut writer = stream.try_clone()?;
+    let response = match read_request(stream) {
+        Ok(request) => route(&request, port, commands, status),
+        Err(err) => Response::text("400 Bad Request", &format!("{err:#}")),
+    };
+    let head = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nX-Frame-Options: DENY\r\nConnection: close\r\n\r\n",
+        response.status,
+        response.content_type,
+        response.body.len()
+    );
+    writer.write_all(head.as_bytes())?;
+    writer.write_all(response.body.as_bytes())?;
+    Ok(())
+}
+
+fn read_request(stream: impl Read) -> Result<Request> {
+    let mut reader = BufReader::new(stream.take(MAX_REQUEST_BYTES as u64));
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+        anyhow::bail!("malformed request line");
+    };
+    let mut request = Request {
+        method: method.to_string(),
+        path: path.to_string(),
+        ..Request::default()
+    };
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            anyhow::bail!("request headers exceed {MAX_REQUEST_BYTES} bytes or were cut off");
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            return Ok(request);
+        }
+        let Some((name, value)) = header.split_once(':') else {
+            continue;
+        };
+        let name = name.trim().to_ascii_lowercase();
+        if name == "host" {
+            request.host = Some(value.trim().to_string());
+        } else if name == COMMAND_HEADER {
+            request.command_header = true;
+        }
+    }
+}
+
+/// Only loopback host names are served, so a rebound DNS name cannot reach the page.
+fn host_allowed(host: Option<&str>, port: u16) -> bool {
+    host.is_some_and(|host| {
+        ["127.0.0.1", "localhost"]
+            .iter()
+            .any(|name| host == format!("{name}:{port}"))
+    })
+}
+
+fn route(
+    request: &Request,
+    port: u16,
+    commands: &Sender<WebUiCommand>,
+    status: &Mutex<WebUiStatus>,
+) -> Response {
+    if !host_allowed(request.host.as_deref(), port) {
+        return Response::text("403 Forbidden", "host not allowed");
+    }
+    match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/") => Response {
+            status: "200 OK",
+            content_type: "text/html; charset=utf-8",
+            body: PAGE.to_string(),
+        },
+        ("GET", "/status") => {
+            let snapshot = status
+                .lock()
+                .map(|status| status.clone())
+                .unwrap_or_default();
+            Response {
+                status: "200 OK",
+                content_type: "application/json",
+                body: serde_json::to_string(&snapshot).unwrap_or_else(|_| "{}".to_string()),
+            }
+        }
+        ("POST", "/capture" | "/cancel") if !request.command_header => {
+            Response::text("403 Forbidden", "missing X-VoiceTerm header")
+        }
+        ("POST", path @ ("/capture" | "/cancel")) => {
+            let command = if path == "/capture" {
+                WebUiCommand::Capture
+            } else {
+                WebUiCommand::Cancel
+            };
+            match commands.try_send(command) {
+                Ok(()) => Response::text("202 Accepted", "ok"),
+                Err(TrySendError::Full(_)) => Response::text("503 Service Unavailable", "busy"),
+                Err(TrySendError::Disconnected(_)) => {
+                    Response::text("503 Service Unavailable", "voiceterm is exiting")
+                }
+            }
+        }
+        (_, "/" | "/status" | "/capture" | "/cancel") => {
+            Response::text("405 Method Not Allowed", "method not allowed")
+        }
+        _ => Response::text("404 Not Found", "not found"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(method: &str, path: &str, host: &str, command_header: bool) -> Request {
+        Request {
+            method: method.to_string(),
+            path: path.to_string(),
+            host: Some(host.to_string()),
+            command_header,
+        }
+    }
+
+    #[test]
+    fn from_config_listens_only_when_requested() {
+        use clap::Parser;
+
+        let config = OverlayConfig::parse_from(["voiceterm"]);
+        if config.web_ui_port.is_none() {
+            assert!(WebUiServer::from_config(&config).unwrap().is_none());
+        }
+        let config = OverlayConfig::parse_from(["voiceterm", "--web-ui-port", "0"]);
+        let server = WebUiServer::from_config(&config)
+            .unwrap()
+            .expect("web ui listening");
+        assert!(server.url().starts_with("http://127.0.0.1:"));
+    }
+
+    #[test]
+    fn router_checks_host_and_command_header() {
+        let (tx, rx) = bounded(COMMAND_CHANNEL_CAPACITY);
+        let status = Mutex::new(WebUiStatus::default());
+        let route = |request: Request| route(&request, 8765, &tx, &status).status;
+
+        assert_eq!(
+            route(request("GET", "/", "127.0.0.1:8765", false)),
+            "200 OK"
+        );
+        assert_eq!(
+            route(request("GET", "/status", "localhost:8765", false)),
+            "200 OK"
+        );
+        assert_eq!(
+            route(request("GET", "/", "evil.example:8765", false)),
+            "403 Forbidden"
+        );
+        assert_eq!(
+            route(request("POST", "/capture", "127.0.0.1:8765", false)),
+            "403 Forbidden"
+        );
+        assert!(rx.try_recv().is_err());
+        assert_eq!(
+            route(request("POST", "/capture", "127.0.0.1:8765", true)),
+            "202 Accepted"
+        );
+        assert_eq!(
+            route(request("POST", "/cancel", "127.0.0.1:8765", true)),
+            "202 Accepted"
+        );
+        assert_eq!(rx.try_recv().ok(), Some(WebUiCommand::Capture));
+        assert_eq!(rx.try_recv().ok(), Some(WebUiCommand::Cancel));
+        assert_eq!(
+            route(request("GET", "/capture", "127.0.0.1:8765", true)),
+            "405 Method Not Allowed"
+        );
+        assert_eq!(
+            route(request("GET", "/nope", "127.0.0.1:8765", false)),
+            "404 Not Found"
+        );
+    }
+
+    #[test]
+    fn status_and_commands_round_trip_over_http() {
+        let mut server = WebUiServer::bind(0).expect("bind web ui");
+        let addr = server.addr;
+        let port = addr.port();
+        let exchange = |raw: &str| {
+            let mut stream = TcpStream::connect(addr).unwrap();
+            stream.write_all(raw.as_bytes()).unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        };
+
+        let mut status_state = StatusLineState::new();
+        status_state.queue_depth = 3;
+        server.publish(&status_state, &SessionStats::new());
+        let response = exchange(&format!(
+            "GET /status HTTP/1.1\r\nHost: 127.0.0.1:{port}\r\n\r\n"
+        ));
+        assert!(response.starts_with("HTTP/1.1 200 OK"), "{response}");
+        assert!(response.contains("\"queue_depth\":3"), "{response}");
+
+        let response = exchange(&format!(
+            "POST /capture HTTP/1.1\r\nHost: localhost:{port}\r\nX-VoiceTerm: 1\r\nContent-Length: 0\r\n\r\n"
+        ));
+        assert!(response.starts_with("HTTP/1.1 202"), "{response}");
+        assert_eq!(server.try_next(), Some(WebUiCommand::Capture));
+    }
+}
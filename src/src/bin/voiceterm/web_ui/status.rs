@@ -0,0 +1,119 @@
+//! Status snapshot the web page polls, rebuilt by the event loop each tick.
+
+use serde::Serialize;
+use std::collections::VecDeque;
+
+use crate::session_record::send_mode_key;
+use crate::session_stats::SessionStats;
+use crate::status_line::{RecordingState, StatusLineState};
+
+/// Transcripts kept for the page; older ones drop off the front.
+const RECENT_TRANSCRIPTS_MAX: usize = 8;
+
+/// JSON body of `GET /status`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub(crate) struct WebUiStatus {
+    pub(crate) recording: &'static str,
+    pub(crate) recording_secs: Option<f32>,
+    pub(crate) send_mode: &'static str,
+    pub(crate) auto_voice: bool,
+    pub(crate) queue_depth: usize,
+    pub(crate) message: String,
+    pub(crate) last_latency_ms: Option<u32>,
+    pub(crate) transcripts: u32,
+    pub(crate) empty_captures: u32,
+    pub(crate) errors: u32,
+    /// Newest last, as shown in the HUD preview.
+    pub(crate) recent_transcripts: Vec<String>,
+}
+
+/// Remembers recent transcript previews between snapshots.
+#[derive(Debug, Default)]
+pub(super) struct RecentTranscripts {
+    previews: VecDeque<String>,
+    seen: u32,
+}
+
+impl RecentTranscripts {
+    /// Snapshot the overlay, picking up the preview of any transcript counted since last time.
+    pub(super) fn snapshot(
+        &mut self,
+        status_state: &StatusLineState,
+        stats: &SessionStats,
+    ) -> WebUiStatus {
+        if stats.transcripts != self.seen {
+            self.seen = stats.transcripts;
+            if let Some(preview) = status_state.transcript_preview.as_ref() {
+                if self.previews.len() == RECENT_TRANSCRIPTS_MAX {
+                    self.previews.pop_front();
+                }
+                self.previews.push_back(preview.clone());
+            }
+        }
+        WebUiStatus {
+            recording: recording_key(status_state.recording_state),
+            recording_secs: status_state.recording_duration,
+            send_mode: send_mode_key(status_state.send_mode),
+            auto_voice: status_state.auto_voice_enabled,
+            queue_depth: status_state.queue_depth,
+            message: status_state.message.clone(),
+            last_latency_ms: status_state.last_latency_ms,
+            transcripts: stats.transcripts,
+            empty_captures: stats.empty_captures,
+            errors: stats.errors,
+            recent_transcripts: self.previews.iter().cloned().collect(),
+        }
+    }
+}
+
+pub(super) fn recording_key(state: RecordingState) -> &'static str {
+    match state {
+        RecordingState::Idle => "idle",
+        RecordingState::Recording => "recording",
+        RecordingState::Processing => "processing",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_counted_transcript_is_recorded_once_and_capped() {
+        let mut recent = RecentTranscripts::default();
+        let mut status_state = StatusLineState::new();
+        let mut stats = SessionStats::new();
+        status_state.transcript_preview = Some("stale".to_string());
+        assert!(recent
+            .snapshot(&status_state, &stats)
+            .recent_transcripts
+            .is_empty());
+
+        for index in 0..RECENT_TRANSCRIPTS_MAX + 2 {
+            status_state.transcript_preview = Some(format!("line {index}"));
+            stats.record_transcript(1.0);
+            recent.snapshot(&status_state, &stats);
+            recent.snapshot(&status_state, &stats);
+        }
+        let snapshot = recent.snapshot(&status_state, &stats);
+        assert_eq!(snapshot.recent_transcripts.len(), RECENT_TRANSCRIPTS_MAX);
+        assert_eq!(snapshot.recent_transcripts[0], "line 2");
+        assert_eq!(
+            snapshot.recent_transcripts.last().map(String::as_str),
+            Some("line 9")
+        );
+        assert_eq!(snapshot.transcripts, 10);
+    }
+
+    #[test]
+    fn snapshot_reports_recording_state_and_queue() {
+        let mut status_state = StatusLineState::new();
+        status_state.recording_state = RecordingState::Recording;
+        status_state.queue_depth = 2;
+        let snapshot = RecentTranscripts::default().snapshot(&status_state, &SessionStats::new());
+        assert_eq!(snapshot.recording, "recording");
+        assert_eq!(snapshot.queue_depth, 2);
+        let json = serde_json::to_string(&snapshot).unwrap();
+        assert!(json.contains("\"recording\":\"recording\""), "{json}");
+    }
+}
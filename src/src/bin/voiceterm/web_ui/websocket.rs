@@ -0,0 +1,215 @@
+//! RFC 6455 handshake key and framing for the WebSocket bridge.
+//!
+//! Only what the bridge needs: unfragmented text, ping, pong, and close frames,
+//! masked from the client and unmasked from the server.
+
+use anyhow::{bail, Context, Result};
+use std::io::Read;
+
+use crate::transcript::base64_encode;
+
+/// Appended to the client key before hashing, per RFC 6455 section 4.2.2.
+const ACCEPT_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+/// Largest client frame accepted; commands are a few dozen bytes.
+pub(super) const MAX_FRAME_BYTES: usize = 4096;
+
+pub(super) const OPCODE_TEXT: u8 = 0x1;
+pub(super) const OPCODE_CLOSE: u8 = 0x8;
+pub(super) const OPCODE_PONG: u8 = 0xA;
+
+/// A frame read from a client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum Frame {
+    Text(String),
+    Ping(Vec<u8>),
+    Pong,
+    Close,
+}
+
+/// `Sec-WebSocket-Accept` value for a client's `Sec-WebSocket-Key`.
+pub(super) fn accept_key(client_key: &str) -> String {
+    let mut input = client_key.trim().as_bytes().to_vec();
+    input.extend_from_slice(ACCEPT_GUID.as_bytes());
+    base64_encode(&sha1(&input))
+}
+
+/// Read one masked client frame.
+pub(super) fn read_frame(reader: &mut impl Read) -> Result<Frame> {
+    let mut head = [0u8; 2];
+    reader.read_exact(&mut head)?;
+    let fin = head[0] & 0x80 != 0;
+    let opcode = head[0] & 0x0f;
+    let masked = head[1] & 0x80 != 0;
+    let len = match head[1] & 0x7f {
+        126 => {
+            let mut bytes = [0u8; 2];
+            reader.read_exact(&mut bytes)?;
+            u64::from(u16::from_be_bytes(bytes))
+        }
+        127 => {
+            let mut bytes = [0u8; 8];
+            reader.read_exact(&mut bytes)?;
+            u64::from_be_bytes(bytes)
+        }
+        len => u64::from(len),
+    };
+    if !fin {
+        bail!("fragmented frames are not supported");
+    }
+    if !masked {
+        bail!("client frames must be masked");
+    }
+    if len > MAX_FRAME_BYTES as u64 {
+        bail!("frame exceeds {MAX_FRAME_BYTES} bytes");
+    }
+    let mut mask = [0u8; 4];
+    reader.read_exact(&mut mask)?;
+    let mut payload = vec![0u8; len as usize];
+    reader.read_exact(&mut payload)?;
+    for (index, byte) in payload.iter_mut().enumerate() {
+        *byte ^= mask[index % 4];
+    }
+    match opcode {
+        OPCODE_TEXT => String::from_utf8(payload)
+            .map(Frame::Text)
+            .context("text frame is not UTF-8"),
+        OPCODE_CLOSE => Ok(Frame::Close),
+        0x9 => Ok(Frame::Ping(payload)),
+        OPCODE_PONG => Ok(Frame::Pong),
+        other => bail!("unsupported frame opcode {other:#x}"),
+    }
+}
+
+/// One unmasked server frame.
+pub(super) fn encode_frame(opcode: u8, payload: &[u8]) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(payload.len() + 10);
+    frame.push(0x80 | opcode);
+    match payload.len() {
+        len @ 0..=125 => frame.push(len as u8),
+        len @ 126..=0xffff => {
+            frame.push(126);
+            frame.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            frame.push(127);
+            frame.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+    frame.extend_from_slice(payload);
+    frame
+}
+
+fn sha1(data: &[u8]) -> [u8; 20] {
+    let mut state: [u32; 5] = [
+        0x6745_2301,
+        0xEFCD_AB89,
+        0x98BA_DCFE,
+        0x1032_5476,
+        0xC3D2_E1F0,
+    ];
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&((data.len() as u64) * 8).to_be_bytes());
+    for block in message.chunks(64) {
+        let mut words = [0u32; 80];
+        for (word, bytes) in words.iter_mut().zip(block.chunks(4)) {
+            *word = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        }
+        for index in 16..80 {
+            words[index] =
+                (words[index - 3] ^ words[index - 8] ^ words[index - 14] ^ words[index - 16])
+                    .rotate_left(1);
+        }
+        let [mut a, mut b, mut c, mut d, mut e] = state;
+        for (index, word) in words.iter().enumerate() {
+            let (f, k) = match index {
+                0..=19 => ((b & c) | (!b & d), 0x5A82_7999),
+                20..=39 => (b ^ c ^ d, 0x6ED9_EBA1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1B_BCDC),
+                _ => (b ^ c ^ d, 0xCA62_C1D6),
+            };
+            let next = a
+                .rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(*word);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = next;
+        }
+        for (value, add) in state.iter_mut().zip([a, b, c, d, e]) {
+            *value = value.wrapping_add(add);
+        }
+    }
+    let mut digest = [0u8; 20];
+    for (bytes, value) in digest.chunks_mut(4).zip(state) {
+        bytes.copy_from_slice(&value.to_be_bytes());
+    }
+    digest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn masked(opcode: u8, payload: &[u8]) -> Vec<u8> {
+        let mask = [0x37, 0xfa, 0x21, 0x3d];
+        let mut frame = vec![0x80 | opcode, 0x80 | payload.len() as u8];
+        frame.extend_from_slice(&mask);
+        frame.extend(
+            payload
+                .iter()
+                .enumerate()
+                .map(|(index, byte)| byte ^ mask[index % 4]),
+        );
+        frame
+    }
+
+    #[test]
+    fn accept_key_matches_rfc_example() {
+        let hex: String = sha1(b"abc").iter().map(|b| format!("{b:02x}")).collect();
+        assert_eq!(hex, "a9993e364706816aba3e25717850c26c9cd0d89d");
+        assert_eq!(
+            accept_key("dGhlIHNhbXBsZSBub25jZQ=="),
+            "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+        );
+    }
+
+    #[test]
+    fn read_frame_unmasks_client_frames() {
+        let mut bytes = masked(OPCODE_TEXT, br#"{"cmd":"start"}"#);
+        bytes.extend(masked(0x9, b"hi"));
+        bytes.extend(masked(OPCODE_CLOSE, b""));
+        let mut reader = bytes.as_slice();
+        assert_eq!(
+            read_frame(&mut reader).unwrap(),
+            Frame::Text(r#"{"cmd":"start"}"#.to_string())
+        );
+        assert_eq!(
+            read_frame(&mut reader).unwrap(),
+            Frame::Ping(b"hi".to_vec())
+        );
+        assert_eq!(read_frame(&mut reader).unwrap(), Frame::Close);
+
+        let unmasked = encode_frame(OPCODE_TEXT, b"x");
+        let err = read_frame(&mut unmasked.as_slice()).unwrap_err();
+        assert!(err.to_string().contains("masked"), "{err}");
+    }
+
+    #[test]
+    fn encode_frame_uses_extended_lengths() {
+        assert_eq!(encode_frame(OPCODE_TEXT, b"ok"), b"\x81\x02ok");
+        let medium = encode_frame(OPCODE_TEXT, &[b'a'; 300]);
+        assert_eq!(&medium[..4], &[0x81, 126, 0x01, 0x2c]);
+        assert_eq!(medium.len(), 304);
+        let large = encode_frame(OPCODE_TEXT, &vec![b'a'; 70_000]);
+        assert_eq!(large[1], 127);
+        assert_eq!(large.len(), 70_010);
+    }
+}
@@ -3,7 +3,9 @@
 mod mouse;
 mod render;
 mod sanitize;
+mod scroll;
 mod state;
+mod sync;
 
 use crossbeam_channel::{Receiver, Sender};
 use std::thread;
@@ -23,7 +25,7 @@ pub(crate) enum WriterMessage {
         text: String,
     },
     /// Enhanced status line with full state
-    EnhancedStatus(StatusLineState),
+    EnhancedStatus(Box<StatusLineState>),
     /// Overlay panel content (multi-line box)
     ShowOverlay {
         content: String,
@@ -41,6 +43,10 @@ pub(crate) enum WriterMessage {
         cols: u16,
     },
     SetTheme(Theme),
+    /// Print status changes as plain lines instead of drawing the HUD (dumb terminals)
+    SetPlainStatus(bool),
+    /// Reserve the HUD rows with a terminal scroll region (on by default)
+    SetScrollRegion(bool),
     /// Enable mouse tracking for clickable HUD buttons
     EnableMouse,
     /// Disable mouse tracking
@@ -78,11 +84,13 @@ pub(crate) fn set_status(
     clear_after: Option<Duration>,
 ) {
     let same_text = current_status.as_deref() == Some(text);
-    status_state.message = text.to_string();
+    status_state.message = status_state.status_repeat.record(text, Instant::now());
     if !same_text {
-        *current_status = Some(status_state.message.clone());
+        *current_status = Some(text.to_string());
     }
-    let _ = writer_tx.send(WriterMessage::EnhancedStatus(status_state.clone()));
+    let _ = writer_tx.send(WriterMessage::EnhancedStatus(Box::new(
+        status_state.clone(),
+    )));
     *clear_deadline = clear_after.map(|duration| Instant::now() + duration);
 }
 
@@ -90,7 +98,9 @@ pub(crate) fn send_enhanced_status(
     writer_tx: &Sender<WriterMessage>,
     status_state: &StatusLineState,
 ) {
-    let _ = writer_tx.send(WriterMessage::EnhancedStatus(status_state.clone()));
+    let _ = writer_tx.send(WriterMessage::EnhancedStatus(Box::new(
+        status_state.clone(),
+    )));
 }
 
 #[cfg(test)]
@@ -132,4 +142,31 @@ mod tests {
         );
         assert!(deadline.is_none());
     }
+
+    #[test]
+    fn set_status_coalesces_repeats_with_a_count() {
+        let (tx, rx) = crossbeam_channel::unbounded();
+        let mut deadline = None;
+        let mut current_status = None;
+        let mut status_state = StatusLineState::new();
+        for _ in 0..4 {
+            set_status(
+                &tx,
+                &mut deadline,
+                &mut current_status,
+                &mut status_state,
+                "No speech detected",
+                None,
+            );
+        }
+        assert_eq!(status_state.message, "No speech detected ×4");
+        assert_eq!(current_status.as_deref(), Some("No speech detected"));
+        let last = rx.try_iter().last().expect("status messages");
+        match last {
+            WriterMessage::EnhancedStatus(state) => {
+                assert_eq!(state.message, "No speech detected ×4")
+            }
+            _ => panic!("unexpected writer message"),
+        }
+    }
 }
@@ -119,14 +119,14 @@ fn should_disable_autowrap_during_redraw() -> bool {
     is_jetbrains_terminal()
 }
 
-fn push_cursor_prefix(sequence: &mut Vec<u8>) {
+pub(super) fn push_cursor_prefix(sequence: &mut Vec<u8>) {
     sequence.extend_from_slice(save_cursor_sequence());
     if should_disable_autowrap_during_redraw() {
         sequence.extend_from_slice(WRAP_DISABLE);
     }
 }
 
-fn push_cursor_suffix(sequence: &mut Vec<u8>) {
+pub(super) fn push_cursor_suffix(sequence: &mut Vec<u8>) {
     if should_disable_autowrap_during_redraw() {
         sequence.extend_from_slice(WRAP_ENABLE);
     }
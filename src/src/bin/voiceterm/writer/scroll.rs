@@ -0,0 +1,131 @@
+//! Scroll-region reservation so backend output cannot scroll the HUD away.
+//!
+//! The writer sets the terminal's scroll margins (DECSTBM, `CSI 1 ; <bottom> r`)
+//! to the rows above the HUD. A backend printing past its last row then
+//! scrolls only its own rows instead of pushing the status line up and racing
+//! the next repaint. The region follows HUD height and terminal size, is set
+//! again when the backend resets margins (`CSI r` or a full reset), and is
+//! cleared on exit.
+
+use voiceterm::terminal_restore::note_scroll_region;
+
+use super::render::{push_cursor_prefix, push_cursor_suffix};
+
+const RESET_MARGINS: &[u8] = b"\x1b[r";
+const FULL_RESET: &[u8] = b"\x1bc";
+
+#[derive(Debug, Default)]
+pub(super) struct ScrollRegion {
+    enabled: bool,
+    /// Bottom margin currently set on the terminal; `None` is the full screen.
+    applied: Option<u16>,
+    /// Trailing bytes that may be the start of a reset split across chunks.
+    partial: Vec<u8>,
+}
+
+impl ScrollRegion {
+    pub(super) fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Scan PTY output for sequences that put the margins back to the full screen.
+    pub(super) fn observe(&mut self, bytes: &[u8]) {
+        if self.applied.is_none() || (self.partial.is_empty() && !bytes.contains(&0x1b)) {
+            self.partial.clear();
+            return;
+        }
+        let mut buf = std::mem::take(&mut self.partial);
+        buf.extend_from_slice(bytes);
+        for (idx, _) in buf.iter().enumerate().filter(|(_, &byte)| byte == 0x1b) {
+            let rest = &buf[idx..];
+            if rest.starts_with(RESET_MARGINS) || rest.starts_with(FULL_RESET) {
+                self.applied = None;
+            } else if rest.len() < RESET_MARGINS.len() && RESET_MARGINS.starts_with(rest) {
+                self.partial = rest.to_vec();
+                break;
+            }
+        }
+    }
+
+    /// The terminal dropped its margins (e.g. on resize); set them again on the next repaint.
+    pub(super) fn invalidate(&mut self) {
+        self.applied = None;
+    }
+
+    /// Bytes that reserve the bottom `reserved` rows of a `rows`-high terminal, if that changed.
+    pub(super) fn update(&mut self, rows: u16, reserved: usize) -> Option<Vec<u8>> {
+        let wanted = u16::try_from(reserved)
+            .ok()
+            .filter(|reserved| self.enabled && *reserved > 0 && *reserved < rows)
+            .map(|reserved| rows - reserved);
+        if wanted == self.applied {
+            return None;
+        }
+        self.applied = wanted;
+        note_scroll_region(wanted.is_some());
+        Some(margins_sequence(wanted))
+    }
+
+    /// Bytes that give the whole screen back, for shutdown.
+    pub(super) fn release(&mut self) -> Option<Vec<u8>> {
+        self.enabled = false;
+        self.update(0, 0)
+    }
+}
+
+/// Set the margins to rows `1..=bottom` (or the full screen) without moving the cursor.
+fn margins_sequence(bottom: Option<u16>) -> Vec<u8> {
+    let mut sequence = Vec::new();
+    // DECSTBM homes the cursor, so keep the backend's cursor where it was.
+    push_cursor_prefix(&mut sequence);
+    match bottom {
+        Some(bottom) => sequence.extend_from_slice(format!("\x1b[1;{bottom}r").as_bytes()),
+        None => sequence.extend_from_slice(RESET_MARGINS),
+    }
+    push_cursor_suffix(&mut sequence);
+    sequence
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enabled() -> ScrollRegion {
+        let mut region = ScrollRegion::default();
+        region.set_enabled(true);
+        region
+    }
+
+    #[test]
+    fn update_reserves_hud_rows_only_when_they_change() {
+        let mut region = enabled();
+        let sequence = region.update(24, 2).expect("region set");
+        assert!(String::from_utf8_lossy(&sequence).contains("\x1b[1;22r"));
+        assert_eq!(region.update(24, 2), None);
+        let sequence = region.update(24, 0).expect("region cleared");
+        assert!(String::from_utf8_lossy(&sequence).contains("\x1b[r"));
+        assert_eq!(region.update(24, 0), None);
+        // A HUD as tall as the terminal leaves nothing to scroll.
+        assert_eq!(region.update(3, 3), None);
+
+        let mut disabled = ScrollRegion::default();
+        assert_eq!(disabled.update(24, 2), None);
+    }
+
+    #[test]
+    fn backend_margin_resets_are_set_again() {
+        let mut region = enabled();
+        region.update(24, 1).expect("region set");
+        region.observe(b"\x1b[1;10rscrolling app\x1b[2J");
+        assert_eq!(region.update(24, 1), None);
+        region.observe(b"done\x1b[");
+        region.observe(b"r\r\n");
+        assert!(region.update(24, 1).is_some());
+        region.observe(b"\x1bc");
+        assert!(region.update(24, 1).is_some());
+        region.invalidate();
+        assert!(region.update(24, 1).is_some());
+        assert!(region.release().is_some());
+        assert_eq!(region.release(), None);
+    }
+}
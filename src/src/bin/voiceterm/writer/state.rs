@@ -8,6 +8,9 @@ use super::render::{
     clear_overlay_panel, clear_status_banner, clear_status_line, write_overlay_panel,
     write_status_banner, write_status_line,
 };
+use super::sanitize::sanitize_status;
+use super::scroll::ScrollRegion;
+use super::sync::SyncUpdateTracker;
 use super::WriterMessage;
 use crate::status_line::{format_status_banner, StatusLineState};
 use crate::theme::Theme;
@@ -73,8 +76,12 @@ pub(super) struct WriterState {
     last_output_at: Instant,
     last_output_flush_at: Instant,
     last_status_draw_at: Instant,
+    sync_update: SyncUpdateTracker,
+    scroll_region: ScrollRegion,
     theme: Theme,
     mouse_enabled: bool,
+    plain_status: bool,
+    last_plain_status: String,
 }
 
 impl WriterState {
@@ -89,8 +96,12 @@ impl WriterState {
             last_output_at: Instant::now(),
             last_output_flush_at: Instant::now(),
             last_status_draw_at: Instant::now(),
+            sync_update: SyncUpdateTracker::default(),
+            scroll_region: ScrollRegion::default(),
             theme: Theme::default(),
             mouse_enabled: false,
+            plain_status: false,
+            last_plain_status: String::new(),
         }
     }
 
@@ -103,6 +114,8 @@ impl WriterState {
                 }
                 let now = Instant::now();
                 self.last_output_at = now;
+                self.sync_update.observe(&bytes, now);
+                self.scroll_region.observe(&bytes);
                 if self.display.has_any() {
                     // PTY output may scroll/overwrite the HUD rows even if banner text did not
                     // change; force a full banner repaint on the next redraw.
@@ -131,7 +144,7 @@ impl WriterState {
                 self.maybe_redraw_status();
             }
             WriterMessage::EnhancedStatus(state) => {
-                self.pending.enhanced_status = Some(state);
+                self.pending.enhanced_status = Some(*state);
                 self.pending.status = None;
                 self.pending.clear_status = false;
                 self.needs_redraw = true;
@@ -192,6 +205,8 @@ impl WriterState {
                 }
                 self.rows = rows;
                 self.cols = cols;
+                // Terminals drop scroll margins on resize; the next repaint sets them again.
+                self.scroll_region.invalidate();
                 if self.display.has_any() || self.pending.has_any() {
                     self.needs_redraw = true;
                 }
@@ -203,15 +218,28 @@ impl WriterState {
                     self.needs_redraw = true;
                 }
             }
+            WriterMessage::SetPlainStatus(plain) => {
+                self.plain_status = plain;
+            }
+            WriterMessage::SetScrollRegion(enabled) => {
+                self.scroll_region.set_enabled(enabled);
+                self.needs_redraw = true;
+            }
             WriterMessage::EnableMouse => {
-                enable_mouse(&mut self.stdout, &mut self.mouse_enabled);
+                if !self.plain_status {
+                    enable_mouse(&mut self.stdout, &mut self.mouse_enabled);
+                }
             }
             WriterMessage::DisableMouse => {
                 disable_mouse(&mut self.stdout, &mut self.mouse_enabled);
             }
             WriterMessage::Shutdown => {
-                // Disable mouse before exiting to restore terminal state
+                // Disable mouse and give the scroll region back before exiting
                 disable_mouse(&mut self.stdout, &mut self.mouse_enabled);
+                if let Some(sequence) = self.scroll_region.release() {
+                    let _ = self.stdout.write_all(&sequence);
+                    let _ = self.stdout.flush();
+                }
                 return false;
             }
         }
@@ -231,6 +259,14 @@ impl WriterState {
         {
             return;
         }
+        // Painting inside the backend's synchronized update would tear its frame.
+        if self.sync_update.defers_repaint(Instant::now()) {
+            return;
+        }
+        if self.plain_status {
+            self.write_plain_status();
+            return;
+        }
         if self.rows == 0 || self.cols == 0 {
             if let Ok((c, r)) = terminal_size() {
                 self.rows = r;
@@ -286,6 +322,7 @@ impl WriterState {
             let theme = self.theme;
             let (
                 stdout,
+                scroll_region,
                 overlay_panel,
                 enhanced_status,
                 status,
@@ -294,6 +331,7 @@ impl WriterState {
                 force_full_banner_redraw,
             ) = (
                 &mut self.stdout,
+                &mut self.scroll_region,
                 &self.display.overlay_panel,
                 &self.display.enhanced_status,
                 &self.display.status,
@@ -326,6 +364,15 @@ impl WriterState {
                 current_banner_lines.clear();
                 *force_full_banner_redraw = true;
             }
+            let reserved = match (overlay_panel, enhanced_status, status) {
+                (Some(panel), _, _) => panel.height,
+                (None, Some(_), _) => *current_banner_height,
+                (None, None, Some(_)) => 1,
+                (None, None, None) => 0,
+            };
+            if let Some(sequence) = scroll_region.update(rows, reserved) {
+                let _ = stdout.write_all(&sequence);
+            }
             stdout.flush().err()
         };
         self.needs_redraw = false;
@@ -334,6 +381,58 @@ impl WriterState {
             log_debug(&format!("status redraw flush failed: {err}"));
         }
     }
+
+    /// Plain-mode redraw: print new status messages and overlays as text lines, no escapes.
+    fn write_plain_status(&mut self) {
+        let lines = plain_status_lines(&mut self.pending, &mut self.last_plain_status);
+        self.needs_redraw = false;
+        self.last_status_draw_at = Instant::now();
+        if lines.is_empty() {
+            return;
+        }
+        let text = format!("\r\n{}\r\n", lines.join("\r\n"));
+        if let Err(err) = self
+            .stdout
+            .write_all(text.as_bytes())
+            .and_then(|()| self.stdout.flush())
+        {
+            log_debug(&format!("plain status write failed: {err}"));
+        }
+    }
+}
+
+/// Lines plain mode prints for the pending updates; repeats of the last status are dropped.
+fn plain_status_lines(pending: &mut PendingState, last_status: &mut String) -> Vec<String> {
+    let mut lines = Vec::new();
+    if let Some(panel) = pending.overlay_panel.take() {
+        let content = strip_ansi_escapes::strip_str(&panel.content);
+        lines.extend(
+            content
+                .lines()
+                .map(|line| sanitize_status(line).trim_end().to_string())
+                .filter(|line| !line.trim().is_empty()),
+        );
+    }
+    let message = pending
+        .enhanced_status
+        .take()
+        .map(|state| state.message)
+        .or_else(|| pending.status.take());
+    if pending.clear_status {
+        last_status.clear();
+    }
+    pending.clear_status = false;
+    pending.clear_overlay = false;
+    if let Some(message) = message {
+        let message = sanitize_status(&message).trim().to_string();
+        if message != *last_status {
+            if !message.is_empty() {
+                lines.push(format!("voiceterm: {message}"));
+            }
+            *last_status = message;
+        }
+    }
+    lines
 }
 
 #[cfg(test)]
@@ -369,6 +468,48 @@ mod tests {
         assert_eq!(state.cols, 100);
     }
 
+    #[test]
+    fn redraw_waits_for_synchronized_update_to_end() {
+        let mut state = WriterState::new();
+        state.rows = 24;
+        state.cols = 80;
+        state.last_output_at = Instant::now() - Duration::from_secs(1);
+        state.sync_update.observe(b"\x1b[?2026h", Instant::now());
+        state.needs_redraw = true;
+        state.maybe_redraw_status();
+        assert!(state.needs_redraw);
+    }
+
+    #[test]
+    fn plain_status_prints_new_messages_and_overlays_as_text() {
+        let mut pending = PendingState::default();
+        let mut last = String::new();
+        let mut status = StatusLineState::new();
+        status.message = "Transcript ready".to_string();
+        pending.enhanced_status = Some(status.clone());
+        assert_eq!(
+            plain_status_lines(&mut pending, &mut last),
+            vec!["voiceterm: Transcript ready"]
+        );
+        pending.enhanced_status = Some(status);
+        assert!(
+            plain_status_lines(&mut pending, &mut last).is_empty(),
+            "repeats are not printed"
+        );
+
+        pending.clear_status = true;
+        pending.overlay_panel = Some(OverlayPanel {
+            content: "\x1b[1m+ Help +\x1b[0m\n\n| ? close |".to_string(),
+            height: 3,
+        });
+        assert_eq!(
+            plain_status_lines(&mut pending, &mut last),
+            vec!["+ Help +", "| ? close |"]
+        );
+        assert!(last.is_empty());
+        assert!(!pending.has_any());
+    }
+
     #[test]
     fn status_clear_height_only_when_banner_shrinks() {
         assert_eq!(status_clear_height_for_redraw(4, 4), 0);
@@ -0,0 +1,104 @@
+//! Synchronized-output tracking so HUD repaints never land inside a backend's atomic frame.
+//!
+//! Backends such as Codex wrap redraws in DEC mode 2026 (`CSI ? 2026 h` ... `CSI ? 2026 l`).
+//! Writing the status row between those markers tears the frame, so the writer
+//! defers repaints while a block is open. Blocks that never close stop deferring
+//! after `SYNC_UPDATE_MAX_DEFER_MS`, matching how terminals time them out.
+
+use std::time::{Duration, Instant};
+
+const BEGIN_SYNC_UPDATE: &[u8] = b"\x1b[?2026h";
+const END_SYNC_UPDATE: &[u8] = b"\x1b[?2026l";
+const SYNC_UPDATE_MAX_DEFER_MS: u64 = 200;
+
+/// Tracks whether PTY output is currently inside a synchronized update block.
+#[derive(Debug, Default)]
+pub(super) struct SyncUpdateTracker {
+    active_since: Option<Instant>,
+    /// Trailing bytes that may be the start of a marker split across chunks.
+    partial: Vec<u8>,
+}
+
+impl SyncUpdateTracker {
+    /// Scan a chunk of PTY output for begin/end markers.
+    pub(super) fn observe(&mut self, bytes: &[u8], now: Instant) {
+        if self.partial.is_empty() && !bytes.contains(&0x1b) {
+            return;
+        }
+        let mut buf = std::mem::take(&mut self.partial);
+        buf.extend_from_slice(bytes);
+        for (idx, _) in buf.iter().enumerate().filter(|(_, &byte)| byte == 0x1b) {
+            let rest = &buf[idx..];
+            if rest.starts_with(BEGIN_SYNC_UPDATE) {
+                self.active_since.get_or_insert(now);
+            } else if rest.starts_with(END_SYNC_UPDATE) {
+                self.active_since = None;
+            } else if rest.len() < BEGIN_SYNC_UPDATE.len()
+                && (BEGIN_SYNC_UPDATE.starts_with(rest) || END_SYNC_UPDATE.starts_with(rest))
+            {
+                self.partial = rest.to_vec();
+                break;
+            }
+        }
+    }
+
+    /// Whether status repaints should wait for the block to close.
+    pub(super) fn defers_repaint(&self, now: Instant) -> bool {
+        self.active_since.is_some_and(|since| {
+            now.saturating_duration_since(since) < Duration::from_millis(SYNC_UPDATE_MAX_DEFER_MS)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn begin_and_end_markers_toggle_deferral() {
+        let now = Instant::now();
+        let mut tracker = SyncUpdateTracker::default();
+        tracker.observe(b"plain output\r\n", now);
+        assert!(!tracker.defers_repaint(now));
+
+        tracker.observe(b"\x1b[?2026h\x1b[Hframe", now);
+        assert!(tracker.defers_repaint(now));
+        tracker.observe(b"more frame\x1b[?2026l", now);
+        assert!(!tracker.defers_repaint(now));
+
+        // A complete block inside one chunk leaves nothing open.
+        tracker.observe(b"\x1b[?2026hframe\x1b[?2026l\x1b[?25h", now);
+        assert!(!tracker.defers_repaint(now));
+    }
+
+    #[test]
+    fn markers_split_across_chunks_are_detected() {
+        let now = Instant::now();
+        let mut tracker = SyncUpdateTracker::default();
+        tracker.observe(b"text\x1b[?20", now);
+        assert!(!tracker.defers_repaint(now));
+        tracker.observe(b"26hframe", now);
+        assert!(tracker.defers_repaint(now));
+        tracker.observe(b"frame\x1b", now);
+        tracker.observe(b"[?2026l", now);
+        assert!(!tracker.defers_repaint(now));
+    }
+
+    #[test]
+    fn unrelated_escape_sequences_are_ignored() {
+        let now = Instant::now();
+        let mut tracker = SyncUpdateTracker::default();
+        tracker.observe(b"\x1b[?2004h\x1b[?25l\x1b[2J", now);
+        assert!(!tracker.defers_repaint(now));
+        assert!(tracker.partial.is_empty());
+    }
+
+    #[test]
+    fn unterminated_block_stops_deferring_after_timeout() {
+        let start = Instant::now();
+        let mut tracker = SyncUpdateTracker::default();
+        tracker.observe(b"\x1b[?2026h", start);
+        assert!(tracker.defers_repaint(start + Duration::from_millis(50)));
+        assert!(!tracker.defers_repaint(start + Duration::from_millis(SYNC_UPDATE_MAX_DEFER_MS)));
+    }
+}
@@ -6,8 +6,13 @@ use super::backend::{
     RequestPayload, BACKEND_EVENT_CAPACITY,
 };
 use super::cli::call_codex_cli;
-use crate::{config::AppConfig, lock_or_recover, log_debug, pty_session::PtyCliSession};
+use crate::{
+    config::{AppConfig, LogLevel},
+    lock_or_recover, log_debug, log_event,
+    pty_session::PtyCliSession,
+};
 use anyhow::{anyhow, Context, Result};
+use serde_json::json;
 #[cfg(test)]
 use std::cell::Cell;
 #[cfg(test)]
@@ -409,10 +414,19 @@ fn run_codex_job(
     let line_count = lines.len();
     if config.log_timings {
         let total_ms = duration_ms(stats.finished_at.duration_since(stats.started_at));
-        log_debug(&format!(
-            "timing|phase=codex_job|job_id={job_id}|pty_attempts={}|cli_fallback={}|disable_pty={}|total_ms={total_ms:.1}|lines={line_count}",
-            stats.pty_attempts, stats.cli_fallback_used, outcome.disable_pty
-        ));
+        log_event(
+            LogLevel::Info,
+            "timing",
+            &[
+                ("phase", json!("codex_job")),
+                ("job_id", json!(job_id)),
+                ("pty_attempts", json!(stats.pty_attempts)),
+                ("cli_fallback", json!(stats.cli_fallback_used)),
+                ("disable_pty", json!(outcome.disable_pty)),
+                ("total_ms", json!((total_ms * 10.0).round() / 10.0)),
+                ("lines", json!(line_count)),
+            ],
+        );
     }
 
     let status = format!("Codex returned {line_count} lines.");
@@ -29,6 +29,18 @@ pub const DEFAULT_VOICE_VAD_SMOOTHING_FRAMES: usize = 3;
 pub const DEFAULT_MIC_METER_AMBIENT_MS: u64 = 3000;
 /// Default speech sampling duration for mic calibration (milliseconds).
 pub const DEFAULT_MIC_METER_SPEECH_MS: u64 = 3000;
+/// Default streaming STT chunk length (milliseconds).
+pub const DEFAULT_WHISPER_STREAM_CHUNK_MS: u64 = 6000;
+/// Default audio shared between consecutive streaming chunks (milliseconds).
+pub const DEFAULT_WHISPER_STREAM_OVERLAP_MS: u64 = 1000;
+/// Default size budget for `--dump-audio` captures (megabytes).
+pub const DEFAULT_DUMP_AUDIO_MAX_MB: u64 = 200;
+/// Largest voice-print distance `--speaker-filter` still treats as the enrolled user.
+pub const DEFAULT_SPEAKER_THRESHOLD: f32 = 0.15;
+/// Default `--audio-cue-volume` (percent of full scale).
+pub const DEFAULT_AUDIO_CUE_VOLUME: u32 = 50;
+/// Default cloud STT request timeout before falling back to local Whisper (milliseconds).
+pub const DEFAULT_STT_REMOTE_TIMEOUT_MS: u64 = 15_000;
 /// Minimum allowed mic-meter sampling window (milliseconds).
 pub const MIN_MIC_METER_SAMPLE_MS: u64 = 500;
 /// Maximum allowed mic-meter sampling window (milliseconds).
@@ -37,6 +49,11 @@ pub const MAX_MIC_METER_SAMPLE_MS: u64 = 30_000;
 pub(super) const MAX_CODEX_ARGS: usize = 64;
 pub(super) const MAX_CODEX_ARG_BYTES: usize = 8 * 1024;
 pub(super) const MAX_CAPTURE_HARD_LIMIT_MS: u64 = 60_000;
+pub(super) const MIN_STT_REMOTE_TIMEOUT_MS: u64 = 1000;
+pub(super) const MAX_STT_REMOTE_TIMEOUT_MS: u64 = 120_000;
+pub(super) const MIN_WHISPER_STREAM_CHUNK_MS: u64 = 2000;
+// Whisper decodes at most 30 s of audio per pass.
+pub(super) const MAX_WHISPER_STREAM_CHUNK_MS: u64 = 30_000;
 pub(super) const ISO_639_1_CODES: &[&str] = &[
     "af", "am", "ar", "az", "be", "bg", "bn", "bs", "ca", "cs", "cy", "da", "de", "el", "en", "es",
     "et", "eu", "fa", "fi", "fil", "fr", "ga", "gl", "gu", "he", "hi", "hr", "hu", "hy", "id",
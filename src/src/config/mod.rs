@@ -10,11 +10,13 @@ use std::path::PathBuf;
 
 use defaults::{default_term, DEFAULT_PIPELINE_SCRIPT};
 pub use defaults::{
-    default_vad_engine, DEFAULT_MIC_METER_AMBIENT_MS, DEFAULT_MIC_METER_SPEECH_MS,
-    DEFAULT_VOICE_BUFFER_MS, DEFAULT_VOICE_CHANNEL_CAPACITY, DEFAULT_VOICE_LOOKBACK_MS,
-    DEFAULT_VOICE_MAX_CAPTURE_MS, DEFAULT_VOICE_MIN_SPEECH_MS, DEFAULT_VOICE_SAMPLE_RATE,
-    DEFAULT_VOICE_SILENCE_TAIL_MS, DEFAULT_VOICE_STT_TIMEOUT_MS, DEFAULT_VOICE_VAD_FRAME_MS,
-    DEFAULT_VOICE_VAD_SMOOTHING_FRAMES, DEFAULT_VOICE_VAD_THRESHOLD_DB, MAX_MIC_METER_SAMPLE_MS,
+    default_vad_engine, DEFAULT_AUDIO_CUE_VOLUME, DEFAULT_DUMP_AUDIO_MAX_MB,
+    DEFAULT_MIC_METER_AMBIENT_MS, DEFAULT_MIC_METER_SPEECH_MS, DEFAULT_SPEAKER_THRESHOLD,
+    DEFAULT_STT_REMOTE_TIMEOUT_MS, DEFAULT_VOICE_BUFFER_MS, DEFAULT_VOICE_CHANNEL_CAPACITY,
+    DEFAULT_VOICE_LOOKBACK_MS, DEFAULT_VOICE_MAX_CAPTURE_MS, DEFAULT_VOICE_MIN_SPEECH_MS,
+    DEFAULT_VOICE_SAMPLE_RATE, DEFAULT_VOICE_SILENCE_TAIL_MS, DEFAULT_VOICE_STT_TIMEOUT_MS,
+    DEFAULT_VOICE_VAD_FRAME_MS, DEFAULT_VOICE_VAD_SMOOTHING_FRAMES, DEFAULT_VOICE_VAD_THRESHOLD_DB,
+    DEFAULT_WHISPER_STREAM_CHUNK_MS, DEFAULT_WHISPER_STREAM_OVERLAP_MS, MAX_MIC_METER_SAMPLE_MS,
     MIN_MIC_METER_SAMPLE_MS,
 };
 
@@ -59,6 +61,10 @@ pub struct AppConfig {
     #[arg(long = "doctor", default_value_t = false)]
     pub doctor: bool,
 
+    /// Print `--doctor` diagnostics plus a synthetic capture + transcription benchmark, then exit
+    #[arg(long = "doctor-bench", default_value_t = false)]
+    pub doctor_bench: bool,
+
     /// Run mic meter and suggest a VAD threshold, then exit
     #[arg(long = "mic-meter", default_value_t = false)]
     pub mic_meter: bool,
@@ -71,6 +77,26 @@ pub struct AppConfig {
     #[arg(long = "mic-meter-speech-ms", default_value_t = DEFAULT_MIC_METER_SPEECH_MS)]
     pub mic_meter_speech_ms: u64,
 
+    /// Record a short sample of your voice, save it as your voice print, and exit
+    #[arg(long = "enroll-voice", default_value_t = false)]
+    pub enroll_voice: bool,
+
+    /// Voice print file (defaults to ~/.config/voiceterm/voice-print.json)
+    #[arg(
+        long = "voice-print",
+        value_name = "PATH",
+        env = "VOICETERM_VOICE_PRINT"
+    )]
+    pub voice_print: Option<PathBuf>,
+
+    /// Drop captures whose voice does not match the enrolled voice print
+    #[arg(long = "speaker-filter", default_value_t = false)]
+    pub speaker_filter: bool,
+
+    /// Largest voice-print distance --speaker-filter accepts (0-2, lower is stricter)
+    #[arg(long = "speaker-threshold", default_value_t = DEFAULT_SPEAKER_THRESHOLD)]
+    pub speaker_threshold: f32,
+
     /// Enable notification sounds (terminal bell)
     #[arg(long = "sounds", default_value_t = false)]
     pub sounds: bool,
@@ -83,6 +109,18 @@ pub struct AppConfig {
     #[arg(long = "sound-on-error", default_value_t = false)]
     pub sound_on_error: bool,
 
+    /// Play short tones when a capture starts, stops, or fails
+    #[arg(long = "audio-cues", default_value_t = false)]
+    pub audio_cues: bool,
+
+    /// Audio cue volume (0-100)
+    #[arg(long = "audio-cue-volume", default_value_t = DEFAULT_AUDIO_CUE_VOLUME)]
+    pub audio_cue_volume: u32,
+
+    /// Audio output device for cues (default output when unset)
+    #[arg(long = "output-device")]
+    pub output_device: Option<String>,
+
     /// Enable persistent Codex PTY session (captures full TUI, use --persistent-codex to enable)
     #[arg(long = "persistent-codex", default_value_t = false)]
     pub persistent_codex: bool,
@@ -95,6 +133,28 @@ pub struct AppConfig {
     #[arg(long = "no-logs", env = "VOICETERM_NO_LOGS", default_value_t = false)]
     pub no_logs: bool,
 
+    /// Minimum level written to the debug log
+    #[arg(
+        long = "log-level",
+        env = "VOICETERM_LOG_LEVEL",
+        value_enum,
+        default_value_t = LogLevel::Debug
+    )]
+    pub log_level: LogLevel,
+
+    /// Debug log line format
+    #[arg(
+        long = "log-format",
+        env = "VOICETERM_LOG_FORMAT",
+        value_enum,
+        default_value_t = LogFormat::Text
+    )]
+    pub log_format: LogFormat,
+
+    /// Directory for debug, trace, and crash logs (default: system temp dir)
+    #[arg(long = "log-dir", env = "VOICETERM_LOG_DIR", value_name = "DIR")]
+    pub log_dir: Option<PathBuf>,
+
     /// Allow logging prompt/content snippets (debug log only)
     #[arg(
         long = "log-content",
@@ -123,6 +183,14 @@ pub struct AppConfig {
     #[arg(long)]
     pub whisper_model_path: Option<String>,
 
+    /// Quick-command Whisper model (path or name such as `tiny`); the `switch_model` key toggles to it
+    #[arg(long = "model-fast", value_name = "MODEL")]
+    pub model_fast: Option<String>,
+
+    /// Long-dictation Whisper model (path or name such as `medium`); the `switch_model` key toggles to it
+    #[arg(long = "model-accurate", value_name = "MODEL")]
+    pub model_accurate: Option<String>,
+
     /// Whisper beam size (native pipeline only; >1 enables beam search)
     #[arg(long = "whisper-beam-size", default_value_t = 0)]
     pub whisper_beam_size: u32,
@@ -131,6 +199,32 @@ pub struct AppConfig {
     #[arg(long = "whisper-temperature", default_value_t = 0.0)]
     pub whisper_temperature: f32,
 
+    /// Vocabulary hints passed to Whisper as its initial prompt (native pipeline only)
+    #[arg(long = "whisper-prompt", value_name = "TEXT")]
+    pub whisper_prompt: Option<String>,
+
+    /// Transcribe overlapping chunks while recording instead of after capture ends (native pipeline only)
+    #[arg(long = "whisper-stream", default_value_t = false)]
+    pub whisper_stream: bool,
+
+    /// Streaming chunk length (milliseconds)
+    #[arg(
+        long = "whisper-stream-chunk-ms",
+        default_value_t = DEFAULT_WHISPER_STREAM_CHUNK_MS
+    )]
+    pub whisper_stream_chunk_ms: u64,
+
+    /// Audio shared between consecutive streaming chunks (milliseconds)
+    #[arg(
+        long = "whisper-stream-overlap-ms",
+        default_value_t = DEFAULT_WHISPER_STREAM_OVERLAP_MS
+    )]
+    pub whisper_stream_overlap_ms: u64,
+
+    /// After a streamed capture, re-run Whisper over the whole utterance and prefer that transcript
+    #[arg(long = "whisper-stream-final-pass", default_value_t = false)]
+    pub whisper_stream_final_pass: bool,
+
     /// FFmpeg binary location
     #[arg(long, default_value = "ffmpeg")]
     pub ffmpeg_cmd: String,
@@ -147,6 +241,14 @@ pub struct AppConfig {
     #[arg(long = "voice-sample-rate", default_value_t = DEFAULT_VOICE_SAMPLE_RATE)]
     pub voice_sample_rate: u32,
 
+    /// How microphone audio is converted to --voice-sample-rate (and to Whisper's 16 kHz)
+    #[arg(
+        long = "voice-resample-quality",
+        value_enum,
+        default_value_t = ResampleQuality::High
+    )]
+    pub voice_resample_quality: ResampleQuality,
+
     /// Maximum capture duration before a hard stop (milliseconds)
     #[arg(long = "voice-max-capture-ms", default_value_t = DEFAULT_VOICE_MAX_CAPTURE_MS)]
     pub voice_max_capture_ms: u64,
@@ -155,6 +257,10 @@ pub struct AppConfig {
     #[arg(long = "voice-silence-tail-ms", default_value_t = DEFAULT_VOICE_SILENCE_TAIL_MS)]
     pub voice_silence_tail_ms: u64,
 
+    /// Lengthen the silence tail while speech is rising into a pause and shorten it after sentence-final drops
+    #[arg(long = "voice-silence-tail-adaptive", default_value_t = false)]
+    pub voice_silence_tail_adaptive: bool,
+
     /// Minimum speech before STT can begin (milliseconds)
     #[arg(
         long = "voice-min-speech-ms-before-stt",
@@ -162,10 +268,26 @@ pub struct AppConfig {
     )]
     pub voice_min_speech_ms_before_stt_start: u64,
 
-    /// Amount of audio retained prior to silence stop (milliseconds)
+    /// Speech a capture needs to count as an utterance; shorter captures are treated as empty (milliseconds, 0 = off)
+    #[arg(long = "voice-min-utterance-ms", default_value_t = 0)]
+    pub voice_min_utterance_ms: u64,
+
+    /// Share of frames between speech onset and end that must be voiced for an utterance (0-1, 0 = off)
+    #[arg(long = "voice-min-voiced-ratio", default_value_t = 0.0)]
+    pub voice_min_voiced_ratio: f32,
+
+    /// Audio retained before speech onset and after speech ends on a silence stop (milliseconds)
     #[arg(long = "voice-lookback-ms", default_value_t = DEFAULT_VOICE_LOOKBACK_MS)]
     pub voice_lookback_ms: u64,
 
+    /// Keep the microphone stream open between captures so each capture starts with --voice-lookback-ms of real audio
+    #[arg(long = "keep-mic-open", default_value_t = false)]
+    pub keep_mic_open: bool,
+
+    /// Release the microphone after this many idle seconds between captures (0 = right after each capture); the next capture reopens it
+    #[arg(long = "mic-release-secs", value_name = "SECS")]
+    pub mic_release_secs: Option<u64>,
+
     /// Total buffered audio budget (milliseconds)
     #[arg(long = "voice-buffer-ms", default_value_t = DEFAULT_VOICE_BUFFER_MS)]
     pub voice_buffer_ms: u64,
@@ -207,14 +329,104 @@ pub struct AppConfig {
     )]
     pub voice_vad_engine: VadEngineKind,
 
+    /// Suppress stationary background noise (fans, hum) before VAD and STT
+    #[arg(long = "denoise", default_value_t = false)]
+    pub denoise: bool,
+
+    /// Keep voiceterm's own audio and the speakers out of captures (off, mute, cancel)
+    #[arg(
+        long = "echo-mode",
+        env = "VOICETERM_ECHO_MODE",
+        value_enum,
+        default_value_t = EchoMode::Mute
+    )]
+    pub echo_mode: EchoMode,
+
+    /// Loopback input carrying the speaker output, used as the echo reference
+    #[arg(
+        long = "echo-reference-device",
+        env = "VOICETERM_ECHO_REFERENCE_DEVICE",
+        value_name = "NAME"
+    )]
+    pub echo_reference_device: Option<String>,
+
+    /// Save every capture that reaches STT into this directory (debugging and history)
+    #[arg(
+        long = "dump-audio",
+        visible_alias = "save-audio-dir",
+        value_name = "DIR"
+    )]
+    pub dump_audio: Option<PathBuf>,
+
+    /// Encoding for --dump-audio files (flac is lossless and roughly half the size)
+    #[arg(
+        long = "dump-audio-format",
+        value_enum,
+        default_value_t = AudioDumpFormat::Wav
+    )]
+    pub dump_audio_format: AudioDumpFormat,
+
+    /// Size budget for --dump-audio; the oldest captures are deleted beyond it (megabytes)
+    #[arg(long = "dump-audio-max-mb", default_value_t = DEFAULT_DUMP_AUDIO_MAX_MB)]
+    pub dump_audio_max_mb: u64,
+
+    /// Keep leading and trailing silence in --dump-audio files instead of trimming it
+    #[arg(long = "dump-audio-keep-silence", default_value_t = false)]
+    pub dump_audio_keep_silence: bool,
+
+    /// Peak-normalize --dump-audio files to about -1 dBFS
+    #[arg(long = "dump-audio-normalize", default_value_t = false)]
+    pub dump_audio_normalize: bool,
+
     /// Language passed to Whisper
     #[arg(long, default_value = "en")]
     pub lang: String,
 
+    /// Speech-to-text engine; cloud backends fall back to local Whisper on network failure
+    #[arg(
+        long = "stt-backend",
+        env = "VOICETERM_STT_BACKEND",
+        value_enum,
+        default_value_t = SttBackend::Local
+    )]
+    pub stt_backend: SttBackend,
+
+    /// Device whisper.cpp runs on: GPU when the build has Metal/CUDA (auto), or force cpu/gpu
+    #[arg(
+        long = "stt-device",
+        env = "VOICETERM_STT_DEVICE",
+        value_enum,
+        default_value_t = SttDevice::Auto
+    )]
+    pub stt_device: SttDevice,
+
+    /// At startup, also run a silent test transcription and open the microphone once so the first capture is as fast as later ones
+    #[arg(long = "warm-up", default_value_t = false)]
+    pub warm_up: bool,
+
+    /// Cloud STT endpoint override (for example a self-hosted OpenAI-compatible server)
+    #[arg(long = "stt-endpoint", value_name = "URL")]
+    pub stt_endpoint: Option<String>,
+
+    /// Cloud STT model (default: whisper-1 for openai, nova-2 for deepgram)
+    #[arg(long = "stt-model")]
+    pub stt_model: Option<String>,
+
+    /// Cloud STT request timeout before falling back to local Whisper (milliseconds)
+    #[arg(
+        long = "stt-remote-timeout-ms",
+        default_value_t = DEFAULT_STT_REMOTE_TIMEOUT_MS
+    )]
+    pub stt_remote_timeout_ms: u64,
+
     /// Fail instead of using the python STT fallback
     #[arg(long = "no-python-fallback")]
     pub no_python_fallback: bool,
 
+    /// Run the native and Python pipelines side by side, send the better transcript, and log the diff
+    #[arg(long = "voice-ab-compare")]
+    pub voice_ab_compare: bool,
+
     /// Run in JSON IPC mode for external UI integration
     #[arg(long = "json-ipc")]
     pub json_ipc: bool,
@@ -225,10 +437,14 @@ pub struct AppConfig {
 pub struct VoicePipelineConfig {
     /// Input sample rate used by capture and VAD.
     pub sample_rate: u32,
+    /// Converter used to bring device audio to `sample_rate`.
+    pub resample_quality: ResampleQuality,
     /// Maximum capture duration before forced stop (milliseconds).
     pub max_capture_ms: u64,
     /// Silence required to auto-stop after speech (milliseconds).
     pub silence_tail_ms: u64,
+    /// Whether the silence tail adapts to the energy trend before each pause.
+    pub silence_tail_adaptive: bool,
     /// Minimum detected speech before STT processing begins (milliseconds).
     pub min_speech_ms_before_stt_start: u64,
     /// Audio kept before silence stop to avoid clipping endings (milliseconds).
@@ -249,6 +465,50 @@ pub struct VoicePipelineConfig {
     pub python_fallback_allowed: bool,
     /// Selected VAD engine implementation.
     pub vad_engine: VadEngineKind,
+    /// Whether the noise-suppression preprocessor runs ahead of VAD.
+    pub denoise: bool,
+    /// How playback picked up by the mic is kept out of captures.
+    pub echo_mode: EchoMode,
+    /// Loopback input device used as the echo reference.
+    pub echo_reference_device: Option<String>,
+}
+
+/// Minimum severity written to the debug log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+#[repr(u8)]
+pub enum LogLevel {
+    /// Failures that stopped an operation.
+    Error,
+    /// Recoverable problems.
+    Warn,
+    /// Lifecycle and metric events.
+    Info,
+    /// Detailed diagnostics (default).
+    Debug,
+    /// Everything, including per-chunk detail.
+    Trace,
+}
+
+impl LogLevel {
+    /// Stable lowercase identifier used in log lines.
+    pub fn label(self) -> &'static str {
+        match self {
+            LogLevel::Error => "error",
+            LogLevel::Warn => "warn",
+            LogLevel::Info => "info",
+            LogLevel::Debug => "debug",
+            LogLevel::Trace => "trace",
+        }
+    }
+}
+
+/// Line format for the debug log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    /// `<timestamp> LEVEL [component] message`
+    Text,
+    /// One JSON object per line with `ts`, `level`, `component`, and `msg` or `event`/`fields`.
+    Json,
 }
 
 /// Available runtime-selectable VAD implementations.
@@ -260,6 +520,117 @@ pub enum VadEngineKind {
     Simple,
 }
 
+/// Sample-rate converter for device audio that is not already at the pipeline rate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ResampleQuality {
+    /// Linear interpolation behind a short FIR low-pass; cheapest on CPU.
+    Fast,
+    /// Windowed-sinc conversion when built with `high-quality-audio`, otherwise the same as `fast`.
+    High,
+}
+
+/// How captures handle audio that is playing while the mic is open.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum EchoMode {
+    /// Capture everything the mic hears.
+    Off,
+    /// Silence capture while voiceterm plays audio, or while the echo reference is audible.
+    Mute,
+    /// Subtract the echo predicted from `--echo-reference-device`; mutes without one.
+    Cancel,
+}
+
+/// File encoding for `--dump-audio` captures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum AudioDumpFormat {
+    /// Uncompressed 16-bit PCM WAV.
+    Wav,
+    /// Lossless FLAC.
+    Flac,
+}
+
+impl AudioDumpFormat {
+    /// File extension for dumps in this format.
+    pub fn extension(self) -> &'static str {
+        match self {
+            AudioDumpFormat::Wav => "wav",
+            AudioDumpFormat::Flac => "flac",
+        }
+    }
+}
+
+/// Where captured audio is transcribed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SttBackend {
+    /// In-process whisper.cpp (default).
+    Local,
+    /// OpenAI's `/v1/audio/transcriptions` API, or a compatible server via `--stt-endpoint`.
+    Openai,
+    /// Deepgram's `/v1/listen` API.
+    Deepgram,
+}
+
+impl SttBackend {
+    /// Stable lowercase identifier used in logs and diagnostics.
+    pub fn label(self) -> &'static str {
+        match self {
+            SttBackend::Local => "local",
+            SttBackend::Openai => "openai",
+            SttBackend::Deepgram => "deepgram",
+        }
+    }
+
+    /// Environment variable holding the API key; `None` for the local backend.
+    pub fn api_key_env(self) -> Option<&'static str> {
+        match self {
+            SttBackend::Local => None,
+            SttBackend::Openai => Some("OPENAI_API_KEY"),
+            SttBackend::Deepgram => Some("DEEPGRAM_API_KEY"),
+        }
+    }
+}
+
+/// Device selection for local Whisper.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SttDevice {
+    /// GPU when the build includes Metal or CUDA, otherwise CPU.
+    Auto,
+    /// Always run on the CPU.
+    Cpu,
+    /// Require the GPU backend compiled into this build.
+    Gpu,
+}
+
+impl SttDevice {
+    /// GPU backend compiled into this build, if any.
+    pub const fn gpu_backend() -> Option<&'static str> {
+        if cfg!(feature = "metal") {
+            Some("metal")
+        } else if cfg!(feature = "cuda") {
+            Some("cuda")
+        } else {
+            None
+        }
+    }
+
+    /// Whether whisper.cpp should be asked to use the GPU.
+    pub fn uses_gpu(self) -> bool {
+        match self {
+            SttDevice::Auto => Self::gpu_backend().is_some(),
+            SttDevice::Cpu => false,
+            SttDevice::Gpu => true,
+        }
+    }
+
+    /// Resolved device for banners and logs, e.g. `gpu/metal` or `cpu`.
+    pub fn label(self) -> String {
+        match Self::gpu_backend().filter(|_| self.uses_gpu()) {
+            Some(backend) => format!("gpu/{backend}"),
+            None => "cpu".to_string(),
+        }
+    }
+}
+
 impl VadEngineKind {
     /// Stable lowercase identifier used in logs, metrics, and status output.
     pub fn label(self) -> &'static str {
@@ -268,4 +639,16 @@ impl VadEngineKind {
             VadEngineKind::Simple => "simple",
         }
     }
+
+    /// Engines compiled into this build, in menu order.
+    pub fn available() -> &'static [VadEngineKind] {
+        #[cfg(feature = "vad_earshot")]
+        {
+            &[VadEngineKind::Earshot, VadEngineKind::Simple]
+        }
+        #[cfg(not(feature = "vad_earshot"))]
+        {
+            &[VadEngineKind::Simple]
+        }
+    }
 }
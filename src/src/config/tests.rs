@@ -6,7 +6,7 @@ use super::defaults::{
 use super::validation::{
     canonical_repo_root, canonicalize_within_repo, discover_default_whisper_model, sanitize_binary,
 };
-use super::{default_vad_engine, AppConfig, VadEngineKind};
+use super::{default_vad_engine, AppConfig, AudioDumpFormat, EchoMode, VadEngineKind};
 use clap::Parser;
 use std::fs;
 use std::sync::{Mutex, OnceLock};
@@ -90,6 +90,15 @@ fn rejects_mic_meter_samples_out_of_bounds() {
     assert!(cfg.validate().is_err());
 }
 
+#[test]
+fn echo_cancel_requires_a_reference_device() {
+    let cfg = AppConfig::parse_from(["test-app"]);
+    assert_eq!(cfg.echo_mode, EchoMode::Mute);
+    let mut cfg = AppConfig::parse_from(["test-app", "--echo-mode", "cancel"]);
+    let err = cfg.validate().unwrap_err();
+    assert!(err.to_string().contains("--echo-reference-device"), "{err}");
+}
+
 #[test]
 fn rejects_vad_smoothing_frames_out_of_bounds() {
     let mut cfg = AppConfig::parse_from(["test-app", "--voice-vad-smoothing-frames", "0"]);
@@ -118,6 +127,77 @@ fn rejects_whisper_temperature_out_of_bounds() {
     assert!(cfg.validate().is_err());
 }
 
+#[test]
+fn validates_whisper_stream_chunk_and_overlap() {
+    let mut cfg = AppConfig::parse_from(["test-app", "--whisper-stream-chunk-ms", "1000"]);
+    assert!(cfg.validate().is_err());
+    let mut cfg = AppConfig::parse_from([
+        "test-app",
+        "--whisper-stream-chunk-ms",
+        "4000",
+        "--whisper-stream-overlap-ms",
+        "2001",
+    ]);
+    assert!(cfg.validate().is_err());
+    let mut cfg = AppConfig::parse_from([
+        "test-app",
+        "--whisper-stream",
+        "--whisper-stream-chunk-ms",
+        "4000",
+        "--whisper-stream-overlap-ms",
+        "2000",
+    ]);
+    assert!(cfg.validate().is_ok());
+}
+
+#[test]
+fn audio_dump_settings_follow_flags() {
+    let cfg = AppConfig::parse_from(["test-app"]);
+    assert!(cfg.audio_dump().is_none());
+    let cfg = AppConfig::parse_from(["test-app", "--save-audio-dir", "/tmp/voiceterm-saved"]);
+    assert_eq!(
+        cfg.audio_dump().expect("alias configures the dump").dir,
+        std::path::PathBuf::from("/tmp/voiceterm-saved")
+    );
+    let mut cfg = AppConfig::parse_from([
+        "test-app",
+        "--dump-audio",
+        "/tmp/voiceterm-dumps",
+        "--dump-audio-format",
+        "flac",
+        "--dump-audio-max-mb",
+        "3",
+    ]);
+    assert!(cfg.validate().is_ok());
+    let dump = cfg.audio_dump().expect("dump configured");
+    assert_eq!(dump.dir, std::path::PathBuf::from("/tmp/voiceterm-dumps"));
+    assert_eq!(dump.format, AudioDumpFormat::Flac);
+    assert_eq!(dump.max_bytes, 3 * 1024 * 1024);
+    assert_eq!(dump.trim_below_db, Some(cfg.voice_vad_threshold_db));
+    assert!(!dump.normalize);
+    let cfg = AppConfig::parse_from([
+        "test-app",
+        "--dump-audio",
+        "/tmp/voiceterm-dumps",
+        "--dump-audio-keep-silence",
+        "--dump-audio-normalize",
+    ]);
+    let dump = cfg.audio_dump().expect("dump configured");
+    assert_eq!(dump.trim_below_db, None);
+    assert!(dump.normalize);
+    let mut cfg = AppConfig::parse_from(["test-app", "--dump-audio-max-mb", "0"]);
+    assert!(cfg.validate().is_err());
+}
+
+#[test]
+fn rejects_voice_ab_compare_without_python_fallback() {
+    let mut cfg = AppConfig::parse_from(["test-app", "--voice-ab-compare", "--no-python-fallback"]);
+    assert!(cfg.validate().is_err());
+    // Builds without the Python pipeline imply --no-python-fallback.
+    let mut cfg = AppConfig::parse_from(["test-app", "--voice-ab-compare"]);
+    assert_eq!(cfg.validate().is_ok(), cfg!(feature = "python-fallback"));
+}
+
 #[test]
 fn rejects_invalid_claude_cmd() {
     let mut cfg = AppConfig::parse_from(["test-app", "--claude-cmd", "not-claude"]);
@@ -141,6 +221,17 @@ fn accepts_valid_defaults() {
     assert!(cfg.validate().is_ok());
 }
 
+#[test]
+fn available_vad_engines_include_default_and_simple() {
+    let available = VadEngineKind::available();
+    assert!(available.contains(&default_vad_engine()));
+    assert!(available.contains(&VadEngineKind::Simple));
+    assert_eq!(
+        available.contains(&VadEngineKind::Earshot),
+        cfg!(feature = "vad_earshot")
+    );
+}
+
 #[test]
 fn voice_vad_engine_flag_round_trips_into_pipeline_config() {
     let mut cfg = AppConfig::parse_from(["test-app", "--voice-vad-engine", "simple"]);
@@ -731,3 +822,159 @@ fn sanitize_binary_accepts_executable_path() {
     assert!(sanitized.contains("codex_bin_"));
     let _ = fs::remove_file(temp_path);
 }
+
+#[test]
+fn denoise_flag_flows_into_pipeline_config() {
+    let cfg = AppConfig::parse_from(["test-app"]);
+    assert!(!cfg.denoise);
+    assert!(!cfg.voice_pipeline_config().denoise);
+
+    let cfg = AppConfig::parse_from(["test-app", "--denoise"]);
+    assert!(cfg.denoise);
+    assert!(cfg.voice_pipeline_config().denoise);
+}
+
+#[test]
+fn adaptive_silence_tail_flag_flows_into_pipeline_config() {
+    let cfg = AppConfig::parse_from(["test-app"]);
+    assert!(!cfg.voice_pipeline_config().silence_tail_adaptive);
+
+    let cfg = AppConfig::parse_from(["test-app", "--voice-silence-tail-adaptive"]);
+    assert!(cfg.voice_pipeline_config().silence_tail_adaptive);
+}
+
+#[test]
+fn rejects_log_dir_that_is_a_file() {
+    let file = env::temp_dir().join(format!("voiceterm-log-dir-file-{}", std::process::id()));
+    fs::write(&file, b"x").expect("write temp file");
+    let mut cfg = AppConfig::parse_from(["test-app", "--log-dir", file.to_str().unwrap()]);
+    let err = cfg.validate().expect_err("file path should be rejected");
+    assert!(err.to_string().contains("--log-dir"));
+    let _ = fs::remove_file(&file);
+
+    let mut cfg = AppConfig::parse_from(["test-app", "--log-dir", "missing/logs"]);
+    assert!(
+        cfg.validate().is_ok(),
+        "missing dirs are created at startup"
+    );
+}
+
+#[test]
+fn rejects_speaker_threshold_out_of_range_and_filter_without_print() {
+    let mut cfg = AppConfig::parse_from(["test-app", "--speaker-threshold", "0"]);
+    assert!(cfg.validate().is_err());
+    let mut cfg = AppConfig::parse_from(["test-app", "--speaker-threshold", "2.5"]);
+    assert!(cfg.validate().is_err());
+    let mut cfg = AppConfig::parse_from([
+        "test-app",
+        "--speaker-filter",
+        "--voice-print",
+        "/nonexistent/voice-print.json",
+    ]);
+    let err = cfg.validate().unwrap_err();
+    assert!(err.to_string().contains("--enroll-voice"), "{err}");
+}
+
+#[test]
+fn rejects_audio_cue_volume_above_100() {
+    let mut cfg = AppConfig::parse_from(["test-app", "--audio-cues", "--audio-cue-volume", "100"]);
+    assert!(cfg.validate().is_ok());
+    let mut cfg = AppConfig::parse_from(["test-app", "--audio-cue-volume", "101"]);
+    let err = cfg.validate().unwrap_err();
+    assert!(err.to_string().contains("--audio-cue-volume"), "{err}");
+}
+
+#[test]
+fn validates_remote_stt_endpoint_and_timeout() {
+    let mut cfg = AppConfig::parse_from([
+        "test-app",
+        "--stt-backend",
+        "openai",
+        "--stt-endpoint",
+        "http://127.0.0.1:8000/v1/audio/transcriptions",
+    ]);
+    assert!(cfg.validate().is_ok(), "custom endpoints may be keyless");
+    let mut cfg = AppConfig::parse_from([
+        "test-app",
+        "--stt-backend",
+        "deepgram",
+        "--stt-endpoint",
+        "ftp://stt.example",
+    ]);
+    let err = cfg.validate().unwrap_err();
+    assert!(err.to_string().contains("--stt-endpoint"), "{err}");
+    let mut cfg = AppConfig::parse_from(["test-app", "--stt-remote-timeout-ms", "500"]);
+    let err = cfg.validate().unwrap_err();
+    assert!(err.to_string().contains("--stt-remote-timeout-ms"), "{err}");
+}
+
+#[test]
+fn validates_utterance_thresholds() {
+    let mut cfg = AppConfig::parse_from([
+        "test-app",
+        "--voice-min-utterance-ms",
+        "400",
+        "--voice-min-voiced-ratio",
+        "0.5",
+    ]);
+    assert!(cfg.validate().is_ok());
+    let mut cfg = AppConfig::parse_from(["test-app", "--voice-min-voiced-ratio", "1.5"]);
+    let err = cfg.validate().unwrap_err();
+    assert!(
+        err.to_string().contains("--voice-min-voiced-ratio"),
+        "{err}"
+    );
+    let mut cfg = AppConfig::parse_from([
+        "test-app",
+        "--voice-max-capture-ms",
+        "5000",
+        "--voice-min-utterance-ms",
+        "6000",
+    ]);
+    let err = cfg.validate().unwrap_err();
+    assert!(
+        err.to_string().contains("--voice-min-utterance-ms"),
+        "{err}"
+    );
+}
+
+#[test]
+fn stt_device_gpu_requires_gpu_feature() {
+    let mut cfg = AppConfig::parse_from(["test-app", "--stt-device", "cpu"]);
+    assert!(cfg.validate().is_ok());
+    assert_eq!(cfg.stt_device.label(), "cpu");
+    let mut cfg = AppConfig::parse_from(["test-app", "--stt-device", "gpu"]);
+    let result = cfg.validate();
+    if cfg!(any(feature = "metal", feature = "cuda")) {
+        assert!(result.is_ok());
+    } else {
+        let err = result.unwrap_err();
+        assert!(err.to_string().contains("--stt-device gpu"), "{err}");
+    }
+}
+
+#[test]
+fn model_pair_resolves_paths_and_starts_on_fast_model() {
+    let dir = env::temp_dir().join(format!("voiceterm-model-pair-{}", std::process::id()));
+    fs::create_dir_all(&dir).expect("create model dir");
+    let fast = dir.join("ggml-tiny.bin");
+    let accurate = dir.join("ggml-medium.bin");
+    fs::write(&fast, b"").expect("write fast model");
+    fs::write(&accurate, b"").expect("write accurate model");
+    let mut cfg = AppConfig::parse_from([
+        "test-app",
+        "--model-fast",
+        fast.to_str().unwrap(),
+        "--model-accurate",
+        accurate.to_str().unwrap(),
+    ]);
+    cfg.validate().expect("model pair should validate");
+    let fast = fast.canonicalize().unwrap().to_string_lossy().into_owned();
+    assert_eq!(cfg.model_fast.as_deref(), Some(fast.as_str()));
+    assert_eq!(cfg.whisper_model_path.as_deref(), Some(fast.as_str()));
+
+    let mut cfg = AppConfig::parse_from(["test-app", "--model-accurate", "no-such-model"]);
+    let err = cfg.validate().unwrap_err();
+    assert!(err.to_string().contains("--model-accurate"), "{err}");
+    let _ = fs::remove_dir_all(&dir);
+}
@@ -2,9 +2,14 @@
 
 use super::defaults::{
     FORBIDDEN_DEVICE_CHARS, ISO_639_1_CODES, MAX_CAPTURE_HARD_LIMIT_MS, MAX_CODEX_ARGS,
-    MAX_CODEX_ARG_BYTES,
+    MAX_CODEX_ARG_BYTES, MAX_STT_REMOTE_TIMEOUT_MS, MAX_WHISPER_STREAM_CHUNK_MS,
+    MIN_STT_REMOTE_TIMEOUT_MS, MIN_WHISPER_STREAM_CHUNK_MS,
 };
-use super::{AppConfig, VoicePipelineConfig, MAX_MIC_METER_SAMPLE_MS, MIN_MIC_METER_SAMPLE_MS};
+use super::{
+    AppConfig, EchoMode, SttDevice, VoicePipelineConfig, MAX_MIC_METER_SAMPLE_MS,
+    MIN_MIC_METER_SAMPLE_MS,
+};
+use crate::audio::{default_voice_print_path, AudioDump};
 use anyhow::{anyhow, bail, Context, Result};
 use clap::Parser;
 use std::{
@@ -61,6 +66,15 @@ impl AppConfig {
                 self.voice_max_capture_ms
             );
         }
+        if !cfg!(feature = "python-fallback") {
+            if self.voice_ab_compare {
+                bail!("--voice-ab-compare requires building with the 'python-fallback' feature");
+            }
+            self.no_python_fallback = true;
+        }
+        if self.voice_ab_compare && self.no_python_fallback {
+            bail!("--voice-ab-compare needs the python fallback; drop --no-python-fallback");
+        }
         if self.voice_lookback_ms > self.voice_max_capture_ms {
             bail!(
                 "--voice-lookback-ms ({}) cannot exceed --voice-max-capture-ms ({})",
@@ -130,6 +144,99 @@ impl AppConfig {
                 self.whisper_temperature
             );
         }
+        if !(MIN_WHISPER_STREAM_CHUNK_MS..=MAX_WHISPER_STREAM_CHUNK_MS)
+            .contains(&self.whisper_stream_chunk_ms)
+        {
+            bail!(
+                "--whisper-stream-chunk-ms must be between {MIN_WHISPER_STREAM_CHUNK_MS} and {MAX_WHISPER_STREAM_CHUNK_MS} ms, got {}",
+                self.whisper_stream_chunk_ms
+            );
+        }
+        if self.whisper_stream_overlap_ms * 2 > self.whisper_stream_chunk_ms {
+            bail!(
+                "--whisper-stream-overlap-ms must be at most half of --whisper-stream-chunk-ms ({}), got {}",
+                self.whisper_stream_chunk_ms,
+                self.whisper_stream_overlap_ms
+            );
+        }
+
+        if !(MIN_STT_REMOTE_TIMEOUT_MS..=MAX_STT_REMOTE_TIMEOUT_MS)
+            .contains(&self.stt_remote_timeout_ms)
+        {
+            bail!(
+                "--stt-remote-timeout-ms must be between {MIN_STT_REMOTE_TIMEOUT_MS} and {MAX_STT_REMOTE_TIMEOUT_MS} ms, got {}",
+                self.stt_remote_timeout_ms
+            );
+        }
+        if let Some(endpoint) = &self.stt_endpoint {
+            // The URL is written into a curl config, so keep it to one quoted token.
+            let is_http = endpoint.starts_with("https://") || endpoint.starts_with("http://");
+            if !is_http
+                || endpoint
+                    .chars()
+                    .any(|ch| ch.is_whitespace() || ch.is_control() || ch == '"' || ch == '\\')
+            {
+                bail!("--stt-endpoint must be an http(s) URL without spaces or quotes");
+            }
+        }
+        if let Some(key_env) = self.stt_backend.api_key_env() {
+            // A custom endpoint may be a keyless self-hosted server.
+            let has_key = env::var(key_env).is_ok_and(|key| !key.trim().is_empty());
+            if !has_key && self.stt_endpoint.is_none() {
+                bail!(
+                    "--stt-backend {} needs {key_env} set in the environment",
+                    self.stt_backend.label()
+                );
+            }
+        }
+
+        if self.voice_min_utterance_ms > self.voice_max_capture_ms {
+            bail!(
+                "--voice-min-utterance-ms must be <= --voice-max-capture-ms ({})",
+                self.voice_max_capture_ms
+            );
+        }
+        if !(0.0..=1.0).contains(&self.voice_min_voiced_ratio) {
+            bail!(
+                "--voice-min-voiced-ratio must be between 0.0 and 1.0, got {}",
+                self.voice_min_voiced_ratio
+            );
+        }
+
+        if self.dump_audio_max_mb == 0 {
+            bail!("--dump-audio-max-mb must be at least 1");
+        }
+
+        if !(self.speaker_threshold > 0.0 && self.speaker_threshold <= 2.0) {
+            bail!(
+                "--speaker-threshold must be greater than 0 and at most 2, got {}",
+                self.speaker_threshold
+            );
+        }
+        if self.audio_cue_volume > 100 {
+            bail!(
+                "--audio-cue-volume must be between 0 and 100, got {}",
+                self.audio_cue_volume
+            );
+        }
+        if self.echo_mode == EchoMode::Cancel && self.echo_reference_device.is_none() {
+            bail!("--echo-mode cancel needs --echo-reference-device (a loopback or monitor input)");
+        }
+        if self.speaker_filter {
+            let path = self.voice_print_path().ok_or_else(|| {
+                anyhow!("--speaker-filter needs --voice-print when HOME is unset")
+            })?;
+            if !path.is_file() {
+                bail!(
+                    "--speaker-filter needs a voice print at {}; run voiceterm --enroll-voice first",
+                    path.display()
+                );
+            }
+        }
+
+        if self.stt_device == SttDevice::Gpu && SttDevice::gpu_backend().is_none() {
+            bail!("--stt-device gpu requires building with the 'metal' or 'cuda' feature");
+        }
 
         #[cfg(not(feature = "vad_earshot"))]
         if matches!(self.voice_vad_engine, super::VadEngineKind::Earshot) {
@@ -154,7 +261,16 @@ impl AppConfig {
             canonicalize_within_repo(&self.pipeline_script, "pipeline script", &repo_root)?;
         // PTY helper removed - using native Rust PtyCliSession instead
 
+        self.model_fast = resolve_model_arg(self.model_fast.take(), "--model-fast", &repo_root)?;
+        self.model_accurate =
+            resolve_model_arg(self.model_accurate.take(), "--model-accurate", &repo_root)?;
         if self.whisper_model_path.is_none() {
+            // Start on the fast model: it loads quickest and short commands come first.
+            self.whisper_model_path = self.model_fast.clone().or(self.model_accurate.clone());
+        }
+
+        // Builds without local STT have nothing to load, so skip the search.
+        if self.whisper_model_path.is_none() && cfg!(feature = "stt") {
             if let Some(auto_model) =
                 discover_default_whisper_model(&repo_root, &self.whisper_model)
             {
@@ -184,6 +300,14 @@ impl AppConfig {
                 .ok_or_else(|| anyhow!("whisper model path must be valid UTF-8"))?;
         }
 
+        if self
+            .whisper_prompt
+            .as_deref()
+            .is_some_and(|prompt| prompt.contains('\0'))
+        {
+            bail!("--whisper-prompt must not contain NUL bytes");
+        }
+
         if self.lang.trim().is_empty() {
             bail!("--lang must not be empty");
         }
@@ -237,6 +361,15 @@ impl AppConfig {
             }
         }
 
+        if let Some(dir) = &self.log_dir {
+            if dir.as_os_str().is_empty() {
+                bail!("--log-dir must not be empty");
+            }
+            if dir.exists() && !dir.is_dir() {
+                bail!("--log-dir '{}' is not a directory", dir.display());
+            }
+        }
+
         Ok(())
     }
 
@@ -244,8 +377,10 @@ impl AppConfig {
     pub fn voice_pipeline_config(&self) -> VoicePipelineConfig {
         VoicePipelineConfig {
             sample_rate: self.voice_sample_rate,
+            resample_quality: self.voice_resample_quality,
             max_capture_ms: self.voice_max_capture_ms,
             silence_tail_ms: self.voice_silence_tail_ms,
+            silence_tail_adaptive: self.voice_silence_tail_adaptive,
             min_speech_ms_before_stt_start: self.voice_min_speech_ms_before_stt_start,
             lookback_ms: self.voice_lookback_ms,
             buffer_ms: self.voice_buffer_ms,
@@ -256,8 +391,27 @@ impl AppConfig {
             vad_smoothing_frames: self.voice_vad_smoothing_frames,
             python_fallback_allowed: !self.no_python_fallback,
             vad_engine: self.voice_vad_engine,
+            denoise: self.denoise,
+            echo_mode: self.echo_mode,
+            echo_reference_device: self.echo_reference_device.clone(),
         }
     }
+
+    /// Voice print location: `--voice-print`, else the per-user default.
+    pub fn voice_print_path(&self) -> Option<PathBuf> {
+        self.voice_print.clone().or_else(default_voice_print_path)
+    }
+
+    /// Capture dump settings, or `None` without `--dump-audio`.
+    pub fn audio_dump(&self) -> Option<AudioDump> {
+        self.dump_audio.as_ref().map(|dir| AudioDump {
+            dir: dir.clone(),
+            format: self.dump_audio_format,
+            max_bytes: self.dump_audio_max_mb.saturating_mul(1024 * 1024),
+            trim_below_db: (!self.dump_audio_keep_silence).then_some(self.voice_vad_threshold_db),
+            normalize: self.dump_audio_normalize,
+        })
+    }
 }
 
 /// Resolve the repository root by walking up from the Cargo manifest.
@@ -333,6 +487,42 @@ pub(super) fn discover_default_whisper_model(
     None
 }
 
+/// Resolve `--model-fast`/`--model-accurate`: an existing file, or a model name
+/// installed in the repo's `whisper_models/` directory.
+fn resolve_model_arg(
+    value: Option<String>,
+    flag: &str,
+    repo_root: &Path,
+) -> Result<Option<String>> {
+    let Some(value) = value else {
+        return Ok(None);
+    };
+    let trimmed = value.trim();
+    let path = Path::new(trimmed);
+    let resolved = if path.is_file() {
+        path.canonicalize()
+            .with_context(|| format!("failed to canonicalize {flag} '{trimmed}'"))?
+    } else {
+        let models_dir = repo_root.join("whisper_models");
+        [
+            models_dir.join(format!("ggml-{trimmed}.en.bin")),
+            models_dir.join(format!("ggml-{trimmed}.bin")),
+        ]
+        .into_iter()
+        .find(|candidate| candidate.is_file())
+        .ok_or_else(|| {
+            anyhow!(
+                "{flag} '{trimmed}' is neither a model file nor a model in {}",
+                models_dir.display()
+            )
+        })?
+    };
+    resolved
+        .to_str()
+        .map(|s| Some(s.to_string()))
+        .ok_or_else(|| anyhow!("{flag} path must be valid UTF-8"))
+}
+
 /// Allow either a known binary name or an absolute path.
 pub(super) fn sanitize_binary(value: &str, flag: &str, allowlist: &[&str]) -> Result<String> {
     let trimmed = value.trim();
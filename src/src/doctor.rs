@@ -1,9 +1,30 @@
 //! Doctor-report assembly that surfaces runtime diagnostics and environment mismatches.
-
-use crate::{audio::Recorder, config::AppConfig, crash_log_path, log_file_path};
+//!
+//! `--doctor-bench` adds a benchmark: a synthetic utterance runs through the
+//! capture pipeline and the configured STT backend, and the measured times are
+//! checked against latency targets, so a setup that will feel sluggish shows
+//! up before the first live dictation.
+
+use crate::{
+    audio::{offline_capture_from_pcm, Recorder, VadConfig},
+    config::{AppConfig, SttBackend},
+    legacy_tui::{resolve_log_dir, CRASH_LOG_FILE_NAME, LOG_FILE_NAME},
+    stt::{RemoteTranscriber, Transcriber},
+    voice::create_vad_engine,
+};
+use anyhow::{anyhow, Result};
 use crossterm::terminal::size as terminal_size;
+use std::f32::consts::PI;
+use std::time::Instant;
 use std::{env, fmt::Display};
 
+/// Length of the synthetic utterance the benchmark transcribes.
+const BENCH_SPEECH_MS: u64 = 3_000;
+/// Longest acceptable wait from the end of speech to the transcript.
+const BENCH_TRANSCRIPT_TARGET_MS: u64 = 2_000;
+/// Longest acceptable capture processing (VAD, preprocessing) per second of audio.
+const BENCH_CAPTURE_TARGET_MS_PER_SEC: u64 = 50;
+
 /// Structured text report builder used by `--doctor` commands.
 pub struct DoctorReport {
     lines: Vec<String>,
@@ -90,14 +111,18 @@ pub fn base_doctor_report(config: &AppConfig, binary_name: &str) -> DoctorReport
             "disabled"
         },
     );
-    report.push_kv("log_file", log_file_path().display());
-    report.push_kv("crash_log", crash_log_path().display());
+    let log_dir = resolve_log_dir(resolved);
+    report.push_kv("log_file", log_dir.join(LOG_FILE_NAME).display());
+    report.push_kv("crash_log", log_dir.join(CRASH_LOG_FILE_NAME).display());
     report.push_kv("pipeline_script", resolved.pipeline_script.display());
     report.push_kv("whisper_model", &resolved.whisper_model);
     report.push_kv(
         "whisper_model_path",
         resolved.whisper_model_path.as_deref().unwrap_or("unset"),
     );
+    report.push_kv("build_features", build_features());
+    report.push_kv("stt_backend", resolved.stt_backend.label());
+    report.push_kv("stt_device", resolved.stt_device.label());
     report.push_kv("python_cmd", &resolved.python_cmd);
     report.push_kv("ffmpeg_cmd", &resolved.ffmpeg_cmd);
 
@@ -124,6 +149,175 @@ pub fn base_doctor_report(config: &AppConfig, binary_name: &str) -> DoctorReport
     report
 }
 
+/// Append the `--doctor-bench` section; returns how many latency targets were missed.
+pub fn append_bench_report(report: &mut DoctorReport, config: &AppConfig) -> usize {
+    let mut resolved = config.clone();
+    if resolved.validate().is_err() {
+        resolved = config.clone();
+    }
+    let pipeline = resolved.voice_pipeline_config();
+    let silence_ms = pipeline.silence_tail_ms + 500;
+    let samples = synthetic_utterance(pipeline.sample_rate, BENCH_SPEECH_MS, silence_ms);
+    let mut missed = 0;
+
+    report.section("Benchmark");
+    report.push_kv(
+        "clip",
+        format!("{BENCH_SPEECH_MS} ms synthetic speech + {silence_ms} ms silence"),
+    );
+    let vad_config: VadConfig = (&pipeline).into();
+    let mut vad = create_vad_engine(&pipeline);
+    let started = Instant::now();
+    let capture = offline_capture_from_pcm(&samples, &vad_config, vad.as_mut());
+    let capture_ms = elapsed_ms(started);
+    let capture_target = BENCH_CAPTURE_TARGET_MS_PER_SEC * (BENCH_SPEECH_MS + silence_ms) / 1000;
+    missed += push_target(report, "capture_processing", capture_ms, capture_target);
+    if capture.metrics.speech_ms == 0 {
+        report.push_kv(
+            "vad",
+            "FAIL: no speech detected in the clip (check --voice-vad-threshold-db)",
+        );
+        missed += 1;
+    }
+    report.push_kv(
+        "end_of_speech_wait",
+        format!("{} ms (--voice-silence-tail-ms)", pipeline.silence_tail_ms),
+    );
+
+    match bench_transcription(&resolved, &capture.audio, pipeline.sample_rate) {
+        Ok(stt) => {
+            if let Some(load_ms) = stt.load_ms {
+                report.push_kv("model_load", format!("{load_ms} ms"));
+            }
+            report.push_kv("stt", format!("{} ms ({})", stt.elapsed_ms, stt.label));
+            let latency_ms = pipeline.silence_tail_ms + stt.elapsed_ms;
+            let over = push_target(
+                report,
+                "transcript_latency",
+                latency_ms,
+                BENCH_TRANSCRIPT_TARGET_MS,
+            );
+            if over > 0 {
+                report.push_line(if stt.elapsed_ms > pipeline.silence_tail_ms {
+                    "  hint: transcription dominates; try a smaller --whisper-model or --stt-device gpu"
+                } else {
+                    "  hint: the end-of-speech wait dominates; try a lower --voice-silence-tail-ms"
+                });
+            }
+            missed += over;
+        }
+        Err(err) => {
+            report.push_kv("stt", format!("FAIL: {err:#}"));
+            missed += 1;
+        }
+    }
+
+    report.push_kv(
+        "result",
+        match missed {
+            0 => "pass".to_string(),
+            n => format!("{n} target(s) missed"),
+        },
+    );
+    missed
+}
+
+struct BenchTranscription {
+    label: String,
+    /// Model load time, for local Whisper.
+    load_ms: Option<u64>,
+    elapsed_ms: u64,
+}
+
+fn bench_transcription(
+    config: &AppConfig,
+    samples: &[f32],
+    sample_rate: u32,
+) -> Result<BenchTranscription> {
+    if config.stt_backend != SttBackend::Local {
+        let remote = RemoteTranscriber::from_config(config)
+            .ok_or_else(|| anyhow!("remote STT is not configured"))?;
+        let started = Instant::now();
+        remote.transcribe(samples, sample_rate, &config.lang)?;
+        return Ok(BenchTranscription {
+            label: remote.label().to_string(),
+            load_ms: None,
+            elapsed_ms: elapsed_ms(started),
+        });
+    }
+    let model_path = config
+        .whisper_model_path
+        .as_deref()
+        .ok_or_else(|| anyhow!("no Whisper model found (set --whisper-model-path)"))?;
+    let started = Instant::now();
+    let transcriber = Transcriber::with_device(model_path, config.stt_device)?;
+    let load_ms = elapsed_ms(started);
+    // The first run pays one-time setup that a live session only pays once.
+    let warmup = samples.len().min(sample_rate as usize);
+    transcriber.transcribe(&samples[..warmup], config)?;
+    let started = Instant::now();
+    transcriber.transcribe(samples, config)?;
+    Ok(BenchTranscription {
+        label: format!("{} on {}", config.whisper_model, transcriber.device_label()),
+        load_ms: Some(load_ms),
+        elapsed_ms: elapsed_ms(started),
+    })
+}
+
+/// Report `measured_ms` against `target_ms`; returns 1 when the target was missed.
+fn push_target(report: &mut DoctorReport, key: &str, measured_ms: u64, target_ms: u64) -> usize {
+    let missed = measured_ms > target_ms;
+    let verdict = if missed { "FAIL" } else { "pass" };
+    report.push_kv(
+        key,
+        format!("{measured_ms} ms (target <= {target_ms} ms, {verdict})"),
+    );
+    usize::from(missed)
+}
+
+fn elapsed_ms(started: Instant) -> u64 {
+    u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX)
+}
+
+/// A voiced tone with harmonics, pulsed at a syllable rate, followed by silence.
+fn synthetic_utterance(sample_rate: u32, speech_ms: u64, silence_ms: u64) -> Vec<f32> {
+    let rate = sample_rate as f32;
+    let speech = (speech_ms * u64::from(sample_rate) / 1000) as usize;
+    let silence = (silence_ms * u64::from(sample_rate) / 1000) as usize;
+    let mut samples: Vec<f32> = (0..speech)
+        .map(|n| {
+            let t = n as f32 / rate;
+            let voice: f32 = (1..=4)
+                .map(|h| (2.0 * PI * 140.0 * h as f32 * t).sin() / h as f32)
+                .sum();
+            let envelope = 0.6 + 0.4 * (2.0 * PI * 4.0 * t).sin();
+            0.2 * voice * envelope
+        })
+        .collect();
+    samples.resize(speech + silence, 0.0);
+    samples
+}
+
+/// Voice-related cargo features compiled into this binary, e.g. `audio,stt,python-fallback`.
+fn build_features() -> String {
+    let features: Vec<&str> = [
+        ("audio", cfg!(feature = "audio")),
+        ("stt", cfg!(feature = "stt")),
+        ("python-fallback", cfg!(feature = "python-fallback")),
+        ("overlay-only", cfg!(feature = "overlay-only")),
+        ("metal", cfg!(feature = "metal")),
+        ("cuda", cfg!(feature = "cuda")),
+    ]
+    .into_iter()
+    .filter_map(|(name, enabled)| enabled.then_some(name))
+    .collect();
+    if features.is_empty() {
+        "none".to_string()
+    } else {
+        features.join(",")
+    }
+}
+
 fn has_cursor_marker_env() -> bool {
     for key in [
         "CURSOR_TRACE_ID",
@@ -303,6 +497,36 @@ mod tests {
         })
     }
 
+    #[test]
+    fn synthetic_utterance_is_captured_as_speech() {
+        use crate::audio::SimpleThresholdVad;
+        use clap::Parser;
+
+        let config = AppConfig::parse_from(["voiceterm"]);
+        let pipeline = config.voice_pipeline_config();
+        let samples = synthetic_utterance(pipeline.sample_rate, BENCH_SPEECH_MS, 1_500);
+        assert_eq!(samples.len(), pipeline.sample_rate as usize * 9 / 2);
+        let vad_config: VadConfig = (&pipeline).into();
+        let mut vad = SimpleThresholdVad::new(pipeline.vad_threshold_db);
+        let capture = offline_capture_from_pcm(&samples, &vad_config, &mut vad);
+        assert!(
+            capture.metrics.speech_ms >= 2_000,
+            "{:?}",
+            capture.metrics.speech_ms
+        );
+    }
+
+    #[test]
+    fn bench_targets_count_misses() {
+        let mut report = DoctorReport::new("bench");
+        assert_eq!(push_target(&mut report, "fast", 120, 2_000), 0);
+        assert_eq!(push_target(&mut report, "slow", 2_400, 2_000), 1);
+        assert_eq!(
+            report.render(),
+            "bench\n  fast: 120 ms (target <= 2000 ms, pass)\n  slow: 2400 ms (target <= 2000 ms, FAIL)"
+        );
+    }
+
     #[test]
     fn format_term_program_for_report_defaults_to_raw_term_program() {
         with_term_program_env(Some("vscode"), Some("1.97.0"), None, None, || {
@@ -123,9 +123,12 @@ impl IpcState {
 
         // Load STT lazily from config path; failures remain recoverable.
         let transcriber = if let Some(model_path) = &config.whisper_model_path {
-            match stt::Transcriber::new(model_path) {
+            match stt::Transcriber::with_device(model_path, config.stt_device) {
                 Ok(t) => {
-                    log_debug("Whisper transcriber initialized");
+                    log_debug(&format!(
+                        "Whisper transcriber initialized on {}",
+                        t.device_label()
+                    ));
                     Some(Arc::new(Mutex::new(t)))
                 }
                 Err(e) => {
@@ -889,21 +892,30 @@ pub(super) fn process_voice_events(job: &VoiceJob, cancelled: bool) -> bool {
                     text,
                     source,
                     metrics,
+                    ..
                 } => {
                     let duration_ms = metrics.as_ref().map(|m| m.capture_ms).unwrap_or(0);
                     send_event(&IpcEvent::VoiceEnd { error: None });
                     send_event(&IpcEvent::Transcript { text, duration_ms });
                     log_debug(&format!("Voice transcript via {}", source.label()));
                 }
-                VoiceJobMessage::Empty { source, metrics: _ } => {
+                VoiceJobMessage::Empty { source, metrics } => {
+                    let reason = if metrics
+                        .as_ref()
+                        .is_some_and(|metrics| metrics.mic_appears_muted())
+                    {
+                        audio::MIC_MUTED_STATUS
+                    } else {
+                        "No speech detected"
+                    };
                     send_event(&IpcEvent::VoiceEnd {
-                        error: Some("No speech detected".to_string()),
+                        error: Some(reason.to_string()),
                     });
                     log_debug(&format!("Voice empty via {}", source.label()));
                 }
-                VoiceJobMessage::Error(message) => {
+                VoiceJobMessage::Error(error) => {
                     send_event(&IpcEvent::VoiceEnd {
-                        error: Some(message),
+                        error: Some(error.message),
                     });
                 }
             }
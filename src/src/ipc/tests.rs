@@ -11,6 +11,7 @@ use crate::codex::{
 use crate::config::AppConfig;
 use crate::pty_session::test_pty_session;
 use crate::voice;
+use crate::voice_error::{VoiceError, VoiceErrorKind};
 use crate::{PipelineJsonResult, PipelineMetrics, VoiceJob, VoiceJobMessage};
 use clap::Parser;
 use crossbeam_channel::bounded;
@@ -484,6 +485,8 @@ fn run_ipc_loop_processes_active_jobs() {
         .send(VoiceJobMessage::Transcript {
             text: "hello".to_string(),
             source: voice::VoiceCaptureSource::Native,
+            confidence: None,
+            words: Vec::new(),
             metrics: None,
         })
         .unwrap();
@@ -749,6 +752,8 @@ fn process_voice_events_handles_transcript() {
     tx.send(VoiceJobMessage::Transcript {
         text: "hello".to_string(),
         source: voice::VoiceCaptureSource::Native,
+        confidence: None,
+        words: Vec::new(),
         metrics: Some(audio::CaptureMetrics {
             capture_ms: 123,
             ..Default::default()
@@ -798,7 +803,11 @@ fn process_voice_events_handles_error() {
         handle: None,
         stop_flag: Arc::new(AtomicBool::new(false)),
     };
-    tx.send(VoiceJobMessage::Error("boom".to_string())).unwrap();
+    tx.send(VoiceJobMessage::Error(VoiceError::new(
+        VoiceErrorKind::Unknown,
+        "boom",
+    )))
+    .unwrap();
 
     assert!(process_voice_events(&job, false));
     let events = events_since(snapshot);
@@ -1,66 +1,230 @@
 //! Legacy TUI logging setup so diagnostics match overlay logging conventions.
 
-use crate::config::AppConfig;
+use crate::config::{AppConfig, LogFormat, LogLevel};
 use crate::telemetry;
+use serde_json::{Map, Value};
 use std::{
+    collections::HashMap,
     env, fs,
     io::Write,
     panic,
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::{
-        atomic::{AtomicBool, Ordering},
+        atomic::{AtomicBool, AtomicU8, Ordering},
         Mutex, OnceLock,
     },
-    time::{SystemTime, UNIX_EPOCH},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
+pub(crate) const LOG_FILE_NAME: &str = "voiceterm_tui.log";
+pub(crate) const CRASH_LOG_FILE_NAME: &str = "voiceterm_crash.log";
 const LOG_MAX_BYTES: u64 = 5 * 1024 * 1024;
+/// Start a fresh log file once the active one spans more than a day.
+const LOG_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+/// Rotated files kept beside the active log (`voiceterm_tui.log.1` is the newest).
+const LOG_BACKUPS: usize = 3;
+/// Rotated files older than this are deleted when logging starts.
+const LOG_BACKUP_MAX_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
 const CRASH_LOG_MAX_BYTES: u64 = 256 * 1024;
 static LOG_ENABLED: AtomicBool = AtomicBool::new(false);
 static LOG_CONTENT_ENABLED: AtomicBool = AtomicBool::new(false);
 static CRASH_LOG_ENABLED: AtomicBool = AtomicBool::new(false);
 static LOG_STATE: OnceLock<Mutex<LogState>> = OnceLock::new();
+static LOG_DIR: Mutex<Option<PathBuf>> = Mutex::new(None);
+static LOG_LEVEL: AtomicU8 = AtomicU8::new(LogLevel::Debug as u8);
+static LOG_JSON: AtomicBool = AtomicBool::new(false);
+/// Minimum gap between lines of one throttled event class.
+const LOG_THROTTLE_WINDOW: Duration = Duration::from_secs(10);
+static LOG_THROTTLE: OnceLock<Mutex<LogThrottle>> = OnceLock::new();
 
-/// Path to the temp log file we rotate between runs.
+/// Directory logs are written to: `--log-dir` when set, otherwise the system temp dir.
+pub(crate) fn resolve_log_dir(config: &AppConfig) -> PathBuf {
+    config.log_dir.clone().unwrap_or_else(env::temp_dir)
+}
+
+fn log_dir() -> PathBuf {
+    LOG_DIR
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+        .unwrap_or_else(env::temp_dir)
+}
+
+/// Path to the active debug log file; older runs rotate to numbered backups beside it.
 pub fn log_file_path() -> PathBuf {
-    env::temp_dir().join("voiceterm_tui.log")
+    log_dir().join(LOG_FILE_NAME)
 }
 
 /// Path to the crash log file (metadata only).
 pub fn crash_log_path() -> PathBuf {
-    env::temp_dir().join("voiceterm_crash.log")
+    log_dir().join(CRASH_LOG_FILE_NAME)
+}
+
+/// Subsystem tag written in front of every debug log line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LogComponent {
+    Overlay,
+    Audio,
+    Stt,
+    Pty,
+    Ipc,
+    Core,
+}
+
+impl LogComponent {
+    /// Infer the component from the source file that emitted the log line.
+    fn from_source_path(path: &str) -> Self {
+        let path = path.replace('\\', "/");
+        if path.contains("/bin/") {
+            Self::Overlay
+        } else if path.contains("/audio/")
+            || path.ends_with("vad_earshot.rs")
+            || path.ends_with("mic_meter.rs")
+        {
+            Self::Audio
+        } else if path.ends_with("/stt.rs") || path.ends_with("/voice.rs") {
+            Self::Stt
+        } else if path.contains("/pty_session/") || path.ends_with("pty_backend.rs") {
+            Self::Pty
+        } else if path.contains("/ipc/") {
+            Self::Ipc
+        } else {
+            Self::Core
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Overlay => "overlay",
+            Self::Audio => "audio",
+            Self::Stt => "stt",
+            Self::Pty => "pty",
+            Self::Ipc => "ipc",
+            Self::Core => "core",
+        }
+    }
+}
+
+/// UTC timestamp with millisecond precision, e.g. `2026-01-31T08:15:02.417Z`.
+fn format_timestamp(time: SystemTime) -> String {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let secs = since_epoch.as_secs();
+    let (year, month, day) = civil_from_days((secs / 86_400) as i64);
+    let secs_of_day = secs % 86_400;
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{:03}Z",
+        secs_of_day / 3_600,
+        secs_of_day % 3_600 / 60,
+        secs_of_day % 60,
+        since_epoch.subsec_millis()
+    )
+}
+
+/// Convert days since the Unix epoch to a proleptic Gregorian (year, month, day).
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Size and age limits for the debug log and its rotated backups.
+#[derive(Debug, Clone, Copy)]
+struct RotationPolicy {
+    max_bytes: u64,
+    max_age: Duration,
+    backups: usize,
+    backup_max_age: Duration,
+}
+
+const DEBUG_LOG_POLICY: RotationPolicy = RotationPolicy {
+    max_bytes: LOG_MAX_BYTES,
+    max_age: LOG_MAX_AGE,
+    backups: LOG_BACKUPS,
+    backup_max_age: LOG_BACKUP_MAX_AGE,
+};
+
+fn backup_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{index}"));
+    PathBuf::from(name)
 }
 
 struct LogWriter {
     path: PathBuf,
     file: fs::File,
-    max_bytes: u64,
+    policy: RotationPolicy,
     bytes_written: u64,
+    started_at: SystemTime,
 }
 
 impl LogWriter {
-    fn new(path: PathBuf, max_bytes: u64) -> Option<Self> {
-        let mut bytes_written = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
-        if bytes_written > max_bytes {
-            let _ = fs::remove_file(&path);
-            bytes_written = 0;
+    fn new(path: PathBuf, policy: RotationPolicy) -> Option<Self> {
+        let now = SystemTime::now();
+        for index in 1..=policy.backups {
+            let backup = backup_path(&path, index);
+            let expired = fs::metadata(&backup)
+                .and_then(|meta| meta.modified())
+                .is_ok_and(|modified| {
+                    now.duration_since(modified).unwrap_or_default() > policy.backup_max_age
+                });
+            if expired {
+                let _ = fs::remove_file(&backup);
+            }
         }
         let file = fs::OpenOptions::new()
             .create(true)
             .append(true)
             .open(&path)
             .ok()?;
-        Some(Self {
+        let meta = file.metadata().ok();
+        let bytes_written = meta.as_ref().map(|m| m.len()).unwrap_or(0);
+        let started_at = meta
+            .and_then(|m| m.created().or_else(|_| m.modified()).ok())
+            .unwrap_or(now);
+        let mut writer = Self {
             path,
             file,
-            max_bytes,
+            policy,
             bytes_written,
-        })
+            started_at,
+        };
+        if writer.needs_rotation(0, now) {
+            writer.rotate(now);
+        }
+        Some(writer)
+    }
+
+    fn needs_rotation(&self, next_len: usize, now: SystemTime) -> bool {
+        if self.bytes_written == 0 {
+            return false;
+        }
+        let age = now.duration_since(self.started_at).unwrap_or_default();
+        self.bytes_written.saturating_add(next_len as u64) > self.policy.max_bytes
+            || age > self.policy.max_age
     }
 
-    fn rotate_if_needed(&mut self, next_len: usize) {
-        if self.bytes_written.saturating_add(next_len as u64) <= self.max_bytes {
-            return;
+    /// Shift `log.N-1` to `log.N` down to `log` -> `log.1`, then reopen an empty log.
+    fn rotate(&mut self, now: SystemTime) {
+        if self.policy.backups > 0 {
+            for index in (1..self.policy.backups).rev() {
+                let _ = fs::rename(
+                    backup_path(&self.path, index),
+                    backup_path(&self.path, index + 1),
+                );
+            }
+            let _ = fs::rename(&self.path, backup_path(&self.path, 1));
         }
         if let Ok(file) = fs::OpenOptions::new()
             .create(true)
@@ -70,11 +234,15 @@ impl LogWriter {
         {
             self.file = file;
             self.bytes_written = 0;
+            self.started_at = now;
         }
     }
 
     fn write_line(&mut self, line: &str) {
-        self.rotate_if_needed(line.len());
+        let now = SystemTime::now();
+        if self.needs_rotation(line.len(), now) {
+            self.rotate(now);
+        }
         if self.file.write_all(line.as_bytes()).is_ok() {
             self.bytes_written = self.bytes_written.saturating_add(line.len() as u64);
         }
@@ -90,6 +258,14 @@ fn log_state() -> &'static Mutex<LogState> {
     LOG_STATE.get_or_init(|| Mutex::new(LogState::default()))
 }
 
+fn open_debug_log() -> Option<LogWriter> {
+    let path = log_file_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).ok()?;
+    }
+    LogWriter::new(path, DEBUG_LOG_POLICY)
+}
+
 /// Configure logging based on CLI flags or environment.
 pub fn init_logging(config: &AppConfig) {
     let enabled = (config.logs || config.log_timings) && !config.no_logs;
@@ -97,30 +273,111 @@ pub fn init_logging(config: &AppConfig) {
     LOG_ENABLED.store(enabled, Ordering::Relaxed);
     LOG_CONTENT_ENABLED.store(content_enabled, Ordering::Relaxed);
     CRASH_LOG_ENABLED.store(enabled, Ordering::Relaxed);
+    LOG_LEVEL.store(config.log_level as u8, Ordering::Relaxed);
+    LOG_JSON.store(config.log_format == LogFormat::Json, Ordering::Relaxed);
+    *LOG_DIR
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = config.log_dir.clone();
 
-    telemetry::init_tracing(config);
+    telemetry::init_tracing(config, &log_dir());
 
     let mut state = log_state()
         .lock()
         .unwrap_or_else(|poisoned| poisoned.into_inner());
     if enabled {
-        state.writer = LogWriter::new(log_file_path(), LOG_MAX_BYTES);
+        state.writer = open_debug_log();
     } else {
         state.writer = None;
     }
 }
 
-/// Write debug messages to a temp file so we can troubleshoot without corrupting the TUI.
-pub fn log_debug(msg: &str) {
-    if !LOG_ENABLED.load(Ordering::Relaxed) {
-        return;
+/// Body of one log record: free-form text or a named event with typed fields.
+enum LogRecord<'a> {
+    Message(&'a str),
+    Event(&'a str, &'a [(&'a str, Value)]),
+}
+
+/// Render a field value for text lines; strings are unquoted and null becomes `na`.
+fn text_value(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => "na".to_string(),
+        other => other.to_string(),
     }
-    tracing::debug!(message = %msg);
-    let timestamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_secs();
-    let line = format!("[{timestamp}] {msg}\n");
+}
+
+fn format_record(
+    format: LogFormat,
+    timestamp: &str,
+    level: LogLevel,
+    component: &str,
+    record: &LogRecord<'_>,
+) -> String {
+    match format {
+        LogFormat::Text => {
+            let body = match record {
+                LogRecord::Message(msg) => (*msg).to_string(),
+                LogRecord::Event(event, fields) => {
+                    let mut body = (*event).to_string();
+                    for (key, value) in fields.iter() {
+                        body.push_str(&format!("|{key}={}", text_value(value)));
+                    }
+                    body
+                }
+            };
+            format!(
+                "{timestamp} {:<5} [{component}] {body}\n",
+                level.label().to_ascii_uppercase()
+            )
+        }
+        LogFormat::Json => {
+            let mut line = Map::new();
+            line.insert("ts".into(), Value::from(timestamp));
+            line.insert("level".into(), Value::from(level.label()));
+            line.insert("component".into(), Value::from(component));
+            match record {
+                LogRecord::Message(msg) => {
+                    line.insert("msg".into(), Value::from(*msg));
+                }
+                LogRecord::Event(event, fields) => {
+                    line.insert("event".into(), Value::from(*event));
+                    let fields: Map<String, Value> = fields
+                        .iter()
+                        .map(|(key, value)| ((*key).to_string(), value.clone()))
+                        .collect();
+                    line.insert("fields".into(), Value::Object(fields));
+                }
+            }
+            let mut encoded = Value::Object(line).to_string();
+            encoded.push('\n');
+            encoded
+        }
+    }
+}
+
+fn level_enabled(level: LogLevel) -> bool {
+    LOG_ENABLED.load(Ordering::Relaxed) && level as u8 <= LOG_LEVEL.load(Ordering::Relaxed)
+}
+
+fn write_record(level: LogLevel, caller: &panic::Location<'_>, record: LogRecord<'_>) {
+    let component = LogComponent::from_source_path(caller.file()).label();
+    match &record {
+        LogRecord::Message(msg) => match level {
+            LogLevel::Error => tracing::error!(component, message = %msg),
+            LogLevel::Warn => tracing::warn!(component, message = %msg),
+            LogLevel::Info => tracing::info!(component, message = %msg),
+            LogLevel::Debug => tracing::debug!(component, message = %msg),
+            LogLevel::Trace => tracing::trace!(component, message = %msg),
+        },
+        LogRecord::Event(event, _) => tracing::info!(component, event = %event),
+    }
+    let format = if LOG_JSON.load(Ordering::Relaxed) {
+        LogFormat::Json
+    } else {
+        LogFormat::Text
+    };
+    let timestamp = format_timestamp(SystemTime::now());
+    let line = format_record(format, &timestamp, level, component, &record);
     let mut state = log_state()
         .lock()
         .unwrap_or_else(|poisoned| poisoned.into_inner());
@@ -129,7 +386,118 @@ pub fn log_debug(msg: &str) {
     }
 }
 
+/// Write a message at `level` when logging is on and `--log-level` allows it.
+///
+/// Each line carries a UTC timestamp, the level, and the emitting component
+/// (`overlay`, `audio`, `stt`, `pty`, `ipc`, or `core`), inferred from the caller.
+#[track_caller]
+pub fn log_at(level: LogLevel, msg: &str) {
+    if level_enabled(level) {
+        write_record(level, panic::Location::caller(), LogRecord::Message(msg));
+    }
+}
+
+/// Write a named event with typed fields, e.g. latency samples for dashboards.
+///
+/// Text format renders `event|key=value|...`; JSON format nests the fields
+/// under `"fields"` so numbers stay numbers.
+#[track_caller]
+pub fn log_event(level: LogLevel, event: &str, fields: &[(&str, Value)]) {
+    if level_enabled(level) {
+        write_record(
+            level,
+            panic::Location::caller(),
+            LogRecord::Event(event, fields),
+        );
+    }
+}
+
+/// Seconds rounded to millisecond precision, for timing event fields.
+pub(crate) fn seconds_field(secs: f64) -> Value {
+    Value::from((secs * 1_000.0).round() / 1_000.0)
+}
+
+/// Write a debug-level message to the rotating log file so we can troubleshoot without corrupting the TUI.
+#[track_caller]
+pub fn log_debug(msg: &str) {
+    log_at(LogLevel::Debug, msg);
+}
+
+/// Write an informational message.
+#[track_caller]
+pub fn log_info(msg: &str) {
+    log_at(LogLevel::Info, msg);
+}
+
+/// Write a warning for recoverable failures.
+#[track_caller]
+pub fn log_warn(msg: &str) {
+    log_at(LogLevel::Warn, msg);
+}
+
+/// Write an error that stopped an operation.
+#[track_caller]
+pub fn log_error(msg: &str) {
+    log_at(LogLevel::Error, msg);
+}
+
+/// Write a debug message at most once per `LOG_THROTTLE_WINDOW` for `class`.
+///
+/// For events that can repeat every few seconds (e.g. auto-voice captures with no
+/// speech). Lines dropped in the window are counted and reported as
+/// `|suppressed=N` on the next line written for the class.
+#[track_caller]
+pub fn log_debug_throttled(class: &str, msg: &str) {
+    if !level_enabled(LogLevel::Debug) {
+        return;
+    }
+    let suppressed = LOG_THROTTLE
+        .get_or_init(|| Mutex::new(LogThrottle::default()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .admit(class, Instant::now());
+    let line = match suppressed {
+        None => return,
+        Some(0) => msg.to_string(),
+        Some(count) => format!("{msg}|suppressed={count}"),
+    };
+    write_record(
+        LogLevel::Debug,
+        panic::Location::caller(),
+        LogRecord::Message(&line),
+    );
+}
+
+/// Per-class rate limit for `log_debug_throttled`.
+#[derive(Debug, Default)]
+struct LogThrottle {
+    /// Last written time and lines dropped since, per class.
+    classes: HashMap<String, (Instant, u32)>,
+}
+
+impl LogThrottle {
+    /// `Some(dropped)` when a line for `class` may be written now, `None` to drop it.
+    fn admit(&mut self, class: &str, now: Instant) -> Option<u32> {
+        match self.classes.get_mut(class) {
+            Some((last, dropped)) if now.saturating_duration_since(*last) < LOG_THROTTLE_WINDOW => {
+                *dropped += 1;
+                None
+            }
+            Some(entry) => {
+                let dropped = entry.1;
+                *entry = (now, 0);
+                Some(dropped)
+            }
+            None => {
+                self.classes.insert(class.to_string(), (now, 0));
+                Some(0)
+            }
+        }
+    }
+}
+
 /// Write logs that may contain user content (prompt/transcript snippets).
+#[track_caller]
 pub fn log_debug_content(msg: &str) {
     if !LOG_CONTENT_ENABLED.load(Ordering::Relaxed) {
         return;
@@ -143,10 +511,7 @@ pub fn log_panic(info: &panic::PanicHookInfo<'_>) {
         return;
     }
 
-    let timestamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_secs();
+    let timestamp = format_timestamp(SystemTime::now());
     let location = info
         .location()
         .map(|loc| format!("{}:{}", loc.file(), loc.line()))
@@ -165,7 +530,7 @@ pub fn log_panic(info: &panic::PanicHookInfo<'_>) {
     };
 
     let line = format!(
-        "[{timestamp}] panic at {location}: {payload} (v{})\n",
+        "{timestamp} panic at {location}: {payload} (v{})\n",
         env!("CARGO_PKG_VERSION")
     );
     let path = crash_log_path();
@@ -193,14 +558,201 @@ pub fn log_panic(info: &panic::PanicHookInfo<'_>) {
 #[cfg(test)]
 pub(crate) fn set_logging_for_tests(enabled: bool, content_enabled: bool) {
     LOG_ENABLED.store(enabled, Ordering::Relaxed);
+    LOG_LEVEL.store(LogLevel::Debug as u8, Ordering::Relaxed);
+    LOG_JSON.store(false, Ordering::Relaxed);
     LOG_CONTENT_ENABLED.store(content_enabled, Ordering::Relaxed);
     CRASH_LOG_ENABLED.store(enabled, Ordering::Relaxed);
     let mut state = log_state()
         .lock()
         .unwrap_or_else(|poisoned| poisoned.into_inner());
     if enabled {
-        state.writer = LogWriter::new(log_file_path(), LOG_MAX_BYTES);
+        state.writer = open_debug_log();
     } else {
         state.writer = None;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_log_path(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("voiceterm-log-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).expect("create temp log dir");
+        dir.join(LOG_FILE_NAME)
+    }
+
+    fn small_policy() -> RotationPolicy {
+        RotationPolicy {
+            max_bytes: 16,
+            max_age: LOG_MAX_AGE,
+            backups: 2,
+            backup_max_age: LOG_BACKUP_MAX_AGE,
+        }
+    }
+
+    #[test]
+    fn size_cap_rotates_into_numbered_backups() {
+        let path = temp_log_path("size");
+        let mut writer = LogWriter::new(path.clone(), small_policy()).expect("open log");
+        for line in [
+            "first line 01\n",
+            "second line 2\n",
+            "third line 03\n",
+            "fourth line 4\n",
+        ] {
+            writer.write_line(line);
+        }
+        assert_eq!(fs::read_to_string(&path).unwrap(), "fourth line 4\n");
+        assert_eq!(
+            fs::read_to_string(backup_path(&path, 1)).unwrap(),
+            "third line 03\n"
+        );
+        assert_eq!(
+            fs::read_to_string(backup_path(&path, 2)).unwrap(),
+            "second line 2\n"
+        );
+        assert!(!backup_path(&path, 3).exists(), "backup count is capped");
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn age_cap_starts_a_fresh_file() {
+        let path = temp_log_path("age");
+        let mut writer = LogWriter::new(path.clone(), small_policy()).expect("open log");
+        writer.write_line("old\n");
+        writer.started_at = SystemTime::now() - LOG_MAX_AGE - Duration::from_secs(1);
+        writer.write_line("new\n");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new\n");
+        assert_eq!(fs::read_to_string(backup_path(&path, 1)).unwrap(), "old\n");
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn component_is_inferred_from_source_path() {
+        let cases = [
+            ("src/bin/voiceterm/event_loop.rs", LogComponent::Overlay),
+            (
+                "src/bin/voiceterm/audio_meter/mod.rs",
+                LogComponent::Overlay,
+            ),
+            ("src/audio/recorder.rs", LogComponent::Audio),
+            ("src/stt.rs", LogComponent::Stt),
+            ("src/voice.rs", LogComponent::Stt),
+            ("src/pty_session/pty.rs", LogComponent::Pty),
+            ("src\\pty_session\\io.rs", LogComponent::Pty),
+            ("src/ipc/session.rs", LogComponent::Ipc),
+            ("src/lock.rs", LogComponent::Core),
+        ];
+        for (path, expected) in cases {
+            assert_eq!(LogComponent::from_source_path(path), expected, "{path}");
+        }
+    }
+
+    #[test]
+    fn text_records_show_level_component_and_event_fields() {
+        let ts = "2026-01-31T08:15:02.417Z";
+        assert_eq!(
+            format_record(
+                LogFormat::Text,
+                ts,
+                LogLevel::Warn,
+                "pty",
+                &LogRecord::Message("read failed")
+            ),
+            "2026-01-31T08:15:02.417Z WARN  [pty] read failed\n"
+        );
+        let fields = [
+            ("display_ms", Value::from(412)),
+            ("stt_ms", Value::Null),
+            ("early_stop", Value::from("vad_silence")),
+        ];
+        assert_eq!(
+            format_record(
+                LogFormat::Text,
+                ts,
+                LogLevel::Info,
+                "overlay",
+                &LogRecord::Event("latency_audit", &fields)
+            ),
+            "2026-01-31T08:15:02.417Z INFO  [overlay] latency_audit|display_ms=412|stt_ms=na|early_stop=vad_silence\n"
+        );
+    }
+
+    #[test]
+    fn json_records_keep_typed_fields() {
+        let fields = [
+            ("capture_ms", Value::from(800)),
+            ("record_s", seconds_field(1.23456)),
+        ];
+        let line = format_record(
+            LogFormat::Json,
+            "2026-01-31T08:15:02.417Z",
+            LogLevel::Info,
+            "stt",
+            &LogRecord::Event("voice_metrics", &fields),
+        );
+        assert!(line.ends_with('\n'));
+        let parsed: Value = serde_json::from_str(line.trim_end()).expect("valid json line");
+        assert_eq!(parsed["level"], "info");
+        assert_eq!(parsed["component"], "stt");
+        assert_eq!(parsed["event"], "voice_metrics");
+        assert_eq!(parsed["fields"]["capture_ms"], 800);
+        assert_eq!(parsed["fields"]["record_s"], 1.235);
+
+        let message = format_record(
+            LogFormat::Json,
+            "ts",
+            LogLevel::Debug,
+            "core",
+            &LogRecord::Message("say \"hi\""),
+        );
+        let parsed: Value = serde_json::from_str(message.trim_end()).unwrap();
+        assert_eq!(parsed["msg"], "say \"hi\"");
+        assert!(parsed.get("fields").is_none());
+    }
+
+    #[test]
+    fn throttle_drops_repeats_within_window_and_reports_count() {
+        let start = Instant::now();
+        let mut throttle = LogThrottle::default();
+        assert_eq!(throttle.admit("no_speech", start), Some(0));
+        assert_eq!(
+            throttle.admit("no_speech", start + Duration::from_secs(2)),
+            None
+        );
+        assert_eq!(
+            throttle.admit("no_speech", start + Duration::from_secs(5)),
+            None
+        );
+        // Other classes are limited independently.
+        assert_eq!(throttle.admit("mic_muted", start), Some(0));
+        assert_eq!(
+            throttle.admit("no_speech", start + LOG_THROTTLE_WINDOW),
+            Some(2)
+        );
+        assert_eq!(
+            throttle.admit("no_speech", start + LOG_THROTTLE_WINDOW * 3),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn log_levels_order_from_error_to_trace() {
+        assert!(LogLevel::Error < LogLevel::Warn);
+        assert!(LogLevel::Info < LogLevel::Debug);
+        assert!(LogLevel::Debug < LogLevel::Trace);
+        assert_eq!(LogLevel::Trace as u8, 4);
+    }
+
+    #[test]
+    fn timestamps_are_utc_with_millis() {
+        let at = |secs: u64, millis: u64| {
+            format_timestamp(UNIX_EPOCH + Duration::from_millis(secs * 1_000 + millis))
+        };
+        assert_eq!(at(0, 0), "1970-01-01T00:00:00.000Z");
+        assert_eq!(at(951_782_400, 7), "2000-02-29T00:00:00.007Z");
+        assert_eq!(at(1_700_000_000, 250), "2023-11-14T22:13:20.250Z");
+    }
+}
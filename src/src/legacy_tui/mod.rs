@@ -11,8 +11,10 @@ mod tests;
 #[cfg(test)]
 pub(crate) use logging::set_logging_for_tests;
 pub use logging::{
-    crash_log_path, init_logging, log_debug, log_debug_content, log_file_path, log_panic,
+    crash_log_path, init_logging, log_at, log_debug, log_debug_content, log_debug_throttled,
+    log_error, log_event, log_file_path, log_info, log_panic, log_warn,
 };
+pub(crate) use logging::{resolve_log_dir, seconds_field, CRASH_LOG_FILE_NAME, LOG_FILE_NAME};
 pub use state::CodexApp;
 #[allow(unused_imports)]
 pub(crate) use state::{run_python_transcription, PipelineJsonResult, PipelineMetrics};
@@ -18,11 +18,12 @@ use crate::codex::{
     CodexBackendError, CodexCliBackend, CodexEvent, CodexEventKind, CodexJob, CodexJobRunner,
     CodexRequest, CODEX_SPINNER_FRAMES,
 };
-use crate::config::AppConfig;
+use crate::config::{AppConfig, LogLevel};
 use crate::voice::{self, VoiceCaptureTrigger, VoiceJob, VoiceJobMessage};
-use crate::{audio, log_debug, stt};
+use crate::{audio, log_debug, log_event, seconds_field, stt};
 use anyhow::{anyhow, bail, Context, Result};
 use serde::Deserialize;
+use serde_json::json;
 
 /// Maximum number of lines to retain in the scrollback buffer.
 pub(super) const OUTPUT_MAX_LINES: usize = 500;
@@ -206,14 +207,18 @@ pub(crate) fn run_python_transcription(
     };
     if config.log_timings {
         let elapsed = call_started.elapsed().as_secs_f64();
-        log_debug(&format!(
-            "timing|phase=python_pipeline|record_s={:.3}|stt_s={:.3}|codex_s={:.3}|total_s={:.3}|rust_elapsed_s={:.3}",
-            parsed.metrics.record_s,
-            parsed.metrics.stt_s,
-            parsed.metrics.codex_s,
-            parsed.metrics.total_s,
-            elapsed,
-        ));
+        log_event(
+            LogLevel::Info,
+            "timing",
+            &[
+                ("phase", json!("python_pipeline")),
+                ("record_s", seconds_field(parsed.metrics.record_s)),
+                ("stt_s", seconds_field(parsed.metrics.stt_s)),
+                ("codex_s", seconds_field(parsed.metrics.codex_s)),
+                ("total_s", seconds_field(parsed.metrics.total_s)),
+                ("rust_elapsed_s", seconds_field(elapsed)),
+            ],
+        );
     }
     Ok(parsed)
 }
@@ -277,7 +282,7 @@ impl CodexApp {
             let Some(model_path) = self.config.whisper_model_path.clone() else {
                 return Ok(None);
             };
-            let transcriber = stt::Transcriber::new(&model_path)?;
+            let transcriber = stt::Transcriber::with_device(&model_path, self.config.stt_device)?;
             self.transcriber = Some(Arc::new(Mutex::new(transcriber)));
         }
         Ok(self.transcriber.as_ref().cloned())
@@ -624,6 +629,7 @@ impl CodexApp {
                 text,
                 source,
                 metrics,
+                ..
             } => {
                 log_debug("Voice capture completed successfully");
                 let mut input = text;
@@ -666,7 +672,13 @@ impl CodexApp {
                     .as_ref()
                     .map(|note| format!(", {note}"))
                     .unwrap_or_default();
-                if self.voice_enabled {
+                if metrics
+                    .as_ref()
+                    .is_some_and(|metrics| metrics.mic_appears_muted())
+                {
+                    self.status = audio::MIC_MUTED_STATUS.to_string();
+                    auto_restart = self.voice_enabled;
+                } else if self.voice_enabled {
                     if let Some(note) = drop_note {
                         self.status = format!("Auto-voice enabled ({note})");
                     } else {
@@ -684,7 +696,7 @@ impl CodexApp {
             }
             VoiceJobMessage::Error(err) => {
                 log_debug(&format!("Voice capture worker error: {err}"));
-                self.status = format!("Voice capture failed: {err}");
+                self.status = err.status_message();
             }
         }
         self.request_redraw();
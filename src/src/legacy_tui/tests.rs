@@ -162,10 +162,17 @@ fn perf_smoke_emits_voice_metrics() {
             capture_ms: 800,
             transcribe_ms: 0,
             speech_ms: 600,
+            voiced_span_ms: 600,
             silence_tail_ms: 200,
             frames_processed: 5,
             frames_dropped: 0,
             early_stop_reason: audio::StopReason::VadSilence { tail_ms: 200 },
+            digital_silence_ms: 0,
+            preroll_ms: 120,
+            echo_muted_ms: 0,
+            speaker_rejected: false,
+            utterance_rejected: false,
+            audio_path: None,
         };
         voice::log_voice_metrics(&metrics);
         let contents =
@@ -205,6 +212,23 @@ fn logging_enabled_writes_log() {
     });
 }
 
+#[test]
+fn log_level_filters_lower_severity() {
+    with_log_lock(|| {
+        clear_log_env();
+        let log_path = log_file_path();
+        let _ = std::fs::remove_file(&log_path);
+        let mut config = AppConfig::parse_from(["voiceterm-tests", "--log-level", "warn"]);
+        config.logs = true;
+        init_logging(&config);
+        log_debug("below-threshold");
+        crate::log_warn("at-threshold");
+        let contents = std::fs::read_to_string(&log_path).expect("log file should be created");
+        assert!(!contents.contains("below-threshold"));
+        assert!(contents.contains("WARN  [core] at-threshold"));
+    });
+}
+
 #[test]
 fn log_content_requires_flag() {
     with_log_lock(|| {
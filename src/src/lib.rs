@@ -11,6 +11,7 @@ pub mod legacy_ui;
 mod lock;
 pub mod mic_meter;
 pub mod pty_session;
+pub mod stats;
 pub mod stt;
 mod telemetry;
 pub mod terminal_restore;
@@ -18,9 +19,14 @@ pub mod utf8_safe;
 #[cfg(feature = "vad_earshot")]
 pub mod vad_earshot;
 pub mod voice;
+mod voice_compare;
+pub mod voice_error;
+mod voice_stream;
 
 mod legacy_tui;
 
 pub use legacy_tui::*;
 pub(crate) use lock::lock_or_recover;
-pub use voice::{VoiceCaptureSource, VoiceCaptureTrigger, VoiceJob, VoiceJobMessage};
+pub use voice::{
+    VoiceCaptureSource, VoiceCaptureTrigger, VoiceJob, VoiceJobMessage, VOICE_CAPTURE_BUILT,
+};
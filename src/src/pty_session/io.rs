@@ -1,10 +1,11 @@
 //! PTY read/write loops that keep stream forwarding robust under partial escapes.
 
-use crate::log_debug;
+use crate::log_warn;
 use anyhow::{anyhow, Context, Result};
 use crossbeam_channel::Sender;
 use std::io::{self, ErrorKind};
 use std::os::unix::io::RawFd;
+use std::sync::Arc;
 use std::thread;
 use std::time::Duration;
 #[cfg(any(test, feature = "mutants"))]
@@ -17,6 +18,7 @@ use super::osc::{
     find_csi_sequence, find_osc_terminator, respond_to_terminal_queries,
     respond_to_terminal_queries_passthrough,
 };
+use super::stats::PtyIoStats;
 
 pub(super) fn should_retry_read_error(err: &io::Error) -> bool {
     err.kind() == ErrorKind::Interrupted || err.kind() == ErrorKind::WouldBlock
@@ -103,7 +105,7 @@ pub(super) fn spawn_reader_thread(master_fd: RawFd, tx: Sender<Vec<u8>>) -> thre
                 thread::sleep(Duration::from_millis(10));
                 continue;
             }
-            log_debug(&format!("PTY read error: {err}"));
+            log_warn(&format!("PTY read error: {err}"));
             break;
         }
     })
@@ -113,6 +115,7 @@ pub(super) fn spawn_reader_thread(master_fd: RawFd, tx: Sender<Vec<u8>>) -> thre
 pub(super) fn spawn_passthrough_reader_thread(
     master_fd: RawFd,
     tx: Sender<Vec<u8>>,
+    stats: Arc<PtyIoStats>,
 ) -> thread::JoinHandle<()> {
     thread::spawn(move || {
         let mut buffer = [0u8; 4096];
@@ -143,6 +146,7 @@ pub(super) fn spawn_passthrough_reader_thread(
                 )
             };
             if n > 0 {
+                stats.record_read(n as usize);
                 let mut data = if pending.is_empty() {
                     buffer.get(..n as usize).unwrap_or(&[]).to_vec()
                 } else {
@@ -171,7 +175,7 @@ pub(super) fn spawn_passthrough_reader_thread(
                 thread::sleep(Duration::from_millis(10));
                 continue;
             }
-            log_debug(&format!("PTY read error: {err}"));
+            log_warn(&format!("PTY read error: {err}"));
             break;
         }
     })
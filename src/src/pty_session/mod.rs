@@ -5,11 +5,13 @@ mod counters;
 mod io;
 mod osc;
 mod pty;
+mod stats;
 
 #[cfg(test)]
 mod tests;
 
 pub use pty::{PtyCliSession, PtyOverlaySession};
+pub use stats::{PtyResize, PtySessionStats};
 
 #[cfg(any(test, feature = "mutants"))]
 #[allow(unused_imports)]
@@ -4,7 +4,7 @@
 //! running in an interactive terminal. Handles I/O forwarding, window resize
 //! signals, and graceful process termination.
 
-use crate::log_debug;
+use crate::{log_debug, log_warn};
 use anyhow::{anyhow, Context, Result};
 use crossbeam_channel::{bounded, Receiver};
 use std::ffi::CString;
@@ -13,6 +13,7 @@ use std::mem;
 use std::os::unix::io::RawFd;
 use std::os::unix::process::ExitStatusExt;
 use std::ptr;
+use std::sync::Arc;
 use std::thread;
 use std::time::{Duration, Instant};
 
@@ -23,6 +24,7 @@ use super::counters::{
 };
 use super::counters::{read_output_elapsed, read_output_grace_elapsed, wait_for_exit_elapsed};
 use super::io::{spawn_passthrough_reader_thread, spawn_reader_thread, try_write, write_all};
+use super::stats::{PtyIoStats, PtyResize, PtySessionStats};
 
 /// Uses PTY to run a backend CLI in a proper terminal environment.
 pub struct PtyCliSession {
@@ -202,11 +204,11 @@ impl Drop for PtyCliSession {
             }
             if !wait_for_exit(self.child_pid, Duration::from_millis(500)) {
                 if let Err(err) = signal_process_group_or_pid(self.child_pid, libc::SIGTERM) {
-                    log_debug(&format!("SIGTERM to PTY session failed: {}", err));
+                    log_warn(&format!("SIGTERM to PTY session failed: {}", err));
                 }
                 if !wait_for_exit(self.child_pid, Duration::from_millis(500)) {
                     if let Err(err) = signal_process_group_or_pid(self.child_pid, libc::SIGKILL) {
-                        log_debug(&format!("SIGKILL to PTY session failed: {}", err));
+                        log_warn(&format!("SIGKILL to PTY session failed: {}", err));
                     }
                     #[cfg(any(test, feature = "mutants"))]
                     {
@@ -238,6 +240,7 @@ pub struct PtyOverlaySession {
     /// Stream of raw PTY output chunks from the child process.
     pub output_rx: Receiver<Vec<u8>>,
     pub(super) _output_thread: thread::JoinHandle<()>,
+    pub(super) stats: Arc<PtyIoStats>,
 }
 
 impl PtyOverlaySession {
@@ -272,41 +275,80 @@ impl PtyOverlaySession {
             set_nonblocking(master_fd)?;
 
             let (tx, rx) = bounded(100);
-            let output_thread = spawn_passthrough_reader_thread(master_fd, tx);
+            let stats = Arc::new(PtyIoStats::default());
+            let output_thread = spawn_passthrough_reader_thread(master_fd, tx, Arc::clone(&stats));
 
             Ok(Self {
                 master_fd,
                 child_pid,
                 output_rx: rx,
                 _output_thread: output_thread,
+                stats,
             })
         }
     }
 
     /// Write raw bytes to the PTY master.
     pub fn send_bytes(&mut self, bytes: &[u8]) -> Result<()> {
-        write_all(self.master_fd, bytes)
+        self.write_counted(bytes)
     }
 
     /// Attempt a single non-blocking write to the PTY master.
     pub fn try_send_bytes(&mut self, bytes: &[u8]) -> io::Result<usize> {
-        try_write(self.master_fd, bytes)
+        let written = try_write(self.master_fd, bytes)?;
+        self.stats.record_write(written);
+        Ok(written)
     }
 
     /// Write text to the PTY master.
     pub fn send_text(&mut self, text: &str) -> Result<()> {
-        write_all(self.master_fd, text.as_bytes())
+        self.write_counted(text.as_bytes())
     }
 
     /// Write text to the PTY master and ensure it ends with a newline.
     pub fn send_text_with_newline(&mut self, text: &str) -> Result<()> {
-        write_all(self.master_fd, text.as_bytes())?;
+        self.write_counted(text.as_bytes())?;
         if !text.ends_with('\n') {
-            write_all(self.master_fd, b"\n")?;
+            self.write_counted(b"\n")?;
         }
         Ok(())
     }
 
+    fn write_counted(&self, bytes: &[u8]) -> Result<()> {
+        write_all(self.master_fd, bytes)?;
+        self.stats.record_write(bytes.len());
+        Ok(())
+    }
+
+    /// Byte the child's terminal erases a character with (`stty erase`), DEL when unset.
+    pub fn erase_byte(&self) -> u8 {
+        // SAFETY: libc::termios is a plain C struct; tcgetattr only fills it from master_fd.
+        let mut termios: libc::termios = unsafe { mem::zeroed() };
+        let read = unsafe { libc::tcgetattr(self.master_fd, &mut termios) } == 0;
+        match termios.c_cc[libc::VERASE] {
+            // 0 is _POSIX_VDISABLE on Linux and macOS.
+            byte if read && byte != 0 => byte,
+            _ => 0x7f,
+        }
+    }
+
+    /// Process id of the backend CLI (also its process-group id).
+    pub fn child_pid(&self) -> i32 {
+        self.child_pid
+    }
+
+    /// Snapshot of bytes read/written, read count, and resizes since the session started.
+    pub fn stats(&self) -> PtySessionStats {
+        self.stats.snapshot()
+    }
+
+    /// Subscribe to window-size changes applied through `set_winsize`.
+    ///
+    /// Each call returns an independent receiver; dropping it unsubscribes.
+    pub fn resize_events(&self) -> Receiver<PtyResize> {
+        self.stats.subscribe_resizes()
+    }
+
     /// Update the PTY window size and notify the child.
     pub fn set_winsize(&self, rows: u16, cols: u16) -> Result<()> {
         // SAFETY: libc::winsize is a plain C struct; zeroed is a valid baseline.
@@ -322,11 +364,18 @@ impl PtyOverlaySession {
         }
         // Notify the full PTY process tree about resize events.
         let _ = signal_process_group_or_pid(self.child_pid, libc::SIGWINCH);
+        self.stats.record_resize(PtyResize {
+            rows: ws.ws_row,
+            cols: ws.ws_col,
+        });
         Ok(())
     }
 
     /// Peek whether the child is still running (without reaping it).
     pub fn is_alive(&self) -> bool {
+        if self.child_pid < 0 {
+            return false;
+        }
         unsafe {
             // SAFETY: child_pid is owned by this session; waitpid with WNOHANG only inspects state.
             let mut status = 0;
@@ -334,6 +383,38 @@ impl PtyOverlaySession {
             ret == 0 // 0 means still running
         }
     }
+
+    /// Non-blocking check for child exit; reaps the child on completion.
+    pub fn try_wait(&mut self) -> Option<std::process::ExitStatus> {
+        if self.child_pid < 0 {
+            return None;
+        }
+        unsafe {
+            // SAFETY: child_pid is owned by this session; waitpid with WNOHANG only inspects state.
+            let mut status = 0;
+            let ret = libc::waitpid(self.child_pid, &mut status, libc::WNOHANG);
+            if ret <= 0 {
+                None
+            } else {
+                self.child_pid = -1;
+                Some(std::process::ExitStatus::from_raw(status))
+            }
+        }
+    }
+
+    /// Poll `try_wait` for up to `timeout`; a child can close its terminal just before exiting.
+    pub fn wait_exit(&mut self, timeout: Duration) -> Option<std::process::ExitStatus> {
+        let start = Instant::now();
+        loop {
+            if let Some(status) = self.try_wait() {
+                return Some(status);
+            }
+            if self.child_pid < 0 || start.elapsed() >= timeout {
+                return None;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
 }
 
 #[cfg(any(test, feature = "mutants"))]
@@ -375,11 +456,11 @@ impl Drop for PtyOverlaySession {
             }
             if !wait_for_exit(self.child_pid, Duration::from_millis(500)) {
                 if let Err(err) = signal_process_group_or_pid(self.child_pid, libc::SIGTERM) {
-                    log_debug(&format!("SIGTERM to PTY session failed: {}", err));
+                    log_warn(&format!("SIGTERM to PTY session failed: {}", err));
                 }
                 if !wait_for_exit(self.child_pid, Duration::from_millis(500)) {
                     if let Err(err) = signal_process_group_or_pid(self.child_pid, libc::SIGKILL) {
-                        log_debug(&format!("SIGKILL to PTY session failed: {}", err));
+                        log_warn(&format!("SIGKILL to PTY session failed: {}", err));
                     }
                     #[cfg(any(test, feature = "mutants"))]
                     {
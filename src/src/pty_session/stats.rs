@@ -0,0 +1,103 @@
+//! PTY I/O statistics and resize notifications so callers can build health displays.
+
+use crossbeam_channel::{bounded, Receiver, Sender, TrySendError};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// Pending resize events kept per subscriber; older events are dropped when it falls behind.
+const RESIZE_CHANNEL_CAPACITY: usize = 16;
+
+/// A PTY window-size change applied with `set_winsize`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PtyResize {
+    /// Rows applied to the PTY.
+    pub rows: u16,
+    /// Columns applied to the PTY.
+    pub cols: u16,
+}
+
+/// Point-in-time snapshot of a PTY session's I/O counters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PtySessionStats {
+    /// Bytes read from the PTY master (child output).
+    pub bytes_read: u64,
+    /// Bytes written to the PTY master (child input).
+    pub bytes_written: u64,
+    /// Successful reads from the PTY master.
+    pub read_chunks: u64,
+    /// Window-size changes applied to the PTY.
+    pub resizes: u64,
+    /// Most recent window size applied, if any.
+    pub winsize: Option<PtyResize>,
+}
+
+/// Counters shared between a session and its reader thread.
+#[derive(Debug, Default)]
+pub(super) struct PtyIoStats {
+    bytes_read: AtomicU64,
+    bytes_written: AtomicU64,
+    read_chunks: AtomicU64,
+    resizes: AtomicU64,
+    winsize: Mutex<Option<PtyResize>>,
+    resize_listeners: Mutex<Vec<Sender<PtyResize>>>,
+}
+
+impl PtyIoStats {
+    pub(super) fn record_read(&self, bytes: usize) {
+        self.bytes_read.fetch_add(bytes as u64, Ordering::Relaxed);
+        self.read_chunks.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(super) fn record_write(&self, bytes: usize) {
+        self.bytes_written
+            .fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    /// Count a resize and notify subscribers, pruning ones whose receiver was dropped.
+    pub(super) fn record_resize(&self, resize: PtyResize) {
+        self.resizes.fetch_add(1, Ordering::Relaxed);
+        *self
+            .winsize
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(resize);
+        self.resize_listeners
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .retain(|listener| {
+                !matches!(
+                    listener.try_send(resize),
+                    Err(TrySendError::Disconnected(_))
+                )
+            });
+    }
+
+    pub(super) fn subscribe_resizes(&self) -> Receiver<PtyResize> {
+        let (tx, rx) = bounded(RESIZE_CHANNEL_CAPACITY);
+        self.resize_listeners
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .push(tx);
+        rx
+    }
+
+    pub(super) fn snapshot(&self) -> PtySessionStats {
+        PtySessionStats {
+            bytes_read: self.bytes_read.load(Ordering::Relaxed),
+            bytes_written: self.bytes_written.load(Ordering::Relaxed),
+            read_chunks: self.read_chunks.load(Ordering::Relaxed),
+            resizes: self.resizes.load(Ordering::Relaxed),
+            winsize: *self
+                .winsize
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()),
+        }
+    }
+
+    #[cfg(test)]
+    pub(super) fn resize_listener_count(&self) -> usize {
+        self.resize_listeners
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .len()
+    }
+}
@@ -4,6 +4,7 @@ use super::counters::*;
 use super::io::*;
 use super::osc::*;
 use super::pty::*;
+use super::stats::*;
 use crate::set_logging_for_tests;
 use crossbeam_channel::{bounded, RecvTimeoutError};
 use std::fs;
@@ -12,7 +13,7 @@ use std::mem;
 use std::mem::ManuallyDrop;
 use std::os::unix::io::RawFd;
 use std::ptr;
-use std::sync::{Mutex, OnceLock};
+use std::sync::{Arc, Mutex, OnceLock};
 use std::thread;
 use std::time::{Duration, Instant};
 
@@ -641,6 +642,7 @@ fn pty_overlay_session_send_text_with_newline_appends() {
         child_pid: -1,
         output_rx: rx,
         _output_thread: handle,
+        stats: Arc::default(),
     });
     session.send_text_with_newline("overlay").unwrap();
     unsafe { libc::close(write_fd) };
@@ -668,7 +670,7 @@ fn spawn_reader_thread_forwards_output() {
 fn spawn_passthrough_reader_thread_forwards_output() {
     let (read_fd, write_fd) = pipe_pair();
     let (tx, rx) = bounded(2);
-    let handle = spawn_passthrough_reader_thread(read_fd, tx);
+    let handle = spawn_passthrough_reader_thread(read_fd, tx, Arc::default());
     unsafe {
         libc::write(write_fd, b"hello".as_ptr() as *const libc::c_void, 5);
         libc::close(write_fd);
@@ -1478,6 +1480,7 @@ fn pty_overlay_session_send_bytes_writes() {
         child_pid: -1,
         output_rx: rx,
         _output_thread: handle,
+        stats: Arc::default(),
     });
     session.send_bytes(b"bytes").unwrap();
     unsafe { libc::close(write_fd) };
@@ -1496,6 +1499,7 @@ fn pty_overlay_session_set_winsize_updates_and_minimums() {
         child_pid: unsafe { libc::getpid() },
         output_rx: rx,
         _output_thread: handle,
+        stats: Arc::default(),
     });
     session.set_winsize(0, 0).unwrap();
     let mut ws: libc::winsize = unsafe { mem::zeroed() };
@@ -1519,10 +1523,65 @@ fn pty_overlay_session_set_winsize_errors_on_invalid_fd() {
         child_pid: unsafe { libc::getpid() },
         output_rx: rx,
         _output_thread: handle,
+        stats: Arc::default(),
     });
     assert!(session.set_winsize(10, 10).is_err());
 }
 
+#[test]
+fn pty_overlay_session_stats_count_writes_and_resizes() {
+    let (master, slave) = open_pty_pair();
+    let (_tx, rx) = bounded(1);
+    let handle = thread::spawn(|| {});
+    let mut session = ManuallyDrop::new(PtyOverlaySession {
+        master_fd: master,
+        child_pid: unsafe { libc::getpid() },
+        output_rx: rx,
+        _output_thread: handle,
+        stats: Arc::default(),
+    });
+    assert_eq!(session.child_pid(), unsafe { libc::getpid() });
+    let resizes = session.resize_events();
+    let dropped = session.resize_events();
+    drop(dropped);
+
+    session.send_bytes(b"abc").unwrap();
+    session.send_text_with_newline("hi").unwrap();
+    session.set_winsize(24, 80).unwrap();
+
+    let stats = session.stats();
+    assert_eq!(stats.bytes_written, 6);
+    assert_eq!(stats.resizes, 1);
+    assert_eq!(stats.winsize, Some(PtyResize { rows: 24, cols: 80 }));
+    assert_eq!(
+        resizes.try_recv().unwrap(),
+        PtyResize { rows: 24, cols: 80 }
+    );
+    assert_eq!(session.stats.resize_listener_count(), 1);
+    unsafe {
+        libc::close(master);
+        libc::close(slave);
+    }
+}
+
+#[test]
+fn spawn_passthrough_reader_thread_counts_bytes_read() {
+    let (read_fd, write_fd) = pipe_pair();
+    let (tx, rx) = bounded(2);
+    let stats: Arc<PtyIoStats> = Arc::default();
+    let handle = spawn_passthrough_reader_thread(read_fd, tx, Arc::clone(&stats));
+    unsafe {
+        libc::write(write_fd, b"hello".as_ptr() as *const libc::c_void, 5);
+        libc::close(write_fd);
+    }
+    assert_eq!(rx.recv_timeout(Duration::from_secs(1)).unwrap(), b"hello");
+    handle.join().unwrap();
+    let snapshot = stats.snapshot();
+    assert_eq!(snapshot.bytes_read, 5);
+    assert_eq!(snapshot.read_chunks, 1);
+    unsafe { libc::close(read_fd) };
+}
+
 #[test]
 fn pty_overlay_session_is_alive_reflects_child() {
     let mut child = std::process::Command::new("sleep")
@@ -1537,6 +1596,7 @@ fn pty_overlay_session_is_alive_reflects_child() {
         child_pid: pid,
         output_rx: rx,
         _output_thread: handle,
+        stats: Arc::default(),
     });
     assert!(session.is_alive());
     let _ = child.kill();
@@ -1544,6 +1604,19 @@ fn pty_overlay_session_is_alive_reflects_child() {
     assert!(!session.is_alive());
 }
 
+#[test]
+fn pty_overlay_session_wait_exit_reports_status() {
+    let args = vec!["-c".to_string(), "exit 3".to_string()];
+    let mut session =
+        PtyOverlaySession::new("/bin/sh", ".", &args, "xterm-256color").expect("pty session");
+    let status = session
+        .wait_exit(Duration::from_secs(2))
+        .expect("child exited");
+    assert_eq!(status.code(), Some(3));
+    assert_eq!(session.child_pid(), -1);
+    assert!(session.try_wait().is_none());
+}
+
 #[test]
 fn spawn_reader_thread_recovers_from_wouldblock() {
     let (read_fd, write_fd) = pipe_pair();
@@ -1593,7 +1666,7 @@ fn spawn_passthrough_reader_thread_recovers_from_wouldblock() {
     let (read_fd, write_fd) = pipe_pair();
     set_nonblocking_fd(read_fd);
     let (tx, rx) = bounded(2);
-    let handle = spawn_passthrough_reader_thread(read_fd, tx);
+    let handle = spawn_passthrough_reader_thread(read_fd, tx, Arc::default());
     thread::sleep(Duration::from_millis(20));
     unsafe {
         libc::write(write_fd, b"pong".as_ptr() as *const libc::c_void, 4);
@@ -1609,7 +1682,7 @@ fn spawn_passthrough_reader_thread_recovers_from_wouldblock() {
 fn spawn_passthrough_reader_thread_closes_channel_on_eof() {
     let (read_fd, write_fd) = pipe_pair();
     let (tx, rx) = bounded(1);
-    let handle = spawn_passthrough_reader_thread(read_fd, tx);
+    let handle = spawn_passthrough_reader_thread(read_fd, tx, Arc::default());
     unsafe {
         libc::close(write_fd);
     }
@@ -1623,7 +1696,7 @@ fn spawn_passthrough_reader_thread_closes_channel_on_eof() {
 fn spawn_passthrough_reader_thread_does_not_log_on_eof() {
     let (read_fd, write_fd) = pipe_pair();
     let (tx, _rx) = bounded(1);
-    let handle = spawn_passthrough_reader_thread(read_fd, tx);
+    let handle = spawn_passthrough_reader_thread(read_fd, tx, Arc::default());
     let log = capture_new_log(|| unsafe {
         libc::close(write_fd);
         handle.join().unwrap();
@@ -1811,6 +1884,7 @@ fn pty_overlay_session_drop_terminates_child() {
             child_pid: pid,
             output_rx: rx,
             _output_thread: handle,
+            stats: Arc::default(),
         };
         drop(session);
     });
@@ -1853,6 +1927,7 @@ fn pty_overlay_session_drop_terminates_descendants_in_process_group() {
         child_pid: process_tree.leader_pid,
         output_rx: rx,
         _output_thread: handle,
+        stats: Arc::default(),
     };
     drop(session);
     unsafe { libc::close(read_fd) };
@@ -1882,6 +1957,7 @@ fn pty_overlay_session_drop_sigkill_for_ignored_sigterm() {
             child_pid: pid,
             output_rx: rx,
             _output_thread: handle,
+            stats: Arc::default(),
         };
         drop(session);
     });
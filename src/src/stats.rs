@@ -0,0 +1,26 @@
+//! Small summary statistics shared by the overlay's latency panel and `voice_benchmark`.
+
+/// Nearest-rank percentile of an ascending, non-empty slice.
+///
+/// `pct` is in `0..=100`; the result is always one of the samples, so p50 of
+/// an even-length slice is the lower middle value rather than an average.
+#[must_use]
+pub fn percentile(sorted: &[u64], pct: usize) -> u64 {
+    let rank = (sorted.len() * pct).div_ceil(100).max(1);
+    sorted[rank.min(sorted.len()) - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentile_uses_the_nearest_rank() {
+        let sorted: Vec<u64> = (1..=20).collect();
+        assert_eq!(percentile(&sorted, 0), 1);
+        assert_eq!(percentile(&sorted, 50), 10);
+        assert_eq!(percentile(&sorted, 95), 19);
+        assert_eq!(percentile(&sorted, 100), 20);
+        assert_eq!(percentile(&[7], 95), 7);
+    }
+}
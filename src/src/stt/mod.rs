@@ -0,0 +1,442 @@
+//! Whisper speech-to-text integration so capture results become local transcripts.
+//!
+//! Wraps `whisper_rs` to provide a simple transcription API. The model is loaded
+//! once and reused across captures to avoid repeated initialization overhead.
+//! Cloud backends selected with `--stt-backend` live in [`remote`].
+
+pub mod remote;
+
+pub use remote::RemoteTranscriber;
+
+/// Transcript text plus Whisper's confidence in it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transcription {
+    /// Concatenated segment text.
+    pub text: String,
+    /// Mean probability of the text tokens (0.0-1.0); `None` when no text tokens were decoded.
+    pub confidence: Option<f32>,
+    /// Per-word confidence in transcript order.
+    pub words: Vec<WordConfidence>,
+    /// Text tokens decoded; 0 when the backend does not report tokens.
+    pub tokens: usize,
+}
+
+/// One transcript word and Whisper's confidence in it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WordConfidence {
+    /// Word text without surrounding whitespace.
+    pub word: String,
+    /// Lowest probability among the word's tokens (0.0-1.0).
+    pub confidence: f32,
+}
+
+/// Token read back from a Whisper decode.
+#[cfg_attr(not(all(unix, feature = "stt")), allow(dead_code))]
+struct DecodedToken {
+    id: i32,
+    text: String,
+    prob: f32,
+}
+
+/// Mean probability over text tokens, skipping special/timestamp tokens (`id >= eot`).
+#[cfg_attr(not(all(unix, feature = "stt")), allow(dead_code))]
+fn mean_token_confidence(tokens: &[DecodedToken], eot: i32) -> Option<f32> {
+    let (sum, count) = tokens
+        .iter()
+        .filter(|token| token.id < eot)
+        .fold((0.0f32, 0usize), |(sum, count), token| {
+            (sum + token.prob.clamp(0.0, 1.0), count + 1)
+        });
+    (count > 0).then(|| sum / count as f32)
+}
+
+/// Join BPE text tokens into words; a token starting with whitespace begins a new word.
+#[cfg_attr(not(all(unix, feature = "stt")), allow(dead_code))]
+fn word_confidences(tokens: &[DecodedToken], eot: i32) -> Vec<WordConfidence> {
+    let mut words: Vec<WordConfidence> = Vec::new();
+    let mut continues_word = false;
+    for token in tokens.iter().filter(|token| token.id < eot) {
+        let prob = token.prob.clamp(0.0, 1.0);
+        for (idx, piece) in token.text.split_whitespace().enumerate() {
+            let starts_word =
+                idx > 0 || !continues_word || token.text.starts_with(char::is_whitespace);
+            match words.last_mut() {
+                Some(last) if !starts_word => {
+                    last.word.push_str(piece);
+                    last.confidence = last.confidence.min(prob);
+                }
+                _ => words.push(WordConfidence {
+                    word: piece.to_string(),
+                    confidence: prob,
+                }),
+            }
+            continues_word = true;
+        }
+        if token.text.ends_with(char::is_whitespace) {
+            continues_word = false;
+        }
+    }
+    words.retain(|word| word.word != "[BLANK_AUDIO]");
+    words
+}
+
+#[cfg(all(unix, feature = "stt"))]
+mod platform {
+    use super::{mean_token_confidence, word_confidences, DecodedToken, Transcription};
+    use crate::config::{AppConfig, SttDevice};
+    use crate::log_debug;
+    use crate::voice_error::VoiceErrorKind;
+    use anyhow::{anyhow, Context, Result};
+    use std::io;
+    use std::os::raw::{c_char, c_uint, c_void};
+    use std::os::unix::io::AsRawFd;
+    use std::sync::Once;
+    use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+
+    /// Whisper model context for speech-to-text transcription.
+    ///
+    /// Holds the loaded GGML model in memory. Create once at startup and reuse
+    /// for all transcription requests to avoid repeated model loading.
+    pub struct Transcriber {
+        ctx: WhisperContext,
+        device: String,
+    }
+
+    impl Transcriber {
+        /// Loads the Whisper model on the default device (see [`SttDevice::Auto`]).
+        pub fn new(model_path: &str) -> Result<Self> {
+            Self::with_device(model_path, SttDevice::Auto)
+        }
+
+        /// Loads the Whisper model from disk onto `device`.
+        ///
+        /// Temporarily redirects stderr to `/dev/null` during loading because
+        /// whisper.cpp emits verbose initialization messages.
+        ///
+        /// Note: this is a process-wide redirect; we keep it brief and expect
+        /// model loading to happen during startup before other threads log.
+        ///
+        /// # Errors
+        ///
+        /// Returns an error if the model file cannot be loaded or stderr
+        /// redirection fails.
+        pub fn with_device(model_path: &str, device: SttDevice) -> Result<Self> {
+            install_whisper_log_silencer();
+
+            let null = std::fs::OpenOptions::new()
+                .write(true)
+                .open("/dev/null")
+                .context("failed to open /dev/null")?;
+            let null_fd = null.as_raw_fd();
+
+            // SAFETY: dup(2) duplicates the stderr file descriptor. We restore it
+            // after model loading completes. This is safe because we hold the only
+            // reference and restore before returning.
+            let orig_stderr = unsafe { libc::dup(2) };
+            if orig_stderr < 0 {
+                return Err(anyhow!(
+                    "failed to dup stderr: {}",
+                    io::Error::last_os_error()
+                ));
+            }
+
+            // Redirect stderr to /dev/null temporarily
+            // SAFETY: dup2 replaces stderr with /dev/null; both fds are valid.
+            let dup_result = unsafe { libc::dup2(null_fd, 2) };
+            if dup_result < 0 {
+                // SAFETY: orig_stderr is a valid fd from dup(2).
+                unsafe {
+                    libc::close(orig_stderr);
+                }
+                return Err(anyhow!(
+                    "failed to redirect stderr: {}",
+                    io::Error::last_os_error()
+                ));
+            }
+
+            // Load model (output will be suppressed)
+            let mut params = WhisperContextParameters::default();
+            params.use_gpu(device.uses_gpu());
+            let ctx_result = WhisperContext::new_with_params(model_path, params);
+
+            // Restore original stderr
+            // SAFETY: restore stderr using the saved fd from dup(2).
+            let restore_result = unsafe { libc::dup2(orig_stderr, 2) };
+            // SAFETY: orig_stderr is a valid fd returned by dup(2).
+            unsafe {
+                libc::close(orig_stderr);
+            }
+            if restore_result < 0 {
+                return Err(anyhow!(
+                    "failed to restore stderr: {}",
+                    io::Error::last_os_error()
+                ));
+            }
+
+            let ctx = ctx_result
+                .context("failed to load whisper model")
+                .context(VoiceErrorKind::Model)?;
+            let device = device.label();
+            log_debug(&format!("whisper model loaded on {device}: {model_path}"));
+            Ok(Self { ctx, device })
+        }
+
+        /// Device the model was loaded on, e.g. `gpu/metal` or `cpu`.
+        pub fn device_label(&self) -> &str {
+            &self.device
+        }
+
+        /// Run transcription for the captured PCM samples and return the concatenated text.
+        pub fn transcribe(&self, samples: &[f32], config: &AppConfig) -> Result<String> {
+            self.transcribe_detailed(samples, config)
+                .map(|transcription| transcription.text)
+        }
+
+        /// Like [`Transcriber::transcribe`], but also reports the mean token probability.
+        pub fn transcribe_detailed(
+            &self,
+            samples: &[f32],
+            config: &AppConfig,
+        ) -> Result<Transcription> {
+            let mut state = self
+                .ctx
+                .create_state()
+                .context("failed to create whisper state")
+                .context(VoiceErrorKind::Transcription)?;
+            let mut params = if config.whisper_beam_size > 1 {
+                FullParams::new(SamplingStrategy::BeamSearch {
+                    beam_size: config.whisper_beam_size as i32,
+                    patience: -1.0,
+                })
+            } else {
+                FullParams::new(SamplingStrategy::Greedy { best_of: 1 })
+            };
+            if config.lang.eq_ignore_ascii_case("auto") {
+                params.set_language(None);
+                params.set_detect_language(true);
+            } else {
+                params.set_language(Some(&config.lang));
+                params.set_detect_language(false);
+            }
+            params.set_temperature(config.whisper_temperature);
+            if let Some(prompt) = config
+                .whisper_prompt
+                .as_deref()
+                .filter(|prompt| !prompt.trim().is_empty())
+            {
+                params.set_initial_prompt(prompt);
+            }
+            // Limit CPU usage so laptops don't max out all cores.
+            params.set_n_threads(num_cpus::get().min(8) as i32);
+            params.set_print_progress(false);
+            params.set_print_timestamps(false);
+            params.set_print_special(false);
+            params.set_print_realtime(false);
+            params.set_translate(false);
+            params.set_token_timestamps(false);
+            state
+                .full(params, samples)
+                .context("whisper transcription failed")
+                .context(VoiceErrorKind::Transcription)?;
+            let mut transcript = String::new();
+            let num_segments = match state.full_n_segments() {
+                Ok(count) => count,
+                Err(err) => {
+                    log_debug(&format!("Whisper failed to read segment count: {err}"));
+                    return Ok(Transcription {
+                        text: transcript,
+                        confidence: None,
+                        words: Vec::new(),
+                        tokens: 0,
+                    });
+                }
+            };
+            if num_segments < 0 {
+                log_debug("Whisper returned a negative segment count");
+                return Ok(Transcription {
+                    text: transcript,
+                    confidence: None,
+                    words: Vec::new(),
+                    tokens: 0,
+                });
+            }
+            // Whisper splits output into small segments; stitch them together.
+            let mut tokens = Vec::new();
+            for i in 0..num_segments {
+                match state.full_get_segment_text_lossy(i) {
+                    Ok(text) => transcript.push_str(&text),
+                    Err(err) => log_debug(&format!("Failed to read whisper segment {i}: {err}")),
+                }
+                let num_tokens = state.full_n_tokens(i).unwrap_or(0);
+                for j in 0..num_tokens {
+                    if let (Ok(id), Ok(text), Ok(prob)) = (
+                        state.full_get_token_id(i, j),
+                        state.full_get_token_text_lossy(i, j),
+                        state.full_get_token_prob(i, j),
+                    ) {
+                        tokens.push(DecodedToken { id, text, prob });
+                    }
+                }
+            }
+            // Filter out Whisper's [BLANK_AUDIO] token
+            let filtered = transcript.replace("[BLANK_AUDIO]", "");
+            let eot = self.ctx.token_eot();
+            Ok(Transcription {
+                text: filtered,
+                confidence: mean_token_confidence(&tokens, eot),
+                words: word_confidences(&tokens, eot),
+                tokens: tokens.iter().filter(|token| token.id < eot).count(),
+            })
+        }
+    }
+
+    fn install_whisper_log_silencer() {
+        static INSTALL_LOG_CALLBACK: Once = Once::new();
+        INSTALL_LOG_CALLBACK.call_once(|| unsafe {
+            // SAFETY: whisper_rs expects a valid callback pointer; we pass a function
+            // that ignores its inputs and never dereferences raw pointers.
+            whisper_rs::set_log_callback(Some(whisper_log_callback), std::ptr::null_mut());
+        });
+    }
+
+    #[allow(unused_variables)]
+    unsafe extern "C" fn whisper_log_callback(
+        _level: c_uint,
+        _text: *const c_char,
+        _user_data: *mut c_void,
+    ) {
+        // Silence the default whisper.cpp logger so it does not corrupt the TUI.
+        // SAFETY: We do not dereference any incoming pointers.
+    }
+}
+
+#[cfg(not(all(unix, feature = "stt")))]
+mod platform {
+    use super::Transcription;
+    use crate::config::{AppConfig, SttDevice};
+    use crate::voice_error::VoiceErrorKind;
+    use anyhow::{anyhow, Context, Result};
+
+    const UNAVAILABLE: &str = if cfg!(unix) {
+        "Whisper transcription is not built in (enable the 'stt' feature)"
+    } else {
+        "Whisper transcription is currently supported only on Unix-like platforms"
+    };
+
+    /// Stub for builds without the `stt` feature and unsupported targets such as Windows.
+    pub struct Transcriber;
+
+    impl Transcriber {
+        pub fn new(model_path: &str) -> Result<Self> {
+            Self::with_device(model_path, SttDevice::Auto)
+        }
+
+        pub fn with_device(_: &str, _: SttDevice) -> Result<Self> {
+            Err(anyhow!(UNAVAILABLE)).context(VoiceErrorKind::Model)
+        }
+
+        pub fn device_label(&self) -> &str {
+            "cpu"
+        }
+
+        pub fn transcribe(&self, _: &[f32], _: &AppConfig) -> Result<String> {
+            Err(anyhow!(UNAVAILABLE))
+        }
+
+        pub fn transcribe_detailed(&self, _: &[f32], _: &AppConfig) -> Result<Transcription> {
+            Err(anyhow!(UNAVAILABLE))
+        }
+    }
+}
+
+pub use platform::Transcriber;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EOT: i32 = 50_257;
+
+    fn token(id: i32, text: &str, prob: f32) -> DecodedToken {
+        DecodedToken {
+            id,
+            text: text.to_string(),
+            prob,
+        }
+    }
+
+    #[test]
+    fn mean_token_confidence_skips_special_tokens() {
+        assert_eq!(mean_token_confidence(&[], EOT), None);
+        assert_eq!(
+            mean_token_confidence(&[token(EOT, "", 0.9), token(EOT + 5, "", 0.1)], EOT),
+            None
+        );
+        let confidence = mean_token_confidence(
+            &[
+                token(100, " run", 0.8),
+                token(EOT + 1, "[_TT_50]", 0.01),
+                token(200, " tests", 0.4),
+            ],
+            EOT,
+        )
+        .unwrap();
+        assert!((confidence - 0.6).abs() < 1e-6);
+    }
+
+    #[test]
+    fn word_confidences_join_subword_tokens_with_lowest_probability() {
+        let words = word_confidences(
+            &[
+                token(EOT + 1, "[_BEG_]", 0.99),
+                token(1, " run", 0.9),
+                token(2, " cargo", 0.95),
+                token(3, " cl", 0.8),
+                token(4, "ippy", 0.3),
+                token(5, ".", 0.7),
+                token(6, " now", 0.6),
+            ],
+            EOT,
+        );
+        let summary: Vec<(&str, f32)> = words
+            .iter()
+            .map(|word| (word.word.as_str(), word.confidence))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("run", 0.9),
+                ("cargo", 0.95),
+                ("clippy.", 0.3),
+                ("now", 0.6)
+            ]
+        );
+    }
+
+    #[test]
+    fn word_confidences_split_on_inner_whitespace_and_drop_blank_audio() {
+        let words = word_confidences(
+            &[
+                token(1, " [", 0.9),
+                token(2, "BLANK_AUDIO", 0.9),
+                token(3, "]", 0.9),
+                token(4, " hello world", 0.5),
+            ],
+            EOT,
+        );
+        let text: Vec<&str> = words.iter().map(|word| word.word.as_str()).collect();
+        assert_eq!(text, vec!["hello", "world"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn transcriber_rejects_missing_model() {
+        let result = Transcriber::new("/no/such/model.bin");
+        assert!(result.is_err());
+        let err = result.err().expect("missing model should fail");
+        assert_eq!(
+            crate::voice_error::VoiceErrorKind::classify(&err),
+            crate::voice_error::VoiceErrorKind::Model
+        );
+    }
+}
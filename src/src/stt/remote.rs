@@ -0,0 +1,447 @@
+//! Cloud speech-to-text for `--stt-backend openai|deepgram`.
+//!
+//! The capture is encoded as 16-bit WAV and uploaded with `curl` from the voice
+//! worker thread, so the UI keeps running while the request is in flight. curl
+//! reads its settings (URL, auth header, upload path) as a config on stdin,
+//! which keeps the API key out of the process list. Any failure is returned to
+//! the caller, which falls back to local Whisper.
+
+use super::{Transcription, WordConfidence};
+use crate::audio::wav_bytes;
+use crate::config::{AppConfig, SttBackend};
+use crate::voice_error::VoiceErrorKind;
+use anyhow::{anyhow, bail, Context, Result};
+use serde_json::Value;
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+const CURL_CMD: &str = "curl";
+const OPENAI_ENDPOINT: &str = "https://api.openai.com/v1/audio/transcriptions";
+const OPENAI_MODEL: &str = "whisper-1";
+const DEEPGRAM_ENDPOINT: &str = "https://api.deepgram.com/v1/listen";
+const DEEPGRAM_MODEL: &str = "nova-2";
+/// Longest slice of an error body quoted in the returned error.
+const MAX_ERROR_BODY_CHARS: usize = 200;
+/// Temp names tried before giving up when others are already taken.
+const UPLOAD_FILE_ATTEMPTS: u32 = 16;
+
+/// One cloud STT request target, resolved from the config at capture time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemoteTranscriber {
+    backend: SttBackend,
+    endpoint: String,
+    model: String,
+    api_key: Option<String>,
+    timeout: Duration,
+}
+
+impl RemoteTranscriber {
+    /// Build the request target, or `None` for `--stt-backend local`.
+    pub fn from_config(config: &AppConfig) -> Option<Self> {
+        let key_env = config.stt_backend.api_key_env()?;
+        let api_key = env::var(key_env)
+            .ok()
+            .map(|key| key.trim().to_string())
+            .filter(|key| !key.is_empty());
+        let (endpoint, model) = match config.stt_backend {
+            SttBackend::Openai => (OPENAI_ENDPOINT, OPENAI_MODEL),
+            SttBackend::Deepgram => (DEEPGRAM_ENDPOINT, DEEPGRAM_MODEL),
+            SttBackend::Local => return None,
+        };
+        Some(Self {
+            backend: config.stt_backend,
+            endpoint: config
+                .stt_endpoint
+                .clone()
+                .unwrap_or_else(|| endpoint.to_string()),
+            model: config
+                .stt_model
+                .clone()
+                .unwrap_or_else(|| model.to_string()),
+            api_key,
+            timeout: Duration::from_millis(config.stt_remote_timeout_ms),
+        })
+    }
+
+    /// Backend label for logs.
+    pub fn label(&self) -> &'static str {
+        self.backend.label()
+    }
+
+    /// Upload the capture and return the service's transcript.
+    pub fn transcribe(
+        &self,
+        samples: &[f32],
+        sample_rate: u32,
+        lang: &str,
+    ) -> Result<Transcription> {
+        let upload = UploadFile::write(&wav_bytes(samples, sample_rate))
+            .context(VoiceErrorKind::Transcription)?;
+        let body = self
+            .curl_config(upload.path(), lang)
+            .and_then(|config| self.run_curl(&config))
+            .context(VoiceErrorKind::Transcription)?;
+        match self.backend {
+            SttBackend::Deepgram => parse_deepgram_response(&body),
+            _ => parse_openai_response(&body),
+        }
+        .context(VoiceErrorKind::Transcription)
+    }
+
+    fn curl_config(&self, upload: &Path, lang: &str) -> Result<String> {
+        let language = (!lang.eq_ignore_ascii_case("auto")).then_some(lang);
+        let upload = upload.display();
+        let mut lines = Vec::new();
+        match self.backend {
+            SttBackend::Deepgram => {
+                let mut url = format!(
+                    "{}?model={}&smart_format=true",
+                    self.endpoint,
+                    query_value(&self.model)
+                );
+                match language {
+                    Some(lang) => url.push_str(&format!("&language={}", query_value(lang))),
+                    None => url.push_str("&detect_language=true"),
+                }
+                lines.push(config_line("url", &url)?);
+                if let Some(key) = &self.api_key {
+                    lines.push(config_line(
+                        "header",
+                        &format!("Authorization: Token {key}"),
+                    )?);
+                }
+                lines.push(config_line("header", "Content-Type: audio/wav")?);
+                lines.push(config_line("data-binary", &format!("@{upload}"))?);
+            }
+            _ => {
+                lines.push(config_line("url", &self.endpoint)?);
+                if let Some(key) = &self.api_key {
+                    lines.push(config_line(
+                        "header",
+                        &format!("Authorization: Bearer {key}"),
+                    )?);
+                }
+                lines.push(config_line(
+                    "form",
+                    &format!("file=@{upload};type=audio/wav"),
+                )?);
+                lines.push(config_line("form", &format!("model={}", self.model))?);
+                lines.push(config_line("form", "response_format=json")?);
+                if let Some(lang) = language {
+                    lines.push(config_line("form", &format!("language={lang}"))?);
+                }
+            }
+        }
+        Ok(lines.join("\n") + "\n")
+    }
+
+    fn run_curl(&self, config: &str) -> Result<String> {
+        let mut child = Command::new(CURL_CMD)
+            .args(["--silent", "--show-error", "--fail-with-body", "--max-time"])
+            .arg(format!("{:.3}", self.timeout.as_secs_f64()))
+            .args(["--config", "-"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .with_context(|| format!("failed to start {CURL_CMD} for {} STT", self.label()))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(config.as_bytes())
+                .context("failed to pass request settings to curl")?;
+        }
+        let output = child
+            .wait_with_output()
+            .context("failed to wait for curl")?;
+        let body = String::from_utf8_lossy(&output.stdout).into_owned();
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            bail!(
+                "{} STT request failed ({}): {} {}",
+                self.label(),
+                output.status,
+                stderr.trim(),
+                truncate(body.trim(), MAX_ERROR_BODY_CHARS)
+            );
+        }
+        Ok(body)
+    }
+}
+
+/// Owner-only temp WAV removed on drop.
+struct UploadFile {
+    path: PathBuf,
+}
+
+impl UploadFile {
+    /// Create the file with `create_new`, so an existing file or symlink at a
+    /// guessed path is never opened; a taken name moves on to the next id.
+    fn write(bytes: &[u8]) -> Result<Self> {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+        let mut attempt = 0;
+        let (path, mut file) = loop {
+            let path = env::temp_dir().join(format!(
+                "voiceterm-stt-{}-{}.wav",
+                std::process::id(),
+                NEXT_ID.fetch_add(1, Ordering::Relaxed)
+            ));
+            let mut options = OpenOptions::new();
+            options.write(true).create_new(true);
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::OpenOptionsExt;
+                options.mode(0o600);
+            }
+            match options.open(&path) {
+                Ok(file) => break (path, file),
+                Err(err)
+                    if err.kind() == ErrorKind::AlreadyExists
+                        && attempt + 1 < UPLOAD_FILE_ATTEMPTS =>
+                {
+                    attempt += 1;
+                }
+                Err(err) => {
+                    return Err(err).with_context(|| format!("failed to create {}", path.display()))
+                }
+            }
+        };
+        // Own the path before writing so a failed write still removes it.
+        let upload = Self { path };
+        file.write_all(bytes)
+            .with_context(|| format!("failed to write {}", upload.path.display()))?;
+        Ok(upload)
+    }
+
+    fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for UploadFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// `key = "value"` with curl config escaping.
+///
+/// Control characters are refused rather than escaped: curl turns `\n` back
+/// into a newline, which would split a header or add an option of its own.
+fn config_line(key: &str, value: &str) -> Result<String> {
+    if value.chars().any(char::is_control) {
+        bail!("{key} value for the STT service contains a control character");
+    }
+    let escaped = value.replace('\\', "\\\\").replace('"', "\\\"");
+    Ok(format!("{key} = \"{escaped}\""))
+}
+
+/// Percent-encode a URL query value, keeping only RFC 3986 unreserved characters.
+fn query_value(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+fn truncate(text: &str, max_chars: usize) -> &str {
+    match text.char_indices().nth(max_chars) {
+        Some((idx, _)) => &text[..idx],
+        None => text,
+    }
+}
+
+/// OpenAI's `response_format=json` body: `{"text": "..."}`.
+fn parse_openai_response(body: &str) -> Result<Transcription> {
+    let value: Value = serde_json::from_str(body).context("invalid STT response JSON")?;
+    let text = value
+        .get("text")
+        .and_then(Value::as_str)
+        .ok_or_else(|| anyhow!("STT response has no text field"))?;
+    Ok(Transcription {
+        text: text.to_string(),
+        confidence: None,
+        words: Vec::new(),
+        tokens: 0,
+    })
+}
+
+/// Deepgram's `/v1/listen` body: the first alternative of the first channel.
+fn parse_deepgram_response(body: &str) -> Result<Transcription> {
+    let value: Value = serde_json::from_str(body).context("invalid STT response JSON")?;
+    let alternative = value
+        .pointer("/results/channels/0/alternatives/0")
+        .ok_or_else(|| anyhow!("STT response has no transcript alternative"))?;
+    let text = alternative
+        .get("transcript")
+        .and_then(Value::as_str)
+        .ok_or_else(|| anyhow!("STT response has no transcript field"))?;
+    let words = alternative
+        .get("words")
+        .and_then(Value::as_array)
+        .map(|words| {
+            words
+                .iter()
+                .filter_map(|word| {
+                    let text = word
+                        .get("punctuated_word")
+                        .or_else(|| word.get("word"))
+                        .and_then(Value::as_str)?;
+                    let confidence = word.get("confidence").and_then(Value::as_f64)?;
+                    Some(WordConfidence {
+                        word: text.to_string(),
+                        confidence: (confidence as f32).clamp(0.0, 1.0),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    Ok(Transcription {
+        text: text.to_string(),
+        confidence: alternative
+            .get("confidence")
+            .and_then(Value::as_f64)
+            .map(|confidence| (confidence as f32).clamp(0.0, 1.0)),
+        words,
+        tokens: 0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    fn remote(backend: SttBackend) -> RemoteTranscriber {
+        RemoteTranscriber {
+            backend,
+            endpoint: "https://stt.example/v1".to_string(),
+            model: "m1".to_string(),
+            api_key: Some("sk-\"secret\"".to_string()),
+            timeout: Duration::from_secs(5),
+        }
+    }
+
+    #[test]
+    fn local_backend_has_no_remote_target() {
+        let config = AppConfig::parse_from(["test-app"]);
+        assert_eq!(RemoteTranscriber::from_config(&config), None);
+        let config = AppConfig::parse_from([
+            "test-app",
+            "--stt-backend",
+            "openai",
+            "--stt-endpoint",
+            "http://127.0.0.1:8000/v1/audio/transcriptions",
+        ]);
+        let remote = RemoteTranscriber::from_config(&config).expect("remote target");
+        assert_eq!(
+            remote.endpoint,
+            "http://127.0.0.1:8000/v1/audio/transcriptions"
+        );
+        assert_eq!(remote.model, OPENAI_MODEL);
+    }
+
+    #[test]
+    fn openai_config_uploads_multipart_form_with_escaped_key() {
+        let config = remote(SttBackend::Openai)
+            .curl_config(Path::new("/tmp/a.wav"), "en")
+            .unwrap();
+        assert_eq!(
+            config,
+            "url = \"https://stt.example/v1\"\n\
+             header = \"Authorization: Bearer sk-\\\"secret\\\"\"\n\
+             form = \"file=@/tmp/a.wav;type=audio/wav\"\n\
+             form = \"model=m1\"\n\
+             form = \"response_format=json\"\n\
+             form = \"language=en\"\n"
+        );
+        let auto = remote(SttBackend::Openai)
+            .curl_config(Path::new("/tmp/a.wav"), "auto")
+            .unwrap();
+        assert!(!auto.contains("language="), "{auto}");
+    }
+
+    #[test]
+    fn deepgram_config_posts_raw_wav_with_query_options() {
+        let config = remote(SttBackend::Deepgram)
+            .curl_config(Path::new("/tmp/a.wav"), "auto")
+            .unwrap();
+        assert!(config.starts_with(
+            "url = \"https://stt.example/v1?model=m1&smart_format=true&detect_language=true\"\n"
+        ));
+        assert!(config.contains("header = \"Authorization: Token "));
+        assert!(config.contains("data-binary = \"@/tmp/a.wav\""));
+
+        let config = remote(SttBackend::Deepgram)
+            .curl_config(Path::new("/tmp/a.wav"), "en&callback=https://evil.example")
+            .unwrap();
+        assert!(config.starts_with(
+            "url = \"https://stt.example/v1?model=m1&smart_format=true\
+             &language=en%26callback%3Dhttps%3A%2F%2Fevil.example\"\n"
+        ));
+    }
+
+    #[test]
+    fn control_characters_never_reach_the_curl_config() {
+        let mut injected = remote(SttBackend::Openai);
+        injected.api_key = Some("sk-1\nurl = \"https://evil.example\"".to_string());
+        let err = injected
+            .curl_config(Path::new("/tmp/a.wav"), "en")
+            .expect_err("newline in the key is refused");
+        assert!(err.to_string().contains("control character"), "{err}");
+        assert!(!err.to_string().contains("evil"), "{err}");
+        assert!(remote(SttBackend::Openai)
+            .curl_config(Path::new("/tmp/a.wav"), "en\r\nurl = x")
+            .is_err());
+        let deepgram = remote(SttBackend::Deepgram)
+            .curl_config(Path::new("/tmp/a.wav"), "en\nurl = x")
+            .unwrap();
+        assert!(
+            deepgram.contains("&language=en%0Aurl%20%3D%20x\""),
+            "{deepgram}"
+        );
+    }
+
+    #[test]
+    fn parses_openai_and_deepgram_responses() {
+        let openai = parse_openai_response(r#"{"text":" run the tests"}"#).unwrap();
+        assert_eq!(openai.text, " run the tests");
+        assert_eq!(openai.confidence, None);
+        assert!(parse_openai_response(r#"{"error":{"message":"bad key"}}"#).is_err());
+
+        let deepgram = parse_deepgram_response(
+            r#"{"results":{"channels":[{"alternatives":[{"transcript":"run tests.",
+                "confidence":0.9,"words":[{"word":"run","punctuated_word":"run","confidence":0.95},
+                {"word":"tests","punctuated_word":"tests.","confidence":0.5}]}]}]}}"#,
+        )
+        .unwrap();
+        assert_eq!(deepgram.text, "run tests.");
+        assert_eq!(deepgram.confidence, Some(0.9));
+        let words: Vec<&str> = deepgram.words.iter().map(|w| w.word.as_str()).collect();
+        assert_eq!(words, vec!["run", "tests."]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn upload_files_are_fresh_owner_only_and_removed_on_drop() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let first = UploadFile::write(b"RIFF").unwrap();
+        let second = UploadFile::write(b"RIFF").unwrap();
+        assert_ne!(first.path(), second.path());
+        let mode = fs::metadata(first.path()).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        let path = first.path().to_path_buf();
+        drop(first);
+        assert!(!path.exists());
+    }
+}
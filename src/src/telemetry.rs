@@ -3,26 +3,29 @@
 use crate::config::AppConfig;
 use std::env;
 use std::fs::OpenOptions;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 use tracing_subscriber::fmt::time::UtcTime;
 
 static TRACING_INIT: OnceLock<()> = OnceLock::new();
 
-pub(crate) fn tracing_log_path() -> PathBuf {
+pub(crate) fn tracing_log_path(log_dir: &Path) -> PathBuf {
     env::var("VOICETERM_TRACE_LOG")
         .map(PathBuf::from)
-        .unwrap_or_else(|_| env::temp_dir().join("voiceterm_trace.jsonl"))
+        .unwrap_or_else(|_| log_dir.join("voiceterm_trace.jsonl"))
 }
 
-pub(crate) fn init_tracing(config: &AppConfig) {
+pub(crate) fn init_tracing(config: &AppConfig, log_dir: &Path) {
     let enabled = (config.logs || config.log_timings) && !config.no_logs;
     if !enabled {
         return;
     }
 
     let _ = TRACING_INIT.get_or_init(|| {
-        let path = tracing_log_path();
+        let path = tracing_log_path(log_dir);
+        if let Some(dir) = path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
         let file = match OpenOptions::new().create(true).append(true).open(&path) {
             Ok(file) => file,
             Err(_) => return,
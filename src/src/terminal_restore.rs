@@ -18,6 +18,7 @@ use std::{
 static RAW_MODE_ENABLED: AtomicBool = AtomicBool::new(false);
 static ALT_SCREEN_ENABLED: AtomicBool = AtomicBool::new(false);
 static MOUSE_CAPTURE_ENABLED: AtomicBool = AtomicBool::new(false);
+static SCROLL_REGION_SET: AtomicBool = AtomicBool::new(false);
 static PANIC_HOOK_INSTALLED: OnceLock<()> = OnceLock::new();
 
 /// RAII guard to restore terminal state on drop (and on panic via a shared hook).
@@ -70,7 +71,12 @@ impl Drop for TerminalRestoreGuard {
     }
 }
 
-/// Restore terminal raw mode, mouse capture, alt-screen, and cursor visibility.
+/// Record whether scroll margins narrower than the screen are set, so exit paths can reset them.
+pub fn note_scroll_region(set: bool) {
+    SCROLL_REGION_SET.store(set, Ordering::SeqCst);
+}
+
+/// Restore terminal raw mode, mouse capture, alt-screen, scroll margins, and cursor visibility.
 pub fn restore_terminal() {
     if RAW_MODE_ENABLED.swap(false, Ordering::SeqCst) {
         let _ = disable_raw_mode();
@@ -82,6 +88,10 @@ pub fn restore_terminal() {
     if ALT_SCREEN_ENABLED.swap(false, Ordering::SeqCst) {
         let _ = execute!(stdout, LeaveAlternateScreen);
     }
+    if SCROLL_REGION_SET.swap(false, Ordering::SeqCst) {
+        // Reset the margins without moving the cursor (DECSTBM homes it).
+        let _ = stdout.write_all(b"\x1b7\x1b[r\x1b8");
+    }
     let _ = execute!(stdout, Show);
     let _ = stdout.flush();
 }
@@ -3,16 +3,27 @@
 //! native recorder/transcriber path hits driver issues.
 
 use crate::audio;
-use crate::config::VadEngineKind;
-use crate::log_debug;
+use crate::config::{LogLevel, VadEngineKind};
 use crate::stt;
+use crate::voice_error::{VoiceError, VoiceErrorKind};
+use crate::voice_stream;
+use crate::{log_debug, log_event, seconds_field};
 use anyhow::{anyhow, Result};
 use regex::Regex;
+use serde_json::json;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{mpsc, Arc, Mutex, OnceLock};
 use std::thread;
 use std::time::Instant;
 
+/// Whether this build can capture voice at all.
+///
+/// `false` for `overlay-only` builds, which only relay text injected through
+/// the control socket, and for builds with neither native capture (`audio` +
+/// `stt`) nor the Python fallback.
+pub const VOICE_CAPTURE_BUILT: bool = !cfg!(feature = "overlay-only")
+    && (cfg!(all(feature = "audio", feature = "stt")) || cfg!(feature = "python-fallback"));
+
 /// Shows whether capture was started manually or by auto mode.
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum VoiceCaptureTrigger {
@@ -40,7 +51,7 @@ impl VoiceJob {
 }
 
 /// Messages sent from the worker back to the UI.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq)]
 pub enum VoiceJobMessage {
     /// A transcript was produced successfully.
     Transcript {
@@ -48,6 +59,10 @@ pub enum VoiceJobMessage {
         text: String,
         /// Which pipeline produced the transcript.
         source: VoiceCaptureSource,
+        /// Mean Whisper token probability (0.0-1.0); `None` when the pipeline can't report one.
+        confidence: Option<f32>,
+        /// Per-word Whisper confidence; empty when the pipeline can't report it.
+        words: Vec<stt::WordConfidence>,
         /// Capture metrics if available.
         metrics: Option<audio::CaptureMetrics>,
     },
@@ -59,7 +74,7 @@ pub enum VoiceJobMessage {
         metrics: Option<audio::CaptureMetrics>,
     },
     /// Capture or transcription failed.
-    Error(String),
+    Error(VoiceError),
 }
 
 /// Identifies whether the Rust or Python path produced the transcript.
@@ -116,11 +131,14 @@ fn perform_voice_capture(
     let (Some(recorder), Some(transcriber)) = (recorder, transcriber) else {
         return fallback_or_error(
             config,
-            "native pipeline unavailable",
+            &VoiceError::new(VoiceErrorKind::Unknown, "native pipeline unavailable"),
             Some(stop_flag),
             meter.clone(),
         );
     };
+    if config.voice_ab_compare {
+        return perform_ab_capture(recorder, transcriber, config, stop_flag, meter);
+    }
 
     match capture_voice_native(
         recorder,
@@ -129,9 +147,11 @@ fn perform_voice_capture(
         stop_flag.clone(),
         meter.clone(),
     ) {
-        Ok((Some(transcript), metrics)) => VoiceJobMessage::Transcript {
-            text: transcript,
+        Ok((Some(transcription), metrics)) => VoiceJobMessage::Transcript {
+            text: transcription.text,
             source: VoiceCaptureSource::Native,
+            confidence: transcription.confidence,
+            words: transcription.words,
             metrics: Some(metrics),
         },
         Ok((None, metrics)) => VoiceJobMessage::Empty {
@@ -140,24 +160,139 @@ fn perform_voice_capture(
         },
         Err(native_err) => fallback_or_error(
             config,
-            &format!("{native_err:#}"),
+            &VoiceError::from_anyhow(&native_err),
             Some(stop_flag),
             meter.clone(),
         ),
     }
 }
 
+/// Run the native and Python pipelines on the same utterance and keep the better transcript.
+///
+/// The Python script records its own `--seconds` window alongside the native
+/// recorder, so the result arrives once both have finished.
+fn perform_ab_capture(
+    recorder: Arc<Mutex<audio::Recorder>>,
+    transcriber: Arc<Mutex<stt::Transcriber>>,
+    config: &crate::config::AppConfig,
+    stop_flag: Arc<AtomicBool>,
+    meter: Option<audio::LiveMeter>,
+) -> VoiceJobMessage {
+    let python_config = config.clone();
+    let python_stop = stop_flag.clone();
+    let python_handle =
+        thread::spawn(move || call_python_transcription(&python_config, Some(python_stop)));
+    let native = capture_voice_native(recorder, transcriber, config, stop_flag, meter);
+    let python = python_handle
+        .join()
+        .unwrap_or_else(|_| Err(anyhow!("python pipeline panicked")))
+        .map(|pipeline| sanitize_transcript(&pipeline.transcript));
+
+    let native_text = match &native {
+        Ok((Some(transcription), _)) => {
+            Some((transcription.text.as_str(), transcription.confidence))
+        }
+        _ => None,
+    };
+    let python_text = python.as_deref().ok().filter(|text| !text.is_empty());
+    let (winner, reason) = crate::voice_compare::choose_ab_source(native_text, python_text);
+    log_ab_comparison(config, winner, reason, &native, &python);
+
+    if winner == VoiceCaptureSource::Python {
+        return VoiceJobMessage::Transcript {
+            text: python_text.unwrap_or_default().to_string(),
+            source: VoiceCaptureSource::Python,
+            confidence: None,
+            words: Vec::new(),
+            metrics: None,
+        };
+    }
+    match native {
+        Ok((Some(transcription), metrics)) => VoiceJobMessage::Transcript {
+            text: transcription.text,
+            source: VoiceCaptureSource::Native,
+            confidence: transcription.confidence,
+            words: transcription.words,
+            metrics: Some(metrics),
+        },
+        Ok((None, metrics)) => VoiceJobMessage::Empty {
+            source: VoiceCaptureSource::Native,
+            metrics: Some(metrics),
+        },
+        Err(native_err) => match python {
+            Ok(_) => VoiceJobMessage::Empty {
+                source: VoiceCaptureSource::Python,
+                metrics: None,
+            },
+            Err(python_err) => {
+                let native_err = VoiceError::from_anyhow(&native_err);
+                VoiceJobMessage::Error(VoiceError::new(
+                    native_err.kind,
+                    format!(
+                        "native pipeline failed ({}); python pipeline failed ({python_err:#})",
+                        native_err.message
+                    ),
+                ))
+            }
+        },
+    }
+}
+
+fn log_ab_comparison(
+    config: &crate::config::AppConfig,
+    winner: VoiceCaptureSource,
+    reason: &str,
+    native: &Result<(Option<stt::Transcription>, audio::CaptureMetrics)>,
+    python: &Result<String>,
+) {
+    let native_text = match native {
+        Ok((Some(transcription), _)) => transcription.text.as_str(),
+        _ => "",
+    };
+    let python_text = python.as_deref().unwrap_or_default();
+    let mut fields = vec![
+        ("winner", json!(winner.label())),
+        ("reason", json!(reason)),
+        ("native_chars", json!(native_text.len())),
+        ("python_chars", json!(python_text.len())),
+        (
+            "native_confidence",
+            json!(native
+                .as_ref()
+                .ok()
+                .and_then(|(transcription, _)| transcription.as_ref()?.confidence)),
+        ),
+    ];
+    if let Err(err) = native {
+        fields.push(("native_error", json!(format!("{err:#}"))));
+    }
+    if let Err(err) = python {
+        fields.push(("python_error", json!(format!("{err:#}"))));
+    }
+    match crate::voice_compare::transcript_diff(native_text, python_text) {
+        None => fields.push(("match", json!(true))),
+        Some((native_only, python_only)) => {
+            fields.push(("match", json!(false)));
+            // Transcript words are content; only log them with --log-content.
+            if config.log_content {
+                fields.push(("native_only", json!(native_only)));
+                fields.push(("python_only", json!(python_only)));
+            }
+        }
+    }
+    log_event(LogLevel::Info, "voice_ab", &fields);
+}
+
 fn run_python_fallback(
     config: &crate::config::AppConfig,
-    native_msg: &str,
+    native_err: &VoiceError,
     stop_flag: Option<Arc<AtomicBool>>,
     meter: Option<audio::LiveMeter>,
 ) -> VoiceJobMessage {
     if config.no_python_fallback {
-        return VoiceJobMessage::Error(format!(
-            "native pipeline failed ({native_msg}); python fallback disabled (--no-python-fallback)"
-        ));
+        return fallback_disabled_error(native_err);
     }
+    let native_msg = &native_err.message;
 
     log_debug(&format!(
         "Native voice capture unavailable/failed ({native_msg}). Falling back to python pipeline."
@@ -177,28 +312,50 @@ fn run_python_fallback(
                 VoiceJobMessage::Transcript {
                     text: transcript,
                     source: VoiceCaptureSource::Python,
+                    confidence: None,
+                    words: Vec::new(),
                     metrics: None,
                 }
             }
         }
-        Err(python_err) => VoiceJobMessage::Error(format!(
-            "native pipeline failed ({native_msg}); python fallback failed ({python_err:#})"
-        )),
+        Err(python_err) => {
+            // A mic problem usually breaks both paths; surface the root cause
+            // rather than the generic fallback failure.
+            let kind = if native_err.kind.is_device_problem() {
+                native_err.kind.clone()
+            } else {
+                VoiceErrorKind::Fallback
+            };
+            VoiceJobMessage::Error(VoiceError::new(
+                kind,
+                format!(
+                    "native pipeline failed ({native_msg}); python fallback failed ({python_err:#})"
+                ),
+            ))
+        }
     }
 }
 
+fn fallback_disabled_error(native_err: &VoiceError) -> VoiceJobMessage {
+    VoiceJobMessage::Error(VoiceError::new(
+        native_err.kind.clone(),
+        format!(
+            "native pipeline failed ({}); python fallback disabled (--no-python-fallback)",
+            native_err.message
+        ),
+    ))
+}
+
 fn fallback_or_error(
     config: &crate::config::AppConfig,
-    native_msg: &str,
+    native_err: &VoiceError,
     stop_flag: Option<Arc<AtomicBool>>,
     meter: Option<audio::LiveMeter>,
 ) -> VoiceJobMessage {
     if config.no_python_fallback {
-        VoiceJobMessage::Error(format!(
-            "native pipeline failed ({native_msg}); python fallback disabled (--no-python-fallback)"
-        ))
+        fallback_disabled_error(native_err)
     } else {
-        run_python_fallback(config, native_msg, stop_flag, meter)
+        run_python_fallback(config, native_err, stop_flag, meter)
     }
 }
 
@@ -238,29 +395,46 @@ pub(crate) fn set_python_transcription_hook(hook: Option<PythonTranscriptionHook
     *storage.lock().unwrap_or_else(|e| e.into_inner()) = hook;
 }
 
-/// Record audio, run Whisper, and return the trimmed transcript.
+/// Record audio, run Whisper, and return the trimmed transcript with its confidence.
 fn capture_voice_native(
     recorder: Arc<Mutex<audio::Recorder>>,
     transcriber: Arc<Mutex<stt::Transcriber>>,
     config: &crate::config::AppConfig,
     stop_flag: Arc<AtomicBool>,
     meter: Option<audio::LiveMeter>,
-) -> Result<(Option<String>, audio::CaptureMetrics)> {
+) -> Result<(Option<stt::Transcription>, audio::CaptureMetrics)> {
     log_debug("capture_voice_native: Starting");
     let pipeline_cfg = config.voice_pipeline_config();
     let vad_cfg: audio::VadConfig = (&pipeline_cfg).into();
     let record_start = Instant::now();
+    let mut stream_worker = None;
     let capture = {
         let recorder_guard = recorder
             .lock()
             .map_err(|_| anyhow!("audio recorder lock poisoned"))?;
         let mut vad_engine = create_vad_engine(&pipeline_cfg);
-        recorder_guard.record_with_vad(
-            &vad_cfg,
-            vad_engine.as_mut(),
-            Some(stop_flag),
-            meter.clone(),
-        )
+        if config.whisper_stream {
+            let (tap, frames) = crossbeam_channel::unbounded();
+            stream_worker = Some(voice_stream::spawn_chunk_worker(
+                transcriber.clone(),
+                config.clone(),
+                frames,
+            ));
+            recorder_guard.record_with_vad_streaming(
+                &vad_cfg,
+                vad_engine.as_mut(),
+                Some(stop_flag),
+                meter.clone(),
+                tap,
+            )
+        } else {
+            recorder_guard.record_with_vad(
+                &vad_cfg,
+                vad_engine.as_mut(),
+                Some(stop_flag),
+                meter.clone(),
+            )
+        }
     }?;
     let audio::CaptureResult { audio, mut metrics } = capture;
     log_voice_metrics(&metrics);
@@ -268,16 +442,74 @@ fn capture_voice_native(
         log_debug("capture_voice_native: empty audio capture");
         return Ok((None, metrics));
     }
+    if metrics.mic_appears_muted() {
+        // Whisper tends to hallucinate on pure zeros; skip STT for a muted mic.
+        log_debug("capture_voice_native: digital silence, mic appears muted");
+        return Ok((None, metrics));
+    }
+    if !metrics.is_utterance(config.voice_min_utterance_ms, config.voice_min_voiced_ratio) {
+        // Coughs and chair squeaks: too little speech, or speech too sparse, to transcribe.
+        log_debug(&format!(
+            "capture_voice_native: not an utterance (speech_ms={}, voiced_ratio={:?})",
+            metrics.speech_ms,
+            metrics.voiced_ratio()
+        ));
+        metrics.utterance_rejected = true;
+        return Ok((None, metrics));
+    }
+    // Capture and VAD run at the pipeline rate; everything past here expects 16 kHz.
+    let audio = if pipeline_cfg.sample_rate == audio::TARGET_RATE {
+        audio
+    } else {
+        audio::resample(
+            &audio,
+            pipeline_cfg.sample_rate,
+            audio::TARGET_RATE,
+            pipeline_cfg.resample_quality,
+        )
+    };
+    if config.speaker_filter && !speaker_matches(&audio, config) {
+        metrics.speaker_rejected = true;
+        return Ok((None, metrics));
+    }
+    if let Some(dump) = config.audio_dump() {
+        match dump.write(&audio, audio::TARGET_RATE) {
+            Ok(path) => {
+                log_debug(&format!("capture dumped to {}", path.display()));
+                metrics.audio_path = Some(path);
+            }
+            Err(err) => log_debug(&format!("capture dump failed: {err:#}")),
+        }
+    }
     let record_elapsed = record_start.elapsed().as_secs_f64();
 
     log_debug("capture_voice_native: Starting transcription");
     let stt_start = Instant::now();
-    let transcript = {
-        let transcriber_guard = transcriber
-            .lock()
-            .map_err(|_| anyhow!("transcriber lock poisoned"))?;
-        // Output suppression is now handled inside transcribe() method
-        transcriber_guard.transcribe(&audio, config)?
+    let streamed = stream_worker.and_then(|worker| finish_streamed_transcription(worker, config));
+    let transcription = match streamed {
+        Some(stitched) if !config.whisper_stream_final_pass => stitched,
+        stitched => {
+            let full_pass = match transcribe_remote(&audio, audio::TARGET_RATE, config) {
+                Some(transcription) => Ok(transcription),
+                // Output suppression is now handled inside transcribe() method
+                None => transcriber
+                    .lock()
+                    .map_err(|_| anyhow!("transcriber lock poisoned"))
+                    .and_then(|transcriber_guard| {
+                        transcriber_guard.transcribe_detailed(&audio, config)
+                    }),
+            };
+            match (full_pass, stitched) {
+                (Ok(transcription), _) => transcription,
+                (Err(err), Some(stitched)) => {
+                    log_debug(&format!(
+                        "full-context pass failed, keeping streamed transcript: {err:#}"
+                    ));
+                    stitched
+                }
+                (Err(err), None) => return Err(err),
+            }
+        }
     };
     let stt_elapsed = stt_start.elapsed();
     metrics.transcribe_ms = stt_elapsed.as_millis().min(u128::from(u32::MAX)) as u64;
@@ -287,21 +519,112 @@ fn capture_voice_native(
         "capture_voice_native: Transcription complete in {stt_elapsed:.2}s"
     ));
 
-    let cleaned = sanitize_transcript(&transcript);
+    let cleaned = sanitize_transcript(&transcription.text);
     if config.log_timings {
-        log_debug(&format!(
-            "timing|phase=voice_capture|record_s={:.3}|stt_s={:.3}|chars={}",
-            record_elapsed,
-            stt_elapsed,
-            cleaned.len()
-        ));
+        log_event(
+            LogLevel::Info,
+            "timing",
+            &[
+                ("phase", json!("voice_capture")),
+                ("record_s", seconds_field(record_elapsed)),
+                ("stt_s", seconds_field(stt_elapsed)),
+                ("chars", json!(cleaned.len())),
+                ("confidence", json!(transcription.confidence)),
+            ],
+        );
     }
 
     if cleaned.is_empty() {
         Ok((None, metrics))
     } else {
-        Ok((Some(cleaned), metrics))
+        Ok((
+            Some(stt::Transcription {
+                text: cleaned,
+                ..transcription
+            }),
+            metrics,
+        ))
+    }
+}
+
+/// Cloud transcript for `--stt-backend openai|deepgram`; `None` means use local Whisper.
+fn transcribe_remote(
+    audio: &[f32],
+    sample_rate: u32,
+    config: &crate::config::AppConfig,
+) -> Option<stt::Transcription> {
+    let remote = stt::RemoteTranscriber::from_config(config)?;
+    match remote.transcribe(audio, sample_rate, &config.lang) {
+        Ok(transcription) => Some(transcription),
+        Err(err) => {
+            log_debug(&format!(
+                "{} STT failed, falling back to local whisper: {err:#}",
+                remote.label()
+            ));
+            None
+        }
+    }
+}
+
+/// Whether the capture sounds like the enrolled user; unreadable prints let it through.
+fn speaker_matches(audio: &[f32], config: &crate::config::AppConfig) -> bool {
+    let Some(path) = config.voice_print_path() else {
+        return true;
+    };
+    let enrolled = match audio::VoicePrint::load(&path) {
+        Ok(print) => print,
+        Err(err) => {
+            log_debug(&format!("speaker filter skipped: {err:#}"));
+            return true;
+        }
+    };
+    // Too little speech to judge is left to STT, which usually finds nothing either.
+    let Some(print) = audio::VoicePrint::from_samples(audio) else {
+        return true;
+    };
+    let distance = enrolled.distance(&print);
+    log_debug(&format!(
+        "speaker filter: distance {distance:.3} (threshold {:.3})",
+        config.speaker_threshold
+    ));
+    distance <= config.speaker_threshold
+}
+
+/// Wait for the chunk worker and return the stitched transcript.
+///
+/// `None` means streaming failed or heard nothing; the caller then runs a
+/// full-context pass over the trimmed audio, as it also does (keeping this
+/// transcript as a backup) with `--whisper-stream-final-pass`.
+fn finish_streamed_transcription(
+    worker: thread::JoinHandle<Result<voice_stream::StreamOutcome>>,
+    config: &crate::config::AppConfig,
+) -> Option<stt::Transcription> {
+    let outcome = match worker.join() {
+        Ok(Ok(outcome)) => outcome,
+        Ok(Err(err)) => {
+            log_debug(&format!(
+                "whisper stream failed, using a full pass: {err:#}"
+            ));
+            return None;
+        }
+        Err(_) => {
+            log_debug("whisper stream worker panicked, using a full pass");
+            return None;
+        }
+    };
+    log_event(
+        LogLevel::Debug,
+        "voice_stream",
+        &[
+            ("chunks", json!(outcome.chunks)),
+            ("skipped", json!(outcome.skipped)),
+            ("final_pass", json!(config.whisper_stream_final_pass)),
+        ],
+    );
+    if outcome.transcription.text.trim().is_empty() {
+        return None;
     }
+    Some(outcome.transcription)
 }
 
 fn sanitize_transcript(text: &str) -> String {
@@ -324,20 +647,28 @@ fn sanitize_transcript(text: &str) -> String {
 }
 
 /// Emit structured metrics for perf_smoke consumption.
-/// Format: `voice_metrics|capture_ms=...|speech_ms=...|silence_tail_ms=...|frames_processed=...|frames_dropped=...|early_stop=...`
+/// Text format: `voice_metrics|capture_ms=...|speech_ms=...|silence_tail_ms=...|frames_processed=...|frames_dropped=...|early_stop=...`
 pub(crate) fn log_voice_metrics(metrics: &audio::CaptureMetrics) {
-    log_debug(&format!(
-        "voice_metrics|capture_ms={}|speech_ms={}|silence_tail_ms={}|frames_processed={}|frames_dropped={}|early_stop={}",
-        metrics.capture_ms,
-        metrics.speech_ms,
-        metrics.silence_tail_ms,
-        metrics.frames_processed,
-        metrics.frames_dropped,
-        metrics.early_stop_reason.label()
-    ));
+    log_event(
+        LogLevel::Info,
+        "voice_metrics",
+        &[
+            ("capture_ms", json!(metrics.capture_ms)),
+            ("speech_ms", json!(metrics.speech_ms)),
+            ("voiced_ratio", json!(metrics.voiced_ratio())),
+            ("silence_tail_ms", json!(metrics.silence_tail_ms)),
+            ("frames_processed", json!(metrics.frames_processed)),
+            ("frames_dropped", json!(metrics.frames_dropped)),
+            ("early_stop", json!(metrics.early_stop_reason.label())),
+            ("digital_silence_ms", json!(metrics.digital_silence_ms)),
+            ("preroll_ms", json!(metrics.preroll_ms)),
+            ("echo_muted_ms", json!(metrics.echo_muted_ms)),
+        ],
+    );
 }
 
-fn create_vad_engine(cfg: &crate::config::VoicePipelineConfig) -> Box<dyn audio::VadEngine> {
+/// VAD engine selected by `--voice-vad-engine`.
+pub fn create_vad_engine(cfg: &crate::config::VoicePipelineConfig) -> Box<dyn audio::VadEngine> {
     match cfg.vad_engine {
         VadEngineKind::Simple => Box::new(audio::SimpleThresholdVad::new(cfg.vad_threshold_db)),
         VadEngineKind::Earshot => {
@@ -390,6 +721,10 @@ mod tests {
         assert_eq!(engine.name(), "earshot_vad");
     }
 
+    fn native_error(message: &str) -> VoiceError {
+        VoiceError::new(VoiceErrorKind::Unknown, message)
+    }
+
     fn pipeline_result(transcript: &str) -> PipelineJsonResult {
         PipelineJsonResult {
             transcript: transcript.to_string(),
@@ -424,19 +759,24 @@ mod tests {
         assert_eq!(VoiceCaptureSource::Python.label(), "Python fallback");
     }
 
+    #[cfg(feature = "python-fallback")]
     #[test]
     fn python_fallback_returns_trimmed_transcript() {
         let config = test_config();
         let message = with_python_hook(Box::new(|_, _| Ok(pipeline_result("  hello "))), || {
-            run_python_fallback(&config, "native unavailable", None, None)
+            run_python_fallback(&config, &native_error("native unavailable"), None, None)
         });
 
         match message {
             VoiceJobMessage::Transcript {
                 text,
                 source,
+                confidence,
+                words,
                 metrics: _,
             } => {
+                assert_eq!(confidence, None);
+                assert!(words.is_empty());
                 assert_eq!(text, "hello");
                 assert_eq!(source, VoiceCaptureSource::Python);
             }
@@ -444,11 +784,12 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "python-fallback")]
     #[test]
     fn python_fallback_reports_empty_transcripts() {
         let config = test_config();
         let message = with_python_hook(Box::new(|_, _| Ok(pipeline_result("   "))), || {
-            run_python_fallback(&config, "no native path", None, None)
+            run_python_fallback(&config, &native_error("no native path"), None, None)
         });
 
         match message {
@@ -459,24 +800,28 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "python-fallback")]
     #[test]
     fn python_fallback_surfaces_errors() {
         let config = test_config();
         let message = with_python_hook(Box::new(|_, _| Err(anyhow!("python boom"))), || {
-            run_python_fallback(&config, "native blew up", None, None)
+            run_python_fallback(&config, &native_error("native blew up"), None, None)
         });
 
         match message {
-            VoiceJobMessage::Error(text) => {
+            VoiceJobMessage::Error(err) => {
+                let text = &err.message;
                 assert!(
                     text.contains("native blew up") && text.contains("python boom"),
                     "error should include both paths, got {text}"
                 );
+                assert_eq!(err.kind, VoiceErrorKind::Fallback);
             }
             other => panic!("expected error, got {other:?}"),
         }
     }
 
+    #[cfg(feature = "python-fallback")]
     #[test]
     fn start_voice_job_handles_concurrent_fallbacks() {
         let config = test_config();
@@ -522,6 +867,7 @@ mod tests {
         assert_eq!(counter.load(Ordering::SeqCst), job_count);
     }
 
+    #[cfg(feature = "python-fallback")]
     #[test]
     fn perform_voice_capture_falls_back_when_components_missing() {
         let config = test_config();
@@ -534,8 +880,12 @@ mod tests {
             VoiceJobMessage::Transcript {
                 text,
                 source,
+                confidence,
+                words,
                 metrics: _,
             } => {
+                assert_eq!(confidence, None);
+                assert!(words.is_empty());
                 assert_eq!(text, "fallback success");
                 assert_eq!(source, VoiceCaptureSource::Python);
             }
@@ -551,7 +901,8 @@ mod tests {
             perform_voice_capture(None, None, &config, Arc::new(AtomicBool::new(false)), None);
 
         match message {
-            VoiceJobMessage::Error(text) => {
+            VoiceJobMessage::Error(err) => {
+                let text = &err.message;
                 assert!(
                     text.contains("python fallback disabled"),
                     "expected disable hint, got {text}"
@@ -560,4 +911,25 @@ mod tests {
             other => panic!("expected error, got {other:?}"),
         }
     }
+
+    #[test]
+    fn python_fallback_keeps_device_error_kind() {
+        let config = test_config();
+        let native = VoiceError::new(
+            VoiceErrorKind::MicBusy {
+                owner: Some("zoom".to_string()),
+            },
+            "Device or resource busy",
+        );
+        let message = with_python_hook(Box::new(|_, _| Err(anyhow!("python boom"))), || {
+            run_python_fallback(&config, &native, None, None)
+        });
+
+        match message {
+            VoiceJobMessage::Error(err) => {
+                assert_eq!(err.status_message(), "Mic busy (in use by zoom)");
+            }
+            other => panic!("expected error, got {other:?}"),
+        }
+    }
 }
@@ -0,0 +1,122 @@
+//! A/B comparison between the native and Python pipelines for `--voice-ab-compare`.
+//!
+//! Both pipelines transcribe the same utterance; these helpers pick the transcript
+//! to send and summarize where the two disagree so the native path can be
+//! validated before the fallback is turned off.
+
+use crate::voice::VoiceCaptureSource;
+
+/// Native transcripts below this mean token probability lose to a Python result.
+const AB_MIN_NATIVE_CONFIDENCE: f32 = 0.45;
+
+/// Pick the transcript to send, with a short reason for the log.
+///
+/// `native` is the native transcript and its confidence, `python` the Python
+/// transcript; `None` means that side produced nothing usable. Native wins ties
+/// because it carries confidence and capture metrics.
+pub(crate) fn choose_ab_source(
+    native: Option<(&str, Option<f32>)>,
+    python: Option<&str>,
+) -> (VoiceCaptureSource, &'static str) {
+    let (native_text, native_confidence) = match (native, python) {
+        (None, None) => return (VoiceCaptureSource::Native, "both_empty"),
+        (None, Some(_)) => return (VoiceCaptureSource::Python, "native_empty"),
+        (Some(_), None) => return (VoiceCaptureSource::Native, "python_empty"),
+        (Some(native), Some(_)) => native,
+    };
+    let python_text = python.unwrap_or_default();
+    if native_confidence.is_some_and(|confidence| confidence < AB_MIN_NATIVE_CONFIDENCE) {
+        return (VoiceCaptureSource::Python, "native_low_confidence");
+    }
+    // A native transcript under half the length usually means a clipped capture.
+    if word_count(native_text) * 2 < word_count(python_text) {
+        return (VoiceCaptureSource::Python, "native_truncated");
+    }
+    (VoiceCaptureSource::Native, "native_preferred")
+}
+
+/// Words only in the native and only in the Python transcript, after trimming the
+/// shared prefix and suffix; `None` when they match ignoring case and punctuation.
+pub(crate) fn transcript_diff(native: &str, python: &str) -> Option<(String, String)> {
+    let native_words: Vec<&str> = native.split_whitespace().collect();
+    let python_words: Vec<&str> = python.split_whitespace().collect();
+    let same = |a: &&str, b: &&str| normalize(a) == normalize(b);
+    let prefix = native_words
+        .iter()
+        .zip(&python_words)
+        .take_while(|(a, b)| same(a, b))
+        .count();
+    let suffix = native_words[prefix..]
+        .iter()
+        .rev()
+        .zip(python_words[prefix..].iter().rev())
+        .take_while(|(a, b)| same(a, b))
+        .count();
+    let native_only = &native_words[prefix..native_words.len() - suffix];
+    let python_only = &python_words[prefix..python_words.len() - suffix];
+    if native_only.is_empty() && python_only.is_empty() {
+        return None;
+    }
+    Some((native_only.join(" "), python_only.join(" ")))
+}
+
+fn word_count(text: &str) -> usize {
+    text.split_whitespace().count()
+}
+
+fn normalize(word: &str) -> String {
+    word.chars()
+        .filter(|ch| ch.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn choose_prefers_whichever_side_produced_text() {
+        assert_eq!(
+            choose_ab_source(None, Some("hello")),
+            (VoiceCaptureSource::Python, "native_empty")
+        );
+        assert_eq!(
+            choose_ab_source(Some(("hello", Some(0.1))), None),
+            (VoiceCaptureSource::Native, "python_empty")
+        );
+        assert_eq!(choose_ab_source(None, None).0, VoiceCaptureSource::Native);
+    }
+
+    #[test]
+    fn choose_falls_back_to_python_for_weak_native_results() {
+        assert_eq!(
+            choose_ab_source(Some(("run the tests", Some(0.3))), Some("run the tests")),
+            (VoiceCaptureSource::Python, "native_low_confidence")
+        );
+        assert_eq!(
+            choose_ab_source(
+                Some(("run", Some(0.9))),
+                Some("run the unit tests again please")
+            ),
+            (VoiceCaptureSource::Python, "native_truncated")
+        );
+        assert_eq!(
+            choose_ab_source(Some(("run the tests", None)), Some("run the test suite")),
+            (VoiceCaptureSource::Native, "native_preferred")
+        );
+    }
+
+    #[test]
+    fn diff_trims_shared_words_and_ignores_case_and_punctuation() {
+        assert_eq!(transcript_diff("Run the tests.", "run the tests"), None);
+        assert_eq!(
+            transcript_diff("run cargo clippy now", "run cargo clip e now"),
+            Some(("clippy".to_string(), "clip e".to_string()))
+        );
+        assert_eq!(
+            transcript_diff("open the file", "open the file please"),
+            Some((String::new(), "please".to_string()))
+        );
+    }
+}
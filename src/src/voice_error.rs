@@ -0,0 +1,231 @@
+//! Voice error taxonomy so the UI can show actionable causes instead of "see log".
+//!
+//! Audio, STT, and fallback paths attach a [`VoiceErrorKind`] as `anyhow`
+//! context. The UI renders the short category in the status line while the full
+//! error chain still goes to the debug log.
+
+use std::fmt;
+
+/// High-level failure category for a voice capture attempt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VoiceErrorKind {
+    /// The OS refused microphone access (or delivered no samples at all).
+    MicPermission,
+    /// Another application holds the microphone exclusively.
+    MicBusy {
+        /// Process name holding the device, when it can be identified.
+        owner: Option<String>,
+    },
+    /// No input device exists or the requested device was not found.
+    NoDevice,
+    /// The device exists but could not be opened or disappeared mid-capture.
+    DeviceUnavailable,
+    /// The Whisper model could not be loaded.
+    Model,
+    /// Whisper failed while transcribing captured audio.
+    Transcription,
+    /// The Python fallback pipeline failed after the native path failed.
+    Fallback,
+    /// Anything that does not fit a more specific category.
+    Unknown,
+}
+
+impl VoiceErrorKind {
+    /// Stable lowercase identifier used in logs and IPC payloads.
+    pub fn label(&self) -> &'static str {
+        match self {
+            VoiceErrorKind::MicPermission => "mic_permission",
+            VoiceErrorKind::MicBusy { .. } => "mic_busy",
+            VoiceErrorKind::NoDevice => "no_device",
+            VoiceErrorKind::DeviceUnavailable => "device_unavailable",
+            VoiceErrorKind::Model => "model",
+            VoiceErrorKind::Transcription => "transcription",
+            VoiceErrorKind::Fallback => "fallback",
+            VoiceErrorKind::Unknown => "unknown",
+        }
+    }
+
+    /// Short, actionable status-line text for this category.
+    pub fn status_message(&self) -> String {
+        match self {
+            VoiceErrorKind::MicPermission => {
+                "Mic permission denied (check OS privacy settings)".to_string()
+            }
+            VoiceErrorKind::MicBusy { owner: Some(owner) } => {
+                format!("Mic busy (in use by {owner})")
+            }
+            VoiceErrorKind::MicBusy { owner: None } => {
+                "Mic busy (in use by another app)".to_string()
+            }
+            VoiceErrorKind::NoDevice => {
+                "No microphone found (try --list-input-devices)".to_string()
+            }
+            VoiceErrorKind::DeviceUnavailable => {
+                "Mic unavailable (reconnect or pick --input-device)".to_string()
+            }
+            VoiceErrorKind::Model => {
+                "Whisper model failed to load (check --whisper-model-path)".to_string()
+            }
+            VoiceErrorKind::Transcription => "Transcription failed (see log)".to_string(),
+            VoiceErrorKind::Fallback => "Python fallback failed (see log)".to_string(),
+            VoiceErrorKind::Unknown => "Voice capture failed (see log)".to_string(),
+        }
+    }
+
+    /// Whether the failure is about the microphone rather than the STT stack.
+    pub fn is_device_problem(&self) -> bool {
+        matches!(
+            self,
+            VoiceErrorKind::MicPermission
+                | VoiceErrorKind::MicBusy { .. }
+                | VoiceErrorKind::NoDevice
+                | VoiceErrorKind::DeviceUnavailable
+        )
+    }
+
+    /// Whether the failure came from the STT stack, so capturing again may succeed.
+    pub fn allows_redictation(&self) -> bool {
+        matches!(
+            self,
+            VoiceErrorKind::Transcription | VoiceErrorKind::Fallback
+        )
+    }
+
+    /// Find the category attached to an error chain, falling back to message heuristics.
+    pub fn classify(err: &anyhow::Error) -> Self {
+        if let Some(kind) = err.downcast_ref::<Self>() {
+            return kind.clone();
+        }
+        Self::from_message(&format!("{err:#}"))
+    }
+
+    /// Best-effort classification for backend error strings (cpal, whisper, OS errors).
+    pub fn from_message(message: &str) -> Self {
+        let lower = message.to_ascii_lowercase();
+        if lower.contains("busy") || lower.contains("in use") {
+            VoiceErrorKind::MicBusy { owner: None }
+        } else if lower.contains("permission")
+            || lower.contains("not permitted")
+            || lower.contains("access denied")
+            || lower.contains("no samples captured")
+        {
+            VoiceErrorKind::MicPermission
+        } else if lower.contains("no input devices")
+            || lower.contains("no default input device")
+            || (lower.contains("input device") && lower.contains("not found"))
+        {
+            VoiceErrorKind::NoDevice
+        } else if lower.contains("device is no longer available")
+            || lower.contains("device not available")
+            || lower.contains("disconnected")
+            || lower.contains("no such device")
+        {
+            VoiceErrorKind::DeviceUnavailable
+        } else if lower.contains("whisper model") || lower.contains("model file") {
+            VoiceErrorKind::Model
+        } else if lower.contains("python fallback failed") {
+            VoiceErrorKind::Fallback
+        } else {
+            VoiceErrorKind::Unknown
+        }
+    }
+}
+
+impl fmt::Display for VoiceErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.status_message())
+    }
+}
+
+/// Categorized voice failure delivered from the worker thread to the UI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VoiceError {
+    /// Category used for the status line.
+    pub kind: VoiceErrorKind,
+    /// Full error detail for logs.
+    pub message: String,
+}
+
+impl VoiceError {
+    /// Build a categorized error from a kind and detail message.
+    pub fn new(kind: VoiceErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+
+    /// Build a categorized error from an `anyhow` chain.
+    pub fn from_anyhow(err: &anyhow::Error) -> Self {
+        Self::new(VoiceErrorKind::classify(err), format!("{err:#}"))
+    }
+
+    /// Short status-line text for the UI.
+    pub fn status_message(&self) -> String {
+        self.kind.status_message()
+    }
+}
+
+impl fmt::Display for VoiceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for VoiceError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::{anyhow, Context};
+
+    #[test]
+    fn classify_prefers_attached_kind() {
+        let err = Err::<(), _>(anyhow!("stream build failed"))
+            .context(VoiceErrorKind::NoDevice)
+            .unwrap_err();
+        assert_eq!(VoiceErrorKind::classify(&err), VoiceErrorKind::NoDevice);
+    }
+
+    #[test]
+    fn classify_uses_message_heuristics_without_context() {
+        let err = anyhow!("ALSA function 'snd_pcm_open' failed: Device or resource busy");
+        assert_eq!(
+            VoiceErrorKind::classify(&err),
+            VoiceErrorKind::MicBusy { owner: None }
+        );
+        let err = anyhow!("no samples captured; check microphone permissions");
+        assert_eq!(
+            VoiceErrorKind::classify(&err),
+            VoiceErrorKind::MicPermission
+        );
+        let err = anyhow!("input device 'USB Mic' not found");
+        assert_eq!(VoiceErrorKind::classify(&err), VoiceErrorKind::NoDevice);
+        let err = anyhow!("something odd");
+        assert_eq!(VoiceErrorKind::classify(&err), VoiceErrorKind::Unknown);
+    }
+
+    #[test]
+    fn status_message_names_busy_owner() {
+        let kind = VoiceErrorKind::MicBusy {
+            owner: Some("zoom".to_string()),
+        };
+        assert_eq!(kind.status_message(), "Mic busy (in use by zoom)");
+        assert!(kind.is_device_problem());
+        assert!(!VoiceErrorKind::Model.is_device_problem());
+        assert!(VoiceErrorKind::Transcription.allows_redictation());
+        assert!(!VoiceErrorKind::Model.allows_redictation());
+        assert!(!kind.allows_redictation());
+    }
+
+    #[test]
+    fn voice_error_keeps_full_detail_for_logs() {
+        let err = Err::<(), _>(anyhow!("failed to load whisper model"))
+            .context(VoiceErrorKind::Model)
+            .unwrap_err();
+        let voice_err = VoiceError::from_anyhow(&err);
+        assert_eq!(voice_err.kind, VoiceErrorKind::Model);
+        assert!(voice_err.message.contains("failed to load whisper model"));
+        assert_eq!(voice_err.to_string(), voice_err.message);
+    }
+}
@@ -0,0 +1,392 @@
+//! Sliding-window streaming STT for `--whisper-stream`.
+//!
+//! While the recorder is still capturing, frames are cut into overlapping
+//! windows and transcribed one by one, so by the time the user stops talking
+//! only the last window is left to decode. Neighbouring windows share
+//! `--whisper-stream-overlap-ms` of audio; the words both windows heard are
+//! matched up and kept once when the chunk transcripts are stitched together.
+
+use crate::audio::{resample, StreamFrame, TARGET_RATE};
+use crate::config::AppConfig;
+use crate::stt::{Transcriber, Transcription, WordConfidence};
+use anyhow::{anyhow, Result};
+use crossbeam_channel::Receiver;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// Longest run of words compared when looking for the overlap between chunks.
+const MAX_OVERLAP_WORDS: usize = 8;
+/// A single shared word only counts as overlap when it is at least this long,
+/// so short function words ("the", "a") do not cause false merges.
+const MIN_SINGLE_WORD_OVERLAP_CHARS: usize = 4;
+
+/// A window of captured audio ready for transcription.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ChunkWindow {
+    /// Sample offset of the window within the capture.
+    pub(crate) start: usize,
+    pub(crate) samples: Vec<f32>,
+    /// Whether any frame in the window was labeled speech.
+    pub(crate) has_speech: bool,
+}
+
+/// Cuts the growing capture into overlapping fixed-length windows.
+pub(crate) struct ChunkScheduler {
+    chunk_samples: usize,
+    step_samples: usize,
+    overlap_samples: usize,
+    samples: Vec<f32>,
+    /// Sample ranges labeled speech, in capture order.
+    speech: Vec<(usize, usize)>,
+    next_start: usize,
+    emitted: bool,
+}
+
+impl ChunkScheduler {
+    pub(crate) fn new(chunk_ms: u64, overlap_ms: u64, sample_rate: u32) -> Self {
+        let to_samples = |ms: u64| (ms * u64::from(sample_rate) / 1000) as usize;
+        let chunk_samples = to_samples(chunk_ms).max(1);
+        let overlap_samples = to_samples(overlap_ms).min(chunk_samples / 2);
+        Self {
+            chunk_samples,
+            step_samples: chunk_samples - overlap_samples,
+            overlap_samples,
+            samples: Vec::new(),
+            speech: Vec::new(),
+            next_start: 0,
+            emitted: false,
+        }
+    }
+
+    pub(crate) fn push(&mut self, frame: &StreamFrame) {
+        let start = self.samples.len();
+        self.samples.extend_from_slice(&frame.samples);
+        if frame.speech {
+            let end = self.samples.len();
+            match self.speech.last_mut() {
+                Some(last) if last.1 == start => last.1 = end,
+                _ => self.speech.push((start, end)),
+            }
+        }
+    }
+
+    /// Next full window, once enough audio has arrived for it.
+    pub(crate) fn next_window(&mut self) -> Option<ChunkWindow> {
+        let end = self.next_start + self.chunk_samples;
+        if self.samples.len() < end {
+            return None;
+        }
+        let window = self.window(self.next_start, end);
+        self.next_start += self.step_samples;
+        self.emitted = true;
+        Some(window)
+    }
+
+    /// Whatever the full windows have not covered yet, once capture has ended.
+    pub(crate) fn finish(&mut self) -> Option<ChunkWindow> {
+        let covered = if self.emitted {
+            self.next_start + self.overlap_samples
+        } else {
+            0
+        };
+        if self.samples.len() <= covered {
+            return None;
+        }
+        let window = self.window(self.next_start, self.samples.len());
+        self.next_start = self.samples.len();
+        self.emitted = true;
+        Some(window)
+    }
+
+    fn window(&self, start: usize, end: usize) -> ChunkWindow {
+        ChunkWindow {
+            start,
+            samples: self.samples[start..end].to_vec(),
+            has_speech: self
+                .speech
+                .iter()
+                .any(|&(from, to)| from < end && to > start),
+        }
+    }
+}
+
+/// Chunk transcripts joined into one, with overlapping words kept once.
+#[derive(Debug, Default)]
+pub(crate) struct StitchedTranscript {
+    words: Vec<WordConfidence>,
+    /// Sum of chunk confidences weighted by the words each chunk contributed.
+    confidence_sum: f32,
+    confidence_words: usize,
+    tokens: usize,
+}
+
+impl StitchedTranscript {
+    /// Append the next chunk, dropping the words it shares with the previous one.
+    pub(crate) fn append(&mut self, chunk: &Transcription) {
+        let incoming = chunk_words(chunk);
+        let (drop_tail, skip_head) = overlap_split(&self.words, &incoming);
+        self.words.truncate(self.words.len() - drop_tail);
+        let added = &incoming[skip_head..];
+        if let Some(confidence) = chunk.confidence {
+            self.confidence_sum += confidence * added.len() as f32;
+            self.confidence_words += added.len();
+        }
+        self.words.extend_from_slice(added);
+        self.tokens += chunk.tokens;
+    }
+
+    pub(crate) fn into_transcription(self) -> Transcription {
+        let text = self
+            .words
+            .iter()
+            .map(|word| word.word.as_str())
+            .collect::<Vec<_>>()
+            .join(" ");
+        Transcription {
+            text,
+            confidence: (self.confidence_words > 0)
+                .then(|| self.confidence_sum / self.confidence_words as f32),
+            words: self.words,
+            tokens: self.tokens,
+        }
+    }
+}
+
+/// What the streaming worker produced for one capture.
+pub(crate) struct StreamOutcome {
+    pub(crate) transcription: Transcription,
+    /// Windows sent to Whisper.
+    pub(crate) chunks: usize,
+    /// Windows skipped because VAD heard no speech in them.
+    pub(crate) skipped: usize,
+}
+
+/// Start transcribing windows as frames arrive; the result is ready once `frames` closes.
+pub(crate) fn spawn_chunk_worker(
+    transcriber: Arc<Mutex<Transcriber>>,
+    config: AppConfig,
+    frames: Receiver<StreamFrame>,
+) -> thread::JoinHandle<Result<StreamOutcome>> {
+    thread::spawn(move || {
+        let pipeline = config.voice_pipeline_config();
+        let mut scheduler = ChunkScheduler::new(
+            config.whisper_stream_chunk_ms,
+            config.whisper_stream_overlap_ms,
+            pipeline.sample_rate,
+        );
+        let mut stitched = StitchedTranscript::default();
+        let (mut chunks, mut skipped) = (0, 0);
+        let mut transcribe = |window: ChunkWindow| -> Result<()> {
+            if !window.has_speech {
+                skipped += 1;
+                return Ok(());
+            }
+            chunks += 1;
+            // Frames arrive at the pipeline rate; Whisper only takes 16 kHz.
+            let samples = resample(
+                &window.samples,
+                pipeline.sample_rate,
+                TARGET_RATE,
+                pipeline.resample_quality,
+            );
+            let transcription = transcriber
+                .lock()
+                .map_err(|_| anyhow!("transcriber lock poisoned"))?
+                .transcribe_detailed(&samples, &config)?;
+            stitched.append(&transcription);
+            Ok(())
+        };
+        for frame in frames.iter() {
+            scheduler.push(&frame);
+            while let Some(window) = scheduler.next_window() {
+                transcribe(window)?;
+            }
+        }
+        if let Some(window) = scheduler.finish() {
+            transcribe(window)?;
+        }
+        Ok(StreamOutcome {
+            transcription: stitched.into_transcription(),
+            chunks,
+            skipped,
+        })
+    })
+}
+
+/// Words of a chunk transcript, falling back to its text when Whisper gave no tokens.
+fn chunk_words(chunk: &Transcription) -> Vec<WordConfidence> {
+    if !chunk.words.is_empty() {
+        return chunk.words.clone();
+    }
+    let confidence = chunk.confidence.unwrap_or(1.0);
+    chunk
+        .text
+        .split_whitespace()
+        .filter(|word| *word != "[BLANK_AUDIO]")
+        .map(|word| WordConfidence {
+            word: word.to_string(),
+            confidence,
+        })
+        .collect()
+}
+
+/// How to join `incoming` onto `committed`: trailing committed words to drop and
+/// leading incoming words to skip.
+///
+/// Looks for the longest run of words at the end of `committed` that also
+/// starts `incoming`. Either side may carry one extra word cut in half at the
+/// window edge, so the match may also skip that word. No match means the
+/// chunks are simply appended.
+fn overlap_split(committed: &[WordConfidence], incoming: &[WordConfidence]) -> (usize, usize) {
+    let committed: Vec<String> = committed.iter().map(|word| normalize(&word.word)).collect();
+    let incoming: Vec<String> = incoming.iter().map(|word| normalize(&word.word)).collect();
+    let longest = MAX_OVERLAP_WORDS.min(committed.len()).min(incoming.len());
+    for len in (1..=longest).rev() {
+        for drop_tail in 0..=1 {
+            for skip_head in 0..=1 {
+                let Some(tail_start) = committed.len().checked_sub(len + drop_tail) else {
+                    continue;
+                };
+                if incoming.len() < skip_head + len {
+                    continue;
+                }
+                let tail = &committed[tail_start..tail_start + len];
+                let head = &incoming[skip_head..skip_head + len];
+                if tail != head || (len == 1 && tail[0].len() < MIN_SINGLE_WORD_OVERLAP_CHARS) {
+                    continue;
+                }
+                return (drop_tail, skip_head + len);
+            }
+        }
+    }
+    (0, 0)
+}
+
+fn normalize(word: &str) -> String {
+    word.chars()
+        .filter(|ch| ch.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(len: usize, speech: bool) -> StreamFrame {
+        StreamFrame {
+            samples: vec![0.1; len],
+            speech,
+        }
+    }
+
+    fn transcription(text: &str) -> Transcription {
+        Transcription {
+            text: text.to_string(),
+            confidence: Some(0.8),
+            words: text
+                .split_whitespace()
+                .map(|word| WordConfidence {
+                    word: word.to_string(),
+                    confidence: 0.8,
+                })
+                .collect(),
+            tokens: 0,
+        }
+    }
+
+    #[test]
+    fn scheduler_emits_overlapping_windows_and_a_final_tail() {
+        // At 1 kHz, 1000 ms chunks with 200 ms of overlap advance 800 samples at a time.
+        let mut scheduler = ChunkScheduler::new(1000, 200, 1000);
+        let mut windows = Vec::new();
+        for _ in 0..25 {
+            scheduler.push(&frame(100, true));
+            while let Some(window) = scheduler.next_window() {
+                windows.push((window.start, window.samples.len()));
+            }
+        }
+        assert_eq!(windows, vec![(0, 1000), (800, 1000)]);
+        let tail = scheduler.finish().expect("tail window");
+        assert_eq!((tail.start, tail.samples.len()), (1600, 900));
+        assert!(scheduler.finish().is_none());
+    }
+
+    #[test]
+    fn scheduler_skips_tail_already_covered_by_overlap() {
+        let mut scheduler = ChunkScheduler::new(1000, 200, 1000);
+        scheduler.push(&frame(1000, true));
+        assert!(scheduler.next_window().is_some());
+        assert!(scheduler.finish().is_none());
+
+        let mut short = ChunkScheduler::new(1000, 200, 1000);
+        short.push(&frame(300, true));
+        assert_eq!(short.finish().map(|w| w.samples.len()), Some(300));
+    }
+
+    #[test]
+    fn scheduler_marks_windows_without_speech() {
+        let mut scheduler = ChunkScheduler::new(1000, 200, 1000);
+        scheduler.push(&frame(1000, false));
+        scheduler.push(&frame(500, true));
+        assert!(!scheduler.next_window().unwrap().has_speech);
+        assert!(scheduler.finish().unwrap().has_speech);
+    }
+
+    #[test]
+    fn stitching_keeps_overlapping_words_once() {
+        let mut stitched = StitchedTranscript::default();
+        stitched.append(&transcription("please run the unit tests for"));
+        stitched.append(&transcription("tests for the parser module."));
+        let result = stitched.into_transcription();
+        assert_eq!(
+            result.text,
+            "please run the unit tests for the parser module."
+        );
+        assert_eq!(result.words.len(), 9);
+        assert!((result.confidence.unwrap() - 0.8).abs() < 1e-6);
+    }
+
+    #[test]
+    fn stitching_drops_words_cut_at_the_window_edge() {
+        // The first window ends mid-word ("modu"); the second starts mid-word ("nit").
+        let mut stitched = StitchedTranscript::default();
+        stitched.append(&transcription("refactor the parser modu"));
+        stitched.append(&transcription("nit parser module and rerun"));
+        assert_eq!(
+            stitched.into_transcription().text,
+            "refactor the parser module and rerun"
+        );
+    }
+
+    #[test]
+    fn stitching_ignores_case_punctuation_and_short_single_words() {
+        let (drop_tail, skip_head) = overlap_split(
+            &transcription("Open the Config.").words,
+            &transcription("config, then save").words,
+        );
+        assert_eq!((drop_tail, skip_head), (0, 1));
+        // A lone "the" is too weak to treat as overlap.
+        assert_eq!(
+            overlap_split(
+                &transcription("open the").words,
+                &transcription("the file").words
+            ),
+            (0, 0)
+        );
+    }
+
+    #[test]
+    fn stitching_falls_back_to_text_when_words_are_missing() {
+        let mut stitched = StitchedTranscript::default();
+        stitched.append(&Transcription {
+            text: " hello world".to_string(),
+            confidence: None,
+            words: Vec::new(),
+            tokens: 0,
+        });
+        let result = stitched.into_transcription();
+        assert_eq!(result.text, "hello world");
+        assert_eq!(result.confidence, None);
+    }
+}